@@ -0,0 +1,53 @@
+#!/usr/bin/env bash
+# Regenerate src/reference_profiles.json from crate sources in the cargo registry.
+#
+# Usage: scripts/reference-profiles.sh [REGISTRY_SRC_DIR] > src/reference_profiles.json
+#
+# Each crate is analyzed with the analyzer built from this tree
+# (`cargo coupling --json --no-git --no-cache <crate>/src`). Fetch the sources
+# first, e.g. by adding the crates below as dependencies of a scratch project
+# and running `cargo fetch`.
+set -euo pipefail
+
+REGISTRY="${1:-$(ls -d "${CARGO_HOME:-$HOME/.cargo}"/registry/src/*/ | head -n 1)}"
+CRATES=(
+  axum-0.8.9
+  cargo_metadata-0.23.1
+  clap_builder-4.6.7
+  hyper-1.12.0
+  rayon-1.12.0
+  regex-1.13.1
+  regex-syntax-0.8.11
+  serde_json-1.0.154
+  syn-2.0.119
+  tempfile-3.27.0
+  tokio-1.53.2
+  tower-0.5.3
+  walkdir-2.5.0
+)
+
+cd "$(dirname "$0")/.."
+cargo build --release --quiet
+BIN=target/release/cargo-coupling
+
+echo "["
+for i in "${!CRATES[@]}"; do
+  crate="${CRATES[$i]}"
+  name="${crate%-*}"
+  version="${crate##*-}"
+  separator=","
+  [ "$i" -eq $((${#CRATES[@]} - 1)) ] && separator=""
+  "$BIN" coupling --json --no-git --no-cache --quiet "$REGISTRY/$crate/src" |
+    jq -rj --arg name "$name" --arg version "$version" '
+      def share: . * 1000 | round
+        | "\(. / 1000 | floor).\("00\(. % 1000)" | .[-3:])";
+      .coupling_profile as $p
+      | "  { \"name\": \"\($name)\", \"version\": \"\($version)\", "
+        + "\"couplings\": \(.summary.total_couplings), \"profile\": { "
+        + ([ "intrusive", "functional", "model", "contract",
+             "same_module", "different_module", "external" ]
+           | map("\"\(.)\": \($p[.] | share)") | join(", "))
+        + " } }"'
+  echo "$separator"
+done
+echo "]"
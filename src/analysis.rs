@@ -0,0 +1,384 @@
+//! One-call analysis for library users.
+//!
+//! The CLI loads `.coupling.toml`, applies flag overrides, analyzes the roots,
+//! reads git history for volatility and then applies the configured volatility
+//! overrides. `Analysis` runs the same steps, so an embedding tool gets the
+//! numbers the CLI would print without repeating that orchestration:
+//!
+//! ```no_run
+//! use cargo_coupling::Analysis;
+//!
+//! let (metrics, report) = Analysis::builder()
+//!     .path("./src")
+//!     .git_months(6)
+//!     .skip_tests(true)
+//!     .build()
+//!     .run_with_report()?;
+//! println!("{} modules, grade {}", metrics.module_count(), report.health_grade);
+//! # Ok::<(), cargo_coupling::AnalyzerError>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::analyzer::{AnalyzerError, analyze_roots_with_config};
+use crate::balance::grade::ProjectBalanceReport;
+use crate::balance::project::analyze_project_balance_with_thresholds;
+use crate::balance::score::IssueThresholds;
+use crate::config::{CompiledConfig, load_compiled_config_for};
+use crate::logging::{LogLevel, log_enabled};
+use crate::metrics::project::ProjectMetrics;
+use crate::volatility::VolatilityAnalyzer;
+use crate::{log_debug, log_warn};
+
+/// A configured analysis run; create one with [`Analysis::builder`].
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    paths: Vec<PathBuf>,
+    config_path: Option<PathBuf>,
+    config: Option<CompiledConfig>,
+    git_months: Option<usize>,
+    git_max_commits: Option<usize>,
+    git_timeout: Option<Duration>,
+    git_scope: Option<PathBuf>,
+    thresholds: Option<IssueThresholds>,
+    skip_tests: Option<bool>,
+}
+
+/// How an [`Analysis::run_detailed`] call went, besides the metrics it produced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnalysisOutcome {
+    /// Time spent analyzing the source files.
+    pub analysis_time: Duration,
+    /// Time spent reading git history; zero without `git_months`.
+    pub git_time: Duration,
+    /// Whether git history was read for at least one root.
+    pub git_used: bool,
+}
+
+/// Settings for an [`Analysis`]; unset settings behave like the CLI defaults.
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisBuilder {
+    paths: Vec<PathBuf>,
+    config_path: Option<PathBuf>,
+    config: Option<CompiledConfig>,
+    git_months: Option<usize>,
+    git_max_commits: Option<usize>,
+    git_timeout: Option<Duration>,
+    git_scope: Option<PathBuf>,
+    thresholds: Option<IssueThresholds>,
+    skip_tests: Option<bool>,
+}
+
+impl Analysis {
+    /// Start configuring an analysis; every setting is optional.
+    pub fn builder() -> AnalysisBuilder {
+        AnalysisBuilder::default()
+    }
+
+    /// Analyze the roots and read git volatility, as the CLI does before rendering output.
+    pub fn run(&self) -> Result<ProjectMetrics, AnalyzerError> {
+        self.run_detailed().map(|(metrics, _)| metrics)
+    }
+
+    /// `run`, also returning how long each step took and whether git was read.
+    pub fn run_detailed(&self) -> Result<(ProjectMetrics, AnalysisOutcome), AnalyzerError> {
+        let (mut config, thresholds) = self.resolve_config()?;
+        self.analyze(&mut config, &thresholds)
+    }
+
+    /// `run`, then score the result with the configured thresholds.
+    pub fn run_with_report(&self) -> Result<(ProjectMetrics, ProjectBalanceReport), AnalyzerError> {
+        let (mut config, thresholds) = self.resolve_config()?;
+        let (metrics, _) = self.analyze(&mut config, &thresholds)?;
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        Ok((metrics, report))
+    }
+
+    /// The config to analyze with and the thresholds to score with.
+    ///
+    /// Explicit thresholds replace the config's; `skip_tests` applies to both.
+    fn resolve_config(&self) -> Result<(CompiledConfig, IssueThresholds), AnalyzerError> {
+        let mut config = match &self.config {
+            Some(config) => config.clone(),
+            None => load_compiled_config_for(self.config_path.as_deref(), &self.paths[0])?,
+        };
+        if let Some(skip) = self.skip_tests {
+            config.set_exclude_tests(skip);
+        }
+        if self.git_max_commits.is_some() {
+            config.git_max_commits = self.git_max_commits;
+        }
+        if self.git_timeout.is_some() {
+            config.git_timeout = self.git_timeout;
+        }
+        if self.git_scope.is_some() {
+            config.git_scope = self.git_scope.clone();
+        }
+        let mut thresholds = self
+            .thresholds
+            .clone()
+            .unwrap_or_else(|| config.issue_thresholds());
+        if let Some(skip) = self.skip_tests {
+            thresholds.exclude_tests = skip;
+        }
+        Ok((config, thresholds))
+    }
+
+    fn analyze(
+        &self,
+        config: &mut CompiledConfig,
+        thresholds: &IssueThresholds,
+    ) -> Result<(ProjectMetrics, AnalysisOutcome), AnalyzerError> {
+        let analysis_start = Instant::now();
+        let mut metrics = analyze_roots_with_config(&self.paths, config)?;
+        metrics.config_hash = Some(config.config_hash(thresholds));
+        let analysis_time = analysis_start.elapsed();
+
+        // A root outside a git repository leaves volatility at its static estimate
+        let git_start = Instant::now();
+        let mut git_used = false;
+        if let Some(months) = self.git_months {
+            log_debug!("Analyzing git history ({} months)...", months);
+
+            // Each root is read from its own repository; nested roots were already dropped
+            let roots = if metrics.analysis_roots.is_empty() {
+                self.paths.clone()
+            } else {
+                metrics.analysis_roots.clone()
+            };
+            for root in &roots {
+                let mut volatility = VolatilityAnalyzer::from_config(months, config)
+                    .with_progress(log_enabled(LogLevel::Debug));
+                // Change counts and co-change pairs (temporal coupling) are read concurrently
+                match volatility.analyze_with_temporal(root) {
+                    Ok(temporal) => {
+                        git_used = true;
+                        if volatility.timed_out {
+                            log_warn!(
+                                "Warning: git history analysis of {} stopped after {}s (--git-timeout); volatility uses the {} commits read",
+                                root.display(),
+                                config.git_timeout.unwrap_or_default().as_secs(),
+                                volatility.commits
+                            );
+                        }
+                        if log_enabled(LogLevel::Debug) {
+                            let stats = volatility.statistics();
+                            eprintln!(
+                                "Git analysis: {} commits, {} files, {} total changes",
+                                volatility.commits, stats.total_files, stats.total_changes
+                            );
+                            if config.git_max_commits == Some(volatility.commits) {
+                                eprintln!(
+                                    "  (history limited to the newest {} commits)",
+                                    volatility.commits
+                                );
+                            }
+                            if !temporal.is_empty() {
+                                eprintln!(
+                                    "Temporal coupling: {} co-changing file pairs detected",
+                                    temporal.len()
+                                );
+                            }
+                        }
+                        metrics.temporal_couplings.extend(temporal);
+
+                        // Copy file changes to project metrics (must be after statistics())
+                        metrics.file_changes.extend(volatility.file_changes);
+                    }
+                    Err(e) => {
+                        log_warn!("Warning: Git analysis failed: {}", e);
+                    }
+                }
+            }
+
+            // Update volatility for all couplings based on git history
+            if git_used {
+                metrics.update_volatility_from_git();
+            }
+        }
+        let git_time = git_start.elapsed();
+
+        // Apply volatility overrides from config (includes subdomain classification)
+        if config.has_subdomain_config() {
+            log_debug!("DDD subdomain classification configured (affects volatility)");
+        }
+        if config.has_volatility_overrides() || config.has_subdomain_config() {
+            let override_count = metrics.apply_config_volatility_overrides(config);
+            if override_count > 0 {
+                log_debug!(
+                    "Applied {} volatility overrides from config",
+                    override_count
+                );
+            }
+        }
+
+        let outcome = AnalysisOutcome {
+            analysis_time,
+            git_time,
+            git_used,
+        };
+        Ok((metrics, outcome))
+    }
+}
+
+impl AnalysisBuilder {
+    /// Add a root to analyze: a crate or workspace directory, or a `src` directory.
+    /// Several roots are analyzed as one project; without any, `./src` is used.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Load this config file, or the one in this directory, instead of
+    /// searching upward from the first root.
+    pub fn config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Use this config instead of loading `.coupling.toml`.
+    pub fn config(mut self, config: CompiledConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Read this many months of git history for volatility; without it git is not read.
+    pub fn git_months(mut self, months: usize) -> Self {
+        self.git_months = Some(months);
+        self
+    }
+
+    /// Read at most the newest `max_commits` commits, like `--git-max-commits`.
+    pub fn git_max_commits(mut self, max_commits: usize) -> Self {
+        self.git_max_commits = Some(max_commits);
+        self
+    }
+
+    /// Stop reading git history after `timeout` and keep what was read, like `--git-timeout`.
+    pub fn git_timeout(mut self, timeout: Duration) -> Self {
+        self.git_timeout = Some(timeout);
+        self
+    }
+
+    /// Only read git history under `scope`, relative to each root, like `--git-scope`.
+    pub fn git_scope(mut self, scope: impl Into<PathBuf>) -> Self {
+        self.git_scope = Some(scope.into());
+        self
+    }
+
+    /// Score with these thresholds instead of the config's `[thresholds]` and related sections.
+    pub fn thresholds(mut self, thresholds: IssueThresholds) -> Self {
+        self.thresholds = Some(thresholds);
+        self
+    }
+
+    /// Leave test code out, like `--exclude-tests`, or analyze it too with
+    /// `false`, like `--include-tests`; unset, `[analysis] exclude_tests` decides.
+    pub fn skip_tests(mut self, skip: bool) -> Self {
+        self.skip_tests = Some(skip);
+        self
+    }
+
+    /// Finish configuring; roots default to `./src` when none were added.
+    pub fn build(self) -> Analysis {
+        let paths = if self.paths.is_empty() {
+            vec![Path::new("./src").to_path_buf()]
+        } else {
+            self.paths
+        };
+        Analysis {
+            paths,
+            config_path: self.config_path,
+            config: self.config,
+            git_months: self.git_months,
+            git_max_commits: self.git_max_commits,
+            git_timeout: self.git_timeout,
+            git_scope: self.git_scope,
+            thresholds: self.thresholds,
+            skip_tests: self.skip_tests,
+        }
+    }
+
+    /// Shorthand for `build().run()`.
+    pub fn run(self) -> Result<ProjectMetrics, AnalyzerError> {
+        self.build().run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance::issue_type::IssueType;
+
+    #[test]
+    fn test_builder_applies_config_thresholds_and_test_exclusion() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "pub mod domain;\npub mod infrastructure;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("domain.rs"),
+            "use crate::infrastructure::Db;\n\npub struct Order;\n\npub fn save(order: Order, db: Db) {}\n\n\
+             #[cfg(test)]\nmod tests {\n    #[test]\n    fn saves() {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("infrastructure.rs"), "pub struct Db;\n").unwrap();
+        std::fs::write(
+            tmp.path().join(".coupling.toml"),
+            "[[layers]]\nname = \"domain\"\nmodules = [\"domain\"]\n\n\
+             [[layers]]\nname = \"infrastructure\"\nmodules = [\"infrastructure\"]\n",
+        )
+        .unwrap();
+
+        let has_test_function = |metrics: &ProjectMetrics| {
+            metrics
+                .modules
+                .values()
+                .any(|module| module.function_definitions.contains_key("saves"))
+        };
+
+        let (metrics, report) = Analysis::builder()
+            .path(&src)
+            .build()
+            .run_with_report()
+            .unwrap();
+        assert!(
+            !has_test_function(&metrics),
+            "test code is excluded by default"
+        );
+        assert!(metrics.config_hash.is_some());
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|issue| issue.issue_type == IssueType::LayerViolation),
+            "[[layers]] from .coupling.toml are scored"
+        );
+
+        let metrics = Analysis::builder()
+            .path(&src)
+            .skip_tests(false)
+            .run()
+            .unwrap();
+        assert!(has_test_function(&metrics));
+
+        // Explicit thresholds replace the config's
+        let (_, report) = Analysis::builder()
+            .path(&src)
+            .thresholds(IssueThresholds::default())
+            .build()
+            .run_with_report()
+            .unwrap();
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.issue_type == IssueType::LayerViolation)
+        );
+    }
+}
@@ -4,29 +4,44 @@
 //! giving the balance layer structural evidence about imports, type usage,
 //! calls, visibility, and item-level dependencies.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
 
+use quote::ToTokens;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
 use syn::visit::Visit;
 use syn::{
-    Expr, ExprCall, ExprField, ExprMethodCall, ExprStruct, File, FnArg, ItemFn, ItemImpl, ItemMod,
-    ItemStruct, ItemTrait, ItemUse, ReturnType, Signature, Type, UseTree,
+    Expr, ExprAwait, ExprCall, ExprField, ExprMethodCall, ExprPath, ExprStruct, ExprUnsafe, File,
+    FnArg, GenericArgument, ImplItem, ImplItemConst, ImplItemFn, Item, ItemFn, ItemImpl, ItemMod,
+    ItemStruct, ItemTrait, ItemUse, Local, Member, Pat, PathArguments, ReturnType, Signature,
+    TraitItemFn, Type, UseTree,
 };
 use thiserror::Error;
 
-use crate::config::CompiledConfig;
+use crate::cache::AnalysisCache;
+use crate::config::{CompiledConfig, ConfigError};
 use crate::discovery::{
     DiscoveredWorkspaceFile, canonical_file_key, discover_module_tree, file_path_to_module_path,
-    normalize_exclude_path, rs_files, rs_files_excluding_nested_packages,
+    is_generated_file, normalize_exclude_path, path_attribute_value, rs_files,
+    rs_files_excluding_nested_packages,
 };
 use crate::metrics::coupling::CouplingMetrics;
-use crate::metrics::dimensions::{IntegrationStrength, Visibility};
-use crate::metrics::module::ModuleMetrics;
+use crate::metrics::dimensions::{Connascence, Distance, IntegrationStrength, Visibility};
+use crate::metrics::module::{
+    DerefImpl, FileSubmodule, IssueSuppression, ModuleMetrics, PassThroughMethod,
+};
 use crate::metrics::project::ProjectMetrics;
+use crate::timing::AnalysisTimings;
 use crate::volatility::Volatility;
-use crate::workspace::{WorkspaceError, WorkspaceInfo, resolve_crate_from_path};
+use crate::workspace::{
+    WorkspaceError, WorkspaceInfo, edition_parse_hint, resolve_crate_from_path,
+};
+use crate::{log_info, log_warn};
 
 // ===== Syntax Helpers =====
 
@@ -55,23 +70,375 @@ fn convert_visibility(vis: &syn::Visibility) -> Visibility {
     }
 }
 
-/// Check if an item has the #[test] attribute
+/// Check if an item has a test attribute: `#[test]` or a runtime's `#[tokio::test]`
 fn has_test_attribute(attrs: &[syn::Attribute]) -> bool {
-    attrs.iter().any(|attr| attr.path().is_ident("test"))
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "test")
+    })
 }
 
-/// Check if an item has #[cfg(test)] attribute
+/// Check if an item is only compiled for tests: `#[cfg(test)]` or `#[cfg(all(test, ..))]`
 fn has_cfg_test_attribute(attrs: &[syn::Attribute]) -> bool {
     attrs.iter().any(|attr| {
-        if attr.path().is_ident("cfg") {
-            // Try to parse the attribute content
-            if let Ok(meta) = attr.meta.require_list() {
-                let tokens = meta.tokens.to_string();
-                return tokens.contains("test");
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Meta>()
+                .is_ok_and(|predicate| cfg_requires_test(&predicate))
+    })
+}
+
+/// Whether a `cfg` predicate can only hold when `test` is set.
+///
+/// `not(test)`, `any(test, ..)` and `feature = "test-utils"` also hold in
+/// production builds, so they do not count.
+fn cfg_requires_test(predicate: &syn::Meta) -> bool {
+    let nested = |list: &syn::MetaList| {
+        list.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+        )
+        .map(|predicates| predicates.into_iter().collect::<Vec<_>>())
+        .unwrap_or_default()
+    };
+    match predicate {
+        syn::Meta::Path(path) => path.is_ident("test"),
+        syn::Meta::List(list) if list.path.is_ident("all") => {
+            nested(list).iter().any(cfg_requires_test)
+        }
+        syn::Meta::List(list) if list.path.is_ident("any") => {
+            let alternatives = nested(list);
+            !alternatives.is_empty() && alternatives.iter().all(cfg_requires_test)
+        }
+        _ => false,
+    }
+}
+
+/// `cfg` predicates of an item's attributes that mention a Cargo feature.
+///
+/// Predicates are kept as written (`feature = "x"`, `all(feature = "x", unix)`),
+/// with token spacing normalized.
+fn feature_cfg_predicates(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .map(|meta| {
+            meta.tokens
+                .to_string()
+                .replace(" (", "(")
+                .replace("( ", "(")
+                .replace(" )", ")")
+                .replace(" ,", ",")
+        })
+        .filter(|predicate| predicate.contains("feature ="))
+        .collect()
+}
+
+/// Gate couplings in files loaded by a feature-gated `mod name;` declaration.
+///
+/// Runs after `ProjectMetrics::link_module_tree`; the declaration's predicates
+/// come before any gate recorded inside the file itself.
+fn inherit_module_feature_gates(project: &mut ProjectMetrics) {
+    for coupling in &mut project.couplings {
+        let Some(module) = project.modules.get(&coupling.source) else {
+            continue;
+        };
+        if module.feature_gates.is_empty() {
+            continue;
+        }
+        let mut predicates = module.feature_gates.clone();
+        if let Some(label) = &coupling.feature {
+            predicates.push(feature_gate_predicate(label));
+        }
+        coupling.feature = feature_gate_label(&predicates);
+    }
+}
+
+/// Inverse of `feature_gate_label` for a single gate: a bare feature name
+/// becomes `feature = "name"`, a raw predicate is returned as is.
+fn feature_gate_predicate(label: &str) -> String {
+    if label.contains(['(', '=']) {
+        label.to_string()
+    } else {
+        format!("feature = \"{}\"", label)
+    }
+}
+
+/// Label for couplings under the enclosing feature `cfg` predicates, outermost
+/// first: the feature name for a single `feature = "x"`, otherwise the raw
+/// predicate (nested gates are combined with `all(..)`).
+fn feature_gate_label(predicates: &[String]) -> Option<String> {
+    match predicates {
+        [] => None,
+        [predicate] => Some(
+            predicate
+                .strip_prefix("feature = \"")
+                .and_then(|rest| rest.strip_suffix('"'))
+                .filter(|name| !name.contains('"'))
+                .unwrap_or(predicate)
+                .to_string(),
+        ),
+        _ => Some(format!("all({})", predicates.join(", "))),
+    }
+}
+
+/// Attributes of an item, for the kinds that carry any
+fn item_attrs(item: &Item) -> &[syn::Attribute] {
+    match item {
+        Item::Const(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Render a path as `a::b::C`, dropping generic arguments
+fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Short rendering of a method-call receiver (`self.config`, `Config::new()`, `items[..]`)
+fn receiver_expression(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(path_expr) => path_to_string(&path_expr.path),
+        Expr::Field(field) => {
+            let member = match &field.member {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(idx) => idx.index.to_string(),
+            };
+            format!("{}.{}", receiver_expression(&field.base), member)
+        }
+        Expr::MethodCall(call) => {
+            format!("{}.{}()", receiver_expression(&call.receiver), call.method)
+        }
+        Expr::Call(call) => format!("{}()", receiver_expression(&call.func)),
+        Expr::Index(index) => format!("{}[..]", receiver_expression(&index.expr)),
+        Expr::Paren(paren) => receiver_expression(&paren.expr),
+        Expr::Reference(reference) => receiver_expression(&reference.expr),
+        _ => "..".to_string(),
+    }
+}
+
+/// Callee of a trivial delegation, if the function body is nothing but one.
+///
+/// A function is a pass-through when its body is a single call (optionally
+/// `return`ed or ended with `;`) whose arguments are exactly the function's
+/// own parameters, in order and by name. A method call counts when made on the
+/// first parameter (usually `self`) or one of its fields. Anything more (`?`,
+/// `.await`, `.into()`, extra arguments, a logging statement) makes the
+/// wrapper do real work, so it is not flagged; neither are functions without
+/// parameters. Returns the callee and, for method calls, the receiver.
+fn pass_through_delegate<'a>(
+    sig: &Signature,
+    block: &'a syn::Block,
+) -> Option<(String, Option<&'a Expr>)> {
+    let [syn::Stmt::Expr(expr, _)] = block.stmts.as_slice() else {
+        return None;
+    };
+    let expr = match expr {
+        Expr::Return(ret) => ret.expr.as_deref()?,
+        other => other,
+    };
+
+    let params: Vec<String> = sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Receiver(_) => Some("self".to_string()),
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(ident) if ident.subpat.is_none() => Some(ident.ident.to_string()),
+                _ => None,
+            },
+        })
+        .collect::<Option<_>>()?;
+    if params.is_empty() {
+        return None;
+    }
+    let arg_names = |args: &syn::punctuated::Punctuated<Expr, syn::Token![,]>| {
+        args.iter()
+            .map(|arg| match arg {
+                Expr::Path(path) if path.qself.is_none() => {
+                    path.path.get_ident().map(ToString::to_string)
+                }
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+
+    match expr {
+        Expr::Call(call) => {
+            (arg_names(&call.args)? == params).then(|| (receiver_expression(&call.func), None))
+        }
+        Expr::MethodCall(call) => {
+            let mut root = &*call.receiver;
+            while let Expr::Field(field) = root {
+                root = &field.base;
             }
+            let Expr::Path(root) = root else {
+                return None;
+            };
+            (root.path.get_ident()? == &params[0] && arg_names(&call.args)? == params[1..]).then(
+                || {
+                    let callee = format!("{}.{}", receiver_expression(&call.receiver), call.method);
+                    (callee, Some(&*call.receiver))
+                },
+            )
         }
-        false
-    })
+        _ => None,
+    }
+}
+
+/// 1-based source line of a span (requires proc-macro2's `span-locations`).
+///
+/// Spans without location info (e.g. inside a compiler-driven proc macro) report 0.
+fn span_line(span: proc_macro2::Span) -> usize {
+    span.start().line
+}
+
+/// 1-based column, in characters, where `span` starts (proc_macro2 counts from 0)
+fn span_column(span: proc_macro2::Span) -> usize {
+    span.start().column + 1
+}
+
+/// Span of the type path `ty` names, so `&Config` and `[Config]` are located
+/// at `Config` rather than at the reference or bracket
+fn named_type_span(ty: &Type) -> proc_macro2::Span {
+    match ty {
+        Type::Path(type_path) => type_path.path.span(),
+        Type::Reference(ref_type) => named_type_span(&ref_type.elem),
+        Type::Slice(slice_type) => named_type_span(&slice_type.elem),
+        Type::Array(array_type) => named_type_span(&array_type.elem),
+        Type::Ptr(ptr_type) => named_type_span(&ptr_type.elem),
+        Type::Paren(paren_type) => named_type_span(&paren_type.elem),
+        Type::Group(group_type) => named_type_span(&group_type.elem),
+        _ => ty.span(),
+    }
+}
+
+/// Tokens rendered the way rustfmt would write simple signatures (`&mut self`,
+/// `Vec<String>`, `key: &str`), not with a space between every token.
+fn render_tokens(tokens: &impl ToTokens) -> String {
+    let is_word_end = |token: &str| token.ends_with(|c: char| c.is_alphanumeric() || c == '_');
+    let is_word_start =
+        |token: &str| token.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '\'');
+    let spaced = |token: &str| matches!(token, "->" | "=" | "+");
+    // `&'a [u8]`, `&'a mut T`, but `Cow<'a, str>` and `Cow<'a>`
+    let after_lifetime = |prev: &str, token: &str| {
+        prev.starts_with('\'') && !token.starts_with([',', '>', ')', ':'])
+    };
+
+    let mut rendered = String::new();
+    let mut prev: Option<&str> = None;
+    let tokens = tokens.to_token_stream().to_string();
+    for token in tokens.split_whitespace() {
+        if let Some(prev) = prev {
+            let space = prev.ends_with(',')
+                || matches!(prev, ":" | ";")
+                || spaced(prev)
+                || spaced(token)
+                || after_lifetime(prev, token)
+                || (is_word_end(prev) && is_word_start(token));
+            if space {
+                rendered.push(' ');
+            }
+        }
+        rendered.push_str(token);
+        prev = Some(token);
+    }
+    rendered
+}
+
+/// Generic type and const parameters of an item; lifetimes carry no type coupling
+fn generic_param_count(generics: &syn::Generics) -> usize {
+    generics.type_params().count() + generics.const_params().count()
+}
+
+/// Trait named by a qualified-self path: `Trait` in `<T as Trait>::Assoc`.
+///
+/// `<T>::Assoc` (no `as`) names no trait and yields `None`.
+fn qualified_self_trait(type_path: &syn::TypePath) -> Option<String> {
+    let qself = type_path.qself.as_ref()?;
+    if qself.position == 0 {
+        return None;
+    }
+
+    Some(
+        type_path
+            .path
+            .segments
+            .iter()
+            .take(qself.position)
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+    )
+}
+
+/// Paths (`A`, `a::B::new`, `crate::a::B`) in a macro body, with the span of their first ident.
+///
+/// Macro metavariables (`$name`) and idents inside string literals are skipped.
+fn macro_paths(tokens: proc_macro2::TokenStream) -> Vec<(Vec<String>, proc_macro2::Span)> {
+    use proc_macro2::{Spacing, TokenTree};
+
+    let mut paths = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut start = proc_macro2::Span::call_site();
+    let mut after_separator = false;
+    let mut after_dollar = false;
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(_) if after_dollar => after_dollar = false,
+            TokenTree::Ident(ident) => {
+                if !after_separator && !current.is_empty() {
+                    paths.push((std::mem::take(&mut current), start));
+                }
+                if current.is_empty() {
+                    start = ident.span();
+                }
+                current.push(ident.to_string());
+                after_separator = false;
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == ':'
+                    && punct.spacing() == Spacing::Joint
+                    && matches!(tokens.peek(), Some(TokenTree::Punct(next)) if next.as_char() == ':') =>
+            {
+                tokens.next();
+                after_separator = !current.is_empty();
+            }
+            other => {
+                if !current.is_empty() {
+                    paths.push((std::mem::take(&mut current), start));
+                }
+                after_separator = false;
+                after_dollar = matches!(&other, TokenTree::Punct(punct) if punct.as_char() == '$');
+                if let TokenTree::Group(group) = other {
+                    paths.extend(macro_paths(group.stream()));
+                }
+            }
+        }
+    }
+    if !current.is_empty() {
+        paths.push((current, start));
+    }
+    paths
 }
 
 /// Check if a module is a test module (named "tests" or has #[cfg(test)])
@@ -95,10 +462,13 @@ pub enum AnalyzerError {
 
     #[error("Workspace error: {0}")]
     WorkspaceError(#[from] WorkspaceError),
+
+    #[error(transparent)]
+    ConfigError(#[from] ConfigError),
 }
 
 /// Represents a detected dependency
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
     /// Full path of the dependency (e.g., "crate::models::user")
     pub path: String,
@@ -106,12 +476,19 @@ pub struct Dependency {
     pub kind: DependencyKind,
     /// Line number where the dependency is declared
     pub line: usize,
+    /// 1-based column of the dependency's first token (0 if unknown)
+    pub column: usize,
     /// Usage context for more accurate strength determination
     pub usage: UsageContext,
+    /// Found by scanning a macro body's tokens rather than parsed syntax
+    pub from_macro: bool,
+    /// Feature gating the dependency (`#[cfg(feature = "x")]` on an enclosing
+    /// item): the feature name, or the raw `cfg` predicate when it is compound
+    pub feature: Option<String>,
 }
 
 /// Kind of dependency
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DependencyKind {
     /// use crate::xxx or use super::xxx
     InternalUse,
@@ -126,7 +503,7 @@ pub enum DependencyKind {
 }
 
 /// Context of how a dependency is used - determines Integration Strength
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UsageContext {
     /// Just imported, usage unknown
     Import,
@@ -173,6 +550,35 @@ impl UsageContext {
             UsageContext::TraitBound => IntegrationStrength::Contract,
         }
     }
+
+    /// Static connascence form this usage creates (Page-Jones's taxonomy)
+    ///
+    /// When a usage shows several forms it is classified by the strongest one,
+    /// as Page-Jones rates a coupling by the strongest connascence it carries.
+    pub fn connascence(&self) -> Connascence {
+        match self {
+            // Name: only the item's name has to stay stable. Calls are recorded
+            // without their arguments, so argument order cannot be seen here.
+            UsageContext::Import => Connascence::Name,
+            UsageContext::MethodCall => Connascence::Name,
+            UsageContext::FunctionCall => Connascence::Name,
+
+            // Type: both sides agree on which type fills a slot
+            UsageContext::TraitBound => Connascence::Type,
+            UsageContext::TypeParameter => Connascence::Type,
+            UsageContext::ReturnType => Connascence::Type,
+
+            // Meaning: reading fields (Name) relies on what their values mean,
+            // as does an impl block written against the type's representation
+            UsageContext::FieldAccess => Connascence::Meaning,
+            UsageContext::InherentImplBlock => Connascence::Meaning,
+
+            // Position: literals and parameter lists must supply every slot,
+            // matched by order for tuple structs and arguments
+            UsageContext::StructConstruction => Connascence::Position,
+            UsageContext::FunctionParameter => Connascence::Position,
+        }
+    }
 }
 
 impl DependencyKind {
@@ -188,6 +594,110 @@ impl DependencyKind {
     }
 }
 
+/// User adjustments to the type names treated as primitives rather than coupling targets.
+///
+/// Built-in primitives (`String`, `Option`, `Vec`, ...) are always skipped, and
+/// so are short all-lowercase names, which are usually local variables. Names
+/// are matched against the full path and its last segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimitiveTypes {
+    /// Extra names skipped like built-in primitives (`treat_as_primitive`)
+    extra: BTreeSet<String>,
+    /// Names always counted, even when built in or short (`count_types`)
+    counted: BTreeSet<String>,
+    /// Treat all-lowercase names of up to 3 characters as local variables
+    short_name_heuristic: bool,
+}
+
+impl Default for PrimitiveTypes {
+    fn default() -> Self {
+        Self {
+            extra: BTreeSet::new(),
+            counted: BTreeSet::new(),
+            short_name_heuristic: true,
+        }
+    }
+}
+
+impl PrimitiveTypes {
+    /// Primitive settings from `[analysis]` config values.
+    pub fn new(
+        treat_as_primitive: &[String],
+        count_types: &[String],
+        short_name_heuristic: bool,
+    ) -> Self {
+        Self {
+            extra: treat_as_primitive.iter().cloned().collect(),
+            counted: count_types.iter().cloned().collect(),
+            short_name_heuristic,
+        }
+    }
+
+    fn matches(set: &BTreeSet<String>, type_name: &str) -> bool {
+        let last = type_name.rsplit("::").next().unwrap_or(type_name);
+        set.contains(type_name) || set.contains(last)
+    }
+
+    /// Whether `type_name` names a primitive, wrapper or local variable.
+    pub fn is_primitive(&self, type_name: &str) -> bool {
+        if Self::matches(&self.counted, type_name) {
+            return false;
+        }
+        if Self::matches(&self.extra, type_name) {
+            return true;
+        }
+
+        // Primitive types
+        if matches!(
+            type_name,
+            "bool"
+                | "char"
+                | "str"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+                | "f32"
+                | "f64"
+                | "String"
+                | "Self"
+                | "()"
+                | "Option"
+                | "Result"
+                | "Vec"
+                | "Box"
+                | "Rc"
+                | "Arc"
+                | "RefCell"
+                | "Cell"
+                | "Mutex"
+                | "RwLock"
+        ) {
+            return true;
+        }
+
+        // Short variable names (likely local variables, not types)
+        // Type names in Rust are typically PascalCase and longer
+        if self.short_name_heuristic
+            && type_name.len() <= 3
+            && type_name.chars().all(|c| c.is_lowercase())
+        {
+            return true;
+        }
+
+        // Self-references or obviously local
+        type_name.starts_with("self")
+    }
+}
+
 /// AST visitor for coupling analysis
 #[derive(Debug)]
 pub struct CouplingAnalyzer {
@@ -217,6 +727,34 @@ pub struct CouplingAnalyzer {
     current_item: Option<(String, ItemKind)>,
     /// Item-level dependencies (detailed tracking)
     pub item_dependencies: Vec<ItemDependency>,
+    /// Typed bindings in the current function (binding name -> type name)
+    local_types: HashMap<String, String>,
+    /// Element types of indexable bindings in the current function (`items: Vec<Item>` -> `Item`)
+    local_element_types: HashMap<String, String>,
+    /// Self type of the `impl` block being visited
+    current_impl_type: Option<String>,
+    /// Field types of structs defined in this file (struct -> field -> (type, element type))
+    field_types: HashMap<String, HashMap<String, (String, Option<String>)>>,
+    /// Return types of methods defined in this file (`Type::method` -> type, `Self` resolved)
+    method_return_types: HashMap<String, String>,
+    /// Whether the current impl block implements a trait
+    in_trait_impl: bool,
+    /// Types with a `DerefMut` impl seen before their `Deref` impl
+    deref_mut_types: HashSet<String>,
+    /// Names of the enclosing inline `mod name { .. }` blocks, outermost first
+    inline_modules: Vec<String>,
+    /// Nesting depth of test-only modules (`#[cfg(test)]`, `mod tests`)
+    test_module_depth: usize,
+    /// Nesting depth of `#[test]` functions
+    test_function_depth: usize,
+    /// Skip `#[cfg(test)]` modules and test functions entirely
+    exclude_test_code: bool,
+    /// Type names skipped as primitives
+    primitive_types: PrimitiveTypes,
+    /// Distinct paths named inside macro bodies
+    macro_dependencies: HashSet<String>,
+    /// Feature `cfg` predicates of the enclosing items, outermost first
+    feature_gates: Vec<String>,
 }
 
 /// Statistics about usage patterns
@@ -237,7 +775,7 @@ pub struct UsageCounts {
 }
 
 /// Detailed dependency at the item level (function, struct, etc.)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ItemDependency {
     /// Source item (e.g., "fn analyze_project")
     pub source_item: String,
@@ -256,10 +794,12 @@ pub struct ItemDependency {
 }
 
 /// Kind of source item
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ItemKind {
     Function,
     Method,
+    /// Associated constant (`Type::NAME`)
+    Const,
     Struct,
     Enum,
     Trait,
@@ -268,7 +808,7 @@ pub enum ItemKind {
 }
 
 /// Type of item-level dependency
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ItemDepType {
     /// Calls a function: foo()
     FunctionCall,
@@ -286,6 +826,8 @@ pub enum ItemDepType {
     TraitBound,
     /// Imports: use foo::Bar
     Import,
+    /// Reads an associated constant: Type::MAX
+    ConstUsage,
 }
 
 // ===== AST Visitor =====
@@ -307,23 +849,66 @@ impl CouplingAnalyzer {
             type_visibility: HashMap::new(),
             current_item: None,
             item_dependencies: Vec::new(),
+            local_types: HashMap::new(),
+            local_element_types: HashMap::new(),
+            current_impl_type: None,
+            field_types: HashMap::new(),
+            method_return_types: HashMap::new(),
+            in_trait_impl: false,
+            deref_mut_types: HashSet::new(),
+            inline_modules: Vec::new(),
+            test_module_depth: 0,
+            test_function_depth: 0,
+            exclude_test_code: false,
+            primitive_types: PrimitiveTypes::default(),
+            macro_dependencies: HashSet::new(),
+            feature_gates: Vec::new(),
         }
     }
 
+    /// Skip `#[cfg(test)]` modules and `#[test]`/`#[tokio::test]` functions, so
+    /// they add no definitions or dependencies to the module metrics.
+    ///
+    /// Helpers in non-test modules still count even if only tests call them.
+    pub fn with_exclude_test_code(mut self, exclude: bool) -> Self {
+        self.exclude_test_code = exclude;
+        self
+    }
+
+    /// Use `primitive_types` to decide which type names are not coupling targets.
+    pub fn with_primitive_types(mut self, primitive_types: PrimitiveTypes) -> Self {
+        self.primitive_types = primitive_types;
+        self
+    }
+
     /// Analyze a Rust source file
     pub fn analyze_file(&mut self, content: &str) -> Result<(), AnalyzerError> {
         let syntax: File =
             syn::parse_file(content).map_err(|e| AnalyzerError::ParseError(e.to_string()))?;
 
+        self.collect_type_shapes(&syntax.items);
         self.visit_file(&syntax);
+        self.metrics.allowed_rules = allow_markers(content);
+        self.metrics.suppressions = suppression_markers(content);
 
         Ok(())
     }
 
     /// Add a dependency with deduplication
-    fn add_dependency(&mut self, path: String, kind: DependencyKind, usage: UsageContext) {
+    fn add_dependency(
+        &mut self,
+        path: String,
+        kind: DependencyKind,
+        usage: UsageContext,
+        span: proc_macro2::Span,
+    ) {
+        let feature = feature_gate_label(&self.feature_gates);
         let key = (path.clone(), usage);
         if self.seen_dependencies.contains(&key) {
+            // A dependency used outside any feature gate is unconditional
+            if feature.is_none() {
+                self.clear_feature_gate(&path, usage);
+            }
             return;
         }
         self.seen_dependencies.insert(key);
@@ -331,11 +916,126 @@ impl CouplingAnalyzer {
         self.dependencies.push(Dependency {
             path,
             kind,
-            line: 0,
+            line: span_line(span),
+            column: span_column(span),
             usage,
+            from_macro: false,
+            feature,
         });
     }
 
+    /// Mark the recorded `(path, usage)` dependency as not feature-gated
+    fn clear_feature_gate(&mut self, path: &str, usage: UsageContext) {
+        if let Some(dep) = self
+            .dependencies
+            .iter_mut()
+            .find(|dep| dep.path == path && dep.usage == usage)
+        {
+            dep.feature = None;
+        }
+    }
+
+    /// Re-anchor a `super::` path written inside inline modules to this file's module.
+    ///
+    /// Target resolution reads paths as if written at file level, so each `super::`
+    /// that only leaves an enclosing inline block is dropped; a path that stays
+    /// inside this file becomes a `self::` path.
+    fn anchor_inline_super(&self, path: &str) -> String {
+        let depth = self.inline_modules.len();
+        if depth == 0 || !path.starts_with("super::") {
+            return path.to_string();
+        }
+
+        let mut rest = path;
+        let mut levels = 0;
+        while let Some(next) = rest.strip_prefix("super::") {
+            levels += 1;
+            rest = next;
+        }
+
+        if levels > depth {
+            format!("{}{}", "super::".repeat(levels - depth), rest)
+        } else {
+            let enclosing = &self.inline_modules[..depth - levels];
+            if enclosing.is_empty() {
+                format!("self::{}", rest)
+            } else {
+                format!("self::{}::{}", enclosing.join("::"), rest)
+            }
+        }
+    }
+
+    /// Record the paths a macro body names as Import-strength dependencies.
+    ///
+    /// Macro input is not parsed, so only paths that resolve through this file's
+    /// `use` declarations or start with `crate::`/`super::` are recorded.
+    fn add_macro_dependencies(&mut self, tokens: proc_macro2::TokenStream) {
+        for (segments, span) in macro_paths(tokens) {
+            let Some(path) = self.resolve_macro_path(&segments) else {
+                continue;
+            };
+            if !self.macro_dependencies.insert(path.clone()) {
+                continue;
+            }
+            self.metrics.macro_dependency_count += 1;
+
+            let feature = feature_gate_label(&self.feature_gates);
+            let key = (path.clone(), UsageContext::Import);
+            if !self.seen_dependencies.insert(key) {
+                if feature.is_none() {
+                    self.clear_feature_gate(&path, UsageContext::Import);
+                }
+                continue;
+            }
+            let kind = if path.starts_with("crate::") || path.starts_with("super::") {
+                DependencyKind::InternalUse
+            } else {
+                DependencyKind::TypeRef
+            };
+            self.dependencies.push(Dependency {
+                path,
+                kind,
+                line: span_line(span),
+                column: span_column(span),
+                usage: UsageContext::Import,
+                from_macro: true,
+                feature,
+            });
+        }
+    }
+
+    /// Full path for a path found in a macro body, up to its first type-like segment.
+    fn resolve_macro_path(&self, segments: &[String]) -> Option<String> {
+        let first = segments.first()?;
+        let mut resolved: Vec<String> = if first == "crate" || first == "super" {
+            if segments.len() < 2 {
+                return None;
+            }
+            vec![first.clone()]
+        } else {
+            let import = self.imported_types.get(first)?;
+            if self.is_primitive_type(first) || self.defined_types.contains(first) {
+                return None;
+            }
+            import.split("::").map(str::to_string).collect()
+        };
+        resolved.extend(segments[1..].iter().cloned());
+
+        // `Type::method` couples to `Type`; keep module paths whole.
+        let type_end = resolved
+            .iter()
+            .position(|segment| segment.chars().next().is_some_and(char::is_uppercase))
+            .map_or(resolved.len(), |index| index + 1);
+        resolved.truncate(type_end);
+        let path = resolved.join("::");
+        if first == "super" {
+            // A `super::` path that stays inside this file is not a dependency
+            let anchored = self.anchor_inline_super(&path);
+            return (!anchored.starts_with("self::")).then_some(anchored);
+        }
+        Some(path)
+    }
+
     /// Record an item-level dependency with detailed tracking
     fn add_item_dependency(
         &mut self,
@@ -347,7 +1047,10 @@ impl CouplingAnalyzer {
         if let Some((ref source_item, source_kind)) = self.current_item {
             // Determine target module
             let target_module = self.imported_types.get(&target).cloned().or_else(|| {
+                // `Type::item` of a type defined here stays in this module
+                let owner = target.split("::").next().unwrap_or(&target);
                 if self.defined_types.contains(&target)
+                    || self.defined_types.contains(owner)
                     || self.defined_functions.contains_key(&target)
                 {
                     Some(self.current_module.clone())
@@ -427,8 +1130,12 @@ impl CouplingAnalyzer {
     }
 
     /// Extract type name from a Type
+    ///
+    /// Qualified paths (`<T as Trait>::Assoc`) name an associated type, not a
+    /// nominal type, so they yield `None`; `visit_type_path` records the trait.
     fn extract_type_name(&self, ty: &Type) -> Option<String> {
         match ty {
+            Type::Path(type_path) if type_path.qself.is_some() => None,
             Type::Path(type_path) => {
                 let segments: Vec<_> = type_path
                     .path
@@ -436,7 +1143,7 @@ impl CouplingAnalyzer {
                     .iter()
                     .map(|s| s.ident.to_string())
                     .collect();
-                Some(segments.join("::"))
+                Some(self.anchor_inline_super(&segments.join("::")))
             }
             Type::Reference(ref_type) => self.extract_type_name(&ref_type.elem),
             Type::Slice(slice_type) => self.extract_type_name(&slice_type.elem),
@@ -448,88 +1155,502 @@ impl CouplingAnalyzer {
         }
     }
 
-    /// Analyze function signature for dependencies
-    fn analyze_signature(&mut self, sig: &Signature) {
-        // Analyze parameters
-        for arg in &sig.inputs {
-            if let FnArg::Typed(pat_type) = arg
-                && let Some(type_name) = self.extract_type_name(&pat_type.ty)
-                && !self.is_primitive_type(&type_name)
-            {
-                self.add_dependency(
-                    type_name,
-                    DependencyKind::TypeRef,
-                    UsageContext::FunctionParameter,
-                );
+    /// Element type reached by indexing a value of `ty` (`Vec<T>`, `[T]`, map values)
+    fn element_type_name(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let last = type_path.path.segments.last()?;
+                if !matches!(
+                    last.ident.to_string().as_str(),
+                    "Vec" | "VecDeque" | "HashMap" | "BTreeMap" | "IndexMap"
+                ) {
+                    return None;
+                }
+                let PathArguments::AngleBracketed(args) = &last.arguments else {
+                    return None;
+                };
+                // The last type argument is the element for sequences and the value for maps
+                args.args.iter().rev().find_map(|arg| match arg {
+                    GenericArgument::Type(ty) => self.extract_type_name(ty),
+                    _ => None,
+                })
             }
+            Type::Slice(slice_type) => self.extract_type_name(&slice_type.elem),
+            Type::Array(array_type) => self.extract_type_name(&array_type.elem),
+            Type::Reference(ref_type) => self.element_type_name(&ref_type.elem),
+            Type::Paren(paren_type) => self.element_type_name(&paren_type.elem),
+            Type::Group(group_type) => self.element_type_name(&group_type.elem),
+            _ => None,
         }
+    }
 
-        // Analyze return type
-        if let ReturnType::Type(_, ty) = &sig.output
-            && let Some(type_name) = self.extract_type_name(ty)
-            && !self.is_primitive_type(&type_name)
-        {
-            self.add_dependency(type_name, DependencyKind::TypeRef, UsageContext::ReturnType);
-        }
+    /// Non-primitive types named in the generic arguments of `ty` and their spans, outermost
+    /// first: `HashMap<UserId, Vec<Account>>` yields `UserId` and `Account`
+    fn type_argument_names(&self, ty: &Type) -> Vec<(String, proc_macro2::Span)> {
+        let mut names = Vec::new();
+        self.collect_type_argument_names(ty, &mut names);
+        names
     }
 
-    /// Check if a type should be ignored (primitives, self, or short variable names)
-    fn is_primitive_type(&self, type_name: &str) -> bool {
-        // Primitive types
-        if matches!(
+    fn collect_type_argument_names(&self, ty: &Type, names: &mut Vec<(String, proc_macro2::Span)>) {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                for segment in &type_path.path.segments {
+                    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                        continue;
+                    };
+                    for arg in &args.args {
+                        if let GenericArgument::Type(arg_ty) = arg {
+                            self.collect_argument_type(arg_ty, names);
+                        }
+                    }
+                }
+            }
+            Type::Reference(ref_type) => self.collect_type_argument_names(&ref_type.elem, names),
+            Type::Slice(slice_type) => self.collect_type_argument_names(&slice_type.elem, names),
+            Type::Array(array_type) => self.collect_type_argument_names(&array_type.elem, names),
+            Type::Ptr(ptr_type) => self.collect_type_argument_names(&ptr_type.elem, names),
+            Type::Paren(paren_type) => self.collect_type_argument_names(&paren_type.elem, names),
+            Type::Group(group_type) => self.collect_type_argument_names(&group_type.elem, names),
+            _ => {}
+        }
+    }
+
+    /// A type used as a generic argument, plus the arguments nested inside it
+    fn collect_argument_type(&self, ty: &Type, names: &mut Vec<(String, proc_macro2::Span)>) {
+        if let Type::Tuple(tuple) = ty {
+            for elem in &tuple.elems {
+                self.collect_argument_type(elem, names);
+            }
+            return;
+        }
+        if let Some(name) = self.extract_type_name(ty)
+            && !self.is_primitive_type(&name)
+            && !names.iter().any(|(known, _)| *known == name)
+        {
+            names.push((name, named_type_span(ty)));
+        }
+        self.collect_type_argument_names(ty, names);
+    }
+
+    /// Record the generic arguments of `ty` as type dependencies in `usage`
+    fn add_type_argument_dependencies(&mut self, ty: &Type, usage: UsageContext) {
+        for (type_name, span) in self.type_argument_names(ty) {
+            self.add_dependency(type_name, DependencyKind::TypeRef, usage, span);
+        }
+    }
+
+    /// Record struct field types and method return types defined anywhere in
+    /// the file, so receivers can be resolved before their definitions are visited
+    fn collect_type_shapes(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Struct(item_struct) => {
+                    let fields = item_struct
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(idx, field)| {
+                            let name = field
+                                .ident
+                                .as_ref()
+                                .map_or_else(|| idx.to_string(), ToString::to_string);
+                            let type_name = self.extract_type_name(&field.ty)?;
+                            Some((name, (type_name, self.element_type_name(&field.ty))))
+                        })
+                        .collect();
+                    self.field_types
+                        .insert(item_struct.ident.to_string(), fields);
+                }
+                Item::Impl(item_impl) => {
+                    let Some(self_type) = self.extract_type_name(&item_impl.self_ty) else {
+                        continue;
+                    };
+                    for impl_item in &item_impl.items {
+                        if let ImplItem::Fn(method) = impl_item
+                            && let ReturnType::Type(_, ty) = &method.sig.output
+                            && let Some(return_type) = self.extract_type_name(ty)
+                            && !return_type.starts_with("Self::")
+                        {
+                            let return_type = if return_type == "Self" {
+                                self_type.clone()
+                            } else {
+                                return_type
+                            };
+                            self.method_return_types.insert(
+                                format!("{}::{}", self_type, method.sig.ident),
+                                return_type,
+                            );
+                        }
+                    }
+                }
+                Item::Mod(item_mod) => {
+                    if let Some((_, items)) = &item_mod.content {
+                        self.collect_type_shapes(items);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Remember the type of a simple `name` binding for field-access resolution
+    fn bind_local_type(&mut self, pat: &Pat, type_name: &str) {
+        if let Pat::Ident(pat_ident) = pat {
+            self.local_types
+                .insert(pat_ident.ident.to_string(), type_name.to_string());
+        }
+    }
+
+    /// Remember what indexing a simple `name` binding of type `ty` yields
+    fn bind_local_element_type(&mut self, pat: &Pat, ty: &Type) {
+        if let Pat::Ident(pat_ident) = pat
+            && let Some(element) = self.element_type_name(ty)
+        {
+            self.local_element_types
+                .insert(pat_ident.ident.to_string(), element);
+        }
+    }
+
+    /// Infer the type of a method-call receiver as `(type, element type)`.
+    ///
+    /// Follows typed locals, `self`, fields of structs defined in this file,
+    /// indexing, and calls whose return type is known. `Type::ctor()` calls
+    /// without a known return type are assumed to return `Type`.
+    fn receiver_type(&self, expr: &Expr) -> Option<(String, Option<String>)> {
+        match expr {
+            Expr::Path(path_expr) => {
+                let name = path_to_string(&path_expr.path);
+                let type_name = self.local_types.get(&name)?.clone();
+                Some((type_name, self.local_element_types.get(&name).cloned()))
+            }
+            Expr::Field(field) => {
+                let (base_type, _) = self.receiver_type(&field.base)?;
+                let member = match &field.member {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(idx) => idx.index.to_string(),
+                };
+                self.field_types.get(&base_type)?.get(&member).cloned()
+            }
+            Expr::MethodCall(call) => {
+                let (base_type, _) = self.receiver_type(&call.receiver)?;
+                let return_type = self
+                    .method_return_types
+                    .get(&format!("{}::{}", base_type, call.method))?;
+                Some((return_type.clone(), None))
+            }
+            Expr::Call(call) => {
+                let Expr::Path(func) = &*call.func else {
+                    return None;
+                };
+                let path = path_to_string(&func.path);
+                if let Some(return_type) = self.method_return_types.get(&path) {
+                    return Some((return_type.clone(), None));
+                }
+                // Without a known signature, only `Type::new()`-style constructors
+                // name their return type; `module::function()` says nothing about it
+                let (type_name, _) = path.rsplit_once("::")?;
+                let last_segment = type_name.rsplit("::").next()?;
+                last_segment
+                    .starts_with(char::is_uppercase)
+                    .then(|| (type_name.to_string(), None))
+            }
+            Expr::Index(index) => {
+                let (type_name, element) = self.receiver_type(&index.expr)?;
+                Some((element.unwrap_or(type_name), None))
+            }
+            Expr::Paren(paren) => self.receiver_type(&paren.expr),
+            Expr::Reference(reference) => self.receiver_type(&reference.expr),
+            _ => None,
+        }
+    }
+
+    /// Count an `async fn`, or a public sync function, outside test code
+    fn record_asyncness(
+        &mut self,
+        sig: &Signature,
+        vis: &syn::Visibility,
+        attrs: &[syn::Attribute],
+    ) {
+        if self.test_module_depth > 0 || has_test_attribute(attrs) {
+            return;
+        }
+        if sig.asyncness.is_some() {
+            self.metrics.async_function_count += 1;
+        } else if matches!(vis, syn::Visibility::Public(_)) {
+            self.metrics.sync_public_function_count += 1;
+        }
+    }
+
+    /// Count an `unsafe fn`, outside test code
+    fn record_unsafety(&mut self, sig: &Signature, attrs: &[syn::Attribute]) {
+        if sig.unsafety.is_some() && self.test_module_depth == 0 && !has_test_attribute(attrs) {
+            self.metrics.unsafe_fn_count += 1;
+        }
+    }
+
+    /// Record `name` if it only delegates (see `pass_through_delegate`), outside test code.
+    ///
+    /// Forwarding to a standard container or primitive (`self.items.len()`) is
+    /// encapsulation rather than indirection and is not recorded.
+    fn record_pass_through(
+        &mut self,
+        name: String,
+        sig: &Signature,
+        block: &syn::Block,
+        attrs: &[syn::Attribute],
+    ) {
+        if self.test_module_depth > 0 || has_test_attribute(attrs) {
+            return;
+        }
+        let Some((delegate, receiver)) = pass_through_delegate(sig, block) else {
+            return;
+        };
+        let wraps_std_type = receiver
+            .and_then(|receiver| self.receiver_type(receiver))
+            .is_some_and(|(type_name, element)| {
+                element.is_some() || self.is_primitive_type(&type_name)
+            });
+        if !wraps_std_type {
+            self.metrics.pass_through_methods.push(PassThroughMethod {
+                name,
+                delegate,
+                line: span_line(sig.ident.span()),
+            });
+        }
+    }
+
+    /// Record a `Deref`/`DerefMut` impl that exposes a richer `Target` to callers
+    fn record_deref_impl(&mut self, node: &ItemImpl, trait_name: &str) {
+        if self.test_module_depth > 0 || has_cfg_test_attribute(&node.attrs) {
+            return;
+        }
+        let Some(type_name) = self.extract_type_name(&node.self_ty) else {
+            return;
+        };
+        if trait_name == "DerefMut" {
+            match self
+                .metrics
+                .deref_impls
+                .iter_mut()
+                .find(|deref| deref.type_name == type_name)
+            {
+                Some(deref) => deref.mutable = true,
+                None => {
+                    self.deref_mut_types.insert(type_name);
+                }
+            }
+            return;
+        }
+
+        let Some(target) = node.items.iter().find_map(|item| match item {
+            ImplItem::Type(assoc) if assoc.ident == "Target" => Some(&assoc.ty),
+            _ => None,
+        }) else {
+            return;
+        };
+        let type_params: HashSet<String> = node
+            .generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+        if self.is_plain_deref_target(target, &type_params) {
+            return;
+        }
+        self.metrics.deref_impls.push(DerefImpl {
+            mutable: self.deref_mut_types.contains(&type_name),
             type_name,
-            "bool"
-                | "char"
-                | "str"
-                | "u8"
-                | "u16"
-                | "u32"
-                | "u64"
-                | "u128"
-                | "usize"
-                | "i8"
-                | "i16"
-                | "i32"
-                | "i64"
-                | "i128"
-                | "isize"
-                | "f32"
-                | "f64"
-                | "String"
-                | "Self"
-                | "()"
-                | "Option"
-                | "Result"
-                | "Vec"
-                | "Box"
-                | "Rc"
-                | "Arc"
-                | "RefCell"
-                | "Cell"
-                | "Mutex"
-                | "RwLock"
-        ) {
-            return true;
+            target: render_tokens(target),
+            line: span_line(node.self_ty.span()),
+        });
+    }
+
+    /// Whether dereferencing to `ty` exposes no more than a value: a primitive, a
+    /// slice or array of one, or a type parameter of the impl (smart pointers, guards)
+    fn is_plain_deref_target(&self, ty: &Type, type_params: &HashSet<String>) -> bool {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let Some(last) = type_path.path.segments.last() else {
+                    return false;
+                };
+                let name = last.ident.to_string();
+                last.arguments.is_none()
+                    && (type_params.contains(&name) || self.is_primitive_type(&name))
+            }
+            Type::Slice(slice_type) => self.is_plain_deref_target(&slice_type.elem, type_params),
+            Type::Array(array_type) => self.is_plain_deref_target(&array_type.elem, type_params),
+            Type::Paren(paren_type) => self.is_plain_deref_target(&paren_type.elem, type_params),
+            Type::Group(group_type) => self.is_plain_deref_target(&group_type.elem, type_params),
+            _ => false,
         }
+    }
 
-        // Short variable names (likely local variables, not types)
-        // Type names in Rust are typically PascalCase and longer
-        if type_name.len() <= 3 && type_name.chars().all(|c| c.is_lowercase()) {
-            return true;
+    /// Record a method call on a receiver of type `type_name`
+    fn record_method_call(&mut self, type_name: &str, expression: String, node: &ExprMethodCall) {
+        let full_path = self
+            .imported_types
+            .get(type_name)
+            .cloned()
+            .unwrap_or_else(|| self.anchor_inline_super(type_name));
+
+        if !self.is_primitive_type(&full_path) && !self.defined_types.contains(&full_path) {
+            self.add_dependency(
+                full_path.clone(),
+                DependencyKind::TypeRef,
+                UsageContext::MethodCall,
+                node.method.span(),
+            );
+            self.usage_counts.method_calls += 1;
         }
 
-        // Self-references or obviously local
-        if type_name.starts_with("self") || type_name == "self" {
-            return true;
+        self.add_item_dependency(
+            format!("{}::{}", full_path, node.method),
+            ItemDepType::MethodCall,
+            span_line(node.method.span()),
+            Some(expression),
+        );
+    }
+
+    /// Register a function or method (`Type::method`) with its visibility and parameters
+    fn register_function(&mut self, name: String, sig: &Signature, vis: &syn::Visibility) {
+        let visibility = convert_visibility(vis);
+        self.defined_functions.insert(name.clone(), visibility);
+
+        // Analyze parameters for primitive obsession detection; `self` is not counted
+        let mut param_count = 0;
+        let mut primitive_param_count = 0;
+        let mut param_types = Vec::new();
+        let mut receiver = None;
+        let mut params = Vec::new();
+
+        for arg in &sig.inputs {
+            if let FnArg::Receiver(self_arg) = arg {
+                receiver = Some(render_tokens(self_arg));
+            }
+            if let FnArg::Typed(pat_type) = arg {
+                param_count += 1;
+                let param_name = match pat_type.pat.as_ref() {
+                    Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                    _ => "_".to_string(),
+                };
+                params.push((param_name, render_tokens(&pat_type.ty)));
+                if let Some(type_name) = self.extract_type_name(&pat_type.ty) {
+                    param_types.push(type_name.clone());
+                    if self.is_primitive_type(&type_name) {
+                        primitive_param_count += 1;
+                    }
+                }
+            }
+        }
+
+        // Register in module metrics with full details
+        self.metrics.add_function_definition_full(
+            name.clone(),
+            visibility,
+            param_count,
+            primitive_param_count,
+            param_types,
+            span_line(sig.ident.span()),
+        );
+        let return_type = match &sig.output {
+            ReturnType::Type(_, ty) => Some(render_tokens(ty)),
+            ReturnType::Default => None,
+        };
+        self.metrics
+            .set_function_signature(&name, receiver, params, return_type);
+        if sig.asyncness.is_some() {
+            self.metrics.mark_function_async(&name);
+        }
+    }
+
+    /// `Self::item` inside an impl block, rewritten to `Type::item`
+    fn resolve_self_path(&self, path: &str) -> Option<String> {
+        let rest = path.strip_prefix("Self::")?;
+        let self_type = self.current_impl_type.as_ref()?;
+        Some(format!("{}::{}", self_type, rest))
+    }
+
+    /// Analyze function signature for dependencies
+    fn analyze_signature(&mut self, sig: &Signature) {
+        // Analyze parameters
+        for arg in &sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                self.bind_local_element_type(&pat_type.pat, &pat_type.ty);
+            }
+            if let FnArg::Typed(pat_type) = arg
+                && let Some(type_name) = self.extract_type_name(&pat_type.ty)
+                && !self.is_primitive_type(&type_name)
+            {
+                self.bind_local_type(&pat_type.pat, &type_name);
+                self.add_dependency(
+                    type_name,
+                    DependencyKind::TypeRef,
+                    UsageContext::FunctionParameter,
+                    named_type_span(&pat_type.ty),
+                );
+            }
+            if let FnArg::Typed(pat_type) = arg {
+                self.add_type_argument_dependencies(&pat_type.ty, UsageContext::FunctionParameter);
+            }
+        }
+
+        // Analyze return type
+        if let ReturnType::Type(_, ty) = &sig.output
+            && let Some(type_name) = self.extract_type_name(ty)
+            && !self.is_primitive_type(&type_name)
+        {
+            self.add_dependency(
+                type_name,
+                DependencyKind::TypeRef,
+                UsageContext::ReturnType,
+                named_type_span(ty),
+            );
+        }
+        if let ReturnType::Type(_, ty) = &sig.output {
+            self.add_type_argument_dependencies(ty, UsageContext::ReturnType);
         }
+    }
 
-        false
+    /// Check if a type should be ignored (primitives, self, or short variable names)
+    fn is_primitive_type(&self, type_name: &str) -> bool {
+        self.primitive_types.is_primitive(type_name)
     }
 }
 
 impl<'ast> Visit<'ast> for CouplingAnalyzer {
+    fn visit_item(&mut self, node: &'ast Item) {
+        let depth = self.feature_gates.len();
+        self.feature_gates
+            .extend(feature_cfg_predicates(item_attrs(node)));
+        syn::visit::visit_item(self, node);
+        self.feature_gates.truncate(depth);
+    }
+
+    fn visit_impl_item(&mut self, node: &'ast ImplItem) {
+        let attrs: &[syn::Attribute] = match node {
+            ImplItem::Const(item) => &item.attrs,
+            ImplItem::Fn(item) => &item.attrs,
+            ImplItem::Type(item) => &item.attrs,
+            ImplItem::Macro(item) => &item.attrs,
+            _ => &[],
+        };
+        let depth = self.feature_gates.len();
+        self.feature_gates.extend(feature_cfg_predicates(attrs));
+        syn::visit::visit_impl_item(self, node);
+        self.feature_gates.truncate(depth);
+    }
+
     fn visit_item_use(&mut self, node: &'ast ItemUse) {
         let paths = self.extract_use_paths(&node.tree, "");
+        let test_only = self.test_module_depth > 0 || has_cfg_test_attribute(&node.attrs);
+
+        if !test_only && matches!(node.vis, syn::Visibility::Public(_)) {
+            for (path, _) in &paths {
+                if !self.metrics.reexports.contains(path) {
+                    self.metrics.reexports.push(path.clone());
+                }
+            }
+        }
 
         for (path, kind) in paths {
             // Skip self references
@@ -538,12 +1659,48 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
             }
 
             // Track imported types for later resolution
-            if let Some(type_name) = path.split("::").last() {
+            let anchored = self.anchor_inline_super(&path);
+            if let Some(type_name) = anchored.split("::").last() {
                 self.imported_types
-                    .insert(type_name.to_string(), path.clone());
+                    .insert(type_name.to_string(), anchored.clone());
             }
 
-            self.add_dependency(path.clone(), kind, UsageContext::Import);
+            // Test-only imports (often dev-dependencies) are tracked apart so they
+            // don't inflate the production dependency surface or create couplings.
+            if test_only {
+                let deps = match kind {
+                    DependencyKind::InternalUse => {
+                        Some((&mut self.metrics.test_internal_deps, path.clone()))
+                    }
+                    DependencyKind::ExternalUse => Some((
+                        &mut self.metrics.test_external_deps,
+                        path.split("::").next().unwrap_or(&path).to_string(),
+                    )),
+                    _ => None,
+                };
+                if let Some((deps, name)) = deps
+                    && !deps.contains(&name)
+                {
+                    deps.push(name);
+                }
+                continue;
+            }
+
+            // `use super::..` from an inline module into its own file is not a dependency
+            if anchored.starts_with("self::") {
+                continue;
+            }
+            let path = anchored;
+
+            self.add_dependency(path.clone(), kind, UsageContext::Import, node.tree.span());
+
+            // Cross-module glob imports hide the coupled items
+            if kind == DependencyKind::InternalUse
+                && path.ends_with("::*")
+                && !self.metrics.glob_imports.contains(&path)
+            {
+                self.metrics.glob_imports.push(path.clone());
+            }
 
             // Update metrics
             if kind == DependencyKind::InternalUse {
@@ -579,8 +1736,16 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                 trait_name,
                 DependencyKind::TraitImpl,
                 UsageContext::TraitBound,
+                trait_path.span(),
             );
             self.usage_counts.trait_bounds += 1;
+
+            // Deref leaks the target's whole API to every user of the type
+            if let Some(last) = trait_path.segments.last()
+                && (last.ident == "Deref" || last.ident == "DerefMut")
+            {
+                self.record_deref_impl(node, &last.ident.to_string());
+            }
         } else {
             // Inherent implementation of another module's type is implementation-level coupling.
             self.metrics.inherent_impl_count += 1;
@@ -593,52 +1758,47 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                     type_name,
                     DependencyKind::InherentImpl,
                     UsageContext::InherentImplBlock,
+                    named_type_span(&node.self_ty),
                 );
             }
         }
+
+        let previous_impl = self.current_impl_type.take();
+        let previous_trait_impl = self.in_trait_impl;
+        self.current_impl_type = self.extract_type_name(&node.self_ty);
+        self.in_trait_impl = node.trait_.is_some();
         syn::visit::visit_item_impl(self, node);
+        self.current_impl_type = previous_impl;
+        self.in_trait_impl = previous_trait_impl;
     }
 
     fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if self.exclude_test_code
+            && (has_test_attribute(&node.attrs) || has_cfg_test_attribute(&node.attrs))
+        {
+            return;
+        }
+
         // Record function definition
         let fn_name = node.sig.ident.to_string();
-        let visibility = convert_visibility(&node.vis);
-        self.defined_functions.insert(fn_name.clone(), visibility);
+        self.register_function(fn_name.clone(), &node.sig, &node.vis);
 
         // Check if this is a test function
-        if has_test_attribute(&node.attrs) {
+        let is_test = has_test_attribute(&node.attrs);
+        if is_test {
             self.metrics.test_function_count += 1;
+            self.test_function_depth += 1;
         }
 
-        // Analyze parameters for primitive obsession detection
-        let mut param_count = 0;
-        let mut primitive_param_count = 0;
-        let mut param_types = Vec::new();
-
-        for arg in &node.sig.inputs {
-            if let FnArg::Typed(pat_type) = arg {
-                param_count += 1;
-                if let Some(type_name) = self.extract_type_name(&pat_type.ty) {
-                    param_types.push(type_name.clone());
-                    if self.is_primitive_type(&type_name) {
-                        primitive_param_count += 1;
-                    }
-                }
-            }
-        }
-
-        // Register in module metrics with full details
-        self.metrics.add_function_definition_full(
-            fn_name.clone(),
-            visibility,
-            param_count,
-            primitive_param_count,
-            param_types,
-        );
+        self.record_asyncness(&node.sig, &node.vis, &node.attrs);
+        self.record_unsafety(&node.sig, &node.attrs);
+        self.record_pass_through(fn_name.clone(), &node.sig, &node.block, &node.attrs);
 
         // Set current item context for dependency tracking
         let previous_item = self.current_item.take();
         self.current_item = Some((fn_name, ItemKind::Function));
+        let previous_locals = std::mem::take(&mut self.local_types);
+        let previous_elements = std::mem::take(&mut self.local_element_types);
 
         // Analyze function signature
         self.analyze_signature(&node.sig);
@@ -646,6 +1806,54 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
 
         // Restore previous context
         self.current_item = previous_item;
+        self.local_types = previous_locals;
+        self.local_element_types = previous_elements;
+        if is_test {
+            self.test_function_depth -= 1;
+        }
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.record_asyncness(&node.sig, &node.vis, &node.attrs);
+        self.record_unsafety(&node.sig, &node.attrs);
+        let Some(self_type) = self.current_impl_type.clone() else {
+            syn::visit::visit_impl_item_fn(self, node);
+            return;
+        };
+
+        // Methods are tracked as `Type::method`; `self` resolves to the impl type
+        let method_name = format!("{}::{}", self_type, node.sig.ident);
+        self.register_function(method_name.clone(), &node.sig, &node.vis);
+        let previous_item = self.current_item.take();
+        self.current_item = Some((method_name.clone(), ItemKind::Method));
+        let previous_locals = std::mem::take(&mut self.local_types);
+        let previous_elements = std::mem::take(&mut self.local_element_types);
+        self.local_types
+            .insert("self".to_string(), self_type.clone());
+        self.analyze_signature(&node.sig);
+        // Trait methods delegate because the trait requires them, not by design choice
+        if !self.in_trait_impl {
+            self.record_pass_through(method_name, &node.sig, &node.block, &node.attrs);
+        }
+
+        syn::visit::visit_impl_item_fn(self, node);
+
+        self.current_item = previous_item;
+        self.local_types = previous_locals;
+        self.local_element_types = previous_elements;
+    }
+
+    fn visit_impl_item_const(&mut self, node: &'ast ImplItemConst) {
+        let Some(self_type) = self.current_impl_type.clone() else {
+            syn::visit::visit_impl_item_const(self, node);
+            return;
+        };
+
+        // The initializer's dependencies belong to `Type::NAME`
+        let previous_item = self.current_item.take();
+        self.current_item = Some((format!("{}::{}", self_type, node.ident), ItemKind::Const));
+        syn::visit::visit_impl_item_const(self, node);
+        self.current_item = previous_item;
     }
 
     fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
@@ -690,15 +1898,16 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
             false
         });
 
-        // Count fields and public fields
-        let (total_field_count, public_field_count) = match &node.fields {
+        // Count fields and collect public field names (tuple fields by index)
+        let (total_field_count, public_fields) = match &node.fields {
             syn::Fields::Named(fields) => {
                 let total = fields.named.len();
                 let public = fields
                     .named
                     .iter()
                     .filter(|f| matches!(f.vis, syn::Visibility::Public(_)))
-                    .count();
+                    .filter_map(|f| f.ident.as_ref().map(ToString::to_string))
+                    .collect();
                 (total, public)
             }
             syn::Fields::Unnamed(fields) => {
@@ -706,24 +1915,40 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                 let public = fields
                     .unnamed
                     .iter()
-                    .filter(|f| matches!(f.vis, syn::Visibility::Public(_)))
-                    .count();
+                    .enumerate()
+                    .filter(|(_, f)| matches!(f.vis, syn::Visibility::Public(_)))
+                    .map(|(idx, _)| idx.to_string())
+                    .collect();
                 (total, public)
             }
-            syn::Fields::Unit => (0, 0),
+            syn::Fields::Unit => (0, Vec::new()),
         };
 
         // Register in module metrics with full details
         self.metrics.add_type_definition_full(
-            name,
+            name.clone(),
             visibility,
             false, // is_trait
             is_newtype,
             inner_type,
             has_serde_derive,
-            public_field_count,
+            public_fields,
             total_field_count,
+            span_line(node.ident.span()),
         );
+        self.metrics
+            .set_type_generic_param_count(&name, generic_param_count(&node.generics));
+        if let syn::Fields::Named(fields) = &node.fields {
+            let shape = fields
+                .named
+                .iter()
+                .filter_map(|field| {
+                    let field_name = field.ident.as_ref()?.to_string();
+                    Some((field_name, render_tokens(&field.ty)))
+                })
+                .collect();
+            self.metrics.set_struct_fields(&name, shape);
+        }
 
         // Analyze struct fields for type dependencies
         match &node.fields {
@@ -737,9 +1962,11 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                             type_name,
                             DependencyKind::TypeRef,
                             UsageContext::TypeParameter,
+                            named_type_span(&field.ty),
                         );
                         self.usage_counts.type_parameters += 1;
                     }
+                    self.add_type_argument_dependencies(&field.ty, UsageContext::TypeParameter);
                 }
             }
             syn::Fields::Unnamed(fields) => {
@@ -751,8 +1978,10 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                             type_name,
                             DependencyKind::TypeRef,
                             UsageContext::TypeParameter,
+                            named_type_span(&field.ty),
                         );
                     }
+                    self.add_type_argument_dependencies(&field.ty, UsageContext::TypeParameter);
                 }
             }
             syn::Fields::Unit => {}
@@ -768,40 +1997,47 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
         self.type_visibility.insert(name.clone(), visibility);
 
         // Register in module metrics with visibility
-        self.metrics.add_type_definition(name, visibility, false);
+        self.metrics.add_type_definition_at_line(
+            name.clone(),
+            visibility,
+            false,
+            span_line(node.ident.span()),
+        );
+        self.metrics
+            .set_type_generic_param_count(&name, generic_param_count(&node.generics));
 
-        // Analyze enum variants for type dependencies
+        // Analyze enum variants for type dependencies and the shape they expose
+        let mut data_carrying_variant_count = 0;
+        let mut variant_data_types = BTreeSet::new();
         for variant in &node.variants {
-            match &variant.fields {
-                syn::Fields::Named(fields) => {
-                    for field in &fields.named {
-                        if let Some(type_name) = self.extract_type_name(&field.ty)
-                            && !self.is_primitive_type(&type_name)
-                        {
-                            self.add_dependency(
-                                type_name,
-                                DependencyKind::TypeRef,
-                                UsageContext::TypeParameter,
-                            );
-                        }
-                    }
-                }
-                syn::Fields::Unnamed(fields) => {
-                    for field in &fields.unnamed {
-                        if let Some(type_name) = self.extract_type_name(&field.ty)
-                            && !self.is_primitive_type(&type_name)
-                        {
-                            self.add_dependency(
-                                type_name,
-                                DependencyKind::TypeRef,
-                                UsageContext::TypeParameter,
-                            );
-                        }
+            let exposes_data = !variant.fields.is_empty() && !is_non_exhaustive(&variant.attrs);
+            if exposes_data {
+                data_carrying_variant_count += 1;
+            }
+            for field in &variant.fields {
+                if let Some(type_name) = self.extract_type_name(&field.ty)
+                    && !self.is_primitive_type(&type_name)
+                {
+                    if exposes_data {
+                        variant_data_types.insert(type_name.clone());
                     }
+                    self.add_dependency(
+                        type_name,
+                        DependencyKind::TypeRef,
+                        UsageContext::TypeParameter,
+                        named_type_span(&field.ty),
+                    );
                 }
-                syn::Fields::Unit => {}
+                self.add_type_argument_dependencies(&field.ty, UsageContext::TypeParameter);
             }
         }
+        self.metrics.set_enum_variants(
+            &name,
+            node.variants.len(),
+            data_carrying_variant_count,
+            variant_data_types.into_iter().collect(),
+            is_non_exhaustive(&node.attrs),
+        );
         syn::visit::visit_item_enum(self, node);
     }
 
@@ -813,22 +2049,174 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
         self.type_visibility.insert(name.clone(), visibility);
 
         // Register in module metrics with visibility (is_trait = true)
-        self.metrics.add_type_definition(name, visibility, true);
+        self.metrics.add_type_definition_at_line(
+            name,
+            visibility,
+            true,
+            span_line(node.ident.span()),
+        );
 
         self.metrics.trait_impl_count += 1;
         syn::visit::visit_item_trait(self, node);
     }
 
     fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        // Recorded even for excluded test modules: the file still belongs to the module tree
+        if node.content.is_none() {
+            let mut name = self.inline_modules.join("::");
+            if !name.is_empty() {
+                name.push_str("::");
+            }
+            name.push_str(&node.ident.to_string());
+            self.metrics.file_submodules.push(FileSubmodule {
+                name,
+                path: path_attribute_value(&node.attrs),
+                feature_gates: self.feature_gates.clone(),
+            });
+        }
+
+        if self.exclude_test_code && has_cfg_test_attribute(&node.attrs) {
+            return;
+        }
+
         // Check if this is a test module (named "tests" or has #[cfg(test)])
-        if is_test_module(node) {
+        let is_test = is_test_module(node);
+        if is_test {
             self.metrics.is_test_module = true;
+            self.test_module_depth += 1;
+        }
+
+        if self.test_module_depth == 0
+            && self.inline_modules.is_empty()
+            && matches!(node.vis, syn::Visibility::Public(_))
+        {
+            self.metrics.public_submodules.push(node.ident.to_string());
         }
 
         if node.content.is_some() {
             self.metrics.internal_deps.push(node.ident.to_string());
+            self.inline_modules.push(node.ident.to_string());
+            syn::visit::visit_item_mod(self, node);
+            self.inline_modules.pop();
+        } else {
+            syn::visit::visit_item_mod(self, node);
+        }
+
+        if is_test {
+            self.test_module_depth -= 1;
+        }
+    }
+
+    // Macro input is opaque to syn; scan its tokens for the paths it names
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.add_macro_dependencies(node.tokens.clone());
+        syn::visit::visit_macro(self, node);
+    }
+
+    // Detect associated-type projections: `<T as Trait>::Output`
+    fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+        if let Some(trait_name) = qualified_self_trait(node) {
+            let full_path = self
+                .imported_types
+                .get(&trait_name)
+                .cloned()
+                .unwrap_or_else(|| self.anchor_inline_super(&trait_name));
+
+            if !self.defined_traits.contains(&trait_name) {
+                self.add_dependency(
+                    full_path.clone(),
+                    DependencyKind::TypeRef,
+                    UsageContext::TraitBound,
+                    node.span(),
+                );
+                self.usage_counts.trait_bounds += 1;
+            }
+
+            self.add_item_dependency(
+                full_path,
+                ItemDepType::TraitBound,
+                span_line(node.span()),
+                None,
+            );
+        }
+        syn::visit::visit_type_path(self, node);
+    }
+
+    // `T: Trait`, `where` clauses, supertraits, `dyn Trait` and `impl Trait` all
+    // depend on the trait's contract (`?Sized` relaxes a bound rather than adding one)
+    fn visit_trait_bound(&mut self, node: &'ast syn::TraitBound) {
+        if matches!(node.modifier, syn::TraitBoundModifier::None) {
+            let trait_name = node
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            let full_path = self
+                .imported_types
+                .get(&trait_name)
+                .cloned()
+                .unwrap_or_else(|| self.anchor_inline_super(&trait_name));
+
+            if !self.defined_traits.contains(&trait_name) && !self.is_primitive_type(&trait_name) {
+                self.add_dependency(
+                    full_path.clone(),
+                    DependencyKind::TypeRef,
+                    UsageContext::TraitBound,
+                    node.path.span(),
+                );
+                self.usage_counts.trait_bounds += 1;
+                self.add_item_dependency(
+                    full_path,
+                    ItemDepType::TraitBound,
+                    span_line(node.path.span()),
+                    None,
+                );
+            }
+        }
+        syn::visit::visit_trait_bound(self, node);
+    }
+
+    // Track `let x: T`, `let x = T { .. }` and `let x = T::new(..)` bindings
+    fn visit_local(&mut self, node: &'ast Local) {
+        if let Pat::Type(pat_type) = &node.pat {
+            self.bind_local_element_type(&pat_type.pat, &pat_type.ty);
         }
-        syn::visit::visit_item_mod(self, node);
+        let binding = match &node.pat {
+            Pat::Type(pat_type) => self
+                .extract_type_name(&pat_type.ty)
+                .map(|type_name| (&*pat_type.pat, type_name)),
+            pat => node.init.as_ref().and_then(|init| {
+                let type_name = match &*init.expr {
+                    Expr::Struct(expr_struct) => Some(path_to_string(&expr_struct.path)),
+                    Expr::Call(call) => match &*call.func {
+                        Expr::Path(func) if func.path.segments.len() >= 2 => {
+                            let segments = &func.path.segments;
+                            Some(
+                                segments
+                                    .iter()
+                                    .take(segments.len() - 1)
+                                    .map(|s| s.ident.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join("::"),
+                            )
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                type_name.map(|type_name| (pat, type_name))
+            }),
+        };
+
+        if let Some((pat, type_name)) = binding
+            && !type_name.starts_with("Self")
+            && !self.is_primitive_type(&type_name)
+        {
+            self.bind_local_type(pat, &type_name);
+        }
+        syn::visit::visit_local(self, node);
     }
 
     // Detect field access: `foo.bar`
@@ -848,18 +2236,24 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                 .collect::<Vec<_>>()
                 .join("::");
 
-            // Resolve to full path if imported
-            let full_path = self
-                .imported_types
+            // Resolve a typed local binding to its type, then to a full path if imported
+            let type_name = self
+                .local_types
                 .get(&base_name)
                 .cloned()
                 .unwrap_or(base_name.clone());
+            let full_path = self
+                .imported_types
+                .get(&type_name)
+                .cloned()
+                .unwrap_or_else(|| self.anchor_inline_super(&type_name));
 
-            if !self.is_primitive_type(&full_path) && !self.defined_types.contains(&full_path) {
+            if !self.is_primitive_type(&full_path) && !self.defined_types.contains(&type_name) {
                 self.add_dependency(
                     full_path.clone(),
                     DependencyKind::TypeRef,
                     UsageContext::FieldAccess,
+                    node.member.span(),
                 );
                 self.usage_counts.field_accesses += 1;
             }
@@ -869,7 +2263,7 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
             self.add_item_dependency(
                 format!("{}.{}", full_path, field_name),
                 ItemDepType::FieldAccess,
-                0,
+                span_line(node.member.span()),
                 Some(expr),
             );
         }
@@ -878,43 +2272,43 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
 
     // Detect method calls: `foo.method()`
     fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
-        let method_name = node.method.to_string();
-
         // This is a method call - Functional coupling
         if let Expr::Path(path_expr) = &*node.receiver {
-            let receiver_name = path_expr
-                .path
-                .segments
-                .iter()
-                .map(|s| s.ident.to_string())
-                .collect::<Vec<_>>()
-                .join("::");
-
-            let full_path = self
-                .imported_types
+            let receiver_name = path_to_string(&path_expr.path);
+            let type_name = self
+                .local_types
                 .get(&receiver_name)
                 .cloned()
-                .unwrap_or(receiver_name.clone());
+                .unwrap_or_else(|| receiver_name.clone());
+            let expr = format!("{}.{}()", receiver_name, node.method);
+            self.record_method_call(&type_name, expr, node);
+        } else if let Some((type_name, _)) = self.receiver_type(&node.receiver) {
+            // Chained calls, fields, indexing and constructor results
+            let expr = format!("{}.{}()", receiver_expression(&node.receiver), node.method);
+            self.record_method_call(&type_name, expr, node);
+        }
+        syn::visit::visit_expr_method_call(self, node);
+    }
 
-            if !self.is_primitive_type(&full_path) && !self.defined_types.contains(&full_path) {
-                self.add_dependency(
-                    full_path.clone(),
-                    DependencyKind::TypeRef,
-                    UsageContext::MethodCall,
-                );
-                self.usage_counts.method_calls += 1;
-            }
+    // Count `.await` points: runtime coupling to an executor
+    fn visit_expr_await(&mut self, node: &'ast ExprAwait) {
+        if self.test_module_depth == 0 {
+            self.metrics.await_count += 1;
+        }
+        syn::visit::visit_expr_await(self, node);
+    }
 
-            // Record item-level dependency
-            let expr = format!("{}.{}()", receiver_name, method_name);
-            self.add_item_dependency(
-                format!("{}::{}", full_path, method_name),
-                ItemDepType::MethodCall,
-                0, // TODO: get line number from span
-                Some(expr),
-            );
+    // Count `unsafe` blocks: coupling to invariants the compiler cannot check
+    fn visit_expr_unsafe(&mut self, node: &'ast ExprUnsafe) {
+        if self.test_module_depth == 0 && self.test_function_depth == 0 {
+            self.metrics.unsafe_block_count += 1;
         }
-        syn::visit::visit_expr_method_call(self, node);
+        syn::visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        self.record_unsafety(&node.sig, &node.attrs);
+        syn::visit::visit_trait_item_fn(self, node);
     }
 
     // Detect function calls: `Foo::new()` or `foo()`
@@ -935,22 +2329,23 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                     .imported_types
                     .get(&path_str)
                     .cloned()
-                    .unwrap_or(path_str.clone());
+                    .unwrap_or_else(|| self.anchor_inline_super(&path_str));
 
                 if !self.is_primitive_type(&full_path) && !self.defined_types.contains(&full_path) {
                     self.add_dependency(
                         full_path.clone(),
                         DependencyKind::TypeRef,
                         UsageContext::FunctionCall,
+                        node.func.span(),
                     );
                     self.usage_counts.function_calls += 1;
                 }
 
-                // Record item-level dependency
+                // Record item-level dependency; `Self::new()` targets the impl type
                 self.add_item_dependency(
-                    full_path,
+                    self.resolve_self_path(&path_str).unwrap_or(full_path),
                     ItemDepType::FunctionCall,
-                    0,
+                    span_line(node.func.span()),
                     Some(format!("{}()", path_str)),
                 );
             } else {
@@ -958,7 +2353,7 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
                 self.add_item_dependency(
                     path_str.clone(),
                     ItemDepType::FunctionCall,
-                    0,
+                    span_line(node.func.span()),
                     Some(format!("{}()", path_str)),
                 );
             }
@@ -966,6 +2361,44 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
         syn::visit::visit_expr_call(self, node);
     }
 
+    // Detect associated constant reads: `Self::MAX` or `Limits::MAX`
+    fn visit_expr_path(&mut self, node: &'ast ExprPath) {
+        let segments: Vec<String> = node
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let is_constant = segments.last().is_some_and(|name| {
+            name.chars().any(|c| c.is_ascii_uppercase())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        });
+        if segments.len() >= 2
+            && is_constant
+            && (segments[0] == "Self" || !self.is_primitive_type(&segments[0]))
+        {
+            let path_str = segments.join("::");
+            let target = self.resolve_self_path(&path_str).unwrap_or_else(|| {
+                match self.imported_types.get(&segments[0]) {
+                    Some(owner) => format!("{}::{}", owner, segments[1..].join("::")),
+                    None => self.anchor_inline_super(&path_str),
+                }
+            });
+            // `Self` outside an impl block (trait defaults) has no concrete owner
+            if !target.starts_with("Self::") {
+                self.add_item_dependency(
+                    target,
+                    ItemDepType::ConstUsage,
+                    span_line(node.path.span()),
+                    Some(path_str),
+                );
+            }
+        }
+        syn::visit::visit_expr_path(self, node);
+    }
+
     // Detect struct construction: `Foo { field: value }`
     fn visit_expr_struct(&mut self, node: &'ast ExprStruct) {
         let struct_name = node
@@ -986,13 +2419,14 @@ impl<'ast> Visit<'ast> for CouplingAnalyzer {
             .imported_types
             .get(&struct_name)
             .cloned()
-            .unwrap_or(struct_name.clone());
+            .unwrap_or_else(|| self.anchor_inline_super(&struct_name));
 
         if !self.defined_types.contains(&full_path) && !self.is_primitive_type(&struct_name) {
             self.add_dependency(
                 full_path,
                 DependencyKind::TypeRef,
                 UsageContext::StructConstruction,
+                node.path.span(),
             );
             self.usage_counts.struct_constructions += 1;
         }
@@ -1027,13 +2461,22 @@ pub fn analyze_project(path: &Path) -> Result<ProjectMetrics, AnalyzerError> {
 /// when known; otherwise they fall back to the analysis root. Paths are normalized
 /// to forward slashes for consistent glob matching on Windows.
 fn is_path_excluded(file_path: &Path, exclude_base: &Path, config: &CompiledConfig) -> bool {
+    config.should_exclude(&relative_match_path(file_path, exclude_base))
+}
+
+/// Whether the CLI `--include` / `--exclude` globs drop `file_path`, matched relative to `root`.
+fn is_path_filtered_out(file_path: &Path, root: &Path, config: &CompiledConfig) -> bool {
+    !config.passes_path_filters(&relative_match_path(file_path, root))
+}
+
+/// `file_path` relative to `base`, with forward slashes, for glob matching.
+fn relative_match_path(file_path: &Path, base: &Path) -> String {
     let normalized_file = normalize_exclude_path(file_path);
-    let normalized_base = normalize_exclude_path(exclude_base);
+    let normalized_base = normalize_exclude_path(base);
     let relative = normalized_file
         .strip_prefix(&normalized_base)
         .unwrap_or(&normalized_file);
-    let relative_str = relative.to_string_lossy().replace('\\', "/");
-    config.should_exclude(&relative_str)
+    relative.to_string_lossy().replace('\\', "/")
 }
 
 /// Convert a source path into the same normalized, config-root-relative form used by glob config.
@@ -1073,9 +2516,21 @@ pub fn analyze_project_parallel_with_config(
     let exclude_base = config.config_root().unwrap_or(path);
 
     // Collect all .rs file paths first (sequential, but fast), applying exclude patterns.
-    let file_paths: Vec<PathBuf> = rs_files(path)
-        .filter(|fp| !is_path_excluded(fp, exclude_base, config))
-        .collect();
+    let walk_start = Instant::now();
+    let candidates: Box<dyn Iterator<Item = PathBuf>> = if path.is_file() {
+        Box::new(
+            std::iter::once(path.to_path_buf())
+                .filter(|fp| fp.extension().is_some_and(|ext| ext == "rs")),
+        )
+    } else {
+        Box::new(rs_files(path))
+    };
+    let (generated_files, file_paths): (Vec<PathBuf>, Vec<PathBuf>) = candidates
+        .filter(|fp| {
+            !is_path_excluded(fp, exclude_base, config) && !is_path_filtered_out(fp, path, config)
+        })
+        .partition(|fp| is_generated_file(fp, &config.generated_markers));
+    let walk = walk_start.elapsed();
 
     // Calculate optimal chunk size based on file count and available parallelism
     // Smaller chunks = better load balancing, but more overhead
@@ -1094,56 +2549,82 @@ pub fn analyze_project_parallel_with_config(
     };
 
     // Parallel file analysis with optimized chunking
+    let parse_start = Instant::now();
     let analyzed_results: Vec<_> = file_paths
         .par_chunks(chunk_size)
         .flat_map(|chunk| {
             chunk
                 .iter()
-                .filter_map(|file_path| match analyze_rust_file_full(file_path) {
-                    Ok(result) => {
-                        // Use full module path instead of just file stem (Issue #14)
-                        let module_path = file_path_to_module_path(file_path, path);
-                        let original_module_name = result.metrics.name.clone();
-                        let module_name = if module_path.is_empty() {
-                            // Crate root (lib.rs/main.rs) - use the original name
-                            original_module_name.clone()
-                        } else {
-                            module_path
-                        };
-
-                        // Update target_module in item_dependencies if it referenced the old name
-                        let item_dependencies = result
-                            .item_dependencies
-                            .into_iter()
-                            .map(|mut dep| {
-                                if dep.target_module.as_ref() == Some(&original_module_name) {
-                                    dep.target_module = Some(module_name.clone());
-                                }
-                                dep
+                .filter_map(|file_path| {
+                    match analyze_rust_file_with(
+                        file_path,
+                        config.exclude_tests,
+                        &config.primitive_types,
+                        config.analysis_cache.as_ref(),
+                    ) {
+                        Ok(result) => {
+                            // Use full module path instead of just file stem (Issue #14)
+                            let module_path = file_path_to_module_path(file_path, path);
+                            let original_module_name = result.metrics.name.clone();
+                            let module_name = if module_path.is_empty() {
+                                // Crate root (lib.rs/main.rs) - use the original name
+                                original_module_name.clone()
+                            } else {
+                                module_path
+                            };
+
+                            // Update target_module in item_dependencies if it referenced the old name
+                            let item_dependencies = result
+                                .item_dependencies
+                                .into_iter()
+                                .map(|mut dep| {
+                                    if dep.target_module.as_ref() == Some(&original_module_name) {
+                                        dep.target_module = Some(module_name.clone());
+                                    }
+                                    dep
+                                })
+                                .collect();
+
+                            Some(AnalyzedFile {
+                                module_name: module_name.clone(),
+                                file_path: file_path.clone(),
+                                metrics: {
+                                    let mut module_metrics = result.metrics;
+                                    module_metrics.name = module_name;
+                                    module_metrics
+                                },
+                                dependencies: result.dependencies,
+                                type_visibility: result.type_visibility,
+                                item_dependencies,
                             })
-                            .collect();
-
-                        Some(AnalyzedFile {
-                            module_name: module_name.clone(),
-                            file_path: file_path.clone(),
-                            metrics: {
-                                let mut module_metrics = result.metrics;
-                                module_metrics.name = module_name;
-                                module_metrics
-                            },
-                            dependencies: result.dependencies,
-                            type_visibility: result.type_visibility,
-                            item_dependencies,
-                        })
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to analyze {}: {}", file_path.display(), e);
-                        None
+                        }
+                        Err(e) => {
+                            log_warn!("Warning: Failed to analyze {}: {}", file_path.display(), e);
+                            None
+                        }
                     }
                 })
                 .collect::<Vec<_>>()
         })
         .collect();
+    let parse = parse_start.elapsed();
+    let coupling_build_start = Instant::now();
+
+    let mut analyzed_results = analyzed_results;
+    let renames = qualify_colliding_module_names(
+        analyzed_results
+            .iter()
+            .map(|a| (a.module_name.as_str(), None, a.file_path.as_path())),
+    );
+    for (analyzed, new_name) in analyzed_results.iter_mut().zip(renames) {
+        if let Some(new_name) = new_name {
+            rename_analyzed_module(
+                &mut analyzed.metrics,
+                &mut analyzed.item_dependencies,
+                new_name,
+            );
+        }
+    }
 
     // Build module names set
     let module_names: HashSet<String> = analyzed_results
@@ -1153,7 +2634,9 @@ pub fn analyze_project_parallel_with_config(
 
     // Build project metrics (sequential, but fast)
     let mut project = ProjectMetrics::new();
+    project.searched_paths = vec![path.to_path_buf()];
     project.total_files = analyzed_results.len();
+    project.generated_files = generated_files.len();
     project.parse_failures = file_paths.len().saturating_sub(analyzed_results.len());
     // Discovered (pre-parse) files: a pattern matching only a parse-failing file is
     // covered by the parse-failure note, not drift.
@@ -1162,35 +2645,47 @@ pub fn analyze_project_parallel_with_config(
         .map(|file_path| path_for_config_matching(file_path, config))
         .collect::<Vec<_>>();
 
-    // First pass: register all types with their visibility
+    // First pass: register all types with their visibility, and `pub use` re-exports
     for analyzed in &analyzed_results {
         for (type_name, visibility) in &analyzed.type_visibility {
-            project.register_type(type_name.clone(), analyzed.module_name.clone(), *visibility);
+            project.register_type(
+                type_name.clone(),
+                analyzed.metrics.name.clone(),
+                *visibility,
+            );
         }
+        register_reexports(&mut project, &analyzed.metrics, &module_names);
     }
 
     // Second pass: add modules and couplings
-    for analyzed in &analyzed_results {
-        // Clone metrics and add item_dependencies
-        let mut metrics = analyzed.metrics.clone();
-        metrics.item_dependencies = analyzed.item_dependencies.clone();
-        metrics.subdomain =
-            config.get_subdomain(&path_for_config_matching(&analyzed.file_path, config));
+    for analyzed in analyzed_results {
+        // Move (not clone) metrics and item_dependencies into the project
+        let mut metrics = analyzed.metrics;
+        metrics.item_dependencies = analyzed.item_dependencies;
+        let config_path = path_for_config_matching(&analyzed.file_path, config);
+        metrics.subdomain = config.get_subdomain(&config_path);
+        if config.is_prelude_module(&config_path)
+            || config.is_allowlisted_module(None, &analyzed.module_name)
+        {
+            project.prelude_modules.insert(metrics.name.clone());
+        }
         project.add_module(metrics);
 
         for dep in &analyzed.dependencies {
-            // Skip invalid dependency paths (local variables, Self, etc.)
-            if !is_valid_dependency_path(&dep.path) {
-                continue;
-            }
-
             // Determine if this is an internal coupling
             let target_module =
                 resolve_target_module(&dep.path, &analyzed.module_name, &module_names, &project);
             let target_is_known_internal_module = module_names.contains(&target_module);
 
-            // Skip if target module looks invalid (but allow known module names)
-            if !target_is_known_internal_module && !is_valid_dependency_path(&target_module) {
+            // Skip invalid dependency paths (local variables, Self, etc.)
+            if !config.dependency_path_filter.keeps(
+                &dep.path,
+                &target_module,
+                target_is_known_internal_module,
+            ) {
+                if config.dependency_path_filter.looks_local(&dep.path) {
+                    project.record_dropped_path(&dep.path);
+                }
                 continue;
             }
 
@@ -1211,7 +2706,7 @@ pub fn analyze_project_parallel_with_config(
             let visibility = visibility_for_dependency(dep, target_visibility);
 
             // Create coupling metric with location
-            let coupling = CouplingMetrics::with_location(
+            let mut coupling = CouplingMetrics::with_location(
                 analyzed.module_name.clone(),
                 target_module.clone(),
                 strength,
@@ -1221,6 +2716,23 @@ pub fn analyze_project_parallel_with_config(
                 analyzed.file_path.clone(),
                 dep.line,
             );
+            if distance == Distance::DifferentModule {
+                coupling.distance_score =
+                    module_distance_score(&analyzed.module_name, &target_module);
+            }
+            coupling.location.column = dep.column;
+            coupling.connascence = dep.usage.connascence();
+            coupling.feature = dep.feature.clone();
+
+            if config.record_provenance {
+                coupling.provenance = Some(describe_classification(
+                    dep,
+                    strength,
+                    distance,
+                    &target_module,
+                    target_is_known_internal_module,
+                ));
+            }
 
             project.add_coupling(coupling);
         }
@@ -1228,8 +2740,18 @@ pub fn analyze_project_parallel_with_config(
 
     // Update any remaining coupling visibility information
     project.update_coupling_visibility();
+    if config.merge_couplings {
+        project.merge_duplicate_couplings();
+    }
+    project.link_module_tree();
+    inherit_module_feature_gates(&mut project);
     project.dead_config_patterns =
         format_dead_config_patterns(config, &candidate_config_paths, path);
+    project.analysis_timings = AnalysisTimings {
+        walk,
+        parse,
+        coupling_build: coupling_build_start.elapsed(),
+    };
 
     Ok(project)
 }
@@ -1244,35 +2766,167 @@ pub fn analyze_workspace_with_config(
     path: &Path,
     config: &CompiledConfig,
 ) -> Result<ProjectMetrics, AnalyzerError> {
+    // A single source file is analyzed on its own, not as part of its crate
+    if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+        return analyze_project_parallel_with_config(path, config);
+    }
+    // A manifest path stands for its package directory
+    let path = if path.is_file() && path.file_name().is_some_and(|name| name == "Cargo.toml") {
+        path.parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    } else {
+        path
+    };
+
     // Try to get workspace info
+    let metadata_start = Instant::now();
     let workspace = match WorkspaceInfo::from_path(path) {
         Ok(ws) => Some(ws),
+        Err(e) if !config.project_crates.is_empty() => {
+            log_info!("Note: Could not load workspace metadata: {}", e);
+            log_info!("Using source roots declared in [project] config...");
+            Some(declared_workspace(path, config))
+        }
         Err(e) => {
-            eprintln!("Note: Could not load workspace metadata: {}", e);
-            eprintln!("Falling back to basic analysis...");
+            log_info!("Note: Could not load workspace metadata: {}", e);
+            log_info!(
+                "Falling back to basic analysis (declare [project] source_roots in .coupling.toml for non-cargo layouts)..."
+            );
             None
         }
     };
 
-    if let Some(ws) = workspace {
-        analyze_with_workspace(path, &ws, config)
+    let metadata_time = metadata_start.elapsed();
+
+    let mut project = if let Some(ws) = workspace {
+        analyze_with_workspace(path, &ws, config, false)?
     } else {
         // Fall back to basic analysis
-        analyze_project_parallel_with_config(path, config)
-    }
+        analyze_project_parallel_with_config(path, config)?
+    };
+    // `cargo metadata` is part of discovering what to analyze
+    project.analysis_timings.walk += metadata_time;
+    Ok(project)
 }
 
-/// Analyze project with workspace information (parallel version)
-fn analyze_with_workspace(
-    _project_root: &Path,
-    workspace: &WorkspaceInfo,
+/// Analyze several roots as one project, honoring `[analysis].exclude` patterns from config.
+///
+/// Each root contributes its cargo workspace, or a crate named after the directory
+/// when `cargo metadata` is unavailable. The crates are merged and analyzed together,
+/// so a `use other_root::module` path resolves like a workspace dependency. Roots
+/// nested in another root are dropped, and a file reachable from two roots is
+/// analyzed once. A single root is the same as `analyze_workspace_with_config`.
+pub fn analyze_roots_with_config(
+    paths: &[PathBuf],
     config: &CompiledConfig,
 ) -> Result<ProjectMetrics, AnalyzerError> {
-    // Exclude patterns are rooted at the config file when known. Otherwise fall back
-    // to the workspace root returned by `cargo metadata`.
-    let exclude_base = config.config_root().unwrap_or(workspace.root.as_path());
-
-    let mut project = ProjectMetrics::new();
+    if let [path] = paths {
+        return analyze_workspace_with_config(path, config);
+    }
+
+    let mut roots: Vec<(PathBuf, &PathBuf)> = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            return Err(AnalyzerError::InvalidPath(format!(
+                "{} (each of several paths must be a directory)",
+                path.display()
+            )));
+        }
+        roots.push((canonical_file_key(path), path));
+    }
+    roots.sort_by_key(|(canonical, _)| canonical.components().count());
+    let mut kept: Vec<(PathBuf, &PathBuf)> = Vec::new();
+    for (canonical, path) in roots {
+        if !kept.iter().any(|(root, _)| canonical.starts_with(root)) {
+            kept.push((canonical, path));
+        }
+    }
+    kept.sort_by_key(|(_, path)| paths.iter().position(|p| p == *path));
+    if let [(_, path)] = kept.as_slice() {
+        return analyze_workspace_with_config(path, config);
+    }
+
+    let metadata_start = Instant::now();
+    let mut workspace: Option<WorkspaceInfo> = None;
+    for (canonical, path) in &kept {
+        let root_workspace = WorkspaceInfo::from_path(path).unwrap_or_else(|e| {
+            log_info!(
+                "Note: Could not load workspace metadata for {}: {}",
+                path.display(),
+                e
+            );
+            // A crate directory without a manifest still keeps its sources in `src/`
+            let source_root = Some(canonical.join("src"))
+                .filter(|src| src.is_dir())
+                .unwrap_or_else(|| canonical.clone());
+            WorkspaceInfo::from_source_roots(canonical, [(root_crate_name(canonical), source_root)])
+        });
+        match workspace.as_mut() {
+            Some(workspace) => workspace.merge(root_workspace),
+            None => workspace = Some(root_workspace),
+        }
+    }
+    let mut workspace = workspace.expect("at least two roots");
+    // Include/exclude filters and the project name are relative to the roots' common ancestor
+    workspace.root = kept
+        .iter()
+        .map(|(canonical, _)| canonical.clone())
+        .reduce(|common, root| {
+            common
+                .ancestors()
+                .find(|ancestor| root.starts_with(ancestor))
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    let metadata_time = metadata_start.elapsed();
+
+    let mut project = analyze_with_workspace(&workspace.root, &workspace, config, true)?;
+    project.analysis_timings.walk += metadata_time;
+    project.analysis_roots = kept.into_iter().map(|(_, path)| path.clone()).collect();
+    Ok(project)
+}
+
+/// Crate name for a root outside cargo: its directory, or the parent of a `src` directory.
+fn root_crate_name(root: &Path) -> String {
+    let dir = if root.file_name().is_some_and(|name| name == "src") {
+        root.parent().unwrap_or(root)
+    } else {
+        root
+    };
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("root")
+        .replace('-', "_")
+}
+
+/// Build workspace info from `[project]` crates, resolving paths against the config file.
+fn declared_workspace(path: &Path, config: &CompiledConfig) -> WorkspaceInfo {
+    let root = config.config_root().unwrap_or(path);
+    let crates = config
+        .project_crates
+        .iter()
+        .map(|declared| (declared.name.clone(), root.join(&declared.path)));
+    WorkspaceInfo::from_source_roots(root, crates)
+}
+
+/// Analyze project with workspace information (parallel version)
+///
+/// `merged_roots` is set when `workspace` folds several analysis roots together;
+/// couplings to a known module are then attributed to the crate owning it.
+fn analyze_with_workspace(
+    _project_root: &Path,
+    workspace: &WorkspaceInfo,
+    config: &CompiledConfig,
+    merged_roots: bool,
+) -> Result<ProjectMetrics, AnalyzerError> {
+    // Exclude patterns are rooted at the config file when known. Otherwise fall back
+    // to the workspace root returned by `cargo metadata`.
+    let exclude_base = config.config_root().unwrap_or(workspace.root.as_path());
+
+    let walk_start = Instant::now();
+    let mut project = ProjectMetrics::new();
 
     // Store workspace info for the report
     project.workspace_name = Some(
@@ -1284,9 +2938,31 @@ fn analyze_with_workspace(
             .to_string(),
     );
     project.workspace_members = workspace.members.clone();
+    project.crate_editions = workspace
+        .members
+        .iter()
+        .filter_map(|member| {
+            let crate_info = workspace.get_crate(member)?;
+            Some((member.clone(), crate_info.edition.clone()))
+        })
+        .collect();
+    project.grade_excluded_crates = workspace
+        .members
+        .iter()
+        .filter_map(|member| {
+            let crate_info = workspace.get_crate(member)?;
+            let kind = crate_info
+                .kinds
+                .iter()
+                .find(|kind| config.exclude_crate_kinds.contains(kind))?;
+            Some((member.clone(), *kind))
+        })
+        .collect();
 
     // Collect file paths and names; module-tree parsing only runs for members using `#[path]`.
     let mut discovered_files: Vec<DiscoveredWorkspaceFile> = Vec::new();
+    // Overlapping source roots (e.g. merged analysis roots) must not count a file twice
+    let mut claimed_files: HashSet<PathBuf> = HashSet::new();
 
     for member_name in &workspace.members {
         if let Some(crate_info) = workspace.get_crate(member_name) {
@@ -1297,11 +2973,14 @@ fn analyze_with_workspace(
                 if !source_root.exists() {
                     continue;
                 }
+                project.searched_paths.push(source_root.clone());
 
                 for file_path in
                     rs_files_excluding_nested_packages(source_root, &crate_info.manifest_path)
                 {
-                    if is_path_excluded(&file_path, exclude_base, config) {
+                    if is_path_excluded(&file_path, exclude_base, config)
+                        || is_path_filtered_out(&file_path, &workspace.root, config)
+                    {
                         continue;
                     }
                     let file_key = canonical_file_key(&file_path);
@@ -1337,7 +3016,9 @@ fn analyze_with_workspace(
                     project.boundary_skipped_files += discovery.boundary_skipped_files;
 
                     for module_file in discovery.files {
-                        if is_path_excluded(&module_file.file_path, exclude_base, config) {
+                        if is_path_excluded(&module_file.file_path, exclude_base, config)
+                            || is_path_filtered_out(&module_file.file_path, &workspace.root, config)
+                        {
                             continue;
                         }
                         let file_key = canonical_file_key(&module_file.file_path);
@@ -1360,11 +3041,18 @@ fn analyze_with_workspace(
             if member_files.is_empty() {
                 project.skipped_crates.push(member_name.clone());
             } else {
+                member_files.retain(|file_key, _| claimed_files.insert(file_key.clone()));
                 discovered_files.extend(member_files.into_values());
             }
         }
     }
 
+    let discovered_count = discovered_files.len();
+    discovered_files
+        .retain(|discovered| !is_generated_file(&discovered.file_path, &config.generated_markers));
+    project.generated_files = discovered_count - discovered_files.len();
+    let walk = walk_start.elapsed();
+
     // Calculate optimal chunk size for parallel processing
     let num_threads = rayon::current_num_threads();
     let file_count = discovered_files.len();
@@ -1375,13 +3063,20 @@ fn analyze_with_workspace(
     };
 
     // Parallel file analysis with optimized chunking
+    let parse_start = Instant::now();
+    let edition_parse_failures = Mutex::new(Vec::new());
     let analyzed_files: Vec<AnalyzedFileWithCrate> = discovered_files
         .par_chunks(chunk_size)
         .flat_map(|chunk| {
             chunk
                 .iter()
                 .filter_map(|discovered| {
-                    match analyze_rust_file_full(&discovered.file_path) {
+                    match analyze_rust_file_with(
+                        &discovered.file_path,
+                        config.exclude_tests,
+                        &config.primitive_types,
+                        config.analysis_cache.as_ref(),
+                    ) {
                         Ok(result) => {
                             // Use full module path instead of just file stem (Issue #14)
                             let module_path = discovered.module_name.clone().unwrap_or_else(|| {
@@ -1425,11 +3120,26 @@ fn analyze_with_workspace(
                             })
                         }
                         Err(e) => {
-                            eprintln!(
+                            log_warn!(
                                 "Warning: Failed to analyze {}: {}",
                                 discovered.file_path.display(),
                                 e
                             );
+                            let edition = project.crate_editions.get(&discovered.crate_name);
+                            let source =
+                                fs::read_to_string(&discovered.file_path).unwrap_or_default();
+                            if let Some(edition) = edition
+                                && let Some(hint) =
+                                    edition_parse_hint(edition, &e.to_string(), &source)
+                            {
+                                log_warn!("  Note: {}", hint);
+                                edition_parse_failures.lock().unwrap().push(format!(
+                                    "{} (edition {}): {}",
+                                    discovered.file_path.display(),
+                                    edition,
+                                    hint
+                                ));
+                            }
                             None
                         }
                     }
@@ -1437,9 +3147,13 @@ fn analyze_with_workspace(
                 .collect::<Vec<_>>()
         })
         .collect();
+    let parse = parse_start.elapsed();
+    let coupling_build_start = Instant::now();
 
     project.total_files = analyzed_files.len();
     project.parse_failures = discovered_files.len().saturating_sub(analyzed_files.len());
+    project.edition_parse_failures = edition_parse_failures.into_inner().unwrap();
+    project.edition_parse_failures.sort();
     // Discovered (pre-parse) files: a pattern matching only a parse-failing file is
     // covered by the parse-failure note, not drift.
     let candidate_config_paths = discovered_files
@@ -1447,34 +3161,75 @@ fn analyze_with_workspace(
         .map(|discovered| path_for_config_matching(&discovered.file_path, config))
         .collect::<Vec<_>>();
 
+    let mut analyzed_files = analyzed_files;
+    let renames = qualify_colliding_module_names(analyzed_files.iter().map(|a| {
+        (
+            a.module_name.as_str(),
+            Some(a.crate_name.as_str()),
+            a.file_path.as_path(),
+        )
+    }));
+    for (analyzed, new_name) in analyzed_files.iter_mut().zip(renames) {
+        if let Some(new_name) = new_name {
+            rename_analyzed_module(
+                &mut analyzed.metrics,
+                &mut analyzed.item_dependencies,
+                new_name,
+            );
+        }
+    }
+
     // Build set of known module names for validation
     let module_names: HashSet<String> = analyzed_files
         .iter()
         .map(|a| a.module_name.clone())
         .collect();
+    let module_owners: HashMap<String, String> = analyzed_files
+        .iter()
+        .map(|a| (a.module_name.clone(), a.crate_name.clone()))
+        .collect();
 
-    // First pass: register all types with their visibility before resolving dependencies.
+    // First pass: register all types with their visibility and `pub use` re-exports
+    // before resolving dependencies.
     for analyzed in &analyzed_files {
         for (type_name, visibility) in &analyzed.type_visibility {
-            project.register_type(type_name.clone(), analyzed.module_name.clone(), *visibility);
+            project.register_type(
+                type_name.clone(),
+                analyzed.metrics.name.clone(),
+                *visibility,
+            );
         }
+        register_reexports(&mut project, &analyzed.metrics, &module_names);
     }
 
     // Second pass: build coupling relationships with workspace context
-    for analyzed in &analyzed_files {
-        // Clone metrics and add item_dependencies
-        let mut metrics = analyzed.metrics.clone();
-        metrics.item_dependencies = analyzed.item_dependencies.clone();
-        metrics.subdomain =
-            config.get_subdomain(&path_for_config_matching(&analyzed.file_path, config));
+    for analyzed in analyzed_files {
+        // Move (not clone) metrics and item_dependencies into the project
+        let mut metrics = analyzed.metrics;
+        metrics.item_dependencies = analyzed.item_dependencies;
+        let config_path = path_for_config_matching(&analyzed.file_path, config);
+        metrics.subdomain = config.get_subdomain(&config_path);
+        if config.is_prelude_module(&config_path)
+            || config.is_allowlisted_module(Some(&analyzed.crate_name), &analyzed.module_name)
+        {
+            project.prelude_modules.insert(metrics.name.clone());
+            project
+                .prelude_modules
+                .insert(format!("{}::{}", analyzed.crate_name, analyzed.module_name));
+        }
+        if project
+            .grade_excluded_crates
+            .iter()
+            .any(|(crate_name, _)| *crate_name == analyzed.crate_name)
+        {
+            project.grade_excluded_modules.insert(metrics.name.clone());
+        }
+        project
+            .module_crates
+            .insert(metrics.name.clone(), analyzed.crate_name.clone());
         project.add_module(metrics);
 
         for dep in &analyzed.dependencies {
-            // Skip invalid dependency paths (local variables, Self, etc.)
-            if !is_valid_dependency_path(&dep.path) {
-                continue;
-            }
-
             // Resolve the target crate using workspace info
             let resolved_crate =
                 resolve_crate_from_path(&dep.path, &analyzed.crate_name, workspace);
@@ -1482,14 +3237,32 @@ fn analyze_with_workspace(
             let target_module =
                 resolve_target_module(&dep.path, &analyzed.module_name, &module_names, &project);
             let target_is_known_internal_module = module_names.contains(&target_module);
-            let resolved_crate = if resolved_crate.is_none() && target_is_known_internal_module {
+            let resolved_crate = if merged_roots
+                && target_is_known_internal_module
+                && resolved_crate
+                    .as_ref()
+                    .is_none_or(|name| workspace.get_crate(name).is_none())
+            {
+                // A bare type name (`Order`) is not a crate: attribute a known module to its owner
+                module_owners
+                    .get(&target_module)
+                    .cloned()
+                    .or(Some(analyzed.crate_name.clone()))
+            } else if resolved_crate.is_none() && target_is_known_internal_module {
                 Some(analyzed.crate_name.clone())
             } else {
                 resolved_crate
             };
 
-            // Skip if target module looks invalid (but allow known module names)
-            if !target_is_known_internal_module && !is_valid_dependency_path(&target_module) {
+            // Skip invalid dependency paths (local variables, Self, etc.)
+            if !config.dependency_path_filter.keeps(
+                &dep.path,
+                &target_module,
+                target_is_known_internal_module,
+            ) {
+                if config.dependency_path_filter.looks_local(&dep.path) {
+                    project.record_dropped_path(&dep.path);
+                }
                 continue;
             }
 
@@ -1525,6 +3298,25 @@ fn analyze_with_workspace(
                 analyzed.file_path.clone(),
                 dep.line,
             );
+            if distance == Distance::DifferentModule
+                && resolved_crate.as_deref() == Some(analyzed.crate_name.as_str())
+            {
+                coupling.distance_score =
+                    module_distance_score(&analyzed.module_name, &target_module);
+            }
+            coupling.location.column = dep.column;
+            coupling.connascence = dep.usage.connascence();
+            coupling.feature = dep.feature.clone();
+
+            if config.record_provenance {
+                coupling.provenance = Some(describe_classification(
+                    dep,
+                    strength,
+                    distance,
+                    &target_module,
+                    target_is_known_internal_module,
+                ));
+            }
 
             // Add crate-level info
             coupling.source_crate = Some(analyzed.crate_name.clone());
@@ -1534,6 +3326,10 @@ fn analyze_with_workspace(
         }
     }
 
+    if config.merge_couplings {
+        project.merge_duplicate_couplings();
+    }
+
     // Add crate-level dependency information
     for (crate_name, deps) in &workspace.dependency_graph {
         if workspace.is_workspace_member(crate_name) {
@@ -1548,8 +3344,15 @@ fn analyze_with_workspace(
         }
     }
 
+    project.link_module_tree();
+    inherit_module_feature_gates(&mut project);
     project.dead_config_patterns =
         format_dead_config_patterns(config, &candidate_config_paths, &workspace.root);
+    project.analysis_timings = AnalysisTimings {
+        walk,
+        parse,
+        coupling_build: coupling_build_start.elapsed(),
+    };
 
     Ok(project)
 }
@@ -1643,12 +3446,257 @@ fn cache_file_and_scan_path_attribute(
     has_path_attribute
 }
 
+/// Rules named by `// coupling:allow <rule>[, <rule>]` markers at the top of a file.
+///
+/// Only the leading block of comments, blank lines and inner attributes is read,
+/// so a marker travels with the file but cannot hide further down in it.
+fn allow_markers(content: &str) -> Vec<String> {
+    let mut rules = Vec::new();
+    for line in content.lines().take(header_line_count(content)) {
+        let Some(comment) = line.trim().strip_prefix("//") else {
+            continue;
+        };
+        let comment = comment.trim_start_matches(['/', '!']).trim();
+        if let Some(list) = comment.strip_prefix("coupling:allow") {
+            rules.extend(
+                list.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|rule| !rule.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    rules
+}
+
+/// Number of leading lines that are comments, blank lines or inner attributes
+fn header_line_count(content: &str) -> usize {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with("#!") || line.starts_with("//"))
+        .count()
+}
+
+/// `// coupling:allow <rule>(<target>)[, ...]` markers below the file header, with their line.
+///
+/// `syn` drops comments, so the raw text is scanned for line comments. Markers
+/// may stand on their own line or trail code; a rule without `(<target>)`
+/// matches any target. Markers in the leading comments apply to the whole file
+/// and are read by `allow_markers` instead.
+fn suppression_markers(content: &str) -> Vec<IssueSuppression> {
+    let header = header_line_count(content);
+    let mut suppressions = Vec::new();
+    for (line, comment) in line_comments(content) {
+        if line <= header {
+            continue;
+        }
+        let comment = comment.trim_start_matches(['/', '!']).trim();
+        let Some(list) = comment.strip_prefix("coupling:allow") else {
+            continue;
+        };
+
+        // Split on commas and whitespace outside parentheses
+        let mut entries = Vec::new();
+        let (mut depth, mut start) = (0usize, 0);
+        for (i, c) in list.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' | ' ' | '\t' if depth == 0 => {
+                    entries.push(&list[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        entries.push(&list[start..]);
+
+        for entry in entries.into_iter().map(str::trim).filter(|e| !e.is_empty()) {
+            let (rule, target) = match entry.split_once('(') {
+                Some((rule, rest)) => (rule, Some(rest.trim_end_matches(')').trim())),
+                None => (entry, None),
+            };
+            suppressions.push(IssueSuppression {
+                rule: rule.trim().to_string(),
+                target: target.filter(|t| !t.is_empty()).map(str::to_string),
+                line,
+            });
+        }
+    }
+    suppressions
+}
+
+/// Text of each `//` comment in `content` after the slashes, with its 1-based line.
+///
+/// String, raw string and char literals and block comments are skipped, so a
+/// `//` inside them is not a comment.
+fn line_comments(content: &str) -> Vec<(usize, &str)> {
+    let bytes = content.as_bytes();
+    let mut comments = Vec::new();
+    let (mut i, mut line) = (0, 1);
+    // Advance past `bytes[i]`, counting the newline it may be
+    let step = |i: &mut usize, line: &mut usize| {
+        if bytes[*i] == b'\n' {
+            *line += 1;
+        }
+        *i += 1;
+    };
+    while i < bytes.len() {
+        let follows_ident = i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_');
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                comments.push((line, &content[i + 2..end]));
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let mut depth = 0;
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if bytes[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        step(&mut i, &mut line);
+                    }
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    if i < bytes.len() {
+                        step(&mut i, &mut line);
+                    }
+                }
+                i += 1;
+            }
+            b'r' | b'b' if !follows_ident => {
+                // Raw strings: r"..", r#".."#, br#".."#
+                let start = i + if bytes[i..].starts_with(b"br") { 2 } else { 1 };
+                let hashes = bytes[start..].iter().take_while(|&&b| b == b'#').count();
+                if bytes[i] == b'b' && start == i + 1 || bytes.get(start + hashes) != Some(&b'"') {
+                    i += 1;
+                    continue;
+                }
+                let mut closing = vec![b'"'];
+                closing.extend(std::iter::repeat_n(b'#', hashes));
+                i = start + hashes + 1;
+                while i < bytes.len() && !bytes[i..].starts_with(&closing) {
+                    step(&mut i, &mut line);
+                }
+                i += closing.len();
+            }
+            b'\'' => {
+                // A char literal ('x', '\n', '\u{1F600}') rather than a lifetime
+                if bytes.get(i + 1) == Some(&b'\\') {
+                    i += 3;
+                    while i < bytes.len() && bytes[i] != b'\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                } else {
+                    let width = content[i + 1..].chars().next().map_or(0, char::len_utf8);
+                    let closes = bytes.get(i + 1 + width) == Some(&b'\'');
+                    i += if closes && width > 0 { width + 2 } else { 1 };
+                }
+            }
+            _ => step(&mut i, &mut line),
+        }
+    }
+    comments
+}
+
 // ===== Dependency Resolution (extracted to `classification`) =====
+pub use crate::classification::DependencyPathFilter;
 pub(crate) use crate::classification::{
-    calculate_distance, calculate_distance_with_workspace, is_valid_dependency_path,
-    resolve_target_module, strength_for_dependency, target_type_name, visibility_for_dependency,
+    calculate_distance, calculate_distance_with_workspace, describe_classification,
+    module_distance_score, register_reexports, resolve_target_module, strength_for_dependency,
+    target_type_name, visibility_for_dependency,
 };
 
+/// New names for modules whose name is shared by several files.
+///
+/// `ProjectMetrics::modules` is keyed by name, so colliding entries would
+/// silently overwrite each other. Every colliding entry is qualified with its
+/// crate (`client::config`); entries that still collide (two files in one
+/// crate) get their file path appended. Each rename is reported on stderr.
+/// Returns one entry per input, `None` when the name is unique.
+fn qualify_colliding_module_names<'a>(
+    modules: impl Iterator<Item = (&'a str, Option<&'a str>, &'a Path)>,
+) -> Vec<Option<String>> {
+    let modules: Vec<_> = modules.collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (name, _, _) in &modules {
+        *counts.entry(name).or_default() += 1;
+    }
+
+    let qualified: Vec<Option<String>> = modules
+        .iter()
+        .map(|(name, crate_name, _)| {
+            (counts[name] > 1).then(|| match crate_name {
+                Some(crate_name) => format!("{}::{}", crate_name, name),
+                None => name.to_string(),
+            })
+        })
+        .collect();
+    let mut qualified_counts: HashMap<&str, usize> = HashMap::new();
+    for name in qualified.iter().flatten() {
+        *qualified_counts.entry(name).or_default() += 1;
+    }
+
+    let renames: Vec<Option<String>> = qualified
+        .iter()
+        .zip(&modules)
+        .map(|(qualified, (_, _, file_path))| {
+            let qualified = qualified.as_ref()?;
+            Some(if qualified_counts[qualified.as_str()] > 1 {
+                format!("{}@{}", qualified, file_path.display())
+            } else {
+                qualified.clone()
+            })
+        })
+        .collect();
+
+    for ((name, _, file_path), new_name) in modules.iter().zip(&renames) {
+        if let Some(new_name) = new_name {
+            log_warn!(
+                "Warning: module name '{}' is shared by {} files; {} is reported as '{}'",
+                name,
+                counts[name],
+                file_path.display(),
+                new_name
+            );
+        }
+    }
+    renames
+}
+
+/// Re-key an analyzed module in `ProjectMetrics::modules`, including its
+/// self-references in item dependencies.
+///
+/// The analyzed `module_name` is kept for dependency resolution, so couplings
+/// stay named `<crate>::<module>`, which is exactly the qualified key.
+fn rename_analyzed_module(
+    metrics: &mut ModuleMetrics,
+    item_dependencies: &mut [ItemDependency],
+    new_name: String,
+) {
+    for dep in item_dependencies {
+        if dep.target_module.as_deref() == Some(metrics.name.as_str()) {
+            dep.target_module = Some(new_name.clone());
+        }
+    }
+    metrics.name = new_name;
+}
+
 /// Analyzed file with crate information
 #[derive(Debug, Clone)]
 struct AnalyzedFileWithCrate {
@@ -1664,7 +3712,15 @@ struct AnalyzedFileWithCrate {
     item_dependencies: Vec<ItemDependency>,
 }
 
+/// `#[non_exhaustive]` on an enum or variant.
+fn is_non_exhaustive(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"))
+}
+
 /// Full result of analyzing a single Rust file.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AnalyzedFileResult {
     /// Module metrics collected from definitions and usage patterns.
     pub metrics: ModuleMetrics,
@@ -1684,30 +3740,72 @@ pub fn analyze_rust_file(path: &Path) -> Result<(ModuleMetrics, Vec<Dependency>)
 
 /// Analyze a Rust file and return full results including visibility
 pub fn analyze_rust_file_full(path: &Path) -> Result<AnalyzedFileResult, AnalyzerError> {
-    let content = fs::read_to_string(path)?;
+    analyze_rust_file_full_with_options(path, false)
+}
+
+/// Like `analyze_rust_file_full`, optionally skipping test code
+/// (see `CouplingAnalyzer::with_exclude_test_code`).
+pub fn analyze_rust_file_full_with_options(
+    path: &Path,
+    exclude_test_code: bool,
+) -> Result<AnalyzedFileResult, AnalyzerError> {
+    analyze_rust_file_with(path, exclude_test_code, &PrimitiveTypes::default(), None)
+}
+
+/// Module name of a file analyzed on its own: its stem, or for `mod.rs` the
+/// directory it stands for, so `a/mod.rs` and `b/mod.rs` stay distinct.
+/// Project analysis replaces this with the full module path.
+fn file_module_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str());
+    let name = if stem == Some("mod") {
+        path.parent()
+            .and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+            .or(stem)
+    } else {
+        stem
+    };
+    name.unwrap_or("unknown").to_string()
+}
 
-    let module_name = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+/// Analyze one file with the per-file settings of a `CompiledConfig`,
+/// reusing a cached result when the file is unchanged.
+fn analyze_rust_file_with(
+    path: &Path,
+    exclude_test_code: bool,
+    primitive_types: &PrimitiveTypes,
+    cache: Option<&AnalysisCache>,
+) -> Result<AnalyzedFileResult, AnalyzerError> {
+    let content = fs::read_to_string(path)?;
+    let settings = format!(
+        "exclude_tests={}\u{1f}{:?}",
+        exclude_test_code, primitive_types
+    );
+    if let Some(cached) = cache.and_then(|cache| cache.load(path, &content, &settings)) {
+        return Ok(cached);
+    }
 
-    let mut analyzer = CouplingAnalyzer::new(module_name, path.to_path_buf());
+    let mut analyzer = CouplingAnalyzer::new(file_module_name(path), path.to_path_buf())
+        .with_exclude_test_code(exclude_test_code)
+        .with_primitive_types(primitive_types.clone());
     analyzer.analyze_file(&content)?;
 
-    Ok(AnalyzedFileResult {
+    let result = AnalyzedFileResult {
         metrics: analyzer.metrics,
         dependencies: analyzer.dependencies,
         type_visibility: analyzer.type_visibility,
         item_dependencies: analyzer.item_dependencies,
-    })
+    };
+    if let Some(cache) = cache {
+        cache.store(path, &content, &settings, &result);
+    }
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::classification::extract_target_module;
-    use crate::metrics::dimensions::Distance;
 
     fn resolve_target_module_for_test(
         path: &str,
@@ -1737,141 +3835,953 @@ mod tests {
     }
 
     #[test]
-    fn test_analyzer_creation() {
-        let analyzer = CouplingAnalyzer::new(
-            "test_module".to_string(),
-            std::path::PathBuf::from("test.rs"),
-        );
-        assert_eq!(analyzer.current_module, "test_module");
+    fn test_analyzer_creation() {
+        let analyzer = CouplingAnalyzer::new(
+            "test_module".to_string(),
+            std::path::PathBuf::from("test.rs"),
+        );
+        assert_eq!(analyzer.current_module, "test_module");
+    }
+
+    #[test]
+    fn test_analyze_simple_file() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            pub struct User {
+                name: String,
+                email: String,
+            }
+
+            impl User {
+                pub fn new(name: String, email: String) -> Self {
+                    Self { name, email }
+                }
+            }
+        "#;
+
+        let result = analyzer.analyze_file(code);
+        assert!(result.is_ok());
+        assert_eq!(analyzer.metrics.inherent_impl_count, 1);
+    }
+
+    #[test]
+    fn test_item_dependencies() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            pub struct Config {
+                pub value: i32,
+            }
+
+            pub fn process(config: Config) -> i32 {
+                let x = config.value;
+                helper(x)
+            }
+
+            fn helper(n: i32) -> i32 {
+                n * 2
+            }
+        "#;
+
+        let result = analyzer.analyze_file(code);
+        assert!(result.is_ok());
+
+        // Check that functions are recorded
+        assert!(analyzer.defined_functions.contains_key("process"));
+        assert!(analyzer.defined_functions.contains_key("helper"));
+
+        // Check item dependencies - process should have deps
+        println!(
+            "Item dependencies count: {}",
+            analyzer.item_dependencies.len()
+        );
+        for dep in &analyzer.item_dependencies {
+            println!(
+                "  {} -> {} ({:?})",
+                dep.source_item, dep.target, dep.dep_type
+            );
+        }
+
+        // process function should have dependencies
+        let process_deps: Vec<_> = analyzer
+            .item_dependencies
+            .iter()
+            .filter(|d| d.source_item == "process")
+            .collect();
+
+        assert!(
+            !process_deps.is_empty(),
+            "process function should have item dependencies"
+        );
+    }
+
+    #[test]
+    fn test_analyze_trait_impl() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            trait Printable {
+                fn print(&self);
+            }
+
+            struct Document;
+
+            impl Printable for Document {
+                fn print(&self) {}
+            }
+        "#;
+
+        let result = analyzer.analyze_file(code);
+        assert!(result.is_ok());
+        assert!(analyzer.metrics.trait_impl_count >= 1);
+    }
+
+    #[test]
+    fn test_associated_type_projection_is_contract_coupling() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            use crate::shapes::Shape;
+
+            pub fn measure<T: Shape>(shape: &T) -> <T as Shape>::Output {
+                shape.measure()
+            }
+
+            pub struct Cursor<I: Iterator> {
+                next: Option<<I as Iterator>::Item>,
+            }
+        "#;
+
+        analyzer.analyze_file(code).unwrap();
+
+        let contract_targets: Vec<_> = analyzer
+            .dependencies
+            .iter()
+            .filter(|d| d.usage == UsageContext::TraitBound)
+            .map(|d| d.path.as_str())
+            .collect();
+        assert!(contract_targets.contains(&"crate::shapes::Shape"));
+        assert!(contract_targets.contains(&"Iterator"));
+        assert!(
+            !analyzer
+                .dependencies
+                .iter()
+                .any(|d| d.path.ends_with("Output") || d.path.ends_with("Item")),
+            "associated type projections must not be recorded as nominal types"
+        );
+        assert_eq!(
+            UsageContext::TraitBound.to_strength(),
+            IntegrationStrength::Contract
+        );
+    }
+
+    #[test]
+    fn test_trait_objects_impl_trait_and_bounds_are_contract_dependencies() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            use std::error::Error;
+            use crate::render::{Canvas, Render};
+            use crate::store::Store;
+
+            pub trait Local {}
+
+            pub struct Job {
+                pub failure: Option<Box<dyn Error + Send>>,
+            }
+
+            pub fn draw(items: impl Iterator<Item = u8>, target: &mut dyn Canvas) {}
+
+            pub fn save<S, T: ?Sized>(store: &S, value: &T)
+            where
+                S: Store,
+                T: Render + Local,
+            {
+            }
+        "#;
+
+        analyzer.analyze_file(code).unwrap();
+
+        let contract_targets: Vec<_> = analyzer
+            .dependencies
+            .iter()
+            .filter(|d| d.usage == UsageContext::TraitBound)
+            .map(|d| d.path.as_str())
+            .collect();
+        for expected in [
+            "std::error::Error",
+            "Send",
+            "Iterator",
+            "crate::render::Canvas",
+            "crate::store::Store",
+            "crate::render::Render",
+        ] {
+            assert!(
+                contract_targets.contains(&expected),
+                "missing {expected} in {contract_targets:?}"
+            );
+        }
+        assert!(
+            !contract_targets.contains(&"Local"),
+            "own traits are not dependencies"
+        );
+        assert!(
+            !contract_targets.contains(&"Sized"),
+            "`?Sized` adds no bound"
+        );
+    }
+
+    #[test]
+    fn test_provenance_explains_trait_impl_coupling_when_enabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub mod shapes;\npub mod circle;\n").unwrap();
+        std::fs::write(src.join("shapes.rs"), "pub trait Shape {}\n").unwrap();
+        std::fs::write(
+            src.join("circle.rs"),
+            "use crate::shapes::Shape;\npub struct Circle;\nimpl Shape for Circle {}\n",
+        )
+        .unwrap();
+
+        let quiet = analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).unwrap();
+        assert!(quiet.couplings.iter().all(|c| c.provenance.is_none()));
+
+        let mut config = CompiledConfig::empty();
+        config.set_record_provenance(true);
+        let metrics = analyze_project_parallel_with_config(&src, &config).unwrap();
+        let trait_impl = metrics
+            .couplings
+            .iter()
+            .find(|c| {
+                c.source == "circle"
+                    && c.target == "shapes"
+                    && c.strength == IntegrationStrength::Contract
+            })
+            .expect("circle -> shapes trait impl coupling");
+        let provenance = trait_impl.provenance.as_deref().unwrap();
+        assert!(provenance.contains("TraitBound"), "{provenance}");
+        assert!(provenance.contains("strength Contract"), "{provenance}");
+    }
+
+    #[test]
+    fn test_couplings_carry_the_connascence_of_their_usage() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub mod shapes;\npub mod canvas;\n").unwrap();
+        std::fs::write(
+            src.join("shapes.rs"),
+            "pub trait Shape {}\npub struct Point { pub x: i32, pub y: i32 }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("canvas.rs"),
+            "use crate::shapes::{Point, Shape};\n\
+             pub fn draw(shape: &dyn Shape) {}\n\
+             pub fn origin() -> Point { Point { x: 0, y: 0 } }\n",
+        )
+        .unwrap();
+
+        let metrics = analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).unwrap();
+        let forms: HashSet<_> = metrics
+            .couplings
+            .iter()
+            .filter(|c| c.source == "canvas" && c.target == "shapes")
+            .map(|c| c.connascence)
+            .collect();
+        assert!(forms.contains(&Connascence::Type), "{forms:?}");
+        assert!(forms.contains(&Connascence::Position), "{forms:?}");
+
+        let stats = metrics.calculate_dimension_stats();
+        let counts = &stats.connascence_counts;
+        assert_eq!(
+            counts.name + counts.type_ + counts.meaning + counts.position,
+            stats.total()
+        );
+        assert_eq!(
+            UsageContext::FieldAccess.connascence(),
+            Connascence::Meaning
+        );
+    }
+
+    #[test]
+    fn test_file_modules_are_linked_to_the_module_that_declares_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        for dir in ["orders", "sys", "bin"] {
+            std::fs::create_dir_all(src.join(dir)).unwrap();
+        }
+        std::fs::write(
+            src.join("lib.rs"),
+            "pub mod orders;\nmod nested { mod inner; }\n#[path = \"sys/unix.rs\"]\nmod platform;\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("orders/mod.rs"), "mod line;\n").unwrap();
+        std::fs::write(src.join("orders/line.rs"), "pub struct Line;\n").unwrap();
+        std::fs::write(
+            src.join("orders/stray.rs"),
+            "pub struct Stray;\nmod deeper;\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(src.join("orders/stray")).unwrap();
+        std::fs::write(src.join("orders/stray/deeper.rs"), "pub struct Deeper;\n").unwrap();
+        std::fs::create_dir_all(src.join("nested")).unwrap();
+        std::fs::write(src.join("nested/inner.rs"), "pub struct Inner;\n").unwrap();
+        std::fs::write(src.join("sys/unix.rs"), "pub struct Fd;\n").unwrap();
+        std::fs::write(src.join("bin/tool.rs"), "mod util;\nfn main() {}\n").unwrap();
+        std::fs::write(src.join("bin/util.rs"), "pub fn help() {}\n").unwrap();
+
+        let metrics = analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).unwrap();
+        let parent = |module: &str| metrics.modules[module].parent_module.as_deref();
+
+        let declared: Vec<_> = metrics.modules["lib"]
+            .file_submodules
+            .iter()
+            .map(|submodule| submodule.name.as_str())
+            .collect();
+        assert_eq!(declared, vec!["orders", "nested::inner", "platform"]);
+        assert_eq!(parent("orders"), Some("lib"));
+        assert_eq!(parent("orders::line"), Some("orders"));
+        assert_eq!(parent("nested::inner"), Some("lib"));
+        assert_eq!(
+            parent("sys::unix"),
+            Some("lib"),
+            "#[path] modules are linked"
+        );
+        assert_eq!(parent("bin::util"), Some("bin::tool"));
+        assert_eq!(parent("lib"), None);
+        assert_eq!(metrics.orphaned_modules, vec!["orders::stray".to_string()]);
+
+        // Files rustc never compiles stay out of the grade, with everything they declare
+        assert_eq!(parent("orders::stray::deeper"), Some("orders::stray"));
+        assert!(metrics.is_excluded_from_grade("orders::stray"));
+        assert!(metrics.is_excluded_from_grade("orders::stray::deeper"));
+        assert!(!metrics.is_excluded_from_grade("orders::line"));
+    }
+
+    #[test]
+    fn test_feature_gated_mod_declaration_gates_the_loaded_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(src.join("telemetry")).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "pub mod core;\n#[cfg(feature = \"metrics\")]\npub mod telemetry;\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("core.rs"), "pub struct Engine;\n").unwrap();
+        std::fs::write(
+            src.join("telemetry/mod.rs"),
+            "mod export;\nuse crate::core::Engine;\npub fn record(_: Engine) {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("telemetry/export.rs"),
+            "#[cfg(unix)]\nuse crate::core::Engine;\n",
+        )
+        .unwrap();
+
+        let metrics = analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).unwrap();
+        let features = |source: &str| {
+            metrics
+                .couplings
+                .iter()
+                .filter(|coupling| coupling.source == source)
+                .map(|coupling| coupling.feature.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let telemetry = features("telemetry");
+        assert!(!telemetry.is_empty());
+        assert!(telemetry.iter().all(|f| f.as_deref() == Some("metrics")));
+        assert!(
+            features("telemetry::export")
+                .iter()
+                .all(|f| f.as_deref() == Some("metrics")),
+            "non-feature cfg gates inside the file are not feature gates"
+        );
+        assert!(features("lib").iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_impl_methods_and_associated_items_are_tracked_per_method() {
+        let mut analyzer =
+            CouplingAnalyzer::new("orders".to_string(), std::path::PathBuf::from("orders.rs"));
+
+        let code = r#"
+            use crate::pricing::{Limits, Price};
+
+            pub struct Order { total: u64 }
+
+            impl Order {
+                const FLOOR: u64 = Limits::MIN_TOTAL;
+
+                pub fn new(total: u64, price: &Price) -> Self {
+                    Self { total: total.max(Self::FLOOR) }
+                }
+
+                fn doubled(&self) -> Self {
+                    Self::new(self.total * 2, &Price::default())
+                }
+            }
+        "#;
+
+        analyzer.analyze_file(code).unwrap();
+
+        let new = &analyzer.metrics.function_definitions["Order::new"];
+        assert_eq!(new.visibility, Visibility::Public);
+        assert_eq!(new.param_count, 2);
+        let doubled = &analyzer.metrics.function_definitions["Order::doubled"];
+        assert_eq!(doubled.visibility, Visibility::Private);
+        assert_eq!(doubled.param_count, 0, "`self` is not a parameter");
+        assert!(analyzer.defined_functions.contains_key("Order::new"));
+
+        let from = |source: &str, dep_type: ItemDepType| -> Vec<(String, Option<String>)> {
+            analyzer
+                .item_dependencies
+                .iter()
+                .filter(|dep| dep.source_item == source && dep.dep_type == dep_type)
+                .map(|dep| (dep.target.clone(), dep.target_module.clone()))
+                .collect()
+        };
+        assert_eq!(
+            from("Order::doubled", ItemDepType::FunctionCall),
+            [
+                ("Order::new".to_string(), Some("orders".to_string())),
+                ("Price::default".to_string(), None),
+            ]
+        );
+        assert_eq!(
+            from("Order::new", ItemDepType::ConstUsage),
+            [("Order::FLOOR".to_string(), Some("orders".to_string()))]
+        );
+        assert_eq!(
+            from("Order::FLOOR", ItemDepType::ConstUsage),
+            [("crate::pricing::Limits::MIN_TOTAL".to_string(), None)]
+        );
+        assert!(
+            analyzer
+                .dependencies
+                .iter()
+                .any(|dep| dep.path.ends_with("Price")
+                    && dep.usage == UsageContext::FunctionParameter),
+            "method signatures are analyzed like free functions"
+        );
+    }
+
+    #[test]
+    fn test_generic_type_arguments_are_recorded_as_dependencies() {
+        let code = "pub struct Ledger {\n\
+                    \x20   accounts: HashMap<UserId, Account>,\n\
+                    }\n\
+                    pub fn replay(events: &[Vec<DomainEvent>]) -> Option<Result<Snapshot, LedgerError>> {\n\
+                    \x20   None\n\
+                    }\n";
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+        analyzer.analyze_file(code).unwrap();
+
+        let recorded = |path: &str, usage: UsageContext| {
+            analyzer.dependencies.iter().any(|dep| {
+                dep.path == path && dep.usage == usage && dep.kind == DependencyKind::TypeRef
+            })
+        };
+        assert!(recorded("UserId", UsageContext::TypeParameter));
+        assert!(recorded("Account", UsageContext::TypeParameter));
+        assert!(recorded("DomainEvent", UsageContext::FunctionParameter));
+        assert!(recorded("Snapshot", UsageContext::ReturnType));
+        assert!(recorded("LedgerError", UsageContext::ReturnType));
+        // The containers themselves stay primitive
+        assert!(!analyzer.dependencies.iter().any(|dep| dep.path == "Vec"));
+    }
+
+    #[test]
+    fn test_analyze_use_statements() {
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+
+        let code = r#"
+            use std::collections::HashMap;
+            use serde::Serialize;
+            use crate::utils;
+            use crate::models::{User, Post};
+        "#;
+
+        let result = analyzer.analyze_file(code);
+        assert!(result.is_ok());
+        assert!(analyzer.metrics.external_deps.contains(&"std".to_string()));
+        assert!(
+            analyzer
+                .metrics
+                .external_deps
+                .contains(&"serde".to_string())
+        );
+        assert!(!analyzer.dependencies.is_empty());
+
+        // Check internal dependencies
+        let internal_deps: Vec<_> = analyzer
+            .dependencies
+            .iter()
+            .filter(|d| d.kind == DependencyKind::InternalUse)
+            .collect();
+        assert!(!internal_deps.is_empty());
+    }
+
+    #[test]
+    fn test_item_dependencies_and_dependencies_record_source_lines_and_columns() {
+        let code = "use crate::db::Pool;\n\
+                    pub fn run(pool: &Pool) -> Vec<&[Pool]> {\n\
+                    \x20   pool.connect();\n\
+                    \x20   let size = pool.size;\n\
+                    \x20   Pool::reset(size);\n\
+                    }\n";
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+        analyzer.analyze_file(code).unwrap();
+
+        let item_line = |dep_type: ItemDepType| {
+            analyzer
+                .item_dependencies
+                .iter()
+                .find(|dep| dep.dep_type == dep_type)
+                .map(|dep| dep.line)
+        };
+        assert_eq!(item_line(ItemDepType::MethodCall), Some(3));
+        assert_eq!(item_line(ItemDepType::FieldAccess), Some(4));
+        assert_eq!(item_line(ItemDepType::FunctionCall), Some(5));
+
+        // Columns are 1-based and point at the coupled type, not at `&` or `[`
+        let dep_position = |usage: UsageContext| {
+            analyzer
+                .dependencies
+                .iter()
+                .find(|dep| dep.usage == usage)
+                .map(|dep| (dep.line, dep.column))
+        };
+        assert_eq!(dep_position(UsageContext::Import), Some((1, 5)));
+        assert_eq!(dep_position(UsageContext::FunctionParameter), Some((2, 19)));
+        assert_eq!(dep_position(UsageContext::ReturnType), Some((2, 34)));
+        assert_eq!(dep_position(UsageContext::MethodCall), Some((3, 10)));
+    }
+
+    #[test]
+    fn test_method_calls_on_fields_chains_constructors_and_indexes_resolve_their_type() {
+        let code = r#"
+            use crate::config::Config;
+            use crate::jobs::Job;
+
+            pub struct Service {
+                config: Config,
+                jobs: Vec<Job>,
+            }
+
+            impl Service {
+                pub fn run(&self) {
+                    self.config.load();
+                    self.builder().finish();
+                    self.jobs[0].process();
+                    Config::new().validate();
+                }
+
+                fn builder(&self) -> Self {
+                    todo!()
+                }
+            }
+        "#;
+        let mut analyzer = CouplingAnalyzer::new(
+            "service".to_string(),
+            std::path::PathBuf::from("service.rs"),
+        );
+        analyzer.analyze_file(code).unwrap();
+
+        let calls: Vec<_> = analyzer
+            .item_dependencies
+            .iter()
+            .filter(|dep| {
+                dep.source_item == "Service::run" && dep.dep_type == ItemDepType::MethodCall
+            })
+            .map(|dep| (dep.target.as_str(), dep.expression.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("crate::config::Config::load", "self.config.load()"),
+                ("Service::finish", "self.builder().finish()"),
+                ("Service::builder", "self.builder()"),
+                ("crate::jobs::Job::process", "self.jobs[..].process()"),
+                (
+                    "crate::config::Config::validate",
+                    "Config::new().validate()"
+                ),
+            ]
+        );
+        assert!(analyzer.dependencies.iter().any(|dep| {
+            dep.path == "crate::jobs::Job" && dep.usage == UsageContext::MethodCall
+        }));
+    }
+
+    #[test]
+    fn test_module_function_calls_do_not_invent_a_receiver_type() {
+        let code = r#"
+            pub fn run() {
+                std::env::args().count();
+                std::fs::read_to_string("x").unwrap();
+                crate::util::items().len();
+            }
+        "#;
+        let mut analyzer =
+            CouplingAnalyzer::new("app".to_string(), std::path::PathBuf::from("app.rs"));
+        analyzer.analyze_file(code).unwrap();
+
+        let targets: Vec<_> = analyzer
+            .item_dependencies
+            .iter()
+            .filter(|dep| dep.dep_type == ItemDepType::MethodCall)
+            .map(|dep| dep.target.as_str())
+            .collect();
+        for invented in ["std::env::count", "std::fs::unwrap", "crate::util::len"] {
+            assert!(!targets.contains(&invented), "{:?}", targets);
+        }
+    }
+
+    #[test]
+    fn test_macro_bodies_contribute_import_strength_dependencies() {
+        let code = r#"
+            use crate::registry::Registry;
+            use crate::config::Settings;
+
+            lazy_static! {
+                static ref REGISTRY: Registry = Registry::new();
+            }
+
+            thread_local! {
+                static SETTINGS: std::cell::RefCell<Option<Settings>> = std::cell::RefCell::new(None);
+            }
+
+            macro_rules! open_store {
+                ($name:ident) => { crate::store::Store::open(stringify!($name)) };
+            }
+
+            pub fn log(value: u32) { println!("{}", value); }
+        "#;
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+        analyzer.analyze_file(code).unwrap();
+
+        // Registry, Settings and Store; std paths, statics and metavariables are skipped.
+        assert_eq!(analyzer.metrics.macro_dependency_count, 3);
+
+        let from_macro: Vec<_> = analyzer
+            .dependencies
+            .iter()
+            .filter(|dep| dep.from_macro)
+            .collect();
+        assert_eq!(from_macro.len(), 1, "imported paths are already recorded");
+        let store = from_macro[0];
+        assert_eq!(store.path, "crate::store::Store");
+        assert_eq!(store.usage, UsageContext::Import);
+        assert_eq!(store.kind, DependencyKind::InternalUse);
+        let store_line = code
+            .lines()
+            .position(|line| line.contains("crate::store::Store"))
+            .unwrap()
+            + 1;
+        assert_eq!(store.line, store_line);
+    }
+
+    #[test]
+    fn test_function_signatures_are_recorded_as_written() {
+        let code = r#"
+            pub struct Store;
+
+            impl Store {
+                pub fn insert<'a>(&'a mut self, key: &'a str, values: Vec<(u32, String)>) -> Option<&'a [u8; 4]> { None }
+                pub fn boxed(self: Box<Self>, (a, b): (u8, u8), f: impl Fn(u32) -> bool + Send) {}
+            }
+
+            pub fn open(path: &std::path::Path, retries: u32) -> Result<Store, std::io::Error> { Ok(Store) }
+        "#;
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+        analyzer.analyze_file(code).unwrap();
+        let functions = &analyzer.metrics.function_definitions;
+
+        let insert = &functions["Store::insert"];
+        assert_eq!(insert.receiver.as_deref(), Some("&'a mut self"));
+        assert_eq!(
+            insert.params,
+            [
+                ("key".to_string(), "&'a str".to_string()),
+                ("values".to_string(), "Vec<(u32, String)>".to_string()),
+            ]
+        );
+        assert_eq!(insert.return_type.as_deref(), Some("Option<&'a [u8; 4]>"));
+
+        let boxed = &functions["Store::boxed"];
+        assert_eq!(boxed.receiver.as_deref(), Some("self: Box<Self>"));
+        assert_eq!(boxed.params[0], ("_".to_string(), "(u8, u8)".to_string()));
+        assert_eq!(boxed.params[1].1, "impl Fn(u32) -> bool + Send");
+        assert_eq!(boxed.return_type, None);
+
+        let open = &functions["open"];
+        assert_eq!(open.receiver, None);
+        assert_eq!(open.params[0].1, "&std::path::Path");
+        assert_eq!(
+            open.return_type.as_deref(),
+            Some("Result<Store, std::io::Error>")
+        );
+    }
+
+    #[test]
+    fn test_only_trivial_delegations_are_recorded_as_pass_through() {
+        let code = r#"
+            use crate::store::Store;
+
+            pub struct Repo { store: Store, items: Vec<u32> }
+
+            impl Repo {
+                pub fn load(&self, id: u32) -> Item { self.store.load(id) }
+                pub fn save(&self, item: Item) { return self.store.save(item); }
+                pub fn len(&self) -> usize { self.items.len() }
+                pub fn find(&self, id: u32) -> Result<Item, Error> { Ok(self.store.find(id)?) }
+                pub fn load_twice(&self, id: u32) -> Item { self.store.load2(id, id) }
+                pub fn logged(&self, id: u32) -> Item {
+                    log::debug!("loading");
+                    self.store.load(id)
+                }
+                pub fn fresh() -> Self { Self::default() }
+            }
+
+            impl Clone for Repo {
+                fn clone(&self) -> Self { self.duplicate() }
+            }
+
+            pub fn parse(input: &str, strict: bool) -> Ast { parser::parse(input, strict) }
+            pub fn parse_swapped(input: &str, strict: bool) -> Ast { parser::parse(strict, input) }
+        "#;
+        let mut analyzer =
+            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
+        analyzer.analyze_file(code).unwrap();
+
+        let mut recorded: Vec<_> = analyzer
+            .metrics
+            .pass_through_methods
+            .iter()
+            .map(|method| (method.name.as_str(), method.delegate.as_str()))
+            .collect();
+        recorded.sort_unstable();
+        assert_eq!(
+            recorded,
+            [
+                ("Repo::load", "self.store.load"),
+                ("Repo::save", "self.store.save"),
+                ("parse", "parser::parse"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primitive_types_config_decides_which_names_count() {
+        let code = r#"
+            use crate::ids::{Uuid, id};
+
+            pub struct Order {
+                key: Uuid,
+                owner: id,
+                shared: Arc<Customer>,
+            }
+        "#;
+        let type_refs = |primitive_types: PrimitiveTypes| {
+            let mut analyzer =
+                CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"))
+                    .with_primitive_types(primitive_types);
+            analyzer.analyze_file(code).unwrap();
+            let mut paths: Vec<_> = analyzer
+                .dependencies
+                .into_iter()
+                .filter(|dep| dep.kind == DependencyKind::TypeRef)
+                .map(|dep| dep.path)
+                .collect();
+            paths.sort();
+            paths
+        };
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        // Defaults: `Arc` is a wrapper and `id` looks like a local variable;
+        // the wrapped `Customer` counts either way.
+        assert_eq!(type_refs(PrimitiveTypes::default()), ["Customer", "Uuid"]);
+
+        let configured = PrimitiveTypes::new(&names(&["Uuid"]), &names(&["Arc"]), false);
+        assert_eq!(type_refs(configured), ["Arc", "Customer", "id"]);
     }
 
     #[test]
-    fn test_analyze_simple_file() {
-        let mut analyzer =
-            CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
-
+    fn excluded_test_code_adds_no_definitions_or_dependencies() {
         let code = r#"
-            pub struct User {
-                name: String,
-                email: String,
-            }
+            use crate::models::User;
 
-            impl User {
-                pub fn new(name: String, email: String) -> Self {
-                    Self { name, email }
-                }
+            pub fn fixture_user() -> User { User::default() }
+
+            pub fn load(id: u32) -> User { User::find(id) }
+
+            #[test]
+            fn loads_user() { load(1); }
+
+            #[tokio::test]
+            async fn loads_user_async() { load(2); }
+
+            #[cfg(test)]
+            mod tests {
+                use crate::db::Pool;
+                struct Harness;
+                fn pool() -> Pool { Pool::new() }
             }
         "#;
+        let analyze = |exclude: bool| {
+            let mut analyzer =
+                CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"))
+                    .with_exclude_test_code(exclude);
+            analyzer.analyze_file(code).unwrap();
+            analyzer
+        };
 
-        let result = analyzer.analyze_file(code);
-        assert!(result.is_ok());
-        assert_eq!(analyzer.metrics.inherent_impl_count, 1);
+        let included = analyze(false);
+        assert_eq!(included.metrics.test_function_count, 2);
+        assert!(included.metrics.function_count() > 2);
+
+        let excluded = analyze(true);
+        let mut functions: Vec<_> = excluded
+            .metrics
+            .function_definitions
+            .keys()
+            .map(String::as_str)
+            .collect();
+        functions.sort_unstable();
+        // A helper outside test modules still counts, even if only tests use it.
+        assert_eq!(functions, vec!["fixture_user", "load"]);
+        assert!(excluded.metrics.type_definitions.is_empty());
+        assert!(!excluded.dependencies.iter().any(|d| d.path.contains("db")));
+        assert!(
+            excluded
+                .item_dependencies
+                .iter()
+                .all(|dep| !dep.source_item.contains("loads_user"))
+        );
     }
 
     #[test]
-    fn test_item_dependencies() {
+    fn test_cfg_test_imports_are_tracked_apart_from_production_deps() {
         let mut analyzer =
             CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
 
         let code = r#"
-            pub struct Config {
-                pub value: i32,
-            }
+            use serde::Serialize;
 
-            pub fn process(config: Config) -> i32 {
-                let x = config.value;
-                helper(x)
-            }
+            #[cfg(test)]
+            use proptest::prelude::*;
 
-            fn helper(n: i32) -> i32 {
-                n * 2
+            #[cfg(test)]
+            mod tests {
+                use super::*;
+                use mockall::predicate::eq;
             }
         "#;
 
-        let result = analyzer.analyze_file(code);
-        assert!(result.is_ok());
-
-        // Check that functions are recorded
-        assert!(analyzer.defined_functions.contains_key("process"));
-        assert!(analyzer.defined_functions.contains_key("helper"));
-
-        // Check item dependencies - process should have deps
-        println!(
-            "Item dependencies count: {}",
-            analyzer.item_dependencies.len()
+        analyzer.analyze_file(code).unwrap();
+        let metrics = &analyzer.metrics;
+        assert_eq!(metrics.external_deps, vec!["serde".to_string()]);
+        assert_eq!(
+            metrics.test_external_deps,
+            vec!["proptest".to_string(), "mockall".to_string()]
         );
-        for dep in &analyzer.item_dependencies {
-            println!(
-                "  {} -> {} ({:?})",
-                dep.source_item, dep.target, dep.dep_type
-            );
-        }
-
-        // process function should have dependencies
-        let process_deps: Vec<_> = analyzer
-            .item_dependencies
-            .iter()
-            .filter(|d| d.source_item == "process")
-            .collect();
-
+        assert_eq!(metrics.test_internal_deps, vec!["super::*".to_string()]);
         assert!(
-            !process_deps.is_empty(),
-            "process function should have item dependencies"
+            analyzer
+                .dependencies
+                .iter()
+                .all(|dep| !dep.path.starts_with("mockall") && !dep.path.starts_with("proptest"))
         );
     }
 
     #[test]
-    fn test_analyze_trait_impl() {
+    fn test_cfg_not_test_imports_stay_production_deps() {
         let mut analyzer =
             CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
 
         let code = r#"
-            trait Printable {
-                fn print(&self);
-            }
+            #[cfg(not(test))]
+            use serde::Serialize;
 
-            struct Document;
+            #[cfg(any(test, feature = "fixtures"))]
+            use proptest::prelude::*;
 
-            impl Printable for Document {
-                fn print(&self) {}
-            }
+            #[cfg(all(test, unix))]
+            use mockall::predicate::eq;
         "#;
 
-        let result = analyzer.analyze_file(code);
-        assert!(result.is_ok());
-        assert!(analyzer.metrics.trait_impl_count >= 1);
+        analyzer.analyze_file(code).unwrap();
+        let metrics = &analyzer.metrics;
+        assert_eq!(
+            metrics.external_deps,
+            vec!["serde".to_string(), "proptest".to_string()]
+        );
+        assert_eq!(metrics.test_external_deps, vec!["mockall".to_string()]);
     }
 
     #[test]
-    fn test_analyze_use_statements() {
+    fn test_feature_gated_dependencies_record_their_cfg_predicate() {
         let mut analyzer =
             CouplingAnalyzer::new("test".to_string(), std::path::PathBuf::from("test.rs"));
 
         let code = r#"
-            use std::collections::HashMap;
-            use serde::Serialize;
-            use crate::utils;
-            use crate::models::{User, Post};
+            use crate::core::Engine;
+
+            #[cfg(feature = "metrics")]
+            use crate::telemetry::Recorder;
+
+            #[cfg(all(feature = "tls", unix))]
+            mod secure {
+                #[cfg(feature = "rustls")]
+                use crate::net::Socket;
+            }
+
+            #[cfg(feature = "metrics")]
+            fn run(engine: Engine) {}
         "#;
 
-        let result = analyzer.analyze_file(code);
-        assert!(result.is_ok());
-        assert!(analyzer.metrics.external_deps.contains(&"std".to_string()));
-        assert!(
+        analyzer.analyze_file(code).unwrap();
+        let feature_of = |path: &str| {
             analyzer
-                .metrics
-                .external_deps
-                .contains(&"serde".to_string())
+                .dependencies
+                .iter()
+                .find(|dep| dep.path == path)
+                .unwrap_or_else(|| panic!("no dependency on {path}"))
+                .feature
+                .clone()
+        };
+        assert_eq!(feature_of("crate::core::Engine"), None);
+        assert_eq!(
+            feature_of("crate::telemetry::Recorder"),
+            Some("metrics".to_string())
         );
-        assert!(!analyzer.dependencies.is_empty());
-
-        // Check internal dependencies
-        let internal_deps: Vec<_> = analyzer
+        assert_eq!(
+            feature_of("crate::net::Socket"),
+            Some(r#"all(all(feature = "tls", unix), feature = "rustls")"#.to_string())
+        );
+        // Used as a parameter only under the gate
+        let engine_param = analyzer
             .dependencies
             .iter()
-            .filter(|d| d.kind == DependencyKind::InternalUse)
-            .collect();
-        assert!(!internal_deps.is_empty());
+            .find(|dep| dep.path == "Engine" && dep.usage != UsageContext::Import)
+            .unwrap();
+        assert_eq!(engine_param.feature.as_deref(), Some("metrics"));
     }
 
     #[test]
@@ -1993,31 +4903,46 @@ mod tests {
             path: "crate::PublicType".to_string(),
             kind: DependencyKind::TypeRef,
             line: 0,
+            column: 0,
             usage: UsageContext::FieldAccess,
+            from_macro: false,
+            feature: None,
         };
         let public_struct = Dependency {
             path: "crate::PublicType".to_string(),
             kind: DependencyKind::TypeRef,
             line: 0,
+            column: 0,
             usage: UsageContext::StructConstruction,
+            from_macro: false,
+            feature: None,
         };
         let crate_field = Dependency {
             path: "crate::CrateType".to_string(),
             kind: DependencyKind::TypeRef,
             line: 0,
+            column: 0,
             usage: UsageContext::FieldAccess,
+            from_macro: false,
+            feature: None,
         };
         let unknown_struct = Dependency {
             path: "crate::UnknownType".to_string(),
             kind: DependencyKind::TypeRef,
             line: 0,
+            column: 0,
             usage: UsageContext::StructConstruction,
+            from_macro: false,
+            feature: None,
         };
         let inherent_impl = Dependency {
             path: "crate::PublicType".to_string(),
             kind: DependencyKind::InherentImpl,
             line: 0,
+            column: 0,
             usage: UsageContext::InherentImplBlock,
+            from_macro: false,
+            feature: None,
         };
 
         assert_eq!(
@@ -2129,6 +5054,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_different_module_distance_score_follows_the_module_tree() {
+        // Root-level siblings keep the DifferentModule value.
+        assert_eq!(module_distance_score("diff", "analyzer"), 0.5);
+        // Cousins share a parent package, unrelated deep subtrees do not.
+        let cousins = module_distance_score("web::a", "web::b::c");
+        let deep = module_distance_score("a", "x::y::z");
+        assert!((cousins - 0.45).abs() < 1e-9, "{cousins}");
+        assert!((deep - 0.6).abs() < 1e-9, "{deep}");
+        assert!(module_distance_score("a::b::c::d::e", "x::y::z::w") <= 0.75);
+
+        // Siblings under a parent are closer than a reach into another subtree,
+        // and the finer distance reaches the balance score: strong coupling
+        // into the far subtree scores worse than the flat DifferentModule value.
+        assert_eq!(
+            calculate_distance("a::b", "a::c", true),
+            Distance::SameModule
+        );
+        assert!(Distance::SameModule.value() < deep);
+        let score_at = |distance_score: f64| {
+            let mut coupling = CouplingMetrics::new(
+                "a".into(),
+                "x::y::z".into(),
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::Low,
+            );
+            coupling.distance_score = distance_score;
+            crate::balance::BalanceScore::calculate(&coupling).score
+        };
+        assert!(score_at(deep) < score_at(Distance::DifferentModule.value()));
+    }
+
     #[test]
     fn test_structural_distance_crate_syntax_does_not_make_far_module_close() {
         let known = HashSet::from(["far::away".to_string()]);
@@ -2317,6 +5275,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_has_cfg_test_attribute_requires_test_to_be_set() {
+        let cfg_test = |cfg: &str| {
+            let module: syn::ItemMod =
+                syn::parse_str(&format!("#[cfg({cfg})] mod m {{}}")).unwrap();
+            has_cfg_test_attribute(&module.attrs)
+        };
+
+        assert!(cfg_test("all(test, feature = \"x\")"));
+        assert!(cfg_test("all(unix, any(test, test))"));
+        assert!(!cfg_test("not(test)"));
+        assert!(!cfg_test("any(test, feature = \"x\")"));
+        assert!(!cfg_test("feature = \"test-utils\""));
+        assert!(!cfg_test("feature = \"contest\""));
+    }
+
     #[test]
     fn test_has_cfg_test_attribute_with_other_cfg() {
         let code = r#"
@@ -2530,4 +5504,344 @@ edition = "2024"
             filtered.modules.keys().collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_couplings_through_reexports_land_on_the_defining_module() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).expect("create src");
+        for (file, content) in [
+            (
+                "lib.rs",
+                "pub mod api;\npub mod app;\npub mod internal;\npub mod prelude;\n",
+            ),
+            (
+                "internal.rs",
+                "pub struct Engine {\n    pub speed: u32,\n}\n\npub fn start() {}\n",
+            ),
+            ("prelude.rs", "pub use crate::internal::{Engine, start};\n"),
+            // A re-export of a re-export
+            ("api.rs", "pub use super::prelude::Engine;\n"),
+            (
+                "app.rs",
+                "use crate::api::Engine;\n\npub fn run(engine: &Engine) -> u32 {\n    crate::prelude::start();\n    engine.speed\n}\n",
+            ),
+        ] {
+            std::fs::write(src.join(file), content).expect("write source file");
+        }
+
+        let metrics =
+            analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).expect("analyze");
+
+        assert_eq!(
+            metrics.resolve_reexport("api::Engine"),
+            "internal::Engine",
+            "{:?}",
+            metrics.reexport_targets
+        );
+        let targets: HashSet<&str> = metrics
+            .couplings
+            .iter()
+            .filter(|c| c.source == "app")
+            .map(|c| c.target.as_str())
+            .collect();
+        assert!(targets.contains("internal"), "{targets:?}");
+        assert!(!targets.contains("api") && !targets.contains("prelude"));
+    }
+
+    #[test]
+    fn test_self_nesting_reexports_resolve_without_looping() {
+        let mut metrics = ProjectMetrics::new();
+        metrics.register_reexport("util::a".to_string(), "util::a::a".to_string());
+        metrics.register_reexport("x".to_string(), "y".to_string());
+        metrics.register_reexport("y".to_string(), "x".to_string());
+
+        assert_eq!(metrics.resolve_reexport("util::a::B"), "util::a::a::B");
+        assert_eq!(metrics.resolve_reexport("x::T"), "x::T");
+    }
+
+    #[test]
+    fn test_suppression_markers_are_read_from_comments_below_the_header() {
+        let content = "//! Billing.\n\
+                       // coupling:allow god-module\n\
+                       \n\
+                       pub const URL: &str = \"http://x // coupling:allow a(b)\";\n\
+                       pub const RAW: &str = r#\"\"// coupling:allow c\"#;\n\
+                       pub fn f<'a>(s: &'a str) -> char { '\\'' } // coupling:allow d(e)\n\
+                       /* // coupling:allow f */\n\
+                       pub fn g() -> char { '/' } // coupling:allow g\n";
+        assert_eq!(allow_markers(content), ["god-module"]);
+        let markers: Vec<(String, Option<String>, usize)> = suppression_markers(content)
+            .into_iter()
+            .map(|s| (s.rule, s.target, s.line))
+            .collect();
+        assert_eq!(
+            markers,
+            [
+                ("d".to_string(), Some("e".to_string()), 6),
+                ("g".to_string(), None, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_imports_of_modules_named_like_locals_are_kept() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).expect("create src");
+        for (file, content) in [
+            ("lib.rs", "pub mod app;\npub mod config;\npub mod state;\n"),
+            ("state.rs", "pub struct AppState;\n"),
+            ("config.rs", "pub struct Settings;\n"),
+            (
+                "app.rs",
+                "use crate::config;\n\
+                 use crate::state;\n\
+                 pub fn run(data: u32) -> u32 {\n\
+                 \x20   data.count_ones()\n\
+                 }\n",
+            ),
+        ] {
+            std::fs::write(src.join(file), content).expect("write source file");
+        }
+
+        let metrics =
+            analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).expect("analyze");
+        let targets: HashSet<&str> = metrics
+            .couplings
+            .iter()
+            .filter(|c| c.source == "app")
+            .map(|c| c.target.as_str())
+            .collect();
+        assert_eq!(targets, HashSet::from(["config", "state"]));
+        assert_eq!(metrics.dropped_dependency_paths.get("data"), Some(&1));
+    }
+
+    #[test]
+    fn test_mod_rs_files_keep_distinct_module_names() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let src = tmp.path().join("src");
+        for dir in ["a", "b"] {
+            std::fs::create_dir_all(src.join(dir)).expect("create module dir");
+            std::fs::write(
+                src.join(dir).join("mod.rs"),
+                "pub mod util;\npub struct Root;\n",
+            )
+            .expect("write mod.rs");
+            std::fs::write(src.join(dir).join("util.rs"), "pub struct Helper;\n")
+                .expect("write util.rs");
+        }
+        std::fs::write(
+            src.join("lib.rs"),
+            "pub mod a;\npub mod b;\n\npub fn both(_: a::util::Helper, _: b::Root) {}\n",
+        )
+        .expect("write lib.rs");
+
+        let single = analyze_rust_file_full(&src.join("b/mod.rs")).expect("analyze b/mod.rs");
+        assert_eq!(single.metrics.name, "b");
+
+        let metrics =
+            analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).expect("analyze");
+        let mut names: Vec<_> = metrics.modules.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["a", "a::util", "b", "b::util", "lib"]);
+        assert_eq!(metrics.total_files, metrics.modules.len());
+        assert!(
+            metrics
+                .couplings
+                .iter()
+                .all(|c| c.target != "mod" && c.target != "util")
+        );
+    }
+
+    #[test]
+    fn test_super_paths_resolve_through_the_module_tree() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(src.join("balance")).expect("create balance dir");
+        std::fs::write(src.join("lib.rs"), "pub mod balance;\npub mod report;\n")
+            .expect("write lib.rs");
+        std::fs::write(
+            src.join("balance/mod.rs"),
+            "pub mod patterns;\npub mod score;\n",
+        )
+        .expect("write balance/mod.rs");
+        std::fs::write(
+            src.join("balance/score.rs"),
+            "pub struct Score {\n    pub value: f64,\n}\n",
+        )
+        .expect("write score.rs");
+        std::fs::write(
+            src.join("balance/patterns.rs"),
+            "use super::score::Score;\n\npub fn rate(score: &Score) -> f64 {\n    score.value\n}\n\n\
+             mod detail {\n    use super::super::score::Score;\n    use super::rate;\n\n    \
+             pub fn zero() -> Score {\n        Score { value: 0.0 }\n    }\n}\n",
+        )
+        .expect("write patterns.rs");
+        std::fs::write(
+            src.join("report.rs"),
+            "use crate::balance::score::Score;\n\npub fn show(score: &Score) -> f64 {\n    score.value\n}\n",
+        )
+        .expect("write report.rs");
+
+        let metrics =
+            analyze_project_parallel_with_config(&src, &CompiledConfig::empty()).expect("analyze");
+        let from = |source: &str| {
+            metrics
+                .couplings
+                .iter()
+                .filter(|c| c.source == source)
+                .collect::<Vec<_>>()
+        };
+
+        // Both the file-level and the inline-module `super::` reach the sibling module
+        let patterns = from("balance::patterns");
+        assert!(!patterns.is_empty());
+        for coupling in &patterns {
+            assert_eq!(coupling.target, "balance::score", "{:?}", coupling);
+            assert_eq!(coupling.distance, Distance::SameModule);
+        }
+        // `use super::rate` inside `mod detail` stays within patterns.rs
+        assert!(
+            !metrics.modules["balance::patterns"]
+                .internal_deps
+                .iter()
+                .any(|dep| dep.ends_with("rate"))
+        );
+
+        let report = from("report");
+        assert!(!report.is_empty());
+        for coupling in &report {
+            assert_eq!(coupling.target, "balance::score");
+            assert_eq!(coupling.distance, Distance::DifferentModule);
+            assert!(coupling.distance_value() > Distance::SameModule.value());
+        }
+    }
+
+    #[test]
+    fn test_cli_path_filters_apply_relative_to_the_analysis_root() {
+        use crate::config::CompiledConfig;
+
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let root = tmp.path();
+        let src = root.join("src");
+        std::fs::create_dir_all(src.join("bin")).expect("create bin dir");
+        std::fs::create_dir_all(src.join("billing")).expect("create billing dir");
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"coupling-fixture-filters\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        )
+        .expect("write Cargo.toml");
+        std::fs::write(src.join("lib.rs"), "pub mod billing;\npub mod orders;\n")
+            .expect("write lib.rs");
+        std::fs::write(src.join("orders.rs"), "pub struct Order;\n").expect("write orders.rs");
+        std::fs::write(src.join("billing/mod.rs"), "pub struct Invoice;\n")
+            .expect("write billing/mod.rs");
+        std::fs::write(src.join("bin/seed.rs"), "fn main() {}\n").expect("write seed.rs");
+
+        let modules = |include: &[&str], exclude: &[&str]| {
+            let mut config = CompiledConfig::empty();
+            let owned = |globs: &[&str]| globs.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+            config
+                .set_path_filters(&owned(include), &owned(exclude))
+                .expect("valid globs");
+            let metrics = analyze_workspace_with_config(&src, &config).expect("analyze");
+            let mut names: Vec<_> = metrics.modules.keys().cloned().collect();
+            names.sort();
+            names
+        };
+
+        let all = modules(&[], &[]);
+        assert_eq!(all.len(), 4, "unfiltered run sees every file: {:?}", all);
+
+        let without_bins = modules(&[], &["src/bin/**"]);
+        assert_eq!(without_bins.len(), 3, "saw {:?}", without_bins);
+        assert!(without_bins.iter().all(|name| !name.contains("seed")));
+
+        // `*` stays inside one directory; `--include` and `--exclude` compose
+        assert_eq!(modules(&["src/*.rs"], &["src/lib.rs"]).len(), 1);
+        assert_eq!(modules(&["src/billing/**"], &[]).len(), 1);
+
+        let mut config = CompiledConfig::empty();
+        assert!(
+            config
+                .set_path_filters(&["src/[".to_string()], &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_non_cargo_directory_is_analyzed_from_configured_source_root() {
+        use crate::config::load_compiled_config;
+
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let root = tmp.path();
+        let firmware = root.join("firmware");
+        std::fs::create_dir_all(firmware.join("drivers")).expect("create firmware dirs");
+        std::fs::write(
+            root.join(".coupling.toml"),
+            "[project]\nsource_roots = [\"firmware\"]\n",
+        )
+        .expect("write .coupling.toml");
+        std::fs::write(
+            firmware.join("lib.rs"),
+            "#![no_std]\npub mod drivers;\npub fn boot() { drivers::uart::init(); }\n",
+        )
+        .expect("write lib.rs");
+        std::fs::write(firmware.join("drivers/mod.rs"), "pub mod uart;\n")
+            .expect("write drivers/mod.rs");
+        std::fs::write(firmware.join("drivers/uart.rs"), "pub fn init() {}\n")
+            .expect("write uart.rs");
+        assert!(!root.join("Cargo.toml").exists());
+
+        let config = load_compiled_config(root).expect("load compiled config");
+        let metrics = analyze_workspace_with_config(root, &config).expect("analyze firmware");
+
+        assert_eq!(metrics.total_files, 3);
+        assert_eq!(metrics.workspace_members, vec!["firmware".to_string()]);
+        assert!(
+            metrics.modules.contains_key("drivers::uart"),
+            "expected source-root-relative module names; saw {:?}",
+            metrics.modules.keys().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_separate_roots_are_merged_into_one_project() {
+        let tmp = tempfile::tempdir().expect("create tempdir");
+        let shop = tmp.path().join("shop");
+        let web = tmp.path().join("web");
+        std::fs::create_dir_all(shop.join("src")).expect("create shop");
+        std::fs::create_dir_all(web.join("src")).expect("create web");
+        std::fs::write(shop.join("src/lib.rs"), "pub mod model;\n").expect("write lib.rs");
+        std::fs::write(
+            shop.join("src/model.rs"),
+            "pub struct Order { pub total: u64 }\n",
+        )
+        .expect("write model.rs");
+        std::fs::write(web.join("src/lib.rs"), "pub mod handler;\n").expect("write lib.rs");
+        std::fs::write(
+            web.join("src/handler.rs"),
+            "use shop::model::Order;\npub fn show(order: &Order) -> u64 { order.total }\n",
+        )
+        .expect("write handler.rs");
+
+        // shop/src lies inside shop and must not be analyzed twice
+        let paths = [shop.clone(), web.join("src"), shop.join("src")];
+        let metrics =
+            analyze_roots_with_config(&paths, &CompiledConfig::empty()).expect("analyze roots");
+
+        assert_eq!(metrics.total_files, 4);
+        assert_eq!(metrics.analysis_roots, [shop, web.join("src")]);
+        assert_eq!(metrics.module_crates["model"], "shop");
+        assert_eq!(metrics.module_crates["handler"], "web");
+        let coupling = metrics
+            .couplings
+            .iter()
+            .find(|c| c.source == "web::handler")
+            .expect("handler coupling");
+        assert_eq!(coupling.target, "shop::model");
+        assert_eq!(coupling.target_crate.as_deref(), Some("shop"));
+        assert_eq!(coupling.distance, Distance::DifferentModule);
+    }
 }
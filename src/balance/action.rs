@@ -32,6 +32,54 @@ pub enum RefactoringAction {
     },
 }
 
+/// Rough size of the work a refactoring takes, for ranking fixes by payoff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Effort {
+    Low,
+    Medium,
+    High,
+}
+
+impl Effort {
+    /// Relative cost used to weigh impact against effort
+    pub fn weight(self) -> f64 {
+        match self {
+            Effort::Low => 1.0,
+            Effort::Medium => 2.0,
+            Effort::High => 4.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Effort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Effort::Low => write!(f, "Low"),
+            Effort::Medium => write!(f, "Medium"),
+            Effort::High => write!(f, "High"),
+        }
+    }
+}
+
+impl RefactoringAction {
+    /// Estimated effort: local edits are Low, new abstractions Medium, and
+    /// changes that reshape module boundaries High.
+    pub fn effort(&self) -> Effort {
+        match self {
+            RefactoringAction::AddGetters { .. }
+            | RefactoringAction::IntroduceNewtype { .. }
+            | RefactoringAction::SimplifyAbstraction { .. } => Effort::Low,
+            RefactoringAction::IntroduceTrait { .. }
+            | RefactoringAction::MoveCloser { .. }
+            | RefactoringAction::StabilizeInterface { .. }
+            | RefactoringAction::General { .. } => Effort::Medium,
+            RefactoringAction::ExtractAdapter { .. }
+            | RefactoringAction::SplitModule { .. }
+            | RefactoringAction::BreakCycle { .. } => Effort::High,
+        }
+    }
+}
+
 impl std::fmt::Display for RefactoringAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
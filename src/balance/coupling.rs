@@ -38,7 +38,7 @@ pub fn identify_issues(coupling: &CouplingMetrics) -> Vec<CouplingIssue> {
 /// Identify issues with custom thresholds
 pub fn identify_issues_with_thresholds(
     coupling: &CouplingMetrics,
-    _thresholds: &IssueThresholds,
+    thresholds: &IssueThresholds,
 ) -> Vec<CouplingIssue> {
     let mut issues = Vec::new();
 
@@ -48,17 +48,18 @@ pub fn identify_issues_with_thresholds(
         return issues;
     }
 
-    let balance = BalanceScore::calculate(coupling);
+    let balance = BalanceScore::calculate_for(coupling, thresholds);
 
     // The crate-root re-export facade is a stable Contract, not a volatile
     // implementation — coupling to it is not an intrusive/cascading defect.
     let target_is_facade = is_crate_root_facade(&coupling.target);
+    let strength = coupling.scoring_strength(thresholds.balance_formula.visibility_penalty);
 
     // Pattern 1: Strong (Intrusive) + Far (DifferentModule). Per the Balanced
     // Coupling model, the dangerous quadrant (Strong+Far+High volatility) is owned
     // by Cascading Change Risk below; here we classify the lower-volatility cases:
     // Low volatility = "Acceptable" (Minor), Medium = Global Complexity (review).
-    if coupling.strength == IntegrationStrength::Intrusive
+    if strength == IntegrationStrength::Intrusive
         && coupling.distance == Distance::DifferentModule
         && coupling.volatility != Volatility::High
         && !target_is_facade
@@ -96,7 +97,7 @@ pub fn identify_issues_with_thresholds(
 
     // Pattern 2: Cascading Change Risk is the Strong + Far + High quadrant.
     // Strong + Close + High is cohesion around volatile code, not a defect.
-    if coupling.strength == IntegrationStrength::Intrusive
+    if strength == IntegrationStrength::Intrusive
         && coupling.distance == Distance::DifferentModule
         && coupling.volatility == Volatility::High
         && !target_is_facade
@@ -119,7 +120,7 @@ pub fn identify_issues_with_thresholds(
 
     // Pattern 3: Inappropriate Intimacy (Intrusive coupling across DIFFERENT MODULE only)
     // Same module intrusive coupling is fine (it's cohesion)
-    if coupling.strength == IntegrationStrength::Intrusive
+    if strength == IntegrationStrength::Intrusive
         && coupling.distance == Distance::DifferentModule
         && balance.score < 0.5
         && !target_is_facade
@@ -1,7 +1,7 @@
 // ===== External Crate Heuristics =====
 
 use crate::external::ExternalDependencyUsage;
-use crate::{CouplingIssue, IssueType, RefactoringAction, Severity};
+use crate::{CouplingIssue, IssueThresholds, IssueType, RefactoringAction, Severity};
 
 /// Number of internal modules above which direct third-party usage is considered scattered.
 pub const SCATTERED_EXTERNAL_BREADTH_THRESHOLD: usize = 3;
@@ -59,18 +59,56 @@ pub fn classify_crate_stability(crate_name: &str) -> CrateStability {
     }
 }
 
+/// Stability classification with the crates from `[external]` merged in.
+///
+/// A crate listed in `stable_crates` or `infrastructure_crates` takes that class
+/// over its built-in one. Names compare with `-` and `_` treated alike, so
+/// `acme-core` in the config matches `acme_core::Client` in code.
+pub fn classify_crate_stability_with_thresholds(
+    crate_name: &str,
+    thresholds: &IssueThresholds,
+) -> CrateStability {
+    let base_name = crate_name.split("::").next().unwrap_or(crate_name).trim();
+    let listed = |crates: &[String]| {
+        crates
+            .iter()
+            .any(|name| name.trim().replace('-', "_") == base_name.replace('-', "_"))
+    };
+
+    if listed(&thresholds.stable_crates) {
+        CrateStability::Stable
+    } else if listed(&thresholds.infrastructure_crates) {
+        CrateStability::Infrastructure
+    } else {
+        classify_crate_stability(crate_name)
+    }
+}
+
 /// Check if a crate should be excluded from issue detection
 pub fn should_skip_crate(crate_name: &str) -> bool {
+    should_skip_crate_with_thresholds(crate_name, &IssueThresholds::default())
+}
+
+/// `should_skip_crate` with the crates from `[external]` merged in
+pub fn should_skip_crate_with_thresholds(crate_name: &str, thresholds: &IssueThresholds) -> bool {
     matches!(
-        classify_crate_stability(crate_name),
+        classify_crate_stability_with_thresholds(crate_name, thresholds),
         CrateStability::Fundamental
     )
 }
 
 /// Check if a crate should have reduced severity
 pub fn should_reduce_severity(crate_name: &str) -> bool {
+    should_reduce_severity_with_thresholds(crate_name, &IssueThresholds::default())
+}
+
+/// `should_reduce_severity` with the crates from `[external]` merged in
+pub fn should_reduce_severity_with_thresholds(
+    crate_name: &str,
+    thresholds: &IssueThresholds,
+) -> bool {
     matches!(
-        classify_crate_stability(crate_name),
+        classify_crate_stability_with_thresholds(crate_name, thresholds),
         CrateStability::Stable | CrateStability::Infrastructure
     )
 }
@@ -78,6 +116,15 @@ pub fn should_reduce_severity(crate_name: &str) -> bool {
 /// Check if this is an external crate (not part of the workspace)
 /// External crates are identified by not containing "::" or starting with known external patterns
 pub fn is_external_crate(target: &str, source: &str) -> bool {
+    is_external_crate_with_thresholds(target, source, &IssueThresholds::default())
+}
+
+/// `is_external_crate` with the crates from `[external]` merged in
+pub fn is_external_crate_with_thresholds(
+    target: &str,
+    source: &str,
+    thresholds: &IssueThresholds,
+) -> bool {
     // If target doesn't have ::, it might be external
     // But we need to check if it's the same workspace member
 
@@ -92,7 +139,7 @@ pub fn is_external_crate(target: &str, source: &str) -> bool {
 
     // If target looks like an external crate pattern (no workspace prefix match)
     // Check if it's a known stable/infrastructure crate
-    let stability = classify_crate_stability(target);
+    let stability = classify_crate_stability_with_thresholds(target, thresholds);
     matches!(
         stability,
         CrateStability::Fundamental | CrateStability::Stable | CrateStability::Infrastructure
@@ -103,11 +150,33 @@ pub fn is_external_crate(target: &str, source: &str) -> bool {
 pub fn detect_scattered_external_coupling(
     dependencies: &[ExternalDependencyUsage],
 ) -> Vec<CouplingIssue> {
+    detect_scattered_external_coupling_with_thresholds(dependencies, &IssueThresholds::default())
+}
+
+/// Detect scattered external crates, classified with the crates from `[external]`.
+///
+/// Once `[external]` lists any crate, fundamental crates are no longer flagged
+/// and stable and infrastructure crates are reported one severity lower, since
+/// wrapping them is rarely worth it. Without it every crate is graded by breadth alone.
+pub fn detect_scattered_external_coupling_with_thresholds(
+    dependencies: &[ExternalDependencyUsage],
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
+    let classified =
+        !thresholds.stable_crates.is_empty() || !thresholds.infrastructure_crates.is_empty();
     dependencies
         .iter()
         .filter(|dependency| dependency.breadth > SCATTERED_EXTERNAL_BREADTH_THRESHOLD)
+        .filter(|dependency| {
+            !classified || !should_skip_crate_with_thresholds(&dependency.crate_name, thresholds)
+        })
         .map(|dependency| {
-            let severity = scattered_severity(dependency.breadth);
+            let mut severity = scattered_severity(dependency.breadth);
+            if classified
+                && should_reduce_severity_with_thresholds(&dependency.crate_name, thresholds)
+            {
+                severity = lower_severity(severity);
+            }
             CouplingIssue {
                 issue_type: IssueType::ScatteredExternalCoupling,
                 severity,
@@ -139,6 +208,14 @@ fn scattered_severity(breadth: usize) -> Severity {
     }
 }
 
+fn lower_severity(severity: Severity) -> Severity {
+    match severity {
+        Severity::Critical => Severity::High,
+        Severity::High => Severity::Medium,
+        Severity::Medium | Severity::Low => Severity::Low,
+    }
+}
+
 fn scattered_balance_score(breadth: usize) -> f64 {
     1.0 - (breadth as f64 / 12.0).min(1.0)
 }
@@ -175,4 +252,73 @@ mod tests {
         assert_eq!(issues[0].target, "reqwest");
         assert!(format!("{}", issues[0].refactoring).contains("facade"));
     }
+
+    #[test]
+    fn test_configured_crates_join_the_builtin_stability_classes() {
+        let thresholds = IssueThresholds {
+            stable_crates: vec!["acme-core".to_string()],
+            infrastructure_crates: vec!["acme_runtime".to_string(), "serde".to_string()],
+            ..IssueThresholds::default()
+        };
+        let classify = |name: &str| classify_crate_stability_with_thresholds(name, &thresholds);
+
+        assert_eq!(classify("acme_core::Client"), CrateStability::Stable);
+        assert_eq!(classify("acme_runtime"), CrateStability::Infrastructure);
+        // A configured class wins over the built-in one
+        assert_eq!(classify("serde"), CrateStability::Infrastructure);
+        assert_eq!(classify("std"), CrateStability::Fundamental);
+        assert_eq!(classify("acme_billing"), CrateStability::Normal);
+        assert!(should_reduce_severity_with_thresholds(
+            "acme_core",
+            &thresholds
+        ));
+        assert!(!should_reduce_severity("acme_core"));
+        assert!(should_skip_crate_with_thresholds("core", &thresholds));
+        assert!(is_external_crate_with_thresholds(
+            "acme_core::Client",
+            "billing",
+            &thresholds
+        ));
+        assert!(!is_external_crate("acme_core::Client", "billing"));
+
+        let usage = |crate_name: &str, breadth: usize| ExternalDependencyUsage {
+            crate_name: crate_name.to_string(),
+            versions: vec![],
+            breadth,
+            total_references: breadth,
+            dominant_strength: "Functional".to_string(),
+            source_modules: vec![],
+        };
+        let dependencies = [
+            usage("acme_core", 6),
+            usage("acme_billing", 6),
+            usage("std", 12),
+        ];
+        let issues = detect_scattered_external_coupling_with_thresholds(&dependencies, &thresholds);
+        let severities: Vec<(&str, Severity)> = issues
+            .iter()
+            .map(|issue| (issue.target.as_str(), issue.severity))
+            .collect();
+        assert_eq!(
+            severities,
+            [
+                ("acme_core", Severity::Medium),
+                ("acme_billing", Severity::High)
+            ]
+        );
+        // Without [external], every crate keeps its breadth-based severity
+        let issues = detect_scattered_external_coupling(&dependencies);
+        let severities: Vec<(&str, Severity)> = issues
+            .iter()
+            .map(|issue| (issue.target.as_str(), issue.severity))
+            .collect();
+        assert_eq!(
+            severities,
+            [
+                ("acme_core", Severity::High),
+                ("acme_billing", Severity::High),
+                ("std", Severity::Critical)
+            ]
+        );
+    }
 }
@@ -3,11 +3,13 @@ use std::collections::HashMap;
 use super::issue::CouplingIssue;
 use super::issue_type::IssueType;
 use super::rationale::{GradeDimension, GradeRationale, IssueTypeContribution};
+use super::score::IssueThresholds;
 use super::severity::Severity;
 
 pub(crate) fn build_grade_rationale(
     issues: &[CouplingIssue],
     internal_couplings: usize,
+    minimums: &GradeMinimums,
     japanese: bool,
 ) -> GradeRationale {
     // The narrative must explain the GRADE, and the grade excludes diagnostics
@@ -33,13 +35,15 @@ pub(crate) fn build_grade_rationale(
                 diagnostic_count
             )
         };
+        let uncoupled_capped = internal_couplings == 0 && minimums.cap_uncoupled_at_b;
+        let below_minimum = internal_couplings < minimums.min_couplings_for_a;
         let summary = if japanese {
-            if internal_couplings == 0 {
+            if uncoupled_capped {
                 "内部結合が 0 件のため、バランスを認定するにはデータが少なすぎます。グレードは B が上限です。".to_string()
-            } else if internal_couplings < 10 {
+            } else if below_minimum {
                 format!(
-                    "内部結合が {} 件で 10 件未満のため、バランスを認定するにはデータが少なすぎます。グレードは B が上限です。",
-                    internal_couplings
+                    "内部結合が {} 件で {} 件未満のため、バランスを認定するにはデータが少なすぎます。グレードは B が上限です。",
+                    internal_couplings, minimums.min_couplings_for_a
                 )
             } else {
                 format!(
@@ -47,13 +51,13 @@ pub(crate) fn build_grade_rationale(
                     internal_couplings
                 )
             }
-        } else if internal_couplings == 0 {
+        } else if uncoupled_capped {
             "0 internal couplings: too little data to certify balance; grade capped at B."
                 .to_string()
-        } else if internal_couplings < 10 {
+        } else if below_minimum {
             format!(
-                "{} internal coupling(s): fewer than 10, too little data to certify balance; grade capped at B.",
-                internal_couplings
+                "{} internal coupling(s): fewer than {}, too little data to certify balance; grade capped at B.",
+                internal_couplings, minimums.min_couplings_for_a
             )
         } else {
             format!(
@@ -246,12 +250,22 @@ fn issue_type_japanese_label(issue_type: IssueType) -> &'static str {
         IssueType::HiddenCoupling => "隠れた結合",
         IssueType::AccidentalVolatility => "偶発的な変更頻度",
         IssueType::ScatteredExternalCoupling => "外部クレート結合の分散",
+        IssueType::DeprecatedInternalCoupling => "非推奨モジュールへの結合",
+        IssueType::LayerViolation => "レイヤー違反",
         IssueType::ShallowModule => "浅いモジュール",
         IssueType::PassThroughMethod => "パススルーメソッド",
         IssueType::HighCognitiveLoad => "高認知負荷",
         IssueType::GodModule => "神モジュール",
         IssueType::PublicFieldExposure => "公開フィールド",
         IssueType::PrimitiveObsession => "プリミティブ過多",
+        IssueType::GlobImport => "globインポート",
+        IssueType::OverParameterizedType => "型パラメータ過多",
+        IssueType::SpreadCoupling => "拡散結合",
+        IssueType::MixedAsyncBoundary => "async/同期の混在",
+        IssueType::LowCohesion => "低凝集",
+        IssueType::UnsafeSurface => "unsafeの集中",
+        IssueType::DuplicatedStructure => "構造の重複",
+        IssueType::DerefAbuse => "Derefの乱用",
     }
 }
 
@@ -270,18 +284,28 @@ fn dimension_for_issue(issue_type: IssueType) -> GradeDimension {
         | IssueType::HiddenCoupling
         | IssueType::AccidentalVolatility => GradeDimension::Volatility,
         IssueType::InappropriateIntimacy
+        | IssueType::UnsafeSurface
+        | IssueType::DuplicatedStructure
+        | IssueType::DerefAbuse
         | IssueType::PublicFieldExposure
         | IssueType::PrimitiveObsession
+        | IssueType::GlobImport
+        | IssueType::OverParameterizedType
         | IssueType::ShallowModule
         | IssueType::PassThroughMethod => GradeDimension::Strength,
         IssueType::GlobalComplexity
         | IssueType::ScatteredExternalCoupling
+        | IssueType::DeprecatedInternalCoupling
+        | IssueType::LayerViolation
         | IssueType::HighEfferentCoupling
         | IssueType::HighAfferentCoupling
+        | IssueType::SpreadCoupling
         | IssueType::UnnecessaryAbstraction
         | IssueType::CircularDependency
         | IssueType::HighCognitiveLoad
-        | IssueType::GodModule => GradeDimension::Distance,
+        | IssueType::GodModule
+        | IssueType::LowCohesion
+        | IssueType::MixedAsyncBoundary => GradeDimension::Distance,
     }
 }
 
@@ -332,6 +356,30 @@ impl std::fmt::Display for HealthGrade {
     }
 }
 
+/// Minimum evidence a project must provide before it can be graded A.
+///
+/// Grades are driven by issue density (issues per internal coupling). With only
+/// a handful of couplings a single Medium issue moves the density by 10% or
+/// more, so a clean result says little about balance; the defaults therefore
+/// cap small crates at B. Crates that are small by design can lower the
+/// minimum via `[grading]` in `.coupling.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradeMinimums {
+    /// Internal couplings required before a project can be graded A (default 10).
+    pub min_couplings_for_a: usize,
+    /// Grade projects without internal couplings B, since there is nothing to assess (default true).
+    pub cap_uncoupled_at_b: bool,
+}
+
+impl Default for GradeMinimums {
+    fn default() -> Self {
+        Self {
+            min_couplings_for_a: 10,
+            cap_uncoupled_at_b: true,
+        }
+    }
+}
+
 /// Calculate health grade based on multiple quality factors
 ///
 /// Unlike the previous version that only checked for issues,
@@ -341,17 +389,19 @@ impl std::fmt::Display for HealthGrade {
 /// - Internal coupling complexity
 ///
 /// Callers pass diagnostic-free severity counts; diagnostics may be displayed,
-/// but they do not lower the structural health grade.
+/// but they do not lower the structural health grade. `minimums` sets how much
+/// evidence (internal couplings) an A requires.
 pub(crate) fn calculate_health_grade(
     issues_by_severity: &HashMap<Severity, usize>,
     internal_couplings: usize,
+    minimums: &GradeMinimums,
 ) -> HealthGrade {
     let critical = *issues_by_severity.get(&Severity::Critical).unwrap_or(&0);
     let high = *issues_by_severity.get(&Severity::High).unwrap_or(&0);
     let medium = *issues_by_severity.get(&Severity::Medium).unwrap_or(&0);
 
     // No internal couplings = B (not A - we can't assess quality without data)
-    if internal_couplings == 0 {
+    if internal_couplings == 0 && minimums.cap_uncoupled_at_b {
         return HealthGrade::B;
     }
 
@@ -361,9 +411,10 @@ pub(crate) fn calculate_health_grade(
     }
 
     // Calculate issue density (issues per internal coupling)
-    let high_density = high as f64 / internal_couplings as f64;
-    let medium_density = medium as f64 / internal_couplings as f64;
-    let total_issue_density = (critical + high + medium) as f64 / internal_couplings as f64;
+    let denominator = internal_couplings.max(1) as f64;
+    let high_density = high as f64 / denominator;
+    let medium_density = medium as f64 / denominator;
+    let total_issue_density = (critical + high + medium) as f64 / denominator;
 
     // D: Critical issues or very high issue density (> 5% high)
     if critical > 0 || high_density > 0.05 {
@@ -383,13 +434,16 @@ pub(crate) fn calculate_health_grade(
 
     // S: Over-optimized! Too few issues (< 5%) = you're probably over-engineering
     // This is a WARNING, not a reward. Stop refactoring!
-    if high == 0 && medium_density <= 0.05 && internal_couplings >= 20 {
+    if high == 0
+        && medium_density <= 0.05
+        && internal_couplings >= 20.max(minimums.min_couplings_for_a)
+    {
         return HealthGrade::S;
     }
 
     // A: Well-balanced - no high issues AND reasonable medium issues (5-10%)
     // This is the ideal target grade
-    if high == 0 && medium_density <= 0.10 && internal_couplings >= 10 {
+    if high == 0 && medium_density <= 0.10 && internal_couplings >= minimums.min_couplings_for_a {
         return HealthGrade::A;
     }
 
@@ -422,6 +476,8 @@ pub struct ProjectBalanceReport {
     pub top_priorities: Vec<CouplingIssue>,
     /// Concise explanation of why the health grade was assigned.
     pub grade_rationale: GradeRationale,
+    /// Issues silenced by `// coupling:allow <rule>(<target>)` markers.
+    pub inline_suppressions: usize,
 }
 
 impl ProjectBalanceReport {
@@ -431,6 +487,44 @@ impl ProjectBalanceReport {
         self
     }
 
+    /// Keep only issues matching `keep`, recounting the per-severity and per-type
+    /// tallies. Grade and score are left as computed over all issues.
+    pub fn retain_issues(&mut self, mut keep: impl FnMut(&CouplingIssue) -> bool) {
+        self.issues.retain(|issue| keep(issue));
+        self.top_priorities.retain(|issue| keep(issue));
+        self.issues_by_severity.clear();
+        self.issues_by_type.clear();
+        for issue in &self.issues {
+            *self.issues_by_severity.entry(issue.severity).or_insert(0) += 1;
+            *self.issues_by_type.entry(issue.issue_type).or_insert(0) += 1;
+        }
+    }
+
+    /// Recompute the health grade and its rationale from the remaining issues,
+    /// e.g. after `retain_issues` dropped the ones a baseline file accepts.
+    pub fn regrade(&mut self, thresholds: &IssueThresholds) {
+        let internal_couplings = self.balanced_count + self.needs_review + self.needs_refactoring;
+        let mut gradable_by_severity: HashMap<Severity, usize> = HashMap::new();
+        for issue in self
+            .issues
+            .iter()
+            .filter(|issue| !issue.issue_type.is_diagnostic())
+        {
+            *gradable_by_severity.entry(issue.severity).or_insert(0) += 1;
+        }
+        self.health_grade = calculate_health_grade(
+            &gradable_by_severity,
+            internal_couplings,
+            &thresholds.grade_minimums,
+        );
+        self.grade_rationale = build_grade_rationale(
+            &self.issues,
+            internal_couplings,
+            &thresholds.grade_minimums,
+            thresholds.japanese,
+        );
+    }
+
     /// Get issues grouped by type
     pub fn issues_grouped_by_type(&self) -> HashMap<IssueType, Vec<&CouplingIssue>> {
         let mut grouped: HashMap<IssueType, Vec<&CouplingIssue>> = HashMap::new();
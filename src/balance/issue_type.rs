@@ -1,5 +1,7 @@
+use serde::Deserialize;
+
 /// Types of coupling problems
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
 pub enum IssueType {
     /// Strong coupling spanning a long distance
     GlobalComplexity,
@@ -15,12 +17,16 @@ pub enum IssueType {
     UnnecessaryAbstraction,
     /// Circular dependency detected
     CircularDependency,
-    /// Strong temporal co-change without an explicit code dependency
+    /// Strong temporal co-change without an explicit code dependency, or over only a Contract one
     HiddenCoupling,
     /// Supporting or generic module changing more often than expected
     AccidentalVolatility,
     /// Direct coupling to a third-party crate is spread across many modules
     ScatteredExternalCoupling,
+    /// Coupling into an internal module the config marks as deprecated
+    DeprecatedInternalCoupling,
+    /// Coupling from a configured architecture layer into a layer above it
+    LayerViolation,
 
     // === APOSD-inspired issues (A Philosophy of Software Design) ===
     /// Module with interface complexity close to implementation complexity
@@ -37,6 +43,22 @@ pub enum IssueType {
     PublicFieldExposure,
     /// Functions with too many primitive parameters (consider newtype)
     PrimitiveObsession,
+    /// Cross-module glob imports hide which items are actually coupled
+    GlobImport,
+    /// Type with so many generic parameters that every user couples to all of them
+    OverParameterizedType,
+    /// The same member is accessed directly from many modules
+    SpreadCoupling,
+    /// Module exposing both many `async fn`s and many public sync functions
+    MixedAsyncBoundary,
+    /// Module whose functions work on disjoint sets of its types
+    LowCohesion,
+    /// Module with public API and many unsafe blocks or unsafe fns
+    UnsafeSurface,
+    /// Structs with the same named fields defined in different modules
+    DuplicatedStructure,
+    /// `Deref` impl that exposes a richer target type's whole API
+    DerefAbuse,
 }
 
 impl std::fmt::Display for IssueType {
@@ -52,6 +74,8 @@ impl std::fmt::Display for IssueType {
             IssueType::HiddenCoupling => write!(f, "Hidden Coupling"),
             IssueType::AccidentalVolatility => write!(f, "Accidental Volatility"),
             IssueType::ScatteredExternalCoupling => write!(f, "Scattered External Coupling"),
+            IssueType::DeprecatedInternalCoupling => write!(f, "Deprecated Internal Coupling"),
+            IssueType::LayerViolation => write!(f, "Layer Violation"),
             // APOSD-inspired
             IssueType::ShallowModule => write!(f, "Shallow Module"),
             IssueType::PassThroughMethod => write!(f, "Pass-Through Method"),
@@ -60,16 +84,85 @@ impl std::fmt::Display for IssueType {
             IssueType::GodModule => write!(f, "God Module"),
             IssueType::PublicFieldExposure => write!(f, "Public Field Exposure"),
             IssueType::PrimitiveObsession => write!(f, "Primitive Obsession"),
+            IssueType::GlobImport => write!(f, "Glob Import"),
+            IssueType::OverParameterizedType => write!(f, "Over-Parameterized Type"),
+            IssueType::SpreadCoupling => write!(f, "Spread Coupling"),
+            IssueType::MixedAsyncBoundary => write!(f, "Mixed Async Boundary"),
+            IssueType::LowCohesion => write!(f, "Low Cohesion"),
+            IssueType::UnsafeSurface => write!(f, "Unsafe Surface"),
+            IssueType::DuplicatedStructure => write!(f, "Duplicated Structure"),
+            IssueType::DerefAbuse => write!(f, "Deref Abuse"),
         }
     }
 }
 
 impl IssueType {
+    /// Every issue type, in declaration order.
+    pub const ALL: [IssueType; 26] = [
+        IssueType::GlobalComplexity,
+        IssueType::CascadingChangeRisk,
+        IssueType::InappropriateIntimacy,
+        IssueType::HighEfferentCoupling,
+        IssueType::HighAfferentCoupling,
+        IssueType::UnnecessaryAbstraction,
+        IssueType::CircularDependency,
+        IssueType::HiddenCoupling,
+        IssueType::AccidentalVolatility,
+        IssueType::ScatteredExternalCoupling,
+        IssueType::DeprecatedInternalCoupling,
+        IssueType::LayerViolation,
+        IssueType::ShallowModule,
+        IssueType::PassThroughMethod,
+        IssueType::HighCognitiveLoad,
+        IssueType::GodModule,
+        IssueType::PublicFieldExposure,
+        IssueType::PrimitiveObsession,
+        IssueType::GlobImport,
+        IssueType::OverParameterizedType,
+        IssueType::SpreadCoupling,
+        IssueType::MixedAsyncBoundary,
+        IssueType::LowCohesion,
+        IssueType::UnsafeSurface,
+        IssueType::DuplicatedStructure,
+        IssueType::DerefAbuse,
+    ];
+
+    /// Whether `rule` names this issue type in any case or separator style
+    /// (`inappropriate-intimacy`, `InappropriateIntimacy`, `Inappropriate Intimacy`).
+    pub fn matches_rule_name(&self, rule: &str) -> bool {
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        };
+        normalize(rule) == normalize(&self.to_string())
+    }
+
+    /// The issue type named by `rule`, accepted in any style `matches_rule_name` accepts.
+    pub fn from_rule_name(rule: &str) -> Option<IssueType> {
+        Self::ALL
+            .into_iter()
+            .find(|issue_type| issue_type.matches_rule_name(rule))
+    }
+
+    /// Kebab-case rule name (`god-module`), as used by `coupling:allow` markers.
+    pub fn rule_name(&self) -> String {
+        self.to_string().to_ascii_lowercase().replace(' ', "-")
+    }
+
     /// Whether this finding is a diagnostic observation rather than a structural
-    /// defect. Diagnostics (e.g. raw git churn contradicting a declared subdomain)
-    /// are reported for investigation but do not lower the health grade.
+    /// defect. Diagnostics (e.g. raw git churn contradicting a declared subdomain,
+    /// or unsafe code an audit should look at) are reported for investigation but
+    /// do not lower the health grade.
     pub fn is_diagnostic(&self) -> bool {
-        matches!(self, IssueType::AccidentalVolatility)
+        matches!(
+            self,
+            IssueType::AccidentalVolatility
+                | IssueType::UnsafeSurface
+                | IssueType::DuplicatedStructure
+                | IssueType::DerefAbuse
+        )
     }
 
     /// Get a detailed description of what this issue type means
@@ -97,7 +190,7 @@ impl IssueType {
                 "Circular dependencies make it impossible to understand, test, or modify components in isolation."
             }
             IssueType::HiddenCoupling => {
-                "Files frequently change together without an explicit code dependency. This suggests implicit shared knowledge or a missing abstraction."
+                "Files frequently change together without an explicit code dependency, or with only a trait (Contract) dependency that should have absorbed the change. This suggests implicit shared knowledge or a missing abstraction."
             }
             IssueType::AccidentalVolatility => {
                 "A supporting or generic subdomain changes frequently despite being expected to be stable. This suggests churn from design or ownership issues rather than essential business volatility."
@@ -105,6 +198,12 @@ impl IssueType {
             IssueType::ScatteredExternalCoupling => {
                 "A third-party crate is used directly from many internal modules, spreading upgrade and API-change risk across code you control."
             }
+            IssueType::DeprecatedInternalCoupling => {
+                "The module depends on an internal module that the project config marks as deprecated. Every new dependency makes the legacy module harder to retire."
+            }
+            IssueType::LayerViolation => {
+                "A module in one configured architecture layer depends on a layer above it. Inner layers must not know about outer ones, however balanced the coupling itself is."
+            }
             // APOSD-inspired descriptions
             IssueType::ShallowModule => {
                 "Interface complexity is close to implementation complexity. The module doesn't hide enough complexity behind a simple interface. (APOSD: Deep vs Shallow Modules)"
@@ -125,6 +224,30 @@ impl IssueType {
             IssueType::PrimitiveObsession => {
                 "Function has many primitive parameters of the same type. Consider using newtype pattern (e.g., `struct UserId(u64)`) for type safety and clarity."
             }
+            IssueType::GlobImport => {
+                "Glob imports from other modules hide exactly which items are used, obscuring the coupling surface. Coupling for this module may be under-counted."
+            }
+            IssueType::OverParameterizedType => {
+                "Type has many generic parameters. Every user must name or infer all of its type arguments, so the type imposes coupling to each of them on every use site."
+            }
+            IssueType::SpreadCoupling => {
+                "The same member is accessed directly from many modules. Renaming or changing it means touching every one of them; a single accessor or facade would keep the change in one place."
+            }
+            IssueType::LowCohesion => {
+                "The module's functions fall into clusters that share none of its types. Each cluster could live in its own module; together they only share a file. (LCOM: Lack of Cohesion of Methods)"
+            }
+            IssueType::MixedAsyncBoundary => {
+                "Module mixes many async functions with many synchronous public APIs. Sync callers end up blocking on async work (or async callers on blocking work), a common source of executor stalls; splitting along the async/sync boundary keeps the runtime coupling in one place."
+            }
+            IssueType::UnsafeSurface => {
+                "Module with a public API concentrates unsafe blocks or unsafe functions. Their correctness rests on invariants the compiler cannot check, so changes here, and changes by the modules coupled to it, carry outsized risk. Advisory: it does not lower the grade."
+            }
+            IssueType::DuplicatedStructure => {
+                "Structs in different modules have the same named fields with the same types. Each copy is a separate place to update when the shape changes, and conversions between them tend to follow. Consider one shared type. Advisory: it does not lower the grade."
+            }
+            IssueType::DerefAbuse => {
+                "Type implements Deref (or DerefMut) to a type that is not a primitive or a type parameter. Every method of the target becomes callable on it, so its users couple to the target's whole API and it can no longer change its representation. Deref is meant for smart pointers. Advisory: it does not lower the grade."
+            }
         }
     }
 
@@ -153,7 +276,7 @@ impl IssueType {
                 "循環依存はコンポーネントを単独で理解、テスト、変更することを難しくします。"
             }
             IssueType::HiddenCoupling => {
-                "明示的なコード依存がないのにファイルが頻繁に一緒に変わっています。暗黙の知識や不足した抽象化を示している可能性があります。"
+                "明示的なコード依存がない（またはトレイト契約のみの依存しかない）のにファイルが頻繁に一緒に変わっています。暗黙の知識や不足した抽象化を示している可能性があります。"
             }
             IssueType::AccidentalVolatility => {
                 "安定しているはずの支援/汎用サブドメインが頻繁に変更されています。設計や所有権の問題によるチャーンの可能性があります。"
@@ -161,6 +284,12 @@ impl IssueType {
             IssueType::ScatteredExternalCoupling => {
                 "サードパーティクレートが多くの内部モジュールから直接使われており、更新やAPI変更のリスクが広がっています。"
             }
+            IssueType::DeprecatedInternalCoupling => {
+                "設定で非推奨とされた内部モジュールに依存しています。依存が増えるほど旧モジュールの廃止が難しくなります。"
+            }
+            IssueType::LayerViolation => {
+                "設定されたアーキテクチャレイヤーで、内側のレイヤーが外側のレイヤーに依存しています。結合自体のバランスに関係なく、内側は外側を知るべきではありません。"
+            }
             IssueType::ShallowModule => {
                 "インターフェースの複雑さが実装の複雑さに近く、単純なインターフェースの背後に十分な複雑さを隠せていません。"
             }
@@ -179,6 +308,30 @@ impl IssueType {
             IssueType::PrimitiveObsession => {
                 "同じプリミティブ型の引数が多すぎます。newtypeパターンで型安全性と明確さを高めることを検討してください。"
             }
+            IssueType::GlobImport => {
+                "他モジュールからのglobインポートは実際に使う要素を隠し、結合の範囲を不明瞭にします。このモジュールの結合は過小評価されている可能性があります。"
+            }
+            IssueType::OverParameterizedType => {
+                "型パラメータが多すぎます。利用側はすべての型引数を指定または推論する必要があり、すべての利用箇所がそれぞれの型引数に結合します。"
+            }
+            IssueType::SpreadCoupling => {
+                "同じメンバーが多くのモジュールから直接参照されています。名前や型を変えるとすべての参照箇所の修正が必要です。アクセサやファサードを1つ用意すれば変更を1か所に閉じ込められます。"
+            }
+            IssueType::LowCohesion => {
+                "モジュールの関数が、型を共有しない複数のグループに分かれています。各グループは別モジュールにできます。同じファイルにあるだけでまとまりがありません。(LCOM: メソッドの凝集度の欠如)"
+            }
+            IssueType::MixedAsyncBoundary => {
+                "多くのasync関数と多くの同期公開APIが同じモジュールに混在しています。同期側が非同期処理をブロックする(またはその逆)原因になりやすく、async/同期の境界で分割すると実行時の結合を1か所にまとめられます。"
+            }
+            IssueType::UnsafeSurface => {
+                "公開APIを持つモジュールにunsafeブロックやunsafe関数が集中しています。正しさがコンパイラで検査できない不変条件に依存するため、このモジュールや結合先の変更はリスクが大きくなります。参考情報であり、評価は下げません。"
+            }
+            IssueType::DuplicatedStructure => {
+                "異なるモジュールの構造体が、同じ名前と型のフィールドを持っています。形が変わるたびにそれぞれを更新する必要があり、相互の変換処理も増えがちです。共通の型への統合を検討してください。参考情報であり、評価は下げません。"
+            }
+            IssueType::DerefAbuse => {
+                "プリミティブでも型パラメータでもない型へのDeref (またはDerefMut) を実装しています。参照先のメソッドがすべて呼び出せるため、利用者は参照先のAPI全体に結合し、内部表現を変更できなくなります。Derefはスマートポインタのための仕組みです。参考情報であり、評価は下げません。"
+            }
         }
     }
 }
@@ -24,19 +24,26 @@ pub use action::RefactoringAction;
 pub use coupling::{identify_issues, identify_issues_with_thresholds};
 pub use external_crates::{
     CrateStability, SCATTERED_EXTERNAL_BREADTH_THRESHOLD, classify_crate_stability,
-    detect_scattered_external_coupling, is_external_crate, should_reduce_severity,
-    should_skip_crate,
+    classify_crate_stability_with_thresholds, detect_scattered_external_coupling,
+    detect_scattered_external_coupling_with_thresholds, is_external_crate,
+    is_external_crate_with_thresholds, should_reduce_severity,
+    should_reduce_severity_with_thresholds, should_skip_crate, should_skip_crate_with_thresholds,
 };
-pub use grade::{HealthGrade, ProjectBalanceReport};
+pub use grade::{GradeMinimums, HealthGrade, ProjectBalanceReport};
 pub use issue::CouplingIssue;
 pub use issue_type::IssueType;
 pub use labels::{distance_label, strength_label, volatility_label};
 pub use project::{
-    analyze_project_balance, analyze_project_balance_with_thresholds, calculate_project_score,
+    analyze_crate_balance, analyze_project_balance, analyze_project_balance_with_thresholds,
+    calculate_module_scores, calculate_module_scores_with, calculate_project_score,
+    calculate_project_score_with, worst_couplings,
 };
 pub use rationale::{GradeDimension, GradeRationale, IssueTypeContribution};
-pub use score::{BalanceInterpretation, BalanceScore, IssueThresholds};
-pub use severity::Severity;
+pub use score::{
+    ArchitectureLayer, BalanceCombiner, BalanceFormula, BalanceInterpretation, BalanceScore,
+    CognitiveLoadWeights, GodModuleLimits, IssueThresholds,
+};
+pub use severity::{Severity, SeverityOverride};
 
 #[cfg(test)]
 use crate::metrics::coupling::CouplingMetrics;
@@ -50,11 +57,13 @@ use crate::volatility::Volatility;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{analyze_files, src_fixture};
     use std::collections::HashMap;
     use std::path::PathBuf;
 
     use crate::balance::coupling::{is_crate_root_facade, is_entrypoint_module};
     use crate::balance::grade::{build_grade_rationale, calculate_health_grade};
+    use crate::balance::score::BalanceBands;
     use crate::metrics::dimensions::Visibility;
     use crate::metrics::module::ModuleMetrics;
     use crate::volatility::TemporalCoupling;
@@ -83,12 +92,13 @@ mod tests {
         distance: Distance,
         volatility: Volatility,
     ) -> CouplingMetrics {
-        CouplingMetrics::new(
+        CouplingMetrics::with_visibility(
             "source::module".to_string(),
             "target::module".to_string(),
             strength,
             distance,
             volatility,
+            Visibility::Public,
         )
     }
 
@@ -148,6 +158,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_private_targets_across_modules_are_scored_at_effective_strength() {
+        let mut metrics = ProjectMetrics::new();
+        metrics.add_coupling(CouplingMetrics::with_visibility(
+            "orders".to_string(),
+            "billing".to_string(),
+            IntegrationStrength::Model,
+            Distance::DifferentModule,
+            Volatility::Low,
+            Visibility::Private,
+        ));
+        let coupling = &metrics.couplings[0];
+
+        let penalized = BalanceScore::calculate(coupling).score;
+        let raw = BalanceScore::calculate_with_formula(
+            coupling,
+            &BalanceBands::default(),
+            &BalanceFormula {
+                visibility_penalty: false,
+                ..BalanceFormula::default()
+            },
+        )
+        .score;
+        assert!(penalized < raw, "{} vs {}", penalized, raw);
+
+        // Stats and classification count the strength that was scored
+        let stats = metrics.calculate_dimension_stats();
+        assert_eq!(stats.strength_counts.functional, 1);
+        assert_eq!(stats.strength_counts.model, 0);
+        assert_eq!(
+            metrics
+                .calculate_dimension_stats_with(false)
+                .strength_counts
+                .model,
+            1
+        );
+    }
+
+    #[test]
+    fn test_private_functional_targets_across_modules_raise_intrusive_issues() {
+        let coupling = CouplingMetrics::with_visibility(
+            "orders".to_string(),
+            "billing".to_string(),
+            IntegrationStrength::Functional,
+            Distance::DifferentModule,
+            Volatility::Medium,
+            Visibility::Private,
+        );
+
+        let issues = identify_issues(&coupling);
+        assert!(
+            issues
+                .iter()
+                .any(|issue| issue.issue_type == IssueType::GlobalComplexity)
+        );
+
+        let mut thresholds = IssueThresholds::default();
+        thresholds.balance_formula.visibility_penalty = false;
+        assert!(
+            identify_issues_with_thresholds(&coupling, &thresholds)
+                .iter()
+                .all(|issue| issue.issue_type != IssueType::GlobalComplexity)
+        );
+    }
+
     #[test]
     fn test_identify_global_complexity() {
         // Note: DifferentCrate is now filtered out (external deps)
@@ -289,13 +364,13 @@ mod tests {
 
         let mut structural: HashMap<Severity, usize> = HashMap::new();
         structural.insert(Severity::Medium, mediums);
-        let degraded = calculate_health_grade(&structural, internal);
+        let degraded = calculate_health_grade(&structural, internal, &GradeMinimums::default());
         assert_ne!(degraded, HealthGrade::A);
 
         // Diagnostics are excluded before the grade call (see balance/project.rs);
         // an empty gradable map with enough couplings certifies A or S.
         let gradable: HashMap<Severity, usize> = HashMap::new();
-        let ungraded = calculate_health_grade(&gradable, internal);
+        let ungraded = calculate_health_grade(&gradable, internal, &GradeMinimums::default());
         assert!(matches!(ungraded, HealthGrade::A | HealthGrade::S));
 
         assert!(IssueType::AccidentalVolatility.is_diagnostic());
@@ -429,6 +504,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hidden_coupling_reported_over_contract_only_dependency() {
+        let mut metrics = ProjectMetrics::new();
+        metrics.add_module(ModuleMetrics::new(
+            PathBuf::from("src/pricing.rs"),
+            "pricing".to_string(),
+        ));
+        metrics.add_module(ModuleMetrics::new(
+            PathBuf::from("src/invoicing.rs"),
+            "invoicing".to_string(),
+        ));
+        metrics.add_coupling(CouplingMetrics::with_visibility(
+            "invoicing".to_string(),
+            "pricing".to_string(),
+            IntegrationStrength::Contract,
+            Distance::DifferentModule,
+            Volatility::Low,
+            Visibility::Public,
+        ));
+        metrics.temporal_couplings.push(TemporalCoupling {
+            file_a: "src/pricing.rs".to_string(),
+            file_b: "src/invoicing.rs".to_string(),
+            co_change_count: 6,
+            coupling_ratio: 0.75,
+        });
+
+        let report = analyze_project_balance(&metrics);
+
+        let issue = report
+            .issues
+            .iter()
+            .find(|issue| issue.issue_type == IssueType::HiddenCoupling)
+            .expect("a trait boundary that does not absorb change is still hidden coupling");
+        assert!(
+            issue
+                .description
+                .starts_with("Strong temporal co-change over only a Contract dependency")
+        );
+    }
+
     #[test]
     fn test_accidental_volatility_detected_for_stable_subdomain_churn() {
         let mut metrics = ProjectMetrics::new();
@@ -478,6 +593,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_weight_by_occurrences_counts_merged_couplings_per_usage() {
+        let mut metrics = ProjectMetrics::new();
+        let mut repeated = make_coupling(
+            IntegrationStrength::Contract,
+            Distance::DifferentModule,
+            Volatility::Low,
+        );
+        repeated.occurrences = 3;
+        metrics.add_coupling(repeated);
+        let mut once = make_coupling(
+            IntegrationStrength::Intrusive,
+            Distance::DifferentModule,
+            Volatility::High,
+        );
+        once.target = "other::module".to_string();
+        metrics.add_coupling(once);
+
+        let weighted = IssueThresholds {
+            balance_formula: BalanceFormula {
+                weight_by_occurrences: true,
+                ..BalanceFormula::default()
+            },
+            ..IssueThresholds::default()
+        };
+        let plain = analyze_project_balance(&metrics).average_score;
+        let by_usage = analyze_project_balance_with_thresholds(&metrics, &weighted).average_score;
+        assert!(by_usage > plain, "{} vs {}", by_usage, plain);
+        assert_eq!(
+            calculate_project_score_with(&metrics, &weighted.balance_formula),
+            by_usage
+        );
+    }
+
     #[test]
     fn test_supporting_subdomain_volatility_is_authoritative_for_balance_and_risk() {
         let mut metrics = ProjectMetrics::new();
@@ -677,11 +826,11 @@ mod tests {
 
     #[test]
     fn test_grade_rationale_mentions_data_limit_for_zero_and_low_couplings() {
-        let english_zero = build_grade_rationale(&[], 0, false);
+        let english_zero = build_grade_rationale(&[], 0, &GradeMinimums::default(), false);
         assert!(english_zero.summary.contains("0 internal couplings"));
         assert!(english_zero.summary.contains("grade capped at B"));
 
-        let english_low = build_grade_rationale(&[], 9, false);
+        let english_low = build_grade_rationale(&[], 9, &GradeMinimums::default(), false);
         assert!(
             english_low
                 .summary
@@ -689,11 +838,11 @@ mod tests {
         );
         assert!(english_low.summary.contains("grade capped at B"));
 
-        let japanese_zero = build_grade_rationale(&[], 0, true);
+        let japanese_zero = build_grade_rationale(&[], 0, &GradeMinimums::default(), true);
         assert!(japanese_zero.summary.contains("内部結合が 0 件"));
         assert!(japanese_zero.summary.contains("グレードは B が上限"));
 
-        let japanese_low = build_grade_rationale(&[], 9, true);
+        let japanese_low = build_grade_rationale(&[], 9, &GradeMinimums::default(), true);
         assert!(japanese_low.summary.contains("10 件未満"));
         assert!(japanese_low.summary.contains("グレードは B が上限"));
     }
@@ -703,41 +852,958 @@ mod tests {
         let mut issues = HashMap::new();
 
         // No issues with >= 20 couplings = S (over-optimized warning)
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::S);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::S
+        );
 
         // No issues with 10-19 couplings = A (well-balanced)
-        assert_eq!(calculate_health_grade(&issues, 15), HealthGrade::A);
+        assert_eq!(
+            calculate_health_grade(&issues, 15, &GradeMinimums::default()),
+            HealthGrade::A
+        );
 
         // No internal couplings = B (can't assess without data)
-        assert_eq!(calculate_health_grade(&issues, 0), HealthGrade::B);
+        assert_eq!(
+            calculate_health_grade(&issues, 0, &GradeMinimums::default()),
+            HealthGrade::B
+        );
 
         // Any High issue = C (structural issues)
         issues.insert(Severity::High, 1);
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::C);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::C
+        );
 
         // High density > 5% = D
         issues.clear();
         issues.insert(Severity::High, 6); // 6% of 100
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::D);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::D
+        );
 
         // 1 Critical issue = D
         issues.clear();
         issues.insert(Severity::Critical, 1);
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::D);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::D
+        );
 
         // 4+ Critical issues = F
         issues.clear();
         issues.insert(Severity::Critical, 4);
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::F);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::F
+        );
 
         // Medium issues > 25% = C
         issues.clear();
         issues.insert(Severity::Medium, 30); // 30% of 100
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::C);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::C
+        );
 
         // Medium issues > 5% but <= 25% = B
         issues.clear();
         issues.insert(Severity::Medium, 20); // 20% of 100
-        assert_eq!(calculate_health_grade(&issues, 100), HealthGrade::B);
+        assert_eq!(
+            calculate_health_grade(&issues, 100, &GradeMinimums::default()),
+            HealthGrade::B
+        );
+    }
+
+    #[test]
+    fn test_small_clean_crate_earns_a_only_under_lowered_minimum() {
+        let issues = HashMap::new();
+        let lowered = GradeMinimums {
+            min_couplings_for_a: 5,
+            ..GradeMinimums::default()
+        };
+
+        assert_eq!(
+            calculate_health_grade(&issues, 6, &GradeMinimums::default()),
+            HealthGrade::B
+        );
+        assert_eq!(calculate_health_grade(&issues, 6, &lowered), HealthGrade::A);
+
+        let summary = build_grade_rationale(&[], 6, &lowered, false).summary;
+        assert!(summary.contains("No surfaced coupling issues"), "{summary}");
+
+        // Uncoupled crates stay at B unless that rule is disabled too
+        assert_eq!(calculate_health_grade(&issues, 0, &lowered), HealthGrade::B);
+        let uncapped = GradeMinimums {
+            min_couplings_for_a: 0,
+            cap_uncoupled_at_b: false,
+        };
+        assert_eq!(
+            calculate_health_grade(&issues, 0, &uncapped),
+            HealthGrade::A
+        );
+    }
+
+    #[test]
+    fn test_public_field_exposure_counts_removable_cross_module_accesses() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod geo;\npub mod render;\npub mod physics;\n",
+            ),
+            ("geo.rs", "pub struct Circle {\n    pub radius: f64,\n}\n"),
+            (
+                "render.rs",
+                "use crate::geo::Circle;\npub fn draw(c: &Circle) -> f64 {\n    c.radius\n}\n",
+            ),
+            (
+                "physics.rs",
+                "use crate::geo::Circle;\npub fn mass() -> f64 {\n    let c = Circle { radius: 2.0 };\n    c.radius\n}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let issue = report
+            .issues
+            .iter()
+            .find(|issue| issue.issue_type == IssueType::PublicFieldExposure)
+            .expect("public field exposure issue");
+
+        assert!(
+            issue.description.contains(
+                "Making `radius` private removes 2 cross-module access(es) (callers: physics, render)"
+            ),
+            "{}",
+            issue.description
+        );
+    }
+
+    #[test]
+    fn test_public_enum_variant_data_is_exposure_unless_non_exhaustive() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod orders;\npub mod events;\npub mod billing;\n",
+            ),
+            ("orders.rs", "pub struct Order;\npub struct Refund;\n"),
+            (
+                "events.rs",
+                "use crate::orders::{Order, Refund};\n\
+                 pub enum Event {\n\
+                 \x20   Placed(Order),\n\
+                 \x20   Refunded { refund: Refund, amount: u64 },\n\
+                 \x20   Cancelled,\n\
+                 }\n\
+                 #[non_exhaustive]\n\
+                 pub enum Audit {\n\
+                 \x20   Placed(Order),\n\
+                 }\n\
+                 pub enum Status {\n\
+                 \x20   Open(u64),\n\
+                 }\n",
+            ),
+            (
+                "billing.rs",
+                "use crate::events::{Audit, Event, Status};\n\
+                 pub fn bill(event: &Event) -> u64 {\n\
+                 \x20   match event {\n\
+                 \x20       Event::Refunded { amount, .. } => *amount,\n\
+                 \x20       _ => 0,\n\
+                 \x20   }\n\
+                 }\n\
+                 pub fn audit(_audit: &Audit, _status: &Status) {}\n",
+            ),
+        ]);
+        let event = &metrics.modules["events"].type_definitions["Event"];
+        assert_eq!(
+            (event.variant_count, event.data_carrying_variant_count),
+            (3, 2)
+        );
+        assert_eq!(event.variant_data_types, ["Order", "Refund"]);
+        assert!(metrics.modules["events"].type_definitions["Audit"].is_non_exhaustive);
+
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let exposures: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::PublicFieldExposure)
+            .collect();
+
+        // Audit is non-exhaustive and Status only carries a primitive
+        assert_eq!(exposures.len(), 1, "{:?}", exposures);
+        assert_eq!(exposures[0].source, "events::Event");
+        assert!(
+            exposures[0]
+                .description
+                .contains("exposes 2 of 3 variant(s) carrying Order, Refund; 1 other module(s) depend on that shape (billing)"),
+            "{}",
+            exposures[0].description
+        );
+    }
+
+    #[test]
+    fn test_cross_module_glob_import_is_flagged() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod models;\npub mod glob_user;\npub mod explicit_user;\n",
+            ),
+            ("models.rs", "pub struct User;\npub struct Order;\n"),
+            (
+                "glob_user.rs",
+                "use crate::models::*;\npub fn load() -> User {\n    User\n}\n",
+            ),
+            (
+                "explicit_user.rs",
+                "use crate::models::User;\npub fn load() -> User {\n    User\n}\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let glob_issues: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::GlobImport)
+            .collect();
+
+        assert_eq!(glob_issues.len(), 1);
+        assert_eq!(glob_issues[0].source, "glob_user");
+        assert_eq!(glob_issues[0].target, "crate::models::*");
+        assert_eq!(glob_issues[0].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_type_with_many_generic_params_is_flagged_as_over_parameterized() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod repo;\n"),
+            (
+                "repo.rs",
+                "pub struct Repository<Db, Cache, Clock, Rng> {\n    db: Db,\n    cache: Cache,\n    clock: Clock,\n    rng: Rng,\n}\n\npub struct Wrapper<'a, T> {\n    inner: &'a T,\n}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let flagged: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::OverParameterizedType)
+            .collect();
+
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].source, "repo::Repository");
+        assert_eq!(flagged[0].target, "4 generic params");
+    }
+
+    #[test]
+    fn test_module_mixing_async_and_public_sync_functions_is_flagged() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod client;\npub mod worker;\n"),
+            (
+                "client.rs",
+                "pub struct Client;\n\
+                 \n\
+                 impl Client {\n\
+                 \x20   pub async fn fetch(&self) -> u32 { self.send().await }\n\
+                 \x20   async fn send(&self) -> u32 { 1 }\n\
+                 \x20   pub fn fetch_blocking(&self) -> u32 { 0 }\n\
+                 }\n\
+                 \n\
+                 pub async fn connect() -> Client { Client }\n\
+                 pub fn connect_blocking() -> Client { Client }\n\
+                 pub fn timeout_secs() -> u64 { 30 }\n\
+                 \n\
+                 #[cfg(test)]\n\
+                 mod tests {\n\
+                 \x20   #[tokio::test]\n\
+                 \x20   async fn fetches() {}\n\
+                 }\n",
+            ),
+            (
+                "worker.rs",
+                "pub async fn run() {}\npub async fn stop() {}\nasync fn tick() {}\npub fn name() -> &'static str { \"w\" }\n",
+            ),
+        ]);
+        let client = &metrics.modules["client"];
+        assert_eq!(client.async_function_count, 3);
+        assert_eq!(client.sync_public_function_count, 3);
+        assert_eq!(client.await_count, 1);
+        assert!(client.function_definitions["connect"].is_async);
+        assert!(!client.function_definitions["connect_blocking"].is_async);
+
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let mixed: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::MixedAsyncBoundary)
+            .collect();
+
+        // worker is async-heavy but has a single sync public function
+        assert_eq!(mixed.len(), 1, "{:#?}", mixed);
+        assert_eq!(mixed[0].source, "client");
+        assert_eq!(mixed[0].target, "3 async, 3 sync public functions");
+        assert_eq!(mixed[0].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_unsafe_code_behind_a_public_api_is_an_advisory_issue() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod buffer;\nmod raw;\n"),
+            (
+                "buffer.rs",
+                "pub struct Buffer { ptr: *mut u8 }\n\
+                 \n\
+                 impl Buffer {\n\
+                 \x20   pub fn get(&self, i: usize) -> u8 { unsafe { *self.ptr.add(i) } }\n\
+                 \x20   pub unsafe fn get_unchecked(&self, i: usize) -> u8 { unsafe { *self.ptr.add(i) } }\n\
+                 }\n\
+                 \n\
+                 pub trait RawAccess {\n\
+                 \x20   unsafe fn raw(&self) -> *mut u8;\n\
+                 }\n\
+                 \n\
+                 #[cfg(test)]\n\
+                 mod tests {\n\
+                 \x20   #[test]\n\
+                 \x20   fn reads() { unsafe {} }\n\
+                 }\n",
+            ),
+            (
+                "raw.rs",
+                "fn a() { unsafe {} }\nfn b() { unsafe {} }\nunsafe fn c() {}\n\
+                 #[test]\nfn d() { unsafe {} }\n",
+            ),
+        ]);
+        let buffer = &metrics.modules["buffer"];
+        assert_eq!(buffer.unsafe_block_count, 2);
+        assert_eq!(buffer.unsafe_fn_count, 2);
+        // A #[test] function outside `mod tests` is test code too
+        assert_eq!(metrics.modules["raw"].unsafe_block_count, 2);
+
+        let report = analyze_project_balance(&metrics);
+        let flagged: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::UnsafeSurface)
+            .collect();
+
+        // raw has as much unsafe code but no public API
+        assert_eq!(flagged.len(), 1, "{:#?}", flagged);
+        assert_eq!(flagged[0].source, "buffer");
+        assert_eq!(flagged[0].target, "2 unsafe blocks, 2 unsafe fns");
+        assert!(IssueType::UnsafeSurface.is_diagnostic());
+    }
+
+    #[test]
+    fn test_structs_with_the_same_fields_in_different_modules_are_duplicated() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod api;\npub mod db;\npub mod wire;\n"),
+            (
+                "api.rs",
+                "pub struct UserDto { pub id: u64, pub name: String, pub tags: Vec<String> }\n\
+                 pub struct Point { x: i32, y: i32 }\n",
+            ),
+            (
+                "db.rs",
+                "pub struct UserRow { tags: Vec < String >, id: u64, name: String }\n\
+                 pub struct Point { x: i32, y: i32 }\n",
+            ),
+            (
+                "wire.rs",
+                "pub struct User { id: u64, name: String, tags: Vec<&'static str> }\n",
+            ),
+        ]);
+        assert_eq!(
+            metrics.modules["api"].type_definitions["UserDto"].fields[2],
+            ("tags".to_string(), "Vec<String>".to_string())
+        );
+
+        let duplicated = |thresholds: &IssueThresholds| -> Vec<CouplingIssue> {
+            analyze_project_balance_with_thresholds(&metrics, thresholds)
+                .issues
+                .into_iter()
+                .filter(|issue| issue.issue_type == IssueType::DuplicatedStructure)
+                .collect()
+        };
+
+        // Field order and spacing do not matter; a different field type does, and
+        // Point has too few fields to count
+        let flagged = duplicated(&IssueThresholds::default());
+        assert_eq!(flagged.len(), 1, "{:#?}", flagged);
+        assert_eq!(flagged[0].source, "api");
+        assert_eq!(flagged[0].target, "db::UserRow");
+        assert!(
+            flagged[0]
+                .description
+                .starts_with("api::UserDto has the same 3 fields")
+        );
+        assert!(IssueType::DuplicatedStructure.is_diagnostic());
+
+        let allowlisted = IssueThresholds {
+            duplicated_structure_allowlist: vec!["db::UserRow".to_string()],
+            ..IssueThresholds::default()
+        };
+        assert!(duplicated(&allowlisted).is_empty());
+
+        let two_fields = IssueThresholds {
+            min_duplicated_fields: 2,
+            ..IssueThresholds::default()
+        };
+        assert_eq!(duplicated(&two_fields).len(), 2);
+        let disabled = IssueThresholds {
+            min_duplicated_fields: 0,
+            ..IssueThresholds::default()
+        };
+        assert!(duplicated(&disabled).is_empty());
+    }
+
+    #[test]
+    fn test_deref_to_a_rich_target_is_flagged() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod book;\npub mod desk;\n"),
+            (
+                "book.rs",
+                "use std::ops::{Deref, DerefMut};\n\
+                 pub struct Order;\n\
+                 pub struct OrderBook { orders: Vec<Order> }\n\
+                 impl DerefMut for OrderBook {\n\
+                 \x20   fn deref_mut(&mut self) -> &mut Vec<Order> { &mut self.orders }\n\
+                 }\n\
+                 impl Deref for OrderBook {\n\
+                 \x20   type Target = Vec<Order>;\n\
+                 \x20   fn deref(&self) -> &Vec<Order> { &self.orders }\n\
+                 }\n\
+                 pub struct Price(u64);\n\
+                 impl Deref for Price {\n\
+                 \x20   type Target = u64;\n\
+                 \x20   fn deref(&self) -> &u64 { &self.0 }\n\
+                 }\n\
+                 pub struct Guard<T>(T);\n\
+                 impl<T> std::ops::Deref for Guard<T> {\n\
+                 \x20   type Target = T;\n\
+                 \x20   fn deref(&self) -> &T { &self.0 }\n\
+                 }\n\
+                 pub struct Symbol(Box<str>);\n\
+                 impl Deref for Symbol {\n\
+                 \x20   type Target = str;\n\
+                 \x20   fn deref(&self) -> &str { &self.0 }\n\
+                 }\n",
+            ),
+            (
+                "desk.rs",
+                "use crate::book::OrderBook;\npub fn depth(book: &OrderBook) -> usize { book.len() }\n",
+            ),
+        ]);
+        let report = analyze_project_balance(&metrics);
+        let flagged: Vec<&CouplingIssue> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::DerefAbuse)
+            .collect();
+
+        // Newtypes over primitives and generic wrappers are left alone
+        assert_eq!(flagged.len(), 1, "{:#?}", flagged);
+        assert_eq!(flagged[0].source, "book::OrderBook");
+        assert_eq!(flagged[0].target, "Vec<Order>");
+        assert!(
+            flagged[0]
+                .description
+                .starts_with("Type OrderBook implements Deref and DerefMut to Vec<Order>"),
+            "{}",
+            flagged[0].description
+        );
+        assert!(flagged[0].description.contains("(desk)"));
+        assert!(IssueType::DerefAbuse.is_diagnostic());
+    }
+
+    #[test]
+    fn test_module_with_large_interface_and_little_implementation_is_shallow() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod engine;\npub mod settings;\n"),
+            (
+                "settings.rs",
+                "pub struct Settings { pub name: String, pub retries: u32 }\n\
+                 \n\
+                 pub fn name(s: &Settings) -> &str { &s.name }\n\
+                 pub fn retries(s: &Settings) -> u32 { s.retries }\n",
+            ),
+            (
+                "engine.rs",
+                "pub fn run(input: &str) -> usize { plan(input) + execute(input) }\n\
+                 \n\
+                 fn plan(input: &str) -> usize { parse(input).len() + optimize(input) }\n\
+                 fn parse(input: &str) -> Vec<&str> { input.split(',').collect() }\n\
+                 fn optimize(input: &str) -> usize { parse(input).len() * 2 }\n\
+                 fn execute(input: &str) -> usize { plan(input) + finish(input) }\n\
+                 fn finish(input: &str) -> usize { optimize(input) }\n",
+            ),
+        ]);
+        // 2 public functions + 1 public type + 2 public fields
+        assert_eq!(metrics.modules["settings"].interface_size(), 5);
+        assert_eq!(metrics.modules["engine"].interface_size(), 1);
+
+        let shallow = |thresholds: IssueThresholds| {
+            analyze_project_balance_with_thresholds(&metrics, &thresholds)
+                .issues
+                .into_iter()
+                .filter(|issue| issue.issue_type == IssueType::ShallowModule)
+                .map(|issue| issue.source)
+                .collect::<Vec<_>>()
+        };
+
+        // Both interfaces are below the default minimum size.
+        assert!(
+            shallow(IssueThresholds {
+                strict_mode: false,
+                ..IssueThresholds::default()
+            })
+            .is_empty()
+        );
+        assert_eq!(
+            shallow(IssueThresholds {
+                strict_mode: false,
+                min_shallow_interface: 1,
+                ..IssueThresholds::default()
+            }),
+            ["settings"]
+        );
+    }
+
+    #[test]
+    fn test_module_over_the_cognitive_load_budget_names_its_dominant_factor() {
+        let mut api = String::from("use serde::Serialize;\nuse regex::Regex;\n");
+        for i in 0..12 {
+            api.push_str(&format!(
+                "pub fn op{i}(a: u32, b: u32, c: u32, d: u32) -> u32 {{ a + b + c + d }}\n"
+            ));
+        }
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod api;\npub mod util;\n"),
+            ("api.rs", &api),
+            ("util.rs", "pub fn one() -> u32 { 1 }\n"),
+        ]);
+        let cognitive = |thresholds: IssueThresholds| {
+            analyze_project_balance_with_thresholds(&metrics, &thresholds)
+                .issues
+                .into_iter()
+                .filter(|issue| issue.issue_type == IssueType::HighCognitiveLoad)
+                .collect::<Vec<_>>()
+        };
+
+        // 12 public fns + 2 crates * 2 + 4 params * 4 = 32, under the default budget
+        assert!(cognitive(IssueThresholds::default()).is_empty());
+
+        let issues = cognitive(IssueThresholds {
+            max_cognitive_load: 20.0,
+            ..IssueThresholds::default()
+        });
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "api");
+        assert_eq!(issues[0].severity, Severity::Medium);
+        assert!(
+            issues[0]
+                .target
+                .starts_with("4.0 params per fn, 12 public fns"),
+            "{}",
+            issues[0].target
+        );
+
+        // Weights are tunable: external crates can dominate instead
+        let issues = cognitive(IssueThresholds {
+            max_cognitive_load: 20.0,
+            cognitive_load_weights: CognitiveLoadWeights {
+                external_crates: 20.0,
+                ..CognitiveLoadWeights::default()
+            },
+            ..IssueThresholds::default()
+        });
+        assert_eq!(issues[0].severity, Severity::High);
+        assert!(issues[0].target.starts_with("2 external crates"));
+    }
+
+    #[test]
+    fn test_god_module_limits_follow_overrides_and_allow_markers() {
+        let functions = (0..40)
+            .map(|i| format!("pub fn f{i}() {{}}\n"))
+            .collect::<String>();
+        let tables = format!("//! Lookup tables.\n// coupling:allow god-module\n\n{functions}");
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod generated;\npub mod routes;\npub mod tables;\n",
+            ),
+            ("generated.rs", "pub mod client;\n"),
+            ("generated/client.rs", &functions),
+            ("routes.rs", &functions),
+            ("tables.rs", &tables),
+        ]);
+        let god_modules = |thresholds: IssueThresholds| {
+            let mut sources: Vec<String> =
+                analyze_project_balance_with_thresholds(&metrics, &thresholds)
+                    .issues
+                    .into_iter()
+                    .filter(|issue| issue.issue_type == IssueType::GodModule)
+                    .map(|issue| issue.source)
+                    .collect();
+            sources.sort();
+            sources
+        };
+
+        // The marked file is never flagged
+        assert_eq!(
+            god_modules(IssueThresholds::default()),
+            ["generated::client", "routes"]
+        );
+
+        // An override on a parent path covers its submodules
+        let overridden = IssueThresholds {
+            god_module_overrides: [(
+                "crate::generated".to_string(),
+                GodModuleLimits {
+                    max_functions: Some(500),
+                    ..GodModuleLimits::default()
+                },
+            )]
+            .into(),
+            ..IssueThresholds::default()
+        };
+        assert_eq!(
+            overridden.god_module_limits("generated::client"),
+            (500, 15, 20)
+        );
+        assert_eq!(
+            overridden.god_module_limits("generated_other"),
+            (30, 15, 20)
+        );
+        assert_eq!(god_modules(overridden), ["routes"]);
+    }
+
+    #[test]
+    fn test_inline_markers_suppress_matching_issues_and_are_counted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("billing.rs");
+        std::fs::write(
+            &file,
+            "use crate::orders::Order;\n\n\
+             pub fn charge(o: &Order) -> u64 {\n\
+             \x20   // coupling:allow global-complexity(crate::orders), god-module\n\
+             \x20   o.total // coupling:allow Inappropriate-Intimacy(crate::other)\n\
+             }\n",
+        )
+        .unwrap();
+        let billing = crate::analyzer::analyze_rust_file_full(&file)
+            .unwrap()
+            .metrics;
+        let markers: Vec<(&str, Option<&str>, usize)> = billing
+            .suppressions
+            .iter()
+            .map(|s| (s.rule.as_str(), s.target.as_deref(), s.line))
+            .collect();
+        assert_eq!(
+            markers,
+            [
+                ("global-complexity", Some("crate::orders"), 4),
+                ("god-module", None, 4),
+                ("Inappropriate-Intimacy", Some("crate::other"), 5),
+            ]
+        );
+
+        let intrusive_at = |target: &str, line: usize| {
+            let mut coupling = CouplingMetrics::new(
+                "billing".to_string(),
+                target.to_string(),
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::Medium,
+            );
+            coupling.location.line = line;
+            coupling
+        };
+        let mut metrics = ProjectMetrics::new();
+        metrics.add_module(ModuleMetrics {
+            name: "billing".to_string(),
+            ..billing
+        });
+        metrics.add_module(ModuleMetrics::new(
+            PathBuf::from("src/orders.rs"),
+            "orders".to_string(),
+        ));
+        metrics.add_module(ModuleMetrics::new(
+            PathBuf::from("src/ledger.rs"),
+            "ledger".to_string(),
+        ));
+        // Covered: same rule and target, coupling on the line below the marker
+        metrics.add_coupling(intrusive_at("crate::orders::Order", 5));
+        // Not covered: too far from the marker, and a target the marker does not name
+        metrics.add_coupling(intrusive_at("crate::orders::Invoice", 9));
+        metrics.add_coupling(intrusive_at("crate::ledger::Entry", 5));
+
+        let report = analyze_project_balance_with_thresholds(&metrics, &IssueThresholds::default());
+        let mut targets: Vec<&str> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::GlobalComplexity)
+            .map(|issue| issue.target.as_str())
+            .collect();
+        targets.sort();
+        assert_eq!(targets, ["crate::ledger::Entry", "crate::orders::Invoice"]);
+        assert_eq!(report.inline_suppressions, 1);
+    }
+
+    #[test]
+    fn test_modules_with_disjoint_type_clusters_are_low_cohesion() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod shop;\n"),
+            (
+                "shop.rs",
+                "pub struct Order { total: u64 }\n\
+                 pub struct LineItem { price: u64 }\n\
+                 pub fn order_total(o: &Order) -> u64 { o.total }\n\
+                 pub fn order_double(o: &Order) -> u64 { o.total * 2 }\n\
+                 pub fn order_zero(o: &mut Order) { o.total = 0 }\n\
+                 pub fn item_price(i: &LineItem) -> u64 { i.price }\n\
+                 pub fn item_double(i: &LineItem) -> u64 { i.price * 2 }\n\
+                 pub fn item_zero(i: &mut LineItem) { i.price = 0 }\n",
+            ),
+        ]);
+        let low_cohesion = |thresholds: &IssueThresholds| {
+            analyze_project_balance_with_thresholds(&metrics, thresholds)
+                .issues
+                .into_iter()
+                .find(|issue| issue.issue_type == IssueType::LowCohesion)
+        };
+
+        let issue = low_cohesion(&IssueThresholds::default()).unwrap();
+        assert_eq!(issue.source, "shop");
+        assert_eq!(issue.target, "2 type clusters");
+        assert_eq!(
+            issue.refactoring.to_string(),
+            "Split into modules: shop::line_item, shop::order"
+        );
+
+        // Two equal halves are exactly 0.5 cohesive
+        let lenient = IssueThresholds {
+            min_cohesion: 0.5,
+            ..IssueThresholds::default()
+        };
+        assert!(low_cohesion(&lenient).is_none());
+    }
+
+    #[test]
+    fn test_same_field_access_from_four_modules_is_spread_coupling() {
+        // Only three modules read `port`, below the default spread threshold
+        let with_port = "use crate::config::Config;\n\
+                         pub fn connect(cfg: &Config) -> usize { cfg.database_url.len() + cfg.port as usize }\n";
+        let url_only = "use crate::config::Config;\n\
+                        pub fn connect(cfg: &Config) -> usize { cfg.database_url.len() }\n";
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod config;\npub mod api;\npub mod jobs;\npub mod cli;\npub mod migrate;\n",
+            ),
+            (
+                "config.rs",
+                "pub struct Config {\n    pub database_url: String,\n    pub port: u16,\n}\n",
+            ),
+            ("api.rs", with_port),
+            ("jobs.rs", with_port),
+            ("cli.rs", with_port),
+            ("migrate.rs", url_only),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let spread: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::SpreadCoupling)
+            .collect();
+
+        assert_eq!(spread.len(), 1, "{:#?}", spread);
+        assert_eq!(spread[0].source, "config::Config.database_url");
+        assert_eq!(spread[0].target, "4 modules");
+        assert!(
+            spread[0]
+                .description
+                .contains("(api, cli, jobs, migrate) via cfg.database_url"),
+            "{}",
+            spread[0].description
+        );
+    }
+
+    #[test]
+    fn test_coupling_into_deprecated_internal_module_is_flagged() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod handlers;\npub mod legacy_auth;\npub mod users;\n",
+            ),
+            (
+                "legacy_auth.rs",
+                "pub struct Session {\n    pub user: String,\n}\n",
+            ),
+            ("users.rs", "pub struct User;\n"),
+            (
+                "handlers.rs",
+                "use crate::legacy_auth::Session;\nuse crate::users::User;\n\npub fn login(s: Session, _u: User) -> String {\n    s.user\n}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            deprecated_internal: vec!["legacy_auth".to_string(), "old_db".to_string()],
+            deprecated_internal_severity: Severity::High,
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let deprecated: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::DeprecatedInternalCoupling)
+            .collect();
+
+        assert_eq!(deprecated.len(), 1, "{:?}", deprecated);
+        assert!(deprecated[0].source.ends_with("handlers"));
+        assert_eq!(deprecated[0].target, "legacy_auth");
+        assert_eq!(deprecated[0].severity, Severity::High);
+
+        let report = analyze_project_balance_with_thresholds(&metrics, &IssueThresholds::default());
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.issue_type == IssueType::DeprecatedInternalCoupling)
+        );
+    }
+
+    #[test]
+    fn test_coupling_into_an_outer_layer_is_a_critical_layer_violation() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod application;\npub mod domain;\npub mod infrastructure;\n",
+            ),
+            (
+                "domain.rs",
+                "use crate::infrastructure::Db;\n\npub struct Order;\n\npub fn save(order: Order, db: Db) {}\n",
+            ),
+            (
+                "application.rs",
+                "use crate::domain::Order;\n\npub fn place(order: Order) {}\n",
+            ),
+            (
+                "infrastructure.rs",
+                "use crate::domain::Order;\n\npub struct Db;\n\npub fn load() -> Option<Order> { None }\n",
+            ),
+        ]);
+        let layer = |name: &str| ArchitectureLayer {
+            name: name.to_string(),
+            modules: vec![name.to_string()],
+        };
+        let thresholds = IssueThresholds {
+            layers: vec![
+                layer("domain"),
+                layer("application"),
+                layer("infrastructure"),
+            ],
+            ..IssueThresholds::default()
+        };
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let violations: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.issue_type == IssueType::LayerViolation)
+            .collect();
+
+        // Only domain -> infrastructure points outwards
+        assert_eq!(violations.len(), 1, "{:?}", violations);
+        assert!(violations[0].source.ends_with("domain"));
+        assert_eq!(violations[0].target, "infrastructure");
+        assert_eq!(violations[0].severity, Severity::Critical);
+        assert!(
+            violations[0]
+                .description
+                .contains("in outer layer `infrastructure`"),
+            "{}",
+            violations[0].description
+        );
+
+        let report = analyze_project_balance_with_thresholds(&metrics, &IssueThresholds::default());
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.issue_type == IssueType::LayerViolation)
+        );
+    }
+
+    #[test]
+    fn test_allowlisted_prelude_module_skips_afferent_check_but_keeps_efferent() {
+        let user = |name: &str| {
+            format!(
+                "use crate::prelude::Shared;\n\npub struct {name};\n\npub fn use_shared(_s: &Shared) {{}}\n"
+            )
+        };
+        let (_tmp, src) = src_fixture(&[
+            (
+                "lib.rs",
+                "pub mod prelude;\npub mod a;\npub mod b;\npub mod c;\n",
+            ),
+            (
+                "prelude.rs",
+                "use crate::a::A;\nuse crate::b::B;\n\npub struct Shared {\n    pub a: A,\n    pub b: B,\n}\n",
+            ),
+            ("a.rs", &user("A")),
+            ("b.rs", &user("B")),
+            ("c.rs", &user("C")),
+        ]);
+
+        let thresholds = IssueThresholds {
+            max_dependencies: 1,
+            max_dependents: 2,
+            ..IssueThresholds::default()
+        };
+        let flagged = |config: &crate::config::CompiledConfig| {
+            let metrics = crate::analyzer::analyze_workspace_with_config(&src, config).unwrap();
+            let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+            let has = |issue_type: IssueType| {
+                report.issues.iter().any(|issue| {
+                    issue.issue_type == issue_type
+                        && (issue.source.ends_with("prelude") || issue.target.ends_with("prelude"))
+                })
+            };
+            (
+                has(IssueType::HighAfferentCoupling),
+                has(IssueType::HighEfferentCoupling),
+            )
+        };
+
+        assert_eq!(
+            flagged(&crate::config::CompiledConfig::empty()),
+            (true, true)
+        );
+
+        let config: crate::config::CouplingConfig =
+            toml::from_str("[allowlist]\nprelude_modules = [\"crate::prelude\"]\n").unwrap();
+        let config = crate::config::CompiledConfig::from_config(config).unwrap();
+        assert_eq!(flagged(&config), (false, true));
     }
 }
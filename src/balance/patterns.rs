@@ -1,6 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use crate::analyzer::ItemDepType;
+use crate::metrics::cohesion::{LOW_COHESION_MIN_FUNCTIONS, ModuleCohesion};
 use crate::metrics::dimensions::{Distance, Subdomain, Visibility};
+use crate::metrics::module::ModuleMetrics;
 use crate::metrics::project::ProjectMetrics;
 
 use super::action::RefactoringAction;
@@ -8,7 +11,7 @@ use super::coupling::is_entrypoint_module;
 use super::issue::CouplingIssue;
 use super::issue_type::IssueType;
 use super::labels::extract_type_name;
-use super::score::IssueThresholds;
+use super::score::{CognitiveLoadWeights, IssueThresholds};
 use super::severity::Severity;
 use super::subdomain::build_target_subdomain_map;
 
@@ -21,18 +24,7 @@ pub(crate) fn analyze_module_coupling(
 
     // Count outgoing (efferent) and incoming (afferent) couplings per module
     // Only count INTERNAL dependencies (within workspace), not external crates
-    let mut efferent: HashMap<&str, usize> = HashMap::new();
-    let mut afferent: HashMap<&str, usize> = HashMap::new();
-
-    for coupling in &metrics.couplings {
-        // Skip external crate dependencies entirely
-        if coupling.distance == Distance::DifferentCrate {
-            continue;
-        }
-
-        *efferent.entry(&coupling.source).or_insert(0) += 1;
-        *afferent.entry(&coupling.target).or_insert(0) += 1;
-    }
+    let (efferent, afferent) = metrics.internal_coupling_counts();
 
     // Check for high efferent coupling (depends on too many things)
     for (module, count) in &efferent {
@@ -87,8 +79,9 @@ pub(crate) fn analyze_module_coupling(
 
     // Check for high afferent coupling (too many things depend on this)
     // Only internal modules are counted (external crates already filtered above)
+    // Prelude-like modules are shared by design; only their efferent side is checked.
     for (module, count) in &afferent {
-        if *count > thresholds.max_dependents {
+        if *count > thresholds.max_dependents && !metrics.is_prelude_module(module) {
             // A widely-depended-on module is only risky if it is VOLATILE; a STABLE
             // central abstraction with many dependents is good design (the balance
             // rule: strong + far + low volatility = Acceptable). Scale severity by
@@ -153,6 +146,255 @@ pub(crate) fn analyze_module_coupling(
     issues
 }
 
+/// `// coupling:allow` rule name that opts a file out of God Module detection
+pub(crate) const GOD_MODULE_RULE: &str = "god-module";
+
+/// Suggested fix for cross-module glob imports
+pub(crate) const GLOB_IMPORT_ACTION: &str = "Replace glob imports with explicit imports";
+
+/// Suggested fix for public enums whose variant data crosses module boundaries
+pub(crate) const ENUM_VARIANT_EXPOSURE_ACTION: &str =
+    "Mark the enum #[non_exhaustive] or carry variant data in types with private fields";
+
+/// Suggested fix for unsafe code spread behind a public API
+pub(crate) const UNSAFE_SURFACE_ACTION: &str = "Confine unsafe code to a small private module behind a safe API and document each SAFETY invariant";
+
+/// Suggested fix for `Deref` impls that leak a richer target's API
+pub(crate) const DEREF_ABUSE_ACTION: &str = "Expose the operations callers need as methods, or an explicit accessor such as as_inner(), instead of implementing Deref";
+
+/// Suggested fix for types with too many generic parameters
+pub(crate) const OVER_PARAMETERIZED_TYPE_ACTION: &str =
+    "Group type parameters behind a trait with associated types or a configuration type";
+
+/// Suggested fix for the same member being accessed from many modules
+pub(crate) const SPREAD_COUPLING_ACTION: &str = "Route access through a single accessor or facade instead of reaching into the member everywhere";
+
+/// Suggested fix for modules whose interface is large relative to their implementation
+pub(crate) const SHALLOW_MODULE_ACTION: &str =
+    "Consolidate the public API into fewer, deeper functions and make helpers private";
+
+/// Suggested fix for modules that take too much knowledge to work with
+pub(crate) const COGNITIVE_LOAD_ACTION: &str = "Shrink what callers must know: make helpers private, group parameters into types, and wrap external crates behind local interfaces";
+
+/// Suggested fix for functions that only forward to another call
+pub(crate) const PASS_THROUGH_ACTION: &str =
+    "Call the delegate directly, or give the wrapper a responsibility of its own";
+
+/// Suggested fix for couplings into a deprecated internal module
+pub(crate) const DEPRECATED_INTERNAL_ACTION: &str =
+    "Migrate this dependency off the deprecated module to its replacement";
+
+/// Flag couplings into modules listed in `[policy] deprecated_internal`.
+///
+/// One issue per (source, deprecated module) pair; couplings inside a
+/// deprecated module (or between two of them) are not flagged.
+pub(crate) fn analyze_deprecated_internal_coupling(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
+    if thresholds.deprecated_internal.is_empty() {
+        return Vec::new();
+    }
+
+    let mut counts: HashMap<(&str, &str), usize> = HashMap::new();
+    for coupling in &metrics.couplings {
+        if coupling.distance == Distance::DifferentCrate {
+            continue;
+        }
+        let Some(deprecated) = matching_module(&coupling.target, &thresholds.deprecated_internal)
+        else {
+            continue;
+        };
+        if matching_module(&coupling.source, &thresholds.deprecated_internal).is_some() {
+            continue;
+        }
+        *counts
+            .entry((coupling.source.as_str(), deprecated))
+            .or_default() += 1;
+    }
+
+    let mut pairs: Vec<_> = counts.into_iter().collect();
+    pairs.sort_unstable();
+    pairs
+        .into_iter()
+        .map(|((source, deprecated), count)| CouplingIssue {
+            issue_type: IssueType::DeprecatedInternalCoupling,
+            severity: thresholds.deprecated_internal_severity,
+            source: source.to_string(),
+            target: deprecated.to_string(),
+            description: format!(
+                "{} depends on deprecated module `{}` ({} coupling{})",
+                source,
+                deprecated,
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            refactoring: RefactoringAction::General {
+                action: DEPRECATED_INTERNAL_ACTION.to_string(),
+            },
+            balance_score: 0.5,
+        })
+        .collect()
+}
+
+/// The configured module that `path` lies in, if any.
+///
+/// Matches whole `::` segments anywhere in the path, so `old_db` matches
+/// `crate::old_db::Pool` and `app::old_db` but not `old_dbx`.
+fn matching_module<'a>(path: &str, modules: &'a [String]) -> Option<&'a str> {
+    let segments: Vec<&str> = path.split("::").collect();
+    modules
+        .iter()
+        .find(|module| {
+            let wanted: Vec<&str> = module.split("::").collect();
+            segments
+                .windows(wanted.len())
+                .any(|window| window == wanted)
+        })
+        .map(String::as_str)
+}
+
+/// Suggested fix for couplings from an inner architecture layer into an outer one
+pub(crate) const LAYER_VIOLATION_ACTION: &str =
+    "Invert the dependency: declare a trait in the inner layer and implement it in the outer one";
+
+/// Flag couplings that go against the `[[layers]]` order.
+///
+/// Layers are listed innermost first and may depend on themselves and the
+/// layers before them. A path belongs to the first layer with a matching
+/// module. One issue per (source, outer-layer module) pair, like
+/// `analyze_deprecated_internal_coupling`.
+pub(crate) fn analyze_layer_violations(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
+    if thresholds.layers.is_empty() {
+        return Vec::new();
+    }
+
+    let layer_of = |path: &str| {
+        thresholds
+            .layers
+            .iter()
+            .enumerate()
+            .find_map(|(index, layer)| {
+                matching_module(path, &layer.modules).map(|module| (index, module))
+            })
+    };
+
+    let mut counts: HashMap<(&str, usize, usize, &str), usize> = HashMap::new();
+    for coupling in &metrics.couplings {
+        let (Some((source_layer, _)), Some((target_layer, module))) =
+            (layer_of(&coupling.source), layer_of(&coupling.target))
+        else {
+            continue;
+        };
+        if target_layer > source_layer {
+            *counts
+                .entry((coupling.source.as_str(), source_layer, target_layer, module))
+                .or_default() += 1;
+        }
+    }
+
+    let mut violations: Vec<_> = counts.into_iter().collect();
+    violations.sort_unstable();
+    violations
+        .into_iter()
+        .map(
+            |((source, source_layer, target_layer, module), count)| CouplingIssue {
+                issue_type: IssueType::LayerViolation,
+                severity: Severity::Critical,
+                source: source.to_string(),
+                target: module.to_string(),
+                description: format!(
+                    "{} in layer `{}` depends on `{}` in outer layer `{}` ({} coupling{})",
+                    source,
+                    thresholds.layers[source_layer].name,
+                    module,
+                    thresholds.layers[target_layer].name,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+                refactoring: RefactoringAction::General {
+                    action: LAYER_VIOLATION_ACTION.to_string(),
+                },
+                balance_score: 0.5,
+            },
+        )
+        .collect()
+}
+
+/// Suggested fix for structs duplicated across modules
+pub(crate) const DUPLICATED_STRUCTURE_ACTION: &str = "Consolidate the copies into one shared type, or list them in [allowlist] duplicated_structures if they are meant to evolve separately";
+
+/// Flag structs in different modules with the same named fields (advisory).
+///
+/// Structs match when their `(name, type)` field sets are equal, regardless of
+/// field order; types compare as written, so `String` and `std::string::String`
+/// differ. One issue per group, reported on the first module in path order.
+/// Allowlisted modules and types are left out before grouping.
+pub(crate) fn analyze_duplicated_structures(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
+    if thresholds.min_duplicated_fields == 0 {
+        return Vec::new();
+    }
+
+    // Sorted `(name, type)` fields -> (module, type name) of each copy
+    type Shape<'a> = Vec<&'a (String, String)>;
+    let mut shapes: BTreeMap<Shape, Vec<(&str, &str)>> = BTreeMap::new();
+    for (module_name, module) in &metrics.modules {
+        for type_def in module.type_definitions.values() {
+            if type_def.is_trait || type_def.fields.len() < thresholds.min_duplicated_fields {
+                continue;
+            }
+            let path = format!("{}::{}", module_name, type_def.name);
+            if matching_module(&path, &thresholds.duplicated_structure_allowlist).is_some() {
+                continue;
+            }
+            let mut shape: Vec<_> = type_def.fields.iter().collect();
+            shape.sort_unstable();
+            shapes
+                .entry(shape)
+                .or_default()
+                .push((module_name.as_str(), type_def.name.as_str()));
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (shape, mut copies) in shapes {
+        copies.sort_unstable();
+        let modules: HashSet<&str> = copies.iter().map(|(module, _)| *module).collect();
+        if modules.len() < 2 {
+            continue;
+        }
+        let (module_name, type_name) = copies[0];
+        let others: Vec<String> = copies[1..]
+            .iter()
+            .map(|(module, name)| format!("{}::{}", module, name))
+            .collect();
+        issues.push(CouplingIssue {
+            issue_type: IssueType::DuplicatedStructure,
+            severity: Severity::Medium,
+            source: module_name.to_string(),
+            target: others.join(", "),
+            description: format!(
+                "{}::{} has the same {} fields as {}. Every change to the shape has to be repeated in each copy.",
+                module_name,
+                type_name,
+                shape.len(),
+                others.join(", ")
+            ),
+            refactoring: RefactoringAction::General {
+                action: DUPLICATED_STRUCTURE_ACTION.to_string(),
+            },
+            balance_score: 0.7,
+        });
+    }
+    issues
+}
+
 /// Analyze Rust-specific patterns (God Module, Public Field Exposure, Primitive Obsession)
 pub(crate) fn analyze_rust_patterns(
     metrics: &ProjectMetrics,
@@ -172,18 +414,18 @@ pub(crate) fn analyze_rust_patterns(
         };
         let type_count = module.type_definitions.len();
         let impl_count = module.trait_impl_count + module.inherent_impl_count;
+        let (max_functions, max_types, max_impls) = thresholds.god_module_limits(module_name);
 
-        // Check if module exceeds thresholds (with test exclusion applied)
-        let is_god_module = func_count > thresholds.max_functions
-            || type_count > thresholds.max_types
-            || impl_count > thresholds.max_impls;
+        // Check if module exceeds thresholds (with test exclusion applied); a
+        // `// coupling:allow god-module` marker in the file opts it out
+        let is_god_module =
+            (func_count > max_functions || type_count > max_types || impl_count > max_impls)
+                && !module.allows(GOD_MODULE_RULE);
 
         if is_god_module {
             issues.push(CouplingIssue {
                 issue_type: IssueType::GodModule,
-                severity: if func_count > thresholds.max_functions * 2
-                    || type_count > thresholds.max_types * 2
-                {
+                severity: if func_count > max_functions * 2 || type_count > max_types * 2 {
                     Severity::High
                 } else {
                     Severity::Medium
@@ -196,9 +438,9 @@ pub(crate) fn analyze_rust_patterns(
                 description: format!(
                     "Module {} has too many responsibilities (functions: {}/{}, types: {}/{}, impls: {}/{})",
                     module_name,
-                    func_count, thresholds.max_functions,
-                    type_count, thresholds.max_types,
-                    impl_count, thresholds.max_impls,
+                    func_count, max_functions,
+                    type_count, max_types,
+                    impl_count, max_impls,
                 ),
                 refactoring: RefactoringAction::SplitModule {
                     suggested_modules: vec![
@@ -210,29 +452,374 @@ pub(crate) fn analyze_rust_patterns(
             });
         }
 
+        // Low Cohesion detection (LCOM): functions split into clusters sharing no types
+        if let Some(cohesion) = ModuleCohesion::of(module_name, module)
+            && cohesion.function_count >= LOW_COHESION_MIN_FUNCTIONS
+            && cohesion.cohesion < thresholds.min_cohesion
+        {
+            let clusters: Vec<String> = cohesion
+                .clusters
+                .iter()
+                .map(|cluster| {
+                    format!(
+                        "{} ({} fn)",
+                        cluster.types.join(", "),
+                        cluster.functions.len()
+                    )
+                })
+                .collect();
+            issues.push(CouplingIssue {
+                issue_type: IssueType::LowCohesion,
+                severity: Severity::Medium,
+                source: module_name.clone(),
+                target: format!("{} type clusters", cohesion.clusters.len()),
+                description: format!(
+                    "Module {} has {} functions in {} clusters that share no types (cohesion {:.2}, min {:.2}): {}",
+                    module_name,
+                    cohesion.function_count,
+                    cohesion.clusters.len(),
+                    cohesion.cohesion,
+                    thresholds.min_cohesion,
+                    clusters.join("; ")
+                ),
+                refactoring: RefactoringAction::SplitModule {
+                    suggested_modules: cohesion
+                        .clusters
+                        .iter()
+                        .map(|cluster| format!("{}::{}", module_name, cluster.module_name()))
+                        .collect(),
+                },
+                balance_score: cohesion.cohesion,
+            });
+        }
+
         // Public Field Exposure detection
         for type_def in module.type_definitions.values() {
             if type_def.public_field_count > 0
                 && !type_def.is_trait
                 && type_def.visibility == Visibility::Public
             {
+                let mut description = format!(
+                    "Type {} has {} public field(s). Consider using getter methods.",
+                    type_def.name, type_def.public_field_count
+                );
+                for field in &type_def.public_fields {
+                    let callers =
+                        external_field_accesses(metrics, module_name, &type_def.name, field);
+                    if callers.is_empty() {
+                        continue;
+                    }
+                    let mut caller_modules: Vec<&str> =
+                        callers.iter().map(|caller| caller.as_str()).collect();
+                    caller_modules.sort_unstable();
+                    caller_modules.dedup();
+                    description.push_str(&format!(
+                        " Making `{}` private removes {} cross-module access(es) (callers: {}).",
+                        field,
+                        callers.len(),
+                        caller_modules.join(", ")
+                    ));
+                }
+
+                let fields = if type_def.public_fields.is_empty() {
+                    vec!["// Add getter methods".to_string()]
+                } else {
+                    type_def.public_fields.clone()
+                };
+
                 issues.push(CouplingIssue {
                     issue_type: IssueType::PublicFieldExposure,
                     severity: Severity::Low,
                     source: format!("{}::{}", module_name, type_def.name),
                     target: format!("{} public fields", type_def.public_field_count),
+                    description,
+                    refactoring: RefactoringAction::AddGetters { fields },
+                    balance_score: 0.7,
+                });
+            }
+        }
+
+        // Public enums expose their variant data the way public structs expose fields
+        for type_def in module.type_definitions.values() {
+            if type_def.visibility != Visibility::Public
+                || type_def.is_non_exhaustive
+                || type_def.data_carrying_variant_count == 0
+            {
+                continue;
+            }
+            let internal_types: Vec<&str> = type_def
+                .variant_data_types
+                .iter()
+                .filter(|type_name| metrics.get_type_module(type_name).is_some())
+                .map(String::as_str)
+                .collect();
+            if internal_types.is_empty() {
+                continue;
+            }
+            let users = external_type_users(metrics, module_name, &type_def.name);
+            if users.is_empty() {
+                continue;
+            }
+
+            issues.push(CouplingIssue {
+                issue_type: IssueType::PublicFieldExposure,
+                severity: Severity::Low,
+                source: format!("{}::{}", module_name, type_def.name),
+                target: format!(
+                    "{} data-carrying variants",
+                    type_def.data_carrying_variant_count
+                ),
+                description: format!(
+                    "Enum {} exposes {} of {} variant(s) carrying {}; {} other module(s) depend on that shape ({}).",
+                    type_def.name,
+                    type_def.data_carrying_variant_count,
+                    type_def.variant_count,
+                    internal_types.join(", "),
+                    users.len(),
+                    users.join(", ")
+                ),
+                refactoring: RefactoringAction::General {
+                    action: ENUM_VARIANT_EXPOSURE_ACTION.to_string(),
+                },
+                balance_score: 0.7,
+            });
+        }
+
+        // Over-Parameterized Type detection
+        for type_def in module.type_definitions.values() {
+            if type_def.generic_param_count > thresholds.max_type_generics {
+                let excess = type_def.generic_param_count - thresholds.max_type_generics;
+                issues.push(CouplingIssue {
+                    issue_type: IssueType::OverParameterizedType,
+                    severity: if type_def.generic_param_count > thresholds.max_type_generics * 2 {
+                        Severity::Medium
+                    } else {
+                        Severity::Low
+                    },
+                    source: format!("{}::{}", module_name, type_def.name),
+                    target: format!("{} generic params", type_def.generic_param_count),
                     description: format!(
-                        "Type {} has {} public field(s). Consider using getter methods.",
-                        type_def.name, type_def.public_field_count
+                        "Type {} has {} generic parameters (max {}). Every user couples to all of its type arguments.",
+                        type_def.name, type_def.generic_param_count, thresholds.max_type_generics
                     ),
-                    refactoring: RefactoringAction::AddGetters {
-                        fields: vec!["// Add getter methods".to_string()],
+                    refactoring: RefactoringAction::General {
+                        action: OVER_PARAMETERIZED_TYPE_ACTION.to_string(),
                     },
-                    balance_score: 0.7,
+                    // Lower score for more parameters so the worst offenders sort first
+                    balance_score: (0.8 - 0.05 * excess as f64).max(0.4),
+                });
+            }
+        }
+
+        // Glob Import detection
+        if !module.glob_imports.is_empty() {
+            issues.push(CouplingIssue {
+                issue_type: IssueType::GlobImport,
+                severity: Severity::Low,
+                source: module_name.clone(),
+                target: module.glob_imports.join(", "),
+                description: format!(
+                    "Module {} has {} cross-module glob import(s) ({}). The coupled items are hidden, so coupling for this module may be under-counted.",
+                    module_name,
+                    module.glob_imports.len(),
+                    module.glob_imports.join(", ")
+                ),
+                refactoring: RefactoringAction::General {
+                    action: GLOB_IMPORT_ACTION.to_string(),
+                },
+                balance_score: 0.8,
+            });
+        }
+
+        // Mixed Async Boundary detection
+        let min_mixed = thresholds.min_mixed_async_functions;
+        if min_mixed > 0
+            && module.async_function_count >= min_mixed
+            && module.sync_public_function_count >= min_mixed
+        {
+            issues.push(CouplingIssue {
+                issue_type: IssueType::MixedAsyncBoundary,
+                severity: Severity::Low,
+                source: module_name.clone(),
+                target: format!(
+                    "{} async, {} sync public functions",
+                    module.async_function_count, module.sync_public_function_count
+                ),
+                description: format!(
+                    "Module {} mixes {} async functions ({} .await points) with {} public sync functions. Consider splitting it along the async/sync boundary.",
+                    module_name,
+                    module.async_function_count,
+                    module.await_count,
+                    module.sync_public_function_count
+                ),
+                refactoring: RefactoringAction::SplitModule {
+                    suggested_modules: vec![
+                        format!("{}_async", module_name),
+                        format!("{}_sync", module_name),
+                    ],
+                },
+                balance_score: 0.7,
+            });
+        }
+
+        // Unsafe Surface detection (advisory): unsafe code callers reach through a public API
+        let unsafe_surface = module.unsafe_block_count + module.unsafe_fn_count;
+        if thresholds.min_unsafe_surface > 0
+            && unsafe_surface >= thresholds.min_unsafe_surface
+            && module.interface_size() > 0
+        {
+            issues.push(CouplingIssue {
+                issue_type: IssueType::UnsafeSurface,
+                severity: Severity::Medium,
+                source: module_name.clone(),
+                target: format!(
+                    "{} unsafe blocks, {} unsafe fns",
+                    module.unsafe_block_count, module.unsafe_fn_count
+                ),
+                description: format!(
+                    "Module {} has {} unsafe blocks and {} unsafe fns behind a public API of {} items. Their soundness depends on invariants the compiler cannot check.",
+                    module_name,
+                    module.unsafe_block_count,
+                    module.unsafe_fn_count,
+                    module.interface_size()
+                ),
+                refactoring: RefactoringAction::General {
+                    action: UNSAFE_SURFACE_ACTION.to_string(),
+                },
+                balance_score: 0.7,
+            });
+        }
+
+        // Deref Abuse detection (advisory): users of the type couple to the target's API
+        for deref in &module.deref_impls {
+            let type_name = deref
+                .type_name
+                .rsplit("::")
+                .next()
+                .unwrap_or(&deref.type_name);
+            let traits = if deref.mutable {
+                "Deref and DerefMut"
+            } else {
+                "Deref"
+            };
+            let mut description = format!(
+                "Type {} implements {} to {}, so every method of {} is part of its API.",
+                type_name, traits, deref.target, deref.target
+            );
+            let users = external_type_users(metrics, module_name, type_name);
+            if !users.is_empty() {
+                description.push_str(&format!(
+                    " {} other module(s) use {} ({}).",
+                    users.len(),
+                    type_name,
+                    users.join(", ")
+                ));
+            }
+            issues.push(CouplingIssue {
+                issue_type: IssueType::DerefAbuse,
+                severity: Severity::Medium,
+                source: format!("{}::{}", module_name, type_name),
+                target: deref.target.clone(),
+                description,
+                refactoring: RefactoringAction::General {
+                    action: DEREF_ABUSE_ACTION.to_string(),
+                },
+                balance_score: if deref.mutable { 0.6 } else { 0.7 },
+            });
+        }
+
+        // Shallow Module detection (APOSD)
+        let interface = module.interface_size();
+        let implementation = module.implementation_size();
+        if interface >= thresholds.min_shallow_interface.max(1) && implementation > 0 {
+            let ratio = interface as f64 / implementation as f64;
+            if ratio > thresholds.max_interface_ratio {
+                issues.push(CouplingIssue {
+                    issue_type: IssueType::ShallowModule,
+                    severity: Severity::Low,
+                    source: module_name.clone(),
+                    target: format!(
+                        "interface {} / implementation {}",
+                        interface, implementation
+                    ),
+                    description: format!(
+                        "Module {} exposes {} public items against an implementation of {} (functions + item dependencies), ratio {:.2} (max {:.2}). It hides little behind its interface.",
+                        module_name,
+                        interface,
+                        implementation,
+                        ratio,
+                        thresholds.max_interface_ratio
+                    ),
+                    refactoring: RefactoringAction::General {
+                        action: SHALLOW_MODULE_ACTION.to_string(),
+                    },
+                    balance_score: (1.0 - ratio / 2.0).clamp(0.4, 0.8),
                 });
             }
         }
 
+        // High Cognitive Load detection (APOSD)
+        let factors = cognitive_load_factors(module, &thresholds.cognitive_load_weights);
+        let load: f64 = factors.iter().map(|factor| factor.load).sum();
+        let mut ranked: Vec<&CognitiveLoadFactor> =
+            factors.iter().filter(|factor| factor.load > 0.0).collect();
+        ranked.sort_by(|a, b| b.load.total_cmp(&a.load));
+        if load > thresholds.max_cognitive_load
+            && let Some(dominant) = ranked.first()
+        {
+            issues.push(CouplingIssue {
+                issue_type: IssueType::HighCognitiveLoad,
+                severity: if load > thresholds.max_cognitive_load * 2.0 {
+                    Severity::High
+                } else {
+                    Severity::Medium
+                },
+                source: module_name.clone(),
+                target: ranked
+                    .iter()
+                    .take(2)
+                    .map(|factor| factor.label.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                description: format!(
+                    "Module {} has a cognitive load of {:.0} (budget {:.0}), mostly from {} ({:.0}%). Factors: {}.",
+                    module_name,
+                    load,
+                    thresholds.max_cognitive_load,
+                    dominant.label,
+                    dominant.load / load * 100.0,
+                    ranked
+                        .iter()
+                        .map(|factor| format!("{} = {:.0}", factor.label, factor.load))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                refactoring: RefactoringAction::General {
+                    action: COGNITIVE_LOAD_ACTION.to_string(),
+                },
+                // Lower score for heavier modules so the worst offenders sort first
+                balance_score: (thresholds.max_cognitive_load / load * 0.7).clamp(0.3, 0.7),
+            });
+        }
+
+        // Pass-Through Method detection (APOSD)
+        for method in &module.pass_through_methods {
+            issues.push(CouplingIssue {
+                issue_type: IssueType::PassThroughMethod,
+                severity: Severity::Low,
+                source: format!("{}::{}", module_name, method.name),
+                target: method.delegate.clone(),
+                description: format!(
+                    "{} only forwards its parameters to {} without adding behavior.",
+                    method.name, method.delegate
+                ),
+                refactoring: RefactoringAction::General {
+                    action: PASS_THROUGH_ACTION.to_string(),
+                },
+                balance_score: 0.8,
+            });
+        }
+
         // Primitive Obsession detection
         for func_def in module.function_definitions.values() {
             if func_def.primitive_param_count >= thresholds.min_primitive_params
@@ -263,9 +850,217 @@ pub(crate) fn analyze_rust_patterns(
         }
     }
 
+    issues.extend(analyze_spread_couplings(metrics, thresholds));
+
+    issues
+}
+
+/// One weighted factor of a module's cognitive load
+struct CognitiveLoadFactor {
+    /// What was counted, e.g. `28 public fns`
+    label: String,
+    /// Weighted contribution to the module's load
+    load: f64,
+}
+
+/// Break a module's cognitive load into its weighted factors.
+///
+/// Public API counts public functions and types; external crates are the
+/// distinct crates in `external_deps`; parameters are averaged over all
+/// functions; item dependencies are the module's item-level edges.
+fn cognitive_load_factors(
+    module: &ModuleMetrics,
+    weights: &CognitiveLoadWeights,
+) -> [CognitiveLoadFactor; 4] {
+    let public_fns = module.public_function_count();
+    let public_types = module.public_type_count();
+    let external_crates = module
+        .external_deps
+        .iter()
+        .filter_map(|dep| dep.split("::").next())
+        .collect::<HashSet<_>>()
+        .len();
+    let average_params = if module.function_definitions.is_empty() {
+        0.0
+    } else {
+        module
+            .function_definitions
+            .values()
+            .map(|func| func.param_count)
+            .sum::<usize>() as f64
+            / module.function_definitions.len() as f64
+    };
+    let item_dependencies = module.item_dependencies.len();
+
+    [
+        CognitiveLoadFactor {
+            label: format!("{} public fns, {} public types", public_fns, public_types),
+            load: (public_fns + public_types) as f64 * weights.public_api,
+        },
+        CognitiveLoadFactor {
+            label: format!("{} external crates", external_crates),
+            load: external_crates as f64 * weights.external_crates,
+        },
+        CognitiveLoadFactor {
+            label: format!("{:.1} params per fn", average_params),
+            load: average_params * weights.average_params,
+        },
+        CognitiveLoadFactor {
+            label: format!("{} item dependencies", item_dependencies),
+            load: item_dependencies as f64 * weights.item_dependencies,
+        },
+    ]
+}
+
+/// Detect field accesses that are repeated across many modules (spread coupling).
+///
+/// Unlike afferent coupling, this is keyed on the specific accessed member:
+/// twenty modules reading `Config.database_url` is one spread dependency worth
+/// consolidating, even if each of them also uses `Config` in other ways.
+fn analyze_spread_couplings(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
+    // "<type path>.<field>" -> (accessing module -> first expression seen there)
+    let mut accesses: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+    for (module_name, module) in &metrics.modules {
+        for dep in &module.item_dependencies {
+            if dep.dep_type != ItemDepType::FieldAccess {
+                continue;
+            }
+            let Some((type_path, _)) = dep.target.rsplit_once('.') else {
+                continue;
+            };
+            // Only accesses resolved to a type; `self.x` and untyped locals are skipped
+            let type_name = type_path.rsplit("::").next().unwrap_or(type_path);
+            if !type_name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                continue;
+            }
+            if metrics.get_type_module(type_name) == Some(module_name.as_str()) {
+                continue;
+            }
+            accesses
+                .entry(dep.target.as_str())
+                .or_default()
+                .entry(module_name.as_str())
+                .or_insert_with(|| dep.expression.as_deref().unwrap_or(dep.target.as_str()));
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (target, modules) in accesses {
+        let spread = modules.len();
+        if spread < thresholds.min_spread_modules {
+            continue;
+        }
+
+        let (type_path, field) = target.rsplit_once('.').unwrap_or((target, ""));
+        let type_name = type_path.rsplit("::").next().unwrap_or(type_path);
+        let member = match metrics.get_type_module(type_name) {
+            Some(owner) => format!("{}::{}.{}", owner, type_name, field),
+            None => target.to_string(),
+        };
+
+        let mut module_names: Vec<&str> = modules.keys().copied().collect();
+        module_names.sort_unstable();
+        let mut expressions: Vec<&str> = modules.values().copied().collect();
+        expressions.sort_unstable();
+        expressions.dedup();
+
+        issues.push(CouplingIssue {
+            issue_type: IssueType::SpreadCoupling,
+            severity: if spread >= thresholds.min_spread_modules * 2 {
+                Severity::Medium
+            } else {
+                Severity::Low
+            },
+            source: member.clone(),
+            target: format!("{} modules", spread),
+            description: format!(
+                "`{}` is accessed directly from {} modules ({}) via {}. A change to this member ripples through all of them.",
+                member,
+                spread,
+                module_names.join(", "),
+                expressions.join(", ")
+            ),
+            refactoring: RefactoringAction::General {
+                action: SPREAD_COUPLING_ACTION.to_string(),
+            },
+            // Lower score for wider spread so the worst offenders sort first
+            balance_score: (0.8 - 0.02 * (spread - thresholds.min_spread_modules) as f64)
+                .max(0.4),
+        });
+    }
     issues
 }
 
+/// Modules accessing `type_name.field` from outside `owner_module`, one entry per access site.
+///
+/// Field accesses are recorded as `<type path>.<field>` item dependencies; the type
+/// registry disambiguates same-named types so only the owner's type is counted.
+fn external_field_accesses(
+    metrics: &ProjectMetrics,
+    owner_module: &str,
+    type_name: &str,
+    field: &str,
+) -> Vec<String> {
+    if metrics
+        .get_type_module(type_name)
+        .is_some_and(|registered| registered != owner_module)
+    {
+        return Vec::new();
+    }
+
+    let mut callers = Vec::new();
+    for (module_name, module) in &metrics.modules {
+        if module_name == owner_module {
+            continue;
+        }
+        for dep in &module.item_dependencies {
+            if dep.dep_type != ItemDepType::FieldAccess {
+                continue;
+            }
+            let Some((type_path, accessed_field)) = dep.target.rsplit_once('.') else {
+                continue;
+            };
+            if accessed_field == field && type_path.rsplit("::").next() == Some(type_name) {
+                callers.push(module_name.clone());
+            }
+        }
+    }
+    callers
+}
+
+/// Other modules importing or using `type_name` of `owner_module`, sorted.
+fn external_type_users(
+    metrics: &ProjectMetrics,
+    owner_module: &str,
+    type_name: &str,
+) -> Vec<String> {
+    if metrics
+        .get_type_module(type_name)
+        .is_some_and(|registered| registered != owner_module)
+    {
+        return Vec::new();
+    }
+
+    let mut users: Vec<String> = metrics
+        .modules
+        .iter()
+        .filter(|(module_name, _)| module_name.as_str() != owner_module)
+        .filter(|(_, module)| {
+            module
+                .internal_deps
+                .iter()
+                .chain(module.item_dependencies.iter().map(|dep| &dep.target))
+                .any(|path| path.split(['.', ':']).any(|segment| segment == type_name))
+        })
+        .map(|(module_name, _)| module_name.clone())
+        .collect();
+    users.sort();
+    users
+}
+
 /// Capitalize first letter of a string
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
@@ -1,13 +1,21 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Instant;
 
 use crate::metrics::dimensions::{Distance, Subdomain};
 use crate::metrics::project::ProjectMetrics;
+use crate::timing::record_balance_pass;
 
 use super::coupling::identify_issues_with_thresholds;
 use super::grade::{ProjectBalanceReport, build_grade_rationale, calculate_health_grade};
+use super::issue::{CouplingIssue, IssueKey};
 use super::issue_type::IssueType;
-use super::patterns::{analyze_module_coupling, analyze_rust_patterns};
-use super::score::{BalanceInterpretation, BalanceScore, IssueThresholds};
+use super::patterns::{
+    analyze_deprecated_internal_coupling, analyze_duplicated_structures, analyze_layer_violations,
+    analyze_module_coupling, analyze_rust_patterns,
+};
+use super::score::{
+    BalanceBands, BalanceFormula, BalanceInterpretation, BalanceScore, IssueThresholds,
+};
 use super::severity::Severity;
 use super::signals::{analyze_accidental_volatility, analyze_hidden_temporal_coupling};
 use super::subdomain::{build_target_subdomain_map, coupling_with_essential_volatility};
@@ -21,27 +29,41 @@ pub fn analyze_project_balance_with_thresholds(
     metrics: &ProjectMetrics,
     thresholds: &IssueThresholds,
 ) -> ProjectBalanceReport {
+    let start = Instant::now();
     let thresholds = thresholds.clone();
     let mut all_issues = Vec::new();
     let mut internal_balance_scores: Vec<BalanceScore> = Vec::new();
+    let mut weighted_score_sum = 0.0;
+    let mut total_weight = 0;
     let mut all_balance_scores: Vec<BalanceScore> = Vec::new();
     let target_subdomains = build_target_subdomain_map(metrics);
+    let mut suppressed = HashSet::new();
 
     // Analyze individual couplings
     // Only INTERNAL couplings affect the health score
     for coupling in &metrics.couplings {
         let effective_coupling = coupling_with_essential_volatility(coupling, &target_subdomains);
-        let score = BalanceScore::calculate(&effective_coupling);
+        let score = BalanceScore::calculate_for(&effective_coupling, &thresholds);
         all_balance_scores.push(score.clone());
 
-        // Only count internal couplings for scoring
-        if effective_coupling.distance != Distance::DifferentCrate {
+        // Only count internal couplings for scoring, and none from crates left out
+        // of the grade by kind (proc-macro crates by default)
+        if effective_coupling.distance != Distance::DifferentCrate
+            && !metrics.is_excluded_from_grade(&coupling.source)
+        {
+            let weight = thresholds.balance_formula.weight(coupling);
+            weighted_score_sum += score.score * weight as f64;
+            total_weight += weight;
             internal_balance_scores.push(score);
-            let issues = identify_issues_with_thresholds(&effective_coupling, &thresholds);
+            let mut issues = identify_issues_with_thresholds(&effective_coupling, &thresholds);
+            let line = Some(coupling.location.line);
+            drop_inline_suppressed(metrics, &mut issues, line, &mut suppressed);
             all_issues.extend(issues);
         }
     }
 
+    let coupling_issue_count = all_issues.len();
+
     // Analyze module-level coupling patterns (already filters external)
     let module_issues = analyze_module_coupling(metrics, &thresholds);
     all_issues.extend(module_issues);
@@ -49,9 +71,16 @@ pub fn analyze_project_balance_with_thresholds(
     // Analyze Khononov/Rust-specific issues
     let rust_issues = analyze_rust_patterns(metrics, &thresholds);
     all_issues.extend(rust_issues);
+    all_issues.extend(analyze_duplicated_structures(metrics, &thresholds));
+
+    // Policy: couplings into internal modules the config marks as deprecated
+    all_issues.extend(analyze_deprecated_internal_coupling(metrics, &thresholds));
+
+    // Policy: couplings from an inner architecture layer into an outer one
+    all_issues.extend(analyze_layer_violations(metrics, &thresholds));
 
     // Analyze git-backed implicit coupling and volatility/subdomain mismatches.
-    let temporal_issues = analyze_hidden_temporal_coupling(metrics);
+    let temporal_issues = analyze_hidden_temporal_coupling(metrics, &thresholds);
     all_issues.extend(temporal_issues);
     let accidental_volatility_issues = analyze_accidental_volatility(metrics);
     all_issues.extend(accidental_volatility_issues);
@@ -75,6 +104,28 @@ pub fn analyze_project_balance_with_thresholds(
         }
     }
 
+    // Module-level issues carry no line, so a marker anywhere in the source file covers them
+    let mut module_level = all_issues.split_off(coupling_issue_count);
+    drop_inline_suppressed(metrics, &mut module_level, None, &mut suppressed);
+    all_issues.extend(module_level);
+
+    // Crates excluded by kind stay in the module/coupling listings, not in the issues
+    all_issues.retain(|issue| !metrics.is_excluded_from_grade(&issue.source));
+
+    // `[severity]` remaps issue types (or turns them off) before anything filters on severity
+    if !thresholds.severity_overrides.is_empty() {
+        all_issues.retain_mut(
+            |issue| match thresholds.severity_overrides.get(&issue.issue_type) {
+                Some(Some(severity)) => {
+                    issue.severity = *severity;
+                    true
+                }
+                Some(None) => false,
+                None => true,
+            },
+        );
+    }
+
     // Strict mode: filter out Low severity issues to reduce noise
     if thresholds.strict_mode {
         all_issues.retain(|issue| issue.severity >= Severity::Medium);
@@ -111,8 +162,7 @@ pub fn analyze_project_balance_with_thresholds(
     let average_score = if internal_balance_scores.is_empty() {
         1.0 // No internal couplings = perfect score
     } else {
-        internal_balance_scores.iter().map(|s| s.score).sum::<f64>()
-            / internal_balance_scores.len() as f64
+        weighted_score_sum / total_weight as f64
     };
 
     // Report display counts include diagnostics such as Accidental Volatility.
@@ -141,11 +191,19 @@ pub fn analyze_project_balance_with_thresholds(
     }
 
     // Determine overall health grade based on INTERNAL coupling issues
-    let health_grade = calculate_health_grade(&gradable_by_severity, internal_couplings);
-    let grade_rationale =
-        build_grade_rationale(&all_issues, internal_couplings, thresholds.japanese);
+    let health_grade = calculate_health_grade(
+        &gradable_by_severity,
+        internal_couplings,
+        &thresholds.grade_minimums,
+    );
+    let grade_rationale = build_grade_rationale(
+        &all_issues,
+        internal_couplings,
+        &thresholds.grade_minimums,
+        thresholds.japanese,
+    );
 
-    ProjectBalanceReport {
+    let report = ProjectBalanceReport {
         total_couplings,
         balanced_count,
         needs_review,
@@ -157,8 +215,51 @@ pub fn analyze_project_balance_with_thresholds(
         issues: all_issues,
         top_priorities: Vec::new(), // Will be filled below
         grade_rationale,
+        inline_suppressions: suppressed.len(),
     }
-    .with_top_priorities(5) // Increased from 3 to 5 for better actionability
+    .with_top_priorities(5); // Increased from 3 to 5 for better actionability
+    record_balance_pass(start.elapsed());
+    report
+}
+
+/// Remove issues silenced by a `// coupling:allow <rule>(<target>)` marker in
+/// their source module, recording each removed issue's key in `suppressed`.
+///
+/// With a `line` (coupling issues) the marker must sit on that line or the one
+/// above; without one (module-level issues) it may be anywhere in the file.
+fn drop_inline_suppressed(
+    metrics: &ProjectMetrics,
+    issues: &mut Vec<CouplingIssue>,
+    line: Option<usize>,
+    suppressed: &mut HashSet<IssueKey>,
+) {
+    issues.retain(|issue| {
+        let Some(module) = metrics
+            .module_for_endpoint(&issue.source)
+            .and_then(|name| metrics.modules.get(name))
+        else {
+            return true;
+        };
+        let covered = module.suppressions.iter().any(|marker| {
+            issue.issue_type.matches_rule_name(&marker.rule)
+                && marker
+                    .target
+                    .as_deref()
+                    .is_none_or(|target| suppression_target_matches(target, &issue.target))
+                && line.is_none_or(|line| marker.line == line || marker.line + 1 == line)
+        });
+        if covered {
+            suppressed.insert(issue.stable_key());
+        }
+        !covered
+    });
+}
+
+/// Whether a marker target names the issue target or a path around it:
+/// `crate::foo` covers `foo`, `foo::Bar` and `crate::foo::Bar`.
+fn suppression_target_matches(marker: &str, target: &str) -> bool {
+    let marker = marker.strip_prefix("crate::").unwrap_or(marker);
+    format!("::{}::", target).contains(&format!("::{}::", marker))
 }
 
 pub(crate) fn dedupe_issues_by_stable_key(issues: &mut Vec<super::issue::CouplingIssue>) {
@@ -171,16 +272,29 @@ pub(crate) fn dedupe_issues_by_stable_key(issues: &mut Vec<super::issue::Couplin
 /// Only considers internal couplings (not external crate dependencies)
 /// since external dependencies are outside the developer's control.
 pub fn calculate_project_score(metrics: &ProjectMetrics) -> f64 {
+    calculate_project_score_with(metrics, &BalanceFormula::default())
+}
+
+/// Overall project balance score, with each coupling scored by a custom formula
+pub fn calculate_project_score_with(metrics: &ProjectMetrics, formula: &BalanceFormula) -> f64 {
     let target_subdomains = build_target_subdomain_map(metrics);
 
     // Filter to internal couplings only
-    let internal_scores: Vec<f64> = metrics
+    let internal_scores: Vec<(f64, usize)> = metrics
         .couplings
         .iter()
-        .filter(|c| c.distance != Distance::DifferentCrate)
+        .filter(|c| {
+            c.distance != Distance::DifferentCrate && !metrics.is_excluded_from_grade(&c.source)
+        })
         .map(|c| {
             let effective_coupling = coupling_with_essential_volatility(c, &target_subdomains);
-            BalanceScore::calculate(&effective_coupling).score
+            let score = BalanceScore::calculate_with_formula(
+                &effective_coupling,
+                &BalanceBands::default(),
+                formula,
+            )
+            .score;
+            (score, formula.weight(c))
         })
         .collect();
 
@@ -188,5 +302,118 @@ pub fn calculate_project_score(metrics: &ProjectMetrics) -> f64 {
         return 1.0; // No internal couplings = perfect score
     }
 
-    internal_scores.iter().sum::<f64>() / internal_scores.len() as f64
+    let total_weight: usize = internal_scores.iter().map(|(_, weight)| weight).sum();
+    internal_scores
+        .iter()
+        .map(|(score, weight)| score * *weight as f64)
+        .sum::<f64>()
+        / total_weight as f64
+}
+
+/// Health report for each workspace member crate, keyed by crate name
+///
+/// Each crate is graded on its own modules and the couplings it originates
+/// (see `ProjectMetrics::crate_metrics`), so one unhealthy crate cannot hide
+/// behind a healthy workspace average. Crates left out of the grade by kind
+/// are skipped.
+pub fn analyze_crate_balance(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> HashMap<String, ProjectBalanceReport> {
+    metrics
+        .crate_names()
+        .into_iter()
+        .filter(|crate_name| {
+            !metrics
+                .grade_excluded_crates
+                .iter()
+                .any(|(excluded, _)| excluded == crate_name)
+        })
+        .map(|crate_name| {
+            let report = analyze_project_balance_with_thresholds(
+                &metrics.crate_metrics(crate_name),
+                thresholds,
+            );
+            (crate_name.to_string(), report)
+        })
+        .collect()
+}
+
+/// The `limit` worst-scored internal couplings, lowest score first
+///
+/// Scores each coupling individually (with the configured bands and formula)
+/// over the same couplings as `calculate_project_score`, so the weakest lines
+/// surface regardless of which module they are in. Ties keep the stronger
+/// coupling first, then source, target and line order.
+pub fn worst_couplings(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    limit: usize,
+) -> Vec<BalanceScore> {
+    let target_subdomains = build_target_subdomain_map(metrics);
+    let mut scores: Vec<BalanceScore> = metrics
+        .couplings
+        .iter()
+        .filter(|c| {
+            c.distance != Distance::DifferentCrate && !metrics.is_excluded_from_grade(&c.source)
+        })
+        .map(|c| {
+            let effective_coupling = coupling_with_essential_volatility(c, &target_subdomains);
+            BalanceScore::calculate_for(&effective_coupling, thresholds)
+        })
+        .collect();
+
+    scores.sort_by(|a, b| {
+        a.score
+            .total_cmp(&b.score)
+            .then_with(|| {
+                let visibility_penalty = thresholds.balance_formula.visibility_penalty;
+                b.coupling
+                    .scoring_strength(visibility_penalty)
+                    .value()
+                    .total_cmp(&a.coupling.scoring_strength(visibility_penalty).value())
+            })
+            .then_with(|| a.coupling.source.cmp(&b.coupling.source))
+            .then_with(|| a.coupling.target.cmp(&b.coupling.target))
+            .then_with(|| a.coupling.location.line.cmp(&b.coupling.location.line))
+    });
+    scores.truncate(limit);
+    scores
+}
+
+/// Average balance score of each module's outgoing internal couplings
+///
+/// Uses the same couplings and scoring as `calculate_project_score`; modules
+/// without internal couplings are absent from the map.
+pub fn calculate_module_scores(metrics: &ProjectMetrics) -> BTreeMap<String, f64> {
+    calculate_module_scores_with(metrics, &BalanceFormula::default())
+}
+
+/// Average balance score of each module's outgoing internal couplings, scored
+/// with a custom formula
+pub fn calculate_module_scores_with(
+    metrics: &ProjectMetrics,
+    formula: &BalanceFormula,
+) -> BTreeMap<String, f64> {
+    let target_subdomains = build_target_subdomain_map(metrics);
+    let mut totals: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+
+    for coupling in metrics.couplings.iter().filter(|c| {
+        c.distance != Distance::DifferentCrate && !metrics.is_excluded_from_grade(&c.source)
+    }) {
+        let effective_coupling = coupling_with_essential_volatility(coupling, &target_subdomains);
+        let entry = totals.entry(coupling.source.clone()).or_default();
+        entry.0 += BalanceScore::calculate_with_formula(
+            &effective_coupling,
+            &BalanceBands::default(),
+            formula,
+        )
+        .score;
+        entry.1 += 1;
+    }
+
+    totals
+        .into_iter()
+        .map(|(module, (sum, count))| (module, sum / count as f64))
+        .collect()
 }
@@ -1,7 +1,15 @@
 // ===== Balance Scoring =====
 
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
 use crate::metrics::coupling::CouplingMetrics;
 
+use super::grade::GradeMinimums;
+use super::issue_type::IssueType;
+use super::severity::Severity;
+
 /// Balance score for a coupling relationship
 #[derive(Debug, Clone)]
 pub struct BalanceScore {
@@ -44,6 +52,74 @@ impl std::fmt::Display for BalanceInterpretation {
     }
 }
 
+/// Score cutoffs that map a balance score onto its interpretation.
+///
+/// Each field is the minimum score for that band; anything below
+/// `needs_refactoring` is Critical.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceBands {
+    pub balanced: f64,
+    pub acceptable: f64,
+    pub needs_review: f64,
+    pub needs_refactoring: f64,
+}
+
+impl Default for BalanceBands {
+    fn default() -> Self {
+        Self {
+            balanced: 0.8,
+            acceptable: 0.6,
+            needs_review: 0.4,
+            needs_refactoring: 0.2,
+        }
+    }
+}
+
+impl BalanceBands {
+    /// Interpret a balance score against these cutoffs
+    pub fn interpret(&self, score: f64) -> BalanceInterpretation {
+        match score {
+            s if s >= self.balanced => BalanceInterpretation::Balanced,
+            s if s >= self.acceptable => BalanceInterpretation::Acceptable,
+            s if s >= self.needs_review => BalanceInterpretation::NeedsReview,
+            s if s >= self.needs_refactoring => BalanceInterpretation::NeedsRefactoring,
+            _ => BalanceInterpretation::Critical,
+        }
+    }
+
+    /// Check that cutoffs lie within 0.0-1.0 and strictly decrease band by band
+    pub fn validate(&self) -> Result<(), String> {
+        let cutoffs = [
+            ("balanced", self.balanced),
+            ("acceptable", self.acceptable),
+            ("needs_review", self.needs_review),
+            ("needs_refactoring", self.needs_refactoring),
+        ];
+
+        for (name, value) in cutoffs {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!(
+                    "{} must be between 0.0 and 1.0, got {}",
+                    name, value
+                ));
+            }
+        }
+
+        for pair in cutoffs.windows(2) {
+            let (upper_name, upper) = pair[0];
+            let (lower_name, lower) = pair[1];
+            if upper <= lower {
+                return Err(format!(
+                    "{} ({}) must be greater than {} ({})",
+                    upper_name, upper, lower_name, lower
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl BalanceScore {
     /// Calculate balance score for a coupling
     ///
@@ -57,7 +133,38 @@ impl BalanceScore {
     /// - Strong (1.0) + Far (1.0) → Low alignment (global complexity)
     /// - Any + High volatility → Reduced by volatility impact
     pub fn calculate(coupling: &CouplingMetrics) -> Self {
-        let strength = coupling.strength_value();
+        Self::calculate_with(coupling, &BalanceBands::default())
+    }
+
+    /// Whether volatility impact, rather than strength/distance alignment, is
+    /// the factor holding the score down
+    pub fn volatility_dominated(&self) -> bool {
+        self.volatility_impact < self.alignment
+    }
+
+    /// Calculate balance score, interpreting it against custom bands
+    pub fn calculate_with(coupling: &CouplingMetrics, bands: &BalanceBands) -> Self {
+        Self::calculate_with_formula(coupling, bands, &BalanceFormula::default())
+    }
+
+    /// Calculate balance score with the bands and formula of `thresholds`
+    pub fn calculate_for(coupling: &CouplingMetrics, thresholds: &IssueThresholds) -> Self {
+        Self::calculate_with_formula(
+            coupling,
+            &thresholds.balance_bands,
+            &thresholds.balance_formula,
+        )
+    }
+
+    /// Calculate balance score with a custom formula, interpreting it against custom bands
+    pub fn calculate_with_formula(
+        coupling: &CouplingMetrics,
+        bands: &BalanceBands,
+        formula: &BalanceFormula,
+    ) -> Self {
+        let strength = coupling
+            .scoring_strength(formula.visibility_penalty)
+            .value();
         let distance = coupling.distance_value();
         let volatility = coupling.volatility_value();
 
@@ -72,18 +179,12 @@ impl BalanceScore {
         let volatility_penalty = volatility * strength;
         let volatility_impact = 1.0 - volatility_penalty;
 
-        // Combined score: both alignment AND stability matter
-        // Using AND (multiplication) instead of OR (max) for stricter scoring
-        let score = alignment * volatility_impact;
+        // Combined score: both alignment AND stability matter. The default
+        // formula multiplies them (AND rather than OR) for stricter scoring
+        let score = formula.combine(alignment, volatility_impact);
 
         // Determine interpretation based on score
-        let interpretation = match score {
-            s if s >= 0.8 => BalanceInterpretation::Balanced,
-            s if s >= 0.6 => BalanceInterpretation::Acceptable,
-            s if s >= 0.4 => BalanceInterpretation::NeedsReview,
-            s if s >= 0.2 => BalanceInterpretation::NeedsRefactoring,
-            _ => BalanceInterpretation::Critical,
-        };
+        let interpretation = bands.interpret(score);
 
         Self {
             coupling: coupling.clone(),
@@ -111,6 +212,174 @@ impl BalanceScore {
     }
 }
 
+/// How alignment and volatility impact combine into a balance score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceCombiner {
+    /// `alignment^a * impact^v`: either factor alone can sink the score
+    #[default]
+    Multiply,
+    /// `min(alignment^a, impact^v)`: the weaker factor is the score
+    Min,
+    /// `(a * alignment + v * impact) / (a + v)`: the factors offset each other
+    WeightedSum,
+}
+
+/// Formula turning alignment and volatility impact into a balance score.
+///
+/// For `multiply` and `min` the weights are exponents, so a weight above 1.0
+/// makes that factor bite harder; for `weighted_sum` they are linear weights.
+/// The default (`multiply`, both weights 1.0) is `alignment * volatility_impact`.
+/// With `visibility_penalty` a coupling enters the formula at its effective
+/// strength, so reaching a non-public item across a boundary scores as one
+/// step more intrusive. With `weight_by_occurrences` a merged coupling
+/// (`--merge-couplings`) counts once per usage in the average score and in
+/// hotspot coupling counts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BalanceFormula {
+    pub alignment_weight: f64,
+    pub volatility_weight: f64,
+    pub combiner: BalanceCombiner,
+    pub visibility_penalty: bool,
+    pub weight_by_occurrences: bool,
+}
+
+impl Default for BalanceFormula {
+    fn default() -> Self {
+        Self {
+            alignment_weight: 1.0,
+            volatility_weight: 1.0,
+            combiner: BalanceCombiner::Multiply,
+            visibility_penalty: true,
+            weight_by_occurrences: false,
+        }
+    }
+}
+
+impl BalanceFormula {
+    /// Combine alignment and volatility impact (both 0.0-1.0) into a 0.0-1.0 score
+    pub fn combine(&self, alignment: f64, volatility_impact: f64) -> f64 {
+        // A weight of exactly 1.0 leaves the factor untouched, so the default is exact
+        let raise = |value: f64, weight: f64| {
+            if weight == 1.0 {
+                value
+            } else {
+                value.powf(weight)
+            }
+        };
+        match self.combiner {
+            BalanceCombiner::Multiply => {
+                raise(alignment, self.alignment_weight)
+                    * raise(volatility_impact, self.volatility_weight)
+            }
+            BalanceCombiner::Min => raise(alignment, self.alignment_weight)
+                .min(raise(volatility_impact, self.volatility_weight)),
+            BalanceCombiner::WeightedSum => {
+                (self.alignment_weight * alignment + self.volatility_weight * volatility_impact)
+                    / (self.alignment_weight + self.volatility_weight)
+            }
+        }
+    }
+
+    /// How many times `coupling` counts in averages and totals
+    pub fn weight(&self, coupling: &CouplingMetrics) -> usize {
+        if self.weight_by_occurrences {
+            coupling.occurrences.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Check that weights are non-negative and a weighted sum has a non-zero total
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("alignment_weight", self.alignment_weight),
+            ("volatility_weight", self.volatility_weight),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!(
+                    "{} must be a non-negative number, got {}",
+                    name, value
+                ));
+            }
+        }
+        if self.combiner == BalanceCombiner::WeightedSum
+            && self.alignment_weight + self.volatility_weight == 0.0
+        {
+            return Err("weighted_sum needs at least one non-zero weight".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Weights of the factors summed into a module's cognitive load.
+///
+/// The load is the weighted sum of the module's public functions and types,
+/// distinct external crates, average function parameter count and item-level
+/// dependencies. Modules above `IssueThresholds::max_cognitive_load` are
+/// flagged as High Cognitive Load.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CognitiveLoadWeights {
+    /// Load per public function or type
+    pub public_api: f64,
+    /// Load per distinct external crate
+    pub external_crates: f64,
+    /// Load per parameter of the average function
+    pub average_params: f64,
+    /// Load per item-level dependency
+    pub item_dependencies: f64,
+}
+
+impl Default for CognitiveLoadWeights {
+    fn default() -> Self {
+        Self {
+            public_api: 1.0,
+            external_crates: 2.0,
+            average_params: 4.0,
+            item_dependencies: 0.02,
+        }
+    }
+}
+
+impl CognitiveLoadWeights {
+    /// Check that no weight is negative or non-finite
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = [
+            ("public_api", self.public_api),
+            ("external_crates", self.external_crates),
+            ("average_params", self.average_params),
+            ("item_dependencies", self.item_dependencies),
+        ];
+        for (name, value) in weights {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!(
+                    "{} must be a non-negative number, got {}",
+                    name, value
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// God Module limits for one module path; unset limits fall back to the global ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GodModuleLimits {
+    pub max_functions: Option<usize>,
+    pub max_types: Option<usize>,
+    pub max_impls: Option<usize>,
+}
+
+/// One architecture layer from `[[layers]]`; layers are listed innermost first.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArchitectureLayer {
+    pub name: String,
+    /// Module paths in this layer, matched by whole `::` segments (`domain`, `app::infra`)
+    pub modules: Vec<String>,
+}
+
 /// Thresholds for identifying issues
 #[derive(Debug, Clone)]
 pub struct IssueThresholds {
@@ -130,8 +399,32 @@ pub struct IssueThresholds {
     pub max_types: usize,
     /// Maximum implementations before flagging God Module
     pub max_impls: usize,
+    /// Per-module God Module limits, keyed by module path (`generated`, `crate::generated`)
+    pub god_module_overrides: BTreeMap<String, GodModuleLimits>,
     /// Minimum primitive parameter count for Primitive Obsession
     pub min_primitive_params: usize,
+    /// Maximum generic parameters on a type before flagging Over-Parameterized Type
+    pub max_type_generics: usize,
+    /// Minimum number of modules accessing the same member before flagging Spread Coupling
+    pub min_spread_modules: usize,
+    /// Minimum async functions and public sync functions before flagging Mixed Async Boundary
+    pub min_mixed_async_functions: usize,
+    /// Minimum unsafe blocks plus unsafe fns in a module with public API before flagging Unsafe Surface
+    pub min_unsafe_surface: usize,
+    /// Minimum shared named fields before structs in different modules are flagged as Duplicated Structure
+    pub min_duplicated_fields: usize,
+    /// Modules or types (`api::dto`, `wire::Header`) allowed to duplicate another struct's fields
+    pub duplicated_structure_allowlist: Vec<String>,
+    /// Largest-cluster share of a module's functions below which Low Cohesion is flagged
+    pub min_cohesion: f64,
+    /// Maximum interface/implementation size ratio before flagging Shallow Module
+    pub max_interface_ratio: f64,
+    /// Minimum interface size before a module is checked for Shallow Module
+    pub min_shallow_interface: usize,
+    /// Cognitive load budget before flagging High Cognitive Load
+    pub max_cognitive_load: f64,
+    /// Weights of the cognitive load factors
+    pub cognitive_load_weights: CognitiveLoadWeights,
     /// Strict mode: only show Medium/High/Critical issues
     pub strict_mode: bool,
     /// Show explanations in Japanese
@@ -140,6 +433,24 @@ pub struct IssueThresholds {
     pub exclude_tests: bool,
     /// Prelude module patterns (for reporting purposes)
     pub prelude_module_count: usize,
+    /// Score cutoffs for balance interpretation
+    pub balance_bands: BalanceBands,
+    /// How alignment and volatility impact combine into the balance score
+    pub balance_formula: BalanceFormula,
+    /// Minimum evidence required before grading a project A
+    pub grade_minimums: GradeMinimums,
+    /// Deprecated internal modules; couplings into them are flagged
+    pub deprecated_internal: Vec<String>,
+    /// Severity of Deprecated Internal Coupling issues
+    pub deprecated_internal_severity: Severity,
+    /// Crates classified as Stable in addition to the built-in list (`[external] stable`)
+    pub stable_crates: Vec<String>,
+    /// Crates classified as Infrastructure in addition to the built-in list
+    pub infrastructure_crates: Vec<String>,
+    /// Ordered architecture layers; depending on a later layer is a Layer Violation
+    pub layers: Vec<ArchitectureLayer>,
+    /// Per-issue-type severity from `[severity]`; `None` turns the type off
+    pub severity_overrides: BTreeMap<IssueType, Option<Severity>>,
 }
 
 impl Default for IssueThresholds {
@@ -156,8 +467,64 @@ impl Default for IssueThresholds {
             min_primitive_params: 3, // 3+ primitive params = Primitive Obsession
             strict_mode: true,       // Show only important issues by default
             japanese: false,         // English by default
-            exclude_tests: false,    // Include test code by default
+            exclude_tests: true,     // Exclude test code by default
             prelude_module_count: 0, // No prelude modules configured
+            god_module_overrides: BTreeMap::new(),
+            // More than 3 generic params = Over-Parameterized Type
+            max_type_generics: 3,
+            // Same member accessed from 4+ modules = Spread Coupling
+            min_spread_modules: 4,
+            // 3+ async and 3+ public sync functions = Mixed Async Boundary
+            min_mixed_async_functions: 3,
+            // 3+ unsafe blocks/fns behind a public API = Unsafe Surface
+            min_unsafe_surface: 3,
+            // Largest type cluster under 60% of the functions = Low Cohesion
+            min_cohesion: 0.6,
+            // Interface over half the implementation = Shallow Module
+            max_interface_ratio: 0.5,
+            // Smaller interfaces are not checked for Shallow Module
+            min_shallow_interface: 8,
+            // Weighted load above 60 = High Cognitive Load
+            max_cognitive_load: 60.0,
+            cognitive_load_weights: CognitiveLoadWeights::default(),
+            // Same 3+ named fields in two modules = Duplicated Structure
+            min_duplicated_fields: 3,
+            duplicated_structure_allowlist: Vec::new(),
+            balance_bands: BalanceBands::default(),
+            balance_formula: BalanceFormula::default(),
+            grade_minimums: GradeMinimums::default(),
+            deprecated_internal: Vec::new(),
+            deprecated_internal_severity: Severity::Medium,
+            stable_crates: Vec::new(),
+            infrastructure_crates: Vec::new(),
+            layers: Vec::new(),
+            severity_overrides: BTreeMap::new(),
         }
     }
 }
+
+impl IssueThresholds {
+    /// `(max_functions, max_types, max_impls)` for `module`.
+    ///
+    /// An override applies to its module and every submodule; the most specific
+    /// override wins, and limits it leaves unset stay global.
+    pub fn god_module_limits(&self, module: &str) -> (usize, usize, usize) {
+        let limits = self
+            .god_module_overrides
+            .iter()
+            .map(|(path, limits)| (path.strip_prefix("crate::").unwrap_or(path), limits))
+            .filter(|(path, _)| {
+                module
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(path, _)| path.len())
+            .map(|(_, limits)| *limits)
+            .unwrap_or_default();
+        (
+            limits.max_functions.unwrap_or(self.max_functions),
+            limits.max_types.unwrap_or(self.max_types),
+            limits.max_impls.unwrap_or(self.max_impls),
+        )
+    }
+}
@@ -1,5 +1,8 @@
+use serde::Deserialize;
+
 /// Issue severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Minor issue, consider addressing
     Low,
@@ -11,6 +14,30 @@ pub enum Severity {
     Critical,
 }
 
+/// Severity configured for an issue type in `[severity]`; `off` drops the issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityOverride {
+    Off,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl SeverityOverride {
+    /// The severity issues are reported at, `None` when they are turned off
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            SeverityOverride::Off => None,
+            SeverityOverride::Low => Some(Severity::Low),
+            SeverityOverride::Medium => Some(Severity::Medium),
+            SeverityOverride::High => Some(Severity::High),
+            SeverityOverride::Critical => Some(Severity::Critical),
+        }
+    }
+}
+
 impl std::fmt::Display for Severity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
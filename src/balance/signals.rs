@@ -1,14 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::metrics::dimensions::Subdomain;
+use crate::metrics::dimensions::{IntegrationStrength, Subdomain};
 use crate::metrics::project::ProjectMetrics;
 
 use super::action::RefactoringAction;
 use super::issue::CouplingIssue;
 use super::issue_type::IssueType;
+use super::score::IssueThresholds;
 use super::severity::Severity;
 
-pub(crate) fn analyze_hidden_temporal_coupling(metrics: &ProjectMetrics) -> Vec<CouplingIssue> {
+pub(crate) fn analyze_hidden_temporal_coupling(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<CouplingIssue> {
     let file_to_module = build_file_to_module_map(metrics);
     let mut seen = HashSet::new();
     let mut issues = Vec::new();
@@ -32,8 +36,16 @@ pub(crate) fn analyze_hidden_temporal_coupling(metrics: &ProjectMetrics) -> Vec<
         // or siblings in one package) co-change because they form one cohesive
         // unit; hidden coupling's premise (a missing abstraction between DISTANT
         // files) does not apply to them.
+        // A Contract-only dependency does not explain strong co-change: the trait
+        // boundary is meant to absorb change, so the pair shares more than it shows.
+        let code_coupling = strongest_code_coupling(
+            metrics,
+            &source,
+            &target,
+            thresholds.balance_formula.visibility_penalty,
+        );
         if source == target
-            || has_code_coupling(metrics, &source, &target)
+            || code_coupling.is_some_and(|strength| strength != IntegrationStrength::Contract)
             || super::coupling::is_entrypoint_module(&source)
             || super::coupling::is_entrypoint_module(&target)
             || is_facade_module(metrics, &source)
@@ -62,10 +74,17 @@ pub(crate) fn analyze_hidden_temporal_coupling(metrics: &ProjectMetrics) -> Vec<
             },
             source: stable_source,
             target: stable_target,
-            description: format!(
-                "Strong temporal co-change without code dependency ({:.0}% ratio, {} co-changes)",
-                ratio_pct, temporal.co_change_count
-            ),
+            description: if code_coupling.is_some() {
+                format!(
+                    "Strong temporal co-change over only a Contract dependency ({:.0}% ratio, {} co-changes)",
+                    ratio_pct, temporal.co_change_count
+                )
+            } else {
+                format!(
+                    "Strong temporal co-change without code dependency ({:.0}% ratio, {} co-changes)",
+                    ratio_pct, temporal.co_change_count
+                )
+            },
             refactoring: RefactoringAction::General {
                 action: "Extract a shared abstraction or make the dependency explicit".to_string(),
             },
@@ -125,7 +144,7 @@ fn is_facade_module(metrics: &ProjectMetrics, module_name: &str) -> bool {
         .is_some_and(|module| module.path.file_name() == Some(std::ffi::OsStr::new("lib.rs")))
 }
 
-fn build_file_to_module_map(metrics: &ProjectMetrics) -> Vec<(String, String)> {
+pub(crate) fn build_file_to_module_map(metrics: &ProjectMetrics) -> Vec<(String, String)> {
     metrics
         .modules
         .iter()
@@ -133,7 +152,10 @@ fn build_file_to_module_map(metrics: &ProjectMetrics) -> Vec<(String, String)> {
         .collect()
 }
 
-fn module_for_file(file_path: &str, file_to_module: &[(String, String)]) -> Option<String> {
+pub(crate) fn module_for_file(
+    file_path: &str,
+    file_to_module: &[(String, String)],
+) -> Option<String> {
     let normalized_file = normalize_path_str(file_path);
     file_to_module
         .iter()
@@ -143,13 +165,29 @@ fn module_for_file(file_path: &str, file_to_module: &[(String, String)]) -> Opti
 
 /// Checks explicit couplings by assuming coupling source/target names end with
 /// the short module name stored in `ProjectMetrics::modules`.
-fn has_code_coupling(metrics: &ProjectMetrics, module_a: &str, module_b: &str) -> bool {
-    metrics.couplings.iter().any(|coupling| {
-        module_names_match(&coupling.source, module_a)
-            && module_names_match(&coupling.target, module_b)
-            || module_names_match(&coupling.source, module_b)
-                && module_names_match(&coupling.target, module_a)
-    })
+pub(crate) fn has_code_coupling(metrics: &ProjectMetrics, module_a: &str, module_b: &str) -> bool {
+    strongest_code_coupling(metrics, module_a, module_b, false).is_some()
+}
+
+/// Strongest scoring strength of the explicit couplings between two modules,
+/// in either direction (same name matching as `has_code_coupling`).
+fn strongest_code_coupling(
+    metrics: &ProjectMetrics,
+    module_a: &str,
+    module_b: &str,
+    visibility_penalty: bool,
+) -> Option<IntegrationStrength> {
+    metrics
+        .couplings
+        .iter()
+        .filter(|coupling| {
+            module_names_match(&coupling.source, module_a)
+                && module_names_match(&coupling.target, module_b)
+                || module_names_match(&coupling.source, module_b)
+                    && module_names_match(&coupling.target, module_a)
+        })
+        .map(|coupling| coupling.scoring_strength(visibility_penalty))
+        .max_by(|a, b| a.value().total_cmp(&b.value()))
 }
 
 fn module_names_match(coupling_module: &str, module_name: &str) -> bool {
@@ -0,0 +1,226 @@
+//! Benchmarking against reference coupling profiles.
+//!
+//! A small set of `CouplingProfile`s measured by this tool on well-known
+//! crates is embedded in the binary. Comparing a project against them shows
+//! whether its coupling style is typical, e.g. "your intrusive-coupling rate is
+//! higher than 80% of reference crates".
+
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::profile::CouplingProfile;
+
+/// Reference profiles measured by `scripts/reference-profiles.sh`
+/// (`cargo coupling --json --no-git --no-cache <crate>/src`).
+const REFERENCE_PROFILES_JSON: &str = include_str!("reference_profiles.json");
+
+/// Coupling profile of a well-known crate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceProfile {
+    /// Crate name.
+    pub name: String,
+    /// Crate version the profile was measured on.
+    pub version: String,
+    /// Number of couplings the profile was computed from.
+    pub couplings: usize,
+    /// Normalized coupling profile.
+    pub profile: CouplingProfile,
+}
+
+/// Profile distance to one reference crate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceMatch {
+    /// Reference crate name.
+    pub name: String,
+    /// `CouplingProfile::distance_to` result (0.0 identical, 1.0 disjoint).
+    pub distance: f64,
+}
+
+/// Where one profile share ranks among the reference crates.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DimensionPercentile {
+    /// Human-readable name, e.g. `intrusive-coupling rate`.
+    pub dimension: &'static str,
+    /// The analyzed project's share (0.0-1.0).
+    pub value: f64,
+    /// Percentage of reference crates with a strictly lower share.
+    pub higher_than: f64,
+    /// Percentage of reference crates with a strictly higher share.
+    pub lower_than: f64,
+}
+
+impl DimensionPercentile {
+    /// One-line comparison, e.g. `your intrusive-coupling rate (12%) is higher than 80% of reference crates`.
+    pub fn sentence(&self) -> String {
+        let value = self.value * 100.0;
+        if self.higher_than >= self.lower_than {
+            format!(
+                "your {} ({:.0}%) is higher than {:.0}% of reference crates",
+                self.dimension, value, self.higher_than
+            )
+        } else {
+            format!(
+                "your {} ({:.0}%) is lower than {:.0}% of reference crates",
+                self.dimension, value, self.lower_than
+            )
+        }
+    }
+}
+
+/// Comparison of a project's profile against the reference set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchmarkReport {
+    /// The analyzed project's profile.
+    pub profile: CouplingProfile,
+    /// Number of reference crates compared against.
+    pub reference_count: usize,
+    /// Distance to the crate requested with `--compare-to-crate=NAME`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<ReferenceMatch>,
+    /// All reference crates, closest first.
+    pub nearest: Vec<ReferenceMatch>,
+    /// Rank of each profile share among the reference crates.
+    pub dimensions: Vec<DimensionPercentile>,
+}
+
+/// Accessor for one share of a `CouplingProfile`.
+type ProfileShare = fn(&CouplingProfile) -> f64;
+
+/// Reference profiles embedded in the binary.
+pub fn reference_profiles() -> Vec<ReferenceProfile> {
+    serde_json::from_str(REFERENCE_PROFILES_JSON).expect("embedded reference profiles are valid")
+}
+
+/// Compare `profile` against `references`, optionally focusing on one crate.
+///
+/// Returns `None` when `target` names no reference crate.
+pub fn benchmark_profile(
+    profile: &CouplingProfile,
+    references: &[ReferenceProfile],
+    target: Option<&str>,
+) -> Option<BenchmarkReport> {
+    let target = match target {
+        Some(name) => {
+            let reference = references.iter().find(|reference| reference.name == name)?;
+            Some(ReferenceMatch {
+                name: reference.name.clone(),
+                distance: profile.distance_to(&reference.profile),
+            })
+        }
+        None => None,
+    };
+
+    let mut nearest: Vec<ReferenceMatch> = references
+        .iter()
+        .map(|reference| ReferenceMatch {
+            name: reference.name.clone(),
+            distance: profile.distance_to(&reference.profile),
+        })
+        .collect();
+    nearest.sort_by(|a, b| {
+        a.distance
+            .total_cmp(&b.distance)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let shares: [(&'static str, ProfileShare); 7] = [
+        ("intrusive-coupling rate", |p| p.intrusive),
+        ("functional-coupling rate", |p| p.functional),
+        ("model-coupling rate", |p| p.model),
+        ("contract-coupling rate", |p| p.contract),
+        ("same-module share", |p| p.same_module),
+        ("different-module share", |p| p.different_module),
+        ("external share", |p| p.external),
+    ];
+    let dimensions = shares
+        .iter()
+        .map(|(dimension, share)| {
+            let value = share(profile);
+            let count = references.len().max(1) as f64;
+            let below = references
+                .iter()
+                .filter(|reference| share(&reference.profile) < value)
+                .count();
+            let above = references
+                .iter()
+                .filter(|reference| share(&reference.profile) > value)
+                .count();
+            DimensionPercentile {
+                dimension,
+                value,
+                higher_than: below as f64 / count * 100.0,
+                lower_than: above as f64 / count * 100.0,
+            }
+        })
+        .collect();
+
+    Some(BenchmarkReport {
+        profile: *profile,
+        reference_count: references.len(),
+        target,
+        nearest,
+        dimensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(name: &str, intrusive: f64) -> ReferenceProfile {
+        ReferenceProfile {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            couplings: 100,
+            profile: CouplingProfile {
+                intrusive,
+                functional: 0.5 - intrusive,
+                model: 0.5,
+                different_module: 0.5,
+                external: 0.5,
+                ..CouplingProfile::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_embedded_reference_profiles_parse_and_are_normalized() {
+        let references = reference_profiles();
+        assert!(references.len() >= 10);
+        for reference in &references {
+            let p = &reference.profile;
+            let strength = p.intrusive + p.functional + p.model + p.contract;
+            let distance = p.same_module + p.different_module + p.external;
+            assert!((strength - 1.0).abs() < 0.01, "{}", reference.name);
+            assert!((distance - 1.0).abs() < 0.01, "{}", reference.name);
+        }
+    }
+
+    #[test]
+    fn test_synthetic_profile_is_ranked_against_references() {
+        let references: Vec<ReferenceProfile> = [0.0, 0.02, 0.05, 0.1, 0.3]
+            .iter()
+            .enumerate()
+            .map(|(idx, intrusive)| reference(&format!("ref{idx}"), *intrusive))
+            .collect();
+        let profile = CouplingProfile {
+            intrusive: 0.25,
+            functional: 0.25,
+            model: 0.5,
+            different_module: 0.5,
+            external: 0.5,
+            ..CouplingProfile::default()
+        };
+
+        let report = benchmark_profile(&profile, &references, Some("ref4")).unwrap();
+        let intrusive = &report.dimensions[0];
+        assert_eq!(intrusive.higher_than, 80.0);
+        assert_eq!(
+            intrusive.sentence(),
+            "your intrusive-coupling rate (25%) is higher than 80% of reference crates"
+        );
+        assert_eq!(report.nearest[0].name, "ref4");
+        assert!((report.target.unwrap().distance - 0.025).abs() < 1e-9);
+
+        assert!(benchmark_profile(&profile, &references, Some("unknown")).is_none());
+    }
+}
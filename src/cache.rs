@@ -0,0 +1,323 @@
+//! On-disk cache of per-file analysis results (`.coupling-cache/`).
+//!
+//! Parsing dominates analysis time, and between two runs on a large codebase
+//! most files are unchanged. Each `AnalyzedFileResult` is stored under a key
+//! hashed from the cache schema version, the analyzer version, the per-file
+//! analysis settings, the file path and the file content, so any change to one
+//! of them is a miss rather than a stale hit. Entries also record what they
+//! were built from and are checked again on load. The cache is best-effort:
+//! unreadable or unwritable entries fall back to parsing.
+//!
+//! `prune` keeps the directory bounded: only the newest entry per file and
+//! settings survives, and entries nobody has read for `MAX_ENTRY_AGE` go.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::AnalyzedFileResult;
+use crate::header::{ANALYZER_VERSION, stable_hash};
+
+/// Directory the CLI keeps the cache in, relative to the project root.
+pub const CACHE_DIR: &str = ".coupling-cache";
+
+/// Bumped whenever the layout or the per-file analysis of a cached `AnalyzedFileResult` changes.
+const SCHEMA_VERSION: u32 = 21;
+
+/// Entries neither read nor written for this long are removed by `prune`.
+const MAX_ENTRY_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Cached results, one JSON file per (settings, path, content) key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisCache {
+    dir: PathBuf,
+}
+
+/// One cache file; the header fields guard against key collisions.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<R> {
+    schema_version: u32,
+    analyzer_version: String,
+    settings: String,
+    path: PathBuf,
+    content_hash: String,
+    result: R,
+}
+
+impl AnalysisCache {
+    /// Cache stored in `dir`, created on the first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache for analyzing `analysis_path`, kept in the project root's `CACHE_DIR`.
+    ///
+    /// The project root is the config file's directory when there is one, else
+    /// the nearest directory above `analysis_path` with a `Cargo.toml`, else
+    /// `analysis_path` itself, so the cache does not depend on the working directory.
+    pub fn for_project(analysis_path: &Path, config_root: Option<&Path>) -> Self {
+        let root = config_root.map(Path::to_path_buf).unwrap_or_else(|| {
+            let start = std::path::absolute(analysis_path).unwrap_or_else(|_| analysis_path.into());
+            start
+                .ancestors()
+                .find(|dir| dir.join("Cargo.toml").is_file())
+                .map_or(start.clone(), Path::to_path_buf)
+        });
+        Self::new(root.join(CACHE_DIR))
+    }
+
+    /// Directory holding the cache files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Remove entries that a newer entry for the same file and settings
+    /// supersedes, and entries unused for `MAX_ENTRY_AGE`; failures are ignored.
+    pub fn prune(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let now = SystemTime::now();
+        let mut slots: HashMap<String, Vec<(SystemTime, PathBuf)>> = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) else {
+                continue;
+            };
+            let expired = now
+                .duration_since(modified)
+                .is_ok_and(|age| age > MAX_ENTRY_AGE);
+            if name.ends_with(".tmp") {
+                // A partial write from an interrupted run; a live one is never this old
+                if expired {
+                    let _ = fs::remove_file(&path);
+                }
+                continue;
+            }
+            let Some(stem) = name.strip_suffix(".json") else {
+                continue;
+            };
+            // Entries from before the `slot-content` naming can never be hit again
+            match stem.split_once('-') {
+                Some((slot, _)) if !expired => {
+                    slots
+                        .entry(slot.to_string())
+                        .or_default()
+                        .push((modified, path));
+                }
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+
+        for mut entries in slots.into_values() {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+            for (_, path) in entries.into_iter().skip(1) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    /// Cached result for `path` with this `content`, analyzed with `settings`.
+    pub(crate) fn load(
+        &self,
+        path: &Path,
+        content: &str,
+        settings: &str,
+    ) -> Option<AnalyzedFileResult> {
+        let content_hash = stable_hash(content);
+        let entry_path = self.entry_path(path, &content_hash, settings);
+        let raw = fs::read(&entry_path).ok()?;
+        let entry: CacheEntry<AnalyzedFileResult> = serde_json::from_slice(&raw).ok()?;
+        let valid = entry.schema_version == SCHEMA_VERSION
+            && entry.analyzer_version == ANALYZER_VERSION
+            && entry.settings == settings
+            && entry.path == path
+            && entry.content_hash == content_hash;
+        if valid {
+            // A hit counts as use, so `prune` keeps entries that are still read
+            let _ = fs::File::options()
+                .write(true)
+                .open(&entry_path)
+                .and_then(|file| file.set_modified(SystemTime::now()));
+        }
+        valid.then_some(entry.result)
+    }
+
+    /// Store `result` for `path` with this `content`; failures are ignored.
+    pub(crate) fn store(
+        &self,
+        path: &Path,
+        content: &str,
+        settings: &str,
+        result: &AnalyzedFileResult,
+    ) {
+        let content_hash = stable_hash(content);
+        let entry = CacheEntry {
+            schema_version: SCHEMA_VERSION,
+            analyzer_version: ANALYZER_VERSION.to_string(),
+            settings: settings.to_string(),
+            path: path.to_path_buf(),
+            content_hash: content_hash.clone(),
+            result,
+        };
+        let Ok(json) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        if !self.dir.exists() {
+            if fs::create_dir_all(&self.dir).is_err() {
+                return;
+            }
+            // Keep the cache out of version control without touching the project's .gitignore
+            let _ = fs::write(self.dir.join(".gitignore"), "*\n");
+        }
+
+        // Write then rename so a concurrent or interrupted run never reads half an entry
+        let entry_path = self.entry_path(path, &content_hash, settings);
+        let partial = entry_path.with_extension(format!("{}.tmp", std::process::id()));
+        if fs::write(&partial, json).is_ok() && fs::rename(&partial, &entry_path).is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+    }
+
+    /// `<slot>-<content>.json`: the slot names the file and settings, so
+    /// `prune` can tell which entries replace each other.
+    fn entry_path(&self, path: &Path, content_hash: &str, settings: &str) -> PathBuf {
+        let slot = stable_hash(&format!(
+            "{}\u{1f}{}\u{1f}{}\u{1f}{}",
+            SCHEMA_VERSION,
+            ANALYZER_VERSION,
+            settings,
+            path.display()
+        ));
+        self.dir.join(format!("{}-{}.json", slot, content_hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::analyze_rust_file_full;
+
+    #[test]
+    fn test_entries_hit_only_for_the_same_path_content_settings_and_version() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("orders.rs");
+        let content =
+            "pub struct Order { pub total: u64 }\npub fn total(o: &Order) -> u64 { o.total }\n";
+        std::fs::write(&file, content).unwrap();
+        let result = analyze_rust_file_full(&file).unwrap();
+
+        let cache = AnalysisCache::new(tmp.path().join(CACHE_DIR));
+        assert!(cache.load(&file, content, "tests=false").is_none());
+        cache.store(&file, content, "tests=false", &result);
+        assert!(cache.dir().join(".gitignore").is_file());
+
+        let cached = cache.load(&file, content, "tests=false").unwrap();
+        assert_eq!(cached.metrics.name, result.metrics.name);
+        assert_eq!(cached.dependencies.len(), result.dependencies.len());
+        assert_eq!(cached.type_visibility, result.type_visibility);
+        assert_eq!(
+            cached.metrics.function_definitions.len(),
+            result.metrics.function_definitions.len()
+        );
+
+        assert!(
+            cache
+                .load(&file, "pub struct Order;\n", "tests=false")
+                .is_none()
+        );
+        assert!(cache.load(&file, content, "tests=true").is_none());
+        assert!(
+            cache
+                .load(&tmp.path().join("other.rs"), content, "tests=false")
+                .is_none()
+        );
+
+        // An entry written by another analyzer version is never used
+        let entry_path = cache.entry_path(&file, &stable_hash(content), "tests=false");
+        let stale = std::fs::read_to_string(&entry_path).unwrap().replacen(
+            ANALYZER_VERSION,
+            "0.0.0-old",
+            1,
+        );
+        std::fs::write(&entry_path, stale).unwrap();
+        assert!(cache.load(&file, content, "tests=false").is_none());
+    }
+
+    #[test]
+    fn test_prune_keeps_the_newest_entry_per_file_and_drops_unused_ones() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("orders.rs");
+        let other = tmp.path().join("other.rs");
+        std::fs::write(&file, "pub struct Order;\n").unwrap();
+        let result = analyze_rust_file_full(&file).unwrap();
+
+        let cache = AnalysisCache::new(tmp.path().join(CACHE_DIR));
+        cache.store(&file, "pub struct Order;\n", "tests=false", &result);
+        cache.store(&file, "pub struct Order(u64);\n", "tests=false", &result);
+        cache.store(&other, "pub struct Other;\n", "tests=false", &result);
+
+        let set_age = |content: &str, path: &Path, days: u64| {
+            let entry = cache.entry_path(path, &stable_hash(content), "tests=false");
+            std::fs::File::options()
+                .write(true)
+                .open(entry)
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60))
+                .unwrap();
+        };
+        set_age("pub struct Order;\n", &file, 2);
+        set_age("pub struct Other;\n", &other, 31);
+
+        cache.prune();
+
+        assert!(
+            cache
+                .load(&file, "pub struct Order(u64);\n", "tests=false")
+                .is_some()
+        );
+        assert!(
+            cache
+                .load(&file, "pub struct Order;\n", "tests=false")
+                .is_none(),
+            "superseded by the newer content"
+        );
+        assert!(
+            cache
+                .load(&other, "pub struct Other;\n", "tests=false")
+                .is_none(),
+            "unused for longer than MAX_ENTRY_AGE"
+        );
+        assert!(cache.dir().join(".gitignore").is_file());
+    }
+
+    #[test]
+    fn test_cache_is_anchored_at_the_project_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("crate").join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(tmp.path().join("crate").join("Cargo.toml"), "[package]\n").unwrap();
+
+        assert_eq!(
+            AnalysisCache::for_project(&src, None).dir(),
+            tmp.path().join("crate").join(CACHE_DIR)
+        );
+        assert_eq!(
+            AnalysisCache::for_project(&src, Some(tmp.path())).dir(),
+            tmp.path().join(CACHE_DIR)
+        );
+        let loose = tmp.path().join("loose");
+        assert_eq!(
+            AnalysisCache::for_project(&loose, None).dir(),
+            loose.join(CACHE_DIR)
+        );
+    }
+}
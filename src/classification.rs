@@ -1,15 +1,17 @@
 //! Coupling classification: given a detected dependency, decide its target
-//! module (including re-exported bare type names via the type registry), its
+//! module (following `pub use` re-exports to the defining module, and
+//! re-exported bare type names via the type registry), its
 //! structural distance (module-tree adjacency), and its integration strength
 //! (Rust-faithful: published data-model access is Model, unpublished access is
 //! Intrusive). Extracted from `analyzer` so parsing and classification stay
 //! separately cohesive.
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::analyzer::{Dependency, UsageContext};
 use crate::discovery::join_module_path;
 use crate::metrics::dimensions::{Distance, IntegrationStrength, Visibility};
+use crate::metrics::module::ModuleMetrics;
 use crate::metrics::project::ProjectMetrics;
 use crate::workspace::WorkspaceInfo;
 
@@ -73,6 +75,27 @@ pub(crate) fn is_adjacent_module(source_module: &str, target_module: &str) -> bo
     !source_parent.is_empty() && source_parent == target_parent
 }
 
+/// Distance score of a same-crate `DifferentModule` coupling from the module tree.
+///
+/// Root-level siblings keep `DifferentModule`'s 0.5. Every shared ancestor
+/// segment brings the modules 0.1 closer and every hop beyond the two between
+/// siblings moves them 0.05 apart, bounded to stay clear of `SameModule` (0.25)
+/// and `DifferentCrate` (1.0).
+pub(crate) fn module_distance_score(source_module: &str, target_module: &str) -> f64 {
+    let source_segments: Vec<&str> = source_module.split("::").collect();
+    let target_segments: Vec<&str> = target_module.split("::").collect();
+    let shared = source_segments
+        .iter()
+        .zip(&target_segments)
+        .take_while(|(source, target)| source == target)
+        .count();
+    let hops = source_segments.len() + target_segments.len() - 2 * shared;
+
+    let score = Distance::DifferentModule.value() - 0.1 * shared as f64
+        + 0.05 * hops.saturating_sub(2) as f64;
+    score.clamp(0.3, 0.75)
+}
+
 pub(crate) fn target_type_name(path: &str) -> Option<&str> {
     path.trim_end_matches("::*")
         .rsplit("::")
@@ -106,6 +129,37 @@ pub(crate) fn visibility_for_dependency(
     })
 }
 
+/// Explain how a dependency was classified, for verbose provenance output.
+pub(crate) fn describe_classification(
+    dep: &Dependency,
+    strength: IntegrationStrength,
+    distance: Distance,
+    target_module: &str,
+    target_is_known_internal_module: bool,
+) -> String {
+    let distance_reason = match distance {
+        Distance::SameFunction | Distance::SameModule => {
+            format!("`{}` is adjacent in the module tree", target_module)
+        }
+        Distance::DifferentModule if target_is_known_internal_module => {
+            format!("`{}` is a non-adjacent internal module", target_module)
+        }
+        Distance::DifferentModule => format!("`{}` is another workspace crate", target_module),
+        Distance::DifferentCrate => {
+            format!("`{}` is outside the analyzed modules", target_module)
+        }
+    };
+    let origin = if dep.from_macro {
+        " (found in a macro body)"
+    } else {
+        ""
+    };
+    format!(
+        "strength {:?}: {:?} usage of `{}`{}; distance {:?}: {}",
+        strength, dep.usage, dep.path, origin, distance, distance_reason
+    )
+}
+
 /// Extract target module name from a path
 pub(crate) fn extract_target_module(path: &str) -> String {
     // Remove common prefixes and get the module name
@@ -124,7 +178,11 @@ pub(crate) fn resolve_target_module(
     known_modules: &HashSet<String>,
     project: &ProjectMetrics,
 ) -> String {
-    let resolved = resolve_relative_module_path(path, source_module);
+    let mut resolved = resolve_relative_module_path(path, source_module);
+    // Bare names are local or imported items; only qualified paths go through a re-export
+    if path.contains("::") {
+        resolved = project.resolve_reexport(&resolved);
+    }
     let segments: Vec<&str> = resolved
         .split("::")
         .filter(|segment| !segment.is_empty())
@@ -147,6 +205,44 @@ pub(crate) fn resolve_target_module(
     extract_target_module(path)
 }
 
+/// Register each `pub use` of `module` under the crate-relative path it is re-exported as.
+///
+/// Glob re-exports cannot be keyed by name and are skipped. The exported name is
+/// the last segment of the used path, so a renamed re-export (`as`) is keyed by
+/// its original name and never matches a use site.
+pub(crate) fn register_reexports(
+    project: &mut ProjectMetrics,
+    module: &ModuleMetrics,
+    known_modules: &HashSet<String>,
+) {
+    let is_crate_root = !module.name.contains("::")
+        && module
+            .path
+            .file_name()
+            .is_some_and(|name| name == "lib.rs" || name == "main.rs");
+    let exporting = if is_crate_root {
+        ""
+    } else {
+        module.name.as_str()
+    };
+
+    for path in &module.reexports {
+        let Some(name) = path.rsplit("::").next().filter(|name| *name != "*") else {
+            continue;
+        };
+        let first = path.split("::").next().unwrap_or(path);
+        // Unprefixed paths name a child of the exporting module when one exists
+        let original = if !should_resolve_bare_type(path)
+            && known_modules.contains(&join_module_path(exporting, first))
+        {
+            join_module_path(exporting, path)
+        } else {
+            resolve_relative_module_path(path, exporting)
+        };
+        project.register_reexport(join_module_path(exporting, name), original);
+    }
+}
+
 pub(crate) fn should_resolve_bare_type(path: &str) -> bool {
     !path.contains("::")
         || path.starts_with("crate::")
@@ -182,70 +278,122 @@ pub(crate) fn resolve_relative_module_path(path: &str, source_module: &str) -> S
     join_module_path(&prefix, rest)
 }
 
-/// Check if a path looks like a valid module/type reference (not a local variable)
-pub(crate) fn is_valid_dependency_path(path: &str) -> bool {
-    // Skip empty paths
-    if path.is_empty() {
-        return false;
-    }
+/// Names treated as local variables when they end a path of at most two segments.
+pub const DEFAULT_LOCAL_VARIABLE_NAMES: &[&str] = &[
+    "request",
+    "response",
+    "result",
+    "content",
+    "config",
+    "proto",
+    "domain",
+    "info",
+    "data",
+    "item",
+    "value",
+    "error",
+    "message",
+    "expected",
+    "actual",
+    "status",
+    "state",
+    "context",
+    "params",
+    "args",
+    "options",
+    "settings",
+    "violation",
+    "page_token",
+];
+
+/// Single-segment all-lowercase names up to this length are treated as local variables.
+pub const DEFAULT_MAX_LOCAL_NAME_LEN: usize = 8;
+
+/// Heuristics that tell local variable accesses apart from module and type paths.
+///
+/// Both heuristics are guesses; a qualified path that resolves to a module of
+/// the project is kept whatever its name (see [`keeps`](Self::keeps)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyPathFilter {
+    /// Last segments dropped from paths of one or two segments (`local_variable_names`)
+    local_names: BTreeSet<String>,
+    /// Longest single-segment lowercase name dropped, 0 for none (`max_local_name_len`)
+    max_local_name_len: usize,
+}
 
-    // Skip Self references
-    if path == "Self" || path.starts_with("Self::") {
-        return false;
+impl Default for DependencyPathFilter {
+    fn default() -> Self {
+        Self {
+            local_names: DEFAULT_LOCAL_VARIABLE_NAMES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            max_local_name_len: DEFAULT_MAX_LOCAL_NAME_LEN,
+        }
     }
+}
 
-    let segments: Vec<&str> = path.split("::").collect();
+impl DependencyPathFilter {
+    /// Filter settings from `[analysis]` config values.
+    pub fn new(local_names: &[String], max_local_name_len: usize) -> Self {
+        Self {
+            local_names: local_names.iter().cloned().collect(),
+            max_local_name_len,
+        }
+    }
 
-    // Skip short single-segment lowercase names (likely local variables)
-    if segments.len() == 1 {
-        let name = segments[0];
-        if name.len() <= 8 && name.chars().all(|c| c.is_lowercase() || c == '_') {
-            return false;
+    /// Whether a dependency on `path`, resolved to `target_module`, is kept.
+    pub fn keeps(&self, path: &str, target_module: &str, target_is_known_module: bool) -> bool {
+        // A qualified path into a module of the project is never a local variable
+        if target_is_known_module && path.contains("::") {
+            return true;
         }
+        self.is_valid_path(path) && (target_is_known_module || self.is_valid_path(target_module))
     }
 
-    // Skip patterns where last two segments are the same (likely module::type patterns from variables)
-    if segments.len() >= 2 {
-        let last = segments.last().unwrap();
-        let second_last = segments.get(segments.len() - 2).unwrap();
-        if last == second_last {
+    /// Whether `path` itself was taken for a local variable (not `Self`, not an
+    /// external path dropped for its short crate name).
+    pub fn looks_local(&self, path: &str) -> bool {
+        !is_self_path(path) && !self.is_valid_path(path)
+    }
+
+    /// Check if a path looks like a valid module/type reference (not a local variable)
+    pub fn is_valid_path(&self, path: &str) -> bool {
+        // Skip empty paths and Self references
+        if is_self_path(path) {
             return false;
         }
-    }
 
-    // Skip common patterns that look like local variable accesses
-    let last_segment = segments.last().unwrap_or(&path);
-    let common_locals = [
-        "request",
-        "response",
-        "result",
-        "content",
-        "config",
-        "proto",
-        "domain",
-        "info",
-        "data",
-        "item",
-        "value",
-        "error",
-        "message",
-        "expected",
-        "actual",
-        "status",
-        "state",
-        "context",
-        "params",
-        "args",
-        "options",
-        "settings",
-        "violation",
-        "page_token",
-    ];
-    if common_locals.contains(last_segment) && segments.len() <= 2 {
-        return false;
+        let segments: Vec<&str> = path.split("::").collect();
+
+        // Skip short single-segment lowercase names (likely local variables)
+        if segments.len() == 1 {
+            let name = segments[0];
+            if name.len() <= self.max_local_name_len
+                && name.chars().all(|c| c.is_lowercase() || c == '_')
+            {
+                return false;
+            }
+        }
+
+        // Skip patterns where last two segments are the same (likely module::type patterns from variables)
+        if segments.len() >= 2 {
+            let last = segments.last().unwrap();
+            let second_last = segments.get(segments.len() - 2).unwrap();
+            if last == second_last {
+                return false;
+            }
+        }
+
+        // Skip common patterns that look like local variable accesses
+        let last_segment = segments.last().unwrap_or(&path);
+        !(segments.len() <= 2 && self.local_names.contains(*last_segment))
     }
+}
 
-    true
+/// Empty and `Self` paths, which never name another module.
+fn is_self_path(path: &str) -> bool {
+    path.is_empty() || path == "Self" || path.starts_with("Self::")
 }
 
 /// Calculate same-crate structural distance after target resolution.
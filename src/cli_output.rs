@@ -6,25 +6,45 @@
 //! - Check: CI/CD quality gate with exit codes
 //! - JSON: Machine-readable output for automation
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{self, Write};
 
+use schemars::JsonSchema;
 use serde::Serialize;
 
-use crate::balance::grade::HealthGrade;
+use crate::balance::action::Effort;
+use crate::balance::grade::{HealthGrade, ProjectBalanceReport};
 use crate::balance::issue::CouplingIssue;
 use crate::balance::issue_type::IssueType;
-use crate::balance::project::analyze_project_balance_with_thresholds;
-use crate::balance::score::{BalanceScore, IssueThresholds};
+use crate::balance::project::{
+    analyze_crate_balance, analyze_project_balance_with_thresholds, calculate_module_scores_with,
+    worst_couplings,
+};
+use crate::balance::score::{BalanceFormula, BalanceScore, IssueThresholds};
 use crate::balance::severity::Severity;
-use crate::diff::BaselineDiff;
+use crate::benchmark::BenchmarkReport;
+use crate::config::ModuleBudget;
+use crate::diff::{BaselineDiff, DimensionStatsDelta, ModuleScoreDelta};
 use crate::external::{
-    ExternalDependencyReport, ExternalDependencyUsage, analyze_external_dependencies,
+    ExternalDependencyReport, ExternalDependencyUsage,
+    analyze_external_dependencies_with_thresholds,
 };
+use crate::header::OutputHeader;
 use crate::history::HistoryReport;
+use crate::integration_tests::IntegrationTestReport;
+use crate::issue_baseline::IssueBaseline;
 use crate::manifest::AnalysisManifest;
-use crate::metrics::dimensions::Distance;
-use crate::metrics::project::ProjectMetrics;
+use crate::metric_snapshot::SnapshotComparison;
+use crate::metrics::cohesion::ModuleCohesion;
+use crate::metrics::coupling::CouplingLocation;
+use crate::metrics::dimensions::{Distance, IntegrationStrength};
+use crate::metrics::module::{BalanceClassification, DimensionStats, ModuleMetrics};
+use crate::metrics::profile::CouplingProfile;
+use crate::metrics::project::{MutualDirection, ProjectMetrics};
+use crate::metrics::stability::{ModuleStability, StabilityZone, calculate_module_stability};
+use crate::temporal::{CoChangeKind, TemporalCouplingReport};
+use crate::trend::{TrendEntry, TrendHistory, sparkline};
+use crate::visibility::VisibilityAudit;
 use crate::volatility::Volatility;
 
 // ============================================================================
@@ -32,7 +52,7 @@ use crate::volatility::Volatility;
 // ============================================================================
 
 /// A hotspot module that needs attention
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct Hotspot {
     /// Module name
     pub module: String,
@@ -49,13 +69,29 @@ pub struct Hotspot {
 }
 
 /// An issue contributing to a hotspot
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct HotspotIssue {
     pub severity: String,
     pub issue_type: String,
     pub description: String,
 }
 
+/// An issue ranked by impact per unit of estimated effort
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QuickWin {
+    pub issue_type: String,
+    pub severity: String,
+    pub source: String,
+    pub target: String,
+    pub suggestion: String,
+    /// `Low`, `Medium` or `High`, from the suggested refactoring
+    pub estimated_effort: String,
+    /// Modules that benefit: the source plus its direct and second-order dependents
+    pub impact: usize,
+    /// `impact` divided by the effort weight (Low 1, Medium 2, High 4); higher first
+    pub roi: f64,
+}
+
 // ============================================================================
 // Beginner-friendly explanations
 // ============================================================================
@@ -133,6 +169,196 @@ pub fn get_issue_explanation(issue_type: &str) -> IssueExplanation {
             how_to_fix: "Access through public methods or traits instead",
             example: Some("e.g., foo.internal_field -> foo.get_value()"),
         },
+        "Unnecessary Abstraction" => IssueExplanation {
+            what_it_means: "A trait or indirection sits between two closely related, stable parts",
+            why_its_bad: vec![
+                "Extra layers to read for no flexibility gained",
+                "Navigation jumps through code that never varies",
+                "Signals a boundary that does not exist",
+            ],
+            how_to_fix: "Call the concrete type directly until a second implementation appears",
+            example: None,
+        },
+        "Hidden Coupling" => IssueExplanation {
+            what_it_means: "Two files keep changing together although neither imports the other",
+            why_its_bad: vec![
+                "The dependency is invisible in the code",
+                "Changing one file silently requires changing the other",
+                "Reviewers miss the second half of a change",
+            ],
+            how_to_fix: "Make the shared knowledge explicit in one module both depend on",
+            example: Some(
+                "e.g., a wire format duplicated in client.rs and server.rs moves to protocol.rs",
+            ),
+        },
+        "Accidental Volatility" => IssueExplanation {
+            what_it_means: "A module expected to be stable (supporting/generic) changes often",
+            why_its_bad: vec![
+                "Churn spreads to everything that depends on it",
+                "Often points to unclear ownership or design",
+                "Stable-looking code cannot be relied on",
+            ],
+            how_to_fix: "Find out why it changes; move the volatile part out, or reclassify the subdomain",
+            example: None,
+        },
+        "Deprecated Internal Coupling" => IssueExplanation {
+            what_it_means: "This module uses an internal module the config marks as deprecated",
+            why_its_bad: vec![
+                "Each new user makes the legacy module harder to remove",
+                "New code inherits old design decisions",
+            ],
+            how_to_fix: "Depend on the replacement module instead",
+            example: None,
+        },
+        "Layer Violation" => IssueExplanation {
+            what_it_means: "An inner architecture layer depends on an outer one",
+            why_its_bad: vec![
+                "Infrastructure changes ripple into the domain",
+                "The inner layer can no longer be tested or reused on its own",
+            ],
+            how_to_fix: "Invert the dependency: define a trait in the inner layer and implement it in the outer one",
+            example: Some(
+                "e.g., domain declares `trait OrderRepository`, infrastructure implements it",
+            ),
+        },
+        "Shallow Module" => IssueExplanation {
+            what_it_means: "The module's interface is nearly as large as its implementation",
+            why_its_bad: vec![
+                "Callers must learn almost everything inside it",
+                "It hides little complexity for what it costs to use",
+            ],
+            how_to_fix: "Merge it with its callers, or give it a smaller interface over more logic",
+            example: None,
+        },
+        "Pass-Through Method" => IssueExplanation {
+            what_it_means: "A function only forwards its call to another function",
+            why_its_bad: vec![
+                "Adds a layer without adding behavior",
+                "Suggests unclear division of responsibility",
+            ],
+            how_to_fix: "Call the delegate directly, or give the wrapper real work to do",
+            example: Some("e.g., fn load(&self) { self.store.load() } -> use store directly"),
+        },
+        "High Cognitive Load" => IssueExplanation {
+            what_it_means: "Too much must be understood at once to work on this module",
+            why_its_bad: vec![
+                "Slow to read and review",
+                "Easy to break something unnoticed",
+                "Hard for newcomers to contribute",
+            ],
+            how_to_fix: "Reduce public API, dependencies and generic parameters; split by concern",
+            example: None,
+        },
+        "God Module" => IssueExplanation {
+            what_it_means: "One module holds too many functions, types or impls",
+            why_its_bad: vec![
+                "Mixes unrelated responsibilities",
+                "Every change touches the same file",
+                "Hard to test or reuse any one part",
+            ],
+            how_to_fix: "Split it into focused modules, one responsibility each",
+            example: Some("e.g., utils.rs -> parsing.rs, formatting.rs, paths.rs"),
+        },
+        "Public Field Exposure" => IssueExplanation {
+            what_it_means: "Other modules read or write a struct's fields directly",
+            why_its_bad: vec![
+                "The struct's layout becomes part of its API",
+                "Invariants cannot be enforced",
+                "Renaming a field touches every user",
+            ],
+            how_to_fix: "Make the fields private and expose methods",
+            example: Some("e.g., order.total -> order.total()"),
+        },
+        "Primitive Obsession" => IssueExplanation {
+            what_it_means: "A function takes several parameters of the same primitive type",
+            why_its_bad: vec![
+                "Arguments are easy to swap by mistake",
+                "The meaning of each value is not in the type",
+            ],
+            how_to_fix: "Wrap the values in newtypes",
+            example: Some(
+                "e.g., fn transfer(from: u64, to: u64) -> fn transfer(from: AccountId, to: AccountId)",
+            ),
+        },
+        "Glob Import" => IssueExplanation {
+            what_it_means: "`use other_module::*` pulls in everything from another module",
+            why_its_bad: vec![
+                "Hides which items are actually used",
+                "New items upstream can clash or shadow names",
+                "Coupling is under-counted",
+            ],
+            how_to_fix: "Import the items you use by name",
+            example: Some("e.g., use crate::models::* -> use crate::models::{Order, User}"),
+        },
+        "Over-Parameterized Type" => IssueExplanation {
+            what_it_means: "A type has so many generic parameters that every user must deal with all of them",
+            why_its_bad: vec![
+                "Long, noisy signatures at every use site",
+                "Adding a parameter breaks all users",
+            ],
+            how_to_fix: "Group parameters into a trait with associated types, or fix some with type aliases",
+            example: None,
+        },
+        "Spread Coupling" => IssueExplanation {
+            what_it_means: "The same field or function is used directly from many modules",
+            why_its_bad: vec![
+                "Changing it means editing every caller",
+                "No single place controls how it is used",
+            ],
+            how_to_fix: "Route access through one accessor or facade",
+            example: None,
+        },
+        "Low Cohesion" => IssueExplanation {
+            what_it_means: "The module's functions form groups that use completely different types",
+            why_its_bad: vec![
+                "Unrelated code shares one file and one name",
+                "Readers must skip whole groups to find what they need",
+                "Changes to one group churn the module for everyone",
+            ],
+            how_to_fix: "Move each group of functions and its types into its own module",
+            example: Some("e.g., shop.rs with Order and Invoice code -> order.rs, invoice.rs"),
+        },
+        "Mixed Async Boundary" => IssueExplanation {
+            what_it_means: "A module exposes many async functions alongside many blocking public functions",
+            why_its_bad: vec![
+                "Sync callers end up blocking on async work",
+                "A common source of executor stalls",
+            ],
+            how_to_fix: "Split the async and sync APIs into separate modules",
+            example: None,
+        },
+        "Unsafe Surface" => IssueExplanation {
+            what_it_means: "A module with a public API contains many unsafe blocks or unsafe functions",
+            why_its_bad: vec![
+                "Soundness depends on invariants the compiler cannot check",
+                "Callers couple to those invariants through the public API",
+                "Any change here needs an unsafe review",
+            ],
+            how_to_fix: "Confine unsafe to a small private module behind a safe API, and document each SAFETY invariant",
+            example: Some("e.g., move raw pointer handling from buffer.rs into buffer/raw.rs"),
+        },
+        "Duplicated Structure" => IssueExplanation {
+            what_it_means: "Structs in different modules have the same fields with the same types",
+            why_its_bad: vec![
+                "A change to the shape must be repeated in every copy",
+                "Copies drift apart and need conversion code between them",
+            ],
+            how_to_fix: "Keep one shared type, or allowlist the duplication if the copies are meant to evolve separately",
+            example: Some(
+                "e.g., api::UserDto and db::UserRow with the same fields -> one model::User",
+            ),
+        },
+        "Deref Abuse" => IssueExplanation {
+            what_it_means: "A type implements Deref to a richer type than a primitive or a type parameter",
+            why_its_bad: vec![
+                "Every method of the target is callable on the type, so callers couple to it without naming it",
+                "The type can no longer change its representation without breaking them",
+            ],
+            how_to_fix: "Expose the operations callers need as methods, or an explicit as_inner() accessor",
+            example: Some(
+                "e.g., OrderBook derefs to Vec<Order> -> OrderBook::iter() and OrderBook::len()",
+            ),
+        },
         _ => IssueExplanation {
             what_it_means: "A coupling-related issue was detected",
             why_its_bad: vec![
@@ -157,6 +383,31 @@ pub struct IssueExplanation {
     pub example: Option<&'static str>,
 }
 
+/// Generate `coupling explain <issue-type>`: the description and beginner
+/// explanation of one issue type.
+pub fn generate_explain_output<W: Write>(issue_type: IssueType, writer: &mut W) -> io::Result<()> {
+    let explanation = get_issue_explanation(&issue_type.to_string());
+
+    writeln!(writer, "{} ({})", issue_type, issue_type.rule_name())?;
+    writeln!(writer)?;
+    writeln!(writer, "{}", issue_type.description())?;
+    writeln!(writer)?;
+    writeln!(writer, "💡 What it means:")?;
+    writeln!(writer, "   {}", explanation.what_it_means)?;
+    writeln!(writer)?;
+    writeln!(writer, "⚠️  Why it's a problem:")?;
+    for reason in &explanation.why_its_bad {
+        writeln!(writer, "   • {}", reason)?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "🔧 How to fix:")?;
+    writeln!(writer, "   {}", explanation.how_to_fix)?;
+    if let Some(example) = explanation.example {
+        writeln!(writer, "   {}", example)?;
+    }
+    Ok(())
+}
+
 /// Calculate hotspots from project metrics
 pub fn calculate_hotspots(
     metrics: &ProjectMetrics,
@@ -181,8 +432,9 @@ pub fn calculate_hotspots(
     let mut couplings_in: HashMap<String, usize> = HashMap::new();
     for coupling in &metrics.couplings {
         if coupling.distance != Distance::DifferentCrate {
-            *couplings_out.entry(coupling.source.clone()).or_default() += 1;
-            *couplings_in.entry(coupling.target.clone()).or_default() += 1;
+            let weight = thresholds.balance_formula.weight(coupling);
+            *couplings_out.entry(coupling.source.clone()).or_default() += weight;
+            *couplings_in.entry(coupling.target.clone()).or_default() += weight;
         }
     }
 
@@ -190,6 +442,9 @@ pub fn calculate_hotspots(
     let mut hotspots: Vec<Hotspot> = Vec::new();
 
     for (module, issues) in &module_issues {
+        if metrics.is_prelude_module(module) {
+            continue;
+        }
         let mut score: u32 = 0;
 
         // Base score from issue count and severity
@@ -248,7 +503,7 @@ pub fn calculate_hotspots(
 
     // Also add modules in cycles that don't have other issues
     for module in &cycle_modules {
-        if !module_issues.contains_key(module) {
+        if !module_issues.contains_key(module) && !metrics.is_prelude_module(module) {
             let file_path = metrics
                 .modules
                 .get(module)
@@ -363,6 +618,234 @@ pub fn generate_hotspots_output<W: Write>(
     Ok(())
 }
 
+/// Modules reached by a change to `module`: direct and second-order dependents.
+fn dependents_within_two_hops(metrics: &ProjectMetrics, module: &str) -> HashSet<String> {
+    let internal = || {
+        metrics
+            .couplings
+            .iter()
+            .filter(|c| c.distance != Distance::DifferentCrate)
+    };
+    let direct: HashSet<&str> = internal()
+        .filter(|c| c.target == module && c.source != module)
+        .map(|c| c.source.as_str())
+        .collect();
+    let mut reached: HashSet<String> = internal()
+        .filter(|c| direct.contains(c.target.as_str()) && c.source != module)
+        .map(|c| c.source.clone())
+        .collect();
+    reached.extend(direct.into_iter().map(str::to_string));
+    reached
+}
+
+/// Impact of fixing an issue in each source module: the module itself plus
+/// everything within two dependent hops (the cascading reach of `analyze_impact`).
+fn issue_impacts<'a>(
+    metrics: &ProjectMetrics,
+    issues: impl IntoIterator<Item = &'a CouplingIssue>,
+) -> HashMap<String, usize> {
+    let mut impacts = HashMap::new();
+    for issue in issues {
+        if impacts.contains_key(&issue.source) {
+            continue;
+        }
+        // Type-level sources (`config::Config`) count the reach of their module
+        let mut name = issue.source.as_str();
+        let module = loop {
+            if let Some(module) = find_module(metrics, name) {
+                break Some(module);
+            }
+            match name.rsplit_once("::") {
+                Some((parent, _)) => name = parent,
+                None => break None,
+            }
+        };
+        let reach = module.map_or(0, |module| {
+            dependents_within_two_hops(metrics, &module).len()
+        });
+        impacts.insert(issue.source.clone(), 1 + reach);
+    }
+    impacts
+}
+
+/// Issues with the most impact per unit of estimated effort, best first.
+///
+/// Ties go to the more severe issue, so among equally cheap fixes the one that
+/// matters more for the grade comes first.
+pub fn calculate_quick_wins(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    limit: usize,
+) -> Vec<QuickWin> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    quick_wins_from_report(metrics, &report, limit)
+}
+
+/// `calculate_quick_wins` for an already computed balance report.
+fn quick_wins_from_report(
+    metrics: &ProjectMetrics,
+    report: &ProjectBalanceReport,
+    limit: usize,
+) -> Vec<QuickWin> {
+    let issues: Vec<&CouplingIssue> = report
+        .issues
+        .iter()
+        .filter(|issue| {
+            !issue.issue_type.is_diagnostic() && !metrics.is_prelude_module(&issue.source)
+        })
+        .collect();
+    let impacts = issue_impacts(metrics, issues.iter().copied());
+
+    let mut ranked: Vec<(&CouplingIssue, Effort, usize)> = issues
+        .into_iter()
+        .map(|issue| (issue, issue.refactoring.effort(), impacts[&issue.source]))
+        .collect();
+    ranked.sort_by(|a, b| {
+        let roi = |(_, effort, impact): &(&CouplingIssue, Effort, usize)| {
+            *impact as f64 / effort.weight()
+        };
+        roi(b)
+            .total_cmp(&roi(a))
+            .then_with(|| b.0.severity.cmp(&a.0.severity))
+            .then_with(|| a.0.source.cmp(&b.0.source))
+            .then_with(|| a.0.target.cmp(&b.0.target))
+    });
+
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(issue, effort, impact)| QuickWin {
+            issue_type: issue.issue_type.to_string(),
+            severity: issue.severity.to_string(),
+            source: issue.source.clone(),
+            target: issue.target.clone(),
+            suggestion: issue.refactoring.to_string(),
+            estimated_effort: effort.to_string(),
+            impact,
+            roi: impact as f64 / effort.weight(),
+        })
+        .collect()
+}
+
+/// Generate `--quick-wins N`: high-impact, low-effort fixes first.
+pub fn generate_quick_wins_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    limit: usize,
+    writer: &mut W,
+) -> io::Result<()> {
+    let quick_wins = calculate_quick_wins(metrics, thresholds, limit);
+
+    writeln!(writer, "Top {} Quick Wins (impact per effort)", limit)?;
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+
+    if quick_wins.is_empty() {
+        writeln!(writer)?;
+        writeln!(writer, "✅ No issues to fix.")?;
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+
+    for (i, win) in quick_wins.iter().enumerate() {
+        writeln!(
+            writer,
+            "#{} {}: {} → {} ({})",
+            i + 1,
+            win.issue_type,
+            win.source,
+            win.target,
+            win.severity
+        )?;
+        writeln!(
+            writer,
+            "   Effort: {} · Impact: {} module(s) · ROI: {:.1}",
+            win.estimated_effort, win.impact, win.roi
+        )?;
+        writeln!(writer, "   → Fix: {}", win.suggestion)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Generate `--worst N`: the individually worst-scored internal couplings
+/// across the project, with their location and the factor driving the score.
+pub fn generate_worst_couplings_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    limit: usize,
+    writer: &mut W,
+) -> io::Result<()> {
+    let worst = worst_couplings(metrics, thresholds, limit);
+
+    writeln!(writer, "Worst {} Couplings", limit)?;
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+
+    if worst.is_empty() {
+        writeln!(writer)?;
+        writeln!(writer, "✅ No internal couplings to rank.")?;
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+
+    for (i, balance) in worst.iter().enumerate() {
+        let coupling = &balance.coupling;
+        writeln!(
+            writer,
+            "#{} {} → {} (Score: {:.2}, {})",
+            i + 1,
+            coupling.source,
+            coupling.target,
+            balance.score,
+            balance.interpretation
+        )?;
+        let classification = BalanceClassification::classify(
+            coupling.strength,
+            coupling.distance,
+            coupling.volatility,
+        );
+        writeln!(
+            writer,
+            "   {:?} / {:?} / {:?} · {}",
+            coupling.strength,
+            coupling.distance,
+            coupling.volatility,
+            classification.description_en()
+        )?;
+        if let Some(file_path) = &coupling.location.file_path
+            && coupling.location.line > 0
+        {
+            writeln!(
+                writer,
+                "   📍 {}:{}",
+                file_path.display(),
+                coupling.location.line
+            )?;
+        }
+        let why = if balance.volatility_dominated() {
+            "volatility-dominated, strong coupling to a target that changes often"
+        } else {
+            "alignment-dominated, strength does not fit the distance"
+        };
+        writeln!(
+            writer,
+            "   Why: {} (alignment {:.2}, volatility impact {:.2})",
+            why, balance.alignment, balance.volatility_impact
+        )?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Impact Analysis: Change Impact Assessment
 // ============================================================================
@@ -750,6 +1233,24 @@ pub fn generate_impact_output<W: Write>(
 // Check/Gate: CI/CD Quality Gate
 // ============================================================================
 
+/// Exit code: the command ran and every gate passed.
+pub const EXIT_PASSED: i32 = 0;
+/// Exit code: a quality gate (`--check`, `snapshot check`, `diff --max-regression`) failed.
+pub const EXIT_GATE_FAILED: i32 = 1;
+/// Exit code: analysis could not run (missing path, unreadable input, git failure).
+pub const EXIT_ANALYSIS_ERROR: i32 = 2;
+/// Exit code: invalid `.coupling.toml` or command-line arguments.
+pub const EXIT_CONFIG_ERROR: i32 = 3;
+
+/// `EXIT_PASSED` or `EXIT_GATE_FAILED`.
+fn gate_exit_code(passed: bool) -> i32 {
+    if passed {
+        EXIT_PASSED
+    } else {
+        EXIT_GATE_FAILED
+    }
+}
+
 /// Quality check configuration
 #[derive(Debug, Clone)]
 pub struct CheckConfig {
@@ -761,6 +1262,10 @@ pub struct CheckConfig {
     pub max_circular: Option<usize>,
     /// Fail on any issue of this severity or higher
     pub fail_on: Option<Severity>,
+    /// Issues and cycles accepted as pre-existing (`--baseline-file`), ignored by the gate
+    pub baseline: Option<IssueBaseline>,
+    /// Coupling budgets per module path (`[budgets]`)
+    pub budgets: BTreeMap<String, ModuleBudget>,
 }
 
 impl Default for CheckConfig {
@@ -770,6 +1275,8 @@ impl Default for CheckConfig {
             max_critical: Some(0),
             max_circular: Some(0),
             fail_on: None,
+            baseline: None,
+            budgets: BTreeMap::new(),
         }
     }
 }
@@ -784,7 +1291,126 @@ pub struct CheckResult {
     pub high_count: usize,
     pub medium_count: usize,
     pub circular_count: usize,
+    /// Issues and circular dependencies ignored because the baseline file accepts them
+    pub suppressed_count: usize,
+    /// Issues silenced by `// coupling:allow <rule>(<target>)` markers
+    pub inline_suppressed_count: usize,
     pub failures: Vec<String>,
+    /// Modules over their `[budgets]` entry
+    pub budget_overages: Vec<BudgetOverage>,
+    /// `[budgets]` entries that match no module
+    pub unmatched_budgets: Vec<String>,
+    /// Issues that failed the `--max-critical` or `--fail-on` gate
+    #[serde(skip)]
+    pub blocking_issues: Vec<CouplingIssue>,
+}
+
+/// A module with more internal couplings than its `[budgets]` entry allows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BudgetOverage {
+    /// Module path as written in `[budgets]`
+    pub module: String,
+    /// `outgoing` or `incoming`
+    pub direction: &'static str,
+    pub count: usize,
+    pub budget: usize,
+}
+
+impl std::fmt::Display for BudgetOverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}/{} {} (over budget by {})",
+            self.module,
+            self.count,
+            self.budget,
+            self.direction,
+            self.count - self.budget
+        )
+    }
+}
+
+/// Modules whose internal couplings exceed their budget, sorted by budget path.
+///
+/// A budget path matches the module with that name, with or without `crate::`
+/// or a leading crate name, so `crate::api` covers `api` and `shop::api`.
+/// Submodules have budgets of their own.
+pub fn budget_overages(
+    metrics: &ProjectMetrics,
+    budgets: &BTreeMap<String, ModuleBudget>,
+) -> Vec<BudgetOverage> {
+    let (efferent, afferent) = metrics.internal_coupling_counts();
+    let crate_names = metrics.crate_names();
+    let count = |counts: &HashMap<&str, usize>, path: &str| -> usize {
+        counts
+            .iter()
+            .filter(|(module, _)| budget_path_matches(module, path, &crate_names))
+            .map(|(_, count)| count)
+            .sum()
+    };
+
+    let mut overages = Vec::new();
+    for (module, budget) in budgets {
+        let path = module.strip_prefix("crate::").unwrap_or(module);
+        let limits = [
+            ("outgoing", budget.max_out, &efferent),
+            ("incoming", budget.max_in, &afferent),
+        ];
+        for (direction, max, counts) in limits {
+            let Some(max) = max else {
+                continue;
+            };
+            let count = count(counts, path);
+            if count > max {
+                overages.push(BudgetOverage {
+                    module: module.clone(),
+                    direction,
+                    count,
+                    budget: max,
+                });
+            }
+        }
+    }
+    overages
+}
+
+/// `[budgets]` paths that match no analyzed module, sorted; their limits never apply.
+pub fn unmatched_budgets(
+    metrics: &ProjectMetrics,
+    budgets: &BTreeMap<String, ModuleBudget>,
+) -> Vec<String> {
+    let crate_names = metrics.crate_names();
+    let modules: Vec<&str> = metrics
+        .modules
+        .keys()
+        .map(String::as_str)
+        .chain(
+            metrics
+                .couplings
+                .iter()
+                .filter(|coupling| coupling.distance != Distance::DifferentCrate)
+                .flat_map(|coupling| [coupling.source.as_str(), coupling.target.as_str()]),
+        )
+        .collect();
+    budgets
+        .keys()
+        .filter(|module| {
+            let path = module.strip_prefix("crate::").unwrap_or(module);
+            !modules
+                .iter()
+                .any(|name| budget_path_matches(name, path, &crate_names))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether `module` is the module a `[budgets]` path (without `crate::`) names.
+fn budget_path_matches(module: &str, path: &str, crate_names: &[&str]) -> bool {
+    module == path
+        || module
+            .strip_suffix(path)
+            .and_then(|prefix| prefix.strip_suffix("::"))
+            .is_some_and(|prefix| crate_names.contains(&prefix))
 }
 
 /// Run quality check and return result
@@ -793,8 +1419,16 @@ pub fn run_check(
     thresholds: &IssueThresholds,
     config: &CheckConfig,
 ) -> CheckResult {
-    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
-    let circular_deps = metrics.detect_circular_dependencies();
+    let mut report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    let mut circular_deps = metrics.detect_circular_dependencies();
+    let mut suppressed_count = 0;
+    if let Some(baseline) = &config.baseline {
+        suppressed_count = baseline.suppress(&mut report);
+        report.regrade(thresholds);
+        let before = circular_deps.len();
+        circular_deps.retain(|cycle| !baseline.contains_cycle(cycle));
+        suppressed_count += before - circular_deps.len();
+    }
 
     let critical_count = *report
         .issues_by_severity
@@ -829,6 +1463,29 @@ pub fn run_check(
                 report.health_grade, min_grade
             ));
         }
+
+        // Every member crate must also meet the minimum on its own
+        if metrics.crate_names().len() > 1 {
+            let mut crate_reports: Vec<_> = analyze_crate_balance(metrics, thresholds)
+                .into_iter()
+                .collect();
+            if let Some(baseline) = &config.baseline {
+                for (_, crate_report) in &mut crate_reports {
+                    baseline.suppress(crate_report);
+                    crate_report.regrade(thresholds);
+                }
+            }
+            crate_reports.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (crate_name, crate_report) in crate_reports {
+                if grade_order(&crate_report.health_grade) < grade_order(min_grade) {
+                    passed = false;
+                    failures.push(format!(
+                        "Crate {} grade {:?} is below minimum {:?}",
+                        crate_name, crate_report.health_grade, min_grade
+                    ));
+                }
+            }
+        }
     }
 
     // Check critical issues
@@ -867,6 +1524,25 @@ pub fn run_check(
         }
     }
 
+    // Check per-module budgets
+    let budget_overages = budget_overages(metrics, &config.budgets);
+    if !budget_overages.is_empty() {
+        passed = false;
+        failures.extend(budget_overages.iter().map(ToString::to_string));
+    }
+
+    let critical_gate_failed = config.max_critical.is_some_and(|max| critical_count > max);
+    let blocking_issues = report
+        .issues
+        .into_iter()
+        .filter(|issue| {
+            config
+                .fail_on
+                .is_some_and(|fail_severity| issue.severity >= fail_severity)
+                || (critical_gate_failed && issue.severity == Severity::Critical)
+        })
+        .collect();
+
     CheckResult {
         passed,
         grade: report.health_grade.letter().to_string(),
@@ -875,11 +1551,16 @@ pub fn run_check(
         high_count,
         medium_count,
         circular_count,
+        suppressed_count,
+        inline_suppressed_count: report.inline_suppressions,
         failures,
+        budget_overages,
+        unmatched_budgets: unmatched_budgets(metrics, &config.budgets),
+        blocking_issues,
     }
 }
 
-/// Generate check output and return exit code (0 = pass, 1 = fail)
+/// Generate check output and return `EXIT_PASSED` or `EXIT_GATE_FAILED`
 pub fn generate_check_output<W: Write>(
     metrics: &ProjectMetrics,
     thresholds: &IssueThresholds,
@@ -913,19 +1594,143 @@ pub fn generate_check_output<W: Write>(
     writeln!(writer, "  High issues: {}", result.high_count)?;
     writeln!(writer, "  Medium issues: {}", result.medium_count)?;
     writeln!(writer, "  Circular dependencies: {}", result.circular_count)?;
-
-    if !result.passed {
-        writeln!(writer)?;
-        writeln!(writer, "Blocking Issues:")?;
-        for failure in &result.failures {
-            writeln!(writer, "  - {}", failure)?;
-        }
+    if config.baseline.is_some() {
+        writeln!(
+            writer,
+            "  Suppressed by baseline: {}",
+            result.suppressed_count
+        )?;
     }
-
-    Ok(if result.passed { 0 } else { 1 })
-}
-
-/// Generate a readable baseline diff report.
+    if result.inline_suppressed_count > 0 {
+        writeln!(
+            writer,
+            "  Suppressed inline: {}",
+            result.inline_suppressed_count
+        )?;
+    }
+    if !config.budgets.is_empty() {
+        writeln!(
+            writer,
+            "  Module budgets: {} configured, {} over",
+            config.budgets.len(),
+            result.budget_overages.len()
+        )?;
+    }
+    for module in &result.unmatched_budgets {
+        writeln!(
+            writer,
+            "  Warning: [budgets] entry {} matches no module and is not checked",
+            module
+        )?;
+    }
+
+    if !result.passed {
+        writeln!(writer)?;
+        writeln!(writer, "Blocking Issues:")?;
+        for failure in &result.failures {
+            writeln!(writer, "  - {}", failure)?;
+        }
+    }
+
+    Ok(gate_exit_code(result.passed))
+}
+
+/// Generate `--check --format github`: GitHub Actions workflow commands.
+///
+/// Each blocking issue becomes an `::error` or `::warning` annotation at the
+/// coupling that caused it, else at its module's file, so it shows inline on
+/// the pull request diff. Gate failures without an issue behind them (grade,
+/// circular dependencies) are annotated without a location. Returns the same
+/// exit code as `generate_check_output`.
+pub fn generate_check_github_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    config: &CheckConfig,
+    writer: &mut W,
+) -> io::Result<i32> {
+    let result = run_check(metrics, thresholds, config);
+    let locations = coupling_locations(metrics);
+
+    for issue in &result.blocking_issues {
+        let level = match issue.severity {
+            Severity::Critical | Severity::High => "error",
+            Severity::Medium | Severity::Low => "warning",
+        };
+        let mut properties = Vec::new();
+        if let Some(location) = sarif_location(metrics, &locations, issue) {
+            let physical = location.physical_location;
+            properties.push(format!(
+                "file={}",
+                escape_workflow_property(&physical.artifact_location.uri)
+            ));
+            if let Some(region) = physical.region {
+                properties.push(format!("line={}", region.start_line));
+                if let Some(column) = region.start_column {
+                    properties.push(format!("col={}", column));
+                }
+            }
+        }
+        properties.push(format!(
+            "title={}",
+            escape_workflow_property(&issue.issue_type.to_string())
+        ));
+        writeln!(
+            writer,
+            "::{} {}::{}",
+            level,
+            properties.join(","),
+            escape_workflow_data(&format!(
+                "{} -> {}: {}",
+                issue.source, issue.target, issue.description
+            ))
+        )?;
+    }
+
+    for failure in &result.failures {
+        writeln!(
+            writer,
+            "::error title=Coupling Quality Gate::{}",
+            escape_workflow_data(failure)
+        )?;
+    }
+    for module in &result.unmatched_budgets {
+        writeln!(
+            writer,
+            "::warning title=Coupling Quality Gate::{}",
+            escape_workflow_data(&format!(
+                "[budgets] entry {} matches no module and is not checked",
+                module
+            ))
+        )?;
+    }
+
+    writeln!(
+        writer,
+        "Coupling Quality Gate: grade {} ({:.0}%) {}",
+        result.grade,
+        result.score * 100.0,
+        if result.passed { "passed" } else { "failed" }
+    )?;
+
+    Ok(gate_exit_code(result.passed))
+}
+
+/// Escape a workflow command message (the part after `::`).
+fn escape_workflow_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value (`file=`, `title=`).
+fn escape_workflow_property(value: &str) -> String {
+    escape_workflow_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Generate a readable baseline diff report.
 pub fn generate_baseline_diff_output<W: Write>(
     diff: &BaselineDiff,
     baseline_ref: &str,
@@ -937,6 +1742,9 @@ pub fn generate_baseline_diff_output<W: Write>(
         "═══════════════════════════════════════════════════════════"
     )?;
     writeln!(writer, "Baseline: {}", baseline_ref)?;
+    for warning in &diff.warnings {
+        writeln!(writer, "Warning: {}", warning)?;
+    }
     writeln!(
         writer,
         "Grade: {} -> {}",
@@ -950,13 +1758,53 @@ pub fn generate_baseline_diff_output<W: Write>(
     writeln!(writer, "  Resolved: {}", diff.resolved_issues.len())?;
     writeln!(writer, "  Unchanged: {}", diff.unchanged)?;
 
+    if let Some(delta) = &diff.dimension_delta {
+        writeln!(writer)?;
+        write_dimension_delta(writer, delta)?;
+    }
+
     write_issue_section(writer, "New Issues", &diff.new_issues)?;
     write_issue_section(writer, "Resolved Issues", &diff.resolved_issues)?;
 
     Ok(())
 }
 
-/// Generate ratchet gate output and return exit code (0 = pass, 1 = fail).
+/// Generate only the dimension-stats shift against a baseline ref.
+pub fn generate_dimension_delta_output<W: Write>(
+    delta: &DimensionStatsDelta,
+    baseline_ref: &str,
+    json: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    if json {
+        let text = serde_json::to_string_pretty(delta).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(());
+    }
+
+    writeln!(writer, "Coupling Dimension Shift")?;
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    writeln!(writer, "Baseline: {}", baseline_ref)?;
+    writeln!(writer)?;
+    write_dimension_delta(writer, delta)
+}
+
+fn write_dimension_delta<W: Write>(writer: &mut W, delta: &DimensionStatsDelta) -> io::Result<()> {
+    writeln!(writer, "Dimension shifts: {}", delta)?;
+    for bucket in delta.changed() {
+        writeln!(
+            writer,
+            "  {:<10} {:<16} {:>5} -> {:<5} ({:+})",
+            bucket.dimension, bucket.bucket, bucket.baseline, bucket.current, bucket.delta
+        )?;
+    }
+    Ok(())
+}
+
+/// Generate ratchet gate output and return `EXIT_PASSED` or `EXIT_GATE_FAILED`.
 pub fn generate_ratchet_check_output<W: Write>(
     diff: &BaselineDiff,
     baseline_ref: &str,
@@ -972,6 +1820,9 @@ pub fn generate_ratchet_check_output<W: Write>(
         "═══════════════════════════════════════════════════════════"
     )?;
     writeln!(writer, "Baseline: {}", baseline_ref)?;
+    for warning in &diff.warnings {
+        writeln!(writer, "Warning: {}", warning)?;
+    }
     writeln!(
         writer,
         "Grade: {} -> {}",
@@ -999,7 +1850,136 @@ pub fn generate_ratchet_check_output<W: Write>(
         }
     }
 
-    Ok(if passed { 0 } else { 1 })
+    Ok(gate_exit_code(passed))
+}
+
+/// Generate the `cargo coupling diff` output comparing two git revisions.
+///
+/// Returns `EXIT_GATE_FAILED` when the average score dropped by more than
+/// `max_regression`, `EXIT_PASSED` otherwise (always without a threshold).
+pub fn generate_revision_diff_output<W: Write>(
+    diff: &BaselineDiff,
+    base_ref: &str,
+    head_ref: &str,
+    max_regression: Option<f64>,
+    json: bool,
+    writer: &mut W,
+) -> io::Result<i32> {
+    let regressed = max_regression.is_some_and(|max| diff.regressed_beyond(max));
+
+    if json {
+        let output = JsonRevisionDiff {
+            base: base_ref.to_string(),
+            head: head_ref.to_string(),
+            headline: diff.headline(),
+            max_regression,
+            regressed,
+            diff: json_baseline_diff(diff),
+        };
+        let text = serde_json::to_string_pretty(&output).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(gate_exit_code(!regressed));
+    }
+
+    writeln!(writer, "Coupling Revision Diff")?;
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    writeln!(writer, "Base: {}", base_ref)?;
+    writeln!(writer, "Head: {}", head_ref)?;
+    for warning in &diff.warnings {
+        writeln!(writer, "Warning: {}", warning)?;
+    }
+    writeln!(writer)?;
+    writeln!(writer, "{}", diff.headline())?;
+    writeln!(writer, "Score delta: {:+.3}", diff.score_delta)?;
+    writeln!(writer)?;
+    writeln!(writer, "Issues:")?;
+    writeln!(writer, "  New: {}", diff.new_issues.len())?;
+    writeln!(writer, "  Resolved: {}", diff.resolved_issues.len())?;
+    writeln!(writer, "  Unchanged: {}", diff.unchanged)?;
+
+    writeln!(writer)?;
+    writeln!(writer, "Module Score Changes:")?;
+    if diff.module_deltas.is_empty() {
+        writeln!(writer, "  (none)")?;
+    }
+    for delta in &diff.module_deltas {
+        let score = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.2}", v));
+        writeln!(
+            writer,
+            "  {:<40} {} -> {} ({:+.2})",
+            delta.module,
+            score(delta.baseline),
+            score(delta.current),
+            delta.delta
+        )?;
+    }
+
+    write_issue_section(writer, "New Issues", &diff.new_issues)?;
+    write_issue_section(writer, "Resolved Issues", &diff.resolved_issues)?;
+
+    if let Some(max) = max_regression {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "Status: {} (max regression: {:.3})",
+            if regressed { "FAILED" } else { "PASSED" },
+            max
+        )?;
+    }
+
+    Ok(gate_exit_code(!regressed))
+}
+
+/// Render `coupling snapshot check`; `EXIT_GATE_FAILED` when any metric regressed beyond its tolerance.
+pub fn generate_snapshot_check_output<W: Write>(
+    comparison: &SnapshotComparison,
+    snapshot_path: &std::path::Path,
+    json: bool,
+    writer: &mut W,
+) -> io::Result<i32> {
+    let regressed = comparison.regressed();
+
+    if json {
+        let output = JsonSnapshotCheck {
+            snapshot: snapshot_path.display().to_string(),
+            regressed,
+            comparison: comparison.clone(),
+        };
+        let text = serde_json::to_string_pretty(&output).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(gate_exit_code(!regressed));
+    }
+
+    writeln!(writer, "Coupling Snapshot Check")?;
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    writeln!(writer, "Snapshot: {}", snapshot_path.display())?;
+    for warning in &comparison.warnings {
+        writeln!(writer, "Warning: {}", warning)?;
+    }
+    writeln!(writer)?;
+
+    if comparison.changes.is_empty() {
+        writeln!(writer, "No metric changed.")?;
+    }
+    for change in &comparison.changes {
+        let marker = if change.regressed { "✗" } else { "·" };
+        writeln!(writer, "  {} {}", marker, change)?;
+    }
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Status: {}",
+        if regressed { "FAILED" } else { "PASSED" }
+    )?;
+
+    Ok(gate_exit_code(!regressed))
 }
 
 fn write_issue_section<W: Write>(
@@ -1217,7 +2197,7 @@ fn issue_instance_description_japanese(issue: &CouplingIssue) -> String {
             )
         }
         IssueType::HiddenCoupling => {
-            "明示的なコード依存はありませんが、ファイルが頻繁に一緒に変更されています。暗黙の知識や不足した抽象化を示している可能性があります。"
+            "明示的なコード依存（またはトレイト契約以外の依存）はありませんが、ファイルが頻繁に一緒に変更されています。暗黙の知識や不足した抽象化を示している可能性があります。"
                 .to_string()
         }
         IssueType::AccidentalVolatility => {
@@ -1255,137 +2235,1396 @@ fn issue_refactoring_japanese(issue: &CouplingIssue) -> String {
 }
 
 // ============================================================================
-// JSON Output
+// Integration Tests: Public-API coupling from `tests/`
 // ============================================================================
 
-/// Temporal coupling in JSON format
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonTemporalCoupling {
-    pub file_a: String,
-    pub file_b: String,
-    pub co_change_count: usize,
-    pub coupling_ratio: f64,
-    pub is_strong: bool,
-}
+/// Render the integration-test coupling section (excluded from the health grade).
+pub fn generate_integration_tests_output<W: Write>(
+    report: &IntegrationTestReport,
+    japanese: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer)?;
+    if japanese {
+        writeln!(writer, "統合テストの結合（グレード対象外）")?;
+    } else {
+        writeln!(writer, "Integration Test Coupling (excluded from grade)")?;
+    }
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    if japanese {
+        writeln!(
+            writer,
+            "テストファイル: {}  公開APIへの参照: {}",
+            report.test_files,
+            report.couplings.len()
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "Test files: {}  Public API references: {}",
+            report.test_files,
+            report.couplings.len()
+        )?;
+    }
 
-/// Complete analysis in JSON format
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonOutput {
-    pub summary: JsonSummary,
-    pub grade_rationale: JsonGradeRationale,
-    pub analysis_manifest: JsonAnalysisManifest,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub diff: Option<JsonBaselineDiff>,
-    pub external_dependencies: JsonExternalDependencies,
-    pub hotspots: Vec<Hotspot>,
-    pub issues: Vec<JsonIssue>,
-    pub circular_dependencies: Vec<Vec<String>>,
-    pub temporal_couplings: Vec<JsonTemporalCoupling>,
-    pub modules: Vec<JsonModule>,
-}
+    if report.targets.is_empty() {
+        if japanese {
+            writeln!(writer, "  (なし)")?;
+        } else {
+            writeln!(writer, "  (none)")?;
+        }
+        return Ok(());
+    }
 
-/// Standalone external-dependency JSON output.
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonExternalDependenciesOutput {
-    pub external_dependencies: JsonExternalDependencies,
-}
+    writeln!(writer)?;
+    for target in report.targets.iter().take(10) {
+        writeln!(
+            writer,
+            "  {} ({} refs) <- {}",
+            target.module,
+            target.references,
+            target.test_modules.join(", ")
+        )?;
+    }
 
-/// External dependency analysis in JSON format.
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonExternalDependencies {
-    pub total_crates: usize,
-    pub total_references: usize,
-    pub dependencies: Vec<ExternalDependencyUsage>,
-    pub scattered_couplings: Vec<JsonIssue>,
+    Ok(())
 }
 
-/// Summary in JSON format
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonSummary {
-    pub health_grade: String,
-    pub health_score: f64,
-    pub total_modules: usize,
-    pub total_couplings: usize,
-    pub internal_couplings: usize,
-    pub external_couplings: usize,
-    pub critical_issues: usize,
-    pub high_issues: usize,
-    pub medium_issues: usize,
-}
+// ============================================================================
+// Benchmark: Coupling profile against reference crates
+// ============================================================================
 
-/// Health-grade rationale in JSON format.
-#[derive(Debug, Clone, Serialize)]
-pub struct JsonGradeRationale {
-    pub summary: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub dominant_dimension: Option<String>,
-    pub top_issue_types: Vec<JsonIssueTypeContribution>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+/// Render how the project's coupling profile compares with the reference crates.
+pub fn generate_benchmark_output<W: Write>(
+    report: &BenchmarkReport,
+    json: bool,
+    japanese: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    if json {
+        let text = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(());
+    }
+
+    if japanese {
+        writeln!(
+            writer,
+            "参照クレートとの比較 ({} クレート)",
+            report.reference_count
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "Coupling Profile vs Reference Crates ({} crates)",
+            report.reference_count
+        )?;
+    }
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    if japanese {
+        writeln!(writer, "プロファイル: {}", report.profile)?;
+    } else {
+        writeln!(writer, "Profile: {}", report.profile)?;
+    }
+
+    if let Some(target) = &report.target {
+        if japanese {
+            writeln!(
+                writer,
+                "{} との距離: {:.2} (0.00 = 同一, 1.00 = 完全に異なる)",
+                target.name, target.distance
+            )?;
+        } else {
+            writeln!(
+                writer,
+                "Distance to {}: {:.2} (0.00 = identical, 1.00 = disjoint)",
+                target.name, target.distance
+            )?;
+        }
+    }
+
+    writeln!(writer)?;
+    for dimension in &report.dimensions {
+        writeln!(writer, "  - {}", dimension.sentence())?;
+    }
+
+    writeln!(writer)?;
+    if japanese {
+        writeln!(writer, "最も近いクレート:")?;
+    } else {
+        writeln!(writer, "Closest reference crates:")?;
+    }
+    for reference in report.nearest.iter().take(3) {
+        writeln!(writer, "  {} ({:.2})", reference.name, reference.distance)?;
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Temporal Coupling: Co-change pairs from git history
+// ============================================================================
+
+/// Render co-changing module pairs, hidden ones (no static coupling) first.
+pub fn generate_temporal_coupling_output<W: Write>(
+    report: &TemporalCouplingReport,
+    json: bool,
+    japanese: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    if json {
+        let text = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(());
+    }
+
+    if japanese {
+        writeln!(writer, "時間的結合 (gitの同時変更)")?;
+    } else {
+        writeln!(writer, "Temporal Coupling (git co-change)")?;
+    }
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    if japanese {
+        writeln!(
+            writer,
+            "隠れた結合: {}  静的結合を補強: {}",
+            report.hidden, report.reinforcing
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "Hidden: {}  Reinforcing static coupling: {}",
+            report.hidden, report.reinforcing
+        )?;
+    }
+
+    for kind in [CoChangeKind::Hidden, CoChangeKind::Reinforcing] {
+        let pairs: Vec<_> = report
+            .pairs
+            .iter()
+            .filter(|pair| pair.kind == kind)
+            .collect();
+        if pairs.is_empty() {
+            continue;
+        }
+        writeln!(writer)?;
+        let heading = match (kind, japanese) {
+            (CoChangeKind::Hidden, false) => "Hidden (no static coupling):",
+            (CoChangeKind::Hidden, true) => "隠れた結合 (静的結合なし):",
+            (CoChangeKind::Reinforcing, false) => "Reinforcing (also statically coupled):",
+            (CoChangeKind::Reinforcing, true) => "静的結合を補強:",
+        };
+        writeln!(writer, "{}", heading)?;
+        for pair in pairs {
+            writeln!(
+                writer,
+                "  {} <-> {} ({} co-changes, {:.0}%)",
+                pair.module_a,
+                pair.module_b,
+                pair.co_change_count,
+                pair.coupling_ratio * 100.0
+            )?;
+        }
+    }
+
+    if report.pairs.is_empty() {
+        if japanese {
+            writeln!(writer, "  (なし)")?;
+        } else {
+            writeln!(writer, "  (none)")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate `--visibility-audit` output.
+pub fn generate_visibility_audit_output<W: Write>(
+    audit: &VisibilityAudit,
+    json: bool,
+    japanese: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    if json {
+        let text = serde_json::to_string_pretty(audit).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(());
+    }
+
+    if japanese {
+        writeln!(writer, "可視性の監査 (pub の縮小候補)")?;
+    } else {
+        writeln!(
+            writer,
+            "Visibility Audit (pub items that could be narrower)"
+        )?;
+    }
+    writeln!(
+        writer,
+        "═══════════════════════════════════════════════════════════"
+    )?;
+    if japanese {
+        writeln!(
+            writer,
+            "pub アイテム: {}  縮小候補: {}",
+            audit.checked,
+            audit.suggestions.len()
+        )?;
+    } else {
+        writeln!(
+            writer,
+            "pub items: {}  Could be narrower: {}",
+            audit.checked,
+            audit.suggestions.len()
+        )?;
+    }
+
+    for suggestion in &audit.suggestions {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "  {} {} ({}:{})",
+            suggestion.kind, suggestion.item, suggestion.file, suggestion.line
+        )?;
+        if japanese {
+            writeln!(
+                writer,
+                "    → `{}` を検討: 利用元 {}",
+                suggestion.suggested,
+                suggestion.used_by.join(", ")
+            )?;
+        } else {
+            writeln!(
+                writer,
+                "    → {} (used by {})",
+                suggestion.reason(),
+                suggestion.used_by.join(", ")
+            )?;
+        }
+    }
+
+    if audit.suggestions.is_empty() {
+        if japanese {
+            writeln!(writer, "  (なし)")?;
+        } else {
+            writeln!(writer, "  (none)")?;
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// JSON Output
+// ============================================================================
+
+/// Temporal coupling in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonTemporalCoupling {
+    pub file_a: String,
+    pub file_b: String,
+    pub co_change_count: usize,
+    pub coupling_ratio: f64,
+    pub is_strong: bool,
+}
+
+/// One direction of a mutual coupling in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonMutualDirection {
+    pub strength: String,
+    pub distance: String,
+    pub volatility: String,
+    pub couplings: usize,
+}
+
+/// Mutual (A ↔ B) coupling in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonMutualCoupling {
+    pub module_a: String,
+    pub module_b: String,
+    pub a_to_b: JsonMutualDirection,
+    pub b_to_a: JsonMutualDirection,
+}
+
+/// Couplings a feature adds to one module in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonFeatureCoupling {
+    pub feature: String,
+    pub module: String,
+    pub couplings: usize,
+}
+
+impl From<&MutualDirection> for JsonMutualDirection {
+    fn from(direction: &MutualDirection) -> Self {
+        Self {
+            strength: format!("{:?}", direction.strength),
+            distance: format!("{:?}", direction.distance),
+            volatility: format!("{:?}", direction.volatility),
+            couplings: direction.couplings,
+        }
+    }
+}
+
+/// Version of the `--json` output shape, pinned as `schema_version`.
+///
+/// Bumped whenever a field of `JsonOutput` (or a type nested in it) is
+/// removed, renamed or changes type; added fields do not bump it.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Complete analysis in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonOutput {
+    /// Output shape version (`JSON_SCHEMA_VERSION`); check it before reading the rest
+    #[schemars(extend("const" = JSON_SCHEMA_VERSION))]
+    pub schema_version: u32,
+    /// `analyzer_version`, `config_hash`, and `generated_at`
+    #[serde(flatten)]
+    pub header: OutputHeader,
+    pub summary: JsonSummary,
+    pub coupling_profile: CouplingProfile,
+    pub dimension_stats: DimensionStats,
+    pub grade_rationale: JsonGradeRationale,
+    pub analysis_manifest: JsonAnalysisManifest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<JsonBaselineDiff>,
+    pub external_dependencies: JsonExternalDependencies,
+    pub hotspots: Vec<Hotspot>,
+    pub issues: Vec<JsonIssue>,
+    /// Top 10 issues by impact per unit of estimated effort
+    pub quick_wins: Vec<QuickWin>,
+    pub circular_dependencies: Vec<Vec<String>>,
+    pub mutual_couplings: Vec<JsonMutualCoupling>,
+    pub feature_gated_couplings: Vec<JsonFeatureCoupling>,
+    pub temporal_couplings: Vec<JsonTemporalCoupling>,
+    pub modules: Vec<JsonModule>,
+}
+
+/// Headline numbers only, written by `--format summary-json`.
+///
+/// Shares the header and `summary` with `JsonOutput`, so summary files also
+/// work as `--web --snapshots` input.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonSummaryOutput {
+    #[serde(flatten)]
+    pub header: OutputHeader,
+    pub summary: JsonSummary,
+    /// Number of circular dependency cycles
+    pub circular_dependencies: usize,
+}
+
+/// Standalone external-dependency JSON output.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonExternalDependenciesOutput {
+    pub external_dependencies: JsonExternalDependencies,
+}
+
+/// External dependency analysis in JSON format.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonExternalDependencies {
+    pub total_crates: usize,
+    pub total_references: usize,
+    pub dependencies: Vec<ExternalDependencyUsage>,
+    pub scattered_couplings: Vec<JsonIssue>,
+}
+
+/// Summary in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonSummary {
+    pub health_grade: String,
+    pub health_score: f64,
+    pub total_modules: usize,
+    pub total_couplings: usize,
+    pub internal_couplings: usize,
+    pub external_couplings: usize,
+    pub critical_issues: usize,
+    pub high_issues: usize,
+    pub medium_issues: usize,
+    /// Issues silenced by `// coupling:allow <rule>(<target>)` markers
+    pub inline_suppressions: usize,
+}
+
+/// Health-grade rationale in JSON format.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonGradeRationale {
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dominant_dimension: Option<String>,
+    pub top_issue_types: Vec<JsonIssueTypeContribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
 }
 
-/// Issue-type contribution in JSON format.
+/// Issue-type contribution in JSON format.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonIssueTypeContribution {
+    pub issue_type: String,
+    pub count: usize,
+    pub highest_severity: String,
+}
+
+/// Declared analysis blind spots in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonAnalysisManifest {
+    pub blind_spots: Vec<JsonBlindSpot>,
+    pub notes: Vec<String>,
+}
+
+/// Structural blind spot in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonBlindSpot {
+    pub area: String,
+    pub description: String,
+}
+
+/// Issue in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonIssue {
+    pub issue_type: String,
+    pub severity: String,
+    pub source: String,
+    pub target: String,
+    pub description: String,
+    pub suggestion: String,
+    pub balance_score: f64,
+    /// `Low`, `Medium` or `High`, from the suggested refactoring
+    pub estimated_effort: String,
+    /// Modules that benefit from the fix: the source plus its dependents within two hops
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impact: Option<usize>,
+    /// `file:line` of the first coupling between source and target, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// 1-based column of that coupling's first token on the `location` line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// Module in JSON format
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonModule {
+    pub name: String,
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subdomain: Option<String>,
+    pub couplings_out: usize,
+    pub couplings_in: usize,
+    pub balance_score: f64,
+    pub in_cycle: bool,
+    /// Martin's instability `Ce/(Ce+Ca)`; absent without internal couplings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instability: Option<f64>,
+    /// Traits over all defined types; absent without type definitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abstractness: Option<f64>,
+    /// Distance from the main sequence `|A + I - 1|`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_sequence_distance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability_zone: Option<StabilityZone>,
+    /// Largest type cluster's share of the functions; absent without clustered functions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cohesion: Option<f64>,
+}
+
+/// Baseline diff in JSON format.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonBaselineDiff {
+    pub new_issues: Vec<JsonIssue>,
+    pub resolved_issues: Vec<JsonIssue>,
+    pub unchanged: usize,
+    pub score_delta: f64,
+    pub grade_change: JsonGradeChange,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension_delta: Option<DimensionStatsDelta>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub module_deltas: Vec<ModuleScoreDelta>,
+    /// Why the baseline may not be comparable (analyzer version or config changed)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// `cargo coupling diff` output in JSON format.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRevisionDiff {
+    pub base: String,
+    pub head: String,
+    pub headline: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_regression: Option<f64>,
+    /// Whether the score dropped by more than `max_regression`
+    pub regressed: bool,
+    #[serde(flatten)]
+    pub diff: JsonBaselineDiff,
+}
+
+/// `cargo coupling snapshot check` output in JSON format.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonSnapshotCheck {
+    pub snapshot: String,
+    /// Whether any metric regressed beyond its tolerance
+    pub regressed: bool,
+    #[serde(flatten)]
+    pub comparison: SnapshotComparison,
+}
+
+/// Baseline/current grade transition in JSON format.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JsonGradeChange {
+    pub baseline: String,
+    pub current: String,
+}
+
+/// Generate `--schema`: the JSON Schema of `--json` output (`JsonOutput`).
+pub fn generate_json_schema_output<W: Write>(writer: &mut W) -> io::Result<()> {
+    let schema = schemars::schema_for!(JsonOutput);
+    let json = serde_json::to_string_pretty(&schema).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)
+}
+
+/// Generate the compact `--format summary-json` output.
+///
+/// Skips hotspot, module, and issue serialization entirely.
+pub fn generate_summary_json_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    let output = JsonSummaryOutput {
+        header: OutputHeader::new(metrics.config_hash.clone()),
+        summary: json_summary(metrics, &report),
+        circular_dependencies: metrics.detect_circular_dependencies().len(),
+    };
+
+    let json = serde_json::to_string_pretty(&output).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)?;
+    Ok(())
+}
+
+/// One `--format jsonl` line: a record tagged with its `kind`
+#[derive(Serialize)]
+struct JsonlRecord<'a, T: Serialize> {
+    kind: &'static str,
+    #[serde(flatten)]
+    record: &'a T,
+}
+
+fn write_jsonl_record<W: Write, T: Serialize>(
+    writer: &mut W,
+    kind: &'static str,
+    record: &T,
+) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, &JsonlRecord { kind, record }).map_err(io::Error::other)?;
+    writeln!(writer)
+}
+
+/// Generate JSON Lines output (`--format jsonl`)
+///
+/// Writes a `summary` line (same fields as `--format summary-json`), then one
+/// `issue` line per `JsonIssue` and one `module` line per `JsonModule`. Each
+/// record is serialized straight to `writer` as it is built, so the report is
+/// never held in memory as a whole.
+pub fn generate_jsonl_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    let circular_deps = metrics.detect_circular_dependencies();
+
+    let summary = JsonSummaryOutput {
+        header: OutputHeader::new(metrics.config_hash.clone()),
+        summary: json_summary(metrics, &report),
+        circular_dependencies: circular_deps.len(),
+    };
+    write_jsonl_record(writer, "summary", &summary)?;
+
+    let locations = coupling_locations(metrics);
+    let impacts = issue_impacts(metrics, &report.issues);
+    for issue in &report.issues {
+        write_jsonl_record(writer, "issue", &json_issue_at(issue, &locations, &impacts))?;
+    }
+
+    let modules = JsonModuleBuilder::new(metrics, thresholds, &circular_deps);
+    for (name, module) in &metrics.modules {
+        write_jsonl_record(writer, "module", &modules.build(name, module))?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// CSV output (--format csv)
+// ============================================================================
+
+/// One `--format csv` row: a single coupling with its scores
+#[derive(Serialize)]
+struct CsvCouplingRow<'a> {
+    source: &'a str,
+    target: &'a str,
+    strength: String,
+    effective_strength: String,
+    distance: String,
+    volatility: String,
+    balance_score: String,
+    classification: String,
+    file_path: String,
+    line: Option<usize>,
+    occurrences: usize,
+    feature: Option<&'a str>,
+}
+
+/// Generate CSV output (`--format csv`)
+///
+/// One row per coupling, sorted by source, target and location. Both the
+/// detected strength and the visibility-adjusted effective strength are
+/// included; the balance score uses the configured bands.
+pub fn generate_csv_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut couplings: Vec<_> = metrics.couplings.iter().collect();
+    couplings.sort_by(|a, b| {
+        (&a.source, &a.target, &a.location.file_path, a.location.line).cmp(&(
+            &b.source,
+            &b.target,
+            &b.location.file_path,
+            b.location.line,
+        ))
+    });
+
+    let mut csv = csv::Writer::from_writer(writer);
+    for coupling in couplings {
+        let score = BalanceScore::calculate_for(coupling, thresholds);
+        let classification = BalanceClassification::classify(
+            coupling.strength,
+            coupling.distance,
+            coupling.volatility,
+        );
+        csv.serialize(CsvCouplingRow {
+            source: &coupling.source,
+            target: &coupling.target,
+            strength: format!("{:?}", coupling.strength),
+            effective_strength: format!("{:?}", coupling.effective_strength()),
+            distance: format!("{:?}", coupling.distance),
+            volatility: format!("{:?}", coupling.volatility),
+            balance_score: format!("{:.2}", score.score),
+            classification: format!("{:?}", classification),
+            file_path: coupling
+                .location
+                .file_path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            line: (coupling.location.line > 0).then_some(coupling.location.line),
+            occurrences: coupling.occurrences,
+            feature: coupling.feature.as_deref(),
+        })?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+// ============================================================================
+// Markdown issue table (--format markdown-table)
+// ============================================================================
+
+/// Generate a bare Markdown table of issues (`--format markdown-table`)
+///
+/// Only the table, for pasting into PR descriptions and tickets: one row per
+/// issue, most severe first. Pipes and line breaks in cells are escaped so a
+/// row never spills into the next column.
+pub fn generate_markdown_table_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    let locations = coupling_locations(metrics);
+    let mut issues: Vec<&CouplingIssue> = report.issues.iter().collect();
+    issues.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.target.cmp(&b.target))
+    });
+
+    writeln!(
+        writer,
+        "| Severity | Type | Source | Target | Suggestion | Score | Location |"
+    )?;
+    writeln!(
+        writer,
+        "|----------|------|--------|--------|------------|-------|----------|"
+    )?;
+    for issue in issues {
+        let location = locations
+            .get(&(issue.source.as_str(), issue.target.as_str()))
+            .and_then(|location| file_line(location))
+            .map(|location| format!("`{}`", markdown_cell(&location)))
+            .unwrap_or_default();
+        writeln!(
+            writer,
+            "| {} | {} | `{}` | `{}` | {} | {:.2} | {} |",
+            issue.severity,
+            issue.issue_type,
+            markdown_cell(&issue.source),
+            markdown_cell(&issue.target),
+            markdown_cell(&issue.refactoring.to_string()),
+            issue.balance_score,
+            location
+        )?;
+    }
+    Ok(())
+}
+
+/// Cell text that keeps a Markdown table row intact.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+// ============================================================================
+// Prometheus output (--format prometheus)
+// ============================================================================
+
+/// Generate Prometheus text exposition output (`--format prometheus`)
+///
+/// Gauges only, so CI can push one sample per run to a Pushgateway and
+/// dashboards can trend health across releases. Per-module scores use the
+/// same internal couplings as the project average.
+pub fn generate_prometheus_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+
+    write_prometheus_header(
+        writer,
+        "coupling_health_score",
+        "Average balance score of internal couplings (0.0-1.0)",
+    )?;
+    writeln!(writer, "coupling_health_score {}", report.average_score)?;
+
+    write_prometheus_header(
+        writer,
+        "coupling_health_grade",
+        "Current health grade; the sample with value 1 carries the grade label",
+    )?;
+    writeln!(
+        writer,
+        "coupling_health_grade{{grade=\"{}\"}} 1",
+        report.health_grade.letter()
+    )?;
+
+    write_prometheus_header(
+        writer,
+        "coupling_issues_total",
+        "Detected coupling issues by severity",
+    )?;
+    for severity in [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+    ] {
+        writeln!(
+            writer,
+            "coupling_issues_total{{severity=\"{}\"}} {}",
+            severity.to_string().to_lowercase(),
+            report.issues_by_severity.get(&severity).unwrap_or(&0)
+        )?;
+    }
+
+    write_prometheus_header(
+        writer,
+        "coupling_cycles_total",
+        "Circular dependencies between modules",
+    )?;
+    writeln!(
+        writer,
+        "coupling_cycles_total {}",
+        metrics.detect_circular_dependencies().len()
+    )?;
+
+    write_prometheus_header(writer, "coupling_modules_total", "Analyzed modules")?;
+    writeln!(writer, "coupling_modules_total {}", metrics.module_count())?;
+
+    write_prometheus_header(
+        writer,
+        "coupling_balance_score",
+        "Average balance score of each module's internal couplings (0.0-1.0)",
+    )?;
+    for (module, score) in calculate_module_scores_with(metrics, &thresholds.balance_formula) {
+        writeln!(
+            writer,
+            "coupling_balance_score{{module=\"{}\"}} {}",
+            escape_prometheus_label(&module),
+            score
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_prometheus_header<W: Write>(writer: &mut W, name: &str, help: &str) -> io::Result<()> {
+    writeln!(writer, "# HELP {} {}", name, help)?;
+    writeln!(writer, "# TYPE {} gauge", name)
+}
+
+/// Escape a label value per the text exposition format.
+fn escape_prometheus_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// ============================================================================
+// SARIF output (--format sarif)
+// ============================================================================
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF 2.1.0 log with a single run.
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonIssueTypeContribution {
-    pub issue_type: String,
-    pub count: usize,
-    pub highest_severity: String,
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
 }
 
-/// Declared analysis blind spots in JSON format
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonAnalysisManifest {
-    pub blind_spots: Vec<JsonBlindSpot>,
-    pub notes: Vec<String>,
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+    /// Columns count characters, as proc_macro2 spans do
+    pub column_kind: &'static str,
 }
 
-/// Structural blind spot in JSON format
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonBlindSpot {
-    pub area: String,
-    pub description: String,
+pub struct SarifTool {
+    pub driver: SarifDriver,
 }
 
-/// Issue in JSON format
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonIssue {
-    pub issue_type: String,
-    pub severity: String,
-    pub source: String,
-    pub target: String,
-    pub description: String,
-    pub suggestion: String,
-    pub balance_score: f64,
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
 }
 
-/// Module in JSON format
+/// One `IssueType`, described for the code scanning UI.
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonModule {
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
     pub name: String,
-    pub file_path: Option<String>,
+    pub short_description: SarifText,
+    pub full_description: SarifText,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifText {
+    pub text: String,
+}
+
+/// One `CouplingIssue`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    /// `error` (Critical/High), `warning` (Medium) or `note` (Low)
+    pub level: &'static str,
+    pub message: SarifText,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub subdomain: Option<String>,
-    pub couplings_out: usize,
-    pub couplings_in: usize,
-    pub balance_score: f64,
-    pub in_cycle: bool,
+    pub region: Option<SarifRegion>,
 }
 
-/// Baseline diff in JSON format.
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonBaselineDiff {
-    pub new_issues: Vec<JsonIssue>,
-    pub resolved_issues: Vec<JsonIssue>,
-    pub unchanged: usize,
-    pub score_delta: f64,
-    pub grade_change: JsonGradeChange,
+pub struct SarifArtifactLocation {
+    pub uri: String,
 }
 
-/// Baseline/current grade transition in JSON format.
 #[derive(Debug, Clone, Serialize)]
-pub struct JsonGradeChange {
-    pub baseline: String,
-    pub current: String,
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+}
+
+/// Generate `--format sarif` output for GitHub code scanning.
+///
+/// Each issue is located at the coupling that caused it (file and line) when
+/// known, else at its module's file.
+pub fn generate_sarif_output<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let report = analyze_project_balance_with_thresholds(metrics, thresholds);
+    let log = build_sarif_log(metrics, &report);
+    let json = serde_json::to_string_pretty(&log).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)?;
+    Ok(())
+}
+
+fn build_sarif_log(metrics: &ProjectMetrics, report: &ProjectBalanceReport) -> SarifLog {
+    let locations = coupling_locations(metrics);
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut results = Vec::new();
+
+    for issue in &report.issues {
+        let rule_id = format!("{:?}", issue.issue_type);
+        let rule_index = match rules.iter().position(|rule| rule.id == rule_id) {
+            Some(index) => index,
+            None => {
+                rules.push(sarif_rule(issue.issue_type, rule_id.clone()));
+                rules.len() - 1
+            }
+        };
+        let location = sarif_location(metrics, &locations, issue);
+        results.push(SarifResult {
+            rule_id,
+            rule_index,
+            level: sarif_level(issue.severity),
+            message: SarifText {
+                text: issue.description.clone(),
+            },
+            locations: location.into_iter().collect(),
+        });
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: "2.1.0",
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules,
+                },
+            },
+            results,
+            column_kind: "unicodeCodePoints",
+        }],
+    }
+}
+
+fn sarif_rule(issue_type: IssueType, id: String) -> SarifRule {
+    SarifRule {
+        id,
+        name: issue_type.to_string(),
+        short_description: SarifText {
+            text: issue_type.to_string(),
+        },
+        full_description: SarifText {
+            text: issue_type.description().to_string(),
+        },
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+fn sarif_location(
+    metrics: &ProjectMetrics,
+    locations: &HashMap<(&str, &str), &CouplingLocation>,
+    issue: &CouplingIssue,
+) -> Option<SarifLocation> {
+    let (path, region) = match locations.get(&(issue.source.as_str(), issue.target.as_str())) {
+        Some(location) => (
+            location.file_path.as_ref()?.display().to_string(),
+            Some(SarifRegion {
+                start_line: location.line,
+                start_column: (location.column > 0).then_some(location.column),
+            }),
+        ),
+        None => {
+            let module = [issue.source.as_str(), issue.target.as_str()]
+                .into_iter()
+                .find_map(|name| module_for_issue_name(metrics, name))?;
+            (module.path.display().to_string(), None)
+        }
+    };
+
+    Some(SarifLocation {
+        physical_location: SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation {
+                uri: sarif_uri(&path),
+            },
+            region,
+        },
+    })
+}
+
+/// Module named by an issue endpoint.
+///
+/// Endpoints may carry a crate prefix (`my_crate::module`) or name an item
+/// inside the module (`module::Type.field`), so trailing segments are dropped
+/// until a module matches.
+fn module_for_issue_name<'a>(metrics: &'a ProjectMetrics, name: &str) -> Option<&'a ModuleMetrics> {
+    metrics
+        .module_for_endpoint(name)
+        .and_then(|module| metrics.modules.get(module))
+}
+
+/// Repository-relative, forward-slash URI as code scanning expects.
+fn sarif_uri(path: &str) -> String {
+    let path = std::path::Path::new(path);
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    let uri = relative.to_string_lossy().replace('\\', "/");
+    uri.strip_prefix("./").unwrap_or(&uri).to_string()
+}
+
+/// Generate `--format dot`: the internal dependency graph as Graphviz DOT.
+///
+/// Nodes are filled by the module's average balance score, edges are labeled
+/// with the strongest integration strength between the two modules, and
+/// edges that close a circular dependency are drawn red.
+pub fn generate_dot_output<W: Write>(
+    metrics: &ProjectMetrics,
+    formula: &BalanceFormula,
+    writer: &mut W,
+) -> io::Result<()> {
+    let module_scores = calculate_module_scores_with(metrics, formula);
+    let edges = dependency_edges(metrics);
+    let mut nodes: Vec<&str> = edges
+        .iter()
+        .flat_map(|edge| [edge.source.as_str(), edge.target.as_str()])
+        .collect();
+    nodes.sort_unstable();
+    nodes.dedup();
+
+    write_dot_header(writer)?;
+    for node in nodes {
+        let score = module_scores.get(node).copied().unwrap_or(1.0);
+        writeln!(
+            writer,
+            "    {} [fillcolor=\"{}\", tooltip=\"balance score {:.2}\"];",
+            dot_id(node),
+            dot_health_color(score),
+            score
+        )?;
+    }
+    write_dot_edges(&edges, writer)?;
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+/// Edge of the internal dependency graph, as drawn by `--format dot` and `graph`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    /// Strongest integration strength between the two modules
+    pub strength: Option<String>,
+    /// Whether the edge closes a circular dependency
+    pub in_cycle: bool,
+}
+
+/// How a module in a focused graph relates to the focus module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusRole {
+    Focus,
+    /// Reached by following dependencies out of the focus module
+    Dependency,
+    /// Reached by following dependents into the focus module
+    Dependent,
+    /// Reached both ways, i.e. on a cycle through the focus module
+    Both,
+}
+
+/// Module in a focused graph.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusNode {
+    pub module: String,
+    pub role: FocusRole,
+    /// Hops from the focus module (0 for the focus itself)
+    pub hops: usize,
+    pub balance_score: f64,
+}
+
+/// The neighborhood of one module, `depth` hops out in both directions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusGraph {
+    pub focus: String,
+    pub depth: usize,
+    /// Focus first, then by hops and name
+    pub nodes: Vec<FocusNode>,
+    /// Every edge between two nodes of the neighborhood
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Internal dependency edges, sorted, with their strongest strength and cycle membership.
+fn dependency_edges(metrics: &ProjectMetrics) -> Vec<GraphEdge> {
+    let graph = metrics.build_dependency_graph();
+    let cycle_edges: HashSet<(String, String)> = metrics
+        .detect_circular_dependencies()
+        .iter()
+        .flat_map(|cycle| {
+            cycle
+                .iter()
+                .zip(cycle.iter().cycle().skip(1))
+                .map(|(from, to)| (from.clone(), to.clone()))
+        })
+        .collect();
+
+    let mut strongest: HashMap<(&str, &str), IntegrationStrength> = HashMap::new();
+    for coupling in &metrics.couplings {
+        if coupling.distance == Distance::DifferentCrate {
+            continue;
+        }
+        strongest
+            .entry((&coupling.source, &coupling.target))
+            .and_modify(|strength| {
+                if coupling.strength.value() > strength.value() {
+                    *strength = coupling.strength;
+                }
+            })
+            .or_insert(coupling.strength);
+    }
+
+    let mut edges: Vec<GraphEdge> = graph
+        .iter()
+        .flat_map(|(source, targets)| targets.iter().map(move |target| (source, target)))
+        .filter(|(source, target)| source != target)
+        .map(|(source, target)| GraphEdge {
+            source: source.clone(),
+            target: target.clone(),
+            strength: strongest
+                .get(&(source.as_str(), target.as_str()))
+                .map(|strength| format!("{:?}", strength)),
+            in_cycle: cycle_edges.contains(&(source.clone(), target.clone())),
+        })
+        .collect();
+    edges.sort_unstable_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+    edges
+}
+
+/// Neighborhood of `module_name` (resolved like `--impact`): its dependencies
+/// and its dependents, each followed up to `depth` hops. Nodes are scored with `formula`.
+pub fn build_focus_graph(
+    metrics: &ProjectMetrics,
+    module_name: &str,
+    depth: usize,
+    formula: &BalanceFormula,
+) -> Option<FocusGraph> {
+    let focus = find_module(metrics, module_name)?;
+    let edges = dependency_edges(metrics);
+
+    let mut outgoing: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut incoming: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &edges {
+        outgoing.entry(&edge.source).or_default().push(&edge.target);
+        incoming.entry(&edge.target).or_default().push(&edge.source);
+    }
+    let reachable = |adjacent: &HashMap<&str, Vec<&str>>| {
+        let mut hops: HashMap<String, usize> = HashMap::from([(focus.clone(), 0)]);
+        let mut frontier = vec![focus.as_str()];
+        for hop in 1..=depth {
+            let mut next = Vec::new();
+            for module in frontier {
+                for &neighbor in adjacent.get(module).into_iter().flatten() {
+                    if !hops.contains_key(neighbor) {
+                        hops.insert(neighbor.to_string(), hop);
+                        next.push(neighbor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        hops.remove(&focus);
+        hops
+    };
+    let dependencies = reachable(&outgoing);
+    let dependents = reachable(&incoming);
+
+    let module_scores = calculate_module_scores_with(metrics, formula);
+    let score = |module: &str| module_scores.get(module).copied().unwrap_or(1.0);
+    let mut nodes = vec![FocusNode {
+        module: focus.clone(),
+        role: FocusRole::Focus,
+        hops: 0,
+        balance_score: score(&focus),
+    }];
+    let mut neighbors: Vec<&String> = dependencies.keys().chain(dependents.keys()).collect();
+    neighbors.sort_unstable();
+    neighbors.dedup();
+    for module in neighbors {
+        let (role, hops) = match (dependencies.get(module), dependents.get(module)) {
+            (Some(&out), Some(&back)) => (FocusRole::Both, out.min(back)),
+            (Some(&out), None) => (FocusRole::Dependency, out),
+            (None, Some(&back)) => (FocusRole::Dependent, back),
+            (None, None) => continue,
+        };
+        nodes.push(FocusNode {
+            module: module.clone(),
+            role,
+            hops,
+            balance_score: score(module),
+        });
+    }
+    nodes[1..].sort_by(|a, b| a.hops.cmp(&b.hops).then_with(|| a.module.cmp(&b.module)));
+
+    let included: HashSet<&str> = nodes.iter().map(|node| node.module.as_str()).collect();
+    let edges = edges
+        .iter()
+        .filter(|edge| {
+            included.contains(edge.source.as_str()) && included.contains(edge.target.as_str())
+        })
+        .cloned()
+        .collect();
+
+    Some(FocusGraph {
+        focus,
+        depth,
+        nodes,
+        edges,
+    })
+}
+
+/// Generate `graph --focus` as Graphviz DOT.
+///
+/// Nodes are filled by their role (focus, dependency, dependent, or both) and
+/// carry their balance score as a tooltip; edges are drawn as in `--format dot`.
+pub fn generate_focus_dot_output<W: Write>(graph: &FocusGraph, writer: &mut W) -> io::Result<()> {
+    write_dot_header(writer)?;
+    for node in &graph.nodes {
+        let (color, extra) = match node.role {
+            FocusRole::Focus => ("#ffd54f", ", penwidth=3"),
+            FocusRole::Dependency => ("#bbdefb", ""),
+            FocusRole::Dependent => ("#e1bee7", ""),
+            FocusRole::Both => ("#b2dfdb", ""),
+        };
+        writeln!(
+            writer,
+            "    {} [fillcolor=\"{}\"{}, tooltip=\"{:?}, {} hop(s), balance score {:.2}\"];",
+            dot_id(&node.module),
+            color,
+            extra,
+            node.role,
+            node.hops,
+            node.balance_score
+        )?;
+    }
+    write_dot_edges(&graph.edges, writer)?;
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+/// Generate `graph --focus --format json`.
+pub fn generate_focus_json_output<W: Write>(graph: &FocusGraph, writer: &mut W) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(graph).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)
+}
+
+fn write_dot_header<W: Write>(writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "digraph coupling {{")?;
+    writeln!(writer, "    rankdir=LR;")?;
+    writeln!(
+        writer,
+        "    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];"
+    )?;
+    writeln!(writer, "    edge [fontname=\"Helvetica\", fontsize=10];")
+}
+
+fn write_dot_edges<W: Write>(edges: &[GraphEdge], writer: &mut W) -> io::Result<()> {
+    for edge in edges {
+        writeln!(
+            writer,
+            "    {} -> {} [label=\"{}\"{}];",
+            dot_id(&edge.source),
+            dot_id(&edge.target),
+            edge.strength.as_deref().unwrap_or_default(),
+            if edge.in_cycle {
+                ", color=red, fontcolor=red, penwidth=2"
+            } else {
+                ""
+            }
+        )?;
+    }
+    Ok(())
+}
+
+/// Quoted DOT identifier.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Fill color for a balance score, using the web UI's health bands.
+fn dot_health_color(score: f64) -> &'static str {
+    if score >= 0.8 {
+        "#c8e6c9"
+    } else if score >= 0.6 {
+        "#fff9c4"
+    } else if score >= 0.4 {
+        "#ffe0b2"
+    } else {
+        "#ffcdd2"
+    }
+}
+
+fn json_summary(metrics: &ProjectMetrics, report: &ProjectBalanceReport) -> JsonSummary {
+    let internal_count = metrics
+        .couplings
+        .iter()
+        .filter(|coupling| coupling.distance != Distance::DifferentCrate)
+        .count();
+    let issues = |severity| *report.issues_by_severity.get(&severity).unwrap_or(&0);
+
+    JsonSummary {
+        health_grade: report.health_grade.letter().to_string(),
+        health_score: report.average_score,
+        total_modules: metrics.modules.len(),
+        total_couplings: metrics.couplings.len(),
+        internal_couplings: internal_count,
+        external_couplings: metrics.couplings.len() - internal_count,
+        critical_issues: issues(Severity::Critical),
+        high_issues: issues(Severity::High),
+        medium_issues: issues(Severity::Medium),
+        inline_suppressions: report.inline_suppressions,
+    }
 }
 
 /// Generate complete JSON output
@@ -1417,41 +3656,22 @@ fn generate_json_output_with_optional_diff<W: Write>(
     writer: &mut W,
 ) -> io::Result<()> {
     let report = analyze_project_balance_with_thresholds(metrics, thresholds);
-    let external_dependencies = analyze_external_dependencies(metrics, &HashMap::new());
+    let external_dependencies =
+        analyze_external_dependencies_with_thresholds(metrics, &HashMap::new(), thresholds);
     let circular_deps = metrics.detect_circular_dependencies();
-    let cycle_modules: HashSet<String> = circular_deps.iter().flatten().cloned().collect();
     let hotspots = calculate_hotspots(metrics, thresholds, 10);
+    let modules = JsonModuleBuilder::new(metrics, thresholds, &circular_deps);
 
-    // Count couplings per module
-    let mut couplings_out: HashMap<String, usize> = HashMap::new();
-    let mut couplings_in: HashMap<String, usize> = HashMap::new();
-    let mut balance_scores: HashMap<String, Vec<f64>> = HashMap::new();
-    let mut internal_count = 0;
-
-    for coupling in &metrics.couplings {
-        if coupling.distance != Distance::DifferentCrate {
-            internal_count += 1;
-            *couplings_out.entry(coupling.source.clone()).or_default() += 1;
-            *couplings_in.entry(coupling.target.clone()).or_default() += 1;
-            let score = BalanceScore::calculate(coupling);
-            balance_scores
-                .entry(coupling.source.clone())
-                .or_default()
-                .push(score.score);
-        }
-    }
-
-    let external_count = metrics.couplings.len() - internal_count;
-
-    let critical = *report
-        .issues_by_severity
-        .get(&Severity::Critical)
-        .unwrap_or(&0);
-    let high = *report.issues_by_severity.get(&Severity::High).unwrap_or(&0);
-    let medium = *report
-        .issues_by_severity
-        .get(&Severity::Medium)
-        .unwrap_or(&0);
+    let mutual_couplings: Vec<JsonMutualCoupling> = metrics
+        .detect_mutual_couplings()
+        .iter()
+        .map(|pair| JsonMutualCoupling {
+            module_a: pair.module_a.clone(),
+            module_b: pair.module_b.clone(),
+            a_to_b: (&pair.a_to_b).into(),
+            b_to_a: (&pair.b_to_a).into(),
+        })
+        .collect();
 
     let temporal_couplings: Vec<JsonTemporalCoupling> = metrics
         .temporal_couplings
@@ -1467,17 +3687,12 @@ fn generate_json_output_with_optional_diff<W: Write>(
         .collect();
 
     let output = JsonOutput {
-        summary: JsonSummary {
-            health_grade: report.health_grade.letter().to_string(),
-            health_score: report.average_score,
-            total_modules: metrics.modules.len(),
-            total_couplings: metrics.couplings.len(),
-            internal_couplings: internal_count,
-            external_couplings: external_count,
-            critical_issues: critical,
-            high_issues: high,
-            medium_issues: medium,
-        },
+        schema_version: JSON_SCHEMA_VERSION,
+        header: OutputHeader::new(metrics.config_hash.clone()),
+        summary: json_summary(metrics, &report),
+        coupling_profile: metrics.coupling_profile(),
+        dimension_stats: metrics
+            .calculate_dimension_stats_with(thresholds.balance_formula.visibility_penalty),
         grade_rationale: JsonGradeRationale {
             summary: report.grade_rationale.summary.clone(),
             dominant_dimension: report
@@ -1514,27 +3729,32 @@ fn generate_json_output_with_optional_diff<W: Write>(
         diff: diff.map(json_baseline_diff),
         external_dependencies: json_external_dependencies(&external_dependencies),
         hotspots,
-        issues: report.issues.iter().map(json_issue).collect(),
+        issues: {
+            let locations = coupling_locations(metrics);
+            let impacts = issue_impacts(metrics, &report.issues);
+            report
+                .issues
+                .iter()
+                .map(|issue| json_issue_at(issue, &locations, &impacts))
+                .collect()
+        },
+        quick_wins: quick_wins_from_report(metrics, &report, 10),
         circular_dependencies: circular_deps,
+        mutual_couplings,
+        feature_gated_couplings: metrics
+            .feature_gated_couplings()
+            .into_iter()
+            .map(|gated| JsonFeatureCoupling {
+                feature: gated.feature,
+                module: gated.module,
+                couplings: gated.couplings,
+            })
+            .collect(),
         temporal_couplings,
         modules: metrics
             .modules
             .iter()
-            .map(|(name, module)| {
-                let avg_score = balance_scores
-                    .get(name)
-                    .map(|scores| scores.iter().sum::<f64>() / scores.len() as f64)
-                    .unwrap_or(1.0);
-                JsonModule {
-                    name: name.clone(),
-                    file_path: Some(module.path.display().to_string()),
-                    subdomain: module.subdomain.map(|subdomain| subdomain.to_string()),
-                    couplings_out: couplings_out.get(name).copied().unwrap_or(0),
-                    couplings_in: couplings_in.get(name).copied().unwrap_or(0),
-                    balance_score: avg_score,
-                    in_cycle: cycle_modules.contains(name),
-                }
-            })
+            .map(|(name, module)| modules.build(name, module))
             .collect(),
     };
 
@@ -1554,6 +3774,94 @@ fn json_baseline_diff(diff: &BaselineDiff) -> JsonBaselineDiff {
             baseline: diff.baseline_grade.letter().to_string(),
             current: diff.current_grade.letter().to_string(),
         },
+        dimension_delta: diff.dimension_delta.clone(),
+        module_deltas: diff.module_deltas.clone(),
+        warnings: diff.warnings.clone(),
+    }
+}
+
+/// Per-module figures shared by `--json` and `--format jsonl`
+struct JsonModuleBuilder {
+    couplings_out: HashMap<String, usize>,
+    couplings_in: HashMap<String, usize>,
+    balance_scores: HashMap<String, Vec<f64>>,
+    cycle_modules: HashSet<String>,
+    stability: HashMap<String, ModuleStability>,
+}
+
+impl JsonModuleBuilder {
+    fn new(
+        metrics: &ProjectMetrics,
+        thresholds: &IssueThresholds,
+        circular_deps: &[Vec<String>],
+    ) -> Self {
+        // Count couplings per module
+        let mut couplings_out: HashMap<String, usize> = HashMap::new();
+        let mut couplings_in: HashMap<String, usize> = HashMap::new();
+        let mut balance_scores: HashMap<String, Vec<f64>> = HashMap::new();
+
+        for coupling in &metrics.couplings {
+            if coupling.distance != Distance::DifferentCrate {
+                *couplings_out.entry(coupling.source.clone()).or_default() += 1;
+                *couplings_in.entry(coupling.target.clone()).or_default() += 1;
+                let score = BalanceScore::calculate_for(coupling, thresholds);
+                balance_scores
+                    .entry(coupling.source.clone())
+                    .or_default()
+                    .push(score.score);
+            }
+        }
+
+        Self {
+            couplings_out,
+            couplings_in,
+            balance_scores,
+            cycle_modules: circular_deps.iter().flatten().cloned().collect(),
+            stability: calculate_module_stability(metrics)
+                .into_iter()
+                .map(|entry| (entry.module.clone(), entry))
+                .collect(),
+        }
+    }
+
+    fn build(&self, name: &str, module: &ModuleMetrics) -> JsonModule {
+        let avg_score = self
+            .balance_scores
+            .get(name)
+            .map(|scores| scores.iter().sum::<f64>() / scores.len() as f64)
+            .unwrap_or(1.0);
+        let stability = self.stability.get(name);
+        JsonModule {
+            name: name.to_string(),
+            file_path: Some(module.path.display().to_string()),
+            subdomain: module.subdomain.map(|subdomain| subdomain.to_string()),
+            couplings_out: self.couplings_out.get(name).copied().unwrap_or(0),
+            couplings_in: self.couplings_in.get(name).copied().unwrap_or(0),
+            balance_score: avg_score,
+            in_cycle: self.cycle_modules.contains(name),
+            instability: stability.map(|s| s.instability),
+            abstractness: stability.map(|s| s.abstractness),
+            main_sequence_distance: stability.map(|s| s.distance),
+            stability_zone: stability.and_then(|s| s.zone),
+            cohesion: ModuleCohesion::of(name, module).map(|c| c.cohesion),
+        }
+    }
+}
+
+/// `json_issue` with the `file:line` and column of its first coupling, when known
+fn json_issue_at(
+    issue: &CouplingIssue,
+    locations: &HashMap<(&str, &str), &CouplingLocation>,
+    impacts: &HashMap<String, usize>,
+) -> JsonIssue {
+    let location = locations.get(&(issue.source.as_str(), issue.target.as_str()));
+    JsonIssue {
+        impact: impacts.get(&issue.source).copied(),
+        location: location.and_then(|location| file_line(location)),
+        column: location
+            .map(|location| location.column)
+            .filter(|&column| column > 0),
+        ..json_issue(issue)
     }
 }
 
@@ -1566,9 +3874,33 @@ fn json_issue(issue: &CouplingIssue) -> JsonIssue {
         description: issue.description.clone(),
         suggestion: format!("{}", issue.refactoring),
         balance_score: issue.balance_score,
+        estimated_effort: issue.refactoring.effort().to_string(),
+        impact: None,
+        location: None,
+        column: None,
     }
 }
 
+/// First coupling with a file and line for each (source, target) pair.
+fn coupling_locations(metrics: &ProjectMetrics) -> HashMap<(&str, &str), &CouplingLocation> {
+    let mut locations = HashMap::new();
+    for coupling in &metrics.couplings {
+        if coupling.location.file_path.is_none() || coupling.location.line == 0 {
+            continue;
+        }
+        locations
+            .entry((coupling.source.as_str(), coupling.target.as_str()))
+            .or_insert(&coupling.location);
+    }
+    locations
+}
+
+/// `file:line` of a coupling location, when it has a file
+fn file_line(location: &CouplingLocation) -> Option<String> {
+    let file_path = location.file_path.as_ref()?;
+    Some(format!("{}:{}", file_path.display(), location.line))
+}
+
 fn json_external_dependencies(report: &ExternalDependencyReport) -> JsonExternalDependencies {
     JsonExternalDependencies {
         total_crates: report.dependencies.len(),
@@ -1677,7 +4009,9 @@ pub fn generate_trace_output<W: Write>(
                 let strength = match dep.dep_type {
                     ItemDepType::FieldAccess | ItemDepType::StructConstruction => "Intrusive",
                     ItemDepType::FunctionCall | ItemDepType::MethodCall => "Functional",
-                    ItemDepType::TypeUsage | ItemDepType::Import => "Model",
+                    ItemDepType::TypeUsage | ItemDepType::Import | ItemDepType::ConstUsage => {
+                        "Model"
+                    }
                     ItemDepType::TraitImpl | ItemDepType::TraitBound => "Contract",
                 };
                 outgoing.push(TraceDependency {
@@ -1698,7 +4032,9 @@ pub fn generate_trace_output<W: Write>(
                 let strength = match dep.dep_type {
                     ItemDepType::FieldAccess | ItemDepType::StructConstruction => "Intrusive",
                     ItemDepType::FunctionCall | ItemDepType::MethodCall => "Functional",
-                    ItemDepType::TypeUsage | ItemDepType::Import => "Model",
+                    ItemDepType::TypeUsage | ItemDepType::Import | ItemDepType::ConstUsage => {
+                        "Model"
+                    }
                     ItemDepType::TraitImpl | ItemDepType::TraitBound => "Contract",
                 };
                 incoming.push(TraceDependency {
@@ -2076,6 +4412,105 @@ pub fn generate_history_output<W: Write>(
     Ok(())
 }
 
+/// JSON form of `coupling trend`.
+#[derive(Serialize)]
+struct JsonTrend<'a> {
+    entries: &'a [TrendEntry],
+    warnings: Vec<String>,
+}
+
+/// Render recorded runs as a table with one sparkline per metric, or as JSON.
+pub fn generate_trend_output<W: Write>(
+    history: &TrendHistory,
+    json: bool,
+    writer: &mut W,
+) -> io::Result<()> {
+    let warnings = history.warnings();
+    if json {
+        let output = JsonTrend {
+            entries: &history.entries,
+            warnings,
+        };
+        let text = serde_json::to_string_pretty(&output).map_err(io::Error::other)?;
+        writeln!(writer, "{}", text)?;
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "Coupling Trend ({} recorded run(s))\n",
+        history.entries.len()
+    )?;
+    if history.entries.is_empty() {
+        writeln!(
+            writer,
+            "  No runs recorded yet; analyze with --record <FILE>."
+        )?;
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "  recorded          grade  avg    critical  high  medium  low  cycles  modules"
+    )?;
+    for entry in &history.entries {
+        let issues = &entry.snapshot.issues;
+        writeln!(
+            writer,
+            "  {:<17} {:<6} {:<6.3} {:<9} {:<5} {:<7} {:<4} {:<7} {}",
+            entry.recorded_date(),
+            entry.snapshot.health_grade,
+            entry.snapshot.average_score,
+            issues.critical,
+            issues.high,
+            issues.medium,
+            issues.low,
+            entry.snapshot.circular_dependencies,
+            entry.module_count,
+        )?;
+    }
+
+    let series =
+        |value: fn(&TrendEntry) -> f64| -> Vec<f64> { history.entries.iter().map(value).collect() };
+    // Scores at display precision, so float noise between equal runs draws no slope
+    let scores = series(|e| (e.snapshot.average_score * 1000.0).round() / 1000.0);
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "  avg score  {}  {:.3} -> {:.3} ({})",
+        sparkline(&scores),
+        scores[0],
+        scores[scores.len() - 1],
+        describe_trend(scores[0], scores[scores.len() - 1]),
+    )?;
+    let counts = [
+        ("issues", series(|e| e.issue_count() as f64)),
+        (
+            "cycles",
+            series(|e| e.snapshot.circular_dependencies as f64),
+        ),
+        ("modules", series(|e| e.module_count as f64)),
+    ];
+    for (label, values) in counts {
+        writeln!(
+            writer,
+            "  {:<9}  {}  {} -> {}",
+            label,
+            sparkline(&values),
+            values[0],
+            values[values.len() - 1],
+        )?;
+    }
+
+    if !warnings.is_empty() {
+        writeln!(writer)?;
+        for warning in &warnings {
+            writeln!(writer, "Warning: {}", warning)?;
+        }
+    }
+    Ok(())
+}
+
 /// Describe the direction of change between two scores.
 fn describe_trend(from: f64, to: f64) -> &'static str {
     let delta = to - from;
@@ -2091,6 +4526,7 @@ fn describe_trend(from: f64, to: f64) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::analyze_files;
     use std::path::PathBuf;
 
     use crate::history::{HistoryPoint, HistoryReport};
@@ -2192,6 +4628,598 @@ mod tests {
         assert_eq!(parse_severity("invalid"), None);
     }
 
+    #[test]
+    fn test_jsonl_output_writes_one_tagged_record_per_line() {
+        let mut metrics = ProjectMetrics::new();
+        for name in ["orders", "billing"] {
+            metrics.add_module(ModuleMetrics::new(
+                PathBuf::from(format!("src/{name}.rs")),
+                name.to_string(),
+            ));
+        }
+        for (source, target) in [("orders", "billing"), ("billing", "orders")] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::Low,
+            ));
+        }
+
+        let mut output = Vec::new();
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        generate_jsonl_output(&metrics, &thresholds, &mut output).unwrap();
+        let records: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let kinds: Vec<&str> = records
+            .iter()
+            .map(|record| record["kind"].as_str().unwrap())
+            .collect();
+        let issues = kinds.iter().filter(|kind| **kind == "issue").count();
+        assert!(issues > 0);
+        let mut expected = vec!["summary"];
+        expected.extend(std::iter::repeat_n("issue", issues));
+        expected.extend(["module", "module"]);
+        assert_eq!(kinds, expected);
+
+        assert_eq!(records[0]["circular_dependencies"], 1);
+        assert!(records[0]["summary"]["health_grade"].is_string());
+        assert!(records[0]["analyzer_version"].is_string());
+        assert!(records[1]["issue_type"].is_string());
+        let module = records.last().unwrap();
+        assert_eq!(module["in_cycle"], true);
+    }
+
+    #[test]
+    fn test_dot_output_labels_edges_by_strength_and_marks_cycles_red() {
+        let mut metrics = ProjectMetrics::new();
+        for (source, target, strength) in [
+            ("orders", "billing", IntegrationStrength::Model),
+            ("orders", "billing", IntegrationStrength::Functional),
+            ("billing", "orders", IntegrationStrength::Contract),
+            ("orders", "catalog", IntegrationStrength::Model),
+            ("orders", "serde", IntegrationStrength::Contract),
+        ] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                strength,
+                if target == "serde" {
+                    Distance::DifferentCrate
+                } else {
+                    Distance::DifferentModule
+                },
+                Volatility::Low,
+            ));
+        }
+
+        let mut output = Vec::new();
+        generate_dot_output(&metrics, &BalanceFormula::default(), &mut output).unwrap();
+        let dot = String::from_utf8(output).unwrap();
+
+        assert!(dot.starts_with("digraph coupling {"));
+        assert!(dot.contains(
+            "\"orders\" -> \"billing\" [label=\"Functional\", color=red, fontcolor=red, penwidth=2];"
+        ));
+        assert!(dot.contains(
+            "\"billing\" -> \"orders\" [label=\"Contract\", color=red, fontcolor=red, penwidth=2];"
+        ));
+        assert!(dot.contains("\"orders\" -> \"catalog\" [label=\"Model\"];"));
+        assert!(dot.contains("\"catalog\" [fillcolor="));
+        assert!(!dot.contains("serde"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_focus_graph_keeps_the_neighborhood_within_depth() {
+        let mut metrics = ProjectMetrics::new();
+        for (source, target) in [
+            ("api", "orders"),
+            ("orders", "billing"),
+            ("billing", "ledger"),
+            ("billing", "orders"),
+            ("admin", "api"),
+            ("reports", "ledger"),
+        ] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Model,
+                Distance::DifferentModule,
+                Volatility::Low,
+            ));
+        }
+
+        let graph = build_focus_graph(&metrics, "orders", 1, &BalanceFormula::default()).unwrap();
+        let roles: Vec<(&str, FocusRole, usize)> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.module.as_str(), node.role, node.hops))
+            .collect();
+        assert_eq!(
+            roles,
+            [
+                ("orders", FocusRole::Focus, 0),
+                ("api", FocusRole::Dependent, 1),
+                ("billing", FocusRole::Both, 1),
+            ]
+        );
+        assert_eq!(graph.edges.len(), 3);
+        assert!(
+            graph
+                .edges
+                .iter()
+                .all(|edge| edge.in_cycle == (edge.source != "api"))
+        );
+
+        let wider = build_focus_graph(&metrics, "orders", 2, &BalanceFormula::default()).unwrap();
+        let modules: Vec<&str> = wider.nodes.iter().map(|n| n.module.as_str()).collect();
+        // `reports` only depends on a dependency of the focus and stays out
+        assert_eq!(modules, ["orders", "api", "billing", "admin", "ledger"]);
+
+        let mut output = Vec::new();
+        generate_focus_dot_output(&wider, &mut output).unwrap();
+        let dot = String::from_utf8(output).unwrap();
+        assert!(dot.contains("\"orders\" [fillcolor=\"#ffd54f\", penwidth=3"));
+        assert!(dot.contains("\"ledger\" [fillcolor=\"#bbdefb\""));
+        assert!(dot.contains("\"admin\" [fillcolor=\"#e1bee7\""));
+        assert!(!dot.contains("reports"));
+
+        assert!(build_focus_graph(&metrics, "missing", 2, &BalanceFormula::default()).is_none());
+    }
+
+    #[test]
+    fn test_sarif_results_carry_rule_level_and_coupling_location() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod handlers;\npub mod legacy_auth;\npub mod users;\n",
+            ),
+            ("legacy_auth.rs", "pub struct Session;\n"),
+            ("users.rs", "pub struct User;\n"),
+            (
+                "handlers.rs",
+                "use crate::users::User;\nuse crate::legacy_auth::Session;\n\npub fn login(_s: Session, _u: User) {}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            max_dependencies: 1,
+            deprecated_internal: vec!["legacy_auth".to_string()],
+            deprecated_internal_severity: Severity::High,
+            ..IssueThresholds::default()
+        };
+        let mut output = Vec::new();
+        generate_sarif_output(&metrics, &thresholds, &mut output).unwrap();
+        let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["columnKind"], "unicodeCodePoints");
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let results = run["results"].as_array().unwrap();
+        let result = |rule_id: &str| {
+            results
+                .iter()
+                .find(|result| result["ruleId"] == rule_id)
+                .unwrap_or_else(|| panic!("no {rule_id} result in {results:?}"))
+        };
+
+        let deprecated = result("DeprecatedInternalCoupling");
+        assert_eq!(deprecated["level"], "error");
+        let rule = &rules[deprecated["ruleIndex"].as_u64().unwrap() as usize];
+        assert_eq!(rule["id"], "DeprecatedInternalCoupling");
+        assert_eq!(
+            rule["fullDescription"]["text"],
+            IssueType::DeprecatedInternalCoupling.description()
+        );
+        let location = &deprecated["locations"][0]["physicalLocation"];
+        assert!(
+            location["artifactLocation"]["uri"]
+                .as_str()
+                .unwrap()
+                .ends_with("src/handlers.rs")
+        );
+        assert_eq!(location["region"]["startLine"], 2);
+        // The path after `use`
+        assert_eq!(location["region"]["startColumn"], 5);
+
+        let efferent = result("HighEfferentCoupling");
+        assert_eq!(efferent["level"], "error");
+        let location = &efferent["locations"][0]["physicalLocation"];
+        assert!(
+            location["artifactLocation"]["uri"]
+                .as_str()
+                .unwrap()
+                .ends_with("src/handlers.rs")
+        );
+        assert!(
+            location.get("region").is_none(),
+            "module-level issue has no line"
+        );
+
+        assert_eq!(sarif_level(Severity::Critical), "error");
+        assert_eq!(sarif_level(Severity::Medium), "warning");
+        assert_eq!(sarif_level(Severity::Low), "note");
+
+        // JSON carries the same line and column
+        let mut output = Vec::new();
+        generate_json_output(
+            &metrics,
+            &thresholds,
+            &build_manifest(&ManifestContext::default()),
+            &mut output,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        let deprecated = json["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["issue_type"] == "Deprecated Internal Coupling")
+            .unwrap();
+        assert!(
+            deprecated["location"]
+                .as_str()
+                .unwrap()
+                .ends_with("src/handlers.rs:2")
+        );
+        assert_eq!(deprecated["column"], 5);
+    }
+
+    #[test]
+    fn test_prometheus_output_has_one_gauge_family_per_metric() {
+        let mut metrics = ProjectMetrics::new();
+        for name in ["orders", "billing"] {
+            metrics.add_module(ModuleMetrics::new(
+                std::path::PathBuf::from(format!("src/{name}.rs")),
+                name.to_string(),
+            ));
+        }
+        for (source, target) in [("orders", "billing"), ("billing", "orders")] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Functional,
+                Distance::DifferentModule,
+                Volatility::Low,
+            ));
+        }
+
+        let mut output = Vec::new();
+        generate_prometheus_output(&metrics, &IssueThresholds::default(), &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+
+        assert!(text.contains("# TYPE coupling_health_score gauge\ncoupling_health_score "));
+        assert!(text.contains("coupling_issues_total{severity=\"critical\"} "));
+        assert!(text.contains("coupling_issues_total{severity=\"low\"} "));
+        assert!(text.contains("coupling_cycles_total 1\n"));
+        assert!(text.contains("coupling_modules_total 2\n"));
+        assert!(text.contains("coupling_balance_score{module=\"billing\"} "));
+        // Every sample line is `name[{labels}] value`
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let (_, value) = line.rsplit_once(' ').unwrap();
+            assert!(value.parse::<f64>().is_ok(), "bad sample: {line}");
+        }
+        assert_eq!(escape_prometheus_label("a\"b\\c"), "a\\\"b\\\\c");
+    }
+
+    #[test]
+    fn test_every_issue_type_has_its_own_explanation() {
+        let fallback = get_issue_explanation("").what_it_means;
+        for issue_type in IssueType::ALL {
+            assert_eq!(
+                IssueType::from_rule_name(&issue_type.rule_name()),
+                Some(issue_type)
+            );
+            assert_ne!(
+                get_issue_explanation(&issue_type.to_string()).what_it_means,
+                fallback,
+                "{} has no explanation",
+                issue_type
+            );
+        }
+
+        let mut output = Vec::new();
+        generate_explain_output(IssueType::GodModule, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("God Module (god-module)\n"));
+        assert!(output.contains(IssueType::GodModule.description()));
+        assert!(output.contains("🔧 How to fix:"));
+        assert_eq!(
+            IssueType::from_rule_name("Over-Parameterized Type"),
+            Some(IssueType::OverParameterizedType)
+        );
+        assert_eq!(IssueType::from_rule_name("god-modules"), None);
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_coupling_with_raw_and_effective_strength() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod orders;\npub mod billing;\n"),
+            (
+                "billing.rs",
+                "pub(super) struct Invoice {\n    pub paid: u64,\n    pub total: u64,\n}\n",
+            ),
+            (
+                "orders.rs",
+                "use crate::billing::Invoice;\n\npub fn settle(invoice: &mut Invoice) {\n    invoice.paid = invoice.total;\n}\n",
+            ),
+        ]);
+        let mut output = Vec::new();
+        generate_csv_output(&metrics, &IssueThresholds::default(), &mut output).unwrap();
+
+        let mut reader = csv::Reader::from_reader(output.as_slice());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec![
+                "source",
+                "target",
+                "strength",
+                "effective_strength",
+                "distance",
+                "volatility",
+                "balance_score",
+                "classification",
+                "file_path",
+                "line",
+                "occurrences",
+                "feature",
+            ]
+        );
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), metrics.couplings.len());
+
+        let row = rows
+            .iter()
+            .find(|row| &row[0] == "orders" && &row[1] == "billing" && row[3] != row[2])
+            .unwrap_or_else(|| panic!("no visibility-upgraded orders -> billing row in {rows:?}"));
+        assert_eq!(&row[4], "DifferentModule");
+        assert!(row[6].parse::<f64>().is_ok());
+        assert!(row[8].ends_with("orders.rs"));
+        assert!(row[9].parse::<usize>().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_check_honors_severity_overrides() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod handlers;\npub mod legacy_auth;\npub mod users;\n",
+            ),
+            ("legacy_auth.rs", "pub struct Session;\n"),
+            ("users.rs", "pub struct User;\n"),
+            (
+                "handlers.rs",
+                "use crate::users::User;\nuse crate::legacy_auth::Session;\n\npub fn login(_s: Session, _u: User) {}\n",
+            ),
+        ]);
+        let config = CheckConfig {
+            min_grade: None,
+            max_critical: None,
+            max_circular: None,
+            fail_on: Some(Severity::High),
+            baseline: None,
+            budgets: BTreeMap::new(),
+        };
+        let check = |severity_overrides: &[(IssueType, Option<Severity>)]| {
+            let thresholds = IssueThresholds {
+                max_dependencies: 1,
+                deprecated_internal: vec!["legacy_auth".to_string()],
+                deprecated_internal_severity: Severity::High,
+                severity_overrides: severity_overrides.iter().copied().collect(),
+                ..IssueThresholds::default()
+            };
+            let mut output = Vec::new();
+            let exit_code =
+                generate_check_output(&metrics, &thresholds, &config, &mut output).unwrap();
+            (exit_code, String::from_utf8(output).unwrap())
+        };
+
+        let (exit_code, _) = check(&[]);
+        assert_eq!(exit_code, 1);
+
+        // Downgraded and disabled types no longer block the gate
+        let (exit_code, text) = check(&[
+            (IssueType::DeprecatedInternalCoupling, Some(Severity::Low)),
+            (IssueType::HighEfferentCoupling, None),
+        ]);
+        assert_eq!(exit_code, 0, "{}", text);
+
+        // Raising a type makes it block
+        let (exit_code, _) = check(&[
+            (IssueType::DeprecatedInternalCoupling, Some(Severity::Low)),
+            (IssueType::HighEfferentCoupling, Some(Severity::Critical)),
+        ]);
+        assert_eq!(exit_code, 1);
+    }
+
+    #[test]
+    fn test_check_fails_modules_over_their_budget() {
+        let mut metrics = ProjectMetrics::new();
+        metrics
+            .module_crates
+            .insert("api".to_string(), "shop".to_string());
+        for (source, target, distance) in [
+            ("api", "shop::orders", Distance::DifferentModule),
+            ("shop::api", "billing", Distance::DifferentModule),
+            ("api", "users", Distance::DifferentModule),
+            ("api", "serde", Distance::DifferentCrate),
+            ("api::v1", "users", Distance::DifferentModule),
+            ("orders", "api", Distance::DifferentModule),
+        ] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Model,
+                distance,
+                Volatility::Low,
+            ));
+        }
+        let budget = |max_out, max_in| ModuleBudget { max_out, max_in };
+        let budgets: BTreeMap<String, ModuleBudget> = [
+            ("crate::api".to_string(), budget(Some(2), Some(1))),
+            ("users".to_string(), budget(None, Some(1))),
+        ]
+        .into_iter()
+        .collect();
+
+        // External crates and submodules do not count against `api`
+        let overages = budget_overages(&metrics, &budgets);
+        assert_eq!(overages.len(), 2, "{overages:?}");
+        assert_eq!(
+            overages[0].to_string(),
+            "crate::api: 3/2 outgoing (over budget by 1)"
+        );
+        assert_eq!(
+            overages[1].to_string(),
+            "users: 2/1 incoming (over budget by 1)"
+        );
+
+        let config = CheckConfig {
+            budgets,
+            ..CheckConfig::default()
+        };
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let result = run_check(&metrics, &thresholds, &config);
+        assert!(!result.passed);
+        assert!(
+            result
+                .failures
+                .contains(&"crate::api: 3/2 outgoing (over budget by 1)".to_string())
+        );
+
+        let result = run_check(&metrics, &thresholds, &CheckConfig::default());
+        assert!(result.budget_overages.is_empty());
+        assert!(result.unmatched_budgets.is_empty());
+
+        // A typo leaves a budget that never applies; the gate warns about it
+        let budgets: BTreeMap<String, ModuleBudget> = [
+            ("crate::api::v1".to_string(), budget(Some(5), None)),
+            ("crate::apj".to_string(), budget(Some(1), None)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(unmatched_budgets(&metrics, &budgets), ["crate::apj"]);
+        let config = CheckConfig {
+            budgets,
+            ..CheckConfig::default()
+        };
+        let mut output = Vec::new();
+        generate_check_output(&metrics, &thresholds, &config, &mut output).unwrap();
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains("Warning: [budgets] entry crate::apj matches no module")
+        );
+    }
+
+    #[test]
+    fn test_check_baseline_file_covers_grade_and_circular_gates() {
+        let mut metrics = ProjectMetrics::new();
+        for (source, target) in [("orders", "billing"), ("billing", "orders")] {
+            metrics.add_coupling(crate::metrics::coupling::CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::High,
+            ));
+        }
+        let thresholds = IssueThresholds::default();
+
+        let result = run_check(&metrics, &thresholds, &CheckConfig::default());
+        assert!(!result.passed);
+        assert_eq!(result.grade, "D");
+        assert_eq!(result.circular_count, 1);
+
+        let report = analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let baseline = IssueBaseline::from_issues(&report.issues, None)
+            .with_circular_dependencies(&metrics.detect_circular_dependencies());
+        let config = CheckConfig {
+            baseline: Some(baseline),
+            ..CheckConfig::default()
+        };
+        let result = run_check(&metrics, &thresholds, &config);
+        assert!(result.passed, "{:?}", result.failures);
+        assert_eq!(result.circular_count, 0);
+        assert_eq!(result.suppressed_count, report.issues.len() + 1);
+    }
+
+    #[test]
+    fn test_github_check_annotates_blocking_issues_and_keeps_exit_code() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod handlers;\npub mod legacy_auth;\npub mod users;\n",
+            ),
+            ("legacy_auth.rs", "pub struct Session;\n"),
+            ("users.rs", "pub struct User;\n"),
+            (
+                "handlers.rs",
+                "use crate::users::User;\nuse crate::legacy_auth::Session;\n\npub fn login(_s: Session, _u: User) {}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            max_dependencies: 1,
+            deprecated_internal: vec!["legacy_auth".to_string()],
+            deprecated_internal_severity: Severity::High,
+            ..IssueThresholds::default()
+        };
+        let config = CheckConfig {
+            min_grade: None,
+            max_critical: None,
+            max_circular: None,
+            fail_on: Some(Severity::High),
+            baseline: None,
+            budgets: BTreeMap::new(),
+        };
+        let mut output = Vec::new();
+        let exit_code =
+            generate_check_github_output(&metrics, &thresholds, &config, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let annotation = |title: &str| {
+            text.lines()
+                .find(|line| line.contains(&format!("title={title}::")))
+                .unwrap_or_else(|| panic!("no {title} annotation in {text}"))
+        };
+
+        assert_eq!(exit_code, 1);
+        let deprecated = annotation("Deprecated Internal Coupling");
+        assert!(deprecated.starts_with("::error file="), "{deprecated}");
+        assert!(
+            deprecated.contains("src/handlers.rs,line=2,col=5,"),
+            "{deprecated}"
+        );
+        let efferent = annotation("High Efferent Coupling");
+        assert!(efferent.contains("src/handlers.rs,title="), "{efferent}");
+        assert!(text.contains("::error title=Coupling Quality Gate::"));
+        assert!(text.ends_with("failed\n"));
+
+        let passing = CheckConfig {
+            fail_on: Some(Severity::Critical),
+            ..config
+        };
+        let mut output = Vec::new();
+        let exit_code =
+            generate_check_github_output(&metrics, &thresholds, &passing, &mut output).unwrap();
+        assert_eq!(exit_code, 0);
+        assert!(!String::from_utf8(output).unwrap().contains("::"));
+
+        assert_eq!(escape_workflow_data("50%\nnext"), "50%25%0Anext");
+        assert_eq!(escape_workflow_property("a:b,c"), "a%3Ab%2Cc");
+    }
+
     #[test]
     fn test_empty_metrics_hotspots() {
         let metrics = ProjectMetrics::new();
@@ -2257,9 +5285,12 @@ mod tests {
             git_used: false,
             tests_excluded: true,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         let mut buf = Vec::new();
 
@@ -2292,6 +5323,294 @@ mod tests {
         }));
     }
 
+    /// Check that `schema` describes every object key in `value`, and that
+    /// required keys are present.
+    fn check_schema(
+        value: &serde_json::Value,
+        schema: &serde_json::Value,
+        defs: &serde_json::Value,
+        path: &str,
+    ) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return check_schema(value, &defs[name], defs, path);
+        }
+        if let Some(variants) = schema["anyOf"].as_array().or(schema["oneOf"].as_array()) {
+            return variants
+                .iter()
+                .find_map(|variant| check_schema(value, variant, defs, path).ok())
+                .ok_or_else(|| format!("{path}: no schema variant matches {value}"));
+        }
+        match value {
+            serde_json::Value::Object(object) => {
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    let key = required.as_str().unwrap();
+                    if !object.contains_key(key) {
+                        return Err(format!("{path}.{key}: required but missing"));
+                    }
+                }
+                for (key, field) in object {
+                    let field_schema = match &schema["properties"][key] {
+                        serde_json::Value::Null => &schema["additionalProperties"],
+                        field_schema => field_schema,
+                    };
+                    if !field_schema.is_object() {
+                        return Err(format!("{path}.{key}: not in the schema"));
+                    }
+                    check_schema(field, field_schema, defs, &format!("{path}.{key}"))?;
+                }
+                Ok(())
+            }
+            serde_json::Value::Array(items) => items.iter().try_for_each(|item| {
+                check_schema(item, &schema["items"], defs, &format!("{path}[]"))
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    #[test]
+    fn test_json_schema_describes_the_json_output() {
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::IntegrationStrength;
+        use crate::metrics::module::ModuleMetrics;
+        use crate::volatility::Volatility;
+
+        let mut metrics = ProjectMetrics::new();
+        for name in ["a", "b"] {
+            metrics.add_module(ModuleMetrics::new(
+                PathBuf::from(format!("src/{name}.rs")),
+                name.to_string(),
+            ));
+        }
+        for (source, target) in [("a", "b"), ("b", "a")] {
+            let mut coupling = CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::High,
+            );
+            coupling.feature = Some("extra".to_string());
+            metrics.add_coupling(coupling);
+        }
+        let mut buf = Vec::new();
+        generate_json_output(
+            &metrics,
+            &IssueThresholds::default(),
+            &build_manifest(&ManifestContext::default()),
+            &mut buf,
+        )
+        .unwrap();
+        let output: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(output["schema_version"], JSON_SCHEMA_VERSION);
+        assert!(!output["issues"].as_array().unwrap().is_empty());
+        assert!(!output["mutual_couplings"].as_array().unwrap().is_empty());
+
+        let mut buf = Vec::new();
+        generate_json_schema_output(&mut buf).unwrap();
+        let schema: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            JSON_SCHEMA_VERSION
+        );
+        check_schema(&output, &schema, &schema["$defs"], "$").unwrap();
+    }
+
+    #[test]
+    fn test_worst_couplings_are_ranked_lowest_score_first_with_their_cause() {
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::IntegrationStrength;
+        use crate::volatility::Volatility;
+
+        let mut metrics = ProjectMetrics::new();
+        for (target, strength, distance, volatility, line) in [
+            (
+                "cohesive",
+                IntegrationStrength::Intrusive,
+                Distance::SameModule,
+                Volatility::Low,
+                3,
+            ),
+            (
+                "volatile",
+                IntegrationStrength::Intrusive,
+                Distance::DifferentModule,
+                Volatility::High,
+                7,
+            ),
+            (
+                "misaligned",
+                IntegrationStrength::Contract,
+                Distance::SameFunction,
+                Volatility::Low,
+                9,
+            ),
+        ] {
+            metrics.add_coupling(CouplingMetrics::with_location(
+                "orders".to_string(),
+                target.to_string(),
+                strength,
+                distance,
+                volatility,
+                Default::default(),
+                PathBuf::from("src/orders.rs"),
+                line,
+            ));
+        }
+
+        let mut buf = Vec::new();
+        generate_worst_couplings_output(&metrics, &IssueThresholds::default(), 2, &mut buf)
+            .unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        assert!(text.starts_with("Worst 2 Couplings"));
+        let volatile = text.find("#1 orders → volatile").unwrap();
+        let misaligned = text.find("#2 orders → misaligned").unwrap();
+        assert!(volatile < misaligned, "{text}");
+        assert!(!text.contains("cohesive"), "{text}");
+        assert!(text.contains("📍 src/orders.rs:7"));
+        assert!(text.contains("Why: volatility-dominated"));
+        assert!(text.contains("Why: alignment-dominated"));
+    }
+
+    #[test]
+    fn test_quick_wins_rank_cheap_fixes_with_wide_reach_first() {
+        let getters = "use crate::config::Config;\n\
+                       pub fn port(c: &Config) -> u16 { c.port }\n\
+                       pub fn name(c: &Config) -> &str { &c.name }\n";
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod config;\npub mod api;\npub mod jobs;\npub mod client;\n",
+            ),
+            (
+                "config.rs",
+                "pub struct Config { pub name: String, pub port: u16 }\n",
+            ),
+            ("api.rs", getters),
+            ("jobs.rs", getters),
+            (
+                "client.rs",
+                "pub async fn a() {}\npub async fn b() {}\npub async fn c() {}\n\
+                 pub fn d() {}\npub fn e() {}\npub fn f() {}\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let wins = calculate_quick_wins(&metrics, &thresholds, 20);
+
+        // Getters reach config and its two dependents; the split is costly and reaches nobody
+        let ranked: Vec<(&str, &str, usize)> = wins
+            .iter()
+            .map(|w| (w.source.as_str(), w.estimated_effort.as_str(), w.impact))
+            .collect();
+        assert_eq!(
+            ranked,
+            [("config::Config", "Low", 3), ("client", "High", 1)]
+        );
+        assert_eq!(wins[0].roi, 3.0);
+        assert_eq!(wins[1].roi, 0.25);
+        assert_eq!(calculate_quick_wins(&metrics, &thresholds, 1).len(), 1);
+
+        let mut buf = Vec::new();
+        generate_json_output(
+            &metrics,
+            &thresholds,
+            &build_manifest(&ManifestContext::default()),
+            &mut buf,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(json["quick_wins"][0]["source"], "config::Config");
+        let issue = json["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["source"] == "client")
+            .unwrap();
+        assert_eq!(issue["estimated_effort"], "High");
+        assert_eq!(issue["impact"], 1);
+    }
+
+    #[test]
+    fn test_markdown_table_lists_only_issues_most_severe_first() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod config;\npub mod api;\npub mod raw;\n"),
+            (
+                "config.rs",
+                "pub struct Config { pub name: String, pub port: u16 }\n",
+            ),
+            (
+                "api.rs",
+                "use crate::config::Config;\npub fn port(c: &Config) -> u16 { c.port }\n",
+            ),
+            (
+                "raw.rs",
+                "pub fn read(p: *const u8) -> u8 { unsafe { *p } }\n\
+                 pub fn write(p: *mut u8) { unsafe { *p = 0 } }\n\
+                 pub fn zero(p: *mut u8) { unsafe { *p = 0 } }\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            strict_mode: false,
+            ..IssueThresholds::default()
+        };
+        let mut buf = Vec::new();
+        generate_markdown_table_output(&metrics, &thresholds, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "| Severity | Type | Source | Target | Suggestion | Score | Location |"
+        );
+        assert!(lines[1].starts_with("|---"));
+        assert!(
+            lines[2].starts_with("| Medium | Unsafe Surface | `raw` |"),
+            "{text}"
+        );
+        assert!(
+            lines[3..]
+                .iter()
+                .any(|line| line.starts_with("| Low | Public Field Exposure | `config::Config` |")),
+            "{text}"
+        );
+        assert!(lines.iter().all(|line| line.starts_with('|')), "{text}");
+        assert_eq!(markdown_cell("a | b\nc"), "a \\| b c");
+    }
+
+    #[test]
+    fn test_summary_json_has_headline_numbers_without_detail_arrays() {
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::IntegrationStrength;
+        use crate::volatility::Volatility;
+
+        let mut metrics = ProjectMetrics::new();
+        for (source, target) in [("a", "b"), ("b", "a")] {
+            metrics.add_coupling(CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Functional,
+                Distance::DifferentModule,
+                Volatility::Low,
+            ));
+        }
+        let mut buf = Vec::new();
+
+        generate_summary_json_output(&metrics, &IssueThresholds::default(), &mut buf).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        for detail in ["modules", "issues", "hotspots", "couplings"] {
+            assert!(parsed.get(detail).is_none(), "{detail} should be omitted");
+        }
+        assert!(parsed["summary"]["health_grade"].is_string());
+        assert!(parsed["summary"]["health_score"].is_number());
+        assert_eq!(parsed["summary"]["total_couplings"], 2);
+        assert_eq!(parsed["circular_dependencies"], 1);
+    }
+
     #[test]
     fn test_json_output_includes_module_subdomain_when_present() {
         use crate::config::Subdomain;
@@ -9,16 +9,44 @@
 //! # .coupling.toml
 //!
 //! [analysis]
-//! # Exclude test code (#[test], #[cfg(test)], mod tests) from analysis
+//! # Exclude test code (#[test], #[cfg(test)], mod tests) from analysis (default)
 //! exclude_tests = true
 //!
-//! # "Prelude-like" modules that are expected to be used by many other modules.
-//! # These modules will not trigger "High Afferent Coupling" warnings.
-//! prelude_modules = ["src/lib.rs", "src/prelude.rs", "src/core/*"]
+//! # Deprecated alias of [allowlist] prelude_modules; both lists are combined
+//! prelude_modules = ["src/lib.rs"]
 //!
 //! # Modules to completely exclude from analysis
 //! exclude = ["src/generated/*", "src/test_utils/*"]
 //!
+//! # Workspace crate kinds analyzed and listed but left out of the health grade
+//! # ("proc-macro", "build-dependency"; default: ["proc-macro"])
+//! exclude_crate_kinds = ["proc-macro", "build-dependency"]
+//!
+//! # Type names skipped like built-in primitives (String, Option, Vec, ...)
+//! treat_as_primitive = ["Uuid", "Bytes"]
+//!
+//! # Type names always counted, even when built in or short and lowercase
+//! count_types = ["Arc", "id"]
+//!
+//! # Treat all-lowercase names of up to 3 characters as local variables (default: true)
+//! short_name_heuristic = false
+//!
+//! # Count each (source, target, strength, distance) coupling once, with an
+//! # occurrence count, instead of once per usage (default: false)
+//! merge_couplings = true
+//!
+//! # Skip files whose first lines contain one of these markers, such as
+//! # prost/tonic-build output (default: ["@generated"]; [] analyzes every file)
+//! generated_markers = ["@generated", "DO NOT EDIT"]
+//!
+//! # Path names dropped as likely local variables (`config.x`, `state`); replaces
+//! # the built-in list. Qualified paths into a module of the project are always kept
+//! local_variable_names = ["request", "response", "ctx"]
+//!
+//! # Single-segment lowercase names up to this length count as local variables
+//! # (default: 8; 0 turns the rule off)
+//! max_local_name_len = 4
+//!
 //! [volatility]
 //! # Modules expected to change frequently (High volatility)
 //! high = ["src/business_rules/*", "src/pricing/*"]
@@ -29,6 +57,12 @@
 //! # Paths to ignore from analysis (deprecated: use [analysis].exclude instead)
 //! ignore = ["src/generated/*", "tests/*"]
 //!
+//! # Git paths left out of change counts and co-change pairs
+//! exclude = ["**/generated/**", "vendor/**"]
+//!
+//! # Only read git history under this directory of the analyzed path
+//! scope = "crates/core"
+//!
 //! [subdomains]
 //! # DDD subdomain classification (Khononov's Balanced Coupling model)
 //! # Volatility is derived from business domain, not just git history.
@@ -43,18 +77,167 @@
 //!
 //! # Maximum dependents before flagging High Afferent Coupling
 //! max_dependents = 20
+//!
+//! # Maximum generic parameters on a type before flagging Over-Parameterized Type
+//! max_type_generics = 3
+//!
+//! # Minimum modules accessing the same member before flagging Spread Coupling
+//! min_spread_modules = 4
+//!
+//! # Minimum async and public sync functions before flagging Mixed Async Boundary
+//! min_mixed_async_functions = 3
+//!
+//! # Minimum unsafe blocks plus unsafe fns before a module with public API gets
+//! # an (advisory) Unsafe Surface issue; 0 disables it
+//! min_unsafe_surface = 3
+//!
+//! # Minimum shared named fields before structs in different modules get an
+//! # (advisory) Duplicated Structure issue; 0 disables it
+//! min_duplicated_fields = 3
+//!
+//! # Largest-cluster share (0.0-1.0) of a module's functions below which Low Cohesion is flagged
+//! min_cohesion = 0.6
+//!
+//! # Maximum public interface / implementation size ratio before flagging Shallow Module
+//! max_interface_ratio = 0.5
+//!
+//! # Minimum public interface size before a module is checked for Shallow Module
+//! min_shallow_interface = 8
+//!
+//! # Weighted cognitive load before flagging High Cognitive Load
+//! max_cognitive_load = 60.0
+//!
+//! [balance_bands]
+//! # Minimum balance score for each interpretation band (must strictly decrease)
+//! balanced = 0.8
+//! acceptable = 0.6
+//! needs_review = 0.4
+//! needs_refactoring = 0.2
+//!
+//! [balance]
+//! # How alignment and volatility impact combine into the balance score:
+//! # "multiply" (default), "min" or "weighted_sum". Weights are exponents for
+//! # multiply and min, linear weights for weighted_sum.
+//! combiner = "multiply"
+//! alignment_weight = 1.0
+//! volatility_weight = 1.0
+//! # Score couplings to non-public items across a boundary one strength level
+//! # higher (Model -> Functional, ...); false scores the measured strength
+//! visibility_penalty = true
+//! # With merge_couplings, count a merged coupling once per occurrence in the
+//! # average score and hotspot coupling counts (default: false)
+//! weight_by_occurrences = false
+//!
+//! [cognitive_load]
+//! # Load added per unit of each factor (summed, then compared to max_cognitive_load)
+//! public_api = 1.0          # per public function or type
+//! external_crates = 2.0     # per distinct external crate
+//! average_params = 4.0      # per parameter of the average function
+//! item_dependencies = 0.02  # per item-level dependency
+//!
+//! [god_module]
+//! # Functions, types and impls a module may have before it is a God Module
+//! max_functions = 30
+//! max_types = 15
+//! max_impls = 20
+//!
+//! # Per-module limits; apply to the module and its submodules, unset limits stay global.
+//! # A file can also opt out with a `// coupling:allow god-module` comment at its top.
+//! [god_module.overrides]
+//! "crate::generated" = { max_functions = 500 }
+//!
+//! [grading]
+//! # Internal couplings required before a project can be graded A.
+//! # Below this, issue density is too noisy to certify balance (grade capped at B).
+//! min_couplings_for_a = 10
+//!
+//! # Grade projects without internal couplings B (nothing to assess)
+//! cap_uncoupled_at_b = true
+//!
+//! [allowlist]
+//! # Shared modules depended on everywhere by design, matched by module path or file path.
+//! # Exempt from "High Afferent Coupling" and hotspots; efferent coupling is still checked.
+//! prelude_modules = ["crate::prelude", "crate::utils::*", "src/core/*"]
+//! # Intentional copies exempt from "Duplicated Structure": a module, a type
+//! # name or `module::Type`, matched by whole `::` segments.
+//! duplicated_structures = ["api::dto", "wire::Header"]
+//!
+//! [policy]
+//! # Legacy internal modules to migrate away from; every coupling into them is flagged
+//! deprecated_internal = ["legacy_auth", "old_db"]
+//! # Severity of those issues (low, medium, high, critical)
+//! deprecated_internal_severity = "high"
+//!
+//! [external]
+//! # Your own crates to treat like the built-in lists: stable ones like serde,
+//! # infrastructure like tokio. Once set, Scattered External Coupling on them is
+//! # reported one severity lower and std/core/alloc are not flagged.
+//! stable = ["acme_core"]
+//! infrastructure = ["acme_runtime"]
+//!
+//! # Architecture layers, innermost first. A layer may depend on itself and the
+//! # layers listed before it; any coupling into a later layer is a Critical
+//! # Layer Violation. Modules match by whole `::` segments.
+//! [[layers]]
+//! name = "domain"
+//! modules = ["domain"]
+//!
+//! [[layers]]
+//! name = "application"
+//! modules = ["application", "app::services"]
+//!
+//! [[layers]]
+//! name = "infrastructure"
+//! modules = ["infrastructure", "db", "web"]
+//!
+//! # Per-module coupling budgets enforced by `--check`: internal couplings out
+//! # of the module (max_out) and into it (max_in). Unset limits are not checked.
+//! [budgets]
+//! "crate::api" = { max_out = 10, max_in = 50 }
+//!
+//! [severity]
+//! # Report an issue type at another severity, or "off" to drop it entirely
+//! PrimitiveObsession = "off"
+//! GodModule = "high"
+//!
+//! [project]
+//! # Source roots for projects without `cargo metadata` (kernel modules, no_std firmware).
+//! # Only used when no Cargo workspace is found. Each root is analyzed as its own crate,
+//! # named after its directory (the parent directory for a trailing `src`).
+//! source_roots = ["firmware/src"]
+//!
+//! # Crates with an explicit name
+//! [[project.crates]]
+//! name = "kmod"
+//! path = "kernel/module"
 //! ```
 
 use glob::Pattern;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use serde::Deserialize;
-use std::collections::HashMap;
+use serde::de::IntoDeserializer;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::analyzer::{DependencyPathFilter, PrimitiveTypes};
+use crate::balance::grade::GradeMinimums;
+use crate::balance::issue_type::IssueType;
+use crate::balance::score::{
+    ArchitectureLayer, BalanceBands, BalanceCombiner, BalanceFormula, CognitiveLoadWeights,
+    GodModuleLimits, IssueThresholds,
+};
+use crate::balance::severity::{Severity, SeverityOverride};
+use crate::cache::AnalysisCache;
+use crate::classification::{DEFAULT_LOCAL_VARIABLE_NAMES, DEFAULT_MAX_LOCAL_NAME_LEN};
+use crate::header::stable_hash;
 use crate::metrics::dimensions::MetricsConfig;
 pub use crate::metrics::dimensions::Subdomain;
 use crate::volatility::Volatility;
+use crate::workspace::CrateKind;
+use crate::{log_debug, log_warn};
 
 /// Errors that can occur when loading configuration
 #[derive(Error, Debug)]
@@ -67,23 +250,104 @@ pub enum ConfigError {
 
     #[error("Invalid glob pattern: {0}")]
     PatternError(String),
+
+    #[error("Invalid config value: {0}")]
+    InvalidValue(String),
 }
 
 /// Analysis configuration section
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct AnalysisConfig {
-    /// Exclude test code from analysis (#[test], #[cfg(test)], mod tests)
-    #[serde(default)]
+    /// Exclude test code from analysis (#[test], #[cfg(test)], mod tests); on by default
+    #[serde(default = "default_exclude_tests")]
     pub exclude_tests: bool,
 
-    /// "Prelude-like" modules that are expected to be depended on by many modules.
-    /// These modules will not trigger "High Afferent Coupling" warnings.
+    /// Deprecated alias of `[allowlist] prelude_modules`; the two lists are combined.
     #[serde(default)]
     pub prelude_modules: Vec<String>,
 
     /// Modules to completely exclude from analysis
     #[serde(default)]
     pub exclude: Vec<String>,
+
+    /// Workspace crate kinds left out of the health grade (still analyzed and listed)
+    #[serde(default = "default_exclude_crate_kinds")]
+    pub exclude_crate_kinds: Vec<CrateKind>,
+
+    /// Extra type names skipped like built-in primitives (e.g. `Uuid`)
+    #[serde(default)]
+    pub treat_as_primitive: Vec<String>,
+
+    /// Type names always counted, even when built in (`Arc`) or short (`id`)
+    #[serde(default)]
+    pub count_types: Vec<String>,
+
+    /// Treat all-lowercase names of up to 3 characters as local variables
+    #[serde(default = "default_short_name_heuristic")]
+    pub short_name_heuristic: bool,
+
+    /// Fold repeated couplings with the same source, target, strength and distance into one
+    #[serde(default)]
+    pub merge_couplings: bool,
+
+    /// Markers that identify generated files when found in a file's header
+    #[serde(default = "default_generated_markers")]
+    pub generated_markers: Vec<String>,
+
+    /// Names dropped as local variables when they end a path of one or two segments
+    #[serde(default = "default_local_variable_names")]
+    pub local_variable_names: Vec<String>,
+
+    /// Single-segment lowercase names up to this length are dropped as local variables (0: none)
+    #[serde(default = "default_max_local_name_len")]
+    pub max_local_name_len: usize,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            exclude_tests: true,
+            prelude_modules: Vec::new(),
+            exclude: Vec::new(),
+            exclude_crate_kinds: default_exclude_crate_kinds(),
+            treat_as_primitive: Vec::new(),
+            count_types: Vec::new(),
+            short_name_heuristic: default_short_name_heuristic(),
+            merge_couplings: false,
+            generated_markers: default_generated_markers(),
+            local_variable_names: default_local_variable_names(),
+            max_local_name_len: default_max_local_name_len(),
+        }
+    }
+}
+
+/// Proc-macro crates are build tooling, not the architecture being graded.
+fn default_exclude_crate_kinds() -> Vec<CrateKind> {
+    vec![CrateKind::ProcMacro]
+}
+
+fn default_short_name_heuristic() -> bool {
+    true
+}
+
+/// `// @generated` is the convention of prost, tonic-build and most code generators.
+fn default_generated_markers() -> Vec<String> {
+    vec!["@generated".to_string()]
+}
+
+fn default_local_variable_names() -> Vec<String> {
+    DEFAULT_LOCAL_VARIABLE_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn default_exclude_tests() -> bool {
+    true
+}
+
+fn default_max_local_name_len() -> usize {
+    DEFAULT_MAX_LOCAL_NAME_LEN
 }
 
 /// Volatility configuration section
@@ -104,6 +368,14 @@ pub struct VolatilityConfig {
     /// Paths to ignore from analysis
     #[serde(default)]
     pub ignore: Vec<String>,
+
+    /// Git paths left out of change counts (generated or vendored code)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Directory, relative to the analyzed path, that git history is read from
+    #[serde(default)]
+    pub scope: Option<PathBuf>,
 }
 
 /// DDD subdomain classification for volatility assessment
@@ -138,6 +410,42 @@ pub struct ThresholdsConfig {
     /// Maximum dependents before flagging High Afferent Coupling
     #[serde(default = "default_max_dependents")]
     pub max_dependents: usize,
+
+    /// Maximum generic parameters on a type before flagging Over-Parameterized Type
+    #[serde(default = "default_max_type_generics")]
+    pub max_type_generics: usize,
+
+    /// Minimum modules accessing the same member before flagging Spread Coupling
+    #[serde(default = "default_min_spread_modules")]
+    pub min_spread_modules: usize,
+
+    /// Minimum async and public sync functions before flagging Mixed Async Boundary
+    #[serde(default = "default_min_mixed_async_functions")]
+    pub min_mixed_async_functions: usize,
+
+    /// Minimum unsafe blocks plus unsafe fns in a module with public API before flagging Unsafe Surface
+    #[serde(default = "default_min_unsafe_surface")]
+    pub min_unsafe_surface: usize,
+
+    /// Minimum shared named fields before flagging Duplicated Structure
+    #[serde(default = "default_min_duplicated_fields")]
+    pub min_duplicated_fields: usize,
+
+    /// Largest-cluster share (0.0-1.0) of a module's functions below which Low Cohesion is flagged
+    #[serde(default = "default_min_cohesion")]
+    pub min_cohesion: f64,
+
+    /// Maximum interface/implementation size ratio before flagging Shallow Module
+    #[serde(default = "default_max_interface_ratio")]
+    pub max_interface_ratio: f64,
+
+    /// Minimum interface size before a module is checked for Shallow Module
+    #[serde(default = "default_min_shallow_interface")]
+    pub min_shallow_interface: usize,
+
+    /// Weighted cognitive load before flagging High Cognitive Load
+    #[serde(default = "default_max_cognitive_load")]
+    pub max_cognitive_load: f64,
 }
 
 fn default_max_dependencies() -> usize {
@@ -148,15 +456,284 @@ fn default_max_dependents() -> usize {
     20
 }
 
+fn default_max_type_generics() -> usize {
+    3
+}
+
+fn default_min_spread_modules() -> usize {
+    4
+}
+
+fn default_min_mixed_async_functions() -> usize {
+    3
+}
+
+fn default_min_unsafe_surface() -> usize {
+    3
+}
+
+fn default_min_duplicated_fields() -> usize {
+    3
+}
+
+fn default_min_cohesion() -> f64 {
+    0.6
+}
+
+fn default_max_interface_ratio() -> f64 {
+    0.5
+}
+
+fn default_min_shallow_interface() -> usize {
+    8
+}
+
+fn default_max_cognitive_load() -> f64 {
+    60.0
+}
+
 impl Default for ThresholdsConfig {
     fn default() -> Self {
         Self {
             max_dependencies: default_max_dependencies(),
             max_dependents: default_max_dependents(),
+            max_type_generics: default_max_type_generics(),
+            min_spread_modules: default_min_spread_modules(),
+            min_mixed_async_functions: default_min_mixed_async_functions(),
+            min_unsafe_surface: default_min_unsafe_surface(),
+            min_duplicated_fields: default_min_duplicated_fields(),
+            min_cohesion: default_min_cohesion(),
+            max_interface_ratio: default_max_interface_ratio(),
+            min_shallow_interface: default_min_shallow_interface(),
+            max_cognitive_load: default_max_cognitive_load(),
         }
     }
 }
 
+/// Balance interpretation band configuration section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BalanceBandsConfig {
+    /// Minimum score for Balanced
+    pub balanced: f64,
+    /// Minimum score for Acceptable
+    pub acceptable: f64,
+    /// Minimum score for Needs Review
+    pub needs_review: f64,
+    /// Minimum score for Needs Refactoring (below is Critical)
+    pub needs_refactoring: f64,
+}
+
+impl Default for BalanceBandsConfig {
+    fn default() -> Self {
+        let bands = BalanceBands::default();
+        Self {
+            balanced: bands.balanced,
+            acceptable: bands.acceptable,
+            needs_review: bands.needs_review,
+            needs_refactoring: bands.needs_refactoring,
+        }
+    }
+}
+
+/// Balance score formula configuration section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BalanceConfig {
+    /// Weight of strength/distance alignment
+    pub alignment_weight: f64,
+    /// Weight of volatility impact
+    pub volatility_weight: f64,
+    /// How the two factors combine
+    pub combiner: BalanceCombiner,
+    /// Score couplings at their visibility-adjusted strength
+    pub visibility_penalty: bool,
+    /// Count merged couplings once per occurrence in averages
+    pub weight_by_occurrences: bool,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        let formula = BalanceFormula::default();
+        Self {
+            alignment_weight: formula.alignment_weight,
+            volatility_weight: formula.volatility_weight,
+            combiner: formula.combiner,
+            visibility_penalty: formula.visibility_penalty,
+            weight_by_occurrences: formula.weight_by_occurrences,
+        }
+    }
+}
+
+/// Cognitive load weight configuration section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CognitiveLoadConfig {
+    /// Load per public function or type
+    pub public_api: f64,
+    /// Load per distinct external crate
+    pub external_crates: f64,
+    /// Load per parameter of the average function
+    pub average_params: f64,
+    /// Load per item-level dependency
+    pub item_dependencies: f64,
+}
+
+impl Default for CognitiveLoadConfig {
+    fn default() -> Self {
+        let weights = CognitiveLoadWeights::default();
+        Self {
+            public_api: weights.public_api,
+            external_crates: weights.external_crates,
+            average_params: weights.average_params,
+            item_dependencies: weights.item_dependencies,
+        }
+    }
+}
+
+/// God Module limits configuration section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GodModuleConfig {
+    /// Functions a module may define before it is a God Module
+    pub max_functions: usize,
+    /// Types a module may define before it is a God Module
+    pub max_types: usize,
+    /// Trait and inherent impls a module may have before it is a God Module
+    pub max_impls: usize,
+    /// Limits per module path, applied to the module and its submodules
+    pub overrides: BTreeMap<String, GodModuleLimits>,
+}
+
+impl Default for GodModuleConfig {
+    fn default() -> Self {
+        let thresholds = IssueThresholds::default();
+        Self {
+            max_functions: thresholds.max_functions,
+            max_types: thresholds.max_types,
+            max_impls: thresholds.max_impls,
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// Coupling budget for one module path from `[budgets]`; unset limits are not checked.
+///
+/// Counts are internal couplings, the way `max_dependencies` and
+/// `max_dependents` count them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModuleBudget {
+    /// Couplings from the module to other modules
+    pub max_out: Option<usize>,
+    /// Couplings from other modules to the module
+    pub max_in: Option<usize>,
+}
+
+/// Grading configuration section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GradingConfig {
+    /// Internal couplings required before a project can be graded A
+    pub min_couplings_for_a: usize,
+    /// Grade projects without internal couplings B
+    pub cap_uncoupled_at_b: bool,
+}
+
+impl Default for GradingConfig {
+    fn default() -> Self {
+        let minimums = GradeMinimums::default();
+        Self {
+            min_couplings_for_a: minimums.min_couplings_for_a,
+            cap_uncoupled_at_b: minimums.cap_uncoupled_at_b,
+        }
+    }
+}
+
+/// Prelude-like modules listed by module path, and intentional duplication
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct AllowlistConfig {
+    /// Module path or file path globs (`crate::prelude`, `my_crate::utils::*`,
+    /// `src/prelude.rs`) exempt from afferent checks
+    pub prelude_modules: Vec<String>,
+    /// Modules or types (`api::dto`, `wire::Header`) exempt from Duplicated Structure
+    pub duplicated_structures: Vec<String>,
+}
+
+/// Policy checks layered on the coupling graph
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Internal modules new code should stop depending on
+    pub deprecated_internal: Vec<String>,
+    /// Severity of couplings into a deprecated module
+    pub deprecated_internal_severity: Severity,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            deprecated_internal: Vec::new(),
+            deprecated_internal_severity: Severity::Medium,
+        }
+    }
+}
+
+/// Crates added to the built-in stability classes (`[external]`)
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct ExternalConfig {
+    /// Crates as dependable as serde or thiserror
+    pub stable: Vec<String>,
+    /// Crates that are architectural choices, like tokio or tracing
+    pub infrastructure: Vec<String>,
+}
+
+/// A manually declared crate boundary for non-cargo projects
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ProjectCrateConfig {
+    /// Crate name used as the module prefix
+    pub name: String,
+    /// Source root directory, relative to the config file
+    pub path: PathBuf,
+}
+
+/// Manual project layout, used when `cargo metadata` is unavailable
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ProjectConfig {
+    /// Source roots, each analyzed as a crate named after its directory
+    #[serde(default)]
+    pub source_roots: Vec<PathBuf>,
+
+    /// Source roots with explicit crate names
+    #[serde(default)]
+    pub crates: Vec<ProjectCrateConfig>,
+}
+
+impl ProjectConfig {
+    /// All declared crates; `source_roots` entries get a name derived from their path.
+    pub fn declared_crates(&self) -> Vec<ProjectCrateConfig> {
+        let derived = self.source_roots.iter().map(|path| {
+            let named_dir = if path.file_name().is_some_and(|name| name == "src") {
+                path.parent().filter(|parent| parent.file_name().is_some())
+            } else {
+                Some(path.as_path())
+            };
+            let name = named_dir
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "crate".to_string());
+            ProjectCrateConfig {
+                name,
+                path: path.clone(),
+            }
+        });
+
+        derived.chain(self.crates.iter().cloned()).collect()
+    }
+}
+
 /// Root configuration structure
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct CouplingConfig {
@@ -175,6 +752,118 @@ pub struct CouplingConfig {
     /// Threshold configuration
     #[serde(default)]
     pub thresholds: ThresholdsConfig,
+
+    /// Balance score interpretation bands
+    #[serde(default)]
+    pub balance_bands: BalanceBandsConfig,
+
+    /// Balance score formula
+    #[serde(default)]
+    pub balance: BalanceConfig,
+
+    /// Cognitive load factor weights
+    #[serde(default)]
+    pub cognitive_load: CognitiveLoadConfig,
+
+    /// God Module limits, globally and per module path
+    #[serde(default)]
+    pub god_module: GodModuleConfig,
+
+    /// Minimum evidence required for top grades
+    #[serde(default)]
+    pub grading: GradingConfig,
+
+    /// Prelude-like modules exempt from afferent coupling checks
+    #[serde(default)]
+    pub allowlist: AllowlistConfig,
+
+    /// Policy checks (deprecated internal modules)
+    #[serde(default)]
+    pub policy: PolicyConfig,
+
+    /// Stable and infrastructure crates besides the built-in ones
+    #[serde(default)]
+    pub external: ExternalConfig,
+
+    /// Architecture layers, innermost first (`[[layers]]`)
+    #[serde(default)]
+    pub layers: Vec<ArchitectureLayer>,
+
+    /// Severity per issue type name (`GodModule = "high"`, `PrimitiveObsession = "off"`)
+    #[serde(default)]
+    pub severity: HashMap<String, SeverityOverride>,
+
+    /// Coupling budgets per module path, enforced by `--check`
+    #[serde(default)]
+    pub budgets: BTreeMap<String, ModuleBudget>,
+
+    /// Manual layout for projects outside the cargo model
+    #[serde(default)]
+    pub project: ProjectConfig,
+}
+
+/// Fractional thresholds feed comparisons and ratios, so they must be real non-negative numbers
+fn validate_thresholds(thresholds: &ThresholdsConfig) -> Result<(), ConfigError> {
+    let value = thresholds.max_cognitive_load;
+    if !value.is_finite() || value < 0.0 {
+        return Err(ConfigError::InvalidValue(format!(
+            "thresholds: max_cognitive_load must be a non-negative number, got {}",
+            value
+        )));
+    }
+    let value = thresholds.min_cohesion;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(ConfigError::InvalidValue(format!(
+            "thresholds: min_cohesion must be between 0.0 and 1.0, got {}",
+            value
+        )));
+    }
+    Ok(())
+}
+
+/// Layers need a unique name and at least one module, or no path could be placed in them
+fn validate_layers(layers: &[ArchitectureLayer]) -> Result<(), ConfigError> {
+    for (index, layer) in layers.iter().enumerate() {
+        if layer.name.trim().is_empty() {
+            return Err(ConfigError::InvalidValue(format!(
+                "layers: layer {} has no name",
+                index + 1
+            )));
+        }
+        if layer.modules.is_empty() {
+            return Err(ConfigError::InvalidValue(format!(
+                "layers: `{}` lists no modules",
+                layer.name
+            )));
+        }
+        if layers[..index].iter().any(|other| other.name == layer.name) {
+            return Err(ConfigError::InvalidValue(format!(
+                "layers: `{}` is declared twice",
+                layer.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Compile CLI path globs; `*` stays within one path component, `**` spans several
+fn compile_glob_set(patterns: &[String]) -> Result<Option<GlobSet>, ConfigError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches("./");
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| ConfigError::PatternError(format!("{}: {}", pattern, err)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|err| ConfigError::PatternError(err.to_string()))
 }
 
 /// Compiled configuration with glob patterns
@@ -183,12 +872,35 @@ pub struct CompiledConfig {
     // === Analysis settings ===
     /// Whether to exclude test code from analysis
     pub exclude_tests: bool,
+    /// Whether to record why each coupling was classified (CLI --verbose)
+    pub record_provenance: bool,
+    /// Whether duplicate couplings are merged with an occurrence count
+    pub merge_couplings: bool,
+    /// Header markers of generated files, which are skipped
+    pub generated_markers: Vec<String>,
     /// Directory containing the loaded config file, if any.
     config_root: Option<PathBuf>,
-    /// Patterns for prelude-like modules (exempt from afferent coupling warnings)
+    /// Patterns for prelude-like modules (exempt from afferent coupling warnings),
+    /// from `[allowlist] prelude_modules` and its `[analysis]` alias
     prelude_patterns: Vec<Pattern>,
+    /// Modules and types from `[allowlist] duplicated_structures`
+    duplicated_structure_allowlist: Vec<String>,
     /// Patterns for modules to completely exclude from analysis
     exclude_patterns: Vec<Pattern>,
+    /// CLI `--include` globs; when set, only matching files are analyzed
+    path_includes: Option<GlobSet>,
+    /// CLI `--exclude` globs, matched like `path_includes`
+    path_excludes: Option<GlobSet>,
+    /// Source of `path_includes` and `path_excludes`, for `config_hash`
+    path_filter_globs: (Vec<String>, Vec<String>),
+    /// Workspace crate kinds left out of the health grade
+    pub exclude_crate_kinds: Vec<CrateKind>,
+    /// Type names skipped as primitives rather than counted as couplings
+    pub primitive_types: PrimitiveTypes,
+    /// Heuristics dropping dependency paths that look like local variables
+    pub dependency_path_filter: DependencyPathFilter,
+    /// Per-file result cache; `None` parses every file (CLI `--no-cache`)
+    pub analysis_cache: Option<AnalysisCache>,
 
     // === Volatility settings ===
     /// Patterns for high volatility paths
@@ -199,6 +911,14 @@ pub struct CompiledConfig {
     low_patterns: Vec<Pattern>,
     /// Patterns for ignored paths (deprecated, use exclude_patterns)
     ignore_patterns: Vec<Pattern>,
+    /// Git paths left out of volatility change counts
+    volatility_exclude_patterns: Vec<Pattern>,
+    /// Newest commits git history analysis reads at most (CLI `--git-max-commits`)
+    pub git_max_commits: Option<usize>,
+    /// Deadline for each `git log` run (CLI `--git-timeout`)
+    pub git_timeout: Option<Duration>,
+    /// Directory git history is read from (`[volatility] scope`, CLI `--git-scope`)
+    pub git_scope: Option<PathBuf>,
 
     // === Subdomain settings ===
     /// Patterns for core subdomain (high volatility)
@@ -211,6 +931,32 @@ pub struct CompiledConfig {
     // === Thresholds ===
     /// Threshold configuration
     pub thresholds: ThresholdsConfig,
+    /// Validated balance interpretation bands
+    pub balance_bands: BalanceBands,
+    /// Validated balance score formula
+    pub balance_formula: BalanceFormula,
+    /// Validated cognitive load factor weights
+    pub cognitive_load_weights: CognitiveLoadWeights,
+    /// God Module limits, globally and per module path
+    pub god_module: GodModuleConfig,
+    /// Minimum evidence required before grading a project A
+    pub grade_minimums: GradeMinimums,
+
+    // === Policy ===
+    /// Policy checks from `[policy]`
+    pub policy: PolicyConfig,
+    /// Stable and infrastructure crates from `[external]`
+    pub external: ExternalConfig,
+    /// Validated architecture layers from `[[layers]]`, innermost first
+    pub layers: Vec<ArchitectureLayer>,
+    /// Issue type severities from `[severity]`; `None` turns the type off
+    pub severity_overrides: BTreeMap<IssueType, Option<Severity>>,
+    /// Coupling budgets per module path from `[budgets]`
+    pub budgets: BTreeMap<String, ModuleBudget>,
+
+    // === Project layout ===
+    /// Crates declared in `[project]` for non-cargo projects
+    pub project_crates: Vec<ProjectCrateConfig>,
 
     // === Cache ===
     /// Cache of path -> volatility mappings
@@ -247,23 +993,123 @@ impl CompiledConfig {
                 .collect()
         };
 
+        // `[analysis] prelude_modules` is kept as a deprecated alias of the allowlist
+        if !config.analysis.prelude_modules.is_empty() {
+            log_warn!(
+                "Warning: [analysis] prelude_modules is deprecated; move its patterns to [allowlist] prelude_modules"
+            );
+        }
+        let mut prelude_modules = config.allowlist.prelude_modules.clone();
+        for pattern in &config.analysis.prelude_modules {
+            if !prelude_modules.contains(pattern) {
+                prelude_modules.push(pattern.clone());
+            }
+        }
+
+        let severity_overrides = config
+            .severity
+            .iter()
+            .map(|(name, level)| {
+                // Accept report names ("God Module") as well as rule ids ("GodModule")
+                let rule_id: String = name.chars().filter(char::is_ascii_alphanumeric).collect();
+                IssueType::deserialize(rule_id.as_str().into_deserializer())
+                    .map(|issue_type| (issue_type, level.severity()))
+                    .map_err(|_: serde::de::value::Error| {
+                        ConfigError::InvalidValue(format!(
+                            "severity: unknown issue type `{}` (use names like `GodModule`)",
+                            name
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let balance_bands = BalanceBands {
+            balanced: config.balance_bands.balanced,
+            acceptable: config.balance_bands.acceptable,
+            needs_review: config.balance_bands.needs_review,
+            needs_refactoring: config.balance_bands.needs_refactoring,
+        };
+        balance_bands
+            .validate()
+            .map_err(|err| ConfigError::InvalidValue(format!("balance_bands: {}", err)))?;
+
+        let balance_formula = BalanceFormula {
+            alignment_weight: config.balance.alignment_weight,
+            volatility_weight: config.balance.volatility_weight,
+            combiner: config.balance.combiner,
+            visibility_penalty: config.balance.visibility_penalty,
+            weight_by_occurrences: config.balance.weight_by_occurrences,
+        };
+        balance_formula
+            .validate()
+            .map_err(|err| ConfigError::InvalidValue(format!("balance: {}", err)))?;
+
+        let cognitive_load_weights = CognitiveLoadWeights {
+            public_api: config.cognitive_load.public_api,
+            external_crates: config.cognitive_load.external_crates,
+            average_params: config.cognitive_load.average_params,
+            item_dependencies: config.cognitive_load.item_dependencies,
+        };
+        cognitive_load_weights
+            .validate()
+            .map_err(|err| ConfigError::InvalidValue(format!("cognitive_load: {}", err)))?;
+
+        validate_thresholds(&config.thresholds)?;
+        validate_layers(&config.layers)?;
+
         Ok(Self {
             // Analysis settings
             exclude_tests: config.analysis.exclude_tests,
+            record_provenance: false,
+            merge_couplings: config.analysis.merge_couplings,
+            generated_markers: config.analysis.generated_markers,
             config_root: config_root.map(Path::to_path_buf),
-            prelude_patterns: compile_patterns(&config.analysis.prelude_modules)?,
+            prelude_patterns: compile_patterns(&prelude_modules)?,
+            duplicated_structure_allowlist: config.allowlist.duplicated_structures,
             exclude_patterns: compile_patterns(&config.analysis.exclude)?,
+            path_includes: None,
+            path_excludes: None,
+            path_filter_globs: (Vec::new(), Vec::new()),
+            exclude_crate_kinds: config.analysis.exclude_crate_kinds,
+            primitive_types: PrimitiveTypes::new(
+                &config.analysis.treat_as_primitive,
+                &config.analysis.count_types,
+                config.analysis.short_name_heuristic,
+            ),
+            dependency_path_filter: DependencyPathFilter::new(
+                &config.analysis.local_variable_names,
+                config.analysis.max_local_name_len,
+            ),
+            analysis_cache: None,
             // Volatility settings
             high_patterns: compile_patterns(&config.volatility.high)?,
             medium_patterns: compile_patterns(&config.volatility.medium)?,
             low_patterns: compile_patterns(&config.volatility.low)?,
             ignore_patterns: compile_patterns(&config.volatility.ignore)?,
+            volatility_exclude_patterns: compile_patterns(&config.volatility.exclude)?,
+            git_max_commits: None,
+            git_timeout: None,
+            git_scope: config.volatility.scope.clone(),
             // Subdomain settings
             core_patterns: compile_patterns(&config.subdomains.core)?,
             supporting_patterns: compile_patterns(&config.subdomains.supporting)?,
             generic_patterns: compile_patterns(&config.subdomains.generic)?,
             // Thresholds
             thresholds: config.thresholds,
+            balance_bands,
+            balance_formula,
+            cognitive_load_weights,
+            god_module: config.god_module,
+            grade_minimums: GradeMinimums {
+                min_couplings_for_a: config.grading.min_couplings_for_a,
+                cap_uncoupled_at_b: config.grading.cap_uncoupled_at_b,
+            },
+            policy: config.policy,
+            external: config.external,
+            layers: config.layers,
+            severity_overrides,
+            budgets: config.budgets,
+            project_crates: config.project.declared_crates(),
             cache: HashMap::new(),
         })
     }
@@ -271,18 +1117,44 @@ impl CompiledConfig {
     /// Create an empty config (no overrides)
     pub fn empty() -> Self {
         Self {
-            exclude_tests: false,
+            exclude_tests: true,
+            record_provenance: false,
+            merge_couplings: false,
+            generated_markers: default_generated_markers(),
             config_root: None,
             prelude_patterns: Vec::new(),
+            duplicated_structure_allowlist: Vec::new(),
             exclude_patterns: Vec::new(),
+            path_includes: None,
+            path_excludes: None,
+            path_filter_globs: (Vec::new(), Vec::new()),
+            exclude_crate_kinds: default_exclude_crate_kinds(),
+            primitive_types: PrimitiveTypes::default(),
+            dependency_path_filter: DependencyPathFilter::default(),
+            analysis_cache: None,
             high_patterns: Vec::new(),
             medium_patterns: Vec::new(),
             low_patterns: Vec::new(),
             ignore_patterns: Vec::new(),
+            volatility_exclude_patterns: Vec::new(),
+            git_max_commits: None,
+            git_timeout: None,
+            git_scope: None,
             core_patterns: Vec::new(),
             supporting_patterns: Vec::new(),
             generic_patterns: Vec::new(),
             thresholds: ThresholdsConfig::default(),
+            balance_bands: BalanceBands::default(),
+            balance_formula: BalanceFormula::default(),
+            cognitive_load_weights: CognitiveLoadWeights::default(),
+            god_module: GodModuleConfig::default(),
+            grade_minimums: GradeMinimums::default(),
+            policy: PolicyConfig::default(),
+            external: ExternalConfig::default(),
+            layers: Vec::new(),
+            severity_overrides: BTreeMap::new(),
+            budgets: BTreeMap::new(),
+            project_crates: Vec::new(),
             cache: HashMap::new(),
         }
     }
@@ -292,6 +1164,40 @@ impl CompiledConfig {
         self.exclude_tests = exclude;
     }
 
+    /// Restrict analysis to files matching `include` and not matching `exclude`
+    /// (CLI `--include` / `--exclude`), matched against analysis-root-relative paths
+    pub fn set_path_filters(
+        &mut self,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<(), ConfigError> {
+        self.path_includes = compile_glob_set(include)?;
+        self.path_excludes = compile_glob_set(exclude)?;
+        self.path_filter_globs = (include.to_vec(), exclude.to_vec());
+        Ok(())
+    }
+
+    /// Whether a root-relative, `/`-separated path passes the CLI path filters
+    pub fn passes_path_filters(&self, path: &str) -> bool {
+        self.path_includes
+            .as_ref()
+            .is_none_or(|includes| includes.is_match(path))
+            && !self
+                .path_excludes
+                .as_ref()
+                .is_some_and(|excludes| excludes.is_match(path))
+    }
+
+    /// Set merge_couplings flag (used by CLI --merge-couplings option)
+    pub fn set_merge_couplings(&mut self, merge: bool) {
+        self.merge_couplings = merge;
+    }
+
+    /// Set record_provenance flag (used by CLI --verbose option)
+    pub fn set_record_provenance(&mut self, record: bool) {
+        self.record_provenance = record;
+    }
+
     /// Get the directory the config was loaded from, if known.
     pub fn config_root(&self) -> Option<&Path> {
         self.config_root.as_deref()
@@ -302,11 +1208,29 @@ impl CompiledConfig {
         self.config_root = config_root;
     }
 
-    /// Check if a module is marked as "prelude-like" (exempt from afferent coupling warnings)
+    /// Check if a file path is marked as "prelude-like" (exempt from afferent coupling warnings)
     pub fn is_prelude_module(&self, path: &str) -> bool {
         self.prelude_patterns.iter().any(|p| p.matches(path))
     }
 
+    /// Check if a module path is allowlisted as prelude-like (`[allowlist] prelude_modules`)
+    ///
+    /// Patterns are matched against the bare module path, the module path under
+    /// `crate::`, and, when known, the module path under its crate name.
+    pub fn is_allowlisted_module(&self, crate_name: Option<&str>, module_path: &str) -> bool {
+        if self.prelude_patterns.is_empty() {
+            return false;
+        }
+        let relative = format!("crate::{}", module_path);
+        let qualified =
+            crate_name.map(|name| format!("{}::{}", name.replace('-', "_"), module_path));
+        self.prelude_patterns.iter().any(|p| {
+            p.matches(module_path)
+                || p.matches(&relative)
+                || qualified.as_deref().is_some_and(|q| p.matches(q))
+        })
+    }
+
     /// Check if a path should be completely excluded from analysis
     pub fn should_exclude(&self, path: &str) -> bool {
         self.exclude_patterns.iter().any(|p| p.matches(path))
@@ -318,6 +1242,11 @@ impl CompiledConfig {
             || self.exclude_patterns.iter().any(|p| p.matches(path))
     }
 
+    /// Git paths to leave out of volatility change counts (`[volatility] exclude`)
+    pub fn volatility_exclude_patterns(&self) -> &[Pattern] {
+        &self.volatility_exclude_patterns
+    }
+
     /// Scoring-affecting pattern strings (subdomains, volatility overrides) that
     /// matched none of the given analyzed paths.
     ///
@@ -444,6 +1373,93 @@ impl CompiledConfig {
         self.get_volatility_override(path).unwrap_or(git_volatility)
     }
 
+    /// Issue thresholds from the config file alone, before CLI overrides.
+    pub fn issue_thresholds(&self) -> IssueThresholds {
+        IssueThresholds {
+            max_dependencies: self.thresholds.max_dependencies,
+            max_dependents: self.thresholds.max_dependents,
+            max_type_generics: self.thresholds.max_type_generics,
+            min_spread_modules: self.thresholds.min_spread_modules,
+            min_mixed_async_functions: self.thresholds.min_mixed_async_functions,
+            min_unsafe_surface: self.thresholds.min_unsafe_surface,
+            min_duplicated_fields: self.thresholds.min_duplicated_fields,
+            duplicated_structure_allowlist: self.duplicated_structure_allowlist.clone(),
+            min_cohesion: self.thresholds.min_cohesion,
+            max_interface_ratio: self.thresholds.max_interface_ratio,
+            min_shallow_interface: self.thresholds.min_shallow_interface,
+            max_cognitive_load: self.thresholds.max_cognitive_load,
+            cognitive_load_weights: self.cognitive_load_weights,
+            max_functions: self.god_module.max_functions,
+            max_types: self.god_module.max_types,
+            max_impls: self.god_module.max_impls,
+            god_module_overrides: self.god_module.overrides.clone(),
+            exclude_tests: self.exclude_tests,
+            prelude_module_count: self.prelude_module_count(),
+            balance_bands: self.balance_bands,
+            balance_formula: self.balance_formula,
+            grade_minimums: self.grade_minimums,
+            deprecated_internal: self.policy.deprecated_internal.clone(),
+            deprecated_internal_severity: self.policy.deprecated_internal_severity,
+            stable_crates: self.external.stable.clone(),
+            infrastructure_crates: self.external.infrastructure.clone(),
+            layers: self.layers.clone(),
+            severity_overrides: self.severity_overrides.clone(),
+            ..IssueThresholds::default()
+        }
+    }
+
+    /// Stable hash of the effective configuration, written as `config_hash` in output headers.
+    ///
+    /// Covers every setting that changes results: path patterns, crate-kind
+    /// exclusions, primitive type names, the project layout, and `thresholds`
+    /// after CLI overrides.
+    /// Output language and provenance recording are left out since they do not
+    /// change what is measured.
+    pub fn config_hash(&self, thresholds: &IssueThresholds) -> String {
+        let patterns = |patterns: &[Pattern]| {
+            patterns
+                .iter()
+                .map(Pattern::as_str)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let scoring = IssueThresholds {
+            japanese: false,
+            ..thresholds.clone()
+        };
+        let canonical = [
+            format!("exclude_tests={}", self.exclude_tests),
+            format!("merge_couplings={}", self.merge_couplings),
+            format!("generated_markers={:?}", self.generated_markers),
+            format!("prelude={}", patterns(&self.prelude_patterns)),
+            format!("exclude={}", patterns(&self.exclude_patterns)),
+            format!("path_filters={:?}", self.path_filter_globs),
+            format!("exclude_crate_kinds={:?}", self.exclude_crate_kinds),
+            format!("primitive_types={:?}", self.primitive_types),
+            format!("dependency_path_filter={:?}", self.dependency_path_filter),
+            format!("volatility.high={}", patterns(&self.high_patterns)),
+            format!("volatility.medium={}", patterns(&self.medium_patterns)),
+            format!("volatility.low={}", patterns(&self.low_patterns)),
+            format!("volatility.ignore={}", patterns(&self.ignore_patterns)),
+            format!(
+                "volatility.exclude={}",
+                patterns(&self.volatility_exclude_patterns)
+            ),
+            format!("volatility.scope={:?}", self.git_scope),
+            format!("subdomains.core={}", patterns(&self.core_patterns)),
+            format!(
+                "subdomains.supporting={}",
+                patterns(&self.supporting_patterns)
+            ),
+            format!("subdomains.generic={}", patterns(&self.generic_patterns)),
+            format!("thresholds={:?}", self.thresholds),
+            format!("project={:?}", self.project_crates),
+            format!("scoring={:?}", scoring),
+        ]
+        .join("\n");
+        stable_hash(&canonical)
+    }
+
     /// Check if config has any volatility overrides
     pub fn has_volatility_overrides(&self) -> bool {
         !self.high_patterns.is_empty()
@@ -474,65 +1490,115 @@ impl MetricsConfig for CompiledConfig {
     }
 }
 
+/// Config file names, in order of preference.
+const CONFIG_FILE_NAMES: [&str; 2] = [".coupling.toml", "coupling.toml"];
+
 /// Load configuration from the project directory
 ///
 /// Searches for `.coupling.toml` in the given directory and parent directories.
 pub fn load_config(project_path: &Path) -> Result<CouplingConfig, ConfigError> {
-    // Search for config file
-    let config_path = find_config_file(project_path);
-
-    match config_path {
-        Some(path) => {
-            let content = fs::read_to_string(&path)?;
-            let config: CouplingConfig = toml::from_str(&content)?;
-            Ok(config)
-        }
+    match find_config_file(project_path)? {
+        Some(path) => read_config(&path),
         None => Ok(CouplingConfig::default()),
     }
 }
 
-/// Find the config file by searching up the directory tree
-fn find_config_file(start_path: &Path) -> Option<std::path::PathBuf> {
-    let config_names = [".coupling.toml", "coupling.toml"];
-
-    let mut current = if start_path.is_file() {
-        start_path.parent()?.to_path_buf()
+/// Find the config file by searching up the directory tree, like cargo does.
+///
+/// The search starts at `start_path` (made absolute, so a relative `./src`
+/// still reaches the workspace root) and stops at the first directory holding
+/// a config file, or after the cargo workspace root, whichever comes first.
+fn find_config_file(start_path: &Path) -> Result<Option<PathBuf>, ConfigError> {
+    let start = absolute_normalized_path(start_path)?;
+    let mut current = if start.is_file() {
+        start.parent().map(Path::to_path_buf)
     } else {
-        start_path.to_path_buf()
+        Some(start)
     };
 
-    loop {
-        for name in &config_names {
-            let config_path = current.join(name);
-            if config_path.exists() {
-                return Some(config_path);
-            }
+    while let Some(dir) = current {
+        if let Some(path) = config_file_in(&dir) {
+            return Ok(Some(path));
         }
-
-        // Move to parent directory
-        if let Some(parent) = current.parent() {
-            current = parent.to_path_buf();
-        } else {
+        if is_workspace_root(&dir) {
             break;
         }
+        current = dir.parent().map(Path::to_path_buf);
     }
+    Ok(None)
+}
+
+/// The config file directly inside `dir`, if any.
+fn config_file_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Whether `dir` holds a `Cargo.toml` with a `[workspace]` table.
+fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
 
-    None
+fn read_config(path: &Path) -> Result<CouplingConfig, ConfigError> {
+    let content = fs::read_to_string(path)?;
+    Ok(toml::from_str(&content)?)
 }
 
-/// Load and compile configuration
+/// Load and compile configuration found from `project_path`
 pub fn load_compiled_config(project_path: &Path) -> Result<CompiledConfig, ConfigError> {
-    match find_config_file(project_path) {
-        Some(path) => {
-            let content = fs::read_to_string(&path)?;
-            let config: CouplingConfig = toml::from_str(&content)?;
-            let absolute_path = absolute_normalized_path(&path)?;
-            CompiledConfig::from_config_with_root(config, absolute_path.parent())
+    match find_config_file(project_path)? {
+        Some(path) => compile_config_file(&path),
+        None => {
+            log_debug!(
+                "No .coupling.toml found from {}; using defaults",
+                project_path.display()
+            );
+            Ok(CompiledConfig::empty())
         }
-        None => Ok(CompiledConfig::empty()),
     }
 }
 
+/// Load and compile the config named by `--config`, else the one found from `project_path`.
+///
+/// `config` may be a config file or a directory containing one; it is an error
+/// if it is neither, rather than silently analyzing with defaults.
+pub fn load_compiled_config_for(
+    config: Option<&Path>,
+    project_path: &Path,
+) -> Result<CompiledConfig, ConfigError> {
+    let Some(config) = config else {
+        return load_compiled_config(project_path);
+    };
+    let path = if config.is_dir() {
+        config_file_in(config).ok_or_else(|| {
+            ConfigError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no .coupling.toml or coupling.toml in {}", config.display()),
+            ))
+        })?
+    } else if config.is_file() {
+        config.to_path_buf()
+    } else {
+        return Err(ConfigError::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} not found", config.display()),
+        )));
+    };
+    compile_config_file(&path)
+}
+
+fn compile_config_file(path: &Path) -> Result<CompiledConfig, ConfigError> {
+    let config = read_config(path)?;
+    log_debug!("Using config file {}", path.display());
+    let absolute_path = absolute_normalized_path(path)?;
+    CompiledConfig::from_config_with_root(config, absolute_path.parent())
+}
+
 fn absolute_normalized_path(path: &Path) -> Result<PathBuf, std::io::Error> {
     let absolute = if path.is_absolute() {
         path.to_path_buf()
@@ -588,6 +1654,506 @@ mod tests {
         assert_eq!(config.thresholds.max_dependents, 30);
     }
 
+    #[test]
+    fn test_balance_bands_reclassify_scores_and_must_be_monotonic() {
+        use crate::balance::score::{BalanceInterpretation, BalanceScore};
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::{Distance, IntegrationStrength};
+
+        let toml = r#"
+            [balance_bands]
+            balanced = 0.9
+            acceptable = 0.8
+        "#;
+        let config: CouplingConfig = toml::from_str(toml).unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(compiled.balance_bands.needs_review, 0.4);
+
+        // Intrusive + SameModule + Low volatility scores 0.75.
+        let coupling = CouplingMetrics::new(
+            "a".to_string(),
+            "b".to_string(),
+            IntegrationStrength::Intrusive,
+            Distance::SameModule,
+            Volatility::Low,
+        );
+        let default_score = BalanceScore::calculate(&coupling);
+        let strict_score = BalanceScore::calculate_with(&coupling, &compiled.balance_bands);
+        assert_eq!(default_score.score, strict_score.score);
+        assert_eq!(
+            default_score.interpretation,
+            BalanceInterpretation::Acceptable
+        );
+        assert_eq!(
+            strict_score.interpretation,
+            BalanceInterpretation::NeedsReview
+        );
+
+        let inverted: CouplingConfig = toml::from_str(
+            r#"
+            [balance_bands]
+            acceptable = 0.3
+            needs_review = 0.5
+        "#,
+        )
+        .unwrap();
+        let err = CompiledConfig::from_config(inverted).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue(_)));
+        assert!(err.to_string().contains("acceptable"));
+    }
+
+    #[test]
+    fn test_god_module_section_sets_global_and_per_module_limits() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [god_module]
+            max_types = 25
+
+            [god_module.overrides]
+            "crate::generated" = { max_functions = 500 }
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(compiled.god_module.max_types, 25);
+        assert_eq!(compiled.god_module.max_functions, 30);
+        assert_eq!(
+            compiled.god_module.overrides["crate::generated"].max_functions,
+            Some(500)
+        );
+
+        let typo = toml::from_str::<CouplingConfig>(
+            r#"
+            [god_module.overrides]
+            "generated" = { max_function = 500 }
+        "#,
+        );
+        assert!(typo.is_err());
+    }
+
+    #[test]
+    fn test_balance_section_reweights_the_score_formula() {
+        use crate::balance::score::BalanceScore;
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::{Distance, IntegrationStrength};
+
+        // Functional coupling to a volatile neighbour: both factors below 1.0
+        let coupling = CouplingMetrics::new(
+            "a".to_string(),
+            "b".to_string(),
+            IntegrationStrength::Functional,
+            Distance::DifferentModule,
+            Volatility::High,
+        );
+        let default_score = BalanceScore::calculate(&coupling).score;
+
+        let compiled = CompiledConfig::from_config(CouplingConfig::default()).unwrap();
+        assert_eq!(compiled.balance_formula, BalanceFormula::default());
+        assert_eq!(
+            BalanceScore::calculate_with_formula(
+                &coupling,
+                &compiled.balance_bands,
+                &compiled.balance_formula
+            )
+            .score,
+            default_score
+        );
+
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [balance]
+            combiner = "weighted_sum"
+            alignment_weight = 3.0
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        let weighted = BalanceScore::calculate_with_formula(
+            &coupling,
+            &compiled.balance_bands,
+            &compiled.balance_formula,
+        )
+        .score;
+        assert!(
+            weighted > default_score,
+            "{} vs {}",
+            weighted,
+            default_score
+        );
+        assert!(compiled.balance_formula.visibility_penalty);
+        let config: CouplingConfig =
+            toml::from_str("[balance]\nvisibility_penalty = false").unwrap();
+        assert!(
+            !CompiledConfig::from_config(config)
+                .unwrap()
+                .balance_formula
+                .visibility_penalty
+        );
+
+        for invalid in [
+            "volatility_weight = -1.0",
+            "combiner = \"weighted_sum\"\nalignment_weight = 0.0\nvolatility_weight = 0.0",
+            "combiner = \"average\"",
+        ] {
+            let parsed: Result<CouplingConfig, _> =
+                toml::from_str(&format!("[balance]\n{}", invalid));
+            let compiled = parsed
+                .map_err(|_| ())
+                .and_then(|config| CompiledConfig::from_config(config).map_err(|_| ()));
+            assert!(compiled.is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_cognitive_load_section_sets_weights_and_rejects_negative_ones() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [thresholds]
+            max_cognitive_load = 40.0
+
+            [cognitive_load]
+            external_crates = 5.0
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(compiled.thresholds.max_cognitive_load, 40.0);
+        assert_eq!(compiled.cognitive_load_weights.external_crates, 5.0);
+        assert_eq!(
+            compiled.cognitive_load_weights.public_api,
+            CognitiveLoadWeights::default().public_api
+        );
+
+        let negative: CouplingConfig = toml::from_str(
+            r#"
+            [cognitive_load]
+            average_params = -1.0
+        "#,
+        )
+        .unwrap();
+        let err = CompiledConfig::from_config(negative).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue(_)));
+        assert!(err.to_string().contains("average_params"));
+
+        let negative_budget: CouplingConfig = toml::from_str(
+            r#"
+            [thresholds]
+            max_cognitive_load = -1.0
+        "#,
+        )
+        .unwrap();
+        let err = CompiledConfig::from_config(negative_budget).unwrap_err();
+        assert!(err.to_string().contains("max_cognitive_load"));
+
+        let cohesion_above_one: CouplingConfig = toml::from_str(
+            r#"
+            [thresholds]
+            min_cohesion = 1.5
+        "#,
+        )
+        .unwrap();
+        let err = CompiledConfig::from_config(cohesion_above_one).unwrap_err();
+        assert!(err.to_string().contains("min_cohesion"));
+    }
+
+    #[test]
+    fn test_grading_section_overrides_grade_minimums() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [grading]
+            min_couplings_for_a = 5
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(compiled.grade_minimums.min_couplings_for_a, 5);
+        assert!(compiled.grade_minimums.cap_uncoupled_at_b);
+
+        let defaults = CompiledConfig::empty();
+        assert_eq!(defaults.grade_minimums, GradeMinimums::default());
+    }
+
+    #[test]
+    fn test_analysis_section_adjusts_local_variable_heuristics() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [analysis]
+            local_variable_names = ["ctx"]
+            max_local_name_len = 0
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        let filter = &compiled.dependency_path_filter;
+        assert!(filter.is_valid_path("state"));
+        assert!(filter.is_valid_path("self_state::config"));
+        assert!(!filter.is_valid_path("self_state::ctx"));
+        assert!(!filter.is_valid_path("Self::new"));
+
+        let defaults = CompiledConfig::empty();
+        assert!(!defaults.dependency_path_filter.is_valid_path("state"));
+        assert!(!defaults.dependency_path_filter.is_valid_path("app::config"));
+        // Qualified paths into a known module survive whatever their name
+        assert!(
+            defaults
+                .dependency_path_filter
+                .keeps("crate::config", "config", true)
+        );
+        assert!(
+            !defaults
+                .dependency_path_filter
+                .keeps("config", "config", true)
+        );
+        assert_ne!(
+            compiled.config_hash(&IssueThresholds::default()),
+            defaults.config_hash(&IssueThresholds::default())
+        );
+    }
+
+    #[test]
+    fn test_analysis_section_adjusts_primitive_types() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [analysis]
+            treat_as_primitive = ["Uuid"]
+            count_types = ["Arc", "id"]
+            short_name_heuristic = false
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert!(compiled.primitive_types.is_primitive("Uuid"));
+        assert!(compiled.primitive_types.is_primitive("uuid::Uuid"));
+        assert!(!compiled.primitive_types.is_primitive("Arc"));
+        assert!(!compiled.primitive_types.is_primitive("id"));
+        assert!(!compiled.primitive_types.is_primitive("key"));
+        assert!(compiled.primitive_types.is_primitive("String"));
+
+        let defaults = CompiledConfig::empty();
+        assert!(defaults.primitive_types.is_primitive("key"));
+        assert!(defaults.primitive_types.is_primitive("Arc"));
+        assert!(!defaults.primitive_types.is_primitive("Uuid"));
+        assert_ne!(
+            compiled.config_hash(&IssueThresholds::default()),
+            defaults.config_hash(&IssueThresholds::default())
+        );
+    }
+
+    #[test]
+    fn test_project_section_declares_named_and_derived_crates() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [project]
+            source_roots = ["firmware/src", "kernel"]
+
+            [[project.crates]]
+            name = "kmod"
+            path = "drivers/module"
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        let crates: Vec<(&str, &Path)> = compiled
+            .project_crates
+            .iter()
+            .map(|declared| (declared.name.as_str(), declared.path.as_path()))
+            .collect();
+        assert_eq!(
+            crates,
+            vec![
+                ("firmware", Path::new("firmware/src")),
+                ("kernel", Path::new("kernel")),
+                ("kmod", Path::new("drivers/module")),
+            ]
+        );
+        assert!(CompiledConfig::empty().project_crates.is_empty());
+    }
+
+    #[test]
+    fn test_config_hash_is_stable_for_identical_config_and_changes_with_settings() {
+        let compile = |toml: &str| {
+            CompiledConfig::from_config(toml::from_str::<CouplingConfig>(toml).unwrap()).unwrap()
+        };
+        let base =
+            "[analysis]\nexclude = [\"src/generated/*\"]\n[thresholds]\nmax_dependencies = 15\n";
+        let thresholds = IssueThresholds::default();
+
+        let first = compile(base).config_hash(&thresholds);
+        assert_eq!(first, compile(base).config_hash(&thresholds));
+        assert_eq!(first.len(), 16);
+
+        let stricter = base.replace("max_dependencies = 15", "max_dependencies = 10");
+        assert_ne!(first, compile(&stricter).config_hash(&thresholds));
+        let cli_override = IssueThresholds {
+            max_dependencies: 5,
+            ..IssueThresholds::default()
+        };
+        assert_ne!(first, compile(base).config_hash(&cli_override));
+        // Output language does not change what is measured.
+        let japanese = IssueThresholds {
+            japanese: true,
+            ..IssueThresholds::default()
+        };
+        assert_eq!(first, compile(base).config_hash(&japanese));
+        // CLI --include/--exclude change which files are measured
+        let mut filtered = compile(base);
+        filtered
+            .set_path_filters(&[], &["src/legacy/**".to_string()])
+            .unwrap();
+        assert_ne!(first, filtered.config_hash(&thresholds));
+    }
+
+    #[test]
+    fn test_policy_section_lists_deprecated_modules_with_severity() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [policy]
+            deprecated_internal = ["legacy_auth", "old_db"]
+            deprecated_internal_severity = "high"
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(
+            compiled.policy.deprecated_internal,
+            vec!["legacy_auth".to_string(), "old_db".to_string()]
+        );
+        assert_eq!(compiled.policy.deprecated_internal_severity, Severity::High);
+        assert_eq!(
+            CompiledConfig::empty().policy.deprecated_internal_severity,
+            Severity::Medium
+        );
+        assert!(
+            toml::from_str::<CouplingConfig>("[policy]\ndeprecated_internal_severity = \"urgent\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_analysis_prelude_modules_is_an_alias_of_the_allowlist() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [analysis]
+            prelude_modules = ["src/lib.rs", "crate::utils::*"]
+
+            [allowlist]
+            prelude_modules = ["crate::prelude", "crate::utils::*"]
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+
+        // One combined list, matched by module path or by file path
+        assert_eq!(compiled.prelude_module_count(), 3);
+        assert!(compiled.is_allowlisted_module(Some("shop"), "prelude"));
+        assert!(compiled.is_allowlisted_module(None, "utils::fmt"));
+        assert!(compiled.is_prelude_module("src/lib.rs"));
+        assert!(!compiled.is_allowlisted_module(None, "orders"));
+    }
+
+    #[test]
+    fn test_duplicated_structure_settings_reach_the_thresholds() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [thresholds]
+            min_duplicated_fields = 5
+
+            [allowlist]
+            duplicated_structures = ["api::dto", "wire::Header"]
+        "#,
+        )
+        .unwrap();
+        let thresholds = CompiledConfig::from_config(config)
+            .unwrap()
+            .issue_thresholds();
+        assert_eq!(thresholds.min_duplicated_fields, 5);
+        assert_eq!(
+            thresholds.duplicated_structure_allowlist,
+            vec!["api::dto".to_string(), "wire::Header".to_string()]
+        );
+        assert_eq!(
+            CompiledConfig::empty()
+                .issue_thresholds()
+                .min_duplicated_fields,
+            3
+        );
+    }
+
+    #[test]
+    fn test_layers_are_read_in_order_and_validated() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [[layers]]
+            name = "domain"
+            modules = ["domain"]
+
+            [[layers]]
+            name = "infrastructure"
+            modules = ["infrastructure", "db"]
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        let names: Vec<&str> = compiled.layers.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["domain", "infrastructure"]);
+        assert_eq!(compiled.layers[1].modules, ["infrastructure", "db"]);
+        assert!(CompiledConfig::empty().layers.is_empty());
+
+        let invalid = |toml: &str| {
+            let config: CouplingConfig = toml::from_str(toml).unwrap();
+            CompiledConfig::from_config(config).unwrap_err().to_string()
+        };
+        assert!(
+            invalid("[[layers]]\nname = \"domain\"\nmodules = []").contains("lists no modules")
+        );
+        assert!(
+            invalid(
+                "[[layers]]\nname = \"domain\"\nmodules = [\"a\"]\n\
+                 [[layers]]\nname = \"domain\"\nmodules = [\"b\"]"
+            )
+            .contains("declared twice")
+        );
+        assert!(
+            toml::from_str::<CouplingConfig>("[[layers]]\nname = \"domain\"\npaths = [\"a\"]")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_severity_section_remaps_or_disables_issue_types() {
+        let config: CouplingConfig = toml::from_str(
+            r#"
+            [severity]
+            PrimitiveObsession = "off"
+            GodModule = "high"
+        "#,
+        )
+        .unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(
+            compiled.severity_overrides,
+            BTreeMap::from([
+                (IssueType::GodModule, Some(Severity::High)),
+                (IssueType::PrimitiveObsession, None),
+            ])
+        );
+        assert!(CompiledConfig::empty().severity_overrides.is_empty());
+
+        let report_name: CouplingConfig =
+            toml::from_str("[severity]\n\"Pass-Through Method\" = \"low\"").unwrap();
+        assert_eq!(
+            CompiledConfig::from_config(report_name)
+                .unwrap()
+                .severity_overrides,
+            BTreeMap::from([(IssueType::PassThroughMethod, Some(Severity::Low))])
+        );
+
+        let unknown: CouplingConfig = toml::from_str("[severity]\nGodObject = \"low\"").unwrap();
+        let err = CompiledConfig::from_config(unknown).unwrap_err();
+        assert!(err.to_string().contains("GodObject"), "{}", err);
+        assert!(toml::from_str::<CouplingConfig>("[severity]\nGodModule = \"urgent\"").is_err());
+    }
+
     #[test]
     fn test_compiled_config() {
         let toml = r#"
@@ -743,6 +2309,53 @@ mod tests {
         assert!(compiled.has_subdomain_config());
     }
 
+    #[test]
+    fn test_budgets_parse_per_module_limits() {
+        let toml = r#"
+            [budgets]
+            "crate::api" = { max_out = 10, max_in = 50 }
+            "storage" = { max_in = 5 }
+        "#;
+        let config: CouplingConfig = toml::from_str(toml).unwrap();
+        let compiled = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(
+            compiled.budgets["crate::api"],
+            ModuleBudget {
+                max_out: Some(10),
+                max_in: Some(50)
+            }
+        );
+        assert_eq!(compiled.budgets["storage"].max_out, None);
+        assert!(CompiledConfig::empty().budgets.is_empty());
+
+        let typo = r#"
+            [budgets]
+            "crate::api" = { max_outgoing = 10 }
+        "#;
+        assert!(toml::from_str::<CouplingConfig>(typo).is_err());
+    }
+
+    #[test]
+    fn test_external_crates_reach_issue_thresholds() {
+        let toml = r#"
+            [external]
+            stable = ["acme_core"]
+            infrastructure = ["acme-runtime"]
+        "#;
+        let config: CouplingConfig = toml::from_str(toml).unwrap();
+        let thresholds = CompiledConfig::from_config(config)
+            .unwrap()
+            .issue_thresholds();
+        assert_eq!(thresholds.stable_crates, ["acme_core"]);
+        assert_eq!(thresholds.infrastructure_crates, ["acme-runtime"]);
+        assert!(
+            CompiledConfig::empty()
+                .issue_thresholds()
+                .stable_crates
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_has_volatility_overrides() {
         // Empty config → no overrides
@@ -875,4 +2488,64 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[test]
+    fn test_config_discovery_walks_up_to_the_workspace_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = tmp.path().join("ws");
+        let member_src = workspace.join("crates").join("foo").join("src");
+        fs::create_dir_all(&member_src).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/foo\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("crates").join("foo").join("Cargo.toml"),
+            "[package]\nname = \"foo\"\n",
+        )
+        .unwrap();
+        // Above the workspace root: never reached
+        fs::write(
+            tmp.path().join(".coupling.toml"),
+            "[thresholds]\nmax_dependents = 1\n",
+        )
+        .unwrap();
+        assert!(find_config_file(&member_src).unwrap().is_none());
+
+        fs::write(
+            workspace.join("coupling.toml"),
+            "[thresholds]\nmax_dependents = 7\n",
+        )
+        .unwrap();
+        let found = find_config_file(&member_src).unwrap().unwrap();
+        assert_eq!(found, workspace.join("coupling.toml"));
+        let config = load_compiled_config(&member_src).unwrap();
+        assert_eq!(config.issue_thresholds().max_dependents, 7);
+        assert_eq!(config.config_root(), Some(workspace.as_path()));
+    }
+
+    #[test]
+    fn test_explicit_config_may_name_a_file_or_a_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(dir.join("ci.toml"), "[thresholds]\nmax_dependents = 3\n").unwrap();
+        fs::write(
+            dir.join(".coupling.toml"),
+            "[thresholds]\nmax_dependents = 4\n",
+        )
+        .unwrap();
+
+        let from_file = load_compiled_config_for(Some(&dir.join("ci.toml")), dir).unwrap();
+        assert_eq!(from_file.issue_thresholds().max_dependents, 3);
+        let from_dir = load_compiled_config_for(Some(dir), Path::new("elsewhere")).unwrap();
+        assert_eq!(from_dir.issue_thresholds().max_dependents, 4);
+
+        let empty = dir.join("empty");
+        fs::create_dir(&empty).unwrap();
+        for missing in [empty.clone(), dir.join("missing.toml")] {
+            let err = load_compiled_config_for(Some(&missing), dir).unwrap_err();
+            assert!(matches!(err, ConfigError::IoError(_)), "{err}");
+        }
+    }
 }
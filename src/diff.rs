@@ -3,13 +3,26 @@
 //! A diff compares issue identity across two snapshots using the stable key
 //! `(issue_type, source, target)`, so ratchet checks can focus on regressions
 //! introduced by the current change rather than the codebase's absolute state.
+//!
+//! For git-ref baselines the diff also carries the per-bucket shift of the
+//! strength/distance/volatility distributions (`DimensionStatsDelta`), a
+//! higher-level trend than individual issues, and the change of each module's
+//! average balance score (`ModuleScoreDelta`).
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
 
-use std::collections::HashSet;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 // Consume the crate's published facade rather than deep `balance::*` paths: the
 // re-exported surface stays stable when the balance package reorganizes internally.
 use crate::history::RefAnalysis;
-use crate::{CouplingIssue, HealthGrade, IssueKey, ProjectBalanceReport, Severity};
+use crate::{
+    ANALYZER_VERSION, BalanceFormula, CouplingIssue, DimensionStats, HealthGrade, IssueKey,
+    ProjectBalanceReport, ProjectMetrics, Severity, calculate_module_scores_with,
+    comparability_warnings,
+};
 
 /// Difference between a baseline report and the current report.
 #[derive(Debug, Clone)]
@@ -26,6 +39,12 @@ pub struct BaselineDiff {
     pub baseline_grade: HealthGrade,
     /// Current health grade.
     pub current_grade: HealthGrade,
+    /// Shift of the dimension distributions, when both snapshots' metrics are known.
+    pub dimension_delta: Option<DimensionStatsDelta>,
+    /// Modules whose average balance score changed, worst regression first.
+    pub module_deltas: Vec<ModuleScoreDelta>,
+    /// Why the baseline may not be comparable (analyzer version or config hash differs).
+    pub warnings: Vec<String>,
 }
 
 impl BaselineDiff {
@@ -36,6 +55,52 @@ impl BaselineDiff {
             .filter(|issue| issue.meets(severity))
             .collect()
     }
+
+    /// Whether the average score dropped by more than `max_regression`.
+    pub fn regressed_beyond(&self, max_regression: f64) -> bool {
+        -self.score_delta > max_regression
+    }
+
+    /// One-line verdict, e.g. `+3 new Critical issues, grade dropped B→C`.
+    pub fn headline(&self) -> String {
+        let mut parts: Vec<String> = [
+            Severity::Critical,
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+        ]
+        .into_iter()
+        .filter_map(|severity| {
+            let count = self
+                .new_issues
+                .iter()
+                .filter(|issue| issue.severity == severity)
+                .count();
+            (count > 0).then(|| {
+                let noun = if count == 1 { "issue" } else { "issues" };
+                format!("+{} new {} {}", count, severity, noun)
+            })
+        })
+        .collect();
+        if parts.is_empty() {
+            parts.push("no new issues".to_string());
+        }
+
+        let (before, after) = (self.baseline_grade.letter(), self.current_grade.letter());
+        parts.push(
+            match grade_rank(self.current_grade).cmp(&grade_rank(self.baseline_grade)) {
+                std::cmp::Ordering::Greater => format!("grade dropped {}→{}", before, after),
+                std::cmp::Ordering::Less => format!("grade improved {}→{}", before, after),
+                std::cmp::Ordering::Equal => format!("grade unchanged ({})", after),
+            },
+        );
+        parts.join(", ")
+    }
+}
+
+/// Position of a grade from best (S) to worst (F).
+fn grade_rank(grade: HealthGrade) -> usize {
+    "SABCDF".find(grade.letter()).unwrap_or(0)
 }
 
 /// Compute a stable-key issue diff from baseline to current.
@@ -73,12 +138,187 @@ pub fn diff_reports(
         score_delta: current.average_score - baseline.average_score,
         baseline_grade: baseline.health_grade,
         current_grade: current.health_grade,
+        dimension_delta: None,
+        module_deltas: Vec::new(),
+        warnings: Vec::new(),
     }
 }
 
-/// Diff a baseline git-ref analysis against the current report.
-pub fn diff_ref_analysis(baseline: &RefAnalysis, current: &ProjectBalanceReport) -> BaselineDiff {
-    diff_reports(&baseline.report, current)
+/// Diff a baseline git-ref analysis against the current metrics and report.
+///
+/// Module scores on both sides use `formula`, the one the reports were scored with.
+pub fn diff_ref_analysis(
+    baseline: &RefAnalysis,
+    current_metrics: &ProjectMetrics,
+    current: &ProjectBalanceReport,
+    formula: &BalanceFormula,
+) -> BaselineDiff {
+    let mut diff = diff_reports(&baseline.report, current);
+    diff.dimension_delta = Some(diff_dimension_stats(
+        &baseline.metrics.calculate_dimension_stats(),
+        &current_metrics.calculate_dimension_stats(),
+    ));
+    diff.module_deltas = diff_module_scores(&baseline.metrics, current_metrics, formula);
+    // Both sides run on this build; the config hash differs if the ref was
+    // analyzed under other settings.
+    diff.warnings = comparability_warnings(
+        &format!("baseline {}", baseline.git_ref),
+        (
+            Some(ANALYZER_VERSION),
+            baseline.metrics.config_hash.as_deref(),
+        ),
+        (
+            Some(ANALYZER_VERSION),
+            current_metrics.config_hash.as_deref(),
+        ),
+    );
+    diff
+}
+
+/// Change of one module's average balance score between two analyses.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ModuleScoreDelta {
+    pub module: String,
+    /// Score at the baseline; `None` when the module had no internal couplings.
+    pub baseline: Option<f64>,
+    /// Score now; `None` when the module has no internal couplings any more.
+    pub current: Option<f64>,
+    /// `current - baseline`, counting a missing side as 1.0 (no couplings).
+    pub delta: f64,
+}
+
+/// Compare the per-module balance scores of two analyses.
+///
+/// Only modules whose score changed are returned, worst regression first.
+pub fn diff_module_scores(
+    baseline: &ProjectMetrics,
+    current: &ProjectMetrics,
+    formula: &BalanceFormula,
+) -> Vec<ModuleScoreDelta> {
+    let before = calculate_module_scores_with(baseline, formula);
+    let after = calculate_module_scores_with(current, formula);
+    let modules: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+    let mut deltas: Vec<ModuleScoreDelta> = modules
+        .into_iter()
+        .filter_map(|module| {
+            let baseline = before.get(module).copied();
+            let current = after.get(module).copied();
+            let delta = current.unwrap_or(1.0) - baseline.unwrap_or(1.0);
+            (delta.abs() > f64::EPSILON).then(|| ModuleScoreDelta {
+                module: module.clone(),
+                baseline,
+                current,
+                delta,
+            })
+        })
+        .collect();
+    deltas.sort_by(|a, b| a.delta.total_cmp(&b.delta));
+    deltas
+}
+
+/// Change of one distribution bucket, e.g. Intrusive strength.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct BucketDelta {
+    /// `strength`, `distance`, `volatility` or `balance`.
+    pub dimension: &'static str,
+    /// Bucket within the dimension, e.g. `Intrusive` or `DifferentModule`.
+    pub bucket: &'static str,
+    /// Couplings in the bucket at the baseline.
+    pub baseline: usize,
+    /// Couplings in the bucket now.
+    pub current: usize,
+    /// `current - baseline`.
+    pub delta: i64,
+}
+
+/// Per-bucket change of `DimensionStats` between two analyses.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct DimensionStatsDelta {
+    /// Every bucket of every dimension, in report order.
+    pub buckets: Vec<BucketDelta>,
+}
+
+impl DimensionStatsDelta {
+    /// Buckets whose count changed.
+    pub fn changed(&self) -> impl Iterator<Item = &BucketDelta> {
+        self.buckets.iter().filter(|bucket| bucket.delta != 0)
+    }
+}
+
+impl fmt::Display for DimensionStatsDelta {
+    /// Formats the changed buckets, e.g. `Intrusive couplings +12, Contract couplings -3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .changed()
+            .map(|bucket| format!("{} couplings {:+}", bucket.bucket, bucket.delta))
+            .collect();
+        if parts.is_empty() {
+            write!(f, "no change")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Compare two dimension distributions bucket by bucket.
+pub fn diff_dimension_stats(
+    baseline: &DimensionStats,
+    current: &DimensionStats,
+) -> DimensionStatsDelta {
+    let rows = |stats: &DimensionStats| {
+        [
+            ("strength", "Intrusive", stats.strength_counts.intrusive),
+            ("strength", "Functional", stats.strength_counts.functional),
+            ("strength", "Model", stats.strength_counts.model),
+            ("strength", "Contract", stats.strength_counts.contract),
+            ("distance", "SameModule", stats.distance_counts.same_module),
+            (
+                "distance",
+                "DifferentModule",
+                stats.distance_counts.different_module,
+            ),
+            (
+                "distance",
+                "DifferentCrate",
+                stats.distance_counts.different_crate,
+            ),
+            ("volatility", "Low", stats.volatility_counts.low),
+            ("volatility", "Medium", stats.volatility_counts.medium),
+            ("volatility", "High", stats.volatility_counts.high),
+            (
+                "balance",
+                "HighCohesion",
+                stats.balance_counts.high_cohesion,
+            ),
+            (
+                "balance",
+                "LooseCoupling",
+                stats.balance_counts.loose_coupling,
+            ),
+            ("balance", "Acceptable", stats.balance_counts.acceptable),
+            ("balance", "Pain", stats.balance_counts.pain),
+            (
+                "balance",
+                "LocalComplexity",
+                stats.balance_counts.local_complexity,
+            ),
+        ]
+    };
+
+    let buckets = rows(baseline)
+        .into_iter()
+        .zip(rows(current))
+        .map(|((dimension, bucket, before), (_, _, after))| BucketDelta {
+            dimension,
+            bucket,
+            baseline: before,
+            current: after,
+            delta: after as i64 - before as i64,
+        })
+        .collect();
+
+    DimensionStatsDelta { buckets }
 }
 
 #[cfg(test)]
@@ -119,6 +359,7 @@ mod tests {
             issues,
             top_priorities: Vec::new(),
             grade_rationale: GradeRationale::empty(),
+            inline_suppressions: 0,
         }
     }
 
@@ -226,6 +467,55 @@ mod tests {
         assert_eq!(diff.current_grade, HealthGrade::C);
     }
 
+    #[test]
+    fn test_shifting_a_coupling_from_model_to_intrusive_shows_bucket_deltas() {
+        use crate::{CouplingMetrics, Distance, IntegrationStrength, Visibility, Volatility};
+
+        let project = |strength| {
+            let mut metrics = ProjectMetrics::new();
+            metrics.add_coupling(CouplingMetrics::with_visibility(
+                "a".to_string(),
+                "b".to_string(),
+                IntegrationStrength::Contract,
+                Distance::DifferentModule,
+                Volatility::Low,
+                Visibility::Public,
+            ));
+            metrics.add_coupling(CouplingMetrics::with_visibility(
+                "a".to_string(),
+                "c".to_string(),
+                strength,
+                Distance::DifferentModule,
+                Volatility::Low,
+                Visibility::Public,
+            ));
+            metrics.calculate_dimension_stats()
+        };
+
+        let delta = diff_dimension_stats(
+            &project(IntegrationStrength::Model),
+            &project(IntegrationStrength::Intrusive),
+        );
+        let changed: Vec<_> = delta
+            .changed()
+            .map(|bucket| (bucket.dimension, bucket.bucket, bucket.delta))
+            .collect();
+
+        // Model+DifferentModule+Low is Acceptable; Intrusive+DifferentModule+Low as well,
+        // so only the strength buckets move.
+        assert_eq!(
+            changed,
+            vec![("strength", "Intrusive", 1), ("strength", "Model", -1)]
+        );
+        assert_eq!(
+            delta.to_string(),
+            "Intrusive couplings +1, Model couplings -1"
+        );
+
+        let intrusive = &delta.buckets[0];
+        assert_eq!((intrusive.baseline, intrusive.current), (0, 1));
+    }
+
     #[test]
     fn ratchet_filters_by_severity() {
         let diff = BaselineDiff {
@@ -243,12 +533,93 @@ mod tests {
             score_delta: 0.0,
             baseline_grade: HealthGrade::B,
             current_grade: HealthGrade::B,
+            dimension_delta: None,
+            module_deltas: Vec::new(),
+            warnings: Vec::new(),
         };
 
         assert_eq!(diff.ratchet_failures(Severity::High).len(), 1);
         assert_eq!(diff.ratchet_failures(Severity::Medium).len(), 2);
     }
 
+    #[test]
+    fn test_headline_counts_new_issues_by_severity_and_grade_change() {
+        let baseline = report(Vec::new(), 0.8, HealthGrade::B);
+        let current = report(
+            vec![
+                issue(IssueType::GodModule, Severity::Critical, "a", "x"),
+                issue(IssueType::GodModule, Severity::Critical, "b", "x"),
+                issue(IssueType::GodModule, Severity::Critical, "c", "x"),
+                issue(IssueType::GlobalComplexity, Severity::Medium, "d", "e"),
+            ],
+            0.7,
+            HealthGrade::C,
+        );
+
+        let diff = diff_reports(&baseline, &current);
+
+        assert_eq!(
+            diff.headline(),
+            "+3 new Critical issues, +1 new Medium issue, grade dropped B→C"
+        );
+        assert!(diff.regressed_beyond(0.05));
+        assert!(!diff.regressed_beyond(0.2));
+
+        let reverse = diff_reports(&current, &baseline);
+        assert_eq!(reverse.headline(), "no new issues, grade improved C→B");
+        assert!(!reverse.regressed_beyond(0.0));
+    }
+
+    #[test]
+    fn test_module_score_deltas_list_changed_modules_worst_first() {
+        use crate::{CouplingMetrics, Distance, IntegrationStrength, Visibility, Volatility};
+
+        let project = |strength| {
+            let mut metrics = ProjectMetrics::new();
+            metrics.add_coupling(CouplingMetrics::with_visibility(
+                "stable".to_string(),
+                "b".to_string(),
+                IntegrationStrength::Contract,
+                Distance::DifferentModule,
+                Volatility::Low,
+                Visibility::Public,
+            ));
+            metrics.add_coupling(CouplingMetrics::with_visibility(
+                "changed".to_string(),
+                "c".to_string(),
+                strength,
+                Distance::DifferentModule,
+                Volatility::High,
+                Visibility::Public,
+            ));
+            metrics
+        };
+
+        let deltas = diff_module_scores(
+            &project(IntegrationStrength::Contract),
+            &project(IntegrationStrength::Intrusive),
+            &BalanceFormula::default(),
+        );
+
+        assert_eq!(deltas.len(), 1, "unchanged modules are omitted");
+        assert_eq!(deltas[0].module, "changed");
+        assert!(deltas[0].delta < 0.0);
+        let (before, after) = (deltas[0].baseline.unwrap(), deltas[0].current.unwrap());
+        assert!((after - before - deltas[0].delta).abs() < f64::EPSILON);
+
+        // Scored with the configured formula, like the reports being diffed
+        let alignment_only = BalanceFormula {
+            volatility_weight: 0.0,
+            ..BalanceFormula::default()
+        };
+        let configured = diff_module_scores(
+            &project(IntegrationStrength::Contract),
+            &project(IntegrationStrength::Intrusive),
+            &alignment_only,
+        );
+        assert_ne!(configured[0].delta, deltas[0].delta);
+    }
+
     #[test]
     fn high_coupling_count_targets_do_not_create_new_issue_keys() {
         let baseline = report(
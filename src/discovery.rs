@@ -8,6 +8,7 @@
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Component, Path, PathBuf};
 
 use syn::{Expr, ExprLit, ItemMod, Lit, Meta};
@@ -112,6 +113,27 @@ pub(crate) fn rs_files(dir: &Path) -> impl Iterator<Item = PathBuf> {
         .map(|e| e.path().to_path_buf())
 }
 
+/// Leading lines of a file searched for generated-code markers.
+const GENERATED_HEADER_LINES: usize = 5;
+
+/// Return whether one of the first lines of `path` contains one of `markers`
+/// (`// @generated`, `// This file is @generated by prost-build.`).
+///
+/// Unreadable files are not generated; they surface later as parse failures.
+pub(crate) fn is_generated_file(path: &Path, markers: &[String]) -> bool {
+    if markers.is_empty() {
+        return false;
+    }
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    BufReader::new(file)
+        .lines()
+        .take(GENERATED_HEADER_LINES)
+        .map_while(Result::ok)
+        .any(|line| markers.iter().any(|marker| line.contains(marker.as_str())))
+}
+
 /// Get Rust source files under `dir`, pruning nested packages and conventional non-source roots.
 pub(crate) fn rs_files_excluding_nested_packages(
     dir: &Path,
@@ -402,7 +424,7 @@ fn crosses_package_boundary(
 }
 
 /// Extract the string value from a `#[path = "..."]` module attribute.
-fn path_attribute_value(attrs: &[syn::Attribute]) -> Option<PathBuf> {
+pub(crate) fn path_attribute_value(attrs: &[syn::Attribute]) -> Option<PathBuf> {
     attrs.iter().find_map(|attr| {
         if !attr.path().is_ident("path") {
             return None;
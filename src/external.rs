@@ -7,15 +7,16 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub use crate::balance::external_crates::SCATTERED_EXTERNAL_BREADTH_THRESHOLD;
 // Consume the crate's published facade rather than deep `balance::*` paths: the
 // re-exported surface stays stable when the balance package reorganizes internally.
-use crate::CouplingIssue;
 use crate::metrics::coupling::CouplingMetrics;
 use crate::metrics::dimensions::{Distance, IntegrationStrength};
 use crate::metrics::project::ProjectMetrics;
+use crate::{CouplingIssue, IssueThresholds};
 
 /// External dependency coupling report.
 #[derive(Debug, Clone)]
@@ -27,7 +28,7 @@ pub struct ExternalDependencyReport {
 }
 
 /// Aggregated usage of one external crate.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
 pub struct ExternalDependencyUsage {
     /// Crate name.
     pub crate_name: String,
@@ -77,6 +78,15 @@ impl ExternalAccumulator {
 pub fn analyze_external_dependencies(
     metrics: &ProjectMetrics,
     versions: &HashMap<String, Vec<String>>,
+) -> ExternalDependencyReport {
+    analyze_external_dependencies_with_thresholds(metrics, versions, &IssueThresholds::default())
+}
+
+/// Analyze external dependency coupling, classifying crates with `[external]`.
+pub fn analyze_external_dependencies_with_thresholds(
+    metrics: &ProjectMetrics,
+    versions: &HashMap<String, Vec<String>>,
+    thresholds: &IssueThresholds,
 ) -> ExternalDependencyReport {
     let known_external_crates = known_external_crates(metrics, versions);
     let mut by_crate: BTreeMap<String, ExternalAccumulator> = BTreeMap::new();
@@ -124,7 +134,10 @@ pub fn analyze_external_dependencies(
     });
 
     let scattered_couplings =
-        crate::balance::external_crates::detect_scattered_external_coupling(&dependencies);
+        crate::balance::external_crates::detect_scattered_external_coupling_with_thresholds(
+            &dependencies,
+            thresholds,
+        );
 
     ExternalDependencyReport {
         dependencies,
@@ -0,0 +1,93 @@
+//! Output header identifying the analyzer build and effective configuration.
+//!
+//! Scores are only comparable between runs of the same analyzer version with
+//! the same effective config. JSON and Markdown outputs carry an
+//! `OutputHeader` so saved reports (snapshots, baselines, caches) can be
+//! checked for that before they are compared.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Version of this analyzer build.
+pub const ANALYZER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Provenance of one analysis output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct OutputHeader {
+    /// `cargo-coupling` version that produced the output
+    pub analyzer_version: String,
+    /// `CompiledConfig::config_hash` of the run; `None` when unknown
+    pub config_hash: Option<String>,
+    /// Unix timestamp (seconds) of the run; orders saved snapshots in `--web --snapshots`
+    pub generated_at: u64,
+}
+
+impl OutputHeader {
+    /// Header for an output generated now by this build.
+    pub fn new(config_hash: Option<String>) -> Self {
+        Self {
+            analyzer_version: ANALYZER_VERSION.to_string(),
+            config_hash,
+            generated_at: unix_now(),
+        }
+    }
+
+    /// One-line Markdown rendering placed under the report title.
+    pub fn markdown_line(&self) -> String {
+        format!(
+            "_cargo-coupling {} · config {} · generated at {}_",
+            self.analyzer_version,
+            self.config_hash.as_deref().unwrap_or("unknown"),
+            self.generated_at
+        )
+    }
+}
+
+/// Reasons two outputs are not comparable, empty when they are.
+///
+/// `label` names the other output in the messages (a snapshot file, a baseline).
+/// Unknown values on either side are not reported: older outputs have no header.
+pub fn comparability_warnings(
+    label: &str,
+    (version, config_hash): (Option<&str>, Option<&str>),
+    (current_version, current_config_hash): (Option<&str>, Option<&str>),
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if let (Some(version), Some(current)) = (version, current_version)
+        && version != current
+    {
+        warnings.push(format!(
+            "{} was produced by analyzer version {} (current: {}); results may not be comparable",
+            label, version, current
+        ));
+    }
+    if let (Some(hash), Some(current)) = (config_hash, current_config_hash)
+        && hash != current
+    {
+        warnings.push(format!(
+            "{} was produced with a different config (hash {}, current: {}); results are not comparable",
+            label, hash, current
+        ));
+    }
+    warnings
+}
+
+/// 64-bit FNV-1a hash as 16 hex digits; stable across builds and platforms.
+pub(crate) fn stable_hash(input: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = input.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
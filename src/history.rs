@@ -340,6 +340,7 @@ fn analyze_ref_in_repo(
     let mut config = rebase_config_root(params.config, repo_root, &worktree.dir);
     let mut metrics = analyze_workspace_with_config(&analysis_path, &config)
         .map_err(|e| HistoryError::Analysis(e.to_string()))?;
+    metrics.config_hash = Some(config.config_hash(params.thresholds));
 
     // Drift notes assert "the CURRENT config is stale". Historical revisions are
     // analyzed with today's config against an old tree, so an unmatched pattern
@@ -353,7 +354,7 @@ fn analyze_ref_in_repo(
     }
 
     if params.use_git {
-        let mut volatility = VolatilityAnalyzer::new(params.months);
+        let mut volatility = VolatilityAnalyzer::from_config(params.months, &config);
         if volatility.analyze(&analysis_path).is_ok() {
             if let Ok(temporal) = volatility.analyze_temporal_coupling(&analysis_path) {
                 metrics.temporal_couplings = temporal;
@@ -406,6 +407,8 @@ fn rebase_config_root(
     worktree_root: &Path,
 ) -> CompiledConfig {
     let mut config = config.clone();
+    // The worktree is deleted after this analysis, so its cache entries could never be hit
+    config.analysis_cache = None;
     let Some(config_root) = config.config_root() else {
         return config;
     };
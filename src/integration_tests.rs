@@ -0,0 +1,253 @@
+//! Integration test coupling analysis.
+//!
+//! Integration tests under `tests/` link the crate as an external dependency, so
+//! their couplings show how the public API is actually consumed. They are
+//! analyzed as a separate bucket and never feed the core health grade.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::analyzer::{
+    DependencyPathFilter, analyze_rust_file_full, resolve_target_module, strength_for_dependency,
+    target_type_name, visibility_for_dependency,
+};
+use crate::discovery::{file_path_to_module_path, rs_files};
+use crate::log_warn;
+use crate::metrics::coupling::CouplingMetrics;
+use crate::metrics::dimensions::Distance;
+use crate::metrics::project::ProjectMetrics;
+use crate::volatility::Volatility;
+use crate::workspace::WorkspaceInfo;
+
+/// Couplings from `tests/` integration tests into the analyzed crates.
+#[derive(Debug, Clone, Default)]
+pub struct IntegrationTestReport {
+    /// Integration test files analyzed.
+    pub test_files: usize,
+    /// Couplings from integration test modules (`tests::<name>`) to crate modules.
+    pub couplings: Vec<CouplingMetrics>,
+    /// Crate modules exercised by integration tests, most referenced first.
+    pub targets: Vec<IntegrationTestTarget>,
+}
+
+/// One crate module reached from integration tests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IntegrationTestTarget {
+    /// Target module (`crate::module`).
+    pub module: String,
+    /// Direct references from integration tests.
+    pub references: usize,
+    /// Integration test modules referencing this module.
+    pub test_modules: Vec<String>,
+}
+
+/// Analyze the `tests/` directory of each package next to `path`.
+///
+/// `metrics` is the core analysis; its modules and type registry resolve what
+/// the tests reference. Couplings that do not land on a known crate module
+/// (std, dev-dependencies, test helpers) are ignored, and so are paths
+/// `filter` takes for local variables.
+pub fn analyze_integration_tests(
+    path: &Path,
+    metrics: &ProjectMetrics,
+    filter: &DependencyPathFilter,
+) -> IntegrationTestReport {
+    analyze_integration_tests_for_roots(&[path.to_path_buf()], metrics, filter)
+}
+
+/// [`analyze_integration_tests`] for a multi-root analysis: the `tests/`
+/// directories next to every root, each analyzed once.
+pub fn analyze_integration_tests_for_roots(
+    paths: &[PathBuf],
+    metrics: &ProjectMetrics,
+    filter: &DependencyPathFilter,
+) -> IntegrationTestReport {
+    let known_modules: HashSet<String> = metrics.modules.keys().cloned().collect();
+    let mut report = IntegrationTestReport::default();
+
+    let mut seen_dirs = HashSet::new();
+    let test_dirs = paths
+        .iter()
+        .flat_map(|path| integration_test_dirs(path))
+        .filter(|(_, tests_dir)| {
+            seen_dirs.insert(
+                tests_dir
+                    .canonicalize()
+                    .unwrap_or_else(|_| tests_dir.clone()),
+            )
+        });
+    for (crate_name, tests_dir) in test_dirs {
+        let lib_prefix = format!("{}::", crate_name.replace('-', "_"));
+
+        for file_path in rs_files(&tests_dir) {
+            let Ok(result) = analyze_rust_file_full(&file_path) else {
+                log_warn!(
+                    "Warning: Failed to analyze integration test {}",
+                    file_path.display()
+                );
+                continue;
+            };
+            report.test_files += 1;
+            let test_module = format!(
+                "tests::{}",
+                file_path_to_module_path(&file_path, &tests_dir)
+            );
+
+            for dep in &result.dependencies {
+                // The crate is an external dependency from the test's point of view;
+                // rewrite `my_crate::x` to `crate::x` so the core resolver applies.
+                let path = match dep.path.strip_prefix(&lib_prefix) {
+                    Some(rest) => format!("crate::{}", rest),
+                    None => dep.path.clone(),
+                };
+                let target_module = resolve_target_module(&path, "", &known_modules, metrics);
+                if !known_modules.contains(&target_module)
+                    || !filter.keeps(&path, &target_module, true)
+                {
+                    continue;
+                }
+
+                let target_visibility =
+                    target_type_name(&path).and_then(|name| metrics.get_type_visibility(name));
+                let mut coupling = CouplingMetrics::with_location(
+                    test_module.clone(),
+                    format!("{}::{}", crate_name, target_module),
+                    strength_for_dependency(dep, target_visibility),
+                    Distance::DifferentCrate,
+                    Volatility::Low,
+                    visibility_for_dependency(dep, target_visibility),
+                    file_path.clone(),
+                    dep.line,
+                );
+                coupling.target_crate = Some(crate_name.clone());
+                report.couplings.push(coupling);
+            }
+        }
+    }
+
+    report.targets = summarize_targets(&report.couplings);
+    report
+}
+
+/// Locate `tests/` directories, paired with the package that owns them.
+fn integration_test_dirs(path: &Path) -> Vec<(String, PathBuf)> {
+    if let Ok(workspace) = WorkspaceInfo::from_path(path) {
+        return workspace
+            .members
+            .iter()
+            .filter_map(|member| workspace.get_crate(member))
+            .filter_map(|crate_info| {
+                let tests_dir = crate_info.manifest_path.parent()?.join("tests");
+                tests_dir
+                    .is_dir()
+                    .then(|| (crate_info.name.clone(), tests_dir))
+            })
+            .collect();
+    }
+
+    // Without cargo metadata, assume a conventional `<package>/src` layout.
+    let package_dir = if path.ends_with("src") {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    let tests_dir = package_dir.join("tests");
+    let crate_name = package_dir
+        .canonicalize()
+        .ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "crate".to_string());
+
+    if tests_dir.is_dir() {
+        vec![(crate_name, tests_dir)]
+    } else {
+        Vec::new()
+    }
+}
+
+fn summarize_targets(couplings: &[CouplingMetrics]) -> Vec<IntegrationTestTarget> {
+    let mut by_target: BTreeMap<&str, (usize, BTreeSet<&str>)> = BTreeMap::new();
+    for coupling in couplings {
+        let entry = by_target.entry(&coupling.target).or_default();
+        entry.0 += 1;
+        entry.1.insert(&coupling.source);
+    }
+
+    let mut targets: Vec<IntegrationTestTarget> = by_target
+        .into_iter()
+        .map(
+            |(module, (references, test_modules))| IntegrationTestTarget {
+                module: module.to_string(),
+                references,
+                test_modules: test_modules.into_iter().map(str::to_string).collect(),
+            },
+        )
+        .collect();
+    targets.sort_by(|a, b| {
+        b.references
+            .cmp(&a.references)
+            .then_with(|| a.module.cmp(&b.module))
+    });
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::analyze_workspace;
+
+    #[test]
+    fn test_integration_test_coupling_is_reported_separately() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"geo-kit\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        let src = tmp.path().join("src");
+        let tests = tmp.path().join("tests");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::create_dir_all(&tests).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub mod shapes;\n").unwrap();
+        std::fs::write(
+            src.join("shapes.rs"),
+            "pub struct Circle {\n    pub radius: f64,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tests.join("it.rs"),
+            "use geo_kit::shapes::Circle;\n\n#[test]\nfn builds() {\n    let c = Circle { radius: 1.0 };\n    assert_eq!(c.radius, 1.0);\n}\n",
+        )
+        .unwrap();
+
+        let metrics = analyze_workspace(&src).unwrap();
+        assert!(
+            metrics
+                .couplings
+                .iter()
+                .all(|coupling| !coupling.source.contains("tests::it")),
+            "integration tests must stay out of the core analysis"
+        );
+
+        let report = analyze_integration_tests(&src, &metrics, &DependencyPathFilter::default());
+        assert_eq!(report.test_files, 1);
+        assert!(!report.couplings.is_empty());
+        assert!(
+            report
+                .couplings
+                .iter()
+                .all(|coupling| coupling.source == "tests::it")
+        );
+        assert_eq!(report.targets.len(), 1);
+        assert_eq!(report.targets[0].module, "geo-kit::shapes");
+        assert_eq!(report.targets[0].test_modules, vec!["tests::it"]);
+
+        // Two roots of the same package read its tests/ once
+        let roots = [src.clone(), tmp.path().to_path_buf()];
+        let report =
+            analyze_integration_tests_for_roots(&roots, &metrics, &DependencyPathFilter::default());
+        assert_eq!(report.test_files, 1);
+    }
+}
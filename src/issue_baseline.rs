@@ -0,0 +1,222 @@
+//! Issue baseline files (`--write-baseline`, `--check --baseline-file <file>`).
+//!
+//! Adopting the quality gate on a legacy codebase should not require fixing
+//! every existing issue first. A baseline file records a fingerprint of each
+//! current issue and the modules of each circular dependency; `--check` then
+//! ignores those and fails only on new ones. Fingerprints hash the issue type,
+//! source, target and description, never a line number, so they survive
+//! unrelated edits to the same file.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::grade::ProjectBalanceReport;
+use crate::balance::issue::CouplingIssue;
+use crate::header::{ANALYZER_VERSION, stable_hash};
+
+/// File name `--write-baseline` uses when none is given.
+pub const DEFAULT_BASELINE_FILE: &str = "coupling-baseline.json";
+
+/// Stable fingerprint of an issue: hash of type, source, target and description.
+pub fn issue_fingerprint(issue: &CouplingIssue) -> String {
+    stable_hash(&format!(
+        "{:?}\u{1f}{}\u{1f}{}\u{1f}{}",
+        issue.issue_type, issue.source, issue.target, issue.description
+    ))
+}
+
+/// One accepted issue; only `fingerprint` is used for matching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineIssue {
+    pub fingerprint: String,
+    #[serde(default)]
+    pub issue_type: String,
+    #[serde(default)]
+    pub severity: String,
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub target: String,
+}
+
+/// Issues accepted as pre-existing debt.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueBaseline {
+    /// `cargo-coupling` version that wrote the file
+    #[serde(default)]
+    pub analyzer_version: Option<String>,
+    /// `CompiledConfig::config_hash` of the run that wrote the file
+    #[serde(default)]
+    pub config_hash: Option<String>,
+    pub issues: Vec<BaselineIssue>,
+    /// Accepted circular dependencies, each as its sorted module names
+    #[serde(default)]
+    pub circular_dependencies: Vec<Vec<String>>,
+}
+
+impl IssueBaseline {
+    /// Baseline accepting every given issue.
+    pub fn from_issues(issues: &[CouplingIssue], config_hash: Option<String>) -> Self {
+        let mut seen = HashSet::new();
+        let issues = issues
+            .iter()
+            .filter_map(|issue| {
+                let fingerprint = issue_fingerprint(issue);
+                seen.insert(fingerprint.clone()).then(|| BaselineIssue {
+                    fingerprint,
+                    issue_type: issue.issue_type.to_string(),
+                    severity: issue.severity.to_string(),
+                    source: issue.source.clone(),
+                    target: issue.target.clone(),
+                })
+            })
+            .collect();
+
+        Self {
+            analyzer_version: Some(ANALYZER_VERSION.to_string()),
+            config_hash,
+            issues,
+            circular_dependencies: Vec::new(),
+        }
+    }
+
+    /// Also accept the given circular dependencies (as returned by
+    /// `ProjectMetrics::detect_circular_dependencies`).
+    pub fn with_circular_dependencies(mut self, cycles: &[Vec<String>]) -> Self {
+        let mut accepted: Vec<Vec<String>> = cycles.iter().map(|cycle| cycle_key(cycle)).collect();
+        accepted.sort();
+        accepted.dedup();
+        self.circular_dependencies = accepted;
+        self
+    }
+
+    /// Whether the circular dependency through `cycle` was accepted by this baseline.
+    pub fn contains_cycle(&self, cycle: &[String]) -> bool {
+        self.circular_dependencies.contains(&cycle_key(cycle))
+    }
+
+    /// Read a baseline written by `write`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid baseline file {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Write the baseline as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+
+    /// Whether `issue` was accepted by this baseline.
+    pub fn contains(&self, issue: &CouplingIssue) -> bool {
+        let fingerprint = issue_fingerprint(issue);
+        self.issues
+            .iter()
+            .any(|accepted| accepted.fingerprint == fingerprint)
+    }
+
+    /// Drop accepted issues from `report`; returns how many were dropped.
+    pub fn suppress(&self, report: &mut ProjectBalanceReport) -> usize {
+        let fingerprints: HashSet<&str> = self
+            .issues
+            .iter()
+            .map(|accepted| accepted.fingerprint.as_str())
+            .collect();
+        let before = report.issues.len();
+        report.retain_issues(|issue| !fingerprints.contains(issue_fingerprint(issue).as_str()));
+        before - report.issues.len()
+    }
+}
+
+/// A cycle's modules in sorted order, so any rotation of it matches.
+fn cycle_key(cycle: &[String]) -> Vec<String> {
+    let mut modules = cycle.to_vec();
+    modules.sort();
+    modules.dedup();
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance::action::RefactoringAction;
+    use crate::balance::grade::HealthGrade;
+    use crate::balance::issue_type::IssueType;
+    use crate::balance::rationale::GradeRationale;
+    use crate::balance::severity::Severity;
+
+    fn issue(issue_type: IssueType, severity: Severity, source: &str) -> CouplingIssue {
+        CouplingIssue {
+            issue_type,
+            severity,
+            source: source.to_string(),
+            target: "target".to_string(),
+            description: format!("{} is coupled", source),
+            refactoring: RefactoringAction::General {
+                action: String::new(),
+            },
+            balance_score: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_baseline_suppresses_known_issues_and_keeps_new_ones() {
+        let known = issue(IssueType::GodModule, Severity::High, "legacy");
+        let new = issue(IssueType::GodModule, Severity::Critical, "fresh");
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(DEFAULT_BASELINE_FILE);
+        IssueBaseline::from_issues(std::slice::from_ref(&known), Some("abc".to_string()))
+            .write(&path)
+            .unwrap();
+        let baseline = IssueBaseline::load(&path).unwrap();
+        assert_eq!(baseline.config_hash.as_deref(), Some("abc"));
+
+        let mut report = ProjectBalanceReport {
+            total_couplings: 0,
+            balanced_count: 0,
+            needs_review: 0,
+            needs_refactoring: 0,
+            average_score: 1.0,
+            health_grade: HealthGrade::B,
+            issues_by_severity: [(Severity::High, 1), (Severity::Critical, 1)].into(),
+            issues_by_type: [(IssueType::GodModule, 2)].into(),
+            issues: vec![known.clone(), new.clone()],
+            top_priorities: vec![known, new],
+            grade_rationale: GradeRationale::empty(),
+            inline_suppressions: 0,
+        };
+
+        assert_eq!(baseline.suppress(&mut report), 1);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].source, "fresh");
+        assert_eq!(report.top_priorities.len(), 1);
+        assert_eq!(report.issues_by_severity.get(&Severity::High), None);
+        assert_eq!(report.issues_by_severity[&Severity::Critical], 1);
+        assert_eq!(report.issues_by_type[&IssueType::GodModule], 1);
+    }
+
+    #[test]
+    fn test_fingerprint_covers_identity_and_description_only() {
+        let base = issue(IssueType::GodModule, Severity::High, "m");
+        let mut rescored = base.clone();
+        rescored.balance_score = 0.1;
+        rescored.severity = Severity::Medium;
+        assert_eq!(issue_fingerprint(&base), issue_fingerprint(&rescored));
+
+        let mut reworded = base.clone();
+        reworded.description.push_str(" more");
+        assert_ne!(issue_fingerprint(&base), issue_fingerprint(&reworded));
+    }
+}
@@ -36,66 +36,136 @@
 //! - Strong coupling + far distance = Bad (global complexity)
 //! - High volatility + strong coupling = Bad (cascading changes)
 
+pub mod analysis;
 pub mod analyzer;
 pub mod balance;
+pub mod benchmark;
+pub mod cache;
 mod classification;
 pub mod cli_output;
 pub mod config;
 pub mod diff;
 mod discovery;
 pub mod external;
+pub mod header;
 pub mod history;
+pub mod integration_tests;
+pub mod issue_baseline;
+pub mod logging;
 pub mod manifest;
+pub mod metric_snapshot;
 pub mod metrics;
+pub mod module_map;
+pub mod remote;
 pub mod report;
+pub mod suggest;
+pub mod temporal;
+#[cfg(test)]
+mod test_support;
+pub mod timing;
+pub mod trend;
+pub mod visibility;
 pub mod volatility;
 pub mod web;
 pub mod workspace;
 
+pub use analysis::{Analysis, AnalysisBuilder, AnalysisOutcome};
 pub use analyzer::{
-    AnalyzedFileResult, AnalyzerError, CouplingAnalyzer, Dependency, DependencyKind, ItemDepType,
-    ItemDependency, ItemKind, analyze_project, analyze_project_parallel_with_config,
-    analyze_rust_file, analyze_rust_file_full, analyze_workspace, analyze_workspace_with_config,
+    AnalyzedFileResult, AnalyzerError, CouplingAnalyzer, Dependency, DependencyKind,
+    DependencyPathFilter, ItemDepType, ItemDependency, ItemKind, PrimitiveTypes, analyze_project,
+    analyze_project_parallel_with_config, analyze_roots_with_config, analyze_rust_file,
+    analyze_rust_file_full, analyze_rust_file_full_with_options, analyze_workspace,
+    analyze_workspace_with_config,
 };
-pub use balance::action::RefactoringAction;
-pub use balance::grade::{HealthGrade, ProjectBalanceReport};
+pub use balance::action::{Effort, RefactoringAction};
+pub use balance::grade::{GradeMinimums, HealthGrade, ProjectBalanceReport};
 pub use balance::issue::CouplingIssue;
 pub use balance::issue::IssueKey;
 pub use balance::issue_type::IssueType;
 pub use balance::project::{
-    analyze_project_balance, analyze_project_balance_with_thresholds, calculate_project_score,
+    analyze_crate_balance, analyze_project_balance, analyze_project_balance_with_thresholds,
+    calculate_module_scores, calculate_module_scores_with, calculate_project_score,
+    calculate_project_score_with, worst_couplings,
 };
 pub use balance::rationale::{GradeDimension, GradeRationale, IssueTypeContribution};
-pub use balance::score::{BalanceInterpretation, BalanceScore, IssueThresholds};
-pub use balance::severity::Severity;
+pub use balance::score::{
+    ArchitectureLayer, BalanceBands, BalanceCombiner, BalanceFormula, BalanceInterpretation,
+    BalanceScore, CognitiveLoadWeights, GodModuleLimits, IssueThresholds,
+};
+pub use balance::severity::{Severity, SeverityOverride};
+pub use benchmark::{
+    BenchmarkReport, DimensionPercentile, ReferenceMatch, ReferenceProfile, benchmark_profile,
+    reference_profiles,
+};
+pub use cache::{AnalysisCache, CACHE_DIR};
 pub use config::{
-    AnalysisConfig, CompiledConfig, ConfigError, CouplingConfig, DeadConfigPattern,
-    ThresholdsConfig, VolatilityConfig, load_compiled_config, load_config,
+    AnalysisConfig, BalanceBandsConfig, BalanceConfig, CognitiveLoadConfig, CompiledConfig,
+    ConfigError, CouplingConfig, DeadConfigPattern, GodModuleConfig, GradingConfig, ModuleBudget,
+    ProjectConfig, ProjectCrateConfig, ThresholdsConfig, VolatilityConfig, load_compiled_config,
+    load_compiled_config_for, load_config,
+};
+pub use diff::{
+    BaselineDiff, BucketDelta, DimensionStatsDelta, ModuleScoreDelta, diff_dimension_stats,
+    diff_module_scores, diff_ref_analysis, diff_reports,
 };
-pub use diff::{BaselineDiff, diff_ref_analysis, diff_reports};
 pub use external::{
     ExternalDependencyReport, ExternalDependencyUsage, SCATTERED_EXTERNAL_BREADTH_THRESHOLD,
-    analyze_external_dependencies, detect_scattered_external_coupling, load_lock_versions_near,
+    analyze_external_dependencies, analyze_external_dependencies_with_thresholds,
+    detect_scattered_external_coupling, load_lock_versions_near,
 };
+pub use header::{ANALYZER_VERSION, OutputHeader, comparability_warnings};
 pub use history::{
     HistoryError, HistoryPoint, HistoryReport, RefAnalysis, SkippedRevision, analyze_history,
     analyze_ref,
 };
+pub use integration_tests::{
+    IntegrationTestReport, IntegrationTestTarget, analyze_integration_tests,
+    analyze_integration_tests_for_roots,
+};
+pub use issue_baseline::{BaselineIssue, DEFAULT_BASELINE_FILE, IssueBaseline, issue_fingerprint};
+pub use logging::{LogLevel, log_enabled, log_level, set_log_level};
 pub use manifest::{AnalysisManifest, BlindSpot, ManifestContext, build_manifest};
+pub use metric_snapshot::{
+    MetricChange, MetricsSnapshot, SeverityCounts, SnapshotComparison, SnapshotTolerances,
+};
+pub use metrics::cohesion::{ModuleCohesion, TypeCluster, calculate_module_cohesion};
 pub use metrics::coupling::{CouplingLocation, CouplingMetrics};
 pub use metrics::dimensions::{
-    Distance, IntegrationStrength, MetricsConfig, Subdomain, Visibility,
+    Connascence, Distance, IntegrationStrength, MetricsConfig, Subdomain, Visibility,
 };
 pub use metrics::module::{
-    BalanceClassification, BalanceCounts, DimensionStats, DistanceCounts, FunctionDefinition,
-    ModuleMetrics, StrengthCounts, TypeDefinition, VolatilityCounts,
+    BalanceClassification, BalanceCounts, ConnascenceCounts, DerefImpl, DimensionStats,
+    DistanceCounts, FileSubmodule, FunctionDefinition, IssueSuppression, ModuleMetrics,
+    PassThroughMethod, StrengthCounts, TypeDefinition, VolatilityCounts,
 };
-pub use metrics::project::{CircularDependencySummary, ProjectMetrics};
+pub use metrics::profile::CouplingProfile;
+pub use metrics::project::{
+    CircularDependencySummary, FeatureCoupling, MemoryProfile, MutualCoupling, MutualDirection,
+    ProjectMetrics,
+};
+pub use metrics::stability::{ModuleStability, StabilityZone, calculate_module_stability};
+pub use module_map::{
+    ModuleMap, ModuleMapEntry, ModuleMapItem, SymbolListEntry, SymbolListKind, build_module_map,
+    build_symbol_list, generate_module_map_json, generate_module_map_text,
+    generate_symbol_list_json, generate_symbol_list_text,
+};
+pub use remote::{RemoteCheckout, RemoteError, is_remote_source};
 pub use report::{
     TextReportOptions, generate_ai_output, generate_ai_output_with_thresholds, generate_report,
     generate_report_with_options, generate_report_with_thresholds, generate_summary,
     generate_summary_with_options, generate_summary_with_thresholds,
 };
+pub use suggest::{
+    Suggestion, build_suggestions, generate_suggestions_json, generate_suggestions_text,
+};
+pub use temporal::{
+    CoChangeKind, CoChangePair, TemporalCouplingReport, analyze_temporal_coupling_report,
+};
+pub use timing::{AnalysisTimings, PhaseTimings, TimingReport, balance_time};
+pub use trend::{TrendEntry, TrendHistory};
+pub use visibility::{
+    SuggestedVisibility, VisibilityAudit, VisibilitySuggestion, analyze_visibility,
+};
 pub use volatility::Volatility;
 pub use volatility::{VolatilityAnalyzer, VolatilityError, VolatilityStats};
-pub use workspace::{CrateInfo, WorkspaceError, WorkspaceInfo};
+pub use workspace::{CrateInfo, CrateKind, WorkspaceError, WorkspaceInfo};
@@ -0,0 +1,88 @@
+//! Log level for messages on stderr (`--quiet`, `--log-level`).
+//!
+//! Reports go to stdout. Progress lines, notes and warnings go to stderr
+//! through `log_warn!`, `log_info!` and `log_debug!`, so a caller piping the
+//! report into a parser can silence them. Errors that stop a run are always
+//! printed. The level is process-wide, like the rayon thread pool, and
+//! defaults to `Info`.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much is written to stderr; each level includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Only errors that stop the run
+    Error,
+    /// Results that may be incomplete or wrong
+    Warn,
+    /// Progress and notes
+    Info,
+    /// Diagnostics for troubleshooting (`--verbose`)
+    Debug,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+/// Set the process-wide log level.
+pub fn set_log_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// The process-wide log level.
+pub fn log_level() -> LogLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => LogLevel::Error,
+        1 => LogLevel::Warn,
+        2 => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+/// Whether messages at `level` are printed.
+pub fn log_enabled(level: LogLevel) -> bool {
+    level <= log_level()
+}
+
+/// Print a line to stderr unless the log level is below `Warn`.
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        if $crate::logging::log_enabled($crate::logging::LogLevel::Warn) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Print a line to stderr unless the log level is below `Info`.
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        if $crate::logging::log_enabled($crate::logging::LogLevel::Info) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Print a line to stderr only at the `Debug` log level.
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        if $crate::logging::log_enabled($crate::logging::LogLevel::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_include_the_less_verbose_ones() {
+        assert!(LogLevel::Error < LogLevel::Warn);
+        assert!(LogLevel::Info < LogLevel::Debug);
+        assert_eq!(log_level(), LogLevel::Info);
+        assert!(log_enabled(LogLevel::Warn));
+        assert!(!log_enabled(LogLevel::Debug));
+    }
+}
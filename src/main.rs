@@ -7,27 +7,46 @@
 //!   cargo coupling [OPTIONS] [PATH]
 //!   cargo-coupling [OPTIONS] [PATH]
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufWriter, Write, stdout};
-use std::path::PathBuf;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use clap::{Parser, Subcommand};
 
 use cargo_coupling::{
-    CompiledConfig, IssueThresholds, ManifestContext, Severity, TextReportOptions,
-    VolatilityAnalyzer, analyze_external_dependencies, analyze_history, analyze_ref,
-    analyze_workspace_with_config, build_manifest,
+    ANALYZER_VERSION, Analysis, AnalysisCache, AnalysisManifest, AnalysisOutcome, AnalyzerError,
+    CompiledConfig, ConfigError, CrateKind, DEFAULT_BASELINE_FILE, IssueBaseline, IssueThresholds,
+    IssueType, LogLevel, ManifestContext, MetricsSnapshot, ProjectMetrics, RemoteCheckout,
+    Severity, SnapshotTolerances, SymbolListKind, TextReportOptions, TimingReport, TrendEntry,
+    TrendHistory, analyze_external_dependencies_with_thresholds, analyze_history,
+    analyze_integration_tests_for_roots, analyze_ref, analyze_temporal_coupling_report,
+    analyze_visibility, analyze_workspace_with_config, balance_time, benchmark_profile,
+    build_manifest, build_module_map, build_suggestions, build_symbol_list,
     cli_output::{
-        CheckConfig, generate_baseline_diff_output, generate_check_output,
-        generate_external_dependencies_output, generate_history_output, generate_hotspots_output,
-        generate_impact_output, generate_json_output, generate_json_output_with_diff,
-        generate_ratchet_check_output, parse_grade, parse_severity,
+        CheckConfig, EXIT_ANALYSIS_ERROR, EXIT_CONFIG_ERROR, EXIT_PASSED, build_focus_graph,
+        generate_baseline_diff_output, generate_benchmark_output, generate_check_github_output,
+        generate_check_output, generate_csv_output, generate_dimension_delta_output,
+        generate_dot_output, generate_explain_output, generate_external_dependencies_output,
+        generate_focus_dot_output, generate_focus_json_output, generate_history_output,
+        generate_hotspots_output, generate_impact_output, generate_integration_tests_output,
+        generate_json_output, generate_json_output_with_diff, generate_json_schema_output,
+        generate_jsonl_output, generate_markdown_table_output, generate_prometheus_output,
+        generate_quick_wins_output, generate_ratchet_check_output, generate_revision_diff_output,
+        generate_sarif_output, generate_snapshot_check_output, generate_summary_json_output,
+        generate_temporal_coupling_output, generate_trend_output, generate_visibility_audit_output,
+        generate_worst_couplings_output, parse_grade, parse_severity,
     },
-    diff_ref_analysis, generate_ai_output_with_thresholds, generate_report_with_options,
-    generate_summary_with_options, load_compiled_config, load_lock_versions_near,
-    web::{DEFAULT_HISTORY_MAX_POINTS, ServerConfig, start_server},
+    comparability_warnings, diff_ref_analysis, generate_ai_output_with_thresholds,
+    generate_module_map_json, generate_module_map_text, generate_report_with_options,
+    generate_suggestions_json, generate_suggestions_text, generate_summary_with_options,
+    generate_symbol_list_json, generate_symbol_list_text, is_remote_source,
+    load_compiled_config_for, load_lock_versions_near, log_debug, log_enabled, log_info, log_warn,
+    reference_profiles, set_log_level,
+    web::{DEFAULT_HISTORY_MAX_POINTS, ServerConfig, generate_html_report, start_server},
 };
 
 /// cargo-coupling - Measure the "right distance" in your Rust code
@@ -48,9 +67,22 @@ enum Commands {
 
 #[derive(Parser, Debug)]
 struct Args {
-    /// Path to the project or directory to analyze
+    #[command(subcommand)]
+    command: Option<CouplingCommand>,
+
+    /// Paths to the projects or directories to analyze, or a git URL to clone.
+    /// Several directories are analyzed as one project; the first one locates the
+    /// config file, git history and Cargo.lock.
     #[arg(default_value = "./src")]
-    path: PathBuf,
+    paths: Vec<PathBuf>,
+
+    /// Git revision (branch, tag, or commit) to check out for a remote source
+    #[arg(long, value_name = "REV")]
+    rev: Option<String>,
+
+    /// Keep the temporary clone of a remote source after analysis
+    #[arg(long)]
+    keep: bool,
 
     /// Output file for the report (default: stdout)
     #[arg(short, long)]
@@ -68,15 +100,57 @@ struct Args {
     #[arg(long, default_value = "6")]
     git_months: usize,
 
+    /// Read at most N of the newest commits from git history
+    #[arg(long, value_name = "N")]
+    git_max_commits: Option<usize>,
+
+    /// Stop git history analysis after SECS seconds (0 = no limit)
+    #[arg(long, value_name = "SECS", default_value = "300")]
+    git_timeout: u64,
+
+    /// Only read git history under DIR, relative to the analyzed path (overrides `[volatility] scope`)
+    #[arg(long, value_name = "DIR")]
+    git_scope: Option<PathBuf>,
+
     /// Skip git history analysis
     #[arg(long)]
     no_git: bool,
 
-    /// Exclude test code from analysis (#[test], #[cfg(test)], mod tests)
-    #[arg(long)]
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
     exclude_tests: bool,
 
-    /// Config file path (default: search for .coupling.toml)
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+
+    /// Count repeated couplings (same source, target, strength, distance) once, with an occurrence count
+    #[arg(long)]
+    merge_couplings: bool,
+
+    /// Parse every file instead of reusing unchanged results from .coupling-cache/
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Only analyze files matching this glob, relative to the workspace root or the
+    /// analyzed directory (repeatable)
+    #[arg(long, visible_alias = "only", value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Skip files matching this glob, e.g. 'src/bin/**' (repeatable, same root as --include)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Also analyze `tests/` integration tests, reported separately from the grade
+    #[arg(long)]
+    include_integration_tests: bool,
+
+    /// Workspace crate kinds left out of the grade: proc-macro, build-dependency, or none
+    /// (overrides `[analysis] exclude_crate_kinds`, default: proc-macro)
+    #[arg(long, value_name = "KINDS", value_delimiter = ',')]
+    exclude_crate_kinds: Option<Vec<String>>,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
     #[arg(short, long)]
     config: Option<PathBuf>,
 
@@ -84,10 +158,26 @@ struct Args {
     #[arg(short, long)]
     verbose: bool,
 
+    /// Print nothing on stderr but errors, even with --verbose; the report on stdout is unchanged
+    #[arg(short, long, global = true, conflicts_with = "log_level")]
+    quiet: bool,
+
+    /// Messages printed on stderr (default: info, or debug with --verbose)
+    #[arg(long, value_name = "LEVEL", global = true)]
+    log_level: Option<LogLevelArg>,
+
     /// Show timing information
     #[arg(long)]
     timing: bool,
 
+    /// Write a per-phase timing breakdown as JSON to FILE
+    #[arg(long, value_name = "FILE")]
+    timing_json: Option<PathBuf>,
+
+    /// Report approximate memory use of the analysis data structures
+    #[arg(long)]
+    profile_memory: bool,
+
     /// Number of threads for parallel processing (default: all CPU cores)
     #[arg(long, short = 'j', value_name = "N")]
     jobs: Option<usize>,
@@ -101,6 +191,18 @@ struct Args {
     #[arg(long)]
     max_dependents: Option<usize>,
 
+    /// Max public interface / implementation size ratio before flagging as Shallow Module (default: 0.5)
+    #[arg(long, value_name = "RATIO")]
+    max_interface_ratio: Option<f64>,
+
+    /// Min public interface size before a module is checked for Shallow Module (default: 8)
+    #[arg(long, value_name = "N")]
+    min_shallow_interface: Option<usize>,
+
+    /// Weighted cognitive load before flagging as High Cognitive Load (default: 60)
+    #[arg(long, value_name = "LOAD")]
+    max_cognitive_load: Option<f64>,
+
     // === Web visualization options ===
     /// Start web server for interactive visualization
     #[arg(long)]
@@ -110,10 +212,22 @@ struct Args {
     #[arg(long, default_value = "3000")]
     port: u16,
 
+    /// Address for the web server to bind (use 0.0.0.0 for remote access)
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+    bind: IpAddr,
+
+    /// Require this token on web API requests (Bearer header, ?token=, or cookie)
+    #[arg(long, value_name = "TOKEN")]
+    token: Option<String>,
+
     /// Don't open browser automatically when starting web server
     #[arg(long)]
     no_open: bool,
 
+    /// Directory of saved --json exports to show as a trend timeline in the web UI
+    #[arg(long, value_name = "DIR", requires = "web")]
+    snapshots: Option<PathBuf>,
+
     /// API endpoint URL for frontend (useful for separate deployments)
     #[arg(long)]
     api_endpoint: Option<String>,
@@ -123,10 +237,30 @@ struct Args {
     #[arg(long, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "5")]
     hotspots: Option<usize>,
 
+    /// Show the N issues with the most impact per unit of effort (default: 3). Use --quick-wins or --quick-wins=N
+    #[arg(long, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "3")]
+    quick_wins: Option<usize>,
+
+    /// Show the N worst-scored individual couplings (default: 10). Use --worst or --worst=N
+    #[arg(long, value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "10")]
+    worst: Option<usize>,
+
     /// Show third-party crate coupling breadth and scattered usage risks
     #[arg(long)]
     deps: bool,
 
+    /// Show files that change together in git history, split by static coupling
+    #[arg(long)]
+    temporal_coupling: bool,
+
+    /// Suggest narrower visibility for `pub` items only used inside the crate
+    #[arg(long)]
+    visibility_audit: bool,
+
+    /// Compare the coupling profile with embedded reference crates (optionally one by name)
+    #[arg(long, value_name = "CRATE", num_args = 0..=1, require_equals = true)]
+    compare_to_crate: Option<Option<String>>,
+
     /// Analyze change impact for a specific module
     #[arg(long, value_name = "MODULE")]
     impact: Option<String>,
@@ -143,6 +277,31 @@ struct Args {
     #[arg(long, value_name = "GIT_REF")]
     baseline: Option<String>,
 
+    /// With --check, ignore the issues and circular dependencies accepted in FILE (see --write-baseline)
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "check",
+        conflicts_with = "baseline"
+    )]
+    baseline_file: Option<PathBuf>,
+
+    /// Write fingerprints of the current issues to FILE (default: coupling-baseline.json)
+    #[arg(long, value_name = "FILE", num_args = 0..=1, require_equals = true, default_missing_value = DEFAULT_BASELINE_FILE)]
+    write_baseline: Option<PathBuf>,
+
+    /// Append this run's grade, score, issue counts, cycle count and module count to FILE
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// With --record, keep only the newest N runs in the file
+    #[arg(long, value_name = "N", requires = "record")]
+    max_history: Option<usize>,
+
+    /// With --baseline, show only how the strength/distance/volatility distribution shifted
+    #[arg(long, requires = "baseline", conflicts_with = "check")]
+    dimension_delta: bool,
+
     /// Run quality gate check (returns non-zero exit code on failure)
     #[arg(long)]
     check: bool,
@@ -163,43 +322,759 @@ struct Args {
     #[arg(long, value_name = "SEVERITY", requires = "check")]
     fail_on: Option<String>,
 
-    /// Output in JSON format (machine-readable)
-    #[arg(long)]
-    json: bool,
+    /// Output in JSON format (machine-readable)
+    #[arg(long)]
+    json: bool,
+
+    /// Print the JSON Schema of `--json` output and exit
+    #[arg(long)]
+    schema: bool,
+
+    /// Output format; `json` is the same as --json, `summary-json` prints only the headline numbers,
+    /// `sarif` prints SARIF 2.1.0 for GitHub code scanning, `dot` prints the module graph for Graphviz,
+    /// `jsonl` prints one JSON object per line (summary, then issues, then modules),
+    /// `github` prints GitHub Actions annotations for the blocking issues of --check,
+    /// `html` prints a standalone report page that opens offline (no server),
+    /// `csv` prints one row per coupling with its scores, for spreadsheets,
+    /// `prometheus` prints gauges in the Prometheus text format, for dashboards,
+    /// `markdown-table` prints only a Markdown table of the issues, for PRs and tickets
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+
+    /// Show all issues including Low severity (default: only Medium/High/Critical)
+    #[arg(long)]
+    all: bool,
+
+    /// Show the full structural blind-spot list in text output
+    #[arg(long)]
+    blind_spots: bool,
+
+    /// Show explanations in Japanese (日本語で解説を表示)
+    #[arg(long, visible_alias = "jp")]
+    japanese: bool,
+}
+
+impl Args {
+    /// First analyzed path: where the config file and analysis cache are looked up
+    fn path(&self) -> &PathBuf {
+        &self.paths[0]
+    }
+
+    /// Stderr log level: `--log-level`, else `--quiet` or `--verbose`
+    fn log_level(&self) -> LogLevel {
+        match (self.log_level, self.quiet, self.verbose) {
+            (Some(level), _, _) => level.into(),
+            (None, true, _) => LogLevel::Error,
+            (None, false, true) => LogLevel::Debug,
+            (None, false, false) => LogLevel::Info,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum CouplingCommand {
+    /// Print a map of modules to their source files and item declaration lines
+    Map(MapArgs),
+    /// List canonical module, type, or function names with their source files
+    List(ListArgs),
+    /// Export the dependency neighborhood of one module as DOT or JSON
+    Graph(GraphArgs),
+    /// Compare coupling health between two git revisions
+    Diff(DiffArgs),
+    /// Store the headline metrics of the current tree, or check it against them
+    Snapshot(SnapshotArgs),
+    /// Explain what an issue type means and how to fix it
+    Explain(ExplainArgs),
+    /// Show grade, score and issue counts over the runs stored with --record
+    Trend(TrendArgs),
+    /// Print starter code for getter, newtype and trait refactorings
+    Suggest(SuggestArgs),
+}
+
+#[derive(Parser, Debug)]
+struct MapArgs {
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "json")]
+    format: MapFormat,
+
+    /// Output file for the map (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ListArgs {
+    /// What to list
+    #[arg(value_enum)]
+    kind: ListKind,
+
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Only list names containing this substring (case-insensitive)
+    #[arg(long, value_name = "SUBSTR")]
+    grep: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: MapFormat,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct GraphArgs {
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Module to center the graph on (full path or suffix, as with --impact)
+    #[arg(long, value_name = "MODULE")]
+    focus: String,
+
+    /// Hops to follow out from the focus module, in both directions
+    #[arg(long, value_name = "N", default_value = "2")]
+    depth: usize,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "dot")]
+    format: GraphFormat,
+
+    /// Output file for the graph (default: stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct DiffArgs {
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Git ref to compare against (e.g. main)
+    #[arg(long, value_name = "REF")]
+    base: String,
+
+    /// Git ref to compare (default: HEAD)
+    #[arg(long, value_name = "REF", default_value = "HEAD")]
+    head: String,
+
+    /// Exit 1 if the average score drops by more than this (e.g. 0.05)
+    #[arg(long, value_name = "DELTA")]
+    max_regression: Option<f64>,
+
+    /// Output in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Analyze git history for volatility (months to look back)
+    #[arg(long, default_value = "6")]
+    git_months: usize,
+
+    /// Skip git history analysis
+    #[arg(long)]
+    no_git: bool,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct ExplainArgs {
+    /// Issue type, as a display name ("God Module") or kebab case (god-module)
+    issue_type: String,
+}
+
+#[derive(Parser, Debug)]
+struct TrendArgs {
+    /// History file written by --record
+    file: PathBuf,
+
+    /// Output in JSON format
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Parser, Debug)]
+struct SuggestArgs {
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Output in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct SnapshotArgs {
+    #[command(subcommand)]
+    command: SnapshotCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum SnapshotCommand {
+    /// Write grade, average score, issue counts and cycle count to a file
+    Save(SnapshotSaveArgs),
+    /// Fail if the current tree regressed against a stored snapshot
+    Check(SnapshotCheckArgs),
+}
+
+#[derive(Parser, Debug)]
+struct SnapshotSaveArgs {
+    /// Snapshot file to write
+    file: PathBuf,
+
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Analyze git history for volatility (months to look back)
+    #[arg(long, default_value = "6")]
+    git_months: usize,
+
+    /// Skip git history analysis
+    #[arg(long)]
+    no_git: bool,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+struct SnapshotCheckArgs {
+    /// Snapshot file written by `coupling snapshot save`
+    file: PathBuf,
+
+    /// Path to the project or directory to analyze
+    #[arg(default_value = "./src")]
+    path: PathBuf,
+
+    /// Allowed drop of the average score (e.g. 0.02)
+    #[arg(long, value_name = "DELTA", default_value = "0")]
+    score_tolerance: f64,
+
+    /// Allowed increase of the issue count, per severity
+    #[arg(long, value_name = "N", default_value = "0")]
+    issue_tolerance: usize,
+
+    /// Allowed increase of the circular dependency count
+    #[arg(long, value_name = "N", default_value = "0")]
+    cycle_tolerance: usize,
+
+    /// Output in JSON format
+    #[arg(long)]
+    json: bool,
+
+    /// Config file, or a directory containing .coupling.toml (default: search upward from the path)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Analyze git history for volatility (months to look back)
+    #[arg(long, default_value = "6")]
+    git_months: usize,
+
+    /// Skip git history analysis
+    #[arg(long)]
+    no_git: bool,
+
+    /// Exclude test code from analysis (#[test], #[tokio::test], #[cfg(test)] modules); the default
+    #[arg(long, visible_alias = "skip-tests")]
+    exclude_tests: bool,
+
+    /// Analyze test code too, overriding [analysis] exclude_tests
+    #[arg(long, conflicts_with = "exclude_tests")]
+    include_tests: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ListKind {
+    Modules,
+    Types,
+    Functions,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    SummaryJson,
+    Sarif,
+    Dot,
+    Jsonl,
+    Github,
+    Html,
+    Csv,
+    Prometheus,
+    MarkdownTable,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum LogLevelArg {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl From<LogLevelArg> for LogLevel {
+    fn from(level: LogLevelArg) -> Self {
+        match level {
+            LogLevelArg::Error => LogLevel::Error,
+            LogLevelArg::Warn => LogLevel::Warn,
+            LogLevelArg::Info => LogLevel::Info,
+            LogLevelArg::Debug => LogLevel::Debug,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum GraphFormat {
+    Dot,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum MapFormat {
+    Json,
+    Text,
+}
+
+fn main() {
+    match run() {
+        Ok(exit_code) => process::exit(exit_code),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(error_exit_code(e.as_ref()));
+        }
+    }
+}
+
+/// Exit code for an error that stopped the run, kept apart from a failed gate (1):
+/// invalid configuration or arguments are 3, anything else failed the analysis (2).
+fn error_exit_code(error: &(dyn std::error::Error + 'static)) -> i32 {
+    let is_config_error = error.is::<ConfigError>()
+        || matches!(
+            error.downcast_ref::<AnalyzerError>(),
+            Some(AnalyzerError::ConfigError(_))
+        )
+        || error.is::<CliInputError>();
+    if is_config_error {
+        EXIT_CONFIG_ERROR
+    } else {
+        EXIT_ANALYSIS_ERROR
+    }
+}
+
+fn run() -> Result<i32, Box<dyn std::error::Error>> {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        // --help and --version print to stdout and succeed; usage errors are config errors
+        Err(e) => {
+            e.print()?;
+            return Ok(if e.use_stderr() {
+                EXIT_CONFIG_ERROR
+            } else {
+                EXIT_PASSED
+            });
+        }
+    };
+
+    let Commands::Coupling(mut args) = cli.command;
+    set_log_level(args.log_level());
+
+    match args.command.take() {
+        Some(CouplingCommand::Map(map_args)) => run_map(map_args),
+        Some(CouplingCommand::List(list_args)) => run_list(list_args),
+        Some(CouplingCommand::Graph(graph_args)) => run_graph(graph_args),
+        Some(CouplingCommand::Diff(diff_args)) => run_diff(diff_args),
+        Some(CouplingCommand::Snapshot(snapshot_args)) => match snapshot_args.command {
+            SnapshotCommand::Save(save_args) => run_snapshot_save(save_args),
+            SnapshotCommand::Check(check_args) => run_snapshot_check(check_args),
+        },
+        Some(CouplingCommand::Explain(explain_args)) => run_explain(explain_args),
+        Some(CouplingCommand::Trend(trend_args)) => run_trend(trend_args),
+        Some(CouplingCommand::Suggest(suggest_args)) => run_suggest(suggest_args),
+        None if args.schema => run_schema(),
+        None => run_coupling(args),
+    }
+}
+
+fn run_map(args: MapArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let mut config = load_compiled_config_for(args.config.as_deref(), &args.path)?;
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+
+    let metrics = analyze_workspace_with_config(&args.path, &config)?;
+    let map = build_module_map(&metrics);
+
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(stdout()),
+    };
+
+    match args.format {
+        MapFormat::Json => generate_module_map_json(&map, &mut writer)?,
+        MapFormat::Text => generate_module_map_text(&map, &mut writer)?,
+    }
+    writer.flush()?;
+
+    Ok(EXIT_PASSED)
+}
+
+fn run_list(args: ListArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let mut config = load_compiled_config_for(args.config.as_deref(), &args.path)?;
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+
+    let metrics = analyze_workspace_with_config(&args.path, &config)?;
+    let kind = match args.kind {
+        ListKind::Modules => SymbolListKind::Modules,
+        ListKind::Types => SymbolListKind::Types,
+        ListKind::Functions => SymbolListKind::Functions,
+    };
+    let entries = build_symbol_list(&metrics, kind, args.grep.as_deref());
+
+    let mut writer = stdout();
+    match args.format {
+        MapFormat::Json => generate_symbol_list_json(&entries, &mut writer)?,
+        MapFormat::Text => generate_symbol_list_text(&entries, &mut writer)?,
+    }
+    writer.flush()?;
+
+    Ok(EXIT_PASSED)
+}
+
+fn run_graph(args: GraphArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let mut config = load_compiled_config_for(args.config.as_deref(), &args.path)?;
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+
+    let metrics = analyze_workspace_with_config(&args.path, &config)?;
+    let Some(graph) = build_focus_graph(
+        &metrics,
+        &args.focus,
+        args.depth,
+        &config.issue_thresholds().balance_formula,
+    ) else {
+        return Err(
+            invalid_cli_input(format!("--focus: module '{}' not found", args.focus)).into(),
+        );
+    };
+
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(stdout()),
+    };
+    match args.format {
+        GraphFormat::Dot => generate_focus_dot_output(&graph, &mut writer)?,
+        GraphFormat::Json => generate_focus_json_output(&graph, &mut writer)?,
+    }
+    writer.flush()?;
+
+    Ok(EXIT_PASSED)
+}
+
+fn run_diff(args: DiffArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    if args.max_regression.is_some_and(|max| max < 0.0) {
+        return Err(invalid_cli_input("--max-regression must not be negative").into());
+    }
+
+    let mut config = load_compiled_config_for(args.config.as_deref(), &args.path)?;
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+    let thresholds = config.issue_thresholds();
+
+    let analyze = |git_ref: &str| {
+        analyze_ref(
+            &args.path,
+            &config,
+            &thresholds,
+            git_ref,
+            args.git_months,
+            !args.no_git,
+        )
+        .map_err(|e| -> Box<dyn std::error::Error> { Box::new(e) })
+    };
+    let base = analyze(&args.base)?;
+    let head = analyze(&args.head)?;
+    let diff = diff_ref_analysis(
+        &base,
+        &head.metrics,
+        &head.report,
+        &thresholds.balance_formula,
+    );
+
+    let mut writer = stdout();
+    let exit_code = generate_revision_diff_output(
+        &diff,
+        &args.base,
+        &args.head,
+        args.max_regression,
+        args.json,
+        &mut writer,
+    )?;
+    writer.flush()?;
+    Ok(exit_code)
+}
+
+fn run_explain(args: ExplainArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let Some(issue_type) = IssueType::from_rule_name(&args.issue_type) else {
+        let valid: Vec<String> = IssueType::ALL
+            .iter()
+            .map(|issue_type| format!("  {} ({})", issue_type.rule_name(), issue_type))
+            .collect();
+        return Err(invalid_cli_input(format!(
+            "unknown issue type `{}`; valid issue types:\n{}",
+            args.issue_type,
+            valid.join("\n")
+        ))
+        .into());
+    };
+
+    let mut writer = stdout();
+    generate_explain_output(issue_type, &mut writer)?;
+    writer.flush()?;
+    Ok(EXIT_PASSED)
+}
+
+fn run_trend(args: TrendArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    // A missing file is an empty history for --record, but here it is most likely a typo
+    if !args.file.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("trend history file {} not found", args.file.display()),
+        )
+        .into());
+    }
+    let history = TrendHistory::load(&args.file)?;
+
+    let mut writer = stdout();
+    generate_trend_output(&history, args.json, &mut writer)?;
+    writer.flush()?;
+    Ok(EXIT_PASSED)
+}
+
+fn run_suggest(args: SuggestArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let mut config = load_compiled_config_for(args.config.as_deref(), &args.path)?;
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+
+    let metrics = analyze_workspace_with_config(&args.path, &config)?;
+    let suggestions = build_suggestions(&metrics, &config.issue_thresholds());
 
-    /// Show all issues including Low severity (default: only Medium/High/Critical)
-    #[arg(long)]
-    all: bool,
+    let mut writer = stdout();
+    if args.json {
+        generate_suggestions_json(&suggestions, &mut writer)?;
+    } else {
+        generate_suggestions_text(&suggestions, &mut writer)?;
+    }
+    writer.flush()?;
+    Ok(EXIT_PASSED)
+}
 
-    /// Show the full structural blind-spot list in text output
-    #[arg(long)]
-    blind_spots: bool,
+fn run_schema() -> Result<i32, Box<dyn std::error::Error>> {
+    let mut writer = stdout();
+    generate_json_schema_output(&mut writer)?;
+    writer.flush()?;
+    Ok(EXIT_PASSED)
+}
 
-    /// Show explanations in Japanese (日本語で解説を表示)
-    #[arg(long, visible_alias = "jp")]
-    japanese: bool,
+fn run_snapshot_save(args: SnapshotSaveArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    let snapshot = current_snapshot(
+        &args.path,
+        args.config.as_deref(),
+        test_code_override(args.exclude_tests, args.include_tests),
+        (!args.no_git).then_some(args.git_months),
+    )?;
+    snapshot.write(&args.file)?;
+    println!(
+        "Wrote snapshot to {} (grade {}, average score {:.2})",
+        args.file.display(),
+        snapshot.health_grade,
+        snapshot.average_score
+    );
+    Ok(EXIT_PASSED)
 }
 
-fn main() {
-    match run() {
-        Ok(exit_code) => process::exit(exit_code),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            process::exit(1);
-        }
+fn run_snapshot_check(args: SnapshotCheckArgs) -> Result<i32, Box<dyn std::error::Error>> {
+    if args.score_tolerance < 0.0 {
+        return Err(invalid_cli_input("--score-tolerance must not be negative").into());
     }
-}
 
-fn run() -> Result<i32, Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+    let stored = MetricsSnapshot::load(&args.file)?;
+    let current = current_snapshot(
+        &args.path,
+        args.config.as_deref(),
+        test_code_override(args.exclude_tests, args.include_tests),
+        (!args.no_git).then_some(args.git_months),
+    )?;
+    let tolerances = SnapshotTolerances {
+        score: args.score_tolerance,
+        issues: args.issue_tolerance,
+        cycles: args.cycle_tolerance,
+    };
+    let comparison = stored.compare(&current, tolerances);
 
-    let Commands::Coupling(args) = cli.command;
+    let mut writer = stdout();
+    let exit_code =
+        generate_snapshot_check_output(&comparison, &args.file, args.json, &mut writer)?;
+    writer.flush()?;
+    Ok(exit_code)
+}
 
-    run_coupling(args)
+/// Analyze the working tree the way the default command does and keep its headline metrics.
+fn current_snapshot(
+    path: &Path,
+    config_path: Option<&Path>,
+    exclude_tests: Option<bool>,
+    git_months: Option<usize>,
+) -> Result<MetricsSnapshot, Box<dyn std::error::Error>> {
+    let mut analysis = Analysis::builder().path(path);
+    if let Some(exclude) = exclude_tests {
+        analysis = analysis.skip_tests(exclude);
+    }
+    if let Some(config_path) = config_path {
+        analysis = analysis.config_path(config_path);
+    }
+    if let Some(months) = git_months {
+        analysis = analysis.git_months(months);
+    }
+    let (metrics, report) = analysis.build().run_with_report()?;
+    Ok(MetricsSnapshot::from_report(&report, &metrics))
 }
 
-fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
+fn run_coupling(mut args: Args) -> Result<i32, Box<dyn std::error::Error>> {
+    if args.format == Some(OutputFormat::Json) {
+        args.json = true;
+    }
     warn_on_output_mode_conflicts(&args);
+    if args.max_history == Some(0) {
+        return Err(invalid_cli_input("--max-history must be greater than 0").into());
+    }
+    if args.worst == Some(0) {
+        return Err(invalid_cli_input("--worst must be greater than 0").into());
+    }
+
+    if args.paths.len() > 1 {
+        let single_path_only = [
+            (
+                "a git URL",
+                args.paths
+                    .iter()
+                    .any(|path| path.to_str().is_some_and(is_remote_source)),
+            ),
+            ("--history", args.history.is_some()),
+            ("--baseline", args.baseline.is_some()),
+            ("--web", args.web),
+        ];
+        if let Some((option, _)) = single_path_only.iter().find(|(_, used)| *used) {
+            return Err(invalid_cli_input(format!(
+                "{} takes a single path; got {} paths",
+                option,
+                args.paths.len()
+            ))
+            .into());
+        }
+    }
+
+    // Remote source: clone into a temp dir that lives until analysis finishes
+    let remote_url = args
+        .path()
+        .to_str()
+        .filter(|path| is_remote_source(path))
+        .map(str::to_string);
+    let _checkout = match remote_url {
+        Some(url) => {
+            log_info!("Cloning {}...", url);
+            let checkout = RemoteCheckout::fetch(&url, args.rev.as_deref(), args.keep)?;
+            if args.keep {
+                log_info!("Keeping clone at: {}", checkout.path().display());
+            }
+            args.paths = vec![checkout.path().to_path_buf()];
+            Some(checkout)
+        }
+        None => {
+            if args.rev.is_some() || args.keep {
+                log_warn!("Warning: --rev and --keep only apply to remote git sources; ignoring.");
+            }
+            None
+        }
+    };
 
     // Detect available CPU cores
     let available_cores = std::thread::available_parallelism()
@@ -212,10 +1087,10 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
         rayon::ThreadPoolBuilder::new()
             .num_threads(num_threads)
             .build_global()
-            .unwrap_or_else(|e| eprintln!("Warning: Could not set thread count: {}", e));
+            .unwrap_or_else(|e| log_warn!("Warning: Could not set thread count: {}", e));
     }
 
-    if args.verbose || args.timing {
+    if args.timing || log_enabled(LogLevel::Debug) {
         eprintln!(
             "Using {} thread(s) for parallel processing ({} CPU cores available)",
             num_threads, available_cores
@@ -225,80 +1100,133 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
     let total_start = Instant::now();
 
     // Load configuration file
-    let config_path = args.config.as_ref().unwrap_or(&args.path);
-    let mut config = match load_compiled_config(config_path) {
-        Ok(config) => {
-            if args.verbose && config.has_volatility_overrides() {
-                eprintln!("Loaded configuration from .coupling.toml");
-            }
-            config
-        }
-        Err(e) => {
-            if args.verbose {
-                eprintln!("Note: No config file loaded: {}", e);
-            }
-            CompiledConfig::empty()
-        }
-    };
+    let mut config = load_compiled_config_for(args.config.as_deref(), args.path())?;
 
     // Apply CLI flags to config (CLI takes precedence over config file)
-    if args.exclude_tests {
-        config.set_exclude_tests(true);
+    if let Some(exclude) = test_code_override(args.exclude_tests, args.include_tests) {
+        config.set_exclude_tests(exclude);
+    }
+    if args.merge_couplings {
+        config.set_merge_couplings(true);
+    }
+    config.git_max_commits = args.git_max_commits;
+    config.git_timeout = (args.git_timeout > 0).then(|| Duration::from_secs(args.git_timeout));
+    if args.git_scope.is_some() {
+        config.git_scope = args.git_scope.clone();
     }
 
-    if args.verbose && config.exclude_tests {
-        eprintln!("Test code will be excluded from analysis");
+    // Reuse per-file results for unchanged files; a throwaway clone gains nothing from it
+    if !args.no_cache && _checkout.is_none() {
+        config.analysis_cache = Some(AnalysisCache::for_project(
+            args.path(),
+            config.config_root(),
+        ));
     }
 
-    if args.verbose && config.prelude_module_count() > 0 {
-        eprintln!(
+    config
+        .set_path_filters(&args.include, &args.exclude)
+        .map_err(|e| invalid_cli_input(format!("--include/--exclude: {}", e)))?;
+
+    if let Some(kinds) = &args.exclude_crate_kinds {
+        config.exclude_crate_kinds = kinds
+            .iter()
+            .filter(|kind| kind.as_str() != "none")
+            .map(|kind| kind.parse::<CrateKind>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| invalid_cli_input(format!("--exclude-crate-kinds: {}", e)))?;
+    }
+
+    if args.verbose {
+        config.set_record_provenance(true);
+    }
+
+    if config.exclude_tests {
+        log_debug!("Test code will be excluded from analysis");
+    }
+
+    if config.prelude_module_count() > 0 {
+        log_debug!(
             "Prelude modules configured: {} pattern(s)",
             config.prelude_module_count()
         );
     }
 
+    if let Some(load) = args.max_cognitive_load
+        && (!load.is_finite() || load < 0.0)
+    {
+        return Err(invalid_cli_input(format!(
+            "--max-cognitive-load must be a non-negative number, got {}",
+            load
+        ))
+        .into());
+    }
+
     // Create custom thresholds - CLI args override config, which overrides defaults.
     // Computed early so both the history timeline and the snapshot analysis share them.
+    let config_thresholds = config.issue_thresholds();
     let thresholds = IssueThresholds {
-        max_dependencies: args.max_deps.unwrap_or(config.thresholds.max_dependencies),
+        max_dependencies: args.max_deps.unwrap_or(config_thresholds.max_dependencies),
         max_dependents: args
             .max_dependents
-            .unwrap_or(config.thresholds.max_dependents),
+            .unwrap_or(config_thresholds.max_dependents),
+        max_interface_ratio: args
+            .max_interface_ratio
+            .unwrap_or(config_thresholds.max_interface_ratio),
+        min_shallow_interface: args
+            .min_shallow_interface
+            .unwrap_or(config_thresholds.min_shallow_interface),
+        max_cognitive_load: args
+            .max_cognitive_load
+            .unwrap_or(config_thresholds.max_cognitive_load),
         strict_mode: !args.all, // Default is strict (hide Low), --all shows everything
         japanese: args.japanese,
-        exclude_tests: config.exclude_tests,
-        prelude_module_count: config.prelude_module_count(),
-        ..IssueThresholds::default()
+        ..config_thresholds
     };
 
-    if args.verbose {
-        eprintln!(
-            "Thresholds: max_deps={}, max_dependents={}",
-            thresholds.max_dependencies, thresholds.max_dependents
+    log_debug!(
+        "Thresholds: max_deps={}, max_dependents={}",
+        thresholds.max_dependencies,
+        thresholds.max_dependents
+    );
+
+    if args.format == Some(OutputFormat::Github) && !args.check {
+        return Err(
+            invalid_cli_input("--format github annotates --check results; add --check").into(),
         );
     }
 
+    if args.temporal_coupling && args.no_git {
+        return Err(invalid_cli_input(
+            "--temporal-coupling requires git history and cannot be combined with --no-git",
+        )
+        .into());
+    }
+
     // --history: time-series coupling health across git revisions. Independent of
     // the snapshot analysis below, so handle it here and return early.
     if let Some(max_points) = args.history {
         if args.baseline.is_some() {
             return Err(invalid_cli_input("--baseline cannot be combined with --history").into());
         }
+        if args.record.is_some() {
+            return Err(invalid_cli_input("--record cannot be combined with --history").into());
+        }
         if max_points == 0 {
             return Err(invalid_cli_input("--history must be greater than 0").into());
         }
         if args.no_git {
-            eprintln!(
+            log_warn!(
                 "Warning: --history requires git history; ignoring --no-git for history analysis."
             );
         }
-        eprintln!(
+        log_info!(
             "Analyzing coupling history ({} months, up to {} samples)...",
-            args.git_months, max_points
+            args.git_months,
+            max_points
         );
 
         let report = analyze_history(
-            &args.path,
+            args.path(),
             &config,
             &thresholds,
             args.git_months,
@@ -310,81 +1238,63 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
             Some(path) => Box::new(BufWriter::new(File::create(path)?)),
             None => Box::new(stdout()),
         };
+        let balance_before = balance_time();
+        let report_start = Instant::now();
         generate_history_output(&report, args.json, max_points, &mut writer)?;
-        return Ok(0);
+        if let Some(path) = &args.timing_json {
+            let report_time = rendering_time(report_start, balance_before);
+            write_timing_json(
+                path,
+                None,
+                num_threads,
+                Duration::ZERO,
+                report_time,
+                total_start,
+            )?;
+        }
+        return Ok(EXIT_PASSED);
     }
 
     // Print analysis header
-    eprintln!("Analyzing project at '{}'...", args.path.display());
-
-    // Analyze the project (uses cargo metadata for better accuracy)
-    let analysis_start = Instant::now();
-    let mut metrics = analyze_workspace_with_config(&args.path, &config)?;
-    let analysis_time = analysis_start.elapsed();
-
-    // Analyze git history for volatility (if not disabled)
-    let mut git_used = false;
+    let roots: Vec<String> = args
+        .paths
+        .iter()
+        .map(|path| format!("'{}'", path.display()))
+        .collect();
+    log_info!("Analyzing project at {}...", roots.join(", "));
+
+    // Analyze the project (uses cargo metadata for better accuracy), then read git history
+    let mut analysis = Analysis::builder()
+        .config(config.clone())
+        .thresholds(thresholds.clone());
+    for path in &args.paths {
+        analysis = analysis.path(path);
+    }
     if !args.no_git {
-        if args.verbose {
-            eprintln!("Analyzing git history ({} months)...", args.git_months);
-        }
-
-        let mut volatility = VolatilityAnalyzer::new(args.git_months);
-        match volatility.analyze(&args.path) {
-            Ok(()) => {
-                git_used = true;
-                if args.verbose {
-                    let stats = volatility.statistics();
-                    eprintln!(
-                        "Git analysis: {} files, {} total changes",
-                        stats.total_files, stats.total_changes
-                    );
-                }
-
-                // Analyze temporal coupling (co-change patterns)
-                match volatility.analyze_temporal_coupling(&args.path) {
-                    Ok(temporal) => {
-                        if args.verbose && !temporal.is_empty() {
-                            eprintln!(
-                                "Temporal coupling: {} co-changing file pairs detected",
-                                temporal.len()
-                            );
-                        }
-                        metrics.temporal_couplings = temporal;
-                    }
-                    Err(e) => {
-                        if args.verbose {
-                            eprintln!("Warning: Temporal coupling analysis failed: {}", e);
-                        }
-                    }
-                }
-
-                // Copy file changes to project metrics (must be after statistics())
-                metrics.file_changes = volatility.file_changes;
-
-                // Update volatility for all couplings based on git history
-                metrics.update_volatility_from_git();
-            }
-            Err(e) => {
-                if args.verbose {
-                    eprintln!("Warning: Git analysis failed: {}", e);
-                }
-            }
-        }
+        analysis = analysis.git_months(args.git_months);
     }
-
-    // Apply volatility overrides from config (includes subdomain classification)
-    if config.has_subdomain_config() && args.verbose {
-        eprintln!("DDD subdomain classification configured (affects volatility)");
+    let (metrics, outcome) = analysis.build().run_detailed()?;
+    let AnalysisOutcome {
+        analysis_time,
+        git_time,
+        git_used,
+    } = outcome;
+    if let Some(cache) = &config.analysis_cache {
+        cache.prune();
     }
-    if config.has_volatility_overrides() || config.has_subdomain_config() {
-        let override_count = metrics.apply_config_volatility_overrides(&mut config);
-        if args.verbose && override_count > 0 {
-            eprintln!(
-                "Applied {} volatility overrides from config",
-                override_count
-            );
-        }
+    if log_enabled(LogLevel::Debug) {
+        print_dropped_dependency_paths(&metrics);
+    }
+
+    if args.profile_memory {
+        let profile = metrics.memory_profile();
+        eprintln!(
+            "Memory: {} modules, {} couplings, {} item dependencies (~{:.1} MiB)",
+            profile.modules,
+            profile.couplings,
+            profile.item_dependencies,
+            profile.estimated_bytes as f64 / (1024.0 * 1024.0)
+        );
     }
 
     if args.timing {
@@ -395,43 +1305,178 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
             analysis_time
         );
     } else {
-        eprintln!(
+        log_info!(
             "Analysis complete: {} files, {} modules\n",
             metrics.total_files,
             metrics.module_count()
         );
     }
+    if metrics.total_files == 0 && log_enabled(LogLevel::Warn) {
+        warn_no_source_files(&metrics);
+    }
+
+    // --record: append this run's headline numbers to the trend history
+    if let Some(path) = &args.record {
+        let report = cargo_coupling::analyze_project_balance_with_thresholds(&metrics, &thresholds);
+        let stored = TrendHistory::record(
+            path,
+            TrendEntry::from_report(&report, &metrics),
+            args.max_history,
+        )?;
+        log_info!(
+            "Recorded run in {} ({} run(s) stored)",
+            path.display(),
+            stored
+        );
+    }
 
     let manifest = build_manifest(&ManifestContext {
         git_used,
         tests_excluded: config.exclude_tests,
         parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
         skipped_crates: metrics.skipped_crates.clone(),
         boundary_skipped_files: metrics.boundary_skipped_files,
         dead_config_patterns: metrics.dead_config_patterns.clone(),
+        edition_parse_failures: metrics.edition_parse_failures.clone(),
+        orphaned_modules: metrics.orphaned_modules.clone(),
     });
 
     // Web visualization mode
     if args.web {
+        if !args.bind.is_loopback() && args.token.is_none() {
+            log_warn!(
+                "Warning: binding to {} without --token exposes the analysis API to the network.",
+                args.bind
+            );
+        }
         let server_config = ServerConfig {
+            bind_addr: args.bind,
             port: args.port,
             open_browser: !args.no_open,
             api_endpoint: args.api_endpoint.clone(),
-            analysis_path: args.path.clone(),
+            analysis_path: args.path().clone(),
             analysis_config: config,
             git_months: args.git_months,
             history_max_points: DEFAULT_HISTORY_MAX_POINTS,
             no_git: args.no_git,
+            auth_token: args.token.clone(),
+            snapshots_dir: args.snapshots.clone(),
         };
 
+        // The server runs until interrupted, so the timings cover the analysis only
+        if let Some(path) = &args.timing_json {
+            write_timing_json(
+                path,
+                Some(&metrics),
+                num_threads,
+                git_time,
+                Duration::ZERO,
+                total_start,
+            )?;
+        }
+
         // Run the web server using tokio runtime
         let rt = tokio::runtime::Runtime::new()?;
         rt.block_on(start_server(metrics, thresholds, server_config))
             .map_err(|e| -> Box<dyn std::error::Error> { e })?;
 
-        return Ok(0);
+        return Ok(EXIT_PASSED);
+    }
+
+    let balance_before = balance_time();
+    let report_start = Instant::now();
+    let exit_code = write_output(&args, &metrics, &config, &thresholds, &manifest)?;
+    let report_time = rendering_time(report_start, balance_before);
+
+    // Show total timing
+    let total_time = total_start.elapsed();
+    if args.timing {
+        let files_per_sec = metrics.total_files as f64 / total_time.as_secs_f64();
+        eprintln!(
+            "Total time: {:.2?} ({:.1} files/sec)",
+            total_time, files_per_sec
+        );
+    }
+
+    if let Some(path) = &args.timing_json {
+        write_timing_json(
+            path,
+            Some(&metrics),
+            num_threads,
+            git_time,
+            report_time,
+            total_start,
+        )?;
+    }
+
+    Ok(exit_code)
+}
+
+/// Time since `start` minus the balance passes run since `balance_before`, so
+/// `--timing-json` reports rendering and balance scoring apart.
+fn rendering_time(start: Instant, balance_before: Duration) -> Duration {
+    let elapsed = start.elapsed();
+    elapsed.saturating_sub(balance_time().saturating_sub(balance_before))
+}
+
+/// Write the `--timing-json` report. `metrics` is `None` when the run analyzed
+/// git history instead of the working tree (`--history`).
+fn write_timing_json(
+    path: &Path,
+    metrics: Option<&ProjectMetrics>,
+    threads: usize,
+    git: Duration,
+    report: Duration,
+    total_start: Instant,
+) -> std::io::Result<()> {
+    let mut timing = match metrics {
+        Some(metrics) => {
+            TimingReport::from_analysis(&metrics.analysis_timings, metrics.total_files, threads)
+        }
+        None => TimingReport {
+            threads,
+            ..TimingReport::default()
+        },
+    };
+    timing.phases.git = git;
+    timing.phases.balance = balance_time();
+    timing.phases.report = report;
+    timing.total = total_start.elapsed();
+    timing.write_json(path)
+}
+
+/// Paths dropped as likely local variables, most frequent first, so a
+/// wrongly filtered module name can be spotted and configured away.
+fn print_dropped_dependency_paths(metrics: &ProjectMetrics) {
+    const SHOWN: usize = 20;
+    let dropped = &metrics.dropped_dependency_paths;
+    if dropped.is_empty() {
+        return;
+    }
+    eprintln!(
+        "Dropped {} dependency path(s) as likely local variables \
+         ([analysis] local_variable_names, max_local_name_len):",
+        dropped.len()
+    );
+    let mut paths: Vec<(&String, &usize)> = dropped.iter().collect();
+    paths.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (path, count) in paths.iter().take(SHOWN) {
+        eprintln!("  {} ({}x)", path, count);
     }
+    if paths.len() > SHOWN {
+        eprintln!("  ... and {} more", paths.len() - SHOWN);
+    }
+}
 
+/// Render the selected output mode and return the process exit code.
+fn write_output(
+    args: &Args,
+    metrics: &ProjectMetrics,
+    config: &CompiledConfig,
+    thresholds: &IssueThresholds,
+    manifest: &AnalysisManifest,
+) -> Result<i32, Box<dyn std::error::Error>> {
     // Generate output
     let output: Box<dyn Write> = match &args.output {
         Some(path) => {
@@ -445,97 +1490,245 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
 
     // Job-focused CLI modes (mutually exclusive with other modes)
 
-    // --baseline: compare current issues against a git ref. With --check this
-    // is a ratchet gate that fails only for new issues at the configured severity.
+    // --write-baseline: accept the current issues as pre-existing debt
+    if let Some(path) = &args.write_baseline {
+        let report = cargo_coupling::analyze_project_balance_with_thresholds(metrics, thresholds);
+        let baseline = IssueBaseline::from_issues(&report.issues, metrics.config_hash.clone())
+            .with_circular_dependencies(&metrics.detect_circular_dependencies());
+        baseline.write(path)?;
+        writeln!(
+            writer,
+            "Wrote {} issue fingerprints and {} circular dependencies to {}",
+            baseline.issues.len(),
+            baseline.circular_dependencies.len(),
+            path.display()
+        )?;
+        return Ok(EXIT_PASSED);
+    }
+
     if let Some(baseline_ref) = &args.baseline {
+        // --baseline <ref>: compare current issues against a git ref. With --check this
+        // is a ratchet gate that fails only for new issues at the configured severity.
         let baseline = analyze_ref(
-            &args.path,
-            &config,
-            &thresholds,
+            args.path(),
+            config,
+            thresholds,
             baseline_ref,
             args.git_months,
             !args.no_git,
         )
         .map_err(|e| -> Box<dyn std::error::Error> { Box::new(e) })?;
         let current_report =
-            cargo_coupling::analyze_project_balance_with_thresholds(&metrics, &thresholds);
-        let diff = diff_ref_analysis(&baseline, &current_report);
+            cargo_coupling::analyze_project_balance_with_thresholds(metrics, thresholds);
+        let diff = diff_ref_analysis(
+            &baseline,
+            metrics,
+            &current_report,
+            &thresholds.balance_formula,
+        );
 
-        if args.json {
-            generate_json_output_with_diff(&metrics, &thresholds, &manifest, &diff, &mut writer)?;
+        if args.dimension_delta {
+            let delta = diff.dimension_delta.clone().unwrap_or_default();
+            generate_dimension_delta_output(&delta, baseline_ref, args.json, &mut writer)?;
+        } else if args.json {
+            generate_json_output_with_diff(metrics, thresholds, manifest, &diff, &mut writer)?;
         } else if args.check {
-            let fail_on = ratchet_fail_on_from_args(&args)?;
+            let fail_on = ratchet_fail_on_from_args(args)?;
             let exit_code =
                 generate_ratchet_check_output(&diff, baseline_ref, fail_on, &mut writer)?;
             return Ok(exit_code);
         } else {
             generate_baseline_diff_output(&diff, baseline_ref, &mut writer)?;
         }
-        return Ok(0);
+        return Ok(EXIT_PASSED);
     }
 
     // --deps: Show third-party dependency coupling exposure
     if args.deps {
-        let versions = load_lock_versions_near(&args.path);
-        let report = analyze_external_dependencies(&metrics, &versions);
+        let mut versions = HashMap::new();
+        for path in &args.paths {
+            for (name, crate_versions) in load_lock_versions_near(path) {
+                let known: &mut Vec<String> = versions.entry(name).or_default();
+                for version in crate_versions {
+                    if !known.contains(&version) {
+                        known.push(version);
+                    }
+                }
+            }
+        }
+        let report = analyze_external_dependencies_with_thresholds(metrics, &versions, thresholds);
         generate_external_dependencies_output(&report, args.json, args.japanese, &mut writer)?;
-        return Ok(0);
+        return Ok(EXIT_PASSED);
+    }
+
+    // --temporal-coupling: Co-changing files, hidden vs reinforcing static coupling
+    if args.temporal_coupling {
+        let report = analyze_temporal_coupling_report(metrics);
+        generate_temporal_coupling_output(&report, args.json, args.japanese, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --visibility-audit: `pub` items that a narrower visibility would cover
+    if args.visibility_audit {
+        let audit = analyze_visibility(metrics);
+        generate_visibility_audit_output(&audit, args.json, args.japanese, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --compare-to-crate: Benchmark the coupling profile against reference crates
+    if let Some(target) = &args.compare_to_crate {
+        let references = reference_profiles();
+        let Some(report) =
+            benchmark_profile(&metrics.coupling_profile(), &references, target.as_deref())
+        else {
+            let names: Vec<&str> = references.iter().map(|r| r.name.as_str()).collect();
+            eprintln!(
+                "Error: unknown reference crate '{}'. Available: {}",
+                target.as_deref().unwrap_or_default(),
+                names.join(", ")
+            );
+            return Ok(EXIT_CONFIG_ERROR);
+        };
+        generate_benchmark_output(&report, args.json, args.japanese, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format summary-json: Headline numbers only
+    if args.format == Some(OutputFormat::SummaryJson) {
+        generate_summary_json_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format sarif: Issues as SARIF results for code scanning
+    if args.format == Some(OutputFormat::Sarif) {
+        generate_sarif_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format dot: Module dependency graph for Graphviz
+    if args.format == Some(OutputFormat::Dot) {
+        generate_dot_output(metrics, &thresholds.balance_formula, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format jsonl: Streamable JSON Lines records
+    if args.format == Some(OutputFormat::Jsonl) {
+        generate_jsonl_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format csv: One row per coupling for spreadsheet analysis
+    if args.format == Some(OutputFormat::Csv) {
+        generate_csv_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format prometheus: Gauges for a Pushgateway or textfile collector
+    if args.format == Some(OutputFormat::Prometheus) {
+        generate_prometheus_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format markdown-table: Issues only, as a table to paste into PRs and tickets
+    if args.format == Some(OutputFormat::MarkdownTable) {
+        generate_markdown_table_output(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --format html: Standalone report page with the web UI's data inlined
+    if args.format == Some(OutputFormat::Html) {
+        generate_html_report(metrics, thresholds, &mut writer)?;
+        return Ok(EXIT_PASSED);
     }
 
     // --json: Machine-readable JSON output
     if args.json {
-        generate_json_output(&metrics, &thresholds, &manifest, &mut writer)?;
-        return Ok(0);
+        generate_json_output(metrics, thresholds, manifest, &mut writer)?;
+        return Ok(EXIT_PASSED);
     }
 
     // --check: Quality gate check (returns exit code)
     if args.check {
-        let check_config = check_config_from_args(&args)?;
-        let exit_code = generate_check_output(&metrics, &thresholds, &check_config, &mut writer)?;
+        let check_config = check_config_from_args(args, config)?;
+        if let (Some(baseline), Some(path)) = (&check_config.baseline, &args.baseline_file) {
+            for warning in comparability_warnings(
+                &format!("baseline file {}", path.display()),
+                (
+                    baseline.analyzer_version.as_deref(),
+                    baseline.config_hash.as_deref(),
+                ),
+                (Some(ANALYZER_VERSION), metrics.config_hash.as_deref()),
+            ) {
+                log_warn!("Warning: {}", warning);
+            }
+        }
+        let exit_code = if args.format == Some(OutputFormat::Github) {
+            generate_check_github_output(metrics, thresholds, &check_config, &mut writer)?
+        } else {
+            generate_check_output(metrics, thresholds, &check_config, &mut writer)?
+        };
         return Ok(exit_code);
     }
 
     // --hotspots: Show top refactoring targets
     if let Some(limit) = args.hotspots {
-        generate_hotspots_output(&metrics, &thresholds, limit, args.verbose, &mut writer)?;
-        return Ok(0);
+        generate_hotspots_output(metrics, thresholds, limit, args.verbose, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --quick-wins: Show high-impact, low-effort fixes
+    if let Some(limit) = args.quick_wins {
+        generate_quick_wins_output(metrics, thresholds, limit, &mut writer)?;
+        return Ok(EXIT_PASSED);
+    }
+
+    // --worst: Show the worst individual couplings
+    if let Some(limit) = args.worst {
+        generate_worst_couplings_output(metrics, thresholds, limit, &mut writer)?;
+        return Ok(EXIT_PASSED);
     }
 
     // --impact: Analyze impact of a specific module
     if let Some(module_name) = &args.impact {
-        let found = generate_impact_output(&metrics, module_name, &mut writer)?;
+        let found = generate_impact_output(metrics, module_name, &mut writer)?;
         if !found {
-            return Ok(1);
+            writer.flush()?;
+            return Err(
+                invalid_cli_input(format!("--impact: module '{}' not found", module_name)).into(),
+            );
         }
-        return Ok(0);
+        return Ok(EXIT_PASSED);
     }
 
     // --trace: Trace dependencies for a specific function/type
     if let Some(item_name) = &args.trace {
         let found =
-            cargo_coupling::cli_output::generate_trace_output(&metrics, item_name, &mut writer)?;
+            cargo_coupling::cli_output::generate_trace_output(metrics, item_name, &mut writer)?;
         if !found {
-            return Ok(1);
+            writer.flush()?;
+            return Err(
+                invalid_cli_input(format!("--trace: item '{}' not found", item_name)).into(),
+            );
         }
-        return Ok(0);
+        return Ok(EXIT_PASSED);
     }
 
     // Default modes
     if args.ai {
-        generate_ai_output_with_thresholds(&metrics, &thresholds, &manifest, &mut writer)?;
+        generate_ai_output_with_thresholds(metrics, thresholds, manifest, &mut writer)?;
     } else if args.summary {
         generate_summary_with_options(
-            &metrics,
-            &thresholds,
-            &manifest,
+            metrics,
+            thresholds,
+            manifest,
             args.blind_spots || args.all,
             &mut writer,
         )?;
     } else {
         generate_report_with_options(
-            &metrics,
-            &thresholds,
-            &manifest,
+            metrics,
+            thresholds,
+            manifest,
             TextReportOptions {
                 show_structural_blind_spots: args.blind_spots || args.all,
                 show_all_temporal_couplings: args.all,
@@ -544,27 +1737,27 @@ fn run_coupling(args: Args) -> Result<i32, Box<dyn std::error::Error>> {
         )?;
     }
 
-    // Notify about output file
-    if let Some(path) = &args.output {
-        eprintln!("Report written to: {}", path.display());
+    // --include-integration-tests: public-API coupling from `tests/`, kept apart
+    if args.include_integration_tests {
+        let report = analyze_integration_tests_for_roots(
+            &args.paths,
+            metrics,
+            &config.dependency_path_filter,
+        );
+        generate_integration_tests_output(&report, args.japanese, &mut writer)?;
     }
 
-    // Show total timing
-    if args.timing {
-        let total_time = total_start.elapsed();
-        let files_per_sec = metrics.total_files as f64 / total_time.as_secs_f64();
-        eprintln!(
-            "Total time: {:.2?} ({:.1} files/sec)",
-            total_time, files_per_sec
-        );
+    // Notify about output file
+    if let Some(path) = &args.output {
+        log_info!("Report written to: {}", path.display());
     }
 
-    Ok(0)
+    Ok(EXIT_PASSED)
 }
 
 fn warn_on_output_mode_conflicts(args: &Args) {
     if let Some((used, ignored)) = output_mode_conflict(args) {
-        eprintln!("Warning: using {}; ignoring {}.", used, ignored.join(", "));
+        log_warn!("Warning: using {}; ignoring {}.", used, ignored.join(", "));
     }
 }
 
@@ -577,18 +1770,63 @@ fn output_mode_conflict(args: &Args) -> Option<(&'static str, Vec<&'static str>)
     if args.web {
         modes.push("--web");
     }
-    if args.json && args.history.is_none() && !args.deps {
+    if args.json
+        && args.history.is_none()
+        && !args.deps
+        && !args.temporal_coupling
+        && !args.visibility_audit
+        && args.compare_to_crate.is_none()
+    {
         modes.push("--json");
     }
     if args.deps {
         modes.push("--deps");
     }
+    if args.temporal_coupling {
+        modes.push("--temporal-coupling");
+    }
+    if args.visibility_audit {
+        modes.push("--visibility-audit");
+    }
+    if args.format == Some(OutputFormat::SummaryJson) {
+        modes.push("--format summary-json");
+    }
+    if args.format == Some(OutputFormat::Sarif) {
+        modes.push("--format sarif");
+    }
+    if args.format == Some(OutputFormat::Dot) {
+        modes.push("--format dot");
+    }
+    if args.format == Some(OutputFormat::Jsonl) {
+        modes.push("--format jsonl");
+    }
+    if args.format == Some(OutputFormat::Html) {
+        modes.push("--format html");
+    }
+    if args.format == Some(OutputFormat::Csv) {
+        modes.push("--format csv");
+    }
+    if args.format == Some(OutputFormat::Prometheus) {
+        modes.push("--format prometheus");
+    }
+    if args.format == Some(OutputFormat::MarkdownTable) {
+        modes.push("--format markdown-table");
+    }
+    if args.compare_to_crate.is_some() {
+        modes.push("--compare-to-crate");
+    }
     if args.check {
         modes.push("--check");
     }
     if args.hotspots.is_some() {
         modes.push("--hotspots");
     }
+    if args.quick_wins.is_some() {
+        modes.push("--quick-wins");
+    }
+    if args.worst.is_some() {
+        modes.push("--worst");
+    }
     if args.impact.is_some() {
         modes.push("--impact");
     }
@@ -601,18 +1839,34 @@ fn output_mode_conflict(args: &Args) -> Option<(&'static str, Vec<&'static str>)
     if args.summary {
         modes.push("--summary");
     }
+    if args.write_baseline.is_some() {
+        modes.push("--write-baseline");
+    }
 
     (modes.len() > 1).then(|| (modes[0], modes[1..].to_vec()))
 }
 
-fn check_config_from_args(args: &Args) -> Result<CheckConfig, std::io::Error> {
+fn check_config_from_args(
+    args: &Args,
+    config: &CompiledConfig,
+) -> Result<CheckConfig, Box<dyn std::error::Error>> {
     let has_gate_flag = args.min_grade.is_some()
         || args.max_critical.is_some()
         || args.max_circular.is_some()
         || args.fail_on.is_some();
 
+    let baseline = args
+        .baseline_file
+        .as_deref()
+        .map(IssueBaseline::load)
+        .transpose()?;
+
     if !has_gate_flag {
-        return Ok(CheckConfig::default());
+        return Ok(CheckConfig {
+            baseline,
+            budgets: config.budgets.clone(),
+            ..CheckConfig::default()
+        });
     }
 
     let min_grade = match args.min_grade.as_deref() {
@@ -640,10 +1894,12 @@ fn check_config_from_args(args: &Args) -> Result<CheckConfig, std::io::Error> {
         max_critical: args.max_critical,
         max_circular: args.max_circular,
         fail_on,
+        baseline,
+        budgets: config.budgets.clone(),
     })
 }
 
-fn ratchet_fail_on_from_args(args: &Args) -> Result<Severity, std::io::Error> {
+fn ratchet_fail_on_from_args(args: &Args) -> Result<Severity, CliInputError> {
     match args.fail_on.as_deref() {
         Some(value) => parse_severity(value).ok_or_else(|| {
             invalid_cli_input(format!(
@@ -655,8 +1911,44 @@ fn ratchet_fail_on_from_args(args: &Args) -> Result<Severity, std::io::Error> {
     }
 }
 
-fn invalid_cli_input(message: impl Into<String>) -> std::io::Error {
-    std::io::Error::new(std::io::ErrorKind::InvalidInput, message.into())
+/// Explain an empty analysis: where we looked and what gets skipped.
+fn warn_no_source_files(metrics: &ProjectMetrics) {
+    eprintln!("Warning: no Rust source files were found.");
+    if metrics.searched_paths.is_empty() {
+        eprintln!("  Searched: no existing source roots");
+    } else {
+        let searched: Vec<String> = metrics
+            .searched_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        eprintln!("  Searched: {}", searched.join(", "));
+    }
+    if !metrics.skipped_crates.is_empty() {
+        eprintln!(
+            "  Members without sources: {}",
+            metrics.skipped_crates.join(", ")
+        );
+    }
+    eprintln!(
+        "  Pass a crate or workspace directory, its Cargo.toml, or a single .rs file. \
+         target/ and hidden directories are skipped, as are files matched by \
+         [analysis] exclude or --include/--exclude.\n"
+    );
+}
+
+/// `--exclude-tests` / `--include-tests` as an override of `[analysis] exclude_tests`
+fn test_code_override(exclude_tests: bool, include_tests: bool) -> Option<bool> {
+    (exclude_tests || include_tests).then_some(exclude_tests)
+}
+
+/// A flag value or combination of flags the CLI rejects; exits with code 3.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct CliInputError(String);
+
+fn invalid_cli_input(message: impl Into<String>) -> CliInputError {
+    CliInputError(message.into())
 }
 
 #[cfg(test)]
@@ -667,35 +1959,70 @@ mod tests {
 
     fn base_args(path: PathBuf) -> Args {
         Args {
-            path,
+            command: None,
+            paths: vec![path],
+            rev: None,
+            keep: false,
             output: None,
             summary: false,
             ai: false,
             git_months: 6,
+            git_max_commits: None,
+            git_timeout: 300,
+            git_scope: None,
             no_git: true,
             exclude_tests: false,
+            include_tests: false,
+            merge_couplings: false,
+            no_cache: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_integration_tests: false,
+            exclude_crate_kinds: None,
+            compare_to_crate: None,
             config: None,
             verbose: false,
+            quiet: false,
+            log_level: None,
             timing: false,
+            timing_json: None,
+            profile_memory: false,
             jobs: None,
             max_deps: None,
+            max_interface_ratio: None,
+            min_shallow_interface: None,
+            max_cognitive_load: None,
             max_dependents: None,
             web: false,
             port: 3000,
+            bind: IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+            token: None,
             no_open: true,
+            snapshots: None,
             api_endpoint: None,
             hotspots: None,
+            quick_wins: None,
+            worst: None,
             deps: false,
+            temporal_coupling: false,
+            visibility_audit: false,
             impact: None,
             trace: None,
             history: None,
             baseline: None,
+            baseline_file: None,
+            write_baseline: None,
+            record: None,
+            max_history: None,
+            dimension_delta: false,
             check: false,
             min_grade: None,
             max_critical: None,
             max_circular: None,
             fail_on: None,
             json: false,
+            schema: false,
+            format: None,
             all: false,
             blind_spots: false,
             japanese: false,
@@ -723,7 +2050,7 @@ mod tests {
         let mut args = base_args(PathBuf::from("src"));
         args.check = true;
 
-        let config = check_config_from_args(&args).unwrap();
+        let config = check_config_from_args(&args, &CompiledConfig::empty()).unwrap();
 
         assert_eq!(config.min_grade, Some(HealthGrade::C));
         assert_eq!(config.max_critical, Some(0));
@@ -737,12 +2064,25 @@ mod tests {
         args.check = true;
         args.fail_on = Some("high".to_string());
 
-        let config = check_config_from_args(&args).unwrap();
+        let mut compiled = CompiledConfig::empty();
+        compiled.budgets.insert(
+            "crate::api".to_string(),
+            cargo_coupling::ModuleBudget {
+                max_out: Some(3),
+                max_in: None,
+            },
+        );
+        let config = check_config_from_args(&args, &compiled).unwrap();
 
         assert_eq!(config.min_grade, None);
         assert_eq!(config.max_critical, None);
         assert_eq!(config.max_circular, None);
         assert_eq!(config.fail_on, Some(Severity::High));
+        assert_eq!(
+            config.budgets.keys().collect::<Vec<_>>(),
+            ["crate::api"],
+            "budgets come from the config"
+        );
     }
 
     #[test]
@@ -751,7 +2091,7 @@ mod tests {
         args.check = true;
         args.min_grade = Some("ZZZ".to_string());
         assert!(
-            check_config_from_args(&args)
+            check_config_from_args(&args, &CompiledConfig::empty())
                 .unwrap_err()
                 .to_string()
                 .contains("invalid --min-grade")
@@ -760,13 +2100,23 @@ mod tests {
         args.min_grade = None;
         args.fail_on = Some("bogus".to_string());
         assert!(
-            check_config_from_args(&args)
+            check_config_from_args(&args, &CompiledConfig::empty())
                 .unwrap_err()
                 .to_string()
                 .contains("invalid --fail-on")
         );
     }
 
+    #[test]
+    fn test_only_rejected_cli_input_is_a_config_error() {
+        let rejected = invalid_cli_input("--history must be greater than 0");
+        assert_eq!(error_exit_code(&rejected), EXIT_CONFIG_ERROR);
+
+        // An I/O failure that happens to be InvalidInput failed the analysis
+        let io_error = std::io::Error::new(std::io::ErrorKind::InvalidInput, "bad file name");
+        assert_eq!(error_exit_code(&io_error), EXIT_ANALYSIS_ERROR);
+    }
+
     #[test]
     fn ratchet_defaults_to_high_and_parses_fail_on() {
         let mut args = base_args(PathBuf::from("src"));
@@ -834,6 +2184,29 @@ mod tests {
         assert!(error.contains("--history must be greater than 0"));
     }
 
+    #[test]
+    fn test_worst_zero_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut args = base_args(tmp.path().to_path_buf());
+        args.worst = Some(0);
+
+        let error = run_coupling(args).unwrap_err();
+
+        assert!(error.to_string().contains("--worst must be greater than 0"));
+        assert_eq!(error_exit_code(error.as_ref()), EXIT_CONFIG_ERROR);
+    }
+
+    #[test]
+    fn test_negative_max_cognitive_load_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut args = base_args(tmp.path().to_path_buf());
+        args.max_cognitive_load = Some(-1.0);
+
+        let error = run_coupling(args).unwrap_err().to_string();
+
+        assert!(error.contains("--max-cognitive-load must be a non-negative number"));
+    }
+
     #[test]
     fn json_output_reports_no_git_and_excluded_tests() {
         let tmp = tempfile::tempdir().unwrap();
@@ -862,6 +2235,35 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_tests_are_excluded_unless_include_tests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        write_files(&src, false);
+        let output = tmp.path().join("manifest.json");
+        let tests_excluded = |include_tests: bool| {
+            let mut args = base_args(src.clone());
+            args.json = true;
+            args.include_tests = include_tests;
+            args.output = Some(output.clone());
+            assert_eq!(run_coupling(args).unwrap(), 0);
+
+            let text = std::fs::read_to_string(&output).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+            parsed["analysis_manifest"]["notes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|note| {
+                    note.as_str()
+                        .is_some_and(|note| note.contains("Test code was excluded"))
+                })
+        };
+
+        assert!(tests_excluded(false));
+        assert!(!tests_excluded(true));
+    }
+
     #[test]
     fn json_manifest_reports_parse_failures() {
         let tmp = tempfile::tempdir().unwrap();
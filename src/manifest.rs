@@ -28,12 +28,18 @@ pub struct ManifestContext {
     pub tests_excluded: bool,
     /// Number of source files that failed to parse and were skipped.
     pub parse_failures: usize,
+    /// Number of generated files (header marker such as `@generated`) skipped.
+    pub generated_files: usize,
     /// Workspace members with no discoverable source files.
     pub skipped_crates: Vec<String>,
     /// Module references skipped after resolving outside the analyzed boundary.
     pub boundary_skipped_files: usize,
     /// Config patterns that matched no paths in this analysis run.
     pub dead_config_patterns: Vec<String>,
+    /// Parse failures consistent with an edition mismatch.
+    pub edition_parse_failures: Vec<String>,
+    /// Analyzed modules that no `mod` declaration loads.
+    pub orphaned_modules: Vec<String>,
 }
 
 /// The declared negative space of an analysis run.
@@ -89,9 +95,11 @@ const STRUCTURAL_BLIND_SPOTS: &[BlindSpot] = &[
     BlindSpot {
         area: "macro-and-cfg",
         description: "Coupling introduced by macro expansion, or behind inactive `cfg(...)`, is \
-                      invisible to syn-based parsing. Generated code is not analyzed unless it \
-                      exists as source.",
+                      invisible to syn-based parsing. Paths written inside macro invocations \
+                      are counted at Import strength only; generated code is not analyzed \
+                      unless it exists as source.",
         description_ja: "マクロ展開で生じる結合や、無効な `cfg(...)` の背後にある結合は、synベースの解析では見えません。\
+                         マクロ呼び出しの中に書かれたパスはImport強度としてのみ数えます。\
                          生成コードはソースとして存在しない限り解析されません。",
     },
 ];
@@ -131,6 +139,30 @@ pub fn build_manifest(ctx: &ManifestContext) -> AnalysisManifest {
             ctx.parse_failures
         ));
     }
+    if ctx.generated_files > 0 {
+        notes.push(format!(
+            "{} generated file(s) were skipped ([analysis] generated_markers); coupling in \
+             generated code is not counted.",
+            ctx.generated_files
+        ));
+        notes_ja.push(format!(
+            "{} 件の生成ファイルをスキップしました ([analysis] generated_markers)。生成コードの結合はカウントされません。",
+            ctx.generated_files
+        ));
+    }
+    if !ctx.edition_parse_failures.is_empty() {
+        let file_list = ctx.edition_parse_failures.join("; ");
+        notes.push(format!(
+            "{} parse failure(s) may be caused by an edition mismatch: {}",
+            ctx.edition_parse_failures.len(),
+            file_list
+        ));
+        notes_ja.push(format!(
+            "{} 件の解析失敗はエディションの不一致が原因の可能性があります: {}",
+            ctx.edition_parse_failures.len(),
+            file_list
+        ));
+    }
     if !ctx.skipped_crates.is_empty() {
         let crate_names = ctx.skipped_crates.join(", ");
         notes.push(format!(
@@ -164,6 +196,20 @@ pub fn build_manifest(ctx: &ManifestContext) -> AnalysisManifest {
         ));
     }
 
+    if !ctx.orphaned_modules.is_empty() {
+        let module_list = ctx.orphaned_modules.join(", ");
+        notes.push(format!(
+            "{} module file(s) are not declared by any `mod` item, so rustc never compiles them ({}); couplings found in them are left out of the grade.",
+            ctx.orphaned_modules.len(),
+            module_list
+        ));
+        notes_ja.push(format!(
+            "{} 件のモジュールファイルはどの `mod` 宣言からも読み込まれないため、rustcはコンパイルしません（{}）。そこで見つかった結合は評価から除外されます。",
+            ctx.orphaned_modules.len(),
+            module_list
+        ));
+    }
+
     AnalysisManifest {
         blind_spots: STRUCTURAL_BLIND_SPOTS.to_vec(),
         notes,
@@ -181,9 +227,12 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert_eq!(manifest.blind_spots.len(), STRUCTURAL_BLIND_SPOTS.len());
         assert!(
@@ -206,9 +255,12 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert!(manifest.notes.is_empty());
     }
@@ -228,9 +280,12 @@ mod tests {
             git_used: true,
             tests_excluded: true,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert!(manifest.notes.iter().any(|n| n.contains("Test code")));
     }
@@ -241,22 +296,44 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 3,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert!(manifest.notes.iter().any(|n| n.contains("3 source file")));
     }
 
+    #[test]
+    fn test_generated_files_are_reported_with_count() {
+        let manifest = build_manifest(&ManifestContext {
+            git_used: true,
+            generated_files: 4,
+            ..Default::default()
+        });
+        assert!(
+            manifest
+                .notes
+                .iter()
+                .any(|n| n.contains("4 generated file(s) were skipped"))
+        );
+        assert_eq!(manifest.notes.len(), manifest.notes_ja.len());
+    }
+
     #[test]
     fn all_degradations_accumulate() {
         let manifest = build_manifest(&ManifestContext {
             git_used: false,
             tests_excluded: true,
             parse_failures: 2,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert_eq!(manifest.notes.len(), 3);
     }
@@ -267,9 +344,12 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: vec!["empty-member".to_string()],
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         assert!(manifest.notes.iter().any(|n| {
             n.contains(
@@ -287,9 +367,12 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 2,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
 
         assert!(manifest.notes.iter().any(|n| {
@@ -308,12 +391,15 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: vec![
                 "subdomains.core: src/old.rs".to_string(),
                 "volatility.high: src/dead.rs".to_string(),
             ],
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
 
         assert!(manifest.notes.iter().any(|n| {
@@ -330,9 +416,12 @@ mod tests {
             git_used: true,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
 
         assert!(
@@ -342,4 +431,23 @@ mod tests {
                 .any(|n| n.contains(".coupling.toml drift"))
         );
     }
+
+    #[test]
+    fn test_orphaned_modules_are_reported_in_both_languages() {
+        let manifest = build_manifest(&ManifestContext {
+            git_used: true,
+            orphaned_modules: vec!["orders::stray".to_string()],
+            ..Default::default()
+        });
+
+        assert!(manifest.notes.iter().any(|n| {
+            n.contains("1 module file(s) are not declared by any `mod` item, so rustc never compiles them (orders::stray)")
+        }));
+        assert!(
+            manifest
+                .notes_ja
+                .iter()
+                .any(|n| n.contains("（orders::stray）"))
+        );
+    }
 }
@@ -0,0 +1,331 @@
+//! Metrics snapshot files (`coupling snapshot save`, `coupling snapshot check`).
+//!
+//! A snapshot keeps only the headline numbers of a run: health grade, average
+//! score, issue counts by severity and the number of circular dependencies.
+//! Checking the working tree against a committed snapshot catches regressions
+//! without git history, unlike `--baseline <ref>` or `coupling diff`.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::grade::ProjectBalanceReport;
+use crate::balance::severity::Severity;
+use crate::header::{ANALYZER_VERSION, comparability_warnings};
+use crate::metrics::project::ProjectMetrics;
+
+/// Issue counts per severity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityCounts {
+    #[serde(default)]
+    pub critical: usize,
+    #[serde(default)]
+    pub high: usize,
+    #[serde(default)]
+    pub medium: usize,
+    #[serde(default)]
+    pub low: usize,
+}
+
+/// Headline metrics of one analysis run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// `cargo-coupling` version that wrote the file
+    #[serde(default)]
+    pub analyzer_version: Option<String>,
+    /// `CompiledConfig::config_hash` of the run that wrote the file
+    #[serde(default)]
+    pub config_hash: Option<String>,
+    /// Health grade letter (S, A, B, C, D or F)
+    pub health_grade: String,
+    pub average_score: f64,
+    pub issues: SeverityCounts,
+    pub circular_dependencies: usize,
+}
+
+/// How much each metric may worsen before `snapshot check` fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SnapshotTolerances {
+    /// Allowed drop of the average score
+    pub score: f64,
+    /// Allowed increase of the issue count, per severity
+    pub issues: usize,
+    /// Allowed increase of the circular dependency count
+    pub cycles: usize,
+}
+
+/// Change of one snapshot metric between the stored and the current run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricChange {
+    pub metric: String,
+    pub baseline: String,
+    pub current: String,
+    /// Signed change, empty for the grade
+    pub delta: String,
+    /// Allowed worsening, empty for the grade (any drop regresses)
+    pub tolerance: String,
+    pub regressed: bool,
+}
+
+impl fmt::Display for MetricChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} → {}", self.metric, self.baseline, self.current)?;
+        match (self.delta.is_empty(), self.regressed) {
+            (true, true) => write!(f, " (grade dropped)"),
+            (true, false) => Ok(()),
+            (false, true) => write!(f, " ({}, exceeds tolerance {})", self.delta, self.tolerance),
+            (false, false) => write!(f, " ({})", self.delta),
+        }
+    }
+}
+
+/// Result of comparing the current run against a stored snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotComparison {
+    /// Every metric that changed, regressed or not
+    pub changes: Vec<MetricChange>,
+    /// Why the snapshot may not be comparable (analyzer version or config hash differs)
+    pub warnings: Vec<String>,
+}
+
+impl SnapshotComparison {
+    /// Changes that worsened beyond their tolerance.
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricChange> {
+        self.changes.iter().filter(|change| change.regressed)
+    }
+
+    /// Whether any metric regressed beyond its tolerance.
+    pub fn regressed(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+impl MetricsSnapshot {
+    /// Snapshot of `report`, with the cycle count and config hash taken from `metrics`.
+    pub fn from_report(report: &ProjectBalanceReport, metrics: &ProjectMetrics) -> Self {
+        let count = |severity| *report.issues_by_severity.get(&severity).unwrap_or(&0);
+        Self {
+            analyzer_version: Some(ANALYZER_VERSION.to_string()),
+            config_hash: metrics.config_hash.clone(),
+            health_grade: report.health_grade.letter().to_string(),
+            average_score: report.average_score,
+            issues: SeverityCounts {
+                critical: count(Severity::Critical),
+                high: count(Severity::High),
+                medium: count(Severity::Medium),
+                low: count(Severity::Low),
+            },
+            circular_dependencies: metrics.detect_circular_dependencies().len(),
+        }
+    }
+
+    /// Read a snapshot written by `write`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid snapshot file {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Write the snapshot as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+
+    /// Compare `current` against this stored snapshot.
+    pub fn compare(
+        &self,
+        current: &MetricsSnapshot,
+        tolerances: SnapshotTolerances,
+    ) -> SnapshotComparison {
+        let mut changes = Vec::new();
+
+        if self.health_grade != current.health_grade {
+            changes.push(MetricChange {
+                metric: "health_grade".to_string(),
+                baseline: self.health_grade.clone(),
+                current: current.health_grade.clone(),
+                delta: String::new(),
+                tolerance: String::new(),
+                regressed: grade_rank(&current.health_grade) < grade_rank(&self.health_grade),
+            });
+        }
+
+        // Compare at display precision so float noise never fails a check
+        let (baseline_score, current_score) = (
+            format!("{:.2}", self.average_score),
+            format!("{:.2}", current.average_score),
+        );
+        if baseline_score != current_score {
+            let delta = current.average_score - self.average_score;
+            changes.push(MetricChange {
+                metric: "average_score".to_string(),
+                baseline: baseline_score,
+                current: current_score,
+                delta: format!("{:+.2}", delta),
+                tolerance: format!("{:.2}", tolerances.score),
+                regressed: -delta > tolerances.score + 1e-9,
+            });
+        }
+
+        let counts = [
+            (
+                "critical_issues",
+                self.issues.critical,
+                current.issues.critical,
+                tolerances.issues,
+            ),
+            (
+                "high_issues",
+                self.issues.high,
+                current.issues.high,
+                tolerances.issues,
+            ),
+            (
+                "medium_issues",
+                self.issues.medium,
+                current.issues.medium,
+                tolerances.issues,
+            ),
+            (
+                "low_issues",
+                self.issues.low,
+                current.issues.low,
+                tolerances.issues,
+            ),
+            (
+                "circular_dependencies",
+                self.circular_dependencies,
+                current.circular_dependencies,
+                tolerances.cycles,
+            ),
+        ];
+        for (metric, baseline, now, tolerance) in counts {
+            if baseline != now {
+                changes.push(MetricChange {
+                    metric: metric.to_string(),
+                    baseline: baseline.to_string(),
+                    current: now.to_string(),
+                    delta: format!("{:+}", now as i64 - baseline as i64),
+                    tolerance: tolerance.to_string(),
+                    regressed: now > baseline + tolerance,
+                });
+            }
+        }
+
+        SnapshotComparison {
+            changes,
+            warnings: comparability_warnings(
+                "Snapshot",
+                (
+                    self.analyzer_version.as_deref(),
+                    self.config_hash.as_deref(),
+                ),
+                (
+                    current.analyzer_version.as_deref(),
+                    current.config_hash.as_deref(),
+                ),
+            ),
+        }
+    }
+}
+
+/// Rank of a grade letter, higher is better. S is a warning about
+/// over-optimization rather than a better grade, so it ranks with A.
+fn grade_rank(letter: &str) -> usize {
+    match letter {
+        "S" | "A" => 5,
+        "B" => 4,
+        "C" => 3,
+        "D" => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(grade: &str, score: f64, high: usize, cycles: usize) -> MetricsSnapshot {
+        MetricsSnapshot {
+            analyzer_version: Some(ANALYZER_VERSION.to_string()),
+            config_hash: Some("abc".to_string()),
+            health_grade: grade.to_string(),
+            average_score: score,
+            issues: SeverityCounts {
+                high,
+                ..SeverityCounts::default()
+            },
+            circular_dependencies: cycles,
+        }
+    }
+
+    #[test]
+    fn test_regressions_beyond_tolerance_fail_and_improvements_pass() {
+        let stored = snapshot("B", 0.82, 2, 0);
+
+        let unchanged = stored.compare(&stored.clone(), SnapshotTolerances::default());
+        assert!(unchanged.changes.is_empty());
+        assert!(!unchanged.regressed());
+
+        let worse = snapshot("C", 0.78, 3, 1);
+        let tolerant = SnapshotTolerances {
+            score: 0.02,
+            issues: 1,
+            cycles: 0,
+        };
+        let comparison = stored.compare(&worse, tolerant);
+        let regressed: Vec<String> = comparison.regressions().map(|c| c.to_string()).collect();
+        assert_eq!(
+            regressed,
+            vec![
+                "health_grade B → C (grade dropped)",
+                "average_score 0.82 → 0.78 (-0.04, exceeds tolerance 0.02)",
+                "circular_dependencies 0 → 1 (+1, exceeds tolerance 0)",
+            ]
+        );
+        // One extra high issue is within the tolerance of 1
+        let high = comparison
+            .changes
+            .iter()
+            .find(|c| c.metric == "high_issues")
+            .unwrap();
+        assert!(!high.regressed);
+        assert_eq!(high.to_string(), "high_issues 2 → 3 (+1)");
+
+        let better = snapshot("S", 0.9, 0, 0);
+        assert!(
+            !stored
+                .compare(&better, SnapshotTolerances::default())
+                .regressed()
+        );
+    }
+
+    #[test]
+    fn test_snapshots_round_trip_and_warn_on_a_different_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("coupling-snapshot.json");
+        let stored = snapshot("A", 0.91, 0, 0);
+        stored.write(&path).unwrap();
+        assert_eq!(MetricsSnapshot::load(&path).unwrap(), stored);
+
+        let mut current = stored.clone();
+        current.config_hash = Some("def".to_string());
+        let comparison = stored.compare(&current, SnapshotTolerances::default());
+        assert_eq!(comparison.warnings.len(), 1);
+        assert!(!comparison.regressed());
+
+        std::fs::write(&path, "{").unwrap();
+        let err = MetricsSnapshot::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
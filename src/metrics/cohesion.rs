@@ -0,0 +1,272 @@
+//! LCOM-style cohesion per module.
+//!
+//! Coupling says how modules depend on each other; cohesion says whether a
+//! module belongs together at all. Following LCOM4, the module's functions and
+//! types form a graph: a function is linked to the local types in its
+//! parameters and body (methods to their `Self` type) and to the local
+//! functions it calls. Each connected
+//! component is a cluster of functions working on the same types. One cluster
+//! is a focused module; several disjoint clusters are a split candidate, even
+//! when the module is not large enough to be a God Module.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::analyzer::ItemDepType;
+
+use super::module::ModuleMetrics;
+use super::project::ProjectMetrics;
+
+/// Clustered functions a module needs before it is checked for Low Cohesion.
+pub const LOW_COHESION_MIN_FUNCTIONS: usize = 6;
+
+/// Functions and local types connected through signatures, usage or calls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeCluster {
+    /// Local types in the cluster, sorted
+    pub types: Vec<String>,
+    /// Functions and methods (`Type::method`) in the cluster, sorted
+    pub functions: Vec<String>,
+}
+
+/// Cohesion of one module.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleCohesion {
+    pub module: String,
+    /// Clusters, largest first
+    pub clusters: Vec<TypeCluster>,
+    /// Functions in any cluster; functions using no local type are left out
+    pub function_count: usize,
+    /// Share of `function_count` in the largest cluster: 1.0 is fully cohesive
+    pub cohesion: f64,
+}
+
+impl TypeCluster {
+    /// Snake-case name for a module holding this cluster, after its first type.
+    ///
+    /// An acronym stays one word: `HTTPClient` becomes `http_client`.
+    pub fn module_name(&self) -> String {
+        let chars: Vec<char> = self
+            .types
+            .first()
+            .map_or("", String::as_str)
+            .chars()
+            .collect();
+        let mut name = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                let after_lower = !chars[i - 1].is_uppercase();
+                let ends_acronym = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+                if after_lower || ends_acronym {
+                    name.push('_');
+                }
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+}
+
+impl ModuleCohesion {
+    /// Cohesion of `module`, `None` for test modules and when none of its
+    /// functions uses a local type.
+    pub fn of(name: &str, module: &ModuleMetrics) -> Option<Self> {
+        if module.is_test_module {
+            return None;
+        }
+        let types: Vec<&str> = module
+            .type_definitions
+            .values()
+            .filter(|type_def| !type_def.is_trait)
+            .map(|type_def| type_def.name.as_str())
+            .collect();
+        if types.is_empty() {
+            return None;
+        }
+
+        // Nodes: functions first, then types
+        let mut nodes: HashMap<&str, usize> = HashMap::new();
+        let mut functions: Vec<&str> = module
+            .function_definitions
+            .keys()
+            .map(String::as_str)
+            .collect();
+        functions.sort_unstable();
+        for name in functions.iter().chain(&types) {
+            let next = nodes.len();
+            nodes.entry(*name).or_insert(next);
+        }
+        let mut parent: Vec<usize> = (0..nodes.len()).collect();
+        let mut linked = vec![false; nodes.len()];
+
+        let mut link = |function: &str, target: &str| {
+            let (Some(&a), Some(&b)) = (nodes.get(function), nodes.get(target)) else {
+                return;
+            };
+            let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+            parent[root_a] = root_b;
+            linked[a] = true;
+            linked[b] = true;
+        };
+
+        for (function, definition) in &module.function_definitions {
+            if let Some((owner, _)) = function.split_once("::") {
+                link(function, owner);
+            }
+            for param_type in &definition.param_types {
+                link(function, param_type);
+            }
+        }
+        for dep in &module.item_dependencies {
+            if dep.dep_type == ItemDepType::Import {
+                continue;
+            }
+            // `Type::item` and `Type.field` count as a use of `Type`
+            let owner = dep
+                .target
+                .split("::")
+                .next()
+                .and_then(|head| head.split('.').next())
+                .unwrap_or(&dep.target);
+            if types.contains(&owner) {
+                link(&dep.source_item, owner);
+            } else {
+                link(&dep.source_item, &dep.target);
+            }
+        }
+
+        let function_nodes = functions.len();
+        let mut components: BTreeMap<usize, TypeCluster> = BTreeMap::new();
+        let mut ordered: Vec<(&str, usize)> = nodes.iter().map(|(name, &i)| (*name, i)).collect();
+        ordered.sort_unstable();
+        for (name, index) in ordered {
+            if !linked[index] {
+                continue;
+            }
+            let cluster = components
+                .entry(find(&mut parent, index))
+                .or_insert_with(|| TypeCluster {
+                    types: Vec::new(),
+                    functions: Vec::new(),
+                });
+            if index < function_nodes {
+                cluster.functions.push(name.to_string());
+            } else {
+                cluster.types.push(name.to_string());
+            }
+        }
+
+        // Only functions that reach a local type count towards cohesion
+        let mut clusters: Vec<TypeCluster> = components
+            .into_values()
+            .filter(|cluster| !cluster.types.is_empty() && !cluster.functions.is_empty())
+            .collect();
+        clusters.sort_by(|a, b| {
+            b.functions
+                .len()
+                .cmp(&a.functions.len())
+                .then_with(|| a.types.cmp(&b.types))
+        });
+        let function_count: usize = clusters.iter().map(|c| c.functions.len()).sum();
+        let largest = clusters.first()?.functions.len();
+
+        Some(Self {
+            module: name.to_string(),
+            cohesion: largest as f64 / function_count as f64,
+            function_count,
+            clusters,
+        })
+    }
+}
+
+fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+/// Cohesion of every module with clustered functions, least cohesive first.
+pub fn calculate_module_cohesion(metrics: &ProjectMetrics) -> Vec<ModuleCohesion> {
+    let mut cohesion: Vec<ModuleCohesion> = metrics
+        .modules
+        .iter()
+        .filter_map(|(name, module)| ModuleCohesion::of(name, module))
+        .collect();
+    cohesion.sort_by(|a, b| {
+        a.cohesion
+            .total_cmp(&b.cohesion)
+            .then_with(|| a.module.cmp(&b.module))
+    });
+    cohesion
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_module_name_keeps_acronyms_together() {
+        let name = |type_name: &str| {
+            TypeCluster {
+                types: vec![type_name.to_string()],
+                functions: Vec::new(),
+            }
+            .module_name()
+        };
+        assert_eq!(name("OrderBook"), "order_book");
+        assert_eq!(name("HTTPClient"), "http_client");
+        assert_eq!(name("IOError"), "io_error");
+        assert_eq!(name("Utf8URL"), "utf8_url");
+        assert_eq!(name("Order"), "order");
+    }
+
+    #[test]
+    fn test_disjoint_type_sets_form_separate_clusters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("shop.rs");
+        std::fs::write(
+            &file,
+            "pub struct Order { total: u64 }\n\
+             pub struct Invoice { amount: u64 }\n\
+             impl Order {\n\
+             \x20   pub fn total(&self) -> u64 { self.total }\n\
+             \x20   pub fn double(&self) -> u64 { self.total * 2 }\n\
+             }\n\
+             pub fn order_total(order: &Order) -> u64 { order.total() }\n\
+             pub fn billed(invoice: &Invoice) -> u64 { invoice.amount }\n\
+             pub fn void(invoice: &mut Invoice) -> bool { true }\n\
+             pub fn helper(x: u64) -> u64 { x + 1 }\n",
+        )
+        .unwrap();
+        let analyzed = crate::analyzer::analyze_rust_file_full(&file).unwrap();
+        let module = ModuleMetrics {
+            item_dependencies: analyzed.item_dependencies,
+            ..analyzed.metrics
+        };
+
+        let cohesion = ModuleCohesion::of("shop", &module).unwrap();
+        assert_eq!(
+            cohesion.clusters,
+            [
+                TypeCluster {
+                    types: vec!["Order".to_string()],
+                    functions: vec![
+                        "Order::double".to_string(),
+                        "Order::total".to_string(),
+                        "order_total".to_string(),
+                    ],
+                },
+                TypeCluster {
+                    types: vec!["Invoice".to_string()],
+                    functions: vec!["billed".to_string(), "void".to_string()],
+                },
+            ]
+        );
+        // `helper` touches no local type and is left out
+        assert_eq!(cohesion.function_count, 5);
+        assert_eq!(cohesion.cohesion, 0.6);
+    }
+}
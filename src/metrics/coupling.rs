@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use crate::volatility::Volatility;
 
-use super::dimensions::{Distance, IntegrationStrength, Visibility};
+use super::dimensions::{Connascence, Distance, IntegrationStrength, Visibility};
 
 #[derive(Debug, Clone, Default)]
 pub struct CouplingLocation {
@@ -10,6 +10,8 @@ pub struct CouplingLocation {
     pub file_path: Option<PathBuf>,
     /// Line number in the source file
     pub line: usize,
+    /// 1-based column of the coupling's first token on that line (0 if unknown)
+    pub column: usize,
 }
 
 /// Metrics for a single coupling relationship
@@ -23,8 +25,13 @@ pub struct CouplingMetrics {
     pub strength: IntegrationStrength,
     /// Distance between components
     pub distance: Distance,
+    /// Distance on the 0.0-1.0 scale used for scoring: `distance.value()`,
+    /// refined by module-tree position for same-crate `DifferentModule` couplings
+    pub distance_score: f64,
     /// Volatility of the target
     pub volatility: Volatility,
+    /// Static connascence form of the usage behind this coupling
+    pub connascence: Connascence,
     /// Source crate name (when workspace analysis is available)
     pub source_crate: Option<String>,
     /// Target crate name (when workspace analysis is available)
@@ -33,6 +40,13 @@ pub struct CouplingMetrics {
     pub target_visibility: Visibility,
     /// Location where the coupling occurs
     pub location: CouplingLocation,
+    /// Why strength/distance/volatility were assigned (recorded in verbose runs only)
+    pub provenance: Option<String>,
+    /// Usages folded into this coupling; above 1 only with `[analysis] merge_couplings`
+    pub occurrences: usize,
+    /// Cargo feature (or raw `cfg` predicate) the coupling is compiled under;
+    /// `None` when it is unconditional
+    pub feature: Option<String>,
 }
 
 impl CouplingMetrics {
@@ -49,11 +63,16 @@ impl CouplingMetrics {
             target,
             strength,
             distance,
+            distance_score: distance.value(),
             volatility,
+            connascence: Connascence::default(),
             source_crate: None,
             target_crate: None,
             target_visibility: Visibility::default(),
             location: CouplingLocation::default(),
+            provenance: None,
+            occurrences: 1,
+            feature: None,
         }
     }
 
@@ -71,11 +90,16 @@ impl CouplingMetrics {
             target,
             strength,
             distance,
+            distance_score: distance.value(),
             volatility,
+            connascence: Connascence::default(),
             source_crate: None,
             target_crate: None,
             target_visibility: visibility,
             location: CouplingLocation::default(),
+            provenance: None,
+            occurrences: 1,
+            feature: None,
         }
     }
 
@@ -96,14 +120,20 @@ impl CouplingMetrics {
             target,
             strength,
             distance,
+            distance_score: distance.value(),
             volatility,
+            connascence: Connascence::default(),
             source_crate: None,
             target_crate: None,
             target_visibility: visibility,
             location: CouplingLocation {
                 file_path: Some(file_path),
                 line,
+                column: 0,
             },
+            provenance: None,
+            occurrences: 1,
+            feature: None,
         }
     }
 
@@ -142,6 +172,15 @@ impl CouplingMetrics {
         self.effective_strength().value()
     }
 
+    /// Strength used for scoring: effective with the visibility penalty, raw without
+    pub fn scoring_strength(&self, visibility_penalty: bool) -> IntegrationStrength {
+        if visibility_penalty {
+            self.effective_strength()
+        } else {
+            self.strength
+        }
+    }
+
     /// Get numeric strength value
     pub fn strength_value(&self) -> f64 {
         self.strength.value()
@@ -149,7 +188,7 @@ impl CouplingMetrics {
 
     /// Get numeric distance value
     pub fn distance_value(&self) -> f64 {
-        self.distance.value()
+        self.distance_score
     }
 
     /// Get numeric volatility value
@@ -1,9 +1,11 @@
 use std::fmt;
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
 use crate::volatility::Volatility;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Visibility {
     /// Fully public (`pub`)
     Public,
@@ -69,7 +71,7 @@ impl fmt::Display for Visibility {
 }
 
 /// Integration strength levels (how much knowledge is shared)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IntegrationStrength {
     /// Strongest coupling - direct access to internals
     Intrusive,
@@ -94,7 +96,7 @@ impl IntegrationStrength {
 }
 
 /// Distance levels (how far apart components are)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Distance {
     /// Same function/block
     SameFunction,
@@ -118,8 +120,52 @@ impl Distance {
     }
 }
 
+/// Static connascence forms (Page-Jones), ordered from weakest to strongest
+///
+/// A second lens on the same couplings: where integration strength asks what
+/// knowledge is shared, connascence asks what both sides must agree on for a
+/// change to one not to break the other. Connascence of Algorithm is not
+/// listed because it cannot be told apart from syntax alone.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Connascence {
+    /// Both sides agree on the name of an item
+    #[default]
+    Name,
+    /// Both sides agree on the type of an item
+    Type,
+    /// Both sides agree on what particular values mean
+    Meaning,
+    /// Both sides agree on the order of values
+    Position,
+}
+
+impl Connascence {
+    /// Returns the numeric value (0.0 - 1.0, higher = stronger)
+    pub fn value(&self) -> f64 {
+        match self {
+            Connascence::Name => 0.2,
+            Connascence::Type => 0.4,
+            Connascence::Meaning => 0.6,
+            Connascence::Position => 0.8,
+        }
+    }
+}
+
+impl fmt::Display for Connascence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Connascence::Name => write!(f, "Name"),
+            Connascence::Type => write!(f, "Type"),
+            Connascence::Meaning => write!(f, "Meaning"),
+            Connascence::Position => write!(f, "Position"),
+        }
+    }
+}
+
 /// DDD subdomain type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Subdomain {
     /// Core subdomain - competitive advantage, high volatility
     Core,
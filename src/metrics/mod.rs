@@ -3,19 +3,31 @@
 //! It stores the structural facts collected from Rust source and exposes the
 //! aggregate calculations used to interpret coupling through Khononov's model.
 
+pub mod cohesion;
 pub mod coupling;
 pub mod dimensions;
 pub mod module;
+pub mod profile;
 pub mod project;
+pub mod stability;
 
 pub use crate::volatility::{TemporalCoupling, Volatility};
+pub use cohesion::{ModuleCohesion, TypeCluster, calculate_module_cohesion};
 pub use coupling::{CouplingLocation, CouplingMetrics};
-pub use dimensions::{Distance, IntegrationStrength, MetricsConfig, Subdomain, Visibility};
+pub use dimensions::{
+    Connascence, Distance, IntegrationStrength, MetricsConfig, Subdomain, Visibility,
+};
 pub use module::{
-    BalanceClassification, BalanceCounts, DimensionStats, DistanceCounts, FunctionDefinition,
-    ModuleMetrics, StrengthCounts, TypeDefinition, VolatilityCounts,
+    BalanceClassification, BalanceCounts, ConnascenceCounts, DerefImpl, DimensionStats,
+    DistanceCounts, FileSubmodule, FunctionDefinition, IssueSuppression, ModuleMetrics,
+    PassThroughMethod, StrengthCounts, TypeDefinition, VolatilityCounts,
+};
+pub use profile::CouplingProfile;
+pub use project::{
+    CircularDependencySummary, FeatureCoupling, MemoryProfile, MutualCoupling, MutualDirection,
+    ProjectMetrics,
 };
-pub use project::{CircularDependencySummary, ProjectMetrics};
+pub use stability::{ModuleStability, StabilityZone, calculate_module_stability};
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,6 +73,75 @@ mod tests {
         assert_eq!(project.coupling_count(), 0);
     }
 
+    #[test]
+    fn test_merging_folds_repeated_couplings_and_keeps_the_worst_volatility() {
+        let mut project = ProjectMetrics::new();
+        let coupling = |strength, volatility| {
+            CouplingMetrics::new(
+                "orders".to_string(),
+                "billing".to_string(),
+                strength,
+                Distance::DifferentModule,
+                volatility,
+            )
+        };
+        project.add_coupling(coupling(IntegrationStrength::Model, Volatility::Low));
+        let mut field_access = coupling(IntegrationStrength::Model, Volatility::High);
+        field_access.connascence = Connascence::Meaning;
+        project.add_coupling(field_access);
+        project.add_coupling(coupling(IntegrationStrength::Model, Volatility::Medium));
+        project.add_coupling(coupling(IntegrationStrength::Functional, Volatility::Low));
+
+        project.merge_duplicate_couplings();
+
+        assert_eq!(project.coupling_count(), 2);
+        let model = &project.couplings[0];
+        assert_eq!(model.strength, IntegrationStrength::Model);
+        assert_eq!(model.occurrences, 3);
+        assert_eq!(model.volatility, Volatility::High);
+        assert_eq!(model.connascence, Connascence::Meaning);
+        assert_eq!(project.couplings[1].occurrences, 1);
+    }
+
+    #[test]
+    fn test_feature_gated_couplings_are_grouped_by_feature_and_module() {
+        let mut project = ProjectMetrics::new();
+        let coupling = |source: &str, target: &str, feature: Option<&str>| {
+            let mut coupling = CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Model,
+                Distance::DifferentModule,
+                Volatility::Low,
+            );
+            coupling.feature = feature.map(str::to_string);
+            coupling
+        };
+        project.add_coupling(coupling("api", "telemetry", Some("metrics")));
+        project.add_coupling(coupling("api", "recorder", Some("metrics")));
+        project.add_coupling(coupling("worker", "telemetry", Some("metrics")));
+        project.add_coupling(coupling("api", "tls", Some("tls")));
+        project.add_coupling(coupling("api", "core", None));
+        // Also used unconditionally: merging drops the gate
+        project.add_coupling(coupling("worker", "core", Some("metrics")));
+        project.add_coupling(coupling("worker", "core", None));
+        project.merge_duplicate_couplings();
+
+        let gated = |feature: &str, module: &str, couplings| FeatureCoupling {
+            feature: feature.to_string(),
+            module: module.to_string(),
+            couplings,
+        };
+        assert_eq!(
+            project.feature_gated_couplings(),
+            [
+                gated("metrics", "api", 2),
+                gated("metrics", "worker", 1),
+                gated("tls", "api", 1),
+            ]
+        );
+    }
+
     #[test]
     fn test_circular_dependency_detection() {
         let mut project = ProjectMetrics::new();
@@ -166,6 +247,61 @@ mod tests {
         assert!(summary.affected_modules >= 2);
     }
 
+    #[test]
+    fn test_mutual_pair_is_reported_with_both_directions() {
+        let mut project = ProjectMetrics::new();
+
+        // orders uses a customers type; customers calls back into orders.
+        project.add_coupling(CouplingMetrics::new(
+            "orders".to_string(),
+            "customers".to_string(),
+            IntegrationStrength::Model,
+            Distance::DifferentModule,
+            Volatility::Low,
+        ));
+        project.add_coupling(CouplingMetrics::new(
+            "customers".to_string(),
+            "orders".to_string(),
+            IntegrationStrength::Contract,
+            Distance::DifferentModule,
+            Volatility::Low,
+        ));
+        project.add_coupling(CouplingMetrics::new(
+            "customers".to_string(),
+            "orders".to_string(),
+            IntegrationStrength::Functional,
+            Distance::DifferentModule,
+            Volatility::High,
+        ));
+        // One-way dependency is not mutual.
+        project.add_coupling(CouplingMetrics::new(
+            "orders".to_string(),
+            "billing".to_string(),
+            IntegrationStrength::Functional,
+            Distance::DifferentModule,
+            Volatility::Low,
+        ));
+
+        let mutual = project.detect_mutual_couplings();
+        assert_eq!(mutual.len(), 1);
+        let pair = &mutual[0];
+        assert_eq!(
+            (pair.module_a.as_str(), pair.module_b.as_str()),
+            ("customers", "orders")
+        );
+        assert_eq!(
+            pair.a_to_b,
+            MutualDirection {
+                strength: IntegrationStrength::Functional,
+                distance: Distance::DifferentModule,
+                volatility: Volatility::High,
+                couplings: 2,
+            }
+        );
+        assert_eq!(pair.b_to_a.strength, IntegrationStrength::Model);
+        assert_eq!(pair.b_to_a.couplings, 1);
+    }
+
     #[test]
     fn test_visibility_intrusive_detection() {
         // Public items are never intrusive
@@ -487,4 +623,36 @@ mod tests {
             "submodules should not inherit churn from the old parent module file"
         );
     }
+
+    #[test]
+    fn test_item_dependencies_are_moved_not_duplicated_into_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "pub mod a;\npub mod b;\n").unwrap();
+        std::fs::write(src.join("a.rs"), "pub struct A {\n    pub n: u32,\n}\n").unwrap();
+        std::fs::write(
+            src.join("b.rs"),
+            "use crate::a::A;\npub fn read(a: &A) -> u32 {\n    helper();\n    a.n\n}\nfn helper() {}\n",
+        )
+        .unwrap();
+
+        let per_file: usize = ["lib.rs", "a.rs", "b.rs"]
+            .iter()
+            .map(|file| {
+                crate::analyzer::analyze_rust_file_full(&src.join(file))
+                    .unwrap()
+                    .item_dependencies
+                    .len()
+            })
+            .sum();
+        assert!(per_file > 0);
+
+        let project = crate::analyzer::analyze_project_parallel(&src).unwrap();
+        let profile = project.memory_profile();
+        assert_eq!(profile.item_dependencies, per_file);
+        assert_eq!(profile.modules, 3);
+        assert_eq!(profile.couplings, project.couplings.len());
+        assert!(profile.estimated_bytes > 0);
+    }
 }
@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use crate::analyzer::ItemDependency;
 use crate::volatility::Volatility;
 
 use super::dimensions::{Distance, IntegrationStrength, Subdomain, Visibility};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeDefinition {
     /// Name of the type
     pub name: String,
@@ -22,12 +25,28 @@ pub struct TypeDefinition {
     pub has_serde_derive: bool,
     /// Number of public fields (for pub field exposure detection)
     pub public_field_count: usize,
+    /// Names of public fields (tuple fields by index)
+    pub public_fields: Vec<String>,
     /// Total number of fields
     pub total_field_count: usize,
+    /// Named fields as `(name, type)` in declaration order, types as written (structs only)
+    pub fields: Vec<(String, String)>,
+    /// Number of generic type and const parameters (lifetimes excluded)
+    pub generic_param_count: usize,
+    /// Number of variants (enums only)
+    pub variant_count: usize,
+    /// Variants carrying fields, excluding `#[non_exhaustive]` ones (enums only)
+    pub data_carrying_variant_count: usize,
+    /// Non-primitive types carried by those variants (enums only)
+    pub variant_data_types: Vec<String>,
+    /// Declared `#[non_exhaustive]`
+    pub is_non_exhaustive: bool,
+    /// Line of the type's declaration (0 if unknown)
+    pub line: usize,
 }
 
 /// Information about a function definition in a module
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDefinition {
     /// Name of the function
     pub name: String,
@@ -39,6 +58,62 @@ pub struct FunctionDefinition {
     pub primitive_param_count: usize,
     /// Parameter types (for primitive obsession detection)
     pub param_types: Vec<String>,
+    /// Line of the function's declaration (0 if unknown)
+    pub line: usize,
+    /// Whether this is an `async fn`
+    pub is_async: bool,
+    /// `self` receiver as written (`&self`, `&mut self`, ...), if any
+    pub receiver: Option<String>,
+    /// Typed parameters as `(name, type)`, types as written (`_` for patterns)
+    pub params: Vec<(String, String)>,
+    /// Return type as written, if not `()`
+    pub return_type: Option<String>,
+}
+
+/// A function or method whose body only delegates to another call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassThroughMethod {
+    /// Function name, or `Type::method` for methods
+    pub name: String,
+    /// Callee expression (e.g. `self.inner.fetch`, `store::load`)
+    pub delegate: String,
+    /// Line of the function's declaration (0 if unknown)
+    pub line: usize,
+}
+
+/// A `Deref` impl whose `Target` is richer than a primitive or a generic parameter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerefImpl {
+    /// Implementing type as written in the `impl` header
+    pub type_name: String,
+    /// `Target` type as written
+    pub target: String,
+    /// Whether the type also implements `DerefMut` in the same file
+    pub mutable: bool,
+    /// Line of the `impl` block (0 if unknown)
+    pub line: usize,
+}
+
+/// A `mod name;` declaration whose body lives in another file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSubmodule {
+    /// Module name, prefixed with the enclosing inline modules (`inner::name`)
+    pub name: String,
+    /// `#[path = "..."]` value, if the declaration has one
+    pub path: Option<PathBuf>,
+    /// Feature `cfg` predicates on the declaration and its enclosing items, outermost first
+    pub feature_gates: Vec<String>,
+}
+
+/// A `// coupling:allow <rule>(<target>)` marker silencing one issue near its line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueSuppression {
+    /// Issue rule in kebab case (`inappropriate-intimacy`)
+    pub rule: String,
+    /// Coupling target the marker is limited to; `None` matches any target
+    pub target: Option<String>,
+    /// Line of the marker comment
+    pub line: usize,
 }
 
 /// Khononov's balance classification for couplings
@@ -116,7 +191,7 @@ impl BalanceClassification {
 }
 
 /// Statistics for 3-dimensional coupling analysis
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct DimensionStats {
     /// Strength distribution
     pub strength_counts: StrengthCounts,
@@ -126,6 +201,9 @@ pub struct DimensionStats {
     pub volatility_counts: VolatilityCounts,
     /// Balance classification counts
     pub balance_counts: BalanceCounts,
+    /// Connascence form distribution
+    #[serde(default)]
+    pub connascence_counts: ConnascenceCounts,
 }
 
 impl DimensionStats {
@@ -174,6 +252,20 @@ impl DimensionStats {
         )
     }
 
+    /// Get percentage of each connascence form
+    pub fn connascence_percentages(&self) -> (f64, f64, f64, f64) {
+        let total = self.total() as f64;
+        if total == 0.0 {
+            return (0.0, 0.0, 0.0, 0.0);
+        }
+        (
+            self.connascence_counts.name as f64 / total * 100.0,
+            self.connascence_counts.type_ as f64 / total * 100.0,
+            self.connascence_counts.meaning as f64 / total * 100.0,
+            self.connascence_counts.position as f64 / total * 100.0,
+        )
+    }
+
     /// Count of ideal couplings (High Cohesion + Loose Coupling)
     pub fn ideal_count(&self) -> usize {
         self.balance_counts.high_cohesion + self.balance_counts.loose_coupling
@@ -195,7 +287,7 @@ impl DimensionStats {
 }
 
 /// Counts for each strength level
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct StrengthCounts {
     /// Number of intrusive-strength couplings.
     pub intrusive: usize,
@@ -215,7 +307,7 @@ impl StrengthCounts {
 }
 
 /// Counts for each distance level
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct DistanceCounts {
     /// Couplings within one module or function.
     pub same_module: usize,
@@ -226,7 +318,7 @@ pub struct DistanceCounts {
 }
 
 /// Counts for each volatility level
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct VolatilityCounts {
     /// Couplings whose target rarely changes.
     pub low: usize,
@@ -236,8 +328,22 @@ pub struct VolatilityCounts {
     pub high: usize,
 }
 
+/// Counts for each connascence form
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ConnascenceCounts {
+    /// Couplings agreeing only on names.
+    pub name: usize,
+    /// Couplings agreeing on types.
+    #[serde(rename = "type")]
+    pub type_: usize,
+    /// Couplings agreeing on what values mean.
+    pub meaning: usize,
+    /// Couplings agreeing on the order of values.
+    pub position: usize,
+}
+
 /// Counts for each balance classification
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct BalanceCounts {
     /// Strong and close couplings.
     pub high_cohesion: usize,
@@ -252,7 +358,7 @@ pub struct BalanceCounts {
 }
 
 /// Aggregated metrics for a module
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ModuleMetrics {
     /// Module path
     pub path: PathBuf,
@@ -270,6 +376,10 @@ pub struct ModuleMetrics {
     pub external_deps: Vec<String>,
     /// Internal module dependencies
     pub internal_deps: Vec<String>,
+    /// External crates imported only in test code (`#[cfg(test)]`, `mod tests`)
+    pub test_external_deps: Vec<String>,
+    /// Internal paths imported only in test code
+    pub test_internal_deps: Vec<String>,
     /// Type definitions in this module with visibility info
     pub type_definitions: HashMap<String, TypeDefinition>,
     /// Function definitions in this module with visibility info
@@ -282,6 +392,39 @@ pub struct ModuleMetrics {
     pub test_function_count: usize,
     /// DDD subdomain classification from config, if configured.
     pub subdomain: Option<Subdomain>,
+    /// Cross-module glob imports (`use crate::models::*`)
+    pub glob_imports: Vec<String>,
+    /// `pub mod` declarations (child module names)
+    pub public_submodules: Vec<String>,
+    /// `mod name;` declarations, public or not, whose bodies live in other files
+    pub file_submodules: Vec<FileSubmodule>,
+    /// Module whose `mod` declaration loads this file (set by `ProjectMetrics::link_module_tree`)
+    pub parent_module: Option<String>,
+    /// Feature `cfg` predicates on the `mod` declarations that load this file, outermost
+    /// first (set by `ProjectMetrics::link_module_tree`)
+    pub feature_gates: Vec<String>,
+    /// Paths re-exported with `pub use`
+    pub reexports: Vec<String>,
+    /// Distinct paths named inside macro bodies (recorded as Import-strength dependencies)
+    pub macro_dependency_count: usize,
+    /// `async fn` functions and methods, test functions excluded
+    pub async_function_count: usize,
+    /// Public functions and methods that are not `async`
+    pub sync_public_function_count: usize,
+    /// `.await` expressions (runtime coupling to an executor)
+    pub await_count: usize,
+    /// `unsafe { .. }` blocks, test code excluded
+    pub unsafe_block_count: usize,
+    /// `unsafe fn` functions, methods and trait methods, test code excluded
+    pub unsafe_fn_count: usize,
+    /// Functions and inherent methods that only delegate to another call
+    pub pass_through_methods: Vec<PassThroughMethod>,
+    /// `Deref` impls exposing a non-primitive `Target`, test code excluded
+    pub deref_impls: Vec<DerefImpl>,
+    /// Rules named by `// coupling:allow <rule>` markers in the file's leading comments
+    pub allowed_rules: Vec<String>,
+    /// `// coupling:allow <rule>(<target>)` comments below the leading comments
+    pub suppressions: Vec<IssueSuppression>,
 }
 
 impl ModuleMetrics {
@@ -296,6 +439,17 @@ impl ModuleMetrics {
 
     /// Add a type definition to this module (simple version for backward compatibility)
     pub fn add_type_definition(&mut self, name: String, visibility: Visibility, is_trait: bool) {
+        self.add_type_definition_at_line(name, visibility, is_trait, 0);
+    }
+
+    /// Add a type definition declared at a known source line
+    pub fn add_type_definition_at_line(
+        &mut self,
+        name: String,
+        visibility: Visibility,
+        is_trait: bool,
+        line: usize,
+    ) {
         self.type_definitions.insert(
             name.clone(),
             TypeDefinition {
@@ -306,7 +460,15 @@ impl ModuleMetrics {
                 inner_type: None,
                 has_serde_derive: false,
                 public_field_count: 0,
+                public_fields: Vec::new(),
                 total_field_count: 0,
+                fields: Vec::new(),
+                generic_param_count: 0,
+                variant_count: 0,
+                data_carrying_variant_count: 0,
+                variant_data_types: Vec::new(),
+                is_non_exhaustive: false,
+                line,
             },
         );
     }
@@ -321,8 +483,9 @@ impl ModuleMetrics {
         is_newtype: bool,
         inner_type: Option<String>,
         has_serde_derive: bool,
-        public_field_count: usize,
+        public_fields: Vec<String>,
         total_field_count: usize,
+        line: usize,
     ) {
         self.type_definitions.insert(
             name.clone(),
@@ -333,12 +496,73 @@ impl ModuleMetrics {
                 is_newtype,
                 inner_type,
                 has_serde_derive,
-                public_field_count,
+                public_field_count: public_fields.len(),
+                public_fields,
                 total_field_count,
+                fields: Vec::new(),
+                generic_param_count: 0,
+                variant_count: 0,
+                data_carrying_variant_count: 0,
+                variant_data_types: Vec::new(),
+                is_non_exhaustive: false,
+                line,
             },
         );
     }
 
+    /// Record the generic parameter count of an already registered type
+    pub fn set_type_generic_param_count(&mut self, name: &str, count: usize) {
+        if let Some(type_def) = self.type_definitions.get_mut(name) {
+            type_def.generic_param_count = count;
+        }
+    }
+
+    /// Record the variant shape of an already registered enum
+    pub fn set_enum_variants(
+        &mut self,
+        name: &str,
+        variant_count: usize,
+        data_carrying_variant_count: usize,
+        variant_data_types: Vec<String>,
+        is_non_exhaustive: bool,
+    ) {
+        if let Some(type_def) = self.type_definitions.get_mut(name) {
+            type_def.variant_count = variant_count;
+            type_def.data_carrying_variant_count = data_carrying_variant_count;
+            type_def.variant_data_types = variant_data_types;
+            type_def.is_non_exhaustive = is_non_exhaustive;
+        }
+    }
+
+    /// Record the named fields of an already registered struct
+    pub fn set_struct_fields(&mut self, name: &str, fields: Vec<(String, String)>) {
+        if let Some(type_def) = self.type_definitions.get_mut(name) {
+            type_def.fields = fields;
+        }
+    }
+
+    /// Mark an already registered function as `async fn`
+    pub fn mark_function_async(&mut self, name: &str) {
+        if let Some(func_def) = self.function_definitions.get_mut(name) {
+            func_def.is_async = true;
+        }
+    }
+
+    /// Record the signature of an already registered function
+    pub fn set_function_signature(
+        &mut self,
+        name: &str,
+        receiver: Option<String>,
+        params: Vec<(String, String)>,
+        return_type: Option<String>,
+    ) {
+        if let Some(func_def) = self.function_definitions.get_mut(name) {
+            func_def.receiver = receiver;
+            func_def.params = params;
+            func_def.return_type = return_type;
+        }
+    }
+
     /// Add a function definition to this module (simple version for backward compatibility)
     pub fn add_function_definition(&mut self, name: String, visibility: Visibility) {
         self.function_definitions.insert(
@@ -349,6 +573,11 @@ impl ModuleMetrics {
                 param_count: 0,
                 primitive_param_count: 0,
                 param_types: Vec::new(),
+                line: 0,
+                is_async: false,
+                receiver: None,
+                params: Vec::new(),
+                return_type: None,
             },
         );
     }
@@ -361,6 +590,7 @@ impl ModuleMetrics {
         param_count: usize,
         primitive_param_count: usize,
         param_types: Vec<String>,
+        line: usize,
     ) {
         self.function_definitions.insert(
             name.clone(),
@@ -370,6 +600,11 @@ impl ModuleMetrics {
                 param_count,
                 primitive_param_count,
                 param_types,
+                line,
+                is_async: false,
+                receiver: None,
+                params: Vec::new(),
+                return_type: None,
             },
         );
     }
@@ -462,6 +697,35 @@ impl ModuleMetrics {
             .collect()
     }
 
+    /// Count public functions
+    pub fn public_function_count(&self) -> usize {
+        self.function_definitions
+            .values()
+            .filter(|f| f.visibility == Visibility::Public)
+            .count()
+    }
+
+    /// Interface size proxy: public functions, public types and their public fields
+    pub fn interface_size(&self) -> usize {
+        let public_fields: usize = self
+            .type_definitions
+            .values()
+            .filter(|t| t.visibility == Visibility::Public)
+            .map(|t| t.public_field_count)
+            .sum();
+        self.public_function_count() + self.public_type_count() + public_fields
+    }
+
+    /// Implementation size proxy: all functions plus item-level dependencies
+    pub fn implementation_size(&self) -> usize {
+        self.function_count() + self.item_dependencies.len()
+    }
+
+    /// Whether a `// coupling:allow` marker in this module's file names `rule`
+    pub fn allows(&self, rule: &str) -> bool {
+        self.allowed_rules.iter().any(|allowed| allowed == rule)
+    }
+
     /// Check if this module is a potential "God Module"
     /// (too many functions, types, or implementations)
     pub fn is_god_module(&self, max_functions: usize, max_types: usize, max_impls: usize) -> bool {
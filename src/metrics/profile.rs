@@ -0,0 +1,152 @@
+//! Coupling profile fingerprint.
+//!
+//! A profile normalizes the strength and distance distributions of a project's
+//! couplings into shares that sum to 1.0, so crates of different sizes can be
+//! compared and drift can be tracked between runs.
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::module::DimensionStats;
+
+/// Normalized strength and distance distributions of a project's couplings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CouplingProfile {
+    /// Share of intrusive-strength couplings (0.0-1.0).
+    pub intrusive: f64,
+    /// Share of functional-strength couplings (0.0-1.0).
+    pub functional: f64,
+    /// Share of model-strength couplings (0.0-1.0).
+    pub model: f64,
+    /// Share of contract-strength couplings (0.0-1.0).
+    pub contract: f64,
+    /// Share of couplings within one module (0.0-1.0).
+    pub same_module: f64,
+    /// Share of couplings across modules of the same crate (0.0-1.0).
+    pub different_module: f64,
+    /// Share of couplings to external crates (0.0-1.0).
+    pub external: f64,
+}
+
+impl CouplingProfile {
+    /// Build a profile from dimension statistics; all shares are 0.0 without couplings.
+    pub fn from_stats(stats: &DimensionStats) -> Self {
+        let total = stats.total() as f64;
+        if total == 0.0 {
+            return Self::default();
+        }
+
+        Self {
+            intrusive: stats.strength_counts.intrusive as f64 / total,
+            functional: stats.strength_counts.functional as f64 / total,
+            model: stats.strength_counts.model as f64 / total,
+            contract: stats.strength_counts.contract as f64 / total,
+            same_module: stats.distance_counts.same_module as f64 / total,
+            different_module: stats.distance_counts.different_module as f64 / total,
+            external: stats.distance_counts.different_crate as f64 / total,
+        }
+    }
+
+    /// Distance between two profiles, from 0.0 (identical) to 1.0 (disjoint).
+    ///
+    /// This is the total variation distance of each distribution, averaged over
+    /// the strength and distance dimensions.
+    pub fn distance_to(&self, other: &CouplingProfile) -> f64 {
+        let strength = [
+            self.intrusive - other.intrusive,
+            self.functional - other.functional,
+            self.model - other.model,
+            self.contract - other.contract,
+        ];
+        let distance = [
+            self.same_module - other.same_module,
+            self.different_module - other.different_module,
+            self.external - other.external,
+        ];
+
+        let total_variation = |diffs: &[f64]| diffs.iter().map(|d| d.abs()).sum::<f64>() / 2.0;
+        (total_variation(&strength) + total_variation(&distance)) / 2.0
+    }
+}
+
+impl fmt::Display for CouplingProfile {
+    /// Formats as `60% Model, 25% Functional, ...; 40% same-module, ...`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut strengths = [
+            ("Intrusive", self.intrusive),
+            ("Functional", self.functional),
+            ("Model", self.model),
+            ("Contract", self.contract),
+        ];
+        strengths.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let strength_parts: Vec<String> = strengths
+            .iter()
+            .map(|(label, share)| format!("{:.0}% {}", share * 100.0, label))
+            .collect();
+        write!(
+            f,
+            "{}; {:.0}% same-module, {:.0}% different-module, {:.0}% external",
+            strength_parts.join(", "),
+            self.same_module * 100.0,
+            self.different_module * 100.0,
+            self.external * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::coupling::CouplingMetrics;
+    use crate::metrics::dimensions::{Distance, IntegrationStrength, Visibility};
+    use crate::metrics::project::ProjectMetrics;
+    use crate::volatility::Volatility;
+
+    fn project(couplings: &[(IntegrationStrength, Distance)]) -> ProjectMetrics {
+        let mut metrics = ProjectMetrics::new();
+        for (idx, (strength, distance)) in couplings.iter().enumerate() {
+            metrics.add_coupling(CouplingMetrics::with_visibility(
+                format!("source{idx}"),
+                format!("target{idx}"),
+                *strength,
+                *distance,
+                Volatility::Low,
+                Visibility::Public,
+            ));
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_profile_normalizes_and_compares_distributions() {
+        use Distance::*;
+        use IntegrationStrength::*;
+
+        let model_heavy = project(&[
+            (Model, DifferentModule),
+            (Model, DifferentModule),
+            (Model, SameModule),
+            (Functional, DifferentCrate),
+        ]);
+        let profile = model_heavy.coupling_profile();
+        assert_eq!(profile.model, 0.75);
+        assert_eq!(profile.functional, 0.25);
+        assert_eq!(profile.different_module, 0.5);
+        assert_eq!(profile.same_module, 0.25);
+        assert_eq!(profile.external, 0.25);
+        assert_eq!(
+            profile.to_string(),
+            "75% Model, 25% Functional, 0% Intrusive, 0% Contract; 25% same-module, 50% different-module, 25% external"
+        );
+
+        assert_eq!(profile.distance_to(&profile), 0.0);
+
+        let intrusive_local = project(&[(Intrusive, SameModule), (Intrusive, SameModule)]);
+        let other = intrusive_local.coupling_profile();
+        // Strength distributions are disjoint (1.0); distance differs by 0.75.
+        assert!((profile.distance_to(&other) - 0.875).abs() < 1e-9);
+        assert_eq!(profile.distance_to(&other), other.distance_to(&profile));
+    }
+}
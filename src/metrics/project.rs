@@ -1,13 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
 
+use crate::analyzer::ItemDependency;
+use crate::discovery::canonical_file_key;
+use crate::timing::AnalysisTimings;
 use crate::volatility::{TemporalCoupling, Volatility};
+use crate::workspace::CrateKind;
 
 use super::coupling::CouplingMetrics;
-use super::dimensions::{Distance, IntegrationStrength, MetricsConfig, Visibility};
+use super::dimensions::{Connascence, Distance, IntegrationStrength, MetricsConfig, Visibility};
 use super::module::{
-    BalanceClassification, DimensionStats, FunctionDefinition, ModuleMetrics, TypeDefinition,
+    BalanceClassification, DimensionStats, FileSubmodule, FunctionDefinition, ModuleMetrics,
+    TypeDefinition,
 };
+use super::profile::CouplingProfile;
 
 #[derive(Debug, Default)]
 pub struct ProjectMetrics {
@@ -21,22 +27,50 @@ pub struct ProjectMetrics {
     pub total_files: usize,
     /// Source files that failed to parse or analyze and were skipped.
     pub parse_failures: usize,
+    /// Source files skipped because their header carries a generated-code marker.
+    pub generated_files: usize,
+    /// Dependency paths dropped as likely local variables, with how often each was seen.
+    pub dropped_dependency_paths: BTreeMap<String, usize>,
     /// Workspace members with no discoverable source files.
     pub skipped_crates: Vec<String>,
+    /// Directories (or the single file) searched for source files
+    pub searched_paths: Vec<PathBuf>,
+    /// Paths given for a multi-root analysis, in order (empty for a single root)
+    pub analysis_roots: Vec<PathBuf>,
+    /// Members analyzed and listed but left out of the health grade, with the kind that excluded them.
+    pub grade_excluded_crates: Vec<(String, CrateKind)>,
+    /// Module names belonging to `grade_excluded_crates`.
+    pub grade_excluded_modules: HashSet<String>,
+    /// Prelude-like modules (bare and crate-qualified names) exempt from afferent checks.
+    pub prelude_modules: HashSet<String>,
     /// Module references skipped because they cross analyzed package/workspace boundaries.
     pub boundary_skipped_files: usize,
+    /// Modules no `mod` declaration loads although their would-be parent file was analyzed.
+    pub orphaned_modules: Vec<String>,
     /// Config patterns that matched no paths in the analysis candidate set.
     pub dead_config_patterns: Vec<String>,
     /// Workspace name (if available from cargo metadata)
     pub workspace_name: Option<String>,
     /// Workspace member crate names
     pub workspace_members: Vec<String>,
+    /// Crate each module belongs to (workspace analysis only)
+    pub module_crates: HashMap<String, String>,
+    /// Rust edition per workspace member (crate name -> edition)
+    pub crate_editions: HashMap<String, String>,
+    /// Parse failures consistent with an edition mismatch (`path (edition N): hint`)
+    pub edition_parse_failures: Vec<String>,
     /// Crate-level dependencies (crate name -> list of dependencies)
     pub crate_dependencies: HashMap<String, Vec<String>>,
     /// Global type registry: type name -> (module name, visibility)
     pub type_registry: HashMap<String, (String, Visibility)>,
+    /// `pub use` re-exports: crate-relative exported path -> path it re-exports
+    pub reexport_targets: HashMap<String, String>,
     /// Temporal coupling data (files that co-change frequently)
     pub temporal_couplings: Vec<TemporalCoupling>,
+    /// Time spent in each phase of building these metrics
+    pub analysis_timings: AnalysisTimings,
+    /// `CompiledConfig::config_hash` of the run, written to output headers
+    pub config_hash: Option<String>,
 }
 
 impl ProjectMetrics {
@@ -45,16 +79,168 @@ impl ProjectMetrics {
         Self::default()
     }
 
+    /// Whether a module or `crate::module` name belongs to a crate left out of the
+    /// grade, or is never compiled (see [`is_orphaned`](Self::is_orphaned)).
+    pub fn is_excluded_from_grade(&self, name: &str) -> bool {
+        self.grade_excluded_modules.contains(name)
+            || self.grade_excluded_crates.iter().any(|(crate_name, _)| {
+                name.strip_prefix(crate_name.as_str())
+                    .is_some_and(|rest| rest.starts_with("::"))
+            })
+            || self.is_orphaned(name)
+    }
+
+    /// Whether a module is in `orphaned_modules` or declared, through its
+    /// `parent_module` chain, by one that is: rustc compiles neither.
+    pub fn is_orphaned(&self, name: &str) -> bool {
+        if self.orphaned_modules.is_empty() {
+            return false;
+        }
+        let mut current = name;
+        for _ in 0..=self.modules.len() {
+            if self.orphaned_modules.iter().any(|orphan| orphan == current) {
+                return true;
+            }
+            match self
+                .modules
+                .get(current)
+                .and_then(|module| module.parent_module.as_deref())
+            {
+                Some(parent) => current = parent,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Whether a module or `crate::module` name is configured as prelude-like.
+    pub fn is_prelude_module(&self, name: &str) -> bool {
+        self.prelude_modules.contains(name)
+    }
+
+    /// Note a dependency path the local-variable heuristics dropped
+    pub fn record_dropped_path(&mut self, path: &str) {
+        *self
+            .dropped_dependency_paths
+            .entry(path.to_string())
+            .or_insert(0) += 1;
+    }
+
     /// Add module metrics
     pub fn add_module(&mut self, metrics: ModuleMetrics) {
         self.modules.insert(metrics.name.clone(), metrics);
     }
 
+    /// Link every module to the module whose `mod name;` declaration loads its file.
+    ///
+    /// A module left unlinked is recorded in `orphaned_modules` when the file that
+    /// would declare it in the standard layout (`dir/mod.rs`, `dir.rs`, `dir/lib.rs`
+    /// or `dir/main.rs`) was analyzed: rustc never compiles such a file. Crate roots
+    /// have no such parent and are never reported.
+    pub fn link_module_tree(&mut self) {
+        let files: HashMap<PathBuf, String> = self
+            .modules
+            .values()
+            .map(|module| (canonical_file_key(&module.path), module.name.clone()))
+            .collect();
+
+        // child -> (declaring module, feature gates on the declaration)
+        let mut parents: HashMap<String, (String, Vec<String>)> = HashMap::new();
+        for module in self.modules.values() {
+            for submodule in &module.file_submodules {
+                let child = submodule_file_candidates(&module.path, submodule)
+                    .into_iter()
+                    .find_map(|candidate| files.get(&canonical_file_key(&candidate)));
+                if let Some(child) = child.filter(|child| **child != module.name) {
+                    parents
+                        .entry(child.clone())
+                        .or_insert_with(|| (module.name.clone(), submodule.feature_gates.clone()));
+                }
+            }
+        }
+
+        // A gate on `mod name;` applies to everything the loaded file, and the
+        // files it declares in turn, contain
+        let inherited_gates = |name: &str| {
+            let mut chain = Vec::new();
+            let mut current = name;
+            while let Some((parent, gates)) = parents.get(current) {
+                if chain.len() > parents.len() {
+                    break;
+                }
+                chain.push(gates);
+                current = parent;
+            }
+            chain
+                .into_iter()
+                .rev()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let gates: HashMap<String, Vec<String>> = self
+            .modules
+            .keys()
+            .map(|name| (name.clone(), inherited_gates(name)))
+            .collect();
+
+        let mut orphaned = Vec::new();
+        for module in self.modules.values_mut() {
+            module.parent_module = parents.get(&module.name).map(|(parent, _)| parent.clone());
+            module.feature_gates = gates.get(&module.name).cloned().unwrap_or_default();
+            if module.parent_module.is_none()
+                && standard_parent_files(&module.path)
+                    .iter()
+                    .any(|parent| files.contains_key(&canonical_file_key(parent)))
+            {
+                orphaned.push(module.name.clone());
+            }
+        }
+        orphaned.sort();
+        self.orphaned_modules = orphaned;
+    }
+
     /// Add coupling
     pub fn add_coupling(&mut self, coupling: CouplingMetrics) {
         self.couplings.push(coupling);
     }
 
+    /// Fold couplings sharing `(source, target, strength, distance)` into one.
+    ///
+    /// The first usage keeps its location; the merged coupling takes the highest
+    /// volatility and strongest connascence of its usages and counts them in
+    /// `occurrences`. Order of first appearance is preserved.
+    pub fn merge_duplicate_couplings(&mut self) {
+        let mut merged: Vec<CouplingMetrics> = Vec::with_capacity(self.couplings.len());
+        let mut index: HashMap<(String, String, IntegrationStrength, Distance), usize> =
+            HashMap::new();
+        for coupling in std::mem::take(&mut self.couplings) {
+            let key = (
+                coupling.source.clone(),
+                coupling.target.clone(),
+                coupling.strength,
+                coupling.distance,
+            );
+            match index.get(&key) {
+                Some(&idx) => {
+                    let existing = &mut merged[idx];
+                    existing.volatility = existing.volatility.max(coupling.volatility);
+                    existing.connascence = existing.connascence.max(coupling.connascence);
+                    existing.occurrences += coupling.occurrences;
+                    // Unconditional if any of its usages is
+                    if coupling.feature.is_none() {
+                        existing.feature = None;
+                    }
+                }
+                None => {
+                    index.insert(key, merged.len());
+                    merged.push(coupling);
+                }
+            }
+        }
+        self.couplings = merged;
+    }
+
     /// Register a type definition in the global registry
     pub fn register_type(
         &mut self,
@@ -77,6 +263,47 @@ impl ProjectMetrics {
         }
     }
 
+    /// Record that `exported` (e.g. `prelude::Config`) re-exports `original`.
+    ///
+    /// The first registration of an exported path wins.
+    pub fn register_reexport(&mut self, exported: String, original: String) {
+        if exported != original {
+            self.reexport_targets.entry(exported).or_insert(original);
+        }
+    }
+
+    /// Follow `pub use` re-exports until `path` names where its item is defined.
+    ///
+    /// A re-exported prefix is replaced too (`prelude::Config::new` becomes
+    /// `config::Config::new`), and chains resolve transitively. Each re-export
+    /// is followed at most once, so cycles and self-nesting re-exports (a
+    /// `pub use self::a::a` that keeps growing the path) terminate.
+    pub fn resolve_reexport(&self, path: &str) -> String {
+        let mut current = path.to_string();
+        let mut followed = HashSet::new();
+        loop {
+            let segments: Vec<&str> = current.split("::").collect();
+            let Some((len, exported, original)) = (1..=segments.len()).rev().find_map(|len| {
+                let exported = segments[..len].join("::");
+                self.reexport_targets
+                    .get(&exported)
+                    .map(|original| (len, exported, original))
+            }) else {
+                break;
+            };
+            if !followed.insert(exported) {
+                break;
+            }
+            let mut next = original.clone();
+            for segment in &segments[len..] {
+                next.push_str("::");
+                next.push_str(segment);
+            }
+            current = next;
+        }
+        current
+    }
+
     /// Look up visibility of a type by name
     pub fn get_type_visibility(&self, type_name: &str) -> Option<Visibility> {
         self.type_registry.get(type_name).map(|(_, vis)| *vis)
@@ -117,6 +344,81 @@ impl ProjectMetrics {
         }
     }
 
+    /// Internal couplings per module as `(outgoing, incoming)`, keyed by the
+    /// coupling's source and target. Couplings to external crates are not counted.
+    pub fn internal_coupling_counts(&self) -> (HashMap<&str, usize>, HashMap<&str, usize>) {
+        let mut efferent: HashMap<&str, usize> = HashMap::new();
+        let mut afferent: HashMap<&str, usize> = HashMap::new();
+        for coupling in &self.couplings {
+            if coupling.distance == Distance::DifferentCrate {
+                continue;
+            }
+            *efferent.entry(&coupling.source).or_insert(0) += 1;
+            *afferent.entry(&coupling.target).or_insert(0) += 1;
+        }
+        (efferent, afferent)
+    }
+
+    /// Crates owning at least one analyzed module, sorted by name
+    pub fn crate_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.module_crates.values().map(String::as_str).collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Metrics restricted to one crate: its modules and the couplings it originates
+    ///
+    /// Couplings into other workspace crates stay in, so cross-crate dependencies
+    /// still count against the crate that takes them on.
+    pub fn crate_metrics(&self, crate_name: &str) -> ProjectMetrics {
+        let modules: HashMap<String, ModuleMetrics> = self
+            .modules
+            .iter()
+            .filter(|(name, _)| {
+                self.module_crates.get(*name).map(String::as_str) == Some(crate_name)
+            })
+            .map(|(name, module)| (name.clone(), module.clone()))
+            .collect();
+        ProjectMetrics {
+            total_files: modules.len(),
+            module_crates: modules
+                .keys()
+                .map(|name| (name.clone(), crate_name.to_string()))
+                .collect(),
+            modules,
+            couplings: self
+                .couplings
+                .iter()
+                .filter(|coupling| coupling.source_crate.as_deref() == Some(crate_name))
+                .cloned()
+                .collect(),
+            file_changes: self.file_changes.clone(),
+            grade_excluded_crates: self.grade_excluded_crates.clone(),
+            grade_excluded_modules: self.grade_excluded_modules.clone(),
+            prelude_modules: self.prelude_modules.clone(),
+            workspace_name: Some(crate_name.to_string()),
+            workspace_members: vec![crate_name.to_string()],
+            crate_editions: self
+                .crate_editions
+                .iter()
+                .filter(|(name, _)| *name == crate_name)
+                .map(|(name, edition)| (name.clone(), edition.clone()))
+                .collect(),
+            crate_dependencies: self
+                .crate_dependencies
+                .iter()
+                .filter(|(name, _)| *name == crate_name)
+                .map(|(name, deps)| (name.clone(), deps.clone()))
+                .collect(),
+            type_registry: self.type_registry.clone(),
+            reexport_targets: self.reexport_targets.clone(),
+            temporal_couplings: self.temporal_couplings.clone(),
+            config_hash: self.config_hash.clone(),
+            ..ProjectMetrics::default()
+        }
+    }
+
     /// Get total module count
     pub fn module_count(&self) -> usize {
         self.modules.len()
@@ -177,10 +479,16 @@ impl ProjectMetrics {
 
         for coupling in &mut self.couplings {
             if let Some(module_path) = target_module_path(&coupling.target, &module_paths) {
-                coupling.volatility = Volatility::from_count(change_count_for_module_path(
-                    module_path,
-                    &self.file_changes,
-                ));
+                let changes = change_count_for_module_path(module_path, &self.file_changes);
+                coupling.volatility = Volatility::from_count(changes);
+                if let Some(provenance) = &mut coupling.provenance {
+                    provenance.push_str(&format!(
+                        "; volatility {:?}: {} change(s) to {} (module path match)",
+                        coupling.volatility,
+                        changes,
+                        module_path.display()
+                    ));
+                }
                 continue;
             }
 
@@ -198,6 +506,7 @@ impl ProjectMetrics {
 
             // Find the best matching file
             let mut max_target_changes = 0usize;
+            let mut best_match: Option<&str> = None;
             for (file_path, &changes) in &self.file_changes {
                 // Get file name without .rs extension (e.g., "balance" from "src/balance.rs")
                 let file_name = file_path
@@ -242,12 +551,25 @@ impl ProjectMetrics {
                     false
                 });
 
-                if target_matches_file {
-                    max_target_changes = max_target_changes.max(changes);
+                if target_matches_file && (best_match.is_none() || changes > max_target_changes) {
+                    max_target_changes = changes;
+                    best_match = Some(file_path);
                 }
             }
 
             coupling.volatility = Volatility::from_count(max_target_changes);
+            if let Some(provenance) = &mut coupling.provenance {
+                match best_match {
+                    Some(file_path) => provenance.push_str(&format!(
+                        "; volatility {:?}: {} change(s) to {} (file name match)",
+                        coupling.volatility, max_target_changes, file_path
+                    )),
+                    None => provenance.push_str(&format!(
+                        "; volatility {:?}: no changed file matches the target",
+                        coupling.volatility
+                    )),
+                }
+            }
         }
     }
 
@@ -300,8 +622,8 @@ impl ProjectMetrics {
         override_count
     }
 
-    /// Build a dependency graph from couplings
-    fn build_dependency_graph(&self) -> HashMap<String, HashSet<String>> {
+    /// Internal dependency graph: coupling source -> targets, external crates excluded
+    pub fn build_dependency_graph(&self) -> HashMap<String, HashSet<String>> {
         let mut graph: HashMap<String, HashSet<String>> = HashMap::new();
 
         for coupling in &self.couplings {
@@ -426,16 +748,114 @@ impl ProjectMetrics {
         }
     }
 
+    /// Detect mutual couplings: module pairs that depend on each other (A ↔ B).
+    ///
+    /// Each pair is also a two-module cycle, but here both directions are kept
+    /// with their own strength, distance, and volatility so the nature of the
+    /// mutual dependency is visible. Each direction is represented by its
+    /// strongest coupling. Pairs are sorted by combined strength, strongest first.
+    pub fn detect_mutual_couplings(&self) -> Vec<MutualCoupling> {
+        let mut directions: HashMap<(&str, &str), MutualDirection> = HashMap::new();
+
+        for coupling in &self.couplings {
+            if coupling.distance == Distance::DifferentCrate || coupling.source == coupling.target {
+                continue;
+            }
+
+            let key = (coupling.source.as_str(), coupling.target.as_str());
+            let candidate = MutualDirection {
+                strength: coupling.strength,
+                distance: coupling.distance,
+                volatility: coupling.volatility,
+                couplings: 1,
+            };
+            directions
+                .entry(key)
+                .and_modify(|direction| {
+                    let count = direction.couplings + 1;
+                    let stronger = (candidate.strength.value(), candidate.volatility)
+                        > (direction.strength.value(), direction.volatility);
+                    if stronger {
+                        *direction = candidate.clone();
+                    }
+                    direction.couplings = count;
+                })
+                .or_insert(candidate);
+        }
+
+        let mut mutual: Vec<MutualCoupling> = directions
+            .iter()
+            .filter(|((source, target), _)| source < target)
+            .filter_map(|(&(source, target), a_to_b)| {
+                let b_to_a = directions.get(&(target, source))?;
+                Some(MutualCoupling {
+                    module_a: source.to_string(),
+                    module_b: target.to_string(),
+                    a_to_b: a_to_b.clone(),
+                    b_to_a: b_to_a.clone(),
+                })
+            })
+            .collect();
+
+        mutual.sort_by(|a, b| {
+            b.combined_strength()
+                .total_cmp(&a.combined_strength())
+                .then_with(|| a.module_a.cmp(&b.module_a))
+                .then_with(|| a.module_b.cmp(&b.module_b))
+        });
+        mutual
+    }
+
+    /// Couplings compiled only under a Cargo feature, grouped by feature and
+    /// source module: what enabling each feature adds to each module.
+    ///
+    /// Sorted by feature, then by coupling count (most first) and module.
+    pub fn feature_gated_couplings(&self) -> Vec<FeatureCoupling> {
+        let mut counts: HashMap<(&str, &str), usize> = HashMap::new();
+        for coupling in &self.couplings {
+            if let Some(feature) = &coupling.feature {
+                *counts
+                    .entry((feature.as_str(), coupling.source.as_str()))
+                    .or_default() += coupling.occurrences;
+            }
+        }
+
+        let mut gated: Vec<FeatureCoupling> = counts
+            .into_iter()
+            .map(|((feature, module), couplings)| FeatureCoupling {
+                feature: feature.to_string(),
+                module: module.to_string(),
+                couplings,
+            })
+            .collect();
+        gated.sort_by(|a, b| {
+            a.feature
+                .cmp(&b.feature)
+                .then_with(|| b.couplings.cmp(&a.couplings))
+                .then_with(|| a.module.cmp(&b.module))
+        });
+        gated
+    }
+
     /// Calculate 3-dimensional coupling statistics
     ///
     /// Computes distribution of couplings across Strength, Distance,
-    /// Volatility, and Balance Classification dimensions.
+    /// Volatility, and Balance Classification dimensions, plus the
+    /// connascence form of each coupling. Strength is the effective strength,
+    /// as scored by default.
     pub fn calculate_dimension_stats(&self) -> DimensionStats {
+        self.calculate_dimension_stats_with(true)
+    }
+
+    /// `calculate_dimension_stats`, counting raw strength when `visibility_penalty` is off
+    pub fn calculate_dimension_stats_with(&self, visibility_penalty: bool) -> DimensionStats {
         let mut stats = DimensionStats::default();
 
         for coupling in &self.couplings {
+            let strength = coupling.scoring_strength(visibility_penalty);
+
             // Count strength distribution
-            match coupling.strength {
+            match strength {
                 IntegrationStrength::Intrusive => stats.strength_counts.intrusive += 1,
                 IntegrationStrength::Functional => stats.strength_counts.functional += 1,
                 IntegrationStrength::Model => stats.strength_counts.model += 1,
@@ -458,12 +878,17 @@ impl ProjectMetrics {
                 Volatility::High => stats.volatility_counts.high += 1,
             }
 
+            // Count connascence forms
+            match coupling.connascence {
+                Connascence::Name => stats.connascence_counts.name += 1,
+                Connascence::Type => stats.connascence_counts.type_ += 1,
+                Connascence::Meaning => stats.connascence_counts.meaning += 1,
+                Connascence::Position => stats.connascence_counts.position += 1,
+            }
+
             // Classify and count balance
-            let classification = BalanceClassification::classify(
-                coupling.strength,
-                coupling.distance,
-                coupling.volatility,
-            );
+            let classification =
+                BalanceClassification::classify(strength, coupling.distance, coupling.volatility);
             match classification {
                 BalanceClassification::HighCohesion => stats.balance_counts.high_cohesion += 1,
                 BalanceClassification::LooseCoupling => stats.balance_counts.loose_coupling += 1,
@@ -540,6 +965,54 @@ impl ProjectMetrics {
             .collect()
     }
 
+    /// Approximate in-memory footprint of the main analysis data structures
+    pub fn memory_profile(&self) -> MemoryProfile {
+        let item_dependencies: usize = self
+            .modules
+            .values()
+            .map(|m| m.item_dependencies.len())
+            .sum();
+
+        let module_bytes: usize = self
+            .modules
+            .values()
+            .map(|m| {
+                std::mem::size_of::<ModuleMetrics>()
+                    + m.type_definitions.len() * std::mem::size_of::<TypeDefinition>()
+                    + m.function_definitions.len() * std::mem::size_of::<FunctionDefinition>()
+            })
+            .sum();
+        let item_dependency_bytes: usize = self
+            .modules
+            .values()
+            .flat_map(|m| &m.item_dependencies)
+            .map(|dep| {
+                std::mem::size_of::<ItemDependency>()
+                    + dep.source_item.len()
+                    + dep.target.len()
+                    + dep.target_module.as_ref().map_or(0, String::len)
+                    + dep.expression.as_ref().map_or(0, String::len)
+            })
+            .sum();
+        let coupling_bytes: usize = self
+            .couplings
+            .iter()
+            .map(|c| std::mem::size_of::<CouplingMetrics>() + c.source.len() + c.target.len())
+            .sum();
+
+        MemoryProfile {
+            modules: self.modules.len(),
+            couplings: self.couplings.len(),
+            item_dependencies,
+            estimated_bytes: module_bytes + item_dependency_bytes + coupling_bytes,
+        }
+    }
+
+    /// Normalized strength/distance fingerprint of all couplings.
+    pub fn coupling_profile(&self) -> CouplingProfile {
+        CouplingProfile::from_stats(&self.calculate_dimension_stats())
+    }
+
     /// Get types with exposed public fields
     pub fn types_with_public_fields(&self) -> Vec<(&str, &TypeDefinition)> {
         self.modules
@@ -552,6 +1025,139 @@ impl ProjectMetrics {
             })
             .collect()
     }
+
+    /// Resolve a coupling or issue endpoint to the name of an analyzed module.
+    ///
+    /// Endpoints may name an item inside the module (`balance::score::BalanceScore`)
+    /// or carry a crate prefix (`cargo-coupling::balance`, `crate::balance`).
+    pub fn module_for_endpoint(&self, endpoint: &str) -> Option<&str> {
+        let unprefixed = endpoint.split_once("::").map(|(_, rest)| rest);
+        [Some(endpoint), unprefixed]
+            .into_iter()
+            .flatten()
+            .find_map(|name| {
+                let mut candidate = name;
+                loop {
+                    if let Some((key, _)) = self.modules.get_key_value(candidate) {
+                        return Some(key.as_str());
+                    }
+                    candidate = candidate.rsplit_once("::")?.0;
+                }
+            })
+    }
+
+    /// Internal modules that `module` depends on (Martin's `Ce`, counted per module).
+    pub fn efferent_coupling(&self, module: &str) -> usize {
+        self.internal_module_edges()
+            .iter()
+            .filter(|(source, _)| *source == module)
+            .count()
+    }
+
+    /// Internal modules that depend on `module` (Martin's `Ca`, counted per module).
+    pub fn afferent_coupling(&self, module: &str) -> usize {
+        self.internal_module_edges()
+            .iter()
+            .filter(|(_, target)| *target == module)
+            .count()
+    }
+
+    /// Instability `I = Ce / (Ce + Ca)`; `None` for a module with no internal couplings.
+    pub fn instability(&self, module: &str) -> Option<f64> {
+        let efferent = self.efferent_coupling(module);
+        let total = efferent + self.afferent_coupling(module);
+        (total > 0).then(|| efferent as f64 / total as f64)
+    }
+
+    /// Abstractness `A = traits / types`; `None` for a module that defines no types.
+    pub fn abstractness(&self, module: &str) -> Option<f64> {
+        let types = &self.modules.get(module)?.type_definitions;
+        let traits = types.values().filter(|t| t.is_trait).count();
+        (!types.is_empty()).then(|| traits as f64 / types.len() as f64)
+    }
+
+    /// Distinct `(source, target)` module pairs of internal couplings, self-edges excluded.
+    pub(crate) fn internal_module_edges(&self) -> HashSet<(&str, &str)> {
+        self.couplings
+            .iter()
+            .filter(|c| c.distance != Distance::DifferentCrate)
+            .filter_map(|c| {
+                let source = self.module_for_endpoint(&c.source)?;
+                let target = self.module_for_endpoint(&c.target)?;
+                (source != target).then_some((source, target))
+            })
+            .collect()
+    }
+}
+
+/// Files a `mod name;` declaration in `declaring_file` may load, in rustc's order.
+///
+/// `name.rs` and `name/mod.rs` are tried under the declaring module's directory,
+/// then beside the file itself, which is where a non-`lib.rs` crate root such as
+/// `src/bin/tool.rs` keeps its children.
+fn submodule_file_candidates(declaring_file: &Path, submodule: &FileSubmodule) -> Vec<PathBuf> {
+    let dir = declaring_file.parent().unwrap_or_else(|| Path::new(""));
+    let (inline, name) = submodule
+        .name
+        .rsplit_once("::")
+        .unwrap_or(("", submodule.name.as_str()));
+    let inline_dir: PathBuf = inline.split("::").filter(|s| !s.is_empty()).collect();
+    let is_mod_rs = matches!(
+        declaring_file.file_name().and_then(|name| name.to_str()),
+        Some("mod.rs" | "lib.rs" | "main.rs")
+    );
+
+    if let Some(path) = &submodule.path {
+        // Outside inline modules `#[path]` is relative to the declaring file's directory
+        let base = if inline.is_empty() {
+            dir.to_path_buf()
+        } else if is_mod_rs {
+            dir.join(&inline_dir)
+        } else {
+            dir.join(declaring_file.file_stem().unwrap_or_default())
+                .join(&inline_dir)
+        };
+        return vec![base.join(path)];
+    }
+
+    let mut module_dirs = vec![dir.join(&inline_dir)];
+    if !is_mod_rs {
+        module_dirs.insert(
+            0,
+            dir.join(declaring_file.file_stem().unwrap_or_default())
+                .join(&inline_dir),
+        );
+    }
+    module_dirs
+        .into_iter()
+        .flat_map(|module_dir| {
+            [
+                module_dir.join(format!("{}.rs", name)),
+                module_dir.join(name).join("mod.rs"),
+            ]
+        })
+        .collect()
+}
+
+/// Files that would declare `file` as a child module in the standard layout.
+fn standard_parent_files(file: &Path) -> Vec<PathBuf> {
+    let file_name = file.file_name().and_then(|name| name.to_str());
+    if matches!(file_name, Some("lib.rs" | "main.rs")) {
+        return Vec::new();
+    }
+    let mut owner_dir = file.parent();
+    if file_name == Some("mod.rs") {
+        owner_dir = owner_dir.and_then(Path::parent);
+    }
+    let Some(owner_dir) = owner_dir else {
+        return Vec::new();
+    };
+    vec![
+        owner_dir.join("mod.rs"),
+        owner_dir.join("lib.rs"),
+        owner_dir.join("main.rs"),
+        owner_dir.with_extension("rs"),
+    ]
 }
 
 fn should_keep_existing_type_registration(
@@ -677,3 +1283,60 @@ pub struct CircularDependencySummary {
     /// The actual cycles (list of module names)
     pub cycles: Vec<Vec<String>>,
 }
+
+/// One direction of a mutual coupling
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutualDirection {
+    /// Strongest integration strength in this direction
+    pub strength: IntegrationStrength,
+    /// Distance of the strongest coupling
+    pub distance: Distance,
+    /// Volatility of the strongest coupling's target
+    pub volatility: Volatility,
+    /// Number of coupling records in this direction
+    pub couplings: usize,
+}
+
+/// Two modules that depend on each other (A ↔ B)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutualCoupling {
+    /// Lexicographically smaller module of the pair
+    pub module_a: String,
+    /// Lexicographically larger module of the pair
+    pub module_b: String,
+    /// Dependency of `module_a` on `module_b`
+    pub a_to_b: MutualDirection,
+    /// Dependency of `module_b` on `module_a`
+    pub b_to_a: MutualDirection,
+}
+
+impl MutualCoupling {
+    /// Sum of both directions' strength values (0.5-2.0)
+    pub fn combined_strength(&self) -> f64 {
+        self.a_to_b.strength.value() + self.b_to_a.strength.value()
+    }
+}
+
+/// Couplings a Cargo feature adds to one module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureCoupling {
+    /// Feature name, or the raw `cfg` predicate for compound gates
+    pub feature: String,
+    /// Module the gated couplings originate from
+    pub module: String,
+    /// Number of couplings compiled only with the feature
+    pub couplings: usize,
+}
+
+/// Approximate size of the analysis data kept in memory (`--profile-memory`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryProfile {
+    /// Number of modules
+    pub modules: usize,
+    /// Number of coupling records
+    pub couplings: usize,
+    /// Number of item-level dependencies across all modules
+    pub item_dependencies: usize,
+    /// Estimated heap + inline bytes for modules, item dependencies, and couplings
+    pub estimated_bytes: usize,
+}
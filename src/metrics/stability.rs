@@ -0,0 +1,177 @@
+//! Robert C. Martin's package metrics per module.
+//!
+//! Khononov's model scores individual couplings; Martin's metrics look at a
+//! module as a whole. Instability `I = Ce / (Ce + Ca)` says how freely a module
+//! can change, abstractness `A` how much of it is traits, and the distance from
+//! the main sequence `D = |A + I - 1|` how far it strays from the balance
+//! between the two. Modules far below the line are concrete and depended upon
+//! (zone of pain); modules far above it are abstract and unused (zone of
+//! uselessness).
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::project::ProjectMetrics;
+
+/// Distance from the main sequence beyond which a module is placed in a zone.
+pub const ZONE_DISTANCE: f64 = 0.5;
+
+/// Region of the A/I plane far from the main sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityZone {
+    /// Low abstractness, low instability: concrete and hard to change.
+    Pain,
+    /// High abstractness, high instability: abstractions nobody depends on.
+    Uselessness,
+}
+
+impl std::fmt::Display for StabilityZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StabilityZone::Pain => write!(f, "Zone of Pain"),
+            StabilityZone::Uselessness => write!(f, "Zone of Uselessness"),
+        }
+    }
+}
+
+/// Martin's metrics for one module.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleStability {
+    pub module: String,
+    /// Internal modules this module depends on (`Ce`)
+    pub efferent: usize,
+    /// Internal modules depending on this module (`Ca`)
+    pub afferent: usize,
+    /// `Ce / (Ce + Ca)`: 0.0 is maximally stable, 1.0 maximally unstable
+    pub instability: f64,
+    /// Traits over all defined types
+    pub abstractness: f64,
+    /// `|A + I - 1|`: 0.0 is on the main sequence
+    pub distance: f64,
+    /// Set when `distance` exceeds `ZONE_DISTANCE`
+    pub zone: Option<StabilityZone>,
+}
+
+/// Martin's metrics for every module, farthest from the main sequence first.
+///
+/// Modules without internal couplings or without type definitions are left
+/// out: their instability or abstractness is undefined.
+pub fn calculate_module_stability(metrics: &ProjectMetrics) -> Vec<ModuleStability> {
+    let mut efferent: HashMap<&str, usize> = HashMap::new();
+    let mut afferent: HashMap<&str, usize> = HashMap::new();
+    for (source, target) in metrics.internal_module_edges() {
+        *efferent.entry(source).or_default() += 1;
+        *afferent.entry(target).or_default() += 1;
+    }
+
+    let mut stability: Vec<ModuleStability> = metrics
+        .modules
+        .keys()
+        .filter_map(|module| {
+            let ce = efferent.get(module.as_str()).copied().unwrap_or(0);
+            let ca = afferent.get(module.as_str()).copied().unwrap_or(0);
+            if ce + ca == 0 {
+                return None;
+            }
+            let instability = ce as f64 / (ce + ca) as f64;
+            let abstractness = metrics.abstractness(module)?;
+            let signed = abstractness + instability - 1.0;
+            let zone = if signed < -ZONE_DISTANCE {
+                Some(StabilityZone::Pain)
+            } else if signed > ZONE_DISTANCE {
+                Some(StabilityZone::Uselessness)
+            } else {
+                None
+            };
+            Some(ModuleStability {
+                module: module.clone(),
+                efferent: ce,
+                afferent: ca,
+                instability,
+                abstractness,
+                distance: signed.abs(),
+                zone,
+            })
+        })
+        .collect();
+
+    stability.sort_by(|a, b| {
+        b.distance
+            .total_cmp(&a.distance)
+            .then_with(|| a.module.cmp(&b.module))
+    });
+    stability
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::metrics::coupling::CouplingMetrics;
+    use crate::metrics::dimensions::{Distance, IntegrationStrength, Visibility};
+    use crate::metrics::module::ModuleMetrics;
+    use crate::volatility::Volatility;
+
+    #[test]
+    fn test_concrete_core_is_in_zone_of_pain_and_unused_traits_in_zone_of_uselessness() {
+        let mut metrics = ProjectMetrics::new();
+        let mut add = |name: &str, types: &[(&str, bool)]| {
+            let mut module =
+                ModuleMetrics::new(PathBuf::from(format!("src/{name}.rs")), name.into());
+            for (type_name, is_trait) in types {
+                module.add_type_definition(type_name.to_string(), Visibility::Public, *is_trait);
+            }
+            metrics.add_module(module);
+        };
+        add("model", &[("Order", false), ("Customer", false)]);
+        add("ports", &[("Repository", true), ("Clock", true)]);
+        add("api", &[("Handler", false), ("Route", true)]);
+        add("billing", &[("Invoice", false)]);
+
+        // api and billing use model; ports depends on model but nobody uses ports.
+        for (source, target) in [
+            ("cargo-coupling::api", "cargo-coupling::model::Order"),
+            ("cargo-coupling::api", "cargo-coupling::model::Customer"),
+            ("cargo-coupling::billing", "cargo-coupling::model"),
+            ("cargo-coupling::ports", "cargo-coupling::model::Order"),
+            ("cargo-coupling::api", "serde::Serialize"),
+        ] {
+            metrics.add_coupling(CouplingMetrics::new(
+                source.to_string(),
+                target.to_string(),
+                IntegrationStrength::Model,
+                if target.starts_with("serde") {
+                    Distance::DifferentCrate
+                } else {
+                    Distance::DifferentModule
+                },
+                Volatility::Low,
+            ));
+        }
+
+        assert_eq!(metrics.efferent_coupling("api"), 1);
+        assert_eq!(metrics.afferent_coupling("model"), 3);
+        assert_eq!(metrics.instability("model"), Some(0.0));
+        assert_eq!(metrics.abstractness("ports"), Some(1.0));
+
+        let stability = calculate_module_stability(&metrics);
+        let names: Vec<_> = stability.iter().map(|s| s.module.as_str()).collect();
+        assert_eq!(names, ["model", "ports", "api", "billing"]);
+
+        let model = &stability[0];
+        assert_eq!((model.efferent, model.afferent), (0, 3));
+        assert_eq!(model.distance, 1.0);
+        assert_eq!(model.zone, Some(StabilityZone::Pain));
+        assert_eq!(stability[1].zone, Some(StabilityZone::Uselessness));
+        let api = &stability[2];
+        assert_eq!((api.instability, api.abstractness), (1.0, 0.5));
+        assert_eq!(api.distance, 0.5);
+        assert_eq!(api.zone, None);
+        assert_eq!(stability[3].distance, 0.0);
+        assert_eq!(stability[3].zone, None);
+    }
+}
@@ -0,0 +1,288 @@
+//! Module-to-source symbol map.
+//!
+//! This module flattens `ProjectMetrics` into a navigable index of each module's
+//! file and the declaration lines of the items it defines, so external tools can
+//! jump from a coupling to the code that carries it.
+//!
+//! `cargo coupling list` uses the same data to print the canonical names of
+//! modules, types, or functions, so users can find the exact name that
+//! `--impact` and `--trace` expect.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::metrics::project::ProjectMetrics;
+
+/// Symbol map of every analyzed module.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleMap {
+    /// Modules sorted by name.
+    pub modules: Vec<ModuleMapEntry>,
+}
+
+/// One module and the items it defines.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleMapEntry {
+    /// Module name as used in coupling records.
+    pub name: String,
+    /// Source file backing the module.
+    pub file: String,
+    /// Defined items sorted by declaration line, then name.
+    pub items: Vec<ModuleMapItem>,
+}
+
+/// A defined item and where it is declared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleMapItem {
+    /// Item name.
+    pub name: String,
+    /// `function`, `type`, or `trait`.
+    pub kind: &'static str,
+    /// Rust visibility as written (`pub`, `pub(crate)`, ...).
+    pub visibility: String,
+    /// 1-based declaration line (0 if unknown).
+    pub line: usize,
+}
+
+/// Build the symbol map from analyzed metrics.
+pub fn build_module_map(metrics: &ProjectMetrics) -> ModuleMap {
+    let mut modules: Vec<ModuleMapEntry> = metrics
+        .modules
+        .values()
+        .map(|module| {
+            let types = module.type_definitions.values().map(|def| ModuleMapItem {
+                name: def.name.clone(),
+                kind: if def.is_trait { "trait" } else { "type" },
+                visibility: def.visibility.to_string(),
+                line: def.line,
+            });
+            let functions = module
+                .function_definitions
+                .values()
+                .map(|def| ModuleMapItem {
+                    name: def.name.clone(),
+                    kind: "function",
+                    visibility: def.visibility.to_string(),
+                    line: def.line,
+                });
+
+            let mut items: Vec<ModuleMapItem> = types.chain(functions).collect();
+            items.sort_by(|a, b| a.line.cmp(&b.line).then_with(|| a.name.cmp(&b.name)));
+
+            ModuleMapEntry {
+                name: module.name.clone(),
+                file: module.path.display().to_string(),
+                items,
+            }
+        })
+        .collect();
+
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    ModuleMap { modules }
+}
+
+/// Which symbols `cargo coupling list` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolListKind {
+    /// Analyzed modules.
+    Modules,
+    /// Structs, enums, and traits.
+    Types,
+    /// Free functions.
+    Functions,
+}
+
+/// A symbol by its canonical name and where it lives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolListEntry {
+    /// Name as used in coupling records (`module` or `module::Item`).
+    pub name: String,
+    /// `module`, `type`, `trait`, or `function`.
+    pub kind: &'static str,
+    /// Source file backing the symbol.
+    pub file: String,
+    /// 1-based declaration line (0 for modules or if unknown).
+    pub line: usize,
+}
+
+/// List symbols of one kind, sorted by name.
+///
+/// `grep` keeps only names containing the substring (case-insensitive).
+pub fn build_symbol_list(
+    metrics: &ProjectMetrics,
+    kind: SymbolListKind,
+    grep: Option<&str>,
+) -> Vec<SymbolListEntry> {
+    let needle = grep.map(str::to_lowercase);
+    let mut entries: Vec<SymbolListEntry> = build_module_map(metrics)
+        .modules
+        .into_iter()
+        .flat_map(|module| {
+            let items: Vec<SymbolListEntry> = match kind {
+                SymbolListKind::Modules => vec![SymbolListEntry {
+                    name: module.name.clone(),
+                    kind: "module",
+                    file: module.file.clone(),
+                    line: 0,
+                }],
+                SymbolListKind::Types | SymbolListKind::Functions => module
+                    .items
+                    .iter()
+                    .filter(|item| (item.kind == "function") == (kind == SymbolListKind::Functions))
+                    .map(|item| SymbolListEntry {
+                        name: format!("{}::{}", module.name, item.name),
+                        kind: item.kind,
+                        file: module.file.clone(),
+                        line: item.line,
+                    })
+                    .collect(),
+            };
+            items
+        })
+        .filter(|entry| {
+            needle
+                .as_deref()
+                .is_none_or(|needle| entry.name.to_lowercase().contains(needle))
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// Write a symbol list as pretty-printed JSON.
+pub fn generate_symbol_list_json<W: Write>(
+    entries: &[SymbolListEntry],
+    writer: &mut W,
+) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(entries).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)
+}
+
+/// Write a symbol list as `name  file[:line]` lines.
+pub fn generate_symbol_list_text<W: Write>(
+    entries: &[SymbolListEntry],
+    writer: &mut W,
+) -> io::Result<()> {
+    let width = entries
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or(0);
+    for entry in entries {
+        if entry.line > 0 {
+            writeln!(
+                writer,
+                "{:<width$}  {}:{}",
+                entry.name, entry.file, entry.line
+            )?;
+        } else {
+            writeln!(writer, "{:<width$}  {}", entry.name, entry.file)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the symbol map as pretty-printed JSON.
+pub fn generate_module_map_json<W: Write>(map: &ModuleMap, writer: &mut W) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(map).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)
+}
+
+/// Write the symbol map as `file:line` lines grouped by module.
+pub fn generate_module_map_text<W: Write>(map: &ModuleMap, writer: &mut W) -> io::Result<()> {
+    for module in &map.modules {
+        writeln!(writer, "{} ({})", module.name, module.file)?;
+        for item in &module.items {
+            writeln!(
+                writer,
+                "  {}:{}  {} {} {}",
+                module.file, item.line, item.visibility, item.kind, item.name
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::analyze_files;
+
+    #[test]
+    fn test_defined_function_reports_its_declaration_line() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod shapes;\n"),
+            (
+                "shapes.rs",
+                "pub struct Circle;\n\n// area helpers\npub fn area(_c: &Circle) -> f64 {\n    0.0\n}\n",
+            ),
+        ]);
+        let map = build_module_map(&metrics);
+
+        let shapes = map
+            .modules
+            .iter()
+            .find(|module| module.name.ends_with("shapes"))
+            .unwrap();
+        assert!(shapes.file.ends_with("shapes.rs"));
+        assert_eq!(
+            shapes.items,
+            vec![
+                ModuleMapItem {
+                    name: "Circle".to_string(),
+                    kind: "type",
+                    visibility: "pub".to_string(),
+                    line: 1,
+                },
+                ModuleMapItem {
+                    name: "area".to_string(),
+                    kind: "function",
+                    visibility: "pub".to_string(),
+                    line: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_includes_known_module_with_its_path() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod shapes;\npub mod orders;\n"),
+            (
+                "shapes.rs",
+                "pub struct Circle;\npub trait Shape {}\npub fn area(_c: &Circle) -> f64 {\n    0.0\n}\n",
+            ),
+            ("orders.rs", "pub struct Order;\n"),
+        ]);
+
+        let modules = build_symbol_list(&metrics, SymbolListKind::Modules, Some("SHAPE"));
+        assert_eq!(modules.len(), 1);
+        assert!(modules[0].name.ends_with("shapes"));
+        assert!(modules[0].file.ends_with("shapes.rs"));
+
+        let types = build_symbol_list(&metrics, SymbolListKind::Types, Some("shapes"));
+        let names: Vec<(&str, &str, usize)> = types
+            .iter()
+            .map(|entry| {
+                (
+                    entry.name.rsplit("::").next().unwrap(),
+                    entry.kind,
+                    entry.line,
+                )
+            })
+            .collect();
+        assert_eq!(names, vec![("Circle", "type", 1), ("Shape", "trait", 2)]);
+        assert!(types[0].name.starts_with(&modules[0].name));
+
+        let functions = build_symbol_list(&metrics, SymbolListKind::Functions, None);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, format!("{}::area", modules[0].name));
+
+        let mut text = Vec::new();
+        generate_symbol_list_text(&modules, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.contains("shapes.rs"), "{text}");
+    }
+}
@@ -0,0 +1,148 @@
+//! Remote source checkout.
+//!
+//! Lets `cargo coupling <git-url>` analyze a repository without a manual clone:
+//! the source is cloned (shallowly when possible) into a temporary directory that
+//! is removed once analysis finishes, unless the caller asks to keep it.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use thiserror::Error;
+
+static CHECKOUT_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// Errors from cloning a remote source.
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("git clone of '{url}' failed: {message}")]
+    Clone { url: String, message: String },
+
+    #[error("could not check out '{rev}': {message}")]
+    Checkout { rev: String, message: String },
+
+    #[error("'{0}' is not a Rust project (no Cargo.toml at the repository root)")]
+    NotRustProject(String),
+}
+
+/// Whether an analysis path argument names a remote git source rather than a local path.
+pub fn is_remote_source(path: &str) -> bool {
+    ["https://", "http://", "git://", "ssh://", "file://"]
+        .iter()
+        .any(|scheme| path.starts_with(scheme))
+        || (path.starts_with("git@") && path.contains(':'))
+}
+
+/// A cloned remote repository, removed on drop unless kept.
+#[derive(Debug)]
+pub struct RemoteCheckout {
+    dir: PathBuf,
+    keep: bool,
+}
+
+impl RemoteCheckout {
+    /// Clone `url` into a fresh temporary directory, optionally pinned to `rev`.
+    ///
+    /// Branches and tags are fetched shallowly; other revisions (commit hashes)
+    /// fall back to a full clone followed by a detached checkout. On any error
+    /// the directory is removed, even when `keep` is set.
+    pub fn fetch(url: &str, rev: Option<&str>, keep: bool) -> Result<Self, RemoteError> {
+        if let Some(rev) = rev.filter(|rev| rev.is_empty() || rev.starts_with('-')) {
+            return Err(RemoteError::Checkout {
+                rev: rev.to_string(),
+                message: "not a branch, tag or commit".to_string(),
+            });
+        }
+
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-coupling-remote-{}-{}",
+            std::process::id(),
+            CHECKOUT_SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Kept only once the checkout succeeded; dropping it early cleans up.
+        let mut checkout = RemoteCheckout { dir, keep: false };
+
+        let shallow = match rev {
+            Some(rev) => git_clone(url, &checkout.dir, &["--depth", "1", "--branch", rev]),
+            None => git_clone(url, &checkout.dir, &["--depth", "1"]),
+        };
+
+        if let Err(message) = shallow {
+            let Some(rev) = rev else {
+                return Err(RemoteError::Clone {
+                    url: url.to_string(),
+                    message,
+                });
+            };
+
+            // `--branch` only accepts branch and tag names; retry for commit hashes.
+            let _ = std::fs::remove_dir_all(&checkout.dir);
+            git_clone(url, &checkout.dir, &["--no-checkout"]).map_err(|message| {
+                RemoteError::Clone {
+                    url: url.to_string(),
+                    message,
+                }
+            })?;
+            git(&checkout.dir, &["checkout", "-q", "--detach", rev, "--"]).map_err(|message| {
+                RemoteError::Checkout {
+                    rev: rev.to_string(),
+                    message,
+                }
+            })?;
+        }
+
+        if !checkout.dir.join("Cargo.toml").is_file() {
+            return Err(RemoteError::NotRustProject(url.to_string()));
+        }
+
+        checkout.keep = keep;
+        Ok(checkout)
+    }
+
+    /// Root directory of the checkout.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for RemoteCheckout {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+fn git_clone(url: &str, dir: &Path, flags: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(["clone", "-q"])
+        .args(flags)
+        .arg("--")
+        .arg(url)
+        .arg(dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
@@ -3,6 +3,7 @@
 //! This module translates balance scores, issue lists, volatility signals, and
 //! blind-spot manifests into CLI-facing summaries and full reports.
 
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 
 use crate::balance::action::RefactoringAction;
@@ -10,12 +11,22 @@ use crate::balance::coupling::is_crate_root_facade;
 use crate::balance::grade::{HealthGrade, ProjectBalanceReport};
 use crate::balance::issue::CouplingIssue;
 use crate::balance::issue_type::IssueType;
-use crate::balance::project::analyze_project_balance_with_thresholds;
+use crate::balance::patterns::{
+    COGNITIVE_LOAD_ACTION, DEPRECATED_INTERNAL_ACTION, ENUM_VARIANT_EXPOSURE_ACTION,
+    GLOB_IMPORT_ACTION, OVER_PARAMETERIZED_TYPE_ACTION, PASS_THROUGH_ACTION, SHALLOW_MODULE_ACTION,
+    SPREAD_COUPLING_ACTION,
+};
+use crate::balance::project::{analyze_crate_balance, analyze_project_balance_with_thresholds};
 use crate::balance::score::{BalanceInterpretation, BalanceScore, IssueThresholds};
 use crate::balance::severity::Severity;
+use crate::header::OutputHeader;
 use crate::manifest::{AnalysisManifest, ManifestContext, build_manifest};
+use crate::metrics::cohesion::calculate_module_cohesion;
 use crate::metrics::dimensions::{Distance, IntegrationStrength};
-use crate::metrics::project::ProjectMetrics;
+use crate::metrics::profile::CouplingProfile;
+use crate::metrics::project::{MutualDirection, ProjectMetrics};
+use crate::metrics::stability::calculate_module_stability;
+use crate::volatility::Volatility;
 
 const DEFAULT_STRONG_TEMPORAL_LIMIT: usize = 5;
 
@@ -57,7 +68,8 @@ pub fn generate_summary_with_options<W: Write>(
     writer: &mut W,
 ) -> io::Result<()> {
     let report = analyze_project_balance_with_thresholds(metrics, thresholds);
-    let dimension_stats = metrics.calculate_dimension_stats();
+    let dimension_stats =
+        metrics.calculate_dimension_stats_with(thresholds.balance_formula.visibility_penalty);
     let jp = thresholds.japanese;
 
     let project_name = metrics.workspace_name.as_deref().unwrap_or("project");
@@ -74,6 +86,9 @@ pub fn generate_summary_with_options<W: Write>(
             metrics.module_count()
         )?;
         writeln!(writer, "理由: {}", report.grade_rationale.summary)?;
+        if let Some(roots) = analysis_roots_summary(metrics) {
+            writeln!(writer, "解析対象: {}", roots)?;
+        }
     } else {
         writeln!(writer, "Balanced Coupling Analysis: {}", project_name)?;
         writeln!(writer, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
@@ -86,9 +101,44 @@ pub fn generate_summary_with_options<W: Write>(
             metrics.module_count()
         )?;
         writeln!(writer, "Why this grade: {}", report.grade_rationale.summary)?;
+        if let Some(roots) = analysis_roots_summary(metrics) {
+            writeln!(writer, "Roots: {}", roots)?;
+        }
     }
     writeln!(writer)?;
 
+    let crate_reports = crate_health_rows(metrics, thresholds);
+    if !crate_reports.is_empty() {
+        let width = crate_reports
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(writer, "{}", if jp { "クレート別:" } else { "Crates:" })?;
+        for (name, crate_report) in &crate_reports {
+            if jp {
+                writeln!(
+                    writer,
+                    "  {:<width$}  評価: {} | スコア: {:.2} | 課題: {}",
+                    name,
+                    crate_report.health_grade.letter(),
+                    crate_report.average_score,
+                    crate_report.issues.len()
+                )?;
+            } else {
+                writeln!(
+                    writer,
+                    "  {:<width$}  Grade: {} | Score: {:.2} | Issues: {}",
+                    name,
+                    crate_report.health_grade.letter(),
+                    crate_report.average_score,
+                    crate_report.issues.len()
+                )?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
     // 3-Dimensional Analysis
     if !metrics.couplings.is_empty() {
         // Strength distribution
@@ -97,6 +147,10 @@ pub fn generate_summary_with_options<W: Write>(
         let (same_pct, diff_pct, ext_pct) = dimension_stats.distance_percentages();
         // Volatility distribution
         let (low_pct, med_pct, high_pct) = dimension_stats.volatility_percentages();
+        // Connascence distribution
+        let (name_pct, type_pct, meaning_pct, position_pct) =
+            dimension_stats.connascence_percentages();
+        let coupling_profile = CouplingProfile::from_stats(&dimension_stats);
 
         if jp {
             writeln!(writer, "3次元分析:")?;
@@ -119,6 +173,12 @@ pub fn generate_summary_with_options<W: Write>(
                 "  変更頻度: 低 {:.0}% / 中 {:.0}% / 高 {:.0}%",
                 low_pct, med_pct, high_pct
             )?;
+            writeln!(
+                writer,
+                "  コナーセンス: 名前 {:.0}% / 型 {:.0}% / 意味 {:.0}% / 位置 {:.0}%",
+                name_pct, type_pct, meaning_pct, position_pct
+            )?;
+            writeln!(writer, "  プロファイル: {}", coupling_profile)?;
         } else {
             writeln!(writer, "3-Dimensional Analysis:")?;
             writeln!(
@@ -136,6 +196,12 @@ pub fn generate_summary_with_options<W: Write>(
                 "  Volatility: Low {:.0}% / Medium {:.0}% / High {:.0}%",
                 low_pct, med_pct, high_pct
             )?;
+            writeln!(
+                writer,
+                "  Connascence: Name {:.0}% / Type {:.0}% / Meaning {:.0}% / Position {:.0}%",
+                name_pct, type_pct, meaning_pct, position_pct
+            )?;
+            writeln!(writer, "  Profile:    {}", coupling_profile)?;
         }
         writeln!(writer)?;
 
@@ -288,6 +354,21 @@ pub fn generate_summary_with_options<W: Write>(
             )?;
         }
     }
+    if report.inline_suppressions > 0 {
+        if jp {
+            writeln!(
+                writer,
+                "インライン抑制: {} 件 (// coupling:allow)\n",
+                report.inline_suppressions
+            )?;
+        } else {
+            writeln!(
+                writer,
+                "Suppressed inline: {} (// coupling:allow)\n",
+                report.inline_suppressions
+            )?;
+        }
+    }
 
     // Top priority if any
     if !report.top_priorities.is_empty() {
@@ -402,12 +483,24 @@ fn issue_type_japanese(issue_type: IssueType) -> &'static str {
         IssueType::HiddenCoupling => "隠れた結合 (共変更のみで発見)",
         IssueType::AccidentalVolatility => "偶発的な変更頻度",
         IssueType::ScatteredExternalCoupling => "外部クレート結合の分散",
+        IssueType::DeprecatedInternalCoupling => {
+            "非推奨モジュールへの結合 (移行先への置き換えを検討)"
+        }
+        IssueType::LayerViolation => "レイヤー違反 (依存関係の逆転を検討)",
         IssueType::ShallowModule => "浅いモジュール",
         IssueType::PassThroughMethod => "パススルーメソッド",
         IssueType::HighCognitiveLoad => "高認知負荷",
         IssueType::GodModule => "神モジュール (責務が多すぎる)",
         IssueType::PublicFieldExposure => "公開フィールド (getterを検討)",
         IssueType::PrimitiveObsession => "プリミティブ過多 (newtypeを検討)",
+        IssueType::GlobImport => "globインポート (明示的なインポートを検討)",
+        IssueType::OverParameterizedType => "型パラメータ過多 (型引数の集約を検討)",
+        IssueType::SpreadCoupling => "拡散結合 (アクセサの導入を検討)",
+        IssueType::MixedAsyncBoundary => "async/同期の混在 (境界での分割を検討)",
+        IssueType::LowCohesion => "低凝集 (型のまとまりごとに分割を検討)",
+        IssueType::UnsafeSurface => "unsafeの集中 (安全な抽象化への閉じ込めを検討)",
+        IssueType::DuplicatedStructure => "構造の重複 (共通の型への統合を検討)",
+        IssueType::DerefAbuse => "Derefの乱用 (必要な操作をメソッドとして公開)",
     }
 }
 
@@ -442,6 +535,23 @@ fn refactoring_action_japanese(action: &RefactoringAction) -> String {
                 format!("`{}` モジュールを導入し、直接利用をそこに集約する", facade)
             } else if action == "Extract a shared abstraction or make the dependency explicit" {
                 "共有された抽象化を抽出するか、依存関係を明示する".to_string()
+            } else if action == GLOB_IMPORT_ACTION {
+                "globインポートを明示的なインポートに置き換える".to_string()
+            } else if action == ENUM_VARIANT_EXPOSURE_ACTION {
+                "enumに#[non_exhaustive]を付けるか、バリアントのデータを非公開フィールドの型で包む"
+                    .to_string()
+            } else if action == OVER_PARAMETERIZED_TYPE_ACTION {
+                "型パラメータをトレイトの関連型や設定用の型にまとめる".to_string()
+            } else if action == SPREAD_COUPLING_ACTION {
+                "メンバーへの直接参照をやめ、アクセサやファサード経由に集約する".to_string()
+            } else if action == SHALLOW_MODULE_ACTION {
+                "公開APIをより少なく深い関数にまとめ、補助関数を非公開にする".to_string()
+            } else if action == COGNITIVE_LOAD_ACTION {
+                "補助関数を非公開にし、引数を型にまとめ、外部クレートをローカルなインターフェースで包んで、利用者が知るべきことを減らす".to_string()
+            } else if action == PASS_THROUGH_ACTION {
+                "委譲先を直接呼び出すか、ラッパーに固有の責務を持たせる".to_string()
+            } else if action == DEPRECATED_INTERNAL_ACTION {
+                "非推奨モジュールへの依存を移行先のモジュールに置き換える".to_string()
             } else {
                 action.clone()
             }
@@ -463,7 +573,7 @@ fn issue_instance_description_japanese(issue: &CouplingIssue) -> String {
     use IssueType;
     match issue.issue_type {
         IssueType::HiddenCoupling => {
-            "明示的なコード依存はありませんが、ファイルが頻繁に一緒に変更されています。暗黙の知識や不足した抽象化を示している可能性があります。"
+            "明示的なコード依存（またはトレイト契約以外の依存）はありませんが、ファイルが頻繁に一緒に変更されています。暗黙の知識や不足した抽象化を示している可能性があります。"
                 .to_string()
         }
         IssueType::AccidentalVolatility => {
@@ -515,11 +625,19 @@ pub fn generate_report_with_options<W: Write>(
     let report = analyze_project_balance_with_thresholds(metrics, thresholds);
 
     writeln!(writer, "# Coupling Analysis Report\n")?;
+    writeln!(
+        writer,
+        "{}\n",
+        OutputHeader::new(metrics.config_hash.clone()).markdown_line()
+    )?;
 
     // Executive Summary
     let jp = thresholds.japanese;
     write_executive_summary(metrics, &report, jp, writer)?;
 
+    // Per-crate grades (multi-crate workspaces only)
+    write_crate_health_section(&crate_health_rows(metrics, thresholds), writer)?;
+
     // Refactoring Priorities (if any issues)
     if !report.issues.is_empty() {
         write_refactoring_priorities(&report, jp, writer)?;
@@ -529,7 +647,7 @@ pub fn generate_report_with_options<W: Write>(
     write_issues_by_type(&report, jp, writer)?;
 
     // Coupling details
-    write_coupling_section(metrics, writer)?;
+    write_coupling_section(metrics, thresholds, writer)?;
 
     // Module analysis
     write_module_section(metrics, writer)?;
@@ -543,6 +661,12 @@ pub fn generate_report_with_options<W: Write>(
     // Circular dependency section
     write_circular_dependencies_section(metrics, writer)?;
 
+    // Mutual (A ↔ B) coupling section
+    write_mutual_couplings_section(metrics, writer)?;
+
+    // Couplings behind `#[cfg(feature = ..)]`
+    write_feature_gated_couplings_section(metrics, writer)?;
+
     // Best practices
     write_best_practices(writer)?;
 
@@ -591,8 +715,25 @@ fn write_executive_summary<W: Write>(
 
     writeln!(writer, "| Metric | Value |")?;
     writeln!(writer, "|--------|-------|")?;
+    if let Some(roots) = analysis_roots_summary(metrics) {
+        writeln!(writer, "| Roots | {} |", roots)?;
+    }
     writeln!(writer, "| Files Analyzed | {} |", metrics.total_files)?;
+    if let Some(editions) = edition_summary(metrics) {
+        writeln!(writer, "| Edition | {} |", editions)?;
+    }
     writeln!(writer, "| Total Modules | {} |", metrics.module_count())?;
+    if !metrics.grade_excluded_crates.is_empty() {
+        let excluded: Vec<String> = metrics
+            .grade_excluded_crates
+            .iter()
+            .map(|(crate_name, kind)| format!("{} ({})", crate_name, kind))
+            .collect();
+        writeln!(writer, "| Excluded from Grade | {} |", excluded.join(", "))?;
+    }
+    if let Some(unsafe_surface) = unsafe_surface_summary(metrics) {
+        writeln!(writer, "| Unsafe Surface | {} |", unsafe_surface)?;
+    }
     writeln!(writer, "| Total Couplings | {} |", report.total_couplings)?;
     writeln!(
         writer,
@@ -612,6 +753,13 @@ fn write_executive_summary<W: Write>(
     // This headline count mirrors `report.issues`/JSON `issues`; balance buckets
     // such as Pain Zone are separate coupling classifications, not surfaced issues.
     writeln!(writer, "| Issues Surfaced | {} |", report.issues.len())?;
+    if report.inline_suppressions > 0 {
+        writeln!(
+            writer,
+            "| Suppressed Inline | {} |",
+            report.inline_suppressions
+        )?;
+    }
     writeln!(writer)?;
 
     // Issue counts
@@ -665,6 +813,116 @@ fn write_executive_summary<W: Write>(
     Ok(())
 }
 
+/// Per-crate reports, worst score first; empty unless several crates are graded
+fn crate_health_rows(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<(String, ProjectBalanceReport)> {
+    let mut rows: Vec<_> = analyze_crate_balance(metrics, thresholds)
+        .into_iter()
+        .collect();
+    if rows.len() < 2 {
+        return Vec::new();
+    }
+    rows.sort_by(|(a_name, a), (b_name, b)| {
+        a.average_score
+            .total_cmp(&b.average_score)
+            .then_with(|| a_name.cmp(b_name))
+    });
+    rows
+}
+
+fn write_crate_health_section<W: Write>(
+    rows: &[(String, ProjectBalanceReport)],
+    writer: &mut W,
+) -> io::Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        writer,
+        "## Crate Health
+"
+    )?;
+    writeln!(writer, "| Crate | Grade | Score | Couplings | Issues |")?;
+    writeln!(writer, "|-------|-------|-------|-----------|--------|")?;
+    for (name, report) in rows {
+        writeln!(
+            writer,
+            "| {} | {} | {:.2} | {} | {} |",
+            name,
+            report.health_grade.letter(),
+            report.average_score,
+            report.total_couplings,
+            report.issues.len()
+        )?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Roots of a multi-root analysis, comma-separated; `None` for a single root.
+fn analysis_roots_summary(metrics: &ProjectMetrics) -> Option<String> {
+    if metrics.analysis_roots.len() < 2 {
+        return None;
+    }
+    let roots: Vec<String> = metrics
+        .analysis_roots
+        .iter()
+        .map(|root| root.display().to_string())
+        .collect();
+    Some(roots.join(", "))
+}
+
+/// Editions of the analyzed crates, e.g. `2021` or `2018 (legacy), 2024 (app, core)`.
+fn edition_summary(metrics: &ProjectMetrics) -> Option<String> {
+    let mut by_edition: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (crate_name, edition) in &metrics.crate_editions {
+        by_edition.entry(edition).or_default().push(crate_name);
+    }
+    if by_edition.len() <= 1 {
+        return by_edition.into_keys().next().map(str::to_string);
+    }
+
+    let parts: Vec<String> = by_edition
+        .into_iter()
+        .map(|(edition, mut crates)| {
+            crates.sort_unstable();
+            format!("{} ({})", edition, crates.join(", "))
+        })
+        .collect();
+    Some(parts.join(", "))
+}
+
+/// Unsafe code outside tests, e.g. `7 unsafe blocks, 2 unsafe fns in 2 modules (ffi, buffer)`;
+/// `None` when there is none. Lists at most three modules, most unsafe first.
+fn unsafe_surface_summary(metrics: &ProjectMetrics) -> Option<String> {
+    let mut modules: Vec<(&str, usize)> = metrics
+        .modules
+        .iter()
+        .map(|(name, m)| (name.as_str(), m.unsafe_block_count + m.unsafe_fn_count))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    if modules.is_empty() {
+        return None;
+    }
+    modules.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let blocks: usize = metrics.modules.values().map(|m| m.unsafe_block_count).sum();
+    let fns: usize = metrics.modules.values().map(|m| m.unsafe_fn_count).sum();
+    let mut top: Vec<&str> = modules.iter().take(3).map(|(name, _)| *name).collect();
+    if modules.len() > 3 {
+        top.push("…");
+    }
+    Some(format!(
+        "{} unsafe blocks, {} unsafe fns in {} modules ({})",
+        blocks,
+        fns,
+        modules.len(),
+        top.join(", ")
+    ))
+}
+
 fn write_refactoring_priorities<W: Write>(
     report: &ProjectBalanceReport,
     japanese: bool,
@@ -895,7 +1153,11 @@ fn write_issues_by_type<W: Write>(
     Ok(())
 }
 
-fn write_coupling_section<W: Write>(metrics: &ProjectMetrics, writer: &mut W) -> io::Result<()> {
+fn write_coupling_section<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
     if metrics.couplings.is_empty() {
         return Ok(());
     }
@@ -1018,7 +1280,7 @@ fn write_coupling_section<W: Write>(metrics: &ProjectMetrics, writer: &mut W) ->
         .filter(|c| c.distance != Distance::DifferentCrate)
         .filter(|c| !is_crate_root_facade(&c.target))
         .filter(|c| seen_worst.insert((c.source.clone(), c.target.clone())))
-        .map(|c| (c, BalanceScore::calculate(c)))
+        .map(|c| (c, BalanceScore::calculate_for(c, thresholds)))
         .collect();
 
     couplings_with_scores.sort_by(|a, b| {
@@ -1037,23 +1299,9 @@ fn write_coupling_section<W: Write>(metrics: &ProjectMetrics, writer: &mut W) ->
     )?;
 
     for (coupling, score) in couplings_with_scores.iter().take(15) {
-        let strength_str = match coupling.strength {
-            IntegrationStrength::Contract => "Contract",
-            IntegrationStrength::Model => "Model",
-            IntegrationStrength::Functional => "Functional",
-            IntegrationStrength::Intrusive => "Intrusive",
-        };
-        let distance_str = match coupling.distance {
-            Distance::SameFunction => "Same Fn",
-            Distance::SameModule => "Same Mod",
-            Distance::DifferentModule => "Diff Mod",
-            Distance::DifferentCrate => "External",
-        };
-        let volatility_str = match coupling.volatility {
-            crate::volatility::Volatility::Low => "Low",
-            crate::volatility::Volatility::Medium => "Med",
-            crate::volatility::Volatility::High => "High",
-        };
+        let strength_str = strength_label(coupling.strength);
+        let distance_str = distance_label(coupling.distance);
+        let volatility_str = volatility_label(coupling.volatility);
         let status = match score.interpretation {
             BalanceInterpretation::Balanced => "✅ Balanced",
             BalanceInterpretation::Acceptable => "✅ OK",
@@ -1084,6 +1332,24 @@ fn write_coupling_section<W: Write>(metrics: &ProjectMetrics, writer: &mut W) ->
     }
     writeln!(writer)?;
 
+    // Classification provenance (only recorded in verbose runs)
+    let explained: Vec<_> = couplings_with_scores
+        .iter()
+        .take(15)
+        .filter_map(|(coupling, _)| coupling.provenance.as_ref().map(|why| (coupling, why)))
+        .collect();
+    if !explained.is_empty() {
+        writeln!(writer, "#### Why These Classifications\n")?;
+        for (coupling, why) in explained {
+            writeln!(
+                writer,
+                "- `{}` → `{}`: {}",
+                coupling.source, coupling.target, why
+            )?;
+        }
+        writeln!(writer)?;
+    }
+
     Ok(())
 }
 
@@ -1158,6 +1424,75 @@ fn write_module_section<W: Write>(metrics: &ProjectMetrics, writer: &mut W) -> i
     }
     writeln!(writer)?;
 
+    let stability = calculate_module_stability(metrics);
+    if !stability.is_empty() {
+        writeln!(writer, "### Distance from the Main Sequence\n")?;
+        writeln!(
+            writer,
+            "Instability `I = Ce/(Ce+Ca)`, abstractness `A` (traits / types), and `D = |A + I - 1|`. \
+             High `D` with low `A` and `I` is the zone of pain; high `A` and `I` the zone of uselessness.\n"
+        )?;
+        writeln!(writer, "| Module | Ce | Ca | I | A | D | Zone |")?;
+        writeln!(writer, "|--------|----|----|---|---|---|------|")?;
+        for entry in stability.iter().take(10) {
+            writeln!(
+                writer,
+                "| `{}` | {} | {} | {:.2} | {:.2} | {:.2} | {} |",
+                truncate_path(&entry.module, 30),
+                entry.efferent,
+                entry.afferent,
+                entry.instability,
+                entry.abstractness,
+                entry.distance,
+                entry
+                    .zone
+                    .map(|zone| zone.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            )?;
+        }
+        if stability.len() > 10 {
+            writeln!(writer, "\n*Showing top 10 of {} modules*", stability.len())?;
+        }
+        writeln!(writer)?;
+    }
+
+    // Only modules split into several type clusters are worth a row
+    let cohesion: Vec<_> = calculate_module_cohesion(metrics)
+        .into_iter()
+        .filter(|entry| entry.clusters.len() > 1)
+        .collect();
+    if !cohesion.is_empty() {
+        writeln!(writer, "### Cohesion\n")?;
+        writeln!(
+            writer,
+            "Functions grouped by the module types they use (LCOM). Cohesion is the largest group's \
+             share of the functions; several groups sharing no types suggest a split.\n"
+        )?;
+        writeln!(
+            writer,
+            "| Module | Functions | Clusters | Cohesion | Largest Cluster |"
+        )?;
+        writeln!(
+            writer,
+            "|--------|-----------|----------|----------|-----------------|"
+        )?;
+        for entry in cohesion.iter().take(10) {
+            writeln!(
+                writer,
+                "| `{}` | {} | {} | {:.2} | {} |",
+                truncate_path(&entry.module, 30),
+                entry.function_count,
+                entry.clusters.len(),
+                entry.cohesion,
+                entry.clusters[0].types.join(", ")
+            )?;
+        }
+        if cohesion.len() > 10 {
+            writeln!(writer, "\n*Showing top 10 of {} modules*", cohesion.len())?;
+        }
+        writeln!(writer)?;
+    }
+
     Ok(())
 }
 
@@ -1352,6 +1687,120 @@ fn write_circular_dependencies_section<W: Write>(
     Ok(())
 }
 
+fn write_mutual_couplings_section<W: Write>(
+    metrics: &ProjectMetrics,
+    writer: &mut W,
+) -> io::Result<()> {
+    let mutual = metrics.detect_mutual_couplings();
+    if mutual.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "## Mutual Couplings\n")?;
+    writeln!(
+        writer,
+        "Found **{} module pair(s)** that depend on each other. \
+         Each direction is shown by its strongest coupling (strength / distance / volatility).\n",
+        mutual.len()
+    )?;
+    writeln!(writer, "| Module A | Module B | A → B | B → A |")?;
+    writeln!(writer, "|----------|----------|-------|-------|")?;
+
+    for pair in mutual.iter().take(15) {
+        writeln!(
+            writer,
+            "| `{}` | `{}` | {} | {} |",
+            truncate_path(&pair.module_a, 25),
+            truncate_path(&pair.module_b, 25),
+            mutual_direction_label(&pair.a_to_b),
+            mutual_direction_label(&pair.b_to_a)
+        )?;
+    }
+
+    if mutual.len() > 15 {
+        writeln!(writer, "\n*Showing 15 of {} mutual pairs*", mutual.len())?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn write_feature_gated_couplings_section<W: Write>(
+    metrics: &ProjectMetrics,
+    writer: &mut W,
+) -> io::Result<()> {
+    let gated = metrics.feature_gated_couplings();
+    if gated.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "## Feature-Gated Couplings\n")?;
+    writeln!(
+        writer,
+        "Couplings compiled only under `#[cfg(feature = ...)]`, by the feature that \
+         adds them. Compound predicates are shown as written.\n"
+    )?;
+    writeln!(writer, "| Feature | Module | Couplings Added |")?;
+    writeln!(writer, "|---------|--------|-----------------|")?;
+
+    for entry in gated.iter().take(20) {
+        writeln!(
+            writer,
+            "| `{}` | `{}` | {} |",
+            entry.feature,
+            truncate_path(&entry.module, 30),
+            entry.couplings
+        )?;
+    }
+
+    if gated.len() > 20 {
+        writeln!(
+            writer,
+            "\n*Showing 20 of {} feature/module pairs*",
+            gated.len()
+        )?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn mutual_direction_label(direction: &MutualDirection) -> String {
+    format!(
+        "{} / {} / {} ({}×)",
+        strength_label(direction.strength),
+        distance_label(direction.distance),
+        volatility_label(direction.volatility),
+        direction.couplings
+    )
+}
+
+fn strength_label(strength: IntegrationStrength) -> &'static str {
+    match strength {
+        IntegrationStrength::Contract => "Contract",
+        IntegrationStrength::Model => "Model",
+        IntegrationStrength::Functional => "Functional",
+        IntegrationStrength::Intrusive => "Intrusive",
+    }
+}
+
+fn distance_label(distance: Distance) -> &'static str {
+    match distance {
+        Distance::SameFunction => "Same Fn",
+        Distance::SameModule => "Same Mod",
+        Distance::DifferentModule => "Diff Mod",
+        Distance::DifferentCrate => "External",
+    }
+}
+
+fn volatility_label(volatility: Volatility) -> &'static str {
+    match volatility {
+        Volatility::Low => "Low",
+        Volatility::Medium => "Med",
+        Volatility::High => "High",
+    }
+}
+
 fn write_best_practices<W: Write>(writer: &mut W) -> io::Result<()> {
     writeln!(writer, "## Balance Guidelines\n")?;
 
@@ -1571,9 +2020,12 @@ fn default_manifest() -> AnalysisManifest {
         git_used: true,
         tests_excluded: false,
         parse_failures: 0,
+        generated_files: 0,
         skipped_crates: Vec::new(),
         boundary_skipped_files: 0,
         dead_config_patterns: Vec::new(),
+        edition_parse_failures: Vec::new(),
+        orphaned_modules: Vec::new(),
     })
 }
 
@@ -1738,9 +2190,12 @@ mod tests {
             git_used: false,
             tests_excluded: true,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         let mut output = Vec::new();
 
@@ -1764,9 +2219,12 @@ mod tests {
             git_used: false,
             tests_excluded: true,
             parse_failures: 1,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
 
         let mut default_output = Vec::new();
@@ -1897,9 +2355,12 @@ mod tests {
             git_used: false,
             tests_excluded: false,
             parse_failures: 0,
+            generated_files: 0,
             skipped_crates: Vec::new(),
             boundary_skipped_files: 0,
             dead_config_patterns: Vec::new(),
+            edition_parse_failures: Vec::new(),
+            orphaned_modules: Vec::new(),
         });
         let mut output = Vec::new();
 
@@ -1935,6 +2396,36 @@ mod tests {
         assert!(output_str.contains("Module Statistics"));
     }
 
+    #[test]
+    fn test_generate_report_lists_distance_from_main_sequence() {
+        use crate::metrics::coupling::CouplingMetrics;
+        use crate::metrics::dimensions::Visibility;
+        use crate::metrics::module::ModuleMetrics;
+
+        let mut metrics = ProjectMetrics::new();
+        for name in ["model", "api"] {
+            let mut module =
+                ModuleMetrics::new(PathBuf::from(format!("src/{name}.rs")), name.into());
+            module.add_type_definition(name.to_uppercase(), Visibility::Public, false);
+            metrics.add_module(module);
+        }
+        metrics.add_coupling(CouplingMetrics::new(
+            "api".to_string(),
+            "model::MODEL".to_string(),
+            IntegrationStrength::Model,
+            Distance::DifferentModule,
+            Volatility::Low,
+        ));
+
+        let mut output = Vec::new();
+        generate_report(&metrics, &mut output).unwrap();
+
+        let output_str = String::from_utf8(output).unwrap();
+        assert!(output_str.contains("### Distance from the Main Sequence"));
+        assert!(output_str.contains("| `model` | 0 | 1 | 0.00 | 0.00 | 1.00 | Zone of Pain |"));
+        assert!(output_str.contains("| `api` | 1 | 0 | 1.00 | 0.00 | 0.00 | - |"));
+    }
+
     #[test]
     fn test_generate_report_surfaces_subdomain_when_present() {
         use crate::config::Subdomain;
@@ -0,0 +1,801 @@
+//! Starter code for refactoring actions (`cargo coupling suggest`).
+//!
+//! Issues carry a `RefactoringAction` that says what to do; this module turns
+//! three of them into Rust code to paste in and adjust:
+//!
+//! - `AddGetters`: accessor methods for the flagged public fields, typed from
+//!   the field declarations.
+//! - `IntroduceNewtype`: one newtype with `From` and `Deref` impls per primitive
+//!   parameter of the flagged function.
+//! - `IntroduceTrait`: a trait whose methods are the calls other modules make on
+//!   the target, most used first, with the signatures of their definitions.
+//!
+//! The code is a starting point, not a finished refactoring: generic
+//! parameters and callers are left for the user to update.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::analyzer::ItemDepType;
+use crate::balance::action::RefactoringAction;
+use crate::balance::issue::CouplingIssue;
+use crate::balance::project::analyze_project_balance_with_thresholds;
+use crate::balance::score::IssueThresholds;
+use crate::metrics::module::{FunctionDefinition, ModuleMetrics};
+use crate::metrics::project::ProjectMetrics;
+
+/// Generated code for one refactoring action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    /// `getters`, `newtype`, or `trait`.
+    pub kind: &'static str,
+    /// Rule name of the issue that asked for the refactoring.
+    pub issue_type: String,
+    /// Item the code is for (`module::Type`, `module::function`, or a module).
+    pub target: String,
+    /// One-line summary of the code.
+    pub title: String,
+    /// Rust code to paste in.
+    pub code: String,
+}
+
+/// Generate code for every issue whose action has a code template.
+///
+/// Low-severity issues are included even in strict mode: getters and newtypes
+/// are only suggested for them.
+pub fn build_suggestions(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+) -> Vec<Suggestion> {
+    let thresholds = IssueThresholds {
+        strict_mode: false,
+        ..thresholds.clone()
+    };
+    let report = analyze_project_balance_with_thresholds(metrics, &thresholds);
+
+    let mut seen = HashSet::new();
+    let mut suggestions: Vec<Suggestion> = report
+        .issues
+        .iter()
+        .filter_map(|issue| suggest(metrics, issue))
+        .filter(|suggestion| seen.insert((suggestion.kind, suggestion.target.clone())))
+        .collect();
+    suggestions.sort_by(|a, b| a.kind.cmp(b.kind).then_with(|| a.target.cmp(&b.target)));
+    suggestions
+}
+
+fn suggest(metrics: &ProjectMetrics, issue: &CouplingIssue) -> Option<Suggestion> {
+    let (kind, target, title, code) = match &issue.refactoring {
+        RefactoringAction::AddGetters { fields } => {
+            let (title, code) = getters(metrics, &issue.source, fields)?;
+            ("getters", issue.source.clone(), title, code)
+        }
+        RefactoringAction::IntroduceNewtype { .. } => {
+            let (title, code) = newtypes(metrics, &issue.source)?;
+            ("newtype", issue.source.clone(), title, code)
+        }
+        RefactoringAction::IntroduceTrait { suggested_name, .. } => {
+            let target = resolve_trait_target(metrics, &issue.target)?;
+            let trait_name = pascal_case(suggested_name);
+            let (title, code) = trait_skeleton(metrics, &target, &trait_name)?;
+            ("trait", target.display_name(), title, code)
+        }
+        _ => return None,
+    };
+    Some(Suggestion {
+        kind,
+        issue_type: issue.issue_type.rule_name(),
+        target,
+        title,
+        code,
+    })
+}
+
+/// Module that `path` names or lies in, longest match first.
+///
+/// The first segment may be `crate` or the crate name, so
+/// `crate::store::Store::get` and `my-crate::store` both resolve to a module
+/// named `store` (or `app::store`).
+fn owning_module<'a>(metrics: &'a ProjectMetrics, path: &str) -> Option<&'a str> {
+    let segments: Vec<&str> = path.split("::").collect();
+    (1..=segments.len()).rev().find_map(|len| {
+        (0..=1).find_map(|start| {
+            let prefix = segments.get(start..start + len)?.join("::");
+            metrics.modules.keys().find_map(|name| {
+                let matches = *name == prefix || name.ends_with(&format!("::{}", prefix));
+                matches.then_some(name.as_str())
+            })
+        })
+    })
+}
+
+fn getters(metrics: &ProjectMetrics, source: &str, fields: &[String]) -> Option<(String, String)> {
+    let (module_name, type_name) = source.rsplit_once("::")?;
+    let type_def = metrics
+        .modules
+        .get(module_name)?
+        .type_definitions
+        .get(type_name)?;
+
+    let methods: Vec<String> = fields
+        .iter()
+        .filter_map(|field| {
+            let (_, field_type) = type_def.fields.iter().find(|(name, _)| name == field)?;
+            let (return_type, body) = getter_body(field, field_type);
+            Some(format!(
+                "    pub fn {field}(&self) -> {return_type} {{\n        {body}\n    }}\n"
+            ))
+        })
+        .collect();
+    if methods.is_empty() {
+        return None;
+    }
+
+    let code = format!("impl {} {{\n{}}}\n", type_name, methods.join("\n"));
+    Some((
+        format!(
+            "Getters for {}; then make the fields private",
+            fields.join(", ")
+        ),
+        code,
+    ))
+}
+
+/// Return type and body of a getter, borrowing unless the field is `Copy`.
+///
+/// Owned wrappers lend out what they point to (`String` as `&str`, `Box<T>` as
+/// `&T`), and reference fields are handed out without another `&`.
+fn getter_body(field: &str, field_type: &str) -> (String, String) {
+    if is_copy_primitive(field_type) {
+        return (field_type.to_string(), format!("self.{field}"));
+    }
+    if let Some(referent) = field_type.strip_prefix('&') {
+        // The field's lifetime is elided to `&self`; a `&mut` field is lent out shared
+        return match without_lifetime(referent).strip_prefix("mut ") {
+            Some(target) => (format!("&{target}"), format!("&*self.{field}")),
+            None => (
+                format!("&{}", without_lifetime(referent)),
+                format!("self.{field}"),
+            ),
+        };
+    }
+    if let Some(target) = deref_target(field_type) {
+        return (format!("&{target}"), format!("&self.{field}"));
+    }
+    if let Some(inner) = generic_argument(field_type, "Option") {
+        return match deref_target(inner) {
+            Some(target) => (
+                format!("Option<&{target}>"),
+                format!("self.{field}.as_deref()"),
+            ),
+            None => (
+                format!("Option<&{inner}>"),
+                format!("self.{field}.as_ref()"),
+            ),
+        };
+    }
+    (format!("&{field_type}"), format!("&self.{field}"))
+}
+
+/// What an owned `field_type` dereferences to: `str` for `String`, `[T]` for
+/// `Vec<T>`, `T` for `Box<T>`.
+fn deref_target(field_type: &str) -> Option<String> {
+    if field_type == "String" {
+        return Some("str".to_string());
+    }
+    if let Some(element) = generic_argument(field_type, "Vec") {
+        return Some(format!("[{element}]"));
+    }
+    generic_argument(field_type, "Box").map(str::to_string)
+}
+
+/// `T` of `'a T`, or `T` itself when no lifetime is written.
+fn without_lifetime(referent: &str) -> &str {
+    match referent.strip_prefix('\'') {
+        Some(_) => referent.split_once(' ').map_or(referent, |(_, rest)| rest),
+        None => referent,
+    }
+}
+
+/// `T` of `Wrapper<T>`, for field types rendered by the analyzer (`Vec<u32>`).
+fn generic_argument<'a>(field_type: &'a str, wrapper: &str) -> Option<&'a str> {
+    field_type
+        .strip_prefix(wrapper)?
+        .strip_prefix('<')?
+        .strip_suffix('>')
+}
+
+fn is_copy_primitive(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "bool"
+            | "char"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "f32"
+            | "f64"
+    )
+}
+
+/// Owned type a newtype should wrap for a primitive parameter type, if it is one.
+///
+/// `&str` and `&'a str` become `String`; references to `Copy` primitives drop the `&`.
+fn newtype_inner(param_type: &str) -> Option<&str> {
+    let mut owned = param_type;
+    if let Some(rest) = owned.strip_prefix('&') {
+        owned = rest.trim_start();
+        if owned.starts_with('\'') {
+            owned = owned.split_once(' ')?.1;
+        }
+        owned = owned.strip_prefix("mut ").unwrap_or(owned);
+    }
+    match owned {
+        "str" | "String" => Some("String"),
+        _ if is_copy_primitive(owned) => Some(owned),
+        _ => None,
+    }
+}
+
+fn newtypes(metrics: &ProjectMetrics, source: &str) -> Option<(String, String)> {
+    let module_name = owning_module(metrics, source)?;
+    let function_name = source.strip_prefix(module_name)?.strip_prefix("::")?;
+    let function = metrics
+        .modules
+        .get(module_name)?
+        .function_definitions
+        .get(function_name)?;
+
+    let mut blocks = Vec::new();
+    let mut names = Vec::new();
+    let mut new_params = Vec::new();
+    for (param, param_type) in &function.params {
+        let newtype = match newtype_inner(param_type) {
+            Some(inner) if param != "_" => {
+                let name = pascal_case(param);
+                blocks.push(newtype_block(&name, inner));
+                names.push(name.clone());
+                name
+            }
+            _ => param_type.clone(),
+        };
+        new_params.push((param.clone(), newtype));
+    }
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let signature = render_signature(function, function.receiver.as_deref(), &new_params);
+    let code = format!(
+        "{}\n// Then take them in the signature:\n// {}\n",
+        blocks.join("\n"),
+        signature
+    );
+    Some((
+        format!(
+            "Newtypes for the primitive parameters of {}: {}",
+            function.name,
+            names.join(", ")
+        ),
+        code,
+    ))
+}
+
+fn newtype_block(name: &str, inner: &str) -> String {
+    let mut derives = vec!["Debug", "Clone"];
+    if inner != "String" {
+        derives.push("Copy");
+    }
+    derives.push("PartialEq");
+    if !matches!(inner, "f32" | "f64") {
+        derives.push("Eq");
+    }
+    format!(
+        "#[derive({derives})]\n\
+         pub struct {name}({inner});\n\
+         \n\
+         impl From<{inner}> for {name} {{\n    \
+             fn from(value: {inner}) -> Self {{\n        \
+                 Self(value)\n    \
+             }}\n\
+         }}\n\
+         \n\
+         impl std::ops::Deref for {name} {{\n    \
+             type Target = {inner};\n\
+         \n    \
+             fn deref(&self) -> &Self::Target {{\n        \
+                 &self.0\n    \
+             }}\n\
+         }}\n",
+        derives = derives.join(", "),
+    )
+}
+
+/// `retry_count` -> `RetryCount`, `Issue_typeApi` -> `IssueTypeApi`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `fn name(receiver, params) -> R`, with the function's own return type.
+fn render_signature(
+    function: &FunctionDefinition,
+    receiver: Option<&str>,
+    params: &[(String, String)],
+) -> String {
+    let method = function.name.rsplit("::").next().unwrap_or(&function.name);
+    let inputs: Vec<String> = receiver
+        .map(str::to_string)
+        .into_iter()
+        .chain(params.iter().map(|(name, ty)| format!("{name}: {ty}")))
+        .collect();
+    let asyncness = if function.is_async { "async " } else { "" };
+    let output = function
+        .return_type
+        .as_ref()
+        .map(|ty| format!(" -> {ty}"))
+        .unwrap_or_default();
+    format!("{asyncness}fn {method}({}){output}", inputs.join(", "))
+}
+
+/// What an `IntroduceTrait` action abstracts over.
+struct TraitTarget {
+    /// Module the target is defined in, if it is part of the project
+    module: Option<String>,
+    /// Type name, unless the trait stands for a whole module
+    type_name: Option<String>,
+}
+
+impl TraitTarget {
+    fn display_name(&self) -> String {
+        match (&self.module, &self.type_name) {
+            (Some(module), Some(type_name)) => format!("{}::{}", module, type_name),
+            (Some(module), None) => module.clone(),
+            (None, Some(type_name)) => type_name.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Whether a call to `path` (`Type::method`, `module::function`) goes to this target.
+    fn owns(&self, metrics: &ProjectMetrics, path: &str) -> bool {
+        let Some((owner_path, _)) = path.rsplit_once("::") else {
+            return false;
+        };
+        let owner = owner_path.rsplit("::").next().unwrap_or(owner_path);
+        match (&self.type_name, &self.module) {
+            (Some(type_name), _) => owner == type_name,
+            (None, Some(module)) => {
+                metrics.get_type_module(owner) == Some(module.as_str())
+                    || owning_module(metrics, owner_path) == Some(module.as_str())
+            }
+            (None, None) => false,
+        }
+    }
+}
+
+fn resolve_trait_target(metrics: &ProjectMetrics, target: &str) -> Option<TraitTarget> {
+    let last = target.rsplit("::").next().unwrap_or(target);
+    if last.starts_with(char::is_uppercase) {
+        return Some(TraitTarget {
+            module: metrics.get_type_module(last).map(str::to_string),
+            type_name: Some(last.to_string()),
+        });
+    }
+    Some(TraitTarget {
+        module: Some(owning_module(metrics, target)?.to_string()),
+        type_name: None,
+    })
+}
+
+/// A call other modules make on the trait target.
+#[derive(Default)]
+struct UsedMethod<'a> {
+    calls: usize,
+    callers: BTreeSet<String>,
+    definition: Option<&'a FunctionDefinition>,
+}
+
+/// Methods of std traits (`Default::default`, `From::from`, ...); a derive or
+/// trait bound covers them better than a method stub.
+const STD_TRAIT_METHODS: &[&str] = &[
+    "as_ref",
+    "clone",
+    "cmp",
+    "default",
+    "deref",
+    "eq",
+    "fmt",
+    "from",
+    "from_str",
+    "hash",
+    "into",
+    "partial_cmp",
+    "to_string",
+    "try_from",
+];
+
+fn trait_skeleton(
+    metrics: &ProjectMetrics,
+    target: &TraitTarget,
+    trait_name: &str,
+) -> Option<(String, String)> {
+    let target_module = target
+        .module
+        .as_deref()
+        .and_then(|name| metrics.modules.get(name));
+
+    let mut used: BTreeMap<&str, UsedMethod> = BTreeMap::new();
+    for (module_name, module) in &metrics.modules {
+        if target.module.as_deref() == Some(module_name.as_str()) {
+            continue;
+        }
+        for dep in &module.item_dependencies {
+            if !matches!(
+                dep.dep_type,
+                ItemDepType::MethodCall | ItemDepType::FunctionCall
+            ) || !target.owns(metrics, &dep.target)
+            {
+                continue;
+            }
+            let method = dep.target.rsplit("::").next().unwrap_or(&dep.target);
+            if STD_TRAIT_METHODS.contains(&method) {
+                continue;
+            }
+            // Inside the project, a call without a definition is a method of
+            // some other type the analyzer could not tell apart
+            let definition = match target_module {
+                Some(target_module) => {
+                    let Some(definition) = find_definition(target_module, target, &dep.target)
+                    else {
+                        continue;
+                    };
+                    Some(definition)
+                }
+                None => None,
+            };
+            let entry = used.entry(method).or_default();
+            entry.calls += 1;
+            entry
+                .callers
+                .insert(format!("{}::{}", module_name, dep.source_item));
+            entry.definition = entry.definition.or(definition);
+        }
+    }
+    if used.is_empty() {
+        return None;
+    }
+
+    let mut methods: Vec<(&str, UsedMethod)> = used.into_iter().collect();
+    methods.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+
+    let target_name = target.display_name();
+    let mut code = format!(
+        "/// Operations other modules use on `{}`.\npub trait {} {{\n",
+        target_name, trait_name
+    );
+    for (i, (method, usage)) in methods.iter().enumerate() {
+        if i > 0 {
+            code.push('\n');
+        }
+        let callers: Vec<&str> = usage.callers.iter().map(String::as_str).collect();
+        code.push_str(&format!(
+            "    /// Called {} time(s) from {}\n",
+            usage.calls,
+            summarize(&callers, 3)
+        ));
+        match usage.definition {
+            Some(definition) => {
+                // Free functions of a module become methods on the implementor
+                let free_function = !definition.name.contains("::");
+                let receiver = match &definition.receiver {
+                    Some(receiver) => Some(receiver.as_str()),
+                    None if free_function => Some("&self"),
+                    None => None,
+                };
+                let signature = render_signature(definition, receiver, &definition.params);
+                code.push_str(&format!("    {};\n", signature));
+            }
+            None => code.push_str(&format!(
+                "    fn {}(&self); // defined outside the project\n",
+                method
+            )),
+        }
+    }
+    code.push_str("}\n");
+
+    Some((
+        format!(
+            "Trait `{}` with the {} method(s) other modules call on {}",
+            trait_name,
+            methods.len(),
+            target_name
+        ),
+        code,
+    ))
+}
+
+/// Definition of the function `call` (`Type::method`, `module::function`) in the target module.
+///
+/// A method called on a value of unknown type is recorded as `module::method`;
+/// it resolves when exactly one type in the module defines that method.
+fn find_definition<'a>(
+    module: &'a ModuleMetrics,
+    target: &TraitTarget,
+    call: &str,
+) -> Option<&'a FunctionDefinition> {
+    let mut segments = call.rsplit("::");
+    let method = segments.next()?;
+    let owner = segments.next().unwrap_or_default();
+    let functions = &module.function_definitions;
+    if let Some(type_name) = &target.type_name {
+        return functions.get(&format!("{}::{}", type_name, method));
+    }
+    if let Some(definition) = functions
+        .get(&format!("{}::{}", owner, method))
+        .or_else(|| functions.get(method))
+    {
+        return Some(definition);
+    }
+    let suffix = format!("::{}", method);
+    let mut candidates = functions
+        .iter()
+        .filter(|(name, _)| name.ends_with(&suffix))
+        .map(|(_, definition)| definition);
+    let definition = candidates.next()?;
+    candidates.next().is_none().then_some(definition)
+}
+
+/// `a, b, c and 2 more`
+fn summarize(items: &[&str], limit: usize) -> String {
+    if items.len() <= limit {
+        return items.join(", ");
+    }
+    format!(
+        "{} and {} more",
+        items[..limit].join(", "),
+        items.len() - limit
+    )
+}
+
+/// Write the suggestions as JSON.
+pub fn generate_suggestions_json<W: Write>(
+    suggestions: &[Suggestion],
+    writer: &mut W,
+) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(suggestions).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json)
+}
+
+/// Write the suggestions as Markdown with one fenced Rust block each.
+pub fn generate_suggestions_text<W: Write>(
+    suggestions: &[Suggestion],
+    writer: &mut W,
+) -> io::Result<()> {
+    if suggestions.is_empty() {
+        writeln!(writer, "No issues with generated refactoring code.")?;
+        return Ok(());
+    }
+    writeln!(writer, "# Refactoring suggestions ({})", suggestions.len())?;
+    for suggestion in suggestions {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "## {} ({})",
+            suggestion.target, suggestion.issue_type
+        )?;
+        writeln!(writer)?;
+        writeln!(writer, "{}", suggestion.title)?;
+        writeln!(writer)?;
+        writeln!(writer, "```rust")?;
+        write!(writer, "{}", suggestion.code)?;
+        writeln!(writer, "```")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::analyze_files;
+
+    fn find<'a>(suggestions: &'a [Suggestion], kind: &str) -> &'a Suggestion {
+        suggestions
+            .iter()
+            .find(|suggestion| suggestion.kind == kind)
+            .unwrap_or_else(|| panic!("no {kind} suggestion in {suggestions:#?}"))
+    }
+
+    #[test]
+    fn test_getters_borrow_owned_fields_and_copy_primitives() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod store;\n"),
+            (
+                "store.rs",
+                "pub struct Store {\n    pub name: String,\n    pub items: Vec<u32>,\n    pub count: u32,\n    pub parent: Option<Box<Store>>,\n    cache: u8,\n}\n",
+            ),
+        ]);
+        let suggestions = build_suggestions(&metrics, &IssueThresholds::default());
+
+        let getters = find(&suggestions, "getters");
+        assert!(getters.target.ends_with("store::Store"));
+        assert_eq!(getters.issue_type, "public-field-exposure");
+        let code = &getters.code;
+        assert!(code.starts_with("impl Store {\n"), "{code}");
+        assert!(code.contains("pub fn name(&self) -> &str {\n        &self.name\n    }"));
+        assert!(code.contains("pub fn items(&self) -> &[u32] {"));
+        assert!(code.contains("pub fn count(&self) -> u32 {\n        self.count\n    }"));
+        assert!(code.contains(
+            "pub fn parent(&self) -> Option<&Store> {\n        self.parent.as_deref()\n    }"
+        ));
+        assert!(!code.contains("fn cache"));
+    }
+
+    #[test]
+    fn test_getters_lend_references_and_trait_objects_without_extra_borrows() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod view;\n"),
+            (
+                "view.rs",
+                "pub trait Handler {}\n\
+                 pub struct View<'a> {\n    pub label: &'a str,\n    pub buffer: &'a mut Vec<u8>,\n    \
+                 pub handler: Box<dyn Handler>,\n    pub title: Option<String>,\n}\n",
+            ),
+        ]);
+        let suggestions = build_suggestions(&metrics, &IssueThresholds::default());
+
+        let code = &find(&suggestions, "getters").code;
+        assert!(
+            code.contains("pub fn label(&self) -> &str {\n        self.label\n    }"),
+            "{code}"
+        );
+        assert!(code.contains("pub fn buffer(&self) -> &Vec<u8> {\n        &*self.buffer\n    }"));
+        assert!(
+            code.contains("pub fn handler(&self) -> &dyn Handler {\n        &self.handler\n    }")
+        );
+        assert!(code.contains(
+            "pub fn title(&self) -> Option<&str> {\n        self.title.as_deref()\n    }"
+        ));
+    }
+
+    #[test]
+    fn test_newtypes_wrap_each_primitive_parameter() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod net;\n"),
+            (
+                "net.rs",
+                "pub struct Conn;\npub fn connect(host: &str, port: u16, timeout_ms: u64, conn: Conn) -> Conn { conn }\n",
+            ),
+        ]);
+        let suggestions = build_suggestions(&metrics, &IssueThresholds::default());
+
+        let newtype = find(&suggestions, "newtype");
+        assert!(newtype.target.ends_with("net::connect"));
+        assert!(
+            newtype.title.ends_with("Host, Port, TimeoutMs"),
+            "{}",
+            newtype.title
+        );
+        let code = &newtype.code;
+        assert!(code.contains(
+            "#[derive(Debug, Clone, PartialEq, Eq)]\npub struct Host(String);\n\nimpl From<String> for Host {\n    fn from(value: String) -> Self {\n        Self(value)\n    }\n}\n"
+        ), "{code}");
+        assert!(code.contains(
+            "impl std::ops::Deref for Port {\n    type Target = u16;\n\n    fn deref(&self) -> &Self::Target {\n        &self.0\n    }\n}\n"
+        ), "{code}");
+        assert!(
+            code.contains(
+                "#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub struct TimeoutMs(u64);"
+            )
+        );
+        assert!(code.contains(
+            "// fn connect(host: Host, port: Port, timeout_ms: TimeoutMs, conn: Conn) -> Conn"
+        ));
+    }
+
+    #[test]
+    fn test_trait_methods_come_from_calls_in_other_modules() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod store;\npub mod app;\npub mod report;\npub mod audit;\n",
+            ),
+            (
+                "store.rs",
+                "pub struct Store;\n\
+                 impl Store {\n\
+                     pub fn get(&self, key: &str) -> Option<u64> { None }\n\
+                     pub fn insert(&mut self, key: &str, value: u64) -> bool { true }\n\
+                     pub fn compact(&mut self) {}\n\
+                 }\n\
+                 impl Default for Store { fn default() -> Self { Store } }\n\
+                 pub fn open(path: &str) -> Store { Store }\n",
+            ),
+            (
+                "app.rs",
+                "use crate::store::Store;\n\
+                 pub fn run(s: &mut Store) { s.insert(\"a\", 1); s.get(\"a\"); }\n",
+            ),
+            (
+                "report.rs",
+                "use crate::store::Store;\n\
+                 pub fn show(s: &Store) -> Option<u64> { s.get(\"b\") }\n",
+            ),
+            (
+                "audit.rs",
+                "use crate::store;\n\
+                 pub fn check() { let s = store::open(\"db\"); let d = store::Store::default(); }\n",
+            ),
+        ]);
+        let thresholds = IssueThresholds {
+            max_dependents: 1,
+            ..IssueThresholds::default()
+        };
+        let suggestions = build_suggestions(&metrics, &thresholds);
+
+        let skeleton = find(&suggestions, "trait");
+        assert_eq!(skeleton.issue_type, "high-afferent-coupling");
+        assert!(skeleton.target.ends_with("store"));
+        let code = &skeleton.code;
+        assert!(code.contains("pub trait StoreInterface {\n"), "{code}");
+        // Most used first, with the signature of the definition
+        let get = code
+            .find("    fn get(&self, key: &str) -> Option<u64>;")
+            .expect(code);
+        let insert = code
+            .find("    fn insert(&mut self, key: &str, value: u64) -> bool;")
+            .expect(code);
+        assert!(get < insert, "{code}");
+        assert!(code.contains("/// Called 2 time(s) from app::run, report::show\n"));
+        // A free function becomes a method on the implementor
+        assert!(
+            code.contains("    fn open(&self, path: &str) -> Store;"),
+            "{code}"
+        );
+        // Nothing outside the module calls compact; std trait methods are left to derives
+        assert!(!code.contains("compact"));
+        assert!(!code.contains("fn default"));
+    }
+
+    #[test]
+    fn test_text_output_wraps_code_in_rust_blocks() {
+        let suggestions = vec![Suggestion {
+            kind: "getters",
+            issue_type: "public-field-exposure".to_string(),
+            target: "store::Store".to_string(),
+            title: "Getters for count".to_string(),
+            code: "impl Store {}\n".to_string(),
+        }];
+        let mut out = Vec::new();
+        generate_suggestions_text(&suggestions, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "# Refactoring suggestions (1)\n\n## store::Store (public-field-exposure)\n\nGetters for count\n\n```rust\nimpl Store {}\n```\n"
+        );
+
+        let mut out = Vec::new();
+        generate_suggestions_text(&[], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "No issues with generated refactoring code.\n"
+        );
+    }
+}
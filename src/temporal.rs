@@ -0,0 +1,149 @@
+//! Temporal coupling report (`--temporal-coupling`).
+//!
+//! Files that keep changing in the same commits are coupled even when the AST
+//! shows no dependency between them. This report maps each co-changing file
+//! pair from git history onto its modules and tells apart pairs with no static
+//! coupling (hidden: shared knowledge the code does not show) from pairs whose
+//! co-change reinforces an existing static dependency.
+
+use serde::Serialize;
+
+use crate::balance::signals::{build_file_to_module_map, has_code_coupling, module_for_file};
+use crate::metrics::project::ProjectMetrics;
+
+/// How a co-changing pair relates to the static coupling graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoChangeKind {
+    /// No static coupling between the modules: the dependency is invisible in code.
+    Hidden,
+    /// The modules are statically coupled and also change together.
+    Reinforcing,
+}
+
+/// One co-changing file pair, resolved to modules.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoChangePair {
+    /// First file as reported by git.
+    pub file_a: String,
+    /// Second file as reported by git.
+    pub file_b: String,
+    /// Module of `file_a`.
+    pub module_a: String,
+    /// Module of `file_b`.
+    pub module_b: String,
+    /// Commits in which both files changed.
+    pub co_change_count: usize,
+    /// Co-changes relative to the less-changed file (0.0-1.0).
+    pub coupling_ratio: f64,
+    /// Whether the pair is hidden or reinforces static coupling.
+    pub kind: CoChangeKind,
+}
+
+/// Co-changing pairs of a project, most frequent first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TemporalCouplingReport {
+    /// Pairs without static coupling.
+    pub hidden: usize,
+    /// Pairs that also have static coupling.
+    pub reinforcing: usize,
+    /// All pairs that map to two different analyzed modules.
+    pub pairs: Vec<CoChangePair>,
+}
+
+/// Classify `metrics.temporal_couplings` against the static coupling graph.
+///
+/// Pairs whose files are not analyzed modules (deleted files, tests, build
+/// scripts) or that fall within one module are skipped.
+pub fn analyze_temporal_coupling_report(metrics: &ProjectMetrics) -> TemporalCouplingReport {
+    let file_to_module = build_file_to_module_map(metrics);
+    let mut report = TemporalCouplingReport::default();
+
+    for temporal in &metrics.temporal_couplings {
+        let Some(module_a) = module_for_file(&temporal.file_a, &file_to_module) else {
+            continue;
+        };
+        let Some(module_b) = module_for_file(&temporal.file_b, &file_to_module) else {
+            continue;
+        };
+        if module_a == module_b {
+            continue;
+        }
+
+        let kind = if has_code_coupling(metrics, &module_a, &module_b) {
+            report.reinforcing += 1;
+            CoChangeKind::Reinforcing
+        } else {
+            report.hidden += 1;
+            CoChangeKind::Hidden
+        };
+        report.pairs.push(CoChangePair {
+            file_a: temporal.file_a.clone(),
+            file_b: temporal.file_b.clone(),
+            module_a,
+            module_b,
+            co_change_count: temporal.co_change_count,
+            coupling_ratio: temporal.coupling_ratio,
+            kind,
+        });
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::metrics::coupling::CouplingMetrics;
+    use crate::metrics::dimensions::{Distance, IntegrationStrength};
+    use crate::metrics::module::ModuleMetrics;
+    use crate::volatility::{Volatility, VolatilityAnalyzer};
+
+    #[test]
+    fn test_co_changing_files_are_split_into_hidden_and_reinforcing_pairs() {
+        let mut metrics = ProjectMetrics::new();
+        for name in ["pricing", "invoicing", "catalog"] {
+            metrics.add_module(ModuleMetrics::new(
+                PathBuf::from(format!("src/{name}.rs")),
+                name.to_string(),
+            ));
+        }
+        metrics.add_coupling(CouplingMetrics::new(
+            "pricing".to_string(),
+            "catalog".to_string(),
+            IntegrationStrength::Model,
+            Distance::DifferentModule,
+            Volatility::Low,
+        ));
+
+        // pricing+invoicing co-change in 4 commits, pricing+catalog in 3.
+        let commit = |a: &str, b: &str| vec![a.to_string(), b.to_string()];
+        let mut commits = vec![commit("src/pricing.rs", "src/invoicing.rs"); 4];
+        commits.extend(vec![commit("src/catalog.rs", "src/pricing.rs"); 3]);
+        let mut analyzer = VolatilityAnalyzer::new(6);
+        for (file, changes) in [
+            ("src/pricing.rs", 8),
+            ("src/invoicing.rs", 4),
+            ("src/catalog.rs", 6),
+        ] {
+            analyzer.file_changes.insert(file.to_string(), changes);
+        }
+        metrics.temporal_couplings = analyzer.co_change_pairs(&commits);
+
+        let report = analyze_temporal_coupling_report(&metrics);
+
+        assert_eq!((report.hidden, report.reinforcing), (1, 1));
+        let hidden = &report.pairs[0];
+        assert_eq!(hidden.kind, CoChangeKind::Hidden);
+        assert_eq!(hidden.co_change_count, 4);
+        assert_eq!(hidden.coupling_ratio, 1.0);
+        assert_eq!(
+            (hidden.module_a.as_str(), hidden.module_b.as_str()),
+            ("invoicing", "pricing")
+        );
+        assert_eq!(report.pairs[1].kind, CoChangeKind::Reinforcing);
+        assert_eq!(report.pairs[1].co_change_count, 3);
+    }
+}
@@ -0,0 +1,28 @@
+//! Fixtures shared by unit tests.
+
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use crate::analyzer::analyze_workspace;
+use crate::metrics::project::ProjectMetrics;
+
+/// Write `files` (paths relative to `src/`) into a fresh temporary crate.
+///
+/// Returns the directory guard and the `src/` path.
+pub(crate) fn src_fixture(files: &[(&str, &str)]) -> (TempDir, PathBuf) {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src");
+    for (path, content) in files {
+        let path = src.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    (tmp, src)
+}
+
+/// Analyze a temporary crate whose `src/` holds `files`.
+pub(crate) fn analyze_files(files: &[(&str, &str)]) -> ProjectMetrics {
+    let (_tmp, src) = src_fixture(files);
+    analyze_workspace(&src).unwrap()
+}
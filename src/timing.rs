@@ -0,0 +1,149 @@
+//! Per-phase timing of an analysis run.
+//!
+//! `--timing` prints a human-readable total; `--timing-json <file>` writes a
+//! `TimingReport` so CI can track analyzer performance across runs and spot
+//! regressions in a specific phase.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+static BALANCE_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Add the duration of one balance analysis pass to `balance_time`.
+pub(crate) fn record_balance_pass(elapsed: Duration) {
+    let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    BALANCE_NANOS.fetch_add(nanos, Ordering::Relaxed);
+}
+
+/// Total time this process has spent in balance analysis, over every pass the
+/// selected output mode ran.
+pub fn balance_time() -> Duration {
+    Duration::from_nanos(BALANCE_NANOS.load(Ordering::Relaxed))
+}
+
+/// Durations of the phases inside `analyze_workspace_with_config`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnalysisTimings {
+    /// Workspace metadata lookup and source file discovery.
+    pub walk: Duration,
+    /// Parallel parsing and AST analysis of source files.
+    pub parse: Duration,
+    /// Type registration and coupling resolution.
+    pub coupling_build: Duration,
+}
+
+/// Duration of each analysis phase, serialized in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PhaseTimings {
+    /// Workspace metadata lookup and source file discovery.
+    #[serde(serialize_with = "as_millis")]
+    pub walk: Duration,
+    /// Parallel parsing and AST analysis of source files.
+    #[serde(serialize_with = "as_millis")]
+    pub parse: Duration,
+    /// Type registration and coupling resolution.
+    #[serde(serialize_with = "as_millis")]
+    pub coupling_build: Duration,
+    /// Git history analysis (volatility and temporal coupling); zero with `--no-git`.
+    #[serde(serialize_with = "as_millis")]
+    pub git: Duration,
+    /// Balance scoring and issue detection.
+    #[serde(serialize_with = "as_millis")]
+    pub balance: Duration,
+    /// Rendering the selected output mode, without the balance passes it ran.
+    #[serde(serialize_with = "as_millis")]
+    pub report: Duration,
+}
+
+/// Structured timing breakdown written by `--timing-json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TimingReport {
+    /// Source files analyzed.
+    pub files: usize,
+    /// Worker threads used for parallel parsing.
+    pub threads: usize,
+    /// Per-phase durations in milliseconds.
+    #[serde(rename = "phases_ms")]
+    pub phases: PhaseTimings,
+    /// Wall-clock time of the whole run in milliseconds.
+    #[serde(rename = "total_ms", serialize_with = "as_millis")]
+    pub total: Duration,
+}
+
+impl TimingReport {
+    /// Start a report from the timings recorded during workspace analysis.
+    pub fn from_analysis(analysis: &AnalysisTimings, files: usize, threads: usize) -> Self {
+        Self {
+            files,
+            threads,
+            phases: PhaseTimings {
+                walk: analysis.walk,
+                parse: analysis.parse,
+                coupling_build: analysis.coupling_build,
+                ..PhaseTimings::default()
+            },
+            total: Duration::ZERO,
+        }
+    }
+
+    /// Write the report as pretty-printed JSON.
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+}
+
+fn as_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_json_has_every_phase_in_milliseconds() {
+        let analysis = AnalysisTimings {
+            walk: Duration::from_millis(3),
+            parse: Duration::from_micros(12_500),
+            coupling_build: Duration::from_millis(1),
+        };
+        let mut report = TimingReport::from_analysis(&analysis, 42, 8);
+        report.phases.balance = Duration::from_millis(2);
+        report.total = Duration::from_millis(20);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("timing.json");
+        report.write_json(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        let phases = json["phases_ms"].as_object().unwrap();
+        for key in [
+            "walk",
+            "parse",
+            "coupling_build",
+            "git",
+            "balance",
+            "report",
+        ] {
+            let ms = phases[key]
+                .as_f64()
+                .unwrap_or_else(|| panic!("{key} missing"));
+            assert!(ms >= 0.0, "{key} is negative");
+        }
+        assert_eq!(phases.len(), 6);
+        assert_eq!(phases["parse"].as_f64(), Some(12.5));
+        assert_eq!(phases["git"].as_f64(), Some(0.0));
+        assert_eq!(json["total_ms"].as_f64(), Some(20.0));
+        assert_eq!(json["files"], 42);
+        assert_eq!(json["threads"], 8);
+    }
+}
@@ -0,0 +1,253 @@
+//! Trend history file (`--record <FILE>`, `coupling trend <FILE>`).
+//!
+//! Each run started with `--record` appends its headline numbers, stamped with
+//! the time of the run, to a JSON file. `coupling trend` reads the file back and
+//! renders the numbers over time. Unlike `--history`, nothing is re-analyzed:
+//! the file holds exactly what earlier runs measured, so it shows whether a
+//! refactoring moved the needle across any number of runs. `--max-history`
+//! drops the oldest entries once the file holds more than that many.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::grade::ProjectBalanceReport;
+use crate::header::{comparability_warnings, unix_now};
+use crate::metric_snapshot::MetricsSnapshot;
+use crate::metrics::project::ProjectMetrics;
+
+/// Headline metrics of one recorded run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendEntry {
+    /// Unix timestamp (seconds) of the run
+    pub recorded_at: u64,
+    #[serde(flatten)]
+    pub snapshot: MetricsSnapshot,
+    pub module_count: usize,
+}
+
+/// Recorded runs, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrendHistory {
+    pub entries: Vec<TrendEntry>,
+}
+
+impl TrendEntry {
+    /// Entry for a run finished now.
+    pub fn from_report(report: &ProjectBalanceReport, metrics: &ProjectMetrics) -> Self {
+        Self {
+            recorded_at: unix_now(),
+            snapshot: MetricsSnapshot::from_report(report, metrics),
+            module_count: metrics.module_count(),
+        }
+    }
+
+    /// Total issue count across severities.
+    pub fn issue_count(&self) -> usize {
+        let issues = &self.snapshot.issues;
+        issues.critical + issues.high + issues.medium + issues.low
+    }
+
+    /// `recorded_at` as `YYYY-MM-DD HH:MM` (UTC).
+    pub fn recorded_date(&self) -> String {
+        let days = (self.recorded_at / 86_400) as i64;
+        let minutes = self.recorded_at % 86_400 / 60;
+        let (year, month, day) = civil_from_days(days);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+impl TrendHistory {
+    /// Read a history file; a missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid trend history file {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Write the history as pretty-printed JSON, replacing the file in one step.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+        let mut writer = BufWriter::new(File::create(&partial)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&partial, path).inspect_err(|_| {
+            let _ = fs::remove_file(&partial);
+        })
+    }
+
+    /// Append `entry`, then drop the oldest entries beyond `max_entries`.
+    pub fn push(&mut self, entry: TrendEntry, max_entries: Option<usize>) {
+        self.entries.push(entry);
+        if let Some(max) = max_entries {
+            let excess = self.entries.len().saturating_sub(max);
+            self.entries.drain(..excess);
+        }
+    }
+
+    /// Append `entry` to the history file at `path`; returns the number of stored entries.
+    pub fn record(path: &Path, entry: TrendEntry, max_entries: Option<usize>) -> io::Result<usize> {
+        let mut history = Self::load(path)?;
+        history.push(entry, max_entries);
+        history.write(path)?;
+        Ok(history.entries.len())
+    }
+
+    /// Entries not comparable with the newest one (analyzer version or config changed).
+    pub fn warnings(&self) -> Vec<String> {
+        let Some(newest) = self.entries.last() else {
+            return Vec::new();
+        };
+        let current = (
+            newest.snapshot.analyzer_version.as_deref(),
+            newest.snapshot.config_hash.as_deref(),
+        );
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                comparability_warnings(
+                    &format!("Run recorded at {}", entry.recorded_date()),
+                    (
+                        entry.snapshot.analyzer_version.as_deref(),
+                        entry.snapshot.config_hash.as_deref(),
+                    ),
+                    current,
+                )
+            })
+            .collect()
+    }
+}
+
+/// One block character per value, scaled between the smallest and largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max <= min {
+                BARS[BARS.len() / 2]
+            } else {
+                let level = (value - min) / (max - min) * (BARS.len() - 1) as f64;
+                BARS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Gregorian (year, month, day) of a day count since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::ANALYZER_VERSION;
+    use crate::metric_snapshot::SeverityCounts;
+
+    fn entry(recorded_at: u64, grade: &str, score: f64, high: usize) -> TrendEntry {
+        TrendEntry {
+            recorded_at,
+            snapshot: MetricsSnapshot {
+                analyzer_version: Some(ANALYZER_VERSION.to_string()),
+                config_hash: Some("abc".to_string()),
+                health_grade: grade.to_string(),
+                average_score: score,
+                issues: SeverityCounts {
+                    high,
+                    ..SeverityCounts::default()
+                },
+                circular_dependencies: 0,
+            },
+            module_count: 12,
+        }
+    }
+
+    #[test]
+    fn test_records_append_and_keep_only_the_newest_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("trend.json");
+
+        assert_eq!(TrendHistory::load(&path).unwrap(), TrendHistory::default());
+        for (i, score) in [0.70, 0.75, 0.80].into_iter().enumerate() {
+            let stored =
+                TrendHistory::record(&path, entry(i as u64, "B", score, 3 - i), Some(2)).unwrap();
+            assert_eq!(stored, (i + 1).min(2));
+        }
+
+        let history = TrendHistory::load(&path).unwrap();
+        let scores: Vec<f64> = history
+            .entries
+            .iter()
+            .map(|e| e.snapshot.average_score)
+            .collect();
+        assert_eq!(scores, vec![0.75, 0.80]);
+        assert!(history.warnings().is_empty());
+
+        // Snapshot fields sit next to the timestamp rather than in a nested object
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"health_grade\": \"B\""), "{json}");
+
+        std::fs::write(&path, "[").unwrap();
+        let err = TrendHistory::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_entries_from_another_config_are_flagged() {
+        let mut old = entry(0, "C", 0.6, 4);
+        old.snapshot.config_hash = Some("old".to_string());
+        let history = TrendHistory {
+            entries: vec![old, entry(86_400, "B", 0.8, 1)],
+        };
+        let warnings = history.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("Run recorded at 1970-01-01 00:00"));
+    }
+
+    #[test]
+    fn test_sparklines_and_dates_render() {
+        assert_eq!(sparkline(&[0.0, 0.5, 1.0]), "▁▅█");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(
+            entry(1_792_152_000, "A", 0.9, 0).recorded_date(),
+            "2026-10-16 12:00"
+        );
+    }
+}
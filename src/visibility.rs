@@ -0,0 +1,405 @@
+//! Visibility audit (`--visibility-audit`).
+//!
+//! A `pub` item that only its own crate uses invites coupling from places that
+//! have no reason to know about it. For every `pub` type, trait, and function
+//! this audit collects the modules that actually reference it (imports, calls,
+//! and typed field accesses) and suggests the narrowest visibility that still
+//! covers them: `pub(super)` when every user lives under the owner's parent
+//! module, `pub(crate)` otherwise.
+//!
+//! A library's public API is left alone: items in modules reachable from
+//! `lib.rs` through a chain of `pub mod`s, items re-exported with `pub use`
+//! from such modules, and items referenced by `lib.rs` or another workspace
+//! crate. Items no other analyzed module references are skipped as well.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::analyzer::ItemDepType;
+use crate::metrics::dimensions::Visibility;
+use crate::metrics::project::ProjectMetrics;
+
+/// Narrower visibility suggested for a `pub` item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SuggestedVisibility {
+    /// Every user is under the owner's parent module.
+    #[serde(rename = "pub(super)")]
+    PubSuper,
+    /// Every user is inside the crate.
+    #[serde(rename = "pub(crate)")]
+    PubCrate,
+}
+
+impl fmt::Display for SuggestedVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuggestedVisibility::PubSuper => write!(f, "pub(super)"),
+            SuggestedVisibility::PubCrate => write!(f, "pub(crate)"),
+        }
+    }
+}
+
+/// A `pub` item whose actual users fit a narrower visibility.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VisibilitySuggestion {
+    /// `module::Item`
+    pub item: String,
+    /// `type`, `trait`, or `function`
+    pub kind: &'static str,
+    /// Source file of the owning module.
+    pub file: String,
+    /// 1-based declaration line (0 if unknown).
+    pub line: usize,
+    /// Narrowest visibility covering every user.
+    pub suggested: SuggestedVisibility,
+    /// Modules referencing the item, sorted.
+    pub used_by: Vec<String>,
+}
+
+impl VisibilitySuggestion {
+    /// One-line reason, e.g. ``consider `pub(crate)`: only used within this crate``.
+    pub fn reason(&self) -> String {
+        match self.suggested {
+            SuggestedVisibility::PubSuper => format!(
+                "consider `{}`: only used under its parent module",
+                self.suggested
+            ),
+            SuggestedVisibility::PubCrate => {
+                format!("consider `{}`: only used within this crate", self.suggested)
+            }
+        }
+    }
+}
+
+/// Result of the visibility audit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VisibilityAudit {
+    /// `pub` items found.
+    pub checked: usize,
+    /// Items that could be narrowed, sorted by item path.
+    pub suggestions: Vec<VisibilitySuggestion>,
+}
+
+/// Who references an item.
+#[derive(Default)]
+struct ItemUsage<'a> {
+    users: BTreeSet<&'a str>,
+    /// Referenced from `lib.rs` or through another workspace crate.
+    public_api: bool,
+}
+
+/// Suggest narrower visibility for `pub` items that only their own crate uses.
+pub fn analyze_visibility(metrics: &ProjectMetrics) -> VisibilityAudit {
+    let workspace_crates: Vec<String> = metrics
+        .workspace_members
+        .iter()
+        .map(|name| name.replace('-', "_"))
+        .collect();
+
+    // (owner module, item name) -> usage
+    let mut usages: HashMap<(String, String), ItemUsage> = HashMap::new();
+    for (module_name, module) in &metrics.modules {
+        let is_lib_root = module.path.file_name().is_some_and(|name| name == "lib.rs");
+        let mut record = |owner: String, item: String, via_other_crate: bool| {
+            if owner == *module_name {
+                return;
+            }
+            let usage = usages.entry((owner, item)).or_default();
+            usage.users.insert(module_name.as_str());
+            usage.public_api |= is_lib_root || via_other_crate;
+        };
+
+        for import in &module.internal_deps {
+            if let Some((owner, item, via_other_crate)) =
+                resolve_item_path(metrics, module_name, import, &workspace_crates)
+            {
+                record(owner, item, via_other_crate);
+            }
+        }
+
+        for dep in &module.item_dependencies {
+            let path = match dep.dep_type {
+                ItemDepType::FunctionCall => match dep.target_module.as_deref() {
+                    // `target_module` is either the owning module or the full item path
+                    Some(target) if metrics.modules.contains_key(target) => {
+                        let name = dep.target.rsplit("::").next().unwrap_or(&dep.target);
+                        record(target.to_string(), name.to_string(), false);
+                        continue;
+                    }
+                    Some(target) => target,
+                    None => dep.target.as_str(),
+                },
+                ItemDepType::FieldAccess => match dep.target.rsplit_once('.') {
+                    Some((type_path, _)) => type_path,
+                    None => continue,
+                },
+                ItemDepType::MethodCall => continue,
+                _ => dep.target.as_str(),
+            };
+            if let Some((owner, item, via_other_crate)) =
+                resolve_item_path(metrics, module_name, path, &workspace_crates)
+            {
+                record(owner, item, via_other_crate);
+            }
+        }
+    }
+
+    let (public_modules, reexported) = library_public_api(metrics, &workspace_crates);
+
+    let mut audit = VisibilityAudit::default();
+    for (module_name, module) in &metrics.modules {
+        let types = module
+            .type_definitions
+            .values()
+            .filter(|def| def.visibility == Visibility::Public)
+            .map(|def| {
+                let kind = if def.is_trait { "trait" } else { "type" };
+                (def.name.as_str(), kind, def.line)
+            });
+        let functions = module
+            .function_definitions
+            .values()
+            .filter(|def| def.visibility == Visibility::Public)
+            .map(|def| (def.name.as_str(), "function", def.line));
+
+        for (name, kind, line) in types.chain(functions) {
+            audit.checked += 1;
+            if public_modules.contains(module_name.as_str()) {
+                continue;
+            }
+            let Some(usage) = usages.get(&(module_name.clone(), name.to_string())) else {
+                continue;
+            };
+            if usage.public_api
+                || usage.users.is_empty()
+                || reexported.contains(&(module_name.clone(), name.to_string()))
+            {
+                continue;
+            }
+
+            let suggested = match module_name.rsplit_once("::") {
+                Some((parent, _))
+                    if usage.users.iter().all(|user| {
+                        *user == parent || user.starts_with(&format!("{}::", parent))
+                    }) =>
+                {
+                    SuggestedVisibility::PubSuper
+                }
+                _ => SuggestedVisibility::PubCrate,
+            };
+
+            audit.suggestions.push(VisibilitySuggestion {
+                item: format!("{}::{}", module_name, name),
+                kind,
+                file: module.path.display().to_string(),
+                line,
+                suggested,
+                used_by: usage.users.iter().map(|user| user.to_string()).collect(),
+            });
+        }
+    }
+
+    audit.suggestions.sort_by(|a, b| a.item.cmp(&b.item));
+    audit
+}
+
+/// Modules reachable from a `lib.rs` root through `pub mod`s, and the
+/// `(owner, item)` pairs those modules re-export with `pub use`.
+///
+/// A glob re-export (`pub use inner::*`) makes the whole target module public.
+fn library_public_api<'a>(
+    metrics: &'a ProjectMetrics,
+    workspace_crates: &[String],
+) -> (HashSet<&'a str>, HashSet<(String, String)>) {
+    let mut public_modules: HashSet<&str> = HashSet::new();
+    let mut stack: Vec<(&str, bool)> = metrics
+        .modules
+        .iter()
+        .filter(|(_, module)| module.path.file_name().is_some_and(|name| name == "lib.rs"))
+        .map(|(name, _)| (name.as_str(), true))
+        .collect();
+    let mut reexported = HashSet::new();
+
+    while let Some((module_name, is_root)) = stack.pop() {
+        if !public_modules.insert(module_name) {
+            continue;
+        }
+        let module = &metrics.modules[module_name];
+
+        for path in &module.reexports {
+            // Root-relative paths in `lib.rs` (`pub use analyzer::X`) start at the crate root
+            let path = match path.split("::").next() {
+                Some("crate" | "super") => path.clone(),
+                Some("self") if !is_root => path.clone(),
+                Some("self") => path.replacen("self", "crate", 1),
+                _ if is_root => format!("crate::{}", path),
+                _ => path.clone(),
+            };
+            let Some((owner, item, _)) =
+                resolve_item_path(metrics, module_name, &path, workspace_crates)
+            else {
+                continue;
+            };
+            if item == "*" {
+                if let Some((name, _)) = metrics.modules.get_key_value(&owner) {
+                    stack.push((name.as_str(), false));
+                }
+            } else {
+                reexported.insert((owner, item));
+            }
+        }
+
+        for child in &module.public_submodules {
+            let child_name = if is_root {
+                child.clone()
+            } else {
+                format!("{}::{}", module_name, child)
+            };
+            if let Some((name, _)) = metrics.modules.get_key_value(&child_name) {
+                stack.push((name.as_str(), false));
+            }
+        }
+    }
+
+    (public_modules, reexported)
+}
+
+/// Resolve a referenced path to `(owner module, item name, via another crate)`.
+///
+/// Handles `crate::`, `self::`, `super::`, paths through other workspace crates,
+/// and bare type names known to the type registry.
+fn resolve_item_path(
+    metrics: &ProjectMetrics,
+    from_module: &str,
+    path: &str,
+    workspace_crates: &[String],
+) -> Option<(String, String, bool)> {
+    let segments: Vec<&str> = path.split("::").filter(|s| !s.is_empty()).collect();
+    let (&item, module_segments) = segments.split_last()?;
+
+    if module_segments.is_empty() {
+        // Bare name: only types can be resolved unambiguously
+        let owner = metrics.get_type_module(item)?;
+        return Some((owner.to_string(), item.to_string(), false));
+    }
+
+    let mut via_other_crate = false;
+    let mut base: Vec<&str> = Vec::new();
+    let mut rest = module_segments;
+    match rest[0] {
+        "crate" => rest = &rest[1..],
+        "self" => {
+            base = from_module.split("::").collect();
+            rest = &rest[1..];
+        }
+        "super" => {
+            base = from_module.split("::").collect();
+            while rest.first() == Some(&"super") {
+                base.pop();
+                rest = &rest[1..];
+            }
+        }
+        first if workspace_crates.iter().any(|name| name == first) => {
+            via_other_crate = true;
+            rest = &rest[1..];
+        }
+        // `Type::assoc` on a registered type, e.g. `Config::new`
+        first if metrics.get_type_module(first).is_some() && rest.len() == 1 => {
+            let owner = metrics.get_type_module(first)?;
+            return Some((owner.to_string(), first.to_string(), false));
+        }
+        _ => return None,
+    }
+
+    base.extend_from_slice(rest);
+    let owner = base.join("::");
+    if owner.is_empty() || !metrics.modules.contains_key(&owner) {
+        // `crate::module::Type::assoc`: the item is one segment earlier
+        let (&type_name, type_module) = base.split_last()?;
+        let owner = type_module.join("::");
+        if !metrics.modules.contains_key(&owner) {
+            return None;
+        }
+        return Some((owner, type_name.to_string(), via_other_crate));
+    }
+    Some((owner, item.to_string(), via_other_crate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::analyze_files;
+
+    #[test]
+    fn test_pub_type_used_only_within_crate_is_suggested_pub_crate() {
+        let metrics = analyze_files(&[
+            (
+                "main.rs",
+                "mod billing;\nmod orders;\nfn main() { orders::place(); }\n",
+            ),
+            (
+                "orders.rs",
+                "use crate::billing::invoice::Invoice;\n\npub fn place() {\n    let _ = Invoice::default();\n}\n",
+            ),
+            ("billing/mod.rs", "pub mod invoice;\npub mod ledger;\n"),
+            (
+                "billing/invoice.rs",
+                "#[derive(Default)]\npub struct Invoice;\n\npub struct Draft;\n",
+            ),
+            (
+                "billing/ledger.rs",
+                "use super::invoice::Draft;\n\npub fn post(_d: Draft) {}\n",
+            ),
+        ]);
+        let audit = analyze_visibility(&metrics);
+
+        let invoice = audit
+            .suggestions
+            .iter()
+            .find(|s| s.item == "billing::invoice::Invoice")
+            .unwrap_or_else(|| panic!("no suggestion for Invoice: {:?}", audit.suggestions));
+        assert_eq!(invoice.suggested, SuggestedVisibility::PubCrate);
+        assert_eq!(invoice.used_by, vec!["orders".to_string()]);
+        assert_eq!(
+            invoice.reason(),
+            "consider `pub(crate)`: only used within this crate"
+        );
+
+        let draft = audit
+            .suggestions
+            .iter()
+            .find(|s| s.item == "billing::invoice::Draft")
+            .unwrap();
+        assert_eq!(draft.suggested, SuggestedVisibility::PubSuper);
+        assert_eq!(draft.used_by, vec!["billing::ledger".to_string()]);
+
+        // `ledger::post` has no users outside its module: nothing to suggest.
+        assert!(!audit.suggestions.iter().any(|s| s.item.ends_with("post")));
+    }
+
+    #[test]
+    fn test_library_public_api_is_not_narrowed() {
+        let metrics = analyze_files(&[
+            (
+                "lib.rs",
+                "pub mod api;\nmod engine;\nmod store;\n\npub use store::Store;\n",
+            ),
+            (
+                "api.rs",
+                "use crate::engine::Engine;\n\npub struct Request;\n\npub fn run(_e: Engine) {}\n",
+            ),
+            (
+                "engine.rs",
+                "use crate::api::Request;\nuse crate::store::Store;\n\npub struct Engine;\n\npub fn step(_r: Request, _s: Store) {}\n",
+            ),
+            ("store.rs", "pub struct Store;\n"),
+        ]);
+        let audit = analyze_visibility(&metrics);
+        let items: Vec<&str> = audit.suggestions.iter().map(|s| s.item.as_str()).collect();
+
+        // `engine` is private, so its pub items only serve the crate.
+        assert_eq!(items, vec!["engine::Engine"]);
+    }
+}
@@ -1,15 +1,35 @@
 //! Git history analysis for volatility measurement
 //!
 //! Analyzes git log to determine how frequently files change.
-//! Optimized for large repositories using streaming and git path filtering.
+//! Optimized for large repositories using streaming and git path filtering;
+//! `git log` output is counted as it arrives, can be capped at a number of
+//! commits and is stopped after an optional timeout, keeping what was read.
 
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 
+use glob::Pattern;
 use thiserror::Error;
 
+use crate::config::CompiledConfig;
+use crate::log_info;
+
+/// Line `git log --pretty=format:__COMMIT__` prints before each commit's files.
+const COMMIT_MARKER: &str = "__COMMIT__";
+
+/// Commits with more changed files are left out of co-change pairs.
+const MAX_FILES_PER_COMMIT: usize = 50;
+
+/// Distinct co-change pairs kept in memory before the rarest are dropped.
+const MAX_CO_CHANGE_PAIRS: usize = 200_000;
+
+/// Commits between two progress lines in `with_progress` mode.
+const PROGRESS_INTERVAL: usize = 5000;
+
 /// Volatility levels (how often a component changes)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Volatility {
@@ -61,6 +81,20 @@ pub struct VolatilityAnalyzer {
     pub file_changes: HashMap<String, usize>,
     /// Analysis period in months
     pub period_months: usize,
+    /// Subdirectory (relative to the analyzed path) the git log is limited to
+    scope: Option<PathBuf>,
+    /// Paths left out of the change counts (`[volatility] exclude`)
+    exclude: Vec<Pattern>,
+    /// Commits read by the last `analyze`
+    pub commits: usize,
+    /// Whether the last analysis hit the timeout and only read part of the history
+    pub timed_out: bool,
+    /// Newest commits to read at most (`--git-max-commits`)
+    max_commits: Option<usize>,
+    /// Deadline for each `git log` run (`--git-timeout`)
+    timeout: Option<Duration>,
+    /// Print progress to stderr while reading history
+    progress: bool,
 }
 
 impl VolatilityAnalyzer {
@@ -69,65 +103,216 @@ impl VolatilityAnalyzer {
         Self {
             file_changes: HashMap::new(),
             period_months,
+            scope: None,
+            exclude: Vec::new(),
+            commits: 0,
+            timed_out: false,
+            max_commits: None,
+            timeout: None,
+            progress: false,
         }
     }
 
+    /// An analyzer with the config's `[volatility] exclude` patterns and scope
+    /// and the git history limits set on it (`--git-max-commits`, `--git-timeout`)
+    pub fn from_config(period_months: usize, config: &CompiledConfig) -> Self {
+        let analyzer = Self::new(period_months)
+            .with_exclude(config.volatility_exclude_patterns().to_vec())
+            .with_max_commits(config.git_max_commits)
+            .with_timeout(config.git_timeout);
+        match &config.git_scope {
+            Some(scope) => analyzer.with_scope(scope),
+            None => analyzer,
+        }
+    }
+
+    /// Only read history under `scope`, a directory relative to the analyzed path
+    pub fn with_scope(mut self, scope: impl Into<PathBuf>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Leave paths matching `patterns` (git paths, relative to the repository root)
+    /// out of change counts and co-change pairs
+    pub fn with_exclude(mut self, patterns: Vec<Pattern>) -> Self {
+        self.exclude = patterns;
+        self
+    }
+
+    /// Whether a path from git output is a Rust file that is not excluded
+    fn is_relevant(&self, path: &str) -> bool {
+        path.ends_with(".rs") && !self.exclude.iter().any(|pattern| pattern.matches(path))
+    }
+
+    /// Git pathspec for the Rust files in scope
+    fn pathspec(&self) -> String {
+        match &self.scope {
+            Some(scope) => format!(
+                ":(glob){}/**/*.rs",
+                scope.to_string_lossy().trim_end_matches('/')
+            ),
+            None => "*.rs".to_string(),
+        }
+    }
+
+    /// Read at most `max_commits` commits, newest first (`git log --max-count`)
+    pub fn with_max_commits(mut self, max_commits: Option<usize>) -> Self {
+        self.max_commits = max_commits;
+        self
+    }
+
+    /// Kill `git log` if it runs longer than `timeout`; what was read until then
+    /// is kept and [`timed_out`](Self::timed_out) is set
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Report the number of commits read to stderr while git history is streamed
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Analyze git history for a repository (optimized version)
     ///
     /// Optimizations applied:
     /// 1. Use `-- "*.rs"` to filter .rs files at git level
-    /// 2. Use streaming with BufReader instead of loading all into memory
+    /// 2. Stream the log and count changes line by line; nothing per commit is kept
     /// 3. Use `--diff-filter=AMRC` to skip deleted files
+    ///
+    /// Only history under `repo_path` (and `with_scope`, if set) is read.
+    /// Renames are followed, so a moved file keeps the changes made before the
+    /// move, counted under its current path.
     pub fn analyze(&mut self, repo_path: &Path) -> Result<(), VolatilityError> {
-        // Check if it's a git repo
-        let git_check = Command::new("git")
-            .args(["rev-parse", "--git-dir"])
-            .current_dir(repo_path)
-            .stderr(Stdio::null())
-            .output()?;
+        check_git_repo(repo_path)?;
+        let (counter, timed_out) = self.read_changes(repo_path)?;
+        self.store_changes(counter);
+        self.timed_out = timed_out;
+        Ok(())
+    }
 
-        if !git_check.status.success() {
-            return Err(VolatilityError::NotGitRepo);
-        }
+    /// [`analyze`](Self::analyze) and [`analyze_temporal_coupling`](Self::analyze_temporal_coupling)
+    /// with both git logs streamed concurrently.
+    pub fn analyze_with_temporal(
+        &mut self,
+        repo_path: &Path,
+    ) -> Result<Vec<TemporalCoupling>, VolatilityError> {
+        check_git_repo(repo_path)?;
+        let (changes, co_changes) = std::thread::scope(|scope| {
+            let co_changes = scope.spawn(|| self.read_co_changes(repo_path));
+            let changes = self.read_changes(repo_path);
+            let co_changes = co_changes
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            (changes, co_changes)
+        });
+        let (changes, changes_timed_out) = changes?;
+        let (co_changes, co_changes_timed_out) = co_changes?;
+        self.store_changes(changes);
+        self.timed_out = changes_timed_out || co_changes_timed_out;
+        Ok(self.temporal_couplings(co_changes.pair_counts))
+    }
 
-        // Optimized: use --diff-filter and path spec to reduce output
+    fn read_changes(&self, repo_path: &Path) -> Result<(ChangeCounter, bool), VolatilityError> {
         // --diff-filter=AMRC: Added, Modified, Renamed, Copied (skip Deleted)
-        let mut child = Command::new("git")
-            .args([
-                "log",
-                "--pretty=format:",
-                "--name-only",
-                "--diff-filter=AMRC",
-                &format!("--since={} months ago", self.period_months),
-                "--",
-                "*.rs",
-            ])
+        // -M with --name-status reports renames as `R<score>\told\tnew`
+        let mut counter = ChangeCounter::default();
+        let timed_out =
+            self.stream_git_log(repo_path, &["--name-status", "-M"], self.progress, |line| {
+                counter.line(line, |path| self.is_relevant(path))
+            })?;
+        Ok((counter, timed_out))
+    }
+
+    fn read_co_changes(
+        &self,
+        repo_path: &Path,
+    ) -> Result<(CoChangeCounter, bool), VolatilityError> {
+        let mut counter = CoChangeCounter::default();
+        let timed_out = self.stream_git_log(repo_path, &["--name-only"], false, |line| {
+            counter.line(line, |path| self.is_relevant(path))
+        })?;
+        // A commit cut off by the timeout may be missing files; leave it out
+        if timed_out {
+            counter.current_files.clear();
+        } else {
+            counter.end_commit();
+        }
+        Ok((counter, timed_out))
+    }
+
+    fn store_changes(&mut self, counter: ChangeCounter) {
+        self.file_changes = counter.file_changes;
+        self.commits = counter.commits;
+    }
+
+    /// Run `git log` with `args` over the Rust files in scope and hand every
+    /// output line to `on_line` as it arrives. Each commit starts with a
+    /// `__COMMIT__` line. Returns whether the timeout cut the log short.
+    fn stream_git_log(
+        &self,
+        repo_path: &Path,
+        args: &[&str],
+        progress: bool,
+        mut on_line: impl FnMut(&str),
+    ) -> Result<bool, VolatilityError> {
+        let mut command = Command::new("git");
+        command
+            .args(["log", "--pretty=format:__COMMIT__", "--diff-filter=AMRC"])
+            .args(args)
+            .arg(format!("--since={} months ago", self.period_months));
+        if let Some(max_commits) = self.max_commits {
+            command.arg(format!("--max-count={}", max_commits));
+        }
+        let mut child = command
+            .args(["--", &self.pathspec()])
             .current_dir(repo_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::null())
             .spawn()?;
+        let stdout = child.stdout.take();
+
+        // The watchdog owns the child: it kills git at the deadline, which ends
+        // the stream below, and reaps it either way.
+        let timeout = self.timeout;
+        let (done, finished) = mpsc::channel::<()>();
+        let watchdog = std::thread::spawn(move || {
+            let timed_out = match timeout {
+                Some(timeout) => matches!(
+                    finished.recv_timeout(timeout),
+                    Err(RecvTimeoutError::Timeout)
+                ),
+                None => {
+                    let _ = finished.recv();
+                    false
+                }
+            };
+            if timed_out {
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+            timed_out
+        });
 
-        // Stream processing with BufReader
-        if let Some(stdout) = child.stdout.take() {
-            let reader = BufReader::with_capacity(64 * 1024, stdout); // 64KB buffer
-
-            for line in reader.lines() {
-                let line = match line {
-                    Ok(l) => l,
-                    Err(_) => continue,
+        let mut commits = 0;
+        if let Some(stdout) = stdout {
+            for line in BufReader::with_capacity(64 * 1024, stdout).lines() {
+                let Ok(line) = line else {
+                    continue;
                 };
-
-                let line = line.trim();
-                if !line.is_empty() && line.ends_with(".rs") {
-                    *self.file_changes.entry(line.to_string()).or_insert(0) += 1;
+                if line == COMMIT_MARKER {
+                    commits += 1;
+                    if progress && commits % PROGRESS_INTERVAL == 0 {
+                        log_info!("  ...{} commits read", commits);
+                    }
                 }
+                on_line(&line);
             }
         }
+        drop(done);
 
-        // Wait for git to finish
-        let _ = child.wait();
-
-        Ok(())
+        Ok(watchdog.join().unwrap_or(false))
     }
 
     /// Get volatility level for a file
@@ -157,82 +342,31 @@ impl VolatilityAnalyzer {
     /// Based on Khononov's modularity model: co-changing files suggest
     /// shared knowledge even without explicit code dependencies.
     pub fn analyze_temporal_coupling(
-        &self,
+        &mut self,
         repo_path: &Path,
     ) -> Result<Vec<TemporalCoupling>, VolatilityError> {
-        // Get commit-grouped file changes
-        let mut child = Command::new("git")
-            .args([
-                "log",
-                "--pretty=format:__COMMIT__",
-                "--name-only",
-                "--diff-filter=AMRC",
-                &format!("--since={} months ago", self.period_months),
-                "--",
-                "*.rs",
-            ])
-            .current_dir(repo_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()?;
-
-        let mut commits: Vec<Vec<String>> = Vec::new();
-        let mut current_files: Vec<String> = Vec::new();
-
-        if let Some(stdout) = child.stdout.take() {
-            let reader = BufReader::with_capacity(64 * 1024, stdout);
-            for line in reader.lines() {
-                let line = match line {
-                    Ok(l) => l,
-                    Err(_) => continue,
-                };
-                let trimmed = line.trim();
-                if trimmed == "__COMMIT__" {
-                    if current_files.len() >= 2 {
-                        commits.push(std::mem::take(&mut current_files));
-                    } else {
-                        current_files.clear();
-                    }
-                } else if !trimmed.is_empty() && trimmed.ends_with(".rs") {
-                    current_files.push(trimmed.to_string());
-                }
-            }
-            // Don't forget the last commit
-            if current_files.len() >= 2 {
-                commits.push(current_files);
-            }
-        }
-
-        let _ = child.wait();
+        let (counter, timed_out) = self.read_co_changes(repo_path)?;
+        self.timed_out |= timed_out;
+        Ok(self.temporal_couplings(counter.pair_counts))
+    }
 
-        // Count co-change frequency for each file pair
-        // Skip commits with too many files (e.g., formatter runs, merge commits)
-        // as they produce O(n²) noise rather than meaningful coupling signal
-        const MAX_FILES_PER_COMMIT: usize = 50;
-        let mut pair_counts: HashMap<(String, String), usize> = HashMap::new();
-        for changed_files in &commits {
-            if changed_files.len() > MAX_FILES_PER_COMMIT {
-                continue;
-            }
-            for left_index in 0..changed_files.len() {
-                for right_index in (left_index + 1)..changed_files.len() {
-                    let (first_file, second_file) =
-                        if changed_files[left_index] < changed_files[right_index] {
-                            (
-                                changed_files[left_index].clone(),
-                                changed_files[right_index].clone(),
-                            )
-                        } else {
-                            (
-                                changed_files[right_index].clone(),
-                                changed_files[left_index].clone(),
-                            )
-                        };
-                    *pair_counts.entry((first_file, second_file)).or_default() += 1;
-                }
-            }
+    /// Count co-changing file pairs in per-commit file sets.
+    ///
+    /// Pairs that changed together in at least 3 commits are returned, most
+    /// frequent first. The ratio is relative to `file_changes` of the
+    /// less-changed file of each pair.
+    pub fn co_change_pairs(&self, commits: &[Vec<String>]) -> Vec<TemporalCoupling> {
+        let mut counter = CoChangeCounter::default();
+        for changed_files in commits {
+            counter.add_commit(changed_files);
         }
+        self.temporal_couplings(counter.pair_counts)
+    }
 
+    fn temporal_couplings(
+        &self,
+        pair_counts: HashMap<(String, String), usize>,
+    ) -> Vec<TemporalCoupling> {
         // Filter to significant co-changes (3+ times together)
         let mut result: Vec<TemporalCoupling> = pair_counts
             .into_iter()
@@ -251,13 +385,17 @@ impl VolatilityAnalyzer {
             .collect();
 
         result.sort_by(|a, b| {
-            b.co_change_count.cmp(&a.co_change_count).then(
-                b.coupling_ratio
-                    .partial_cmp(&a.coupling_ratio)
-                    .unwrap_or(std::cmp::Ordering::Equal),
-            )
+            b.co_change_count
+                .cmp(&a.co_change_count)
+                .then(
+                    b.coupling_ratio
+                        .partial_cmp(&a.coupling_ratio)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+                .then_with(|| a.file_a.cmp(&b.file_a))
+                .then_with(|| a.file_b.cmp(&b.file_b))
         });
-        Ok(result)
+        result
     }
 
     /// Get volatility statistics
@@ -289,6 +427,129 @@ impl VolatilityAnalyzer {
     }
 }
 
+fn check_git_repo(repo_path: &Path) -> Result<(), VolatilityError> {
+    let git_check = Command::new("git")
+        .args(["rev-parse", "--git-dir"])
+        .current_dir(repo_path)
+        .stderr(Stdio::null())
+        .output()?;
+    if git_check.status.success() {
+        Ok(())
+    } else {
+        Err(VolatilityError::NotGitRepo)
+    }
+}
+
+/// Per-file change counts from `git log --name-status` output, newest commit first.
+///
+/// A rename `R<score> old new` carries later changes of `new` back to the
+/// history of `old`: everything is counted under the file's latest name.
+#[derive(Debug, Default)]
+struct ChangeCounter {
+    file_changes: HashMap<String, usize>,
+    renamed_to: HashMap<String, String>,
+    commits: usize,
+}
+
+impl ChangeCounter {
+    fn line(&mut self, line: &str, is_relevant: impl Fn(&str) -> bool) {
+        let line = line.trim();
+        if line == COMMIT_MARKER {
+            self.commits += 1;
+            return;
+        }
+        let mut fields = line.split('\t');
+        let (Some(status), Some(path)) = (fields.next(), fields.next()) else {
+            return;
+        };
+        let changed = match (status.chars().next(), fields.next()) {
+            (Some('R'), Some(new_path)) => {
+                let current = self.current_name(new_path);
+                if current != path {
+                    self.renamed_to.insert(path.to_string(), current.clone());
+                }
+                current
+            }
+            // Copies start a new history at the destination
+            (Some('C'), Some(new_path)) => self.current_name(new_path),
+            _ => self.current_name(path),
+        };
+        if is_relevant(&changed) {
+            *self.file_changes.entry(changed).or_insert(0) += 1;
+        }
+    }
+
+    fn current_name(&self, path: &str) -> String {
+        let mut name = path.to_string();
+        // Bounded walk: a file renamed back and forth must not loop forever
+        for _ in 0..self.renamed_to.len() {
+            match self.renamed_to.get(&name) {
+                Some(next) => name = next.clone(),
+                None => break,
+            }
+        }
+        name
+    }
+}
+
+/// Co-change pair counts from `git log --pretty=format:__COMMIT__ --name-only`
+/// output, accumulated one commit at a time.
+#[derive(Debug, Default)]
+struct CoChangeCounter {
+    pair_counts: HashMap<(String, String), usize>,
+    current_files: Vec<String>,
+}
+
+impl CoChangeCounter {
+    fn line(&mut self, line: &str, is_relevant: impl Fn(&str) -> bool) {
+        let line = line.trim();
+        if line == COMMIT_MARKER {
+            self.end_commit();
+        } else if !line.is_empty() && is_relevant(line) {
+            self.current_files.push(line.to_string());
+        }
+    }
+
+    fn end_commit(&mut self) {
+        let files = std::mem::take(&mut self.current_files);
+        self.add_commit(&files);
+    }
+
+    fn add_commit(&mut self, changed_files: &[String]) {
+        // Skip commits with too many files (e.g., formatter runs, merge commits)
+        // as they produce O(n²) noise rather than meaningful coupling signal
+        if changed_files.len() < 2 || changed_files.len() > MAX_FILES_PER_COMMIT {
+            return;
+        }
+        for left_index in 0..changed_files.len() {
+            for right_index in (left_index + 1)..changed_files.len() {
+                let (left, right) = (&changed_files[left_index], &changed_files[right_index]);
+                let pair = if left < right {
+                    (left.clone(), right.clone())
+                } else {
+                    (right.clone(), left.clone())
+                };
+                *self.pair_counts.entry(pair).or_default() += 1;
+            }
+        }
+        if self.pair_counts.len() > MAX_CO_CHANGE_PAIRS {
+            self.drop_rare_pairs(MAX_CO_CHANGE_PAIRS / 2);
+        }
+    }
+
+    /// Drop the least frequent pairs until at most `keep` remain.
+    ///
+    /// Pairs seen once or twice dominate long histories and rarely reach the
+    /// reporting minimum, so they go first.
+    fn drop_rare_pairs(&mut self, keep: usize) {
+        let mut min_count = 2;
+        while self.pair_counts.len() > keep {
+            self.pair_counts.retain(|_, count| *count >= min_count);
+            min_count += 1;
+        }
+    }
+}
+
 /// Temporal coupling between two files (co-change pattern)
 ///
 /// Represents files that frequently change together in git commits,
@@ -337,6 +598,147 @@ pub struct VolatilityStats {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_co_change_pairs_detects_files_changed_together_in_n_commits() {
+        let log = "__COMMIT__\nsrc/a.rs\nsrc/b.rs\n\n__COMMIT__\nsrc/b.rs\nsrc/a.rs\nREADME.md\n\n\
+                   __COMMIT__\nsrc/a.rs\n\n__COMMIT__\nsrc/a.rs\nsrc/b.rs\nsrc/c.rs\n\n\
+                   __COMMIT__\nsrc/c.rs\nsrc/b.rs\n";
+        let mut analyzer = VolatilityAnalyzer::new(6);
+        let mut counter = CoChangeCounter::default();
+        for line in log.lines() {
+            counter.line(line, |path| analyzer.is_relevant(path));
+        }
+        counter.end_commit();
+        // The single-file commit adds no pair; non-Rust files are ignored.
+        assert_eq!(counter.pair_counts.len(), 3);
+
+        analyzer.file_changes.insert("src/a.rs".to_string(), 4);
+        analyzer.file_changes.insert("src/b.rs".to_string(), 4);
+        let pairs = analyzer.temporal_couplings(counter.pair_counts);
+
+        // a+b co-change in 3 commits; b+c in only 2, below the reporting minimum.
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].file_a, "src/a.rs");
+        assert_eq!(pairs[0].file_b, "src/b.rs");
+        assert_eq!(pairs[0].co_change_count, 3);
+        assert_eq!(pairs[0].coupling_ratio, 0.75);
+    }
+
+    #[test]
+    fn test_change_counts_follow_renames_and_skip_excluded_paths() {
+        // Newest commit first, as `git log --name-status` prints it.
+        let log = "M\tsrc/orders.rs\n\n\
+                   R095\tsrc/order.rs\tsrc/orders.rs\n\
+                   M\tsrc/generated/schema.rs\n\n\
+                   M\tsrc/order.rs\nM\tREADME.md\n\n\
+                   A\tsrc/order.rs\nC080\tsrc/order.rs\tsrc/invoice.rs\n";
+        let analyzer =
+            VolatilityAnalyzer::new(6).with_exclude(vec![Pattern::new("**/generated/**").unwrap()]);
+        let mut counter = ChangeCounter::default();
+        for line in log.lines() {
+            counter.line(line, |path| analyzer.is_relevant(path));
+        }
+
+        let mut counts: Vec<_> = counter
+            .file_changes
+            .iter()
+            .map(|(path, count)| (path.as_str(), *count))
+            .collect();
+        counts.sort_unstable();
+        // The move keeps the two changes made to src/order.rs before it.
+        assert_eq!(counts, [("src/invoice.rs", 1), ("src/orders.rs", 4)]);
+    }
+
+    #[test]
+    fn test_max_commits_and_timeout_bound_the_history_read() {
+        let tmp = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(tmp.path())
+                .env("GIT_AUTHOR_NAME", "test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        for revision in 0..3 {
+            for file in ["a.rs", "b.rs", "c.rs"] {
+                std::fs::write(tmp.path().join(file), format!("// {}\n", revision)).unwrap();
+            }
+            git(&["add", "."]);
+            git(&["commit", "-qm", &format!("revision {}", revision)]);
+        }
+
+        let mut full = VolatilityAnalyzer::new(6).with_timeout(Some(Duration::from_secs(60)));
+        let temporal = full.analyze_with_temporal(tmp.path()).unwrap();
+        assert_eq!(full.commits, 3);
+        assert_eq!(full.get_change_count("a.rs"), 3);
+        assert_eq!(temporal.len(), 3);
+        assert!(temporal.iter().all(|pair| pair.co_change_count == 3));
+
+        let mut bounded = VolatilityAnalyzer::new(6).with_max_commits(Some(2));
+        bounded.analyze(tmp.path()).unwrap();
+        assert_eq!(bounded.commits, 2);
+        assert_eq!(bounded.get_change_count("a.rs"), 2);
+        assert!(
+            bounded
+                .analyze_temporal_coupling(tmp.path())
+                .unwrap()
+                .is_empty()
+        );
+
+        // Hitting the timeout keeps what was read instead of failing
+        let mut cut_short = VolatilityAnalyzer::new(6).with_timeout(Some(Duration::ZERO));
+        cut_short.analyze_with_temporal(tmp.path()).unwrap();
+        assert!(cut_short.timed_out || cut_short.commits == 3);
+    }
+
+    #[test]
+    fn test_co_change_pairs_are_bounded_by_dropping_the_rarest() {
+        let mut counter = CoChangeCounter::default();
+        for _ in 0..3 {
+            counter.add_commit(&["a.rs".to_string(), "b.rs".to_string()]);
+        }
+        counter.add_commit(&["a.rs".to_string(), "c.rs".to_string()]);
+        counter.add_commit(&["a.rs".to_string(), "c.rs".to_string()]);
+        counter.add_commit(&["b.rs".to_string(), "d.rs".to_string()]);
+
+        counter.drop_rare_pairs(2);
+        let mut kept: Vec<_> = counter.pair_counts.into_iter().collect();
+        kept.sort_unstable();
+        assert_eq!(
+            kept,
+            [
+                (("a.rs".to_string(), "b.rs".to_string()), 3),
+                (("a.rs".to_string(), "c.rs".to_string()), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scope_limits_the_git_pathspec() {
+        assert_eq!(VolatilityAnalyzer::new(6).pathspec(), "*.rs");
+        assert_eq!(
+            VolatilityAnalyzer::new(6)
+                .with_scope("crates/core/")
+                .pathspec(),
+            ":(glob)crates/core/**/*.rs"
+        );
+
+        let config: crate::config::CouplingConfig =
+            toml::from_str("[volatility]\nscope = \"crates/core\"\n").unwrap();
+        let config = CompiledConfig::from_config(config).unwrap();
+        assert_eq!(
+            VolatilityAnalyzer::from_config(6, &config).pathspec(),
+            ":(glob)crates/core/**/*.rs"
+        );
+    }
+
     #[test]
     fn test_volatility_classification() {
         let mut analyzer = VolatilityAnalyzer::new(6);
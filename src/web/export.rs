@@ -0,0 +1,315 @@
+//! Standalone HTML report (`--format html`)
+//!
+//! Renders the dataset the web UI fetches from `/api/graph` into one
+//! self-contained file for docs pipelines and CI artifacts: CSS is embedded,
+//! the dimension distributions and hotspot table are drawn as plain HTML, and
+//! the `GraphData` itself is inlined as JSON (`window.couplingData`) so the
+//! file opens offline and stays scriptable. No server or tokio runtime needed.
+
+use std::io::{self, Write};
+
+use crate::balance::score::IssueThresholds;
+use crate::cli_output::calculate_hotspots;
+use crate::header::OutputHeader;
+use crate::metrics::project::ProjectMetrics;
+
+use super::graph::{DimensionValue, Edge, GraphData, project_to_graph};
+
+/// Hotspots listed in the report table.
+const HOTSPOT_LIMIT: usize = 10;
+
+const STYLE: &str = r#"
+:root { --bg: #0f172a; --panel: #1e293b; --muted: #94a3b8; --text: #f8fafc;
+  --border: #475569; --green: #22c55e; --yellow: #eab308; --red: #ef4444;
+  --blue: #3b82f6; --orange: #f97316; }
+* { box-sizing: border-box; }
+body { margin: 0; padding: 2rem; background: var(--bg); color: var(--text);
+  font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; }
+h1 { margin: 0 0 0.25rem; }
+h2 { margin: 2rem 0 0.75rem; font-size: 1.1rem; }
+.meta { color: var(--muted); font-size: 0.85rem; margin: 0 0 1.5rem; }
+.cards { display: flex; flex-wrap: wrap; gap: 1rem; }
+.card { background: var(--panel); border: 1px solid var(--border); border-radius: 8px;
+  padding: 0.75rem 1rem; min-width: 9rem; }
+.card .value { font-size: 1.6rem; font-weight: 600; }
+.card .label { color: var(--muted); font-size: 0.8rem; }
+.charts { display: grid; grid-template-columns: repeat(auto-fit, minmax(18rem, 1fr)); gap: 1rem; }
+.chart { background: var(--panel); border: 1px solid var(--border); border-radius: 8px; padding: 1rem; }
+.chart h3 { margin: 0 0 0.75rem; font-size: 0.95rem; }
+.bar-row { display: grid; grid-template-columns: 8.5rem 1fr 3rem; gap: 0.5rem;
+  align-items: center; font-size: 0.85rem; margin: 0.3rem 0; }
+.bar { height: 0.8rem; background: var(--blue); border-radius: 4px; }
+.count { text-align: right; color: var(--muted); }
+table { width: 100%; border-collapse: collapse; background: var(--panel); font-size: 0.85rem; }
+th, td { text-align: left; padding: 0.5rem 0.75rem; border-bottom: 1px solid var(--border); vertical-align: top; }
+th { color: var(--muted); font-weight: 500; }
+.severity-critical { color: var(--red); }
+.severity-high { color: var(--orange); }
+.severity-medium { color: var(--yellow); }
+.severity-low { color: var(--green); }
+.no-data { color: var(--muted); }
+"#;
+
+/// Generate `--format html`: a standalone report with the web UI's dataset inlined.
+pub fn generate_html_report<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let graph = project_to_graph(metrics, thresholds);
+    let header = OutputHeader::new(metrics.config_hash.clone());
+    let title = match &metrics.workspace_name {
+        Some(name) => format!("Coupling Report: {}", name),
+        None => "Coupling Report".to_string(),
+    };
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>{}</title>", escape_html(&title))?;
+    writeln!(writer, "<style>{}</style>", STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>{}</h1>", escape_html(&title))?;
+    writeln!(
+        writer,
+        "<p class=\"meta\">cargo-coupling {} · config {} · generated at {}</p>",
+        escape_html(&header.analyzer_version),
+        escape_html(header.config_hash.as_deref().unwrap_or("unknown")),
+        header.generated_at
+    )?;
+
+    write_summary_cards(&graph, writer)?;
+    write_distributions(&graph, writer)?;
+    write_hotspots(metrics, thresholds, writer)?;
+    write_issues(&graph, writer)?;
+    write_inline_data(&graph, writer)?;
+
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
+fn write_summary_cards<W: Write>(graph: &GraphData, writer: &mut W) -> io::Result<()> {
+    let summary = &graph.summary;
+    let issues = &summary.issues_by_severity;
+    let cards = [
+        ("Health grade", summary.health_grade.clone()),
+        (
+            "Balance score",
+            format!("{:.0}%", summary.health_score * 100.0),
+        ),
+        ("Modules", summary.total_modules.to_string()),
+        (
+            "Couplings (internal / external)",
+            format!(
+                "{} / {}",
+                summary.internal_couplings, summary.external_couplings
+            ),
+        ),
+        (
+            "Issues (critical / high / medium / low)",
+            format!(
+                "{} / {} / {} / {}",
+                issues.critical, issues.high, issues.medium, issues.low
+            ),
+        ),
+        (
+            "Circular dependencies",
+            graph.circular_dependencies.len().to_string(),
+        ),
+    ];
+
+    writeln!(writer, "<section class=\"cards\">")?;
+    for (label, value) in cards {
+        writeln!(
+            writer,
+            "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>",
+            escape_html(&value),
+            escape_html(label)
+        )?;
+    }
+    writeln!(writer, "</section>")
+}
+
+fn write_distributions<W: Write>(graph: &GraphData, writer: &mut W) -> io::Result<()> {
+    type Dimension = fn(&Edge) -> &DimensionValue;
+    let charts: [(&str, &[&str], Dimension); 3] = [
+        (
+            "Integration Strength",
+            &["Intrusive", "Functional", "Model", "Contract"],
+            |edge| &edge.dimensions.strength,
+        ),
+        (
+            "Distance",
+            &[
+                "SameFunction",
+                "SameModule",
+                "DifferentModule",
+                "DifferentCrate",
+            ],
+            |edge| &edge.dimensions.distance,
+        ),
+        ("Volatility", &["High", "Medium", "Low"], |edge| {
+            &edge.dimensions.volatility
+        }),
+    ];
+
+    writeln!(writer, "<h2>Coupling Dimensions</h2>")?;
+    writeln!(writer, "<section class=\"charts\">")?;
+    for (name, labels, dimension) in charts {
+        let counts: Vec<usize> = labels
+            .iter()
+            .map(|label| {
+                graph
+                    .edges
+                    .iter()
+                    .filter(|edge| dimension(edge).label == *label)
+                    .count()
+            })
+            .collect();
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+        writeln!(writer, "<div class=\"chart\"><h3>{}</h3>", name)?;
+        for (label, count) in labels.iter().zip(counts) {
+            writeln!(
+                writer,
+                "<div class=\"bar-row\"><span>{}</span><div><div class=\"bar\" style=\"width: {:.1}%\"></div></div><span class=\"count\">{}</span></div>",
+                label,
+                count as f64 * 100.0 / max as f64,
+                count
+            )?;
+        }
+        writeln!(writer, "</div>")?;
+    }
+    writeln!(writer, "</section>")
+}
+
+fn write_hotspots<W: Write>(
+    metrics: &ProjectMetrics,
+    thresholds: &IssueThresholds,
+    writer: &mut W,
+) -> io::Result<()> {
+    let hotspots = calculate_hotspots(metrics, thresholds, HOTSPOT_LIMIT);
+
+    writeln!(writer, "<h2>Hotspots</h2>")?;
+    if hotspots.is_empty() {
+        return writeln!(writer, "<p class=\"no-data\">No hotspots detected</p>");
+    }
+    writeln!(
+        writer,
+        "<table><thead><tr><th>Module</th><th>Score</th><th>Issues</th><th>Suggestion</th><th>File</th></tr></thead><tbody>"
+    )?;
+    for hotspot in &hotspots {
+        let issues: Vec<String> = hotspot
+            .issues
+            .iter()
+            .map(|issue| {
+                format!(
+                    "<span class=\"severity-{}\">{}</span>",
+                    escape_html(&issue.severity.to_lowercase()),
+                    escape_html(&issue.issue_type)
+                )
+            })
+            .collect();
+        writeln!(
+            writer,
+            "<tr><td>{}{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&hotspot.module),
+            if hotspot.in_cycle { " (cycle)" } else { "" },
+            hotspot.score,
+            issues.join("<br>"),
+            escape_html(&hotspot.suggestion),
+            escape_html(hotspot.file_path.as_deref().unwrap_or(""))
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")
+}
+
+fn write_issues<W: Write>(graph: &GraphData, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "<h2>Issues</h2>")?;
+    if graph.issues.is_empty() {
+        return writeln!(writer, "<p class=\"no-data\">No issues detected</p>");
+    }
+    writeln!(
+        writer,
+        "<table><thead><tr><th>Severity</th><th>Type</th><th>Source → Target</th><th>Description</th></tr></thead><tbody>"
+    )?;
+    for issue in &graph.issues {
+        writeln!(
+            writer,
+            "<tr><td class=\"severity-{}\">{}</td><td>{}</td><td>{} → {}</td><td>{}</td></tr>",
+            escape_html(&issue.severity.to_lowercase()),
+            escape_html(&issue.severity),
+            escape_html(&issue.issue_type),
+            escape_html(&issue.source),
+            escape_html(&issue.target),
+            escape_html(&issue.description)
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")
+}
+
+/// Inline the graph as JSON; `<` is escaped so no string can close the script element.
+fn write_inline_data<W: Write>(graph: &GraphData, writer: &mut W) -> io::Result<()> {
+    let json = serde_json::to_string(graph)
+        .map_err(io::Error::other)?
+        .replace('<', "\\u003c");
+    writeln!(
+        writer,
+        "<script id=\"coupling-data\" type=\"application/json\">{}</script>",
+        json
+    )?;
+    writeln!(
+        writer,
+        "<script>window.couplingData = JSON.parse(document.getElementById('coupling-data').textContent);</script>"
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::analyze_files;
+
+    #[test]
+    fn test_html_report_is_standalone_and_inlines_graph_data() {
+        let metrics = analyze_files(&[
+            ("lib.rs", "pub mod handlers;\npub mod users;\n"),
+            ("users.rs", "pub struct User { pub name: String }\n"),
+            (
+                "handlers.rs",
+                "use crate::users::User;\n\npub fn greet(user: &User) -> String { user.name.clone() }\n",
+            ),
+        ]);
+        let mut output = Vec::new();
+        generate_html_report(&metrics, &IssueThresholds::default(), &mut output).unwrap();
+        let html = String::from_utf8(output).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link") && !html.contains("src=\"http"));
+        assert!(html.contains("<h3>Integration Strength</h3>"));
+        assert!(html.contains("<h2>Hotspots</h2>"));
+        assert_eq!(html.matches("</script>").count(), 2);
+
+        let start = html.find("type=\"application/json\">").unwrap() + 24;
+        let end = start + html[start..].find("</script>").unwrap();
+        let data: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert!(data["nodes"].as_array().unwrap().len() >= 2);
+        assert!(data["summary"]["health_grade"].is_string());
+
+        assert_eq!(
+            escape_html("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 //! suitable for Cytoscape.js visualization.
 
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::analyzer::ItemDepType;
 use crate::balance::issue::CouplingIssue;
@@ -93,6 +93,24 @@ pub struct GraphData {
     pub hidden_couplings: Vec<HiddenCouplingEdge>,
     pub issues: Vec<GraphIssue>,
     pub not_analyzed: AnalysisManifestData,
+    /// Set when the graph is a neighborhood of one module (`/api/graph?module=`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subgraph: Option<SubgraphInfo>,
+}
+
+/// How a focused subgraph was cut from the full graph
+#[derive(Debug, Clone, Serialize)]
+pub struct SubgraphInfo {
+    /// Node ID of the focus module
+    pub focus: String,
+    /// Maximum hops from the focus module, in either edge direction
+    pub radius: usize,
+    /// Node count of the full graph
+    pub total_nodes: usize,
+    /// Edge count of the full graph
+    pub total_edges: usize,
+    /// True when `limit` dropped nodes within the radius
+    pub truncated: bool,
 }
 
 /// A node in the coupling graph (represents a module)
@@ -392,7 +410,7 @@ pub fn project_to_graph(metrics: &ProjectMetrics, thresholds: &IssueThresholds)
         *node_couplings_out.entry(source_id.clone()).or_insert(0) += 1;
         *node_couplings_in.entry(target_id.clone()).or_insert(0) += 1;
 
-        let score = BalanceScore::calculate(coupling);
+        let score = BalanceScore::calculate_for(coupling, thresholds);
         node_balance_scores
             .entry(source_id)
             .or_default()
@@ -448,7 +466,7 @@ pub fn project_to_graph(metrics: &ProjectMetrics, thresholds: &IssueThresholds)
             let strength = match dep.dep_type {
                 ItemDepType::FieldAccess | ItemDepType::StructConstruction => "Intrusive",
                 ItemDepType::FunctionCall | ItemDepType::MethodCall => "Functional",
-                ItemDepType::TypeUsage | ItemDepType::Import => "Model",
+                ItemDepType::TypeUsage | ItemDepType::Import | ItemDepType::ConstUsage => "Model",
                 ItemDepType::TraitImpl | ItemDepType::TraitBound => "Contract",
             };
 
@@ -604,7 +622,7 @@ pub fn project_to_graph(metrics: &ProjectMetrics, thresholds: &IssueThresholds)
             continue;
         }
 
-        let score = BalanceScore::calculate(coupling);
+        let score = BalanceScore::calculate_for(coupling, thresholds);
         let in_cycle = cycle_edges.contains(&(coupling.source.clone(), coupling.target.clone()));
 
         let issue = find_issue_for_coupling(coupling, &score, thresholds);
@@ -627,7 +645,11 @@ pub fn project_to_graph(metrics: &ProjectMetrics, thresholds: &IssueThresholds)
             id: format!("e{}", edge_id),
             source: source_id,
             target: target_id,
-            dimensions: coupling_to_dimensions(coupling, &score),
+            dimensions: coupling_to_dimensions(
+                coupling,
+                &score,
+                thresholds.balance_formula.visibility_penalty,
+            ),
             issue,
             in_cycle,
             location,
@@ -717,17 +739,117 @@ pub fn project_to_graph(metrics: &ProjectMetrics, thresholds: &IssueThresholds)
         hidden_couplings,
         issues: graph_issues,
         not_analyzed: build_not_analyzed_manifest(metrics),
+        subgraph: None,
     }
 }
 
+/// Cut the neighborhood of `focus` out of `graph`.
+///
+/// Walks edges in both directions (dependencies and dependents) up to `radius`
+/// hops and keeps at most `limit` nodes, nearest first. `focus` matches a node
+/// ID or label, or a `::`-suffix of one. Edges, hidden couplings, cycles, and
+/// issues are kept only when all their nodes survive; the summary still
+/// describes the whole project. Returns `None` when no node matches `focus`.
+pub fn subgraph(graph: GraphData, focus: &str, radius: usize, limit: usize) -> Option<GraphData> {
+    let suffix = format!("::{}", focus);
+    let focus_id = graph
+        .nodes
+        .iter()
+        .find(|node| node.id == focus || node.label == focus)
+        .or_else(|| graph.nodes.iter().find(|node| node.id.ends_with(&suffix)))?
+        .id
+        .clone();
+
+    let mut neighbors: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for edge in &graph.edges {
+        neighbors
+            .entry(edge.source.as_str())
+            .or_default()
+            .insert(edge.target.as_str());
+        neighbors
+            .entry(edge.target.as_str())
+            .or_default()
+            .insert(edge.source.as_str());
+    }
+
+    // Breadth-first, so truncation drops the farthest nodes
+    let limit = limit.max(1);
+    let mut kept: HashSet<String> = HashSet::from([focus_id.clone()]);
+    let mut queue = VecDeque::from([(focus_id.as_str(), 0)]);
+    let mut truncated = false;
+    while let Some((id, depth)) = queue.pop_front() {
+        if depth == radius {
+            continue;
+        }
+        for &next in neighbors.get(id).into_iter().flatten() {
+            if kept.contains(next) {
+                continue;
+            }
+            if kept.len() == limit {
+                truncated = true;
+                break;
+            }
+            kept.insert(next.to_string());
+            queue.push_back((next, depth + 1));
+        }
+    }
+
+    let info = SubgraphInfo {
+        focus: focus_id,
+        radius,
+        total_nodes: graph.nodes.len(),
+        total_edges: graph.edges.len(),
+        truncated,
+    };
+    let has = |id: &str| kept.contains(id);
+    Some(GraphData {
+        nodes: graph.nodes.into_iter().filter(|n| has(&n.id)).collect(),
+        edges: graph
+            .edges
+            .into_iter()
+            .filter(|e| has(&e.source) && has(&e.target))
+            .collect(),
+        summary: graph.summary,
+        circular_dependencies: graph
+            .circular_dependencies
+            .into_iter()
+            .filter(|cycle| cycle.iter().all(|id| has(id)))
+            .collect(),
+        temporal_couplings: graph
+            .temporal_couplings
+            .into_iter()
+            .filter(|tc| {
+                [&tc.source_module, &tc.target_module]
+                    .into_iter()
+                    .all(|module| module.as_deref().is_some_and(has))
+            })
+            .collect(),
+        hidden_couplings: graph
+            .hidden_couplings
+            .into_iter()
+            .filter(|e| has(&e.source) && has(&e.target))
+            .collect(),
+        issues: graph
+            .issues
+            .into_iter()
+            .filter(|issue| issue.focus.node_ids.iter().all(|id| has(id)))
+            .collect(),
+        not_analyzed: graph.not_analyzed,
+        subgraph: Some(info),
+    })
+}
+
 fn build_not_analyzed_manifest(metrics: &ProjectMetrics) -> AnalysisManifestData {
     let manifest = build_manifest(&ManifestContext {
         git_used: !metrics.file_changes.is_empty() || !metrics.temporal_couplings.is_empty(),
         tests_excluded: false,
         parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
         skipped_crates: metrics.skipped_crates.clone(),
         boundary_skipped_files: metrics.boundary_skipped_files,
         dead_config_patterns: metrics.dead_config_patterns.clone(),
+        edition_parse_failures: metrics.edition_parse_failures.clone(),
+        orphaned_modules: metrics.orphaned_modules.clone(),
     });
 
     AnalysisManifestData {
@@ -893,7 +1015,15 @@ fn issue_focus(
         | IssueType::GodModule
         | IssueType::AccidentalVolatility
         | IssueType::PublicFieldExposure
-        | IssueType::PrimitiveObsession => vec![source.to_string()],
+        | IssueType::PrimitiveObsession
+        | IssueType::GlobImport
+        | IssueType::OverParameterizedType
+        | IssueType::SpreadCoupling
+        | IssueType::MixedAsyncBoundary
+        | IssueType::UnsafeSurface
+        | IssueType::DuplicatedStructure
+        | IssueType::DerefAbuse
+        | IssueType::LowCohesion => vec![source.to_string()],
         _ if source != target => vec![source.to_string(), target.to_string()],
         _ => vec![source.to_string()],
     };
@@ -918,8 +1048,13 @@ where
     normalize_to_node_id(endpoint)
 }
 
-fn coupling_to_dimensions(coupling: &CouplingMetrics, score: &BalanceScore) -> Dimensions {
-    let strength_label = match coupling.strength {
+fn coupling_to_dimensions(
+    coupling: &CouplingMetrics,
+    score: &BalanceScore,
+    visibility_penalty: bool,
+) -> Dimensions {
+    let strength = coupling.scoring_strength(visibility_penalty);
+    let strength_label = match strength {
         IntegrationStrength::Intrusive => "Intrusive",
         IntegrationStrength::Functional => "Functional",
         IntegrationStrength::Model => "Model",
@@ -947,9 +1082,9 @@ fn coupling_to_dimensions(coupling: &CouplingMetrics, score: &BalanceScore) -> D
         BalanceInterpretation::Critical => "Critical",
     };
 
-    // Calculate Khononov's BalanceClassification
+    // Calculate Khononov's BalanceClassification at the strength that was scored
     let classification =
-        BalanceClassification::classify(coupling.strength, coupling.distance, coupling.volatility);
+        BalanceClassification::classify(strength, coupling.distance, coupling.volatility);
     let classification_en = match classification {
         BalanceClassification::Pain => "Global Complexity",
         _ => classification.description_en(),
@@ -961,11 +1096,11 @@ fn coupling_to_dimensions(coupling: &CouplingMetrics, score: &BalanceScore) -> D
 
     Dimensions {
         strength: DimensionValue {
-            value: coupling.strength.value(),
+            value: strength.value(),
             label: strength_label.to_string(),
         },
         distance: DimensionValue {
-            value: coupling.distance.value(),
+            value: coupling.distance_value(),
             label: distance_label.to_string(),
         },
         volatility: DimensionValue {
@@ -979,7 +1114,10 @@ fn coupling_to_dimensions(coupling: &CouplingMetrics, score: &BalanceScore) -> D
             classification: classification_en.to_string(),
             classification_ja: classification_ja.to_string(),
         },
-        connascence: None, // TODO: Add connascence tracking per coupling
+        connascence: Some(ConnascenceValue {
+            connascence_type: coupling.connascence.to_string(),
+            strength: coupling.connascence.value(),
+        }),
     }
 }
 
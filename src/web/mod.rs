@@ -8,9 +8,13 @@
 //! - Balance Score (0.0-1.0)
 //! - Connascence (Name, Type, Meaning, Position, Algorithm)
 
+pub mod export;
 pub mod graph;
 pub mod routes;
 pub mod server;
+pub mod snapshots;
 
+pub use export::generate_html_report;
 pub use graph::GraphData;
 pub use server::{DEFAULT_HISTORY_MAX_POINTS, ServerConfig, start_server};
+pub use snapshots::{SnapshotPoint, SnapshotTimeline, load_snapshots};
@@ -9,10 +9,11 @@ use std::sync::Arc;
 
 use axum::{
     Json, Router,
-    extract::{Query, State},
-    http::{StatusCode, header},
+    extract::{Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
     response::{Html, IntoResponse, Response},
-    routing::get,
+    routing::{get, post},
 };
 use rust_embed::RustEmbed;
 
@@ -25,6 +26,7 @@ use crate::report::{TextReportOptions, generate_report_with_options};
 
 use super::graph;
 use super::server::AppState;
+use super::snapshots::SnapshotTimeline;
 
 /// Embedded static assets
 #[derive(RustEmbed)]
@@ -42,8 +44,20 @@ struct FrontendConfig {
 struct GraphQuery {
     #[serde(rename = "ref")]
     git_ref: Option<String>,
+    /// Focus module: return only its neighborhood
+    module: Option<String>,
+    /// Hops around `module` [default: 2]
+    radius: Option<usize>,
+    /// Maximum nodes around `module` [default: 500]
+    limit: Option<usize>,
 }
 
+/// Default hop radius of `/api/graph?module=`
+const DEFAULT_SUBGRAPH_RADIUS: usize = 2;
+
+/// Default node limit of `/api/graph?module=`
+const DEFAULT_SUBGRAPH_LIMIT: usize = 500;
+
 /// Query parameters for source code request
 #[derive(Deserialize)]
 struct SourceQuery {
@@ -80,16 +94,84 @@ struct ModuleQuery {
     name: String,
 }
 
-/// Create API routes
-pub fn api_routes() -> Router<Arc<AppState>> {
+/// Token passed as `?token=` (lets the browser UI authenticate via its URL)
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// Cookie set by `GET /?token=` so the UI's API requests carry the token
+const TOKEN_COOKIE: &str = "coupling_token";
+
+/// Create API routes, guarded by the configured auth token
+pub fn api_routes(state: &Arc<AppState>) -> Router<Arc<AppState>> {
     Router::new()
         .route("/api/graph", get(get_graph))
         .route("/api/report", get(get_report))
         .route("/api/history", get(get_history))
+        .route("/api/snapshots", get(get_snapshots))
         .route("/api/config", get(get_config))
         .route("/api/health", get(health_check))
         .route("/api/source", get(get_source))
         .route("/api/module", get(get_module))
+        .route("/api/session", get(get_session).put(save_session))
+        .route("/api/reanalyze", post(reanalyze))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(state),
+            require_token,
+        ))
+}
+
+/// Reject API requests without the configured token (401); no-op when auth is off.
+async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = state.auth_token.as_deref() else {
+        return next.run(request).await;
+    };
+
+    let query_token = Query::<TokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.token);
+    let authorized = request_token(request.headers())
+        .into_iter()
+        .chain(query_token)
+        .any(|token| tokens_match(&token, expected));
+
+    if authorized {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, "Missing or invalid token").into_response()
+    }
+}
+
+/// Tokens from the `Authorization: Bearer` header and the session cookie.
+fn request_token(headers: &HeaderMap) -> Vec<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let cookie = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().strip_prefix(TOKEN_COOKIE)?.strip_prefix('='))
+        .map(str::to_string);
+    bearer.into_iter().chain(cookie).collect()
+}
+
+/// Compare without short-circuiting on the first differing byte.
+fn tokens_match(candidate: &str, expected: &str) -> bool {
+    candidate.len() == expected.len()
+        && candidate
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
 }
 
 /// Create static file routes
@@ -104,51 +186,142 @@ pub fn static_routes() -> Router<Arc<AppState>> {
 ///
 /// With `?ref=<git-ref>`, analyzes that revision in a disposable worktree so
 /// the timeline can lazy-load graph snapshots without bloating `/api/history`.
+/// With `?module=<name>&radius=<hops>&limit=<nodes>`, returns only the
+/// neighborhood of that module so large graphs can be expanded lazily.
 async fn get_graph(
     State(state): State<Arc<AppState>>,
     Query(query): Query<GraphQuery>,
 ) -> impl IntoResponse {
-    if let Some(git_ref) = query.git_ref.filter(|value| !value.trim().is_empty()) {
+    let graph = if let Some(git_ref) = query.git_ref.as_deref().map(str::trim)
+        && !git_ref.is_empty()
+    {
         match analyze_ref(
             &state.analysis_path,
             &state.analysis_config,
             &state.thresholds,
-            git_ref.trim(),
+            git_ref,
             state.git_months,
             !state.no_git,
         ) {
-            Ok(analysis) => {
-                let graph = graph::project_to_graph(&analysis.metrics, &state.thresholds);
-                Json(graph).into_response()
+            Ok(analysis) => graph::project_to_graph(&analysis.metrics, &state.thresholds),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": e.to_string()})),
+                )
+                    .into_response();
             }
-            Err(e) => (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": e.to_string()})),
-            )
-                .into_response(),
         }
     } else {
-        let graph = graph::project_to_graph(&state.metrics, &state.thresholds);
-        Json(graph).into_response()
+        graph::project_to_graph(&state.metrics(), &state.thresholds)
+    };
+
+    let Some(module) = query.module.filter(|value| !value.trim().is_empty()) else {
+        return Json(graph).into_response();
+    };
+    match graph::subgraph(
+        graph,
+        module.trim(),
+        query.radius.unwrap_or(DEFAULT_SUBGRAPH_RADIUS),
+        query.limit.unwrap_or(DEFAULT_SUBGRAPH_LIMIT),
+    ) {
+        Some(subgraph) => Json(subgraph).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Module '{}' not found", module)})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/reanalyze - Re-runs the analysis and returns the refreshed graph.
+///
+/// Lets the UI pick up source edits without restarting the server.
+async fn reanalyze(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let task_state = Arc::clone(&state);
+    let result = tokio::task::spawn_blocking(move || task_state.reanalyze()).await;
+
+    match result {
+        Ok(Ok(())) => {
+            let graph = graph::project_to_graph(&state.metrics(), &state.thresholds);
+            Json(graph).into_response()
+        }
+        Ok(Err(e)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Re-analysis task failed: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/session - Returns the saved filter/selection state, if any.
+async fn get_session(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let content = match fs::read_to_string(&state.session_path) {
+        Ok(content) => content,
+        Err(_) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "No saved session"})),
+            )
+                .into_response();
+        }
+    };
+
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(session) => Json(session).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Saved session is corrupt: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+/// PUT /api/session - Persists the UI filter/selection state to a local file.
+///
+/// The payload is opaque to the server; the frontend owns its shape.
+async fn save_session(
+    State(state): State<Arc<AppState>>,
+    Json(session): Json<serde_json::Value>,
+) -> impl IntoResponse {
+    let write_result = serde_json::to_string_pretty(&session)
+        .map_err(|e| e.to_string())
+        .and_then(|content| fs::write(&state.session_path, content).map_err(|e| e.to_string()));
+
+    match write_result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Could not save session: {}", e)})),
+        )
+            .into_response(),
     }
 }
 
 /// GET /api/report - Returns the current Markdown analysis report.
 async fn get_report(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let metrics = state.metrics();
     let manifest = build_manifest(&ManifestContext {
         git_used: !state.no_git
-            && (!state.metrics.file_changes.is_empty()
-                || !state.metrics.temporal_couplings.is_empty()),
+            && (!metrics.file_changes.is_empty() || !metrics.temporal_couplings.is_empty()),
         tests_excluded: state.analysis_config.exclude_tests,
-        parse_failures: state.metrics.parse_failures,
-        skipped_crates: state.metrics.skipped_crates.clone(),
-        boundary_skipped_files: state.metrics.boundary_skipped_files,
-        dead_config_patterns: state.metrics.dead_config_patterns.clone(),
+        parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
+        skipped_crates: metrics.skipped_crates.clone(),
+        boundary_skipped_files: metrics.boundary_skipped_files,
+        dead_config_patterns: metrics.dead_config_patterns.clone(),
+        edition_parse_failures: metrics.edition_parse_failures.clone(),
+        orphaned_modules: metrics.orphaned_modules.clone(),
     });
     let mut output = Vec::new();
 
     match generate_report_with_options(
-        &state.metrics,
+        &metrics,
         &state.thresholds,
         &manifest,
         TextReportOptions::default(),
@@ -172,6 +345,11 @@ async fn get_history(State(state): State<Arc<AppState>>) -> Json<JsonHistory> {
     Json(state.history.clone())
 }
 
+/// GET /api/snapshots - Returns `--snapshots` exports as a time series, oldest first
+async fn get_snapshots(State(state): State<Arc<AppState>>) -> Json<SnapshotTimeline> {
+    Json(state.snapshots.clone())
+}
+
 /// GET /api/config - Returns frontend configuration
 async fn get_config(State(state): State<Arc<AppState>>) -> Json<FrontendConfig> {
     Json(FrontendConfig {
@@ -329,7 +507,7 @@ async fn get_module(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ModuleQuery>,
 ) -> impl IntoResponse {
-    let graph = graph::project_to_graph(&state.metrics, &state.thresholds);
+    let graph = graph::project_to_graph(&state.metrics(), &state.thresholds);
 
     // Find the module by name
     if let Some(node) = graph
@@ -356,10 +534,26 @@ async fn get_module(
 }
 
 /// GET / - Serve index.html
-async fn index_html() -> impl IntoResponse {
-    match Assets::get("index.html") {
-        Some(content) => Html(content.data.into_owned()).into_response(),
-        None => (StatusCode::NOT_FOUND, "index.html not found").into_response(),
+async fn index_html(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TokenQuery>,
+) -> impl IntoResponse {
+    let Some(content) = Assets::get("index.html") else {
+        return (StatusCode::NOT_FOUND, "index.html not found").into_response();
+    };
+    let html = Html(content.data.into_owned());
+
+    // Opening the printed `/?token=` URL stores the token for the UI's API calls.
+    match (state.auth_token.as_deref(), query.token) {
+        (Some(expected), Some(token)) if tokens_match(&token, expected) => (
+            [(
+                header::SET_COOKIE,
+                format!("{TOKEN_COOKIE}={token}; Path=/; HttpOnly; SameSite=Strict"),
+            )],
+            html,
+        )
+            .into_response(),
+        _ => html.into_response(),
     }
 }
 
@@ -384,3 +578,273 @@ async fn static_handler(
             .unwrap(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::analyze_workspace_with_config;
+    use crate::balance::score::IssueThresholds;
+    use crate::config::CompiledConfig;
+    use crate::web::server::SESSION_FILE_NAME;
+    use std::sync::RwLock;
+
+    fn test_state(src: &Path) -> Arc<AppState> {
+        let config = CompiledConfig::empty();
+        let metrics = analyze_workspace_with_config(src, &config).unwrap();
+        Arc::new(AppState {
+            metrics: RwLock::new(metrics),
+            thresholds: IssueThresholds::default(),
+            api_endpoint: None,
+            history: JsonHistory {
+                months: 6,
+                points: Vec::new(),
+                skipped: Vec::new(),
+            },
+            snapshots: SnapshotTimeline::default(),
+            analysis_path: src.to_path_buf(),
+            source_root: src.to_path_buf(),
+            analysis_config: config,
+            git_months: 6,
+            no_git: true,
+            session_path: src.join(SESSION_FILE_NAME),
+            auth_token: None,
+        })
+    }
+
+    async fn response_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reanalyze_returns_graph_for_updated_sources() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "pub mod a;\n").unwrap();
+        fs::write(src.join("a.rs"), "pub struct A;\n").unwrap();
+
+        let state = test_state(&src);
+        let before = graph::project_to_graph(&state.metrics(), &state.thresholds);
+        assert!(!before.nodes.iter().any(|node| node.label == "b"));
+
+        fs::write(src.join("lib.rs"), "pub mod a;\npub mod b;\n").unwrap();
+        fs::write(
+            src.join("b.rs"),
+            "use crate::a::A;\npub fn take(_a: A) {}\n",
+        )
+        .unwrap();
+
+        let response = reanalyze(State(Arc::clone(&state))).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let graph = response_json(response).await;
+        let nodes = graph["nodes"].as_array().unwrap();
+        assert!(nodes.iter().any(|node| node["label"] == "b"));
+        assert!(
+            state
+                .metrics()
+                .modules
+                .keys()
+                .any(|name| name.ends_with('b'))
+        );
+    }
+
+    /// Send a raw HTTP/1.1 GET and return the status code.
+    fn http_get_status(addr: std::net::SocketAddr, path: &str, headers: &str) -> u16 {
+        use std::io::{Read, Write};
+
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\n{headers}Connection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response.split_whitespace().nth(1).unwrap().parse().unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn api_rejects_requests_without_configured_token() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "pub struct A;\n").unwrap();
+        let mut state = Arc::into_inner(test_state(&src)).unwrap();
+        state.auth_token = Some("s3cret".to_string());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = crate::web::server::router(Arc::new(state));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let statuses = tokio::task::spawn_blocking(move || {
+            [
+                http_get_status(addr, "/api/health", ""),
+                http_get_status(addr, "/api/health?token=wrong", ""),
+                http_get_status(addr, "/api/health", "Authorization: Bearer s3cret\r\n"),
+                http_get_status(addr, "/api/health?token=s3cret", ""),
+                http_get_status(addr, "/api/health", "Cookie: coupling_token=s3cret\r\n"),
+            ]
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(statuses, [401, 401, 200, 200, 200]);
+    }
+
+    #[tokio::test]
+    async fn test_session_round_trips_through_local_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), "pub struct A;\n").unwrap();
+        let state = test_state(&src);
+
+        let missing = get_session(State(Arc::clone(&state))).await.into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let session = serde_json::json!({"selectedNode": "a", "showItems": true});
+        let saved = save_session(State(Arc::clone(&state)), Json(session.clone()))
+            .await
+            .into_response();
+        assert_eq!(saved.status(), StatusCode::NO_CONTENT);
+
+        let loaded = get_session(State(state)).await.into_response();
+        assert_eq!(loaded.status(), StatusCode::OK);
+        assert_eq!(response_json(loaded).await, session);
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_endpoint_returns_points_sorted_by_embedded_timestamp() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let snapshots = tmp.path().join("snapshots");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&snapshots).unwrap();
+        fs::write(src.join("lib.rs"), "pub struct A;\n").unwrap();
+
+        // File names sort opposite to the embedded timestamps.
+        fs::write(
+            snapshots.join("a-latest.json"),
+            r#"{"generated_at": 1700000300, "config_hash": "aaaa", "summary": {"health_grade": "A", "health_score": 0.9, "critical_issues": 0}}"#,
+        )
+        .unwrap();
+        fs::write(
+            snapshots.join("b-oldest.json"),
+            r#"{"generated_at": 1700000100, "config_hash": "bbbb", "summary": {"health_grade": "C", "health_score": 0.6, "critical_issues": 2}}"#,
+        )
+        .unwrap();
+        // Older schema: `timestamp` instead of `generated_at`, no issue counts.
+        fs::write(
+            snapshots.join("c-middle.json"),
+            r#"{"timestamp": 1700000200, "summary": {"health_grade": "B"}, "extra": true}"#,
+        )
+        .unwrap();
+        fs::write(snapshots.join("broken.json"), "not json").unwrap();
+        fs::write(snapshots.join("notes.txt"), "ignored").unwrap();
+
+        let mut state = Arc::into_inner(test_state(&src)).unwrap();
+        state.snapshots = crate::web::snapshots::load_snapshots(&snapshots).unwrap();
+
+        let response = get_snapshots(State(Arc::new(state))).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let timeline = response_json(response).await;
+
+        let points = timeline["points"].as_array().unwrap();
+        let order: Vec<(&str, u64)> = points
+            .iter()
+            .map(|point| {
+                (
+                    point["file"].as_str().unwrap(),
+                    point["timestamp"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("b-oldest.json", 1700000100),
+                ("c-middle.json", 1700000200),
+                ("a-latest.json", 1700000300),
+            ]
+        );
+        assert_eq!(points[0]["critical_issues"], 2);
+        assert!(points[1]["health_score"].is_null());
+        assert_eq!(points[2]["health_grade"], "A");
+
+        let skipped = timeline["skipped"].as_array().unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].as_str().unwrap().starts_with("broken.json"));
+
+        // Only the snapshot with a known, different config hash is flagged.
+        let warnings = timeline["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].as_str().unwrap().starts_with("b-oldest.json"));
+    }
+
+    #[tokio::test]
+    async fn test_graph_radius_one_around_module_returns_only_its_neighbors() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "pub mod a;\npub mod b;\npub mod c;\npub mod d;\n",
+        )
+        .unwrap();
+        // Chain d -> c -> b -> a
+        fs::write(src.join("a.rs"), "pub struct A;\n").unwrap();
+        fs::write(
+            src.join("b.rs"),
+            "use crate::a::A;\npub struct B;\npub fn take(_a: A) {}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("c.rs"),
+            "use crate::b::B;\npub struct C;\npub fn take(_b: B) {}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("d.rs"),
+            "use crate::c::C;\npub fn take(_c: C) {}\n",
+        )
+        .unwrap();
+
+        let query = |module: &str, radius: usize| GraphQuery {
+            git_ref: None,
+            module: Some(module.to_string()),
+            radius: Some(radius),
+            limit: None,
+        };
+        let state = test_state(&src);
+        let response = get_graph(State(Arc::clone(&state)), Query(query("b", 1)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let graph = response_json(response).await;
+
+        let mut ids: Vec<&str> = graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["id"].as_str().unwrap())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        for edge in graph["edges"].as_array().unwrap() {
+            assert!(ids.contains(&edge["source"].as_str().unwrap()));
+            assert!(ids.contains(&edge["target"].as_str().unwrap()));
+        }
+        assert_eq!(graph["subgraph"]["focus"], "b");
+        assert_eq!(graph["subgraph"]["truncated"], false);
+
+        let response = get_graph(State(state), Query(query("missing", 1)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
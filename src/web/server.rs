@@ -3,39 +3,75 @@
 //! Provides an HTTP server using Axum to serve the visualization UI
 //! and JSON API endpoints.
 
-use std::net::SocketAddr;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 use axum::Router;
 use tokio::net::TcpListener;
 
 use crate::analyze_history;
+use crate::analyzer::{AnalyzerError, analyze_workspace_with_config};
 use crate::balance::score::IssueThresholds;
 use crate::cli_output::{JsonHistory, history_report_to_json};
 use crate::config::CompiledConfig;
 use crate::metrics::project::ProjectMetrics;
+use crate::volatility::VolatilityAnalyzer;
 use crate::workspace::WorkspaceInfo;
+use crate::{log_info, log_warn};
 
 use super::routes;
+use super::snapshots::{SnapshotTimeline, load_snapshots};
 
 pub const DEFAULT_HISTORY_MAX_POINTS: usize = 30;
 
+/// File (under the source root) where the web UI persists its filter/selection state.
+pub const SESSION_FILE_NAME: &str = ".coupling-web-session.json";
+
 /// Shared application state
 pub struct AppState {
-    pub metrics: ProjectMetrics,
+    /// Current analysis; replaced in place by `POST /api/reanalyze`.
+    pub metrics: RwLock<ProjectMetrics>,
     pub thresholds: IssueThresholds,
     pub api_endpoint: Option<String>,
     pub history: JsonHistory,
+    /// Exported `--json` snapshots from `--snapshots <dir>` (empty when unset)
+    pub snapshots: SnapshotTimeline,
     pub analysis_path: PathBuf,
     pub source_root: PathBuf,
     pub analysis_config: CompiledConfig,
     pub git_months: usize,
     pub no_git: bool,
+    pub session_path: PathBuf,
+    /// Token required on API routes; `None` disables auth.
+    pub auth_token: Option<String>,
+}
+
+impl AppState {
+    /// Read access to the current analysis. A poisoned lock only means a
+    /// re-analysis panicked mid-swap; the previous metrics are still valid.
+    pub fn metrics(&self) -> RwLockReadGuard<'_, ProjectMetrics> {
+        self.metrics.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Re-run the snapshot analysis with the same methodology as the CLI
+    /// (AST + git volatility + config overrides) and swap it in.
+    pub fn reanalyze(&self) -> Result<(), AnalyzerError> {
+        let metrics = analyze_for_web(
+            &self.analysis_path,
+            &self.analysis_config,
+            self.git_months,
+            self.no_git,
+        )?;
+        *self.metrics.write().unwrap_or_else(|e| e.into_inner()) = metrics;
+        Ok(())
+    }
 }
 
 /// Configuration for the web server
 pub struct ServerConfig {
+    /// Address to bind; loopback by default so the UI is not exposed on the network.
+    pub bind_addr: IpAddr,
     pub port: u16,
     pub open_browser: bool,
     pub api_endpoint: Option<String>,
@@ -44,11 +80,16 @@ pub struct ServerConfig {
     pub git_months: usize,
     pub history_max_points: usize,
     pub no_git: bool,
+    /// Token required on API routes (Bearer header, `?token=`, or cookie).
+    pub auth_token: Option<String>,
+    /// Directory of `--json` exports to serve as a trend timeline.
+    pub snapshots_dir: Option<PathBuf>,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
+            bind_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
             port: 3000,
             open_browser: true,
             api_endpoint: None,
@@ -57,6 +98,8 @@ impl Default for ServerConfig {
             git_months: 6,
             history_max_points: DEFAULT_HISTORY_MAX_POINTS,
             no_git: false,
+            auth_token: None,
+            snapshots_dir: None,
         }
     }
 }
@@ -68,47 +111,104 @@ pub async fn start_server(
     config: ServerConfig,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let history = load_history(&config, &thresholds);
+    let snapshots = match &config.snapshots_dir {
+        Some(dir) => load_snapshots(dir).map_err(|e| {
+            format!(
+                "Could not read snapshots directory {}: {}",
+                dir.display(),
+                e
+            )
+        })?,
+        None => SnapshotTimeline::default(),
+    };
+    for skipped in &snapshots.skipped {
+        log_warn!("Warning: Skipping snapshot {}", skipped);
+    }
     let source_root = analysis_source_root(&config.analysis_path);
+    let session_path = source_root.join(SESSION_FILE_NAME);
 
     let state = Arc::new(AppState {
-        metrics,
+        metrics: RwLock::new(metrics),
         thresholds,
         api_endpoint: config.api_endpoint.clone(),
         history,
+        snapshots,
         analysis_path: config.analysis_path.clone(),
         source_root,
         analysis_config: config.analysis_config.clone(),
         git_months: config.git_months,
         no_git: config.no_git,
+        session_path,
+        auth_token: config.auth_token.clone(),
     });
 
-    let app = Router::new()
-        .merge(routes::api_routes())
-        .merge(routes::static_routes())
-        .with_state(state);
+    let app = router(state);
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], config.port));
+    let addr = SocketAddr::new(config.bind_addr, config.port);
     let listener = TcpListener::bind(addr).await?;
 
-    let url = format!("http://localhost:{}", config.port);
-    eprintln!("Starting web server at {}", url);
+    let host = if config.bind_addr.is_unspecified() || config.bind_addr.is_loopback() {
+        "localhost".to_string()
+    } else {
+        config.bind_addr.to_string()
+    };
+    let url = match &config.auth_token {
+        Some(token) => format!("http://{}:{}/?token={}", host, config.port, token),
+        None => format!("http://{}:{}", host, config.port),
+    };
+    log_info!("Starting web server at {} (bound to {})", url, addr);
 
     if config.open_browser {
-        eprintln!("Opening browser...");
+        log_info!("Opening browser...");
         if let Err(e) = open::that(&url) {
-            eprintln!("Warning: Could not open browser: {}", e);
-            eprintln!("Please open {} manually", url);
+            log_warn!("Warning: Could not open browser: {}", e);
+            log_warn!("Please open {} manually", url);
         }
     }
 
-    eprintln!("Press Ctrl+C to stop the server");
+    log_info!("Press Ctrl+C to stop the server");
 
     axum::serve(listener, app).await?;
 
     Ok(())
 }
 
-fn analysis_source_root(analysis_path: &std::path::Path) -> PathBuf {
+/// Build the application router (API and static routes) for `state`.
+pub(crate) fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .merge(routes::api_routes(&state))
+        .merge(routes::static_routes())
+        .with_state(state)
+}
+
+fn analyze_for_web(
+    analysis_path: &Path,
+    config: &CompiledConfig,
+    git_months: usize,
+    no_git: bool,
+) -> Result<ProjectMetrics, AnalyzerError> {
+    let mut config = config.clone();
+    let mut metrics = analyze_workspace_with_config(analysis_path, &config)?;
+
+    if !no_git {
+        let mut volatility = VolatilityAnalyzer::from_config(git_months, &config);
+        if volatility.analyze(analysis_path).is_ok() {
+            if let Ok(temporal) = volatility.analyze_temporal_coupling(analysis_path) {
+                metrics.temporal_couplings = temporal;
+            }
+            metrics.file_changes = volatility.file_changes;
+            metrics.update_volatility_from_git();
+        }
+    }
+
+    if config.has_volatility_overrides() || config.has_subdomain_config() {
+        metrics.apply_config_volatility_overrides(&mut config);
+    }
+
+    Ok(metrics)
+}
+
+pub(crate) fn analysis_source_root(analysis_path: &Path) -> PathBuf {
     let root = WorkspaceInfo::from_path(analysis_path)
         .map(|workspace| workspace.root)
         .unwrap_or_else(|_| analysis_path.to_path_buf());
@@ -133,7 +233,7 @@ fn load_history(config: &ServerConfig, thresholds: &IssueThresholds) -> JsonHist
     ) {
         Ok(report) => history_report_to_json(&report),
         Err(e) => {
-            eprintln!("Warning: History analysis failed: {}", e);
+            log_warn!("Warning: History analysis failed: {}", e);
             JsonHistory {
                 months: config.git_months,
                 points: Vec::new(),
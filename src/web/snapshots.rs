@@ -0,0 +1,151 @@
+//! Snapshot timeline for the web UI (`--web --snapshots <dir>`)
+//!
+//! Loads `--json` exports saved over time and turns them into a health-score
+//! and issue-count time series. Parsing is best-effort: snapshots written by
+//! older or newer versions are accepted as long as they are JSON objects, and
+//! fields they lack are reported as `null`. Snapshots from a different
+//! analyzer version or config than the newest one are flagged in `warnings`,
+//! since their scores are not directly comparable.
+
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::header::comparability_warnings;
+
+/// One snapshot on the timeline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnapshotPoint {
+    /// Snapshot file name
+    pub file: String,
+    /// Unix timestamp (seconds): `generated_at`, else `timestamp`, else file mtime
+    pub timestamp: u64,
+    pub analyzer_version: Option<String>,
+    pub config_hash: Option<String>,
+    pub health_grade: Option<String>,
+    pub health_score: Option<f64>,
+    pub total_couplings: Option<usize>,
+    pub critical_issues: Option<usize>,
+    pub high_issues: Option<usize>,
+    pub medium_issues: Option<usize>,
+}
+
+/// Snapshots sorted by timestamp, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SnapshotTimeline {
+    pub points: Vec<SnapshotPoint>,
+    /// Files that could not be read or parsed, with the reason
+    pub skipped: Vec<String>,
+    /// Snapshots not comparable with the newest one (analyzer version or config changed)
+    pub warnings: Vec<String>,
+}
+
+/// Lenient view of a `--json` export; every field is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SnapshotFile {
+    generated_at: Option<u64>,
+    timestamp: Option<u64>,
+    analyzer_version: Option<String>,
+    config_hash: Option<String>,
+    summary: SnapshotSummary,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SnapshotSummary {
+    health_grade: Option<String>,
+    health_score: Option<f64>,
+    total_couplings: Option<usize>,
+    critical_issues: Option<usize>,
+    high_issues: Option<usize>,
+    medium_issues: Option<usize>,
+}
+
+/// Load every `*.json` file in `dir` as a snapshot.
+///
+/// Unreadable or non-object files are listed in `skipped` rather than failing
+/// the whole timeline.
+pub fn load_snapshots(dir: &Path) -> std::io::Result<SnapshotTimeline> {
+    let mut timeline = SnapshotTimeline::default();
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<SnapshotFile>(&content).map_err(|e| e.to_string())
+            });
+        let snapshot = match parsed {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                timeline.skipped.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+
+        let modified = || {
+            fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|age| age.as_secs())
+                .unwrap_or(0)
+        };
+        let summary = snapshot.summary;
+        timeline.points.push(SnapshotPoint {
+            file,
+            timestamp: snapshot
+                .generated_at
+                .or(snapshot.timestamp)
+                .unwrap_or_else(modified),
+            analyzer_version: snapshot.analyzer_version,
+            config_hash: snapshot.config_hash,
+            health_grade: summary.health_grade,
+            health_score: summary.health_score,
+            total_couplings: summary.total_couplings,
+            critical_issues: summary.critical_issues,
+            high_issues: summary.high_issues,
+            medium_issues: summary.medium_issues,
+        });
+    }
+
+    timeline.points.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    timeline.skipped.sort();
+
+    if let Some(newest) = timeline.points.last() {
+        let current = (
+            newest.analyzer_version.as_deref(),
+            newest.config_hash.as_deref(),
+        );
+        timeline.warnings = timeline
+            .points
+            .iter()
+            .flat_map(|point| {
+                comparability_warnings(
+                    &point.file,
+                    (
+                        point.analyzer_version.as_deref(),
+                        point.config_hash.as_deref(),
+                    ),
+                    current,
+                )
+            })
+            .collect();
+    }
+    Ok(timeline)
+}
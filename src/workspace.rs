@@ -4,11 +4,17 @@
 //! including workspace members, dependencies, and module organization.
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-use cargo_metadata::{Metadata, MetadataCommand, PackageId, TargetKind};
+use cargo_metadata::{DependencyKind, Metadata, MetadataCommand, PackageId, TargetKind};
+use serde::Deserialize;
 use thiserror::Error;
 
+/// Edition assumed for crates declared in `[project]` config (no manifest to read).
+const DECLARED_CRATE_EDITION: &str = "2021";
+
 /// Errors that can occur during workspace analysis
 #[derive(Error, Debug)]
 pub enum WorkspaceError {
@@ -22,6 +28,50 @@ pub enum WorkspaceError {
     InvalidManifest(String),
 }
 
+/// Build-support roles a crate can play, read from `cargo metadata`.
+///
+/// Crates of these kinds can be left out of the health grade
+/// (`[analysis] exclude_crate_kinds`) while still being analyzed and listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrateKind {
+    /// Has a `proc-macro` library target
+    ProcMacro,
+    /// Listed in `[build-dependencies]` of a workspace member
+    BuildDependency,
+}
+
+impl CrateKind {
+    /// Kebab-case name used in config and CLI (`proc-macro`, `build-dependency`)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CrateKind::ProcMacro => "proc-macro",
+            CrateKind::BuildDependency => "build-dependency",
+        }
+    }
+}
+
+impl fmt::Display for CrateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CrateKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proc-macro" => Ok(CrateKind::ProcMacro),
+            "build-dependency" => Ok(CrateKind::BuildDependency),
+            other => Err(format!(
+                "unknown crate kind '{}' (expected proc-macro or build-dependency)",
+                other
+            )),
+        }
+    }
+}
+
 /// Information about a crate in the workspace
 #[derive(Debug, Clone)]
 pub struct CrateInfo {
@@ -43,6 +93,10 @@ pub struct CrateInfo {
     pub dev_dependencies: Vec<String>,
     /// Is this a workspace member?
     pub is_workspace_member: bool,
+    /// Rust edition declared in the manifest (`2015`, `2018`, `2021`, `2024`)
+    pub edition: String,
+    /// Build-support kinds of this crate (empty for ordinary crates)
+    pub kinds: Vec<CrateKind>,
 }
 
 /// Information about the entire workspace
@@ -86,6 +140,16 @@ impl WorkspaceInfo {
         // Collect workspace members
         let workspace_member_ids: HashSet<_> = metadata.workspace_members.iter().collect();
 
+        // Members other members pull in through `[build-dependencies]`
+        let build_dependencies: HashSet<&str> = metadata
+            .packages
+            .iter()
+            .filter(|package| workspace_member_ids.contains(&package.id))
+            .flat_map(|package| &package.dependencies)
+            .filter(|dep| dep.kind == DependencyKind::Build)
+            .map(|dep| dep.name.as_str())
+            .collect();
+
         // Process all packages
         for package in &metadata.packages {
             let is_workspace_member = workspace_member_ids.contains(&package.id);
@@ -124,7 +188,7 @@ impl WorkspaceInfo {
             let mut dev_deps = Vec::new();
 
             for dep in &package.dependencies {
-                if dep.kind == cargo_metadata::DependencyKind::Development {
+                if dep.kind == DependencyKind::Development {
                     dev_deps.push(dep.name.clone());
                 } else {
                     deps.push(dep.name.clone());
@@ -143,6 +207,18 @@ impl WorkspaceInfo {
                     .insert(package_name.clone());
             }
 
+            let mut kinds = Vec::new();
+            if package
+                .targets
+                .iter()
+                .any(|target| target.kind.contains(&TargetKind::ProcMacro))
+            {
+                kinds.push(CrateKind::ProcMacro);
+            }
+            if build_dependencies.contains(package_name.as_str()) {
+                kinds.push(CrateKind::BuildDependency);
+            }
+
             let crate_info = CrateInfo {
                 name: package_name.clone(),
                 id: package.id.clone(),
@@ -153,6 +229,8 @@ impl WorkspaceInfo {
                 dependencies: deps,
                 dev_dependencies: dev_deps,
                 is_workspace_member,
+                edition: package.edition.as_str().to_string(),
+                kinds,
             };
 
             crates.insert(package_name, crate_info);
@@ -167,6 +245,83 @@ impl WorkspaceInfo {
         })
     }
 
+    /// Build workspace info from manually declared crates (`[project]` config).
+    ///
+    /// Used for projects outside the cargo model, where `cargo metadata` fails.
+    /// Each `(name, source_root)` becomes a member crate with no dependencies.
+    pub fn from_source_roots(
+        root: &Path,
+        crates: impl IntoIterator<Item = (String, PathBuf)>,
+    ) -> Self {
+        let mut workspace = Self {
+            root: root.to_path_buf(),
+            crates: HashMap::new(),
+            members: Vec::new(),
+            dependency_graph: HashMap::new(),
+            reverse_deps: HashMap::new(),
+        };
+
+        for (name, source_root) in crates {
+            let crate_roots = ["lib.rs", "main.rs", "mod.rs"]
+                .iter()
+                .map(|file| source_root.join(file))
+                .filter(|path| path.exists())
+                .collect();
+            let crate_info = CrateInfo {
+                name: name.clone(),
+                id: PackageId {
+                    repr: format!("path+file://{}#{}", source_root.display(), name),
+                },
+                src_path: source_root.clone(),
+                source_roots: vec![source_root.clone()],
+                crate_roots,
+                // No manifest exists; anchoring one in the source root keeps nested
+                // cargo packages pruned from the walk.
+                manifest_path: source_root.join("Cargo.toml"),
+                dependencies: Vec::new(),
+                dev_dependencies: Vec::new(),
+                is_workspace_member: true,
+                edition: DECLARED_CRATE_EDITION.to_string(),
+                kinds: Vec::new(),
+            };
+            workspace.members.push(name.clone());
+            workspace.crates.insert(name, crate_info);
+        }
+
+        workspace
+    }
+
+    /// Fold another workspace's crates into this one, for analyzing several
+    /// unrelated roots as one project.
+    ///
+    /// Crates known to both keep this workspace's entry unless only `other` has
+    /// them as a member (a path dependency of one root that is analyzed as its own root).
+    pub fn merge(&mut self, other: WorkspaceInfo) {
+        for (name, crate_info) in other.crates {
+            match self.crates.get(&name) {
+                Some(existing)
+                    if existing.is_workspace_member || !crate_info.is_workspace_member => {}
+                _ => {
+                    self.crates.insert(name, crate_info);
+                }
+            }
+        }
+        for member in other.members {
+            if !self.members.contains(&member) {
+                self.members.push(member);
+            }
+        }
+        for (name, deps) in other.dependency_graph {
+            self.dependency_graph.entry(name).or_default().extend(deps);
+        }
+        for (name, dependents) in other.reverse_deps {
+            self.reverse_deps
+                .entry(name)
+                .or_default()
+                .extend(dependents);
+        }
+    }
+
     /// Get a crate by name
     pub fn get_crate(&self, name: &str) -> Option<&CrateInfo> {
         self.crates.get(name)
@@ -317,6 +472,53 @@ fn find_cargo_toml(start: &Path) -> Result<PathBuf, WorkspaceError> {
     Err(WorkspaceError::InvalidManifest(start.display().to_string()))
 }
 
+/// Explain a parse failure that is consistent with an edition mismatch.
+///
+/// The parser always accepts the latest syntax, so identifiers that later editions
+/// reserved fail in 2015 crates. Newer editions only get a hint when `source` uses
+/// a construct the bundled parser does not know yet (`gen` blocks and functions).
+pub fn edition_parse_hint(edition: &str, error: &str, source: &str) -> Option<&'static str> {
+    match edition {
+        "2015" if error.contains("expected identifier") => Some(
+            "edition 2015 allows `async`, `await`, `dyn` and `try` as identifiers, but the parser treats them as keywords",
+        ),
+        "2015" | "2018" | "2021" => None,
+        _ if uses_gen_syntax(source) => Some(
+            "edition 2024 `gen` blocks and functions are not supported by the bundled parser yet",
+        ),
+        _ => None,
+    }
+}
+
+/// Whether the tokens of `source` contain `gen {`, `gen move {` or `gen fn`.
+fn uses_gen_syntax(source: &str) -> bool {
+    use proc_macro2::{Delimiter, TokenStream, TokenTree};
+
+    fn walk(tokens: TokenStream) -> bool {
+        let mut previous_is_gen = false;
+        for token in tokens {
+            let starts_gen = previous_is_gen
+                && match &token {
+                    TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+                    TokenTree::Ident(ident) => ident == "move" || ident == "fn",
+                    _ => false,
+                };
+            if starts_gen {
+                return true;
+            }
+            previous_is_gen = matches!(&token, TokenTree::Ident(ident) if ident == "gen");
+            if let TokenTree::Group(group) = token
+                && walk(group.stream())
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    source.parse::<TokenStream>().is_ok_and(walk)
+}
+
 /// Resolve a module path to a crate name
 /// e.g., "crate::models::user" in package "my-app" -> "my-app"
 /// e.g., "serde::Serialize" -> "serde"
@@ -403,4 +605,43 @@ mod tests {
             Some("serde".to_string())
         );
     }
+
+    #[test]
+    fn test_crate_info_reads_edition_from_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"old-style\"\nversion = \"0.1.0\"\nedition = \"2018\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(tmp.path().join("src/lib.rs"), "pub struct A;\n").unwrap();
+
+        let workspace = WorkspaceInfo::from_path(tmp.path()).unwrap();
+        let crate_info = workspace.get_crate("old-style").unwrap();
+        assert_eq!(crate_info.edition, "2018");
+    }
+
+    #[test]
+    fn test_edition_parse_hint_flags_reserved_identifiers_in_2015() {
+        assert!(
+            edition_parse_hint("2015", "expected identifier, found keyword `async`", "").is_some()
+        );
+        assert!(edition_parse_hint("2021", "expected identifier", "").is_none());
+    }
+
+    #[test]
+    fn test_edition_parse_hint_needs_a_gen_construct_in_2024() {
+        let hint = |source| edition_parse_hint("2024", "expected an expression", source);
+        assert!(hint("fn f() { let it = gen { yield 1; }; }").is_some());
+        assert!(hint("fn f() { let it = async gen move { yield 1; }; }").is_some());
+        assert!(hint("gen fn f() -> i32 { yield 1; }").is_some());
+
+        // A plain syntax error, or `gen` only as an identifier, is not an edition problem
+        assert!(hint("fn f() { let x = 1 +; }").is_none());
+        assert!(hint("fn f() { let r#gen = gen(1); } // gen { }").is_none());
+        assert!(
+            edition_parse_hint("2021", "expected an expression", "fn f() { gen {} }").is_none()
+        );
+    }
 }
@@ -199,3 +199,92 @@ fn baseline_json_contains_top_level_diff_object() {
     assert!(diff["grade_change"]["baseline"].as_str().is_some());
     assert!(diff["grade_change"]["current"].as_str().is_some());
 }
+
+#[test]
+fn test_diff_subcommand_compares_two_revisions() {
+    let tmp = fixture_repo();
+    let root = tmp.path();
+    let config = root.join("coupling.toml");
+    write(&config, "[thresholds]\nmax_dependencies = 1\n");
+
+    let output = cargo_coupling()
+        .args([
+            "coupling",
+            "diff",
+            "--base",
+            "HEAD~1",
+            "--head",
+            "HEAD",
+            "--max-regression",
+            "0.5",
+            "--config",
+        ])
+        .arg(&config)
+        .arg(root.join("src"))
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "stdout:\n{stdout}");
+    assert!(stdout.contains("Coupling Revision Diff"));
+    assert!(
+        stdout.contains("+1 new High issue, grade dropped"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("High Efferent Coupling: hub"), "{stdout}");
+    assert!(stdout.contains("Status: PASSED"));
+}
+
+#[test]
+fn test_baseline_file_suppresses_existing_issues_but_fails_on_new_ones() {
+    let tmp = fixture_repo();
+    let root = tmp.path();
+    let src = root.join("src");
+    let baseline = root.join("coupling-baseline.json");
+
+    let written = cargo_coupling()
+        .args(["coupling", "--max-deps", "1"])
+        .arg(format!("--write-baseline={}", baseline.display()))
+        .arg(&src)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling");
+    assert!(written.status.success());
+    assert!(
+        String::from_utf8_lossy(&written.stdout)
+            .contains("Wrote 1 issue fingerprints and 0 circular dependencies")
+    );
+
+    let check = || {
+        cargo_coupling()
+            .args([
+                "coupling",
+                "--check",
+                "--fail-on",
+                "high",
+                "--max-deps",
+                "1",
+            ])
+            .arg("--baseline-file")
+            .arg(&baseline)
+            .arg(&src)
+            .current_dir(root)
+            .output()
+            .expect("run cargo-coupling")
+    };
+
+    let existing = check();
+    let stdout = String::from_utf8_lossy(&existing.stdout);
+    assert!(existing.status.success(), "{stdout}");
+    assert!(stdout.contains("Suppressed by baseline: 1"), "{stdout}");
+
+    write(
+        &src.join("relay.rs"),
+        "use crate::a::A;\nuse crate::b::B;\nuse crate::c::C;\n\npub struct Relay {\n    pub a: A,\n    pub b: B,\n    pub c: C,\n}\n",
+    );
+    let regressed = check();
+    let stdout = String::from_utf8_lossy(&regressed.stdout);
+    assert!(!regressed.status.success(), "{stdout}");
+    assert!(stdout.contains("Blocking Issues"), "{stdout}");
+}
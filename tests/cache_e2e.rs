@@ -0,0 +1,83 @@
+//! End-to-end tests for the per-file analysis cache (`.coupling-cache/`).
+//!
+//! These run the real CLI binary twice over a throwaway project and check that
+//! the second, cached run reports the same results and that edits are picked up.
+
+use std::path::Path;
+use std::process::Command;
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+}
+
+fn summary(root: &Path, extra: &[&str]) -> String {
+    let output = cargo_coupling()
+        .args(["coupling", "--summary", "--no-git", "--max-deps", "1"])
+        .args(extra)
+        .arg("src")
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling");
+    assert!(
+        output.status.success(),
+        "cargo-coupling failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+fn cache_entries(root: &Path) -> usize {
+    std::fs::read_dir(root.join(".coupling-cache"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_cached_runs_match_fresh_runs_and_pick_up_edits() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(
+        &root.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+    );
+    write(
+        &src.join("lib.rs"),
+        "pub mod a;\npub mod b;\npub mod hub;\n",
+    );
+    write(&src.join("a.rs"), "pub struct A;\n");
+    write(&src.join("b.rs"), "pub struct B;\n");
+    write(
+        &src.join("hub.rs"),
+        "use crate::a::A;\n\npub struct Hub {\n    pub a: A,\n}\n",
+    );
+
+    let uncached = summary(root, &["--no-cache"]);
+    assert_eq!(cache_entries(root), 0, "--no-cache must not write a cache");
+
+    let first = summary(root, &[]);
+    assert_eq!(cache_entries(root), 4);
+    let second = summary(root, &[]);
+    assert_eq!(first, uncached);
+    assert_eq!(second, uncached);
+
+    // The edited file's entry is replaced, and its new content is analyzed
+    write(
+        &src.join("hub.rs"),
+        "use crate::a::A;\nuse crate::b::B;\n\npub struct Hub {\n    pub a: A,\n    pub b: B,\n}\n",
+    );
+    let edited = summary(root, &[]);
+    assert_eq!(cache_entries(root), 4);
+    assert_eq!(edited, summary(root, &["--no-cache"]));
+    assert_ne!(edited, first);
+}
@@ -0,0 +1,82 @@
+//! End-to-end tests for `.coupling.toml` discovery and `--config <DIR>`.
+//!
+//! The CLI runs from inside a workspace member with a relative path, the way
+//! it is run from a nested crate, and must still pick up the root config.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_nested_crate_finds_the_workspace_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let member = root.join("crates").join("foo");
+    std::fs::create_dir_all(member.join("src")).unwrap();
+    write(
+        &root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/foo\"]\n",
+    );
+    write(
+        &member.join("Cargo.toml"),
+        "[package]\nname = \"foo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    write(&member.join("src").join("lib.rs"), "pub struct Item;\n");
+    write(
+        &root.join(".coupling.toml"),
+        "[thresholds]\nmax_dependents = 9\n",
+    );
+    let stderr = |output: &Output| String::from_utf8_lossy(&output.stderr).into_owned();
+
+    let nested = cargo_coupling(&member, &["src", "--no-git", "--summary", "--verbose"]);
+    assert!(nested.status.success(), "{}", stderr(&nested));
+    let expected = format!(
+        "Using config file {}",
+        root.join(".coupling.toml").display()
+    );
+    assert!(stderr(&nested).contains(&expected), "{}", stderr(&nested));
+
+    // Not reported at the default log level
+    let quiet = cargo_coupling(&member, &["src", "--no-git", "--summary"]);
+    assert!(!stderr(&quiet).contains("Using config file"));
+
+    // --config may name the directory holding the config
+    write(
+        &member.join("coupling.toml"),
+        "[thresholds]\nmax_dependents = 4\n",
+    );
+    let dir = cargo_coupling(
+        root,
+        &[
+            "crates/foo/src",
+            "--no-git",
+            "--summary",
+            "--log-level",
+            "debug",
+            "--config",
+            "crates/foo",
+        ],
+    );
+    assert!(dir.status.success(), "{}", stderr(&dir));
+    assert!(stderr(&dir).contains("Using config file crates/foo/coupling.toml"));
+
+    let missing = cargo_coupling(root, &["crates/foo/src", "--config", "nope"]);
+    assert_eq!(missing.status.code(), Some(3));
+    assert!(
+        stderr(&missing).contains("nope not found"),
+        "{}",
+        stderr(&missing)
+    );
+}
@@ -0,0 +1,77 @@
+//! End-to-end tests for the exit-code contract shared by every subcommand.
+//!
+//! CI scripts tell a failed gate (1) apart from an analysis that could not run
+//! (2) and from invalid configuration or arguments (3).
+
+use std::path::Path;
+use std::process::Command;
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn exit_code(root: &Path, args: &[&str]) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+        .status
+        .code()
+}
+
+#[test]
+fn test_gate_failures_analysis_errors_and_config_errors_exit_differently() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(&src.join("lib.rs"), "pub struct Order;\n");
+
+    assert_eq!(exit_code(root, &["--check", "src", "--no-git"]), Some(0));
+    assert_eq!(
+        exit_code(root, &["--check", "--min-grade=S", "src", "--no-git"]),
+        Some(1)
+    );
+    assert_eq!(exit_code(root, &["missing", "--no-git"]), Some(2));
+    assert_eq!(
+        exit_code(root, &["snapshot", "check", "missing.json", "src"]),
+        Some(2)
+    );
+
+    assert_eq!(exit_code(root, &["--no-such-flag"]), Some(3));
+    // --dimension-delta only reports, so it cannot stand in for the --check gate
+    assert_eq!(
+        exit_code(
+            root,
+            &["--baseline", "HEAD", "--dimension-delta", "--check", "src"]
+        ),
+        Some(3)
+    );
+    assert_eq!(
+        exit_code(root, &["--check", "--min-grade=Q", "src", "--no-git"]),
+        Some(3)
+    );
+    // Unknown names are bad input, not a failed analysis
+    assert_eq!(
+        exit_code(root, &["--impact", "nowhere", "src", "--no-git"]),
+        Some(3)
+    );
+    assert_eq!(
+        exit_code(root, &["--trace", "Nothing", "src", "--no-git"]),
+        Some(3)
+    );
+    assert_eq!(
+        exit_code(root, &["graph", "--focus", "nowhere", "src"]),
+        Some(3)
+    );
+    write(
+        &root.join(".coupling.toml"),
+        "[thresholds]\nmax_dependencies = \"many\"\n",
+    );
+    assert_eq!(exit_code(root, &["src", "--no-git"]), Some(3));
+    assert_eq!(exit_code(root, &["map", "src"]), Some(3));
+
+    assert_eq!(exit_code(root, &["--help"]), Some(0));
+}
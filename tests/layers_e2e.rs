@@ -0,0 +1,77 @@
+//! End-to-end test for `[[layers]]` architecture constraints in the `--check` gate.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_domain_depending_on_infrastructure_fails_the_gate() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(
+        &src.join("lib.rs"),
+        "pub mod domain;\npub mod infrastructure;\n",
+    );
+    write(&src.join("domain.rs"), "pub struct Order;\n");
+    write(
+        &src.join("infrastructure.rs"),
+        "use crate::domain::Order;\n\npub fn store(order: Order) {}\n",
+    );
+    write(
+        &root.join(".coupling.toml"),
+        "[[layers]]\nname = \"domain\"\nmodules = [\"domain\"]\n\n\
+         [[layers]]\nname = \"infrastructure\"\nmodules = [\"infrastructure\"]\n",
+    );
+
+    let check_args = ["--check", "--max-critical=0", "src", "--no-git"];
+    let allowed = cargo_coupling(root, &check_args);
+    assert_eq!(
+        allowed.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&allowed.stdout)
+    );
+
+    // Reverse the dependency without forming a cycle
+    write(
+        &src.join("infrastructure.rs"),
+        "pub struct Db;\n\npub fn connect() -> Db {\n    Db\n}\n",
+    );
+    write(
+        &src.join("domain.rs"),
+        "use crate::infrastructure::Db;\n\npub struct Order;\n\npub fn place(order: Order, db: Db) {}\n",
+    );
+    let violated = cargo_coupling(root, &check_args);
+    let stdout = String::from_utf8_lossy(&violated.stdout);
+    assert_eq!(violated.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("Critical issues: 1"), "{}", stdout);
+    assert!(stdout.contains("Circular dependencies: 0"), "{}", stdout);
+
+    let annotations = cargo_coupling(
+        root,
+        &[
+            "--check",
+            "--fail-on=critical",
+            "--format",
+            "github",
+            "src",
+            "--no-git",
+        ],
+    );
+    let annotations = String::from_utf8_lossy(&annotations.stdout);
+    assert!(annotations.contains("Layer Violation"), "{}", annotations);
+}
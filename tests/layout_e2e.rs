@@ -3,8 +3,10 @@
 use std::fs;
 use std::path::Path;
 
+use cargo_coupling::cli_output::{CheckConfig, run_check};
 use cargo_coupling::{
-    CompiledConfig, ManifestContext, ProjectMetrics, analyze_workspace_with_config, build_manifest,
+    CompiledConfig, CrateKind, HealthGrade, IssueThresholds, ManifestContext, ProjectMetrics,
+    analyze_crate_balance, analyze_project_balance, analyze_workspace_with_config, build_manifest,
 };
 
 fn write(path: &Path, content: &str) {
@@ -30,9 +32,12 @@ fn manifest_for(metrics: &ProjectMetrics) -> cargo_coupling::AnalysisManifest {
         git_used: true,
         tests_excluded: false,
         parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
         skipped_crates: metrics.skipped_crates.clone(),
         boundary_skipped_files: metrics.boundary_skipped_files,
         dead_config_patterns: metrics.dead_config_patterns.clone(),
+        edition_parse_failures: metrics.edition_parse_failures.clone(),
+        orphaned_modules: metrics.orphaned_modules.clone(),
     })
 }
 
@@ -486,9 +491,12 @@ path = "does-not-exist/main.rs"
         git_used: true,
         tests_excluded: false,
         parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
         skipped_crates: metrics.skipped_crates,
         boundary_skipped_files: metrics.boundary_skipped_files,
         dead_config_patterns: metrics.dead_config_patterns,
+        edition_parse_failures: metrics.edition_parse_failures,
+        orphaned_modules: metrics.orphaned_modules,
     });
 
     assert!(manifest.notes.iter().any(|note| {
@@ -502,3 +510,286 @@ path = "does-not-exist/main.rs"
         )
     }));
 }
+
+#[test]
+fn test_same_named_modules_in_different_members_are_both_reported() {
+    let tmp = tempfile::tempdir().expect("create tempdir");
+    let root = tmp.path();
+    write(
+        &root.join("Cargo.toml"),
+        r#"[workspace]
+members = ["client", "server"]
+resolver = "3"
+"#,
+    );
+    for member in ["client", "server"] {
+        create_dir(&root.join(member).join("src"));
+        write(
+            &root.join(member).join("Cargo.toml"),
+            &format!("[package]\nname = \"{member}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n"),
+        );
+        write(
+            &root.join(member).join("src/lib.rs"),
+            "pub mod config;\nuse crate::config::Config;\npub fn run(c: Config) -> Config { c }\n",
+        );
+        write(
+            &root.join(member).join("src/config.rs"),
+            "pub struct Config;\n",
+        );
+    }
+
+    let metrics = analyze(root);
+
+    assert_eq!(
+        module_names(&metrics),
+        vec![
+            "client::config",
+            "client::lib",
+            "server::config",
+            "server::lib"
+        ]
+    );
+    assert!(
+        metrics.modules["client::config"]
+            .path
+            .ends_with("client/src/config.rs")
+    );
+    assert!(
+        metrics
+            .couplings
+            .iter()
+            .any(|c| c.source == "server::lib" && c.target == "server::config")
+    );
+}
+
+#[test]
+fn test_proc_macro_member_is_listed_but_excluded_from_grade_by_default() {
+    let tmp = tempfile::tempdir().expect("create tempdir");
+    let root = tmp.path();
+    write(
+        &root.join("Cargo.toml"),
+        r#"[workspace]
+members = ["app", "macros"]
+resolver = "3"
+"#,
+    );
+    create_dir(&root.join("app/src"));
+    write(
+        &root.join("app/Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    );
+    write(
+        &root.join("app/src/lib.rs"),
+        "pub mod model;\nuse crate::model::Model;\npub fn make(m: Model) -> Model { m }\n",
+    );
+    write(&root.join("app/src/model.rs"), "pub struct Model;\n");
+    create_dir(&root.join("macros/src"));
+    write(
+        &root.join("macros/Cargo.toml"),
+        "[package]\nname = \"macros\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[lib]\nproc-macro = true\n",
+    );
+    write(
+        &root.join("macros/src/lib.rs"),
+        "mod state;\nuse crate::state::State;\npub fn bump(s: &mut State) { s.count += 1; s.total += s.count; }\n",
+    );
+    write(
+        &root.join("macros/src/state.rs"),
+        "pub struct State { pub count: u32, pub total: u32 }\n",
+    );
+
+    let metrics = analyze(root);
+
+    assert_eq!(
+        metrics.grade_excluded_crates,
+        vec![("macros".to_string(), CrateKind::ProcMacro)]
+    );
+    assert!(metrics.modules.contains_key("state"));
+    assert!(
+        metrics
+            .couplings
+            .iter()
+            .any(|c| c.source == "macros::lib" && c.target.ends_with("state"))
+    );
+
+    let report = analyze_project_balance(&metrics);
+    let mut config = CompiledConfig::empty();
+    config.exclude_crate_kinds.clear();
+    let graded = analyze_workspace_with_config(root, &config).expect("analyze fixture");
+    let graded_report = analyze_project_balance(&graded);
+
+    // Only app's coupling is scored by default; grading every crate adds the macro's two.
+    assert_eq!(report.balanced_count, 1);
+    assert_eq!(graded_report.balanced_count, 3);
+    assert!(graded.grade_excluded_crates.is_empty());
+}
+
+#[test]
+fn test_workspace_members_are_graded_separately() {
+    let tmp = tempfile::tempdir().expect("create tempdir");
+    let root = tmp.path();
+    create_dir(&root.join("clock/src"));
+    create_dir(&root.join("shop/src"));
+
+    write(
+        &root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"clock\", \"shop\"]\nresolver = \"3\"\n",
+    );
+    for name in ["clock", "shop"] {
+        write(
+            &root.join(format!("{name}/Cargo.toml")),
+            &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n"),
+        );
+    }
+    write(
+        &root.join("clock/src/lib.rs"),
+        "pub mod ports;\npub mod service;\n",
+    );
+    write(
+        &root.join("clock/src/ports.rs"),
+        "pub trait Clock { fn now(&self) -> u64; }\n",
+    );
+    write(
+        &root.join("clock/src/service.rs"),
+        "use crate::ports::Clock;\npub fn stamp<C: Clock>(clock: &C) -> u64 { clock.now() }\n",
+    );
+    write(
+        &root.join("shop/src/lib.rs"),
+        "pub mod orders;\npub mod billing;\n",
+    );
+    write(
+        &root.join("shop/src/orders.rs"),
+        r#"use crate::billing::Invoice;
+pub struct Order { pub total: u64, pub invoice: Invoice }
+pub fn settle(order: &mut Order) { order.invoice.paid = order.total; crate::billing::charge(order); }
+"#,
+    );
+    write(
+        &root.join("shop/src/billing.rs"),
+        r#"use crate::orders::Order;
+pub struct Invoice { pub paid: u64 }
+pub fn charge(order: &mut Order) { order.total = order.invoice.paid; }
+"#,
+    );
+
+    let metrics = analyze(root);
+    assert_eq!(metrics.crate_names(), ["clock", "shop"]);
+    assert_eq!(metrics.module_crates["orders"], "shop");
+
+    let shop = metrics.crate_metrics("shop");
+    assert!(shop.modules.contains_key("billing"));
+    assert!(!shop.modules.contains_key("ports"));
+    assert!(
+        shop.couplings
+            .iter()
+            .all(|coupling| coupling.source_crate.as_deref() == Some("shop"))
+    );
+
+    let reports = analyze_crate_balance(&metrics, &IssueThresholds::default());
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports["clock"].total_couplings, 4);
+    assert_eq!(reports["shop"].total_couplings, 7);
+
+    // Both members grade B on their own, so an A gate fails each of them.
+    let check = run_check(
+        &metrics,
+        &IssueThresholds::default(),
+        &CheckConfig {
+            min_grade: Some(HealthGrade::A),
+            max_critical: None,
+            max_circular: None,
+            fail_on: None,
+            baseline: None,
+            budgets: Default::default(),
+        },
+    );
+    for crate_name in ["clock", "shop"] {
+        assert!(
+            check
+                .failures
+                .iter()
+                .any(|failure| failure.starts_with(&format!("Crate {crate_name} grade B"))),
+            "{:?}",
+            check.failures
+        );
+    }
+}
+
+#[test]
+fn test_manifest_path_and_single_file_are_accepted_as_analysis_paths() {
+    let tmp = tempfile::tempdir().expect("create tempdir");
+    let root = tmp.path();
+    create_dir(&root.join("src"));
+    write(
+        &root.join("Cargo.toml"),
+        "[package]\nname = \"shop\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    );
+    write(
+        &root.join("src/lib.rs"),
+        "pub mod orders;\npub mod billing;\n",
+    );
+    write(
+        &root.join("src/orders.rs"),
+        "use crate::billing::Invoice;\npub fn settle(invoice: &Invoice) -> u64 { invoice.total }\n",
+    );
+    write(
+        &root.join("src/billing.rs"),
+        "pub struct Invoice { pub total: u64 }\n",
+    );
+
+    let from_dir = analyze(root);
+    let from_manifest = analyze(&root.join("Cargo.toml"));
+    assert_eq!(module_names(&from_manifest), module_names(&from_dir));
+    assert_eq!(from_manifest.total_files, 3);
+    assert_eq!(from_manifest.searched_paths, [root.join("src")]);
+
+    let single = analyze(&root.join("src/orders.rs"));
+    assert_eq!(single.total_files, 1);
+    assert_eq!(module_names(&single), ["orders"]);
+    assert_eq!(single.searched_paths, [root.join("src/orders.rs")]);
+
+    // An empty directory outside any package reports where it looked
+    let empty_dir = tempfile::tempdir().expect("create tempdir");
+    let empty = analyze(empty_dir.path());
+    assert_eq!(empty.total_files, 0);
+    assert_eq!(empty.searched_paths, [empty_dir.path()]);
+}
+
+#[test]
+fn test_generated_files_are_skipped_and_counted() {
+    let tmp = tempfile::tempdir().expect("create tempdir");
+    let root = tmp.path();
+    create_dir(&root.join("src"));
+    write(
+        &root.join("Cargo.toml"),
+        "[package]\nname = \"protos\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    );
+    write(&root.join("src/lib.rs"), "pub mod api;\npub mod proto;\n");
+    write(
+        &root.join("src/api.rs"),
+        "use crate::proto::Request;\n\npub fn handle(request: Request) -> u64 {\n    request.id\n}\n",
+    );
+    write(
+        &root.join("src/proto.rs"),
+        "// This file is @generated by prost-build.\npub struct Request {\n    pub id: u64,\n}\n",
+    );
+
+    let metrics = analyze(root);
+    assert_eq!(metrics.generated_files, 1);
+    assert!(!module_names(&metrics).contains(&"proto".to_string()));
+    assert!(
+        manifest_for(&metrics)
+            .notes
+            .iter()
+            .any(|note| { note.contains("1 generated file(s) were skipped") })
+    );
+
+    let config: cargo_coupling::CouplingConfig =
+        toml::from_str("[analysis]\ngenerated_markers = []\n").expect("parse config");
+    let all_files = analyze_workspace_with_config(
+        root,
+        &CompiledConfig::from_config(config).expect("compile config"),
+    )
+    .expect("analyze fixture");
+    assert_eq!(all_files.generated_files, 0);
+    assert!(module_names(&all_files).contains(&"proto".to_string()));
+}
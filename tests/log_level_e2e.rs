@@ -0,0 +1,84 @@
+//! End-to-end tests for `--quiet` and `--log-level`.
+//!
+//! The report on stdout must stay parseable; these check which status lines
+//! reach stderr at each level.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_quiet_and_log_level_control_stderr() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(&src.join("lib.rs"), "pub mod a;\n");
+    write(&src.join("a.rs"), "pub struct Item;\n");
+
+    let stderr = |output: &Output| String::from_utf8_lossy(&output.stderr).into_owned();
+
+    let default = cargo_coupling(root, &["src", "--no-git", "--format", "json"]);
+    assert!(default.status.success());
+    assert!(stderr(&default).contains("Analysis complete"));
+    // Without a manifest the workspace fallback note is informational
+    assert!(stderr(&default).contains("Could not load workspace metadata"));
+
+    let quiet = cargo_coupling(root, &["src", "--no-git", "--format", "json", "--quiet"]);
+    assert!(quiet.status.success());
+    assert_eq!(stderr(&quiet), "");
+    serde_json::from_slice::<serde_json::Value>(&quiet.stdout).expect("stdout is JSON");
+
+    // Warnings stay at --log-level warn, status lines go
+    let warn = cargo_coupling(
+        root,
+        &[
+            "src",
+            "--no-git",
+            "--summary",
+            "--json",
+            "--log-level",
+            "warn",
+        ],
+    );
+    assert!(warn.status.success());
+    assert!(
+        stderr(&warn).contains("Warning: using --json; ignoring --summary"),
+        "{}",
+        stderr(&warn)
+    );
+    assert!(!stderr(&warn).contains("Analysis complete"));
+
+    let debug = cargo_coupling(root, &["src", "--no-git", "--log-level", "debug"]);
+    assert!(stderr(&debug).contains("Thresholds: max_deps="));
+
+    // Subcommands take the flags too
+    let map = cargo_coupling(root, &["map", "src", "--quiet"]);
+    assert!(map.status.success());
+    assert_eq!(stderr(&map), "");
+
+    assert_eq!(
+        cargo_coupling(root, &["src", "--quiet", "--log-level", "debug"])
+            .status
+            .code(),
+        Some(3)
+    );
+    assert_eq!(
+        cargo_coupling(root, &["src", "--log-level", "loud"])
+            .status
+            .code(),
+        Some(3)
+    );
+}
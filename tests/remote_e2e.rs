@@ -0,0 +1,110 @@
+//! End-to-end tests for analyzing a remote git source.
+//!
+//! A throwaway repository is exposed through a `file://` URL so the
+//! clone-and-analyze flow runs hermetically, without network access.
+
+use std::path::Path;
+use std::process::Command;
+
+use cargo_coupling::{RemoteCheckout, RemoteError, analyze_workspace, is_remote_source};
+
+/// Run a git command in `dir`, panicking with context on failure.
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .expect("git should be runnable");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn file_url(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+#[test]
+fn test_clones_and_analyzes_a_file_url_then_cleans_up() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+
+    git(root, &["init", "-q"]);
+    write(
+        &root.join("Cargo.toml"),
+        "[package]\nname = \"remote-demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    write(&src.join("lib.rs"), "pub mod a;\npub mod b;\n");
+    write(&src.join("a.rs"), "pub struct A;\n");
+    write(
+        &src.join("b.rs"),
+        "use crate::a::A;\npub fn take(_a: A) {}\n",
+    );
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "init"]);
+    let first = git(root, &["rev-parse", "HEAD"]);
+
+    write(&src.join("lib.rs"), "pub mod a;\npub mod b;\npub mod c;\n");
+    write(&src.join("c.rs"), "pub struct C;\n");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "add c"]);
+
+    let url = file_url(root);
+    assert!(is_remote_source(&url));
+
+    let checkout = RemoteCheckout::fetch(&url, None, false).expect("clone head");
+    let clone_dir = checkout.path().to_path_buf();
+    let metrics = analyze_workspace(checkout.path()).expect("analyze clone");
+    assert!(metrics.modules.keys().any(|name| name.ends_with('c')));
+    drop(checkout);
+    assert!(!clone_dir.exists(), "clone should be removed on drop");
+
+    // Commit hashes are not valid `--branch` targets; the full-clone fallback pins them.
+    let pinned = RemoteCheckout::fetch(&url, Some(&first), false).expect("clone pinned rev");
+    assert!(pinned.path().join("src/b.rs").exists());
+    assert!(!pinned.path().join("src/c.rs").exists());
+}
+
+#[test]
+fn test_non_rust_repository_is_rejected() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    git(root, &["init", "-q"]);
+    write(&root.join("README.md"), "not a crate\n");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "docs"]);
+
+    let err = RemoteCheckout::fetch(&file_url(root), None, true).unwrap_err();
+    assert!(matches!(err, RemoteError::NotRustProject(_)), "{err}");
+    // A failed checkout is removed even when asked to keep it
+    let prefix = format!("cargo-coupling-remote-{}-", std::process::id());
+    let leftover = std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .any(|entry| entry.path().join("README.md").exists());
+    assert!(!leftover, "failed checkout should not be kept");
+
+    // Revisions that look like options never reach git
+    let err =
+        RemoteCheckout::fetch(&file_url(root), Some("--upload-pack=touch"), false).unwrap_err();
+    assert!(matches!(err, RemoteError::Checkout { .. }), "{err}");
+
+    let missing = tmp.path().join("missing");
+    let err = RemoteCheckout::fetch(&file_url(&missing), None, false).unwrap_err();
+    assert!(matches!(err, RemoteError::Clone { .. }), "{err}");
+}
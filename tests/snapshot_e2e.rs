@@ -0,0 +1,87 @@
+//! End-to-end tests for `coupling snapshot save` and `coupling snapshot check`.
+//!
+//! These save a snapshot of a throwaway project, then check the unchanged and a
+//! regressed tree against it through the real CLI binary.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_check_passes_on_an_unchanged_tree_and_fails_on_a_new_cycle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(&src.join("lib.rs"), "pub mod a;\npub mod b;\n");
+    write(&src.join("a.rs"), "pub struct A;\n");
+    write(
+        &src.join("b.rs"),
+        "use crate::a::A;\n\npub struct B {\n    pub a: A,\n}\n",
+    );
+
+    let saved = cargo_coupling(
+        root,
+        &["snapshot", "save", "snapshot.json", "src", "--no-git"],
+    );
+    assert!(
+        saved.status.success(),
+        "snapshot save failed: {}",
+        String::from_utf8_lossy(&saved.stderr)
+    );
+    let snapshot = std::fs::read_to_string(root.join("snapshot.json")).unwrap();
+    assert!(snapshot.contains("\"circular_dependencies\": 0"));
+
+    let check_args = ["snapshot", "check", "snapshot.json", "src", "--no-git"];
+    let unchanged = cargo_coupling(root, &check_args);
+    assert_eq!(unchanged.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&unchanged.stdout).contains("Status: PASSED"));
+
+    // a -> b closes a cycle with the existing b -> a
+    write(
+        &src.join("a.rs"),
+        "use crate::b::B;\n\npub struct A;\n\npub fn wrap(b: B) -> B {\n    b\n}\n",
+    );
+    let regressed = cargo_coupling(root, &check_args);
+    let stdout = String::from_utf8_lossy(&regressed.stdout);
+    assert_eq!(regressed.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("circular_dependencies 0 → 1 (+1, exceeds tolerance 0)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Status: FAILED"));
+
+    let tolerated = cargo_coupling(
+        root,
+        &[
+            "snapshot",
+            "check",
+            "snapshot.json",
+            "src",
+            "--no-git",
+            "--json",
+            "--cycle-tolerance",
+            "1",
+            "--issue-tolerance",
+            "10",
+            "--score-tolerance",
+            "1",
+        ],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&tolerated.stdout).unwrap();
+    assert_eq!(tolerated.status.code(), Some(0), "{}", json);
+    assert_eq!(json["regressed"], false);
+}
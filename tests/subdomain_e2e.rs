@@ -75,9 +75,12 @@ edition = "2024"
         git_used: true,
         tests_excluded: config.exclude_tests,
         parse_failures: metrics.parse_failures,
+        generated_files: metrics.generated_files,
         skipped_crates: metrics.skipped_crates,
         boundary_skipped_files: metrics.boundary_skipped_files,
         dead_config_patterns: metrics.dead_config_patterns,
+        edition_parse_failures: metrics.edition_parse_failures,
+        orphaned_modules: metrics.orphaned_modules,
     })
 }
 
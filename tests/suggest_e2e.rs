@@ -0,0 +1,61 @@
+//! End-to-end tests for `coupling suggest`.
+//!
+//! These analyze a throwaway project through the real CLI and check that the
+//! generated code comes out as Markdown Rust blocks or as JSON.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_suggest_prints_getters_and_newtypes_as_rust_blocks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    write(&src.join("lib.rs"), "pub mod store;\n");
+    write(
+        &src.join("store.rs"),
+        "pub struct Store {\n    pub name: String,\n}\n\
+         pub fn open(path: &str, retries: u32, verbose: bool) -> Store {\n    \
+             Store { name: path.to_string() }\n}\n",
+    );
+
+    let text = cargo_coupling(root, &["suggest", "src", "--quiet"]);
+    assert!(
+        text.status.success(),
+        "{}",
+        String::from_utf8_lossy(&text.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(
+        stdout.starts_with("# Refactoring suggestions (2)\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("```rust\nimpl Store {\n    pub fn name(&self) -> &str {"));
+    assert!(stdout.contains("pub struct Retries(u32);"), "{stdout}");
+    assert!(stdout.contains("// fn open(path: Path, retries: Retries, verbose: Verbose) -> Store"));
+
+    let json = cargo_coupling(root, &["suggest", "src", "--json", "--quiet"]);
+    let parsed: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    let kinds: Vec<&str> = parsed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["getters", "newtype"]);
+    assert_eq!(parsed[1]["issue_type"], "primitive-obsession");
+}
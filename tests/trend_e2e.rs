@@ -0,0 +1,93 @@
+//! End-to-end tests for `--record` and `coupling trend`.
+//!
+//! These record several runs of a throwaway project into a history file, with
+//! the project growing in between, then render the file through the real CLI.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).expect("write file");
+}
+
+fn cargo_coupling(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-coupling"))
+        .arg("coupling")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .expect("run cargo-coupling")
+}
+
+#[test]
+fn test_recorded_runs_are_capped_and_shown_as_a_trend() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+
+    let mut modules = Vec::new();
+    for name in ["a", "b", "c"] {
+        modules.push(format!("pub mod {name};\n"));
+        write(&src.join("lib.rs"), &modules.concat());
+        write(&src.join(format!("{name}.rs")), "pub struct Item;\n");
+        let recorded = cargo_coupling(
+            root,
+            &[
+                "src",
+                "--no-git",
+                "--summary",
+                "--record",
+                "trend.json",
+                "--max-history",
+                "2",
+            ],
+        );
+        assert!(
+            recorded.status.success(),
+            "{}",
+            String::from_utf8_lossy(&recorded.stderr)
+        );
+    }
+
+    let trend = cargo_coupling(root, &["trend", "trend.json"]);
+    assert!(trend.status.success());
+    let stdout = String::from_utf8_lossy(&trend.stdout);
+    assert!(
+        stdout.contains("Coupling Trend (2 recorded run(s))"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("avg score"), "{stdout}");
+    // The oldest run (two modules) was dropped by --max-history
+    assert!(stdout.contains("modules    ▁█  3 -> 4"), "{stdout}");
+
+    let json = cargo_coupling(root, &["trend", "trend.json", "--json"]);
+    let parsed: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    let entries = parsed["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0]["recorded_at"].as_u64().unwrap() > 0);
+    assert!(entries[0]["health_grade"].is_string());
+
+    assert_eq!(
+        cargo_coupling(root, &["trend", "missing.json"])
+            .status
+            .code(),
+        Some(2)
+    );
+    assert_eq!(
+        cargo_coupling(
+            root,
+            &[
+                "src",
+                "--no-git",
+                "--record",
+                "t.json",
+                "--max-history",
+                "0"
+            ]
+        )
+        .status
+        .code(),
+        Some(3)
+    );
+}
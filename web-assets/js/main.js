@@ -50,6 +50,7 @@ import { graphQueue, runLayoutAsync, debounce } from './graph-queue.js';
 import { getInitialSelection, updateUrl, initUrlRouter } from './url-router.js';
 import { initTimeline } from './timeline.js';
 import { setupReportView, showReportView, hideReportView } from './report-view.js';
+import { loadSession, applySessionState, startSessionAutoSave, requestReanalysis } from './session.js';
 
 // =====================================================
 // State Management
@@ -95,6 +96,8 @@ async function init() {
 
         // Handle initial URL selection after everything is ready
         handleInitialUrlSelection();
+        await restoreSession();
+        startSessionAutoSave();
 
     } catch (error) {
         console.error('Failed to initialize:', error);
@@ -159,6 +162,44 @@ function handleInitialUrlSelection() {
     }
 }
 
+/**
+ * Restore saved filters; the saved selection only applies when the URL has none
+ */
+async function restoreSession() {
+    const session = await loadSession();
+    const moduleId = applySessionState(session);
+    if (!moduleId || getInitialSelection().module) return;
+
+    setTimeout(() => {
+        const node = state.cy?.getElementById(moduleId);
+        if (node?.length) selectNode(node);
+    }, 500);
+}
+
+/**
+ * Re-run the server-side analysis and swap in the refreshed graph
+ */
+function setupReanalyzeButton() {
+    const button = document.getElementById('reanalyze');
+    if (!button) return;
+
+    button.addEventListener('click', async () => {
+        button.disabled = true;
+        setAppLoading(true);
+        try {
+            const data = await requestReanalysis();
+            state.graphCache.set('current', data);
+            setActiveRevision(null);
+            await replaceGraphData(data);
+        } catch (error) {
+            console.error('Re-analysis failed:', error);
+        } finally {
+            setAppLoading(false);
+            button.disabled = false;
+        }
+    });
+}
+
 /**
  * Handle URL navigation (back/forward button)
  */
@@ -285,6 +326,7 @@ function initUI(data) {
     setupLegendToggle();
     setupCenterModeToggle();
     setupItemToggle();
+    setupReanalyzeButton();
     clearDetails();
 
     // Set up callback for feature module
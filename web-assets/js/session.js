@@ -0,0 +1,130 @@
+// =====================================================
+// Session Persistence & Re-analysis
+// =====================================================
+
+import { CONFIG, state } from './state.js';
+import { applyFilters } from './ui-controls.js';
+
+const AUTO_SAVE_INTERVAL_MS = 30000;
+const FILTER_GROUPS = ['strength-filters', 'distance-filters', 'volatility-filters'];
+const FILTER_TOGGLES = ['show-issues-only', 'show-cycles-only', 'show-hidden-coupling', 'hide-external'];
+
+let lastSavedSession = null;
+
+/**
+ * Capture the current filter/selection state
+ * @returns {Object} Serializable session snapshot
+ */
+export function collectSessionState() {
+    const filters = {};
+    FILTER_GROUPS.forEach(groupId => {
+        filters[groupId] = Array.from(document.querySelectorAll(`#${groupId} input:checked`))
+            .map(cb => cb.value);
+    });
+
+    const toggles = {};
+    FILTER_TOGGLES.forEach(id => {
+        const el = document.getElementById(id);
+        if (el) toggles[id] = el.checked;
+    });
+
+    return {
+        filters,
+        toggles,
+        balanceMin: document.getElementById('balance-min')?.value ?? '0',
+        balanceMax: document.getElementById('balance-max')?.value ?? '100',
+        selectedNode: state.selectedNode?.id?.() ?? null,
+        layout: state.currentLayout,
+        showItems: state.showItems
+    };
+}
+
+/**
+ * Restore filters from a saved session and return the module to re-select
+ * @param {Object} session - Snapshot produced by collectSessionState()
+ * @returns {string|null} Selected module ID from the session
+ */
+export function applySessionState(session) {
+    if (!session) return null;
+
+    FILTER_GROUPS.forEach(groupId => {
+        const checked = session.filters?.[groupId];
+        if (!Array.isArray(checked)) return;
+        document.querySelectorAll(`#${groupId} input`).forEach(cb => {
+            cb.checked = checked.includes(cb.value);
+        });
+    });
+
+    Object.entries(session.toggles || {}).forEach(([id, checked]) => {
+        const el = document.getElementById(id);
+        if (el) el.checked = Boolean(checked);
+    });
+
+    const balanceMin = document.getElementById('balance-min');
+    const balanceMax = document.getElementById('balance-max');
+    if (balanceMin && session.balanceMin !== undefined) balanceMin.value = session.balanceMin;
+    if (balanceMax && session.balanceMax !== undefined) balanceMax.value = session.balanceMax;
+
+    applyFilters();
+    lastSavedSession = JSON.stringify(session);
+    return session.selectedNode || null;
+}
+
+/**
+ * Load the saved session from the server
+ * @returns {Promise<Object|null>} Saved session or null if none exists
+ */
+export async function loadSession() {
+    try {
+        const response = await fetch(`${CONFIG.apiEndpoint}${CONFIG.sessionPath}`);
+        if (!response.ok) return null;
+        return await response.json();
+    } catch (e) {
+        console.log('No saved session');
+        return null;
+    }
+}
+
+/**
+ * Persist the current session if it changed since the last save
+ */
+export async function saveSession() {
+    const snapshot = JSON.stringify(collectSessionState());
+    if (snapshot === lastSavedSession) return;
+
+    try {
+        const response = await fetch(`${CONFIG.apiEndpoint}${CONFIG.sessionPath}`, {
+            method: 'PUT',
+            headers: { 'Content-Type': 'application/json' },
+            body: snapshot
+        });
+        if (response.ok) {
+            lastSavedSession = snapshot;
+        }
+    } catch (e) {
+        console.warn('Failed to save session:', e);
+    }
+}
+
+/**
+ * Periodically auto-save the session, plus once when the page is hidden
+ */
+export function startSessionAutoSave() {
+    setInterval(saveSession, AUTO_SAVE_INTERVAL_MS);
+    document.addEventListener('visibilitychange', () => {
+        if (document.visibilityState === 'hidden') saveSession();
+    });
+}
+
+/**
+ * Ask the server to re-run the analysis
+ * @returns {Promise<Object>} Fresh graph data
+ */
+export async function requestReanalysis() {
+    const url = `${CONFIG.apiEndpoint}${CONFIG.reanalyzePath}`;
+    const response = await fetch(url, { method: 'POST' });
+    if (!response.ok) {
+        throw new Error(`HTTP ${response.status} from ${url}`);
+    }
+    return response.json();
+}
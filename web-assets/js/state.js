@@ -7,7 +7,10 @@ export const CONFIG = {
     graphPath: '/api/graph',
     reportPath: '/api/report',
     historyPath: '/api/history',
-    configPath: '/api/config'
+    snapshotsPath: '/api/snapshots',
+    configPath: '/api/config',
+    sessionPath: '/api/session',
+    reanalyzePath: '/api/reanalyze'
 };
 
 // Global state (mutable)
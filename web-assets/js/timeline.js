@@ -28,6 +28,96 @@ export async function initTimeline(options = {}) {
         console.error('Failed to load history:', error);
         chart.innerHTML = '<p class="placeholder">History unavailable</p>';
     }
+
+    initSnapshotTrend();
+}
+
+// Trend of exported JSON snapshots (`--web --snapshots <dir>`); hidden when none are served.
+async function initSnapshotTrend() {
+    const container = document.getElementById('snapshot-trend');
+    const chart = document.getElementById('snapshot-trend-chart');
+    if (!container || !chart) return;
+
+    try {
+        const response = await fetch(CONFIG.apiEndpoint + CONFIG.snapshotsPath);
+        if (!response.ok) return;
+        const timeline = await response.json();
+        const points = (timeline?.points || []).filter((point) => point.health_score != null);
+        if (points.length === 0) return;
+
+        chart.textContent = '';
+        chart.appendChild(buildSnapshotChart(points));
+        container.hidden = false;
+
+        const latest = points[points.length - 1];
+        let summary = `${points.length} snapshots, latest ${formatTimestamp(latest.timestamp)}: ${latest.health_grade ?? '-'} (${formatScore(latest.health_score)})`;
+        const warnings = timeline?.warnings || [];
+        if (warnings.length > 0) {
+            // Scores from another analyzer version or config are not comparable.
+            summary += ` · ${warnings.length} not comparable (analyzer or config changed)`;
+            chart.title = warnings.join('\n');
+        }
+        setText('snapshot-trend-latest', summary);
+    } catch (error) {
+        console.error('Failed to load snapshots:', error);
+    }
+}
+
+function buildSnapshotChart(points) {
+    const svg = document.createElementNS(SVG_NS, 'svg');
+    svg.setAttribute('viewBox', `0 0 ${CHART_WIDTH} ${CHART_HEIGHT}`);
+    svg.setAttribute('role', 'img');
+    svg.setAttribute('aria-label', 'Health score of exported snapshots');
+    svg.classList.add('timeline-svg');
+
+    appendGrid(svg);
+
+    // Issue counts as bars behind the score line, scaled to the busiest snapshot
+    const issueCount = (point) =>
+        (point.critical_issues ?? 0) + (point.high_issues ?? 0) + (point.medium_issues ?? 0);
+    const maxIssues = Math.max(1, ...points.map(issueCount));
+    points.forEach((point, index) => {
+        const height = (issueCount(point) / maxIssues) * (CHART_HEIGHT - CHART_PADDING * 2);
+        const bar = document.createElementNS(SVG_NS, 'rect');
+        bar.setAttribute('class', 'snapshot-issue-bar');
+        bar.setAttribute('x', String(xForIndex(index, points.length) - 3));
+        bar.setAttribute('y', String(CHART_HEIGHT - CHART_PADDING - height));
+        bar.setAttribute('width', '6');
+        bar.setAttribute('height', String(height));
+        svg.appendChild(bar);
+    });
+
+    const linePath = document.createElementNS(SVG_NS, 'path');
+    linePath.setAttribute('class', 'timeline-line');
+    linePath.setAttribute(
+        'd',
+        points
+            .map((point, index) => {
+                const command = index === 0 ? 'M' : 'L';
+                return `${command} ${xForIndex(index, points.length)} ${yForScore(point.health_score)}`;
+            })
+            .join(' ')
+    );
+    svg.appendChild(linePath);
+
+    points.forEach((point, index) => {
+        const circle = document.createElementNS(SVG_NS, 'circle');
+        circle.setAttribute('class', `timeline-point grade-${point.health_grade ?? ''}`);
+        circle.setAttribute('cx', String(xForIndex(index, points.length)));
+        circle.setAttribute('cy', String(yForScore(point.health_score)));
+        circle.setAttribute('r', '4');
+
+        const title = document.createElementNS(SVG_NS, 'title');
+        title.textContent = `${formatTimestamp(point.timestamp)} ${point.file}: ${formatScore(point.health_score)} (${point.health_grade ?? '-'}), ${issueCount(point)} issues`;
+        circle.appendChild(title);
+        svg.appendChild(circle);
+    });
+
+    return svg;
+}
+
+function formatTimestamp(seconds) {
+    return new Date(Number(seconds) * 1000).toISOString().slice(0, 10);
 }
 
 async function fetchHistoryData() {